@@ -0,0 +1,378 @@
+//! Support for the [arbitrary](https://docs.rs/arbitrary) crate, enabled by the `arbitrary` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) implements [Arbitrary] for `View<Vec<u8>>` of each layout.
+//! Fields are filled with arbitrary values of their type, so the generated views are structurally valid,
+//! e.g. `bool as u8` fields are always `0` or `1` and `NonZeroU32` fields are never zero.
+//! Fields using a custom type via [LayoutAs](crate::LayoutAs) are filled with an arbitrary value of that type
+//! if it implements [Arbitrary], and with arbitrary bytes otherwise. Bit fields are filled with arbitrary bits.
+//! Open ended fields get an arbitrary length, and [LengthPrefixed](crate::LengthPrefixed) fields an arbitrary length that
+//! leaves room for the fields after them.
+
+use core::marker::PhantomData;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::fields::primitive::NestedViewInfo;
+use crate::layout_info::FieldKindInfo;
+use crate::{
+    BitField, Endianness, Field, FieldCopyAccess, FieldKind, LayoutInfo, PrimitiveField,
+    RelativeField,
+};
+
+/// Internal type. Don't use this in user code.
+/// [binary_layout!](crate::binary_layout!) calls `(&&&ArbitraryField::<F>::default()).fill_arbitrary(...)` for each field,
+/// which uses the first of [ArbitraryNestedField] or [ArbitraryBitsField], [ArbitraryValueField] and [ArbitraryBytesField] that is implemented for the field.
+pub struct ArbitraryField<F>(PhantomData<F>);
+
+impl<F> Default for ArbitraryField<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Fills nested layouts field by field.
+pub trait ArbitraryNestedField<'a> {
+    /// Fill the field in the data with arbitrary values
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()>;
+}
+
+impl<'a, N: NestedViewInfo + ArbitraryNested, E: Endianness, const OFFSET_: usize>
+    ArbitraryNestedField<'a> for &&ArbitraryField<PrimitiveField<N, E, OFFSET_>>
+{
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        _layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()> {
+        N::fill_arbitrary(u, field_data::<PrimitiveField<N, E, OFFSET_>>(data)?)
+    }
+}
+
+impl<'a, L, N: NestedViewInfo + ArbitraryNested, E: Endianness, const OFFSET_: usize>
+    ArbitraryNestedField<'a> for &&ArbitraryField<RelativeField<L, PrimitiveField<N, E, OFFSET_>>>
+where
+    RelativeField<L, PrimitiveField<N, E, OFFSET_>>: Field + FieldKindInfo,
+{
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        _layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()> {
+        N::fill_arbitrary(
+            u,
+            field_data::<RelativeField<L, PrimitiveField<N, E, OFFSET_>>>(data)?,
+        )
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Fills bit fields with arbitrary bits. Their values can't be arbitrary values of their type, since those might not fit into the field.
+pub trait ArbitraryBitsField<'a> {
+    /// Fill the bytes of the field in the data with arbitrary bits
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()>;
+}
+
+impl<'a, T, E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+    ArbitraryBitsField<'a> for &&ArbitraryField<BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>
+where
+    BitField<T, E, OFFSET_, BIT_OFFSET, BITS>: Field + FieldKindInfo,
+{
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        _layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()> {
+        u.fill_buffer(field_data::<BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>(
+            data,
+        )?)
+    }
+}
+
+impl<'a, L, T, E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+    ArbitraryBitsField<'a>
+    for &&ArbitraryField<RelativeField<L, BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>>
+where
+    RelativeField<L, BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>: Field + FieldKindInfo,
+{
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        _layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()> {
+        u.fill_buffer(field_data::<
+            RelativeField<L, BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>,
+        >(data)?)
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Fills fields whose type implements [Arbitrary] with an arbitrary value of that type.
+pub trait ArbitraryValueField<'a> {
+    /// Fill the field in the data with an arbitrary value
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()>;
+}
+
+impl<'a, F: FieldCopyAccess + FieldKindInfo> ArbitraryValueField<'a> for &ArbitraryField<F>
+where
+    F::HighLevelType: Arbitrary<'a>,
+{
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        _layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()> {
+        // Varint fields before the field may have left too little room for it
+        field_data::<F>(data)?;
+        let value = F::HighLevelType::arbitrary(u)?;
+        F::try_write(data, value).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Fills all other fields with arbitrary bytes.
+pub trait ArbitraryBytesField<'a> {
+    /// Fill the field in the data with arbitrary bytes
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()>;
+}
+
+impl<'a, F: Field + FieldKindInfo> ArbitraryBytesField<'a> for ArbitraryField<F> {
+    fn fill_arbitrary(
+        &self,
+        u: &mut Unstructured<'a>,
+        layout: &LayoutInfo,
+        data: &mut [u8],
+    ) -> Result<()> {
+        if let FieldKind::LengthPrefixed { prefix_size } = F::KIND {
+            // The storage needs to keep room for the minimal size of the fields after this one
+            let layout_end = layout.min_size() + layout.shift_in(F::OFFSET, data).unwrap_or(0);
+            let spare = data.len().saturating_sub(layout_end);
+            let field_data = field_data::<F>(data)?;
+            return fill_length_prefixed(u, layout, prefix_size, spare, field_data);
+        }
+        u.fill_buffer(field_data::<F>(data)?)
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the `NestedView` types generated by [binary_layout!](crate::binary_layout!).
+pub trait ArbitraryNested {
+    /// Fill all fields of the layout in the data with arbitrary values
+    fn fill_arbitrary(u: &mut Unstructured<'_>, data: &mut [u8]) -> Result<()>;
+}
+
+/// Internal function. Don't use this in user code.
+/// Creates zeroed storage for an arbitrary view of the layout, with an arbitrary length if the layout is open ended.
+pub fn arbitrary_storage(layout: &LayoutInfo, u: &mut Unstructured<'_>) -> Result<Vec<u8>> {
+    let size = match layout.size {
+        Some(size) => size,
+        None => layout.min_size() + u.arbitrary_len::<u8>()?,
+    };
+    Ok(vec![0; size])
+}
+
+/// The bytes of the field in the data, or an error if the varint fields before it left too little room for it
+fn field_data<F: Field + FieldKindInfo>(data: &mut [u8]) -> Result<&mut [u8]> {
+    let offset = F::offset_in(data).ok_or(arbitrary::Error::IncorrectFormat)?;
+    let range = match F::SIZE {
+        Some(size) => offset..offset + size,
+        None => offset..data.len(),
+    };
+    data.get_mut(range).ok_or(arbitrary::Error::IncorrectFormat)
+}
+
+/// Fills a LengthPrefixed field with arbitrary data of at most `max_len` bytes and stores its length in the prefix
+fn fill_length_prefixed(
+    u: &mut Unstructured<'_>,
+    layout: &LayoutInfo,
+    prefix_size: usize,
+    max_len: usize,
+    field_data: &mut [u8],
+) -> Result<()> {
+    let max_len = match prefix_size {
+        size if size >= 8 => max_len,
+        size => max_len.min(usize::try_from((1u64 << (8 * size)) - 1).unwrap_or(usize::MAX)),
+    };
+    let len = u.int_in_range(0..=max_len)?;
+    let (prefix, data) = field_data.split_at_mut(prefix_size);
+    let len_bytes = (len as u64).to_le_bytes();
+    prefix.copy_from_slice(&len_bytes[..prefix_size]);
+    if layout.endianness.is_big_endian() {
+        prefix.reverse();
+    }
+    u.fill_buffer(&mut data[..len])
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+    use crate::prelude::*;
+    use crate::LayoutAs;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Even(u16);
+    impl LayoutAs<u16> for Even {
+        type ReadError = ();
+        type WriteError = ();
+
+        fn try_read(v: u16) -> Result<Self, ()> {
+            if v % 2 == 0 {
+                Ok(Even(v))
+            } else {
+                Err(())
+            }
+        }
+
+        fn try_write(v: Self) -> Result<u16, ()> {
+            Ok(v.0)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Odd(u16);
+    impl LayoutAs<u16> for Odd {
+        type ReadError = ();
+        type WriteError = ();
+
+        fn try_read(v: u16) -> Result<Self, ()> {
+            Ok(Odd(v))
+        }
+
+        fn try_write(v: Self) -> Result<u16, ()> {
+            Ok(v.0)
+        }
+    }
+    impl<'a> Arbitrary<'a> for Odd {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(Odd(u16::arbitrary(u)? | 1))
+        }
+    }
+
+    binary_layout!(inner, LittleEndian, {
+        flag: bool as u8,
+        tail: [u8],
+    });
+    binary_layout!(packet, BigEndian, {
+        flag: bool as u8,
+        character: char as u32,
+        non_zero: core::num::NonZeroU32,
+        odd: Odd as u16,
+        even: Even as u16,
+        float: f64,
+        unit: (),
+        mac: [u8; 6],
+        samples: [u16; 3],
+        version: u8 bits(4),
+        more: bool bits(1),
+        length: u16 bits(11),
+        nested: inner::NestedView,
+    });
+    binary_layout!(sized, BigEndian, {
+        value: u64,
+    });
+    binary_layout!(message, LittleEndian, {
+        name: crate::LengthPrefixed<u16>,
+        flags: u8 bits(4),
+        level: u8 bits(4),
+        delta: crate::ZigZag<i16>,
+        nested: inner::NestedView,
+    });
+
+    fn random_data(seed: u64) -> Vec<u8> {
+        let mut data = vec![0; 1024];
+        StdRng::seed_from_u64(seed).fill_bytes(&mut data);
+        data
+    }
+
+    #[test]
+    fn fields_are_valid() {
+        for seed in 0..100 {
+            let data = random_data(seed);
+            let mut u = Unstructured::new(&data);
+            let view = packet::View::<Vec<u8>>::arbitrary(&mut u).unwrap();
+            view.flag().try_read().unwrap();
+            view.character().try_read().unwrap();
+            view.non_zero().try_read().unwrap();
+            assert_eq!(1, view.odd().try_read().unwrap().0 % 2);
+            view.nested().flag().try_read().unwrap();
+        }
+    }
+
+    #[test]
+    fn open_ended_layouts_have_arbitrary_length() {
+        let lengths: Vec<usize> = (0..20)
+            .map(|seed| {
+                let data = random_data(seed);
+                let mut u = Unstructured::new(&data);
+                packet::View::<Vec<u8>>::arbitrary(&mut u)
+                    .unwrap()
+                    .into_storage()
+                    .len()
+            })
+            .collect();
+        let min_size = packet::nested::OFFSET + inner::tail::OFFSET;
+        assert!(lengths.iter().all(|length| *length >= min_size));
+        assert!(lengths.iter().any(|length| *length != lengths[0]));
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        let mut generated = 0;
+        for seed in 0..100 {
+            let data = random_data(seed);
+            let mut u = Unstructured::new(&data);
+            // Large varints may not leave enough room for the fields after them
+            if let Ok(view) = message::View::<Vec<u8>>::arbitrary(&mut u) {
+                view.name().try_data().unwrap();
+                view.delta().try_read().unwrap();
+                view.nested().flag().try_read().unwrap();
+                generated += 1;
+            }
+        }
+        assert!(generated > 50);
+    }
+
+    #[test]
+    fn sized_layouts_have_their_size() {
+        let data = random_data(0);
+        let mut u = Unstructured::new(&data);
+        let view = sized::View::<Vec<u8>>::arbitrary(&mut u).unwrap();
+        assert_eq!(sized::SIZE, Some(view.into_storage().len()));
+    }
+
+    #[test]
+    fn values_depend_on_input() {
+        let first = random_data(0);
+        let second = random_data(1);
+        let first = sized::View::<Vec<u8>>::arbitrary(&mut Unstructured::new(&first)).unwrap();
+        let second = sized::View::<Vec<u8>>::arbitrary(&mut Unstructured::new(&second)).unwrap();
+        assert_ne!(first.value().read(), second.value().read());
+    }
+}
@@ -0,0 +1,444 @@
+use core::fmt::{Debug, Write};
+
+use crate::layout_info::ViewInfo;
+use crate::{FieldCopyAccess, FieldView};
+
+/// Assert that two views of the same layout have equal field values, or that a view has the given field values.
+/// On failure, the panic message lists the fields that don't match together with their values,
+/// which is much easier to read than a comparison of the underlying byte arrays.
+///
+/// - `assert_layout_eq!(left, right)` compares all fields of two views, including the fields of nested layouts,
+///   see [LayoutInfo::diff](crate::LayoutInfo::diff). The views can be based on different kinds of storage.
+/// - `assert_layout_eq!(view, { field1: expected1, field2: expected2 })` compares the given fields of a view
+///   to the expected values. Fields that aren't listed aren't compared. This works for all fields except nested layouts,
+///   byte array fields are compared to slices.
+///
+/// This macro is only available with the `std` feature.
+///
+/// # Example
+/// ```
+/// use binary_layout::{assert_layout_eq, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   seq_no: u16,
+///   flag: bool as u8,
+///   payload: [u8],
+/// });
+///
+/// let view = my_layout::View::new(vec![0, 5, 1, 10, 20]);
+/// assert_layout_eq!(view, my_layout::View::new(&[0, 5, 1, 10, 20]));
+/// assert_layout_eq!(view, {
+///   seq_no: 5,
+///   flag: true,
+///   payload: &[10, 20],
+/// });
+/// ```
+///
+/// A failing assertion shows the fields that don't match:
+/// ```should_panic
+/// use binary_layout::{assert_layout_eq, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   seq_no: u16,
+///   flag: bool as u8,
+///   payload: [u8],
+/// });
+///
+/// // panics with:
+/// // assertion `left == right` failed: 2 fields of `my_layout` differ
+/// //   seq_no: left = 5, right = 6
+/// //   payload: left = [0a 14], right = [0a]
+/// assert_layout_eq!(
+///   my_layout::View::new(&[0, 5, 1, 10, 20]),
+///   my_layout::View::new(&[0, 6, 1, 10]),
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_layout_eq {
+    ($view: expr, {$($field: ident : $expected: expr),* $(,)?} $(,)?) => {{
+        let view = &$view;
+        #[allow(unused_mut)]
+        let mut mismatches = $crate::internal::Vec::new();
+        $(
+            let actual = $crate::internal::ReadForAssert::read_for_assert(view.$field(), stringify!($field));
+            let expected = $expected;
+            if actual != expected {
+                mismatches.push($crate::internal::Mismatch::new(stringify!($field), &actual, &expected));
+            }
+        )*
+        $crate::internal::assert_fields_match(view, &mismatches);
+    }};
+    ($left: expr, $right: expr $(,)?) => {
+        $crate::internal::assert_views_eq(&$left, &$right)
+    };
+}
+
+/// Define a test checking a layout against a golden test vector, i.e. against hex encoded bytes and the field values they encode.
+/// The test checks that
+/// - decoding the bytes gives the expected field values, like `assert_layout_eq!(view, { ... })` does, see [assert_layout_eq!].
+/// - encoding the expected field values into a zeroed buffer of the same length gives the bytes again.
+///
+/// Fields that aren't listed stay zero when encoding, so they have to be zero in the golden bytes.
+/// Nested layouts can't be listed. The expected values are evaluated twice, once for decoding and once for encoding.
+/// Bytes in the hex string may be separated by whitespace or colons.
+///
+/// This macro is only available with the `std` feature.
+///
+/// # Example
+/// ```
+/// use binary_layout::{golden_test, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   seq_no: u16,
+///   flag: bool as u8,
+///   payload: [u8],
+/// });
+///
+/// golden_test!(my_layout_golden, my_layout, "00 05 01 0a 14", {
+///   seq_no: 5,
+///   flag: true,
+///   payload: &[10, 20],
+/// });
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! golden_test {
+    ($(#[$attr: meta])* $test_name: ident, $($layout: ident)::+, $hex: expr, {$($field: ident : $expected: expr),* $(,)?} $(,)?) => {
+        $(#[$attr])*
+        #[test]
+        fn $test_name() {
+            let golden = $crate::internal::parse_hex($hex);
+            $crate::assert_layout_eq!($($layout)::+::View::new(&golden[..]), {$($field: $expected),*});
+
+            let mut encoded = golden.clone();
+            encoded.iter_mut().for_each(|byte| *byte = 0);
+            #[allow(unused_mut, unused_variables)]
+            let mut view = $($layout)::+::View::new(&mut encoded[..]);
+            $(
+                $crate::internal::paste!{
+                    $crate::internal::WriteForAssert::write_for_assert(view.[<$field _mut>](), $expected, stringify!($field));
+                }
+            )*
+            $crate::assert_layout_eq!($($layout)::+::View::new(&encoded[..]), $($layout)::+::View::new(&golden[..]));
+        }
+    };
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the types returned from field accessors of views so [assert_layout_eq!] can compare them to expected values.
+pub trait ReadForAssert {
+    /// The value of the field
+    type Value: Debug;
+
+    /// Read the value of the field, panicking if it can't be read
+    fn read_for_assert(self, field_name: &str) -> Self::Value;
+}
+
+impl<S: AsRef<[u8]>, F: FieldCopyAccess> ReadForAssert for FieldView<S, F>
+where
+    F::HighLevelType: Debug,
+    F::ReadError: Debug,
+{
+    type Value = F::HighLevelType;
+
+    #[track_caller]
+    fn read_for_assert(self, field_name: &str) -> Self::Value {
+        match self.try_read() {
+            Ok(value) => value,
+            Err(err) => panic!("Couldn't read field `{}`: {:?}", field_name, err),
+        }
+    }
+}
+
+impl<'a> ReadForAssert for &'a [u8] {
+    type Value = &'a [u8];
+
+    fn read_for_assert(self, _field_name: &str) -> Self::Value {
+        self
+    }
+}
+
+impl<'a, const N: usize> ReadForAssert for &'a [u8; N] {
+    type Value = &'a [u8];
+
+    fn read_for_assert(self, _field_name: &str) -> Self::Value {
+        self
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the types returned from mutable field accessors of views so [golden_test!] can write expected values.
+pub trait WriteForAssert<V> {
+    /// Write the value to the field, panicking if it can't be written
+    fn write_for_assert(self, value: V, field_name: &str);
+}
+
+impl<S: AsMut<[u8]>, F: FieldCopyAccess> WriteForAssert<F::HighLevelType> for FieldView<S, F>
+where
+    F::WriteError: Debug,
+{
+    #[track_caller]
+    fn write_for_assert(mut self, value: F::HighLevelType, field_name: &str) {
+        if let Err(err) = self.try_write(value) {
+            panic!("Couldn't write field `{}`: {:?}", field_name, err);
+        }
+    }
+}
+
+impl<V: AsRef<[u8]>> WriteForAssert<V> for &mut [u8] {
+    #[track_caller]
+    fn write_for_assert(self, value: V, field_name: &str) {
+        write_bytes(self, value.as_ref(), field_name);
+    }
+}
+
+impl<V: AsRef<[u8]>, const N: usize> WriteForAssert<V> for &mut [u8; N] {
+    #[track_caller]
+    fn write_for_assert(self, value: V, field_name: &str) {
+        write_bytes(self, value.as_ref(), field_name);
+    }
+}
+
+#[track_caller]
+fn write_bytes(field: &mut [u8], value: &[u8], field_name: &str) {
+    if field.len() != value.len() {
+        panic!(
+            "Couldn't write field `{}`: the field has {} bytes but the value has {} bytes",
+            field_name,
+            field.len(),
+            value.len()
+        );
+    }
+    field.copy_from_slice(value);
+}
+
+/// Internal function. Don't use this in user code.
+/// Parses hex encoded bytes for [golden_test!]. Bytes may be separated by whitespace or colons.
+#[track_caller]
+pub fn parse_hex(hex: &str) -> Vec<u8> {
+    let digits: Vec<u8> = hex
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b':')
+        .collect();
+    if digits.len() % 2 != 0 {
+        panic!("Hex string `{}` has an odd number of digits", hex);
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            core::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .unwrap_or_else(|| panic!("Hex string `{}` has invalid digits", hex))
+        })
+        .collect()
+}
+
+/// Internal type. Don't use this in user code.
+/// A field whose value doesn't match the expected value in [assert_layout_eq!].
+#[derive(Debug)]
+pub struct Mismatch {
+    field_name: &'static str,
+    actual: String,
+    expected: String,
+}
+
+impl Mismatch {
+    /// Remember the mismatching values
+    pub fn new(field_name: &'static str, actual: &dyn Debug, expected: &dyn Debug) -> Self {
+        Self {
+            field_name,
+            actual: format!("{:?}", actual),
+            expected: format!("{:?}", expected),
+        }
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Panics listing the mismatching fields if there are any.
+#[track_caller]
+pub fn assert_fields_match<V: ViewInfo>(_view: &V, mismatches: &[Mismatch]) {
+    if mismatches.is_empty() {
+        return;
+    }
+    let mut message = format!(
+        "assertion failed: {} fields of `{}` don't have the expected values",
+        mismatches.len(),
+        V::LAYOUT.name
+    );
+    for mismatch in mismatches {
+        write!(
+            message,
+            "\n  {}: actual = {}, expected = {}",
+            mismatch.field_name, mismatch.actual, mismatch.expected
+        )
+        .unwrap();
+    }
+    panic!("{}", message);
+}
+
+/// Internal function. Don't use this in user code.
+/// Panics listing the differing fields if the views aren't equal.
+#[track_caller]
+pub fn assert_views_eq<L: ViewInfo, R: ViewInfo>(left: &L, right: &R) {
+    if L::LAYOUT != R::LAYOUT {
+        panic!(
+            "assertion `left == right` failed: the views have different layouts `{}` and `{}`",
+            L::LAYOUT.name,
+            R::LAYOUT.name
+        );
+    }
+    let diffs = L::LAYOUT.diff(left.data(), right.data());
+    if diffs.is_empty() {
+        return;
+    }
+    let mut message = format!(
+        "assertion `left == right` failed: {} fields of `{}` differ",
+        diffs.len(),
+        L::LAYOUT.name
+    );
+    for diff in diffs {
+        write!(
+            message,
+            "\n  {}: left = {}, right = {}",
+            diff.path, diff.left, diff.right
+        )
+        .unwrap();
+    }
+    panic!("{}", message);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(inner, LittleEndian, {
+        value: i16,
+    });
+    binary_layout!(packet, BigEndian, {
+        seq_no: u32,
+        flag: bool as u8,
+        header: inner::NestedView,
+        mac: [u8; 2],
+        payload: [u8],
+    });
+    binary_layout!(other, BigEndian, {
+        seq_no: u32,
+    });
+
+    fn data(seq_no: u8, value: u8, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0, 0, 0, seq_no, 1, value, 0, 0xaa, 0xbb];
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn equal_views() {
+        assert_layout_eq!(
+            packet::View::new(data(1, 2, &[3])),
+            packet::View::new(&data(1, 2, &[3]))
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion `left == right` failed: 2 fields of `packet` differ
+  seq_no: left = 1, right = 2
+  header.value: left = 2, right = 255"
+    )]
+    fn different_views() {
+        assert_layout_eq!(
+            packet::View::new(data(1, 2, &[3])),
+            packet::View::new(data(2, 0xff, &[3]))
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion `left == right` failed: the views have different layouts `packet` and `other`"
+    )]
+    fn different_layouts() {
+        assert_layout_eq!(
+            packet::View::new(data(1, 2, &[3])),
+            other::View::new(data(1, 2, &[3]))
+        );
+    }
+
+    #[test]
+    fn expected_values() {
+        let view = packet::View::new(data(1, 2, &[3, 4]));
+        assert_layout_eq!(view, {});
+        assert_layout_eq!(view, {
+            seq_no: 1,
+            flag: true,
+            mac: &[0xaa, 0xbb],
+            payload: &[3, 4],
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: 2 fields of `packet` don't have the expected values
+  seq_no: actual = 1, expected = 5
+  payload: actual = [3, 4], expected = [3]"
+    )]
+    fn unexpected_values() {
+        let view = packet::View::new(data(1, 2, &[3, 4]));
+        assert_layout_eq!(view, {
+            seq_no: 5,
+            flag: true,
+            payload: &[3],
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Couldn't read field `flag`")]
+    fn unreadable_values() {
+        let mut data = data(1, 2, &[]);
+        data[4] = 2;
+        assert_layout_eq!(packet::View::new(data), { flag: true });
+    }
+
+    golden_test!(golden, packet, "00000001 01 0000 aabb 0304", {
+        seq_no: 1,
+        flag: true,
+        mac: [0xaa, 0xbb],
+        payload: &[3, 4],
+    });
+
+    golden_test!(golden_with_separators, other, "00:00:01:00", { seq_no: 256 });
+
+    golden_test!(
+        #[should_panic(
+            expected = "assertion failed: 1 fields of `other` don't have the expected values\n  seq_no: actual = 256, expected = 255"
+        )]
+        golden_with_unexpected_values,
+        other,
+        "00000100",
+        { seq_no: 255 }
+    );
+
+    golden_test!(
+        #[should_panic(
+            expected = "assertion `left == right` failed: 1 fields of `packet` differ\n  header.value: left = 0, right = 5"
+        )]
+        golden_with_unlisted_fields,
+        packet,
+        "00000001 01 0500 aabb",
+        { seq_no: 1, flag: true, mac: &[0xaa, 0xbb] }
+    );
+
+    golden_test!(golden_with_path, super::tests::other, "00000000", {});
+
+    #[test]
+    #[should_panic(expected = "Hex string `abc` has an odd number of digits")]
+    fn odd_hex() {
+        super::parse_hex("abc");
+    }
+
+    #[test]
+    #[should_panic(expected = "Hex string `zz` has invalid digits")]
+    fn invalid_hex() {
+        super::parse_hex("zz");
+    }
+}
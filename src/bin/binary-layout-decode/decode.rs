@@ -0,0 +1,310 @@
+//! Decodes field values from binary data, based on the type names of the fields in a layout description.
+
+use std::fmt::Write;
+
+use binary_layout::EndianKind;
+use thiserror::Error;
+
+use crate::json::{Field, Layout};
+
+/// The error being thrown when the data doesn't fit the layout.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data is too short for the layout.
+    #[error("The layout needs at least {expected} bytes but the data only has {actual} bytes")]
+    DataTooShort {
+        /// Minimal size of the layout
+        expected: usize,
+        /// Size of the data
+        actual: usize,
+    },
+}
+
+/// A field of a layout together with the value it has in the decoded data.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DecodedField<'a> {
+    pub field: &'a Field,
+    pub value: String,
+}
+
+/// Decode the values of all fields of the layout from the given data.
+pub fn decode<'a>(layout: &'a Layout, data: &[u8]) -> Result<Vec<DecodedField<'a>>, DecodeError> {
+    let expected = layout
+        .fields
+        .iter()
+        .map(|field| field.offset + field.size.unwrap_or(0))
+        .max()
+        .unwrap_or(0)
+        .max(layout.size.unwrap_or(0));
+    if data.len() < expected {
+        return Err(DecodeError::DataTooShort {
+            expected,
+            actual: data.len(),
+        });
+    }
+    // LengthPrefixed and varint fields take up more than their minimal number of bytes depending on their data,
+    // which moves the fields after them
+    let mut shift = 0;
+    let mut decoded = Vec::with_capacity(layout.fields.len());
+    for field in &layout.fields {
+        let offset = field.offset + shift;
+        let bytes = &data[offset.min(data.len())..];
+        let (min_len, len) = match (
+            field.size,
+            dynamic_len(&field.type_name, layout.endianness, bytes),
+        ) {
+            (Some(size), _) => (size, size),
+            (None, Some((min_len, len))) => (min_len, len),
+            (None, None) => (bytes.len(), bytes.len()),
+        };
+        let end = offset.saturating_add(len);
+        if data.len() < end {
+            return Err(DecodeError::DataTooShort {
+                expected: end,
+                actual: data.len(),
+            });
+        }
+        shift += len - min_len;
+        decoded.push(DecodedField {
+            field,
+            value: decode_value(&field.type_name, &data[offset..end], layout.endianness),
+        });
+    }
+    Ok(decoded)
+}
+
+/// The minimal and actual number of bytes of a LengthPrefixed or varint field with its data at the start of the given bytes,
+/// or `None` for other fields. The actual number of bytes can be more than there are bytes if the data is truncated.
+fn dynamic_len(type_name: &str, endianness: EndianKind, bytes: &[u8]) -> Option<(usize, usize)> {
+    let type_name: String = type_name.chars().filter(|c| !c.is_whitespace()).collect();
+    let (generic, parameter) = type_name.strip_suffix('>')?.split_once('<')?;
+    match generic.rsplit("::").next()? {
+        "LengthPrefixed" | "LengthPrefixedStr" => {
+            let prefix_size = match parameter.rsplit("::").next()? {
+                "u8" => 1,
+                "u16" => 2,
+                "u32" => 4,
+                "u64" => 8,
+                _ => return None,
+            };
+            if bytes.len() < prefix_size {
+                return Some((prefix_size, prefix_size));
+            }
+            let mut prefix = [0; 8];
+            match endianness {
+                EndianKind::Big => prefix[8 - prefix_size..].copy_from_slice(&bytes[..prefix_size]),
+                EndianKind::Little | EndianKind::Native => {
+                    prefix[..prefix_size].copy_from_slice(&bytes[..prefix_size])
+                }
+            }
+            let length = match endianness {
+                EndianKind::Big => u64::from_be_bytes(prefix),
+                EndianKind::Little => u64::from_le_bytes(prefix),
+                EndianKind::Native => u64::from_ne_bytes(prefix),
+            };
+            let length = usize::try_from(length).unwrap_or(usize::MAX);
+            Some((prefix_size, prefix_size.saturating_add(length)))
+        }
+        // Each byte of a varint except for the last one has the high bit set
+        "Leb128" | "ZigZag" | "Vlq" => {
+            let len = bytes
+                .iter()
+                .position(|byte| byte & 0x80 == 0)
+                .map_or(bytes.len() + 1, |index| index + 1);
+            Some((1, len))
+        }
+        _ => None,
+    }
+}
+
+macro_rules! decode_primitive {
+    ($type: ty, $bytes: expr, $endianness: expr) => {{
+        let bytes = $bytes.try_into().unwrap();
+        match $endianness {
+            EndianKind::Big => <$type>::from_be_bytes(bytes),
+            EndianKind::Little => <$type>::from_le_bytes(bytes),
+            EndianKind::Native => <$type>::from_ne_bytes(bytes),
+        }
+    }};
+}
+
+/// Decodes a field value. Fields using a custom type via `LayoutAs` are shown as their underlying type,
+/// and fields with types this tool doesn't know, e.g. nested layouts, are shown as hex bytes.
+fn decode_value(type_name: &str, bytes: &[u8], endianness: EndianKind) -> String {
+    let underlying = type_name.rsplit(" as ").next().unwrap_or(type_name);
+    let underlying = underlying.rsplit("::").next().unwrap_or(underlying);
+    let underlying = underlying.strip_prefix("NonZero").map_or_else(
+        || underlying.to_string(),
+        |primitive| primitive.to_lowercase(),
+    );
+    let value = match (underlying.as_str(), bytes.len()) {
+        ("u8", 1) => decode_primitive!(u8, bytes, endianness).to_string(),
+        ("u16", 2) => decode_primitive!(u16, bytes, endianness).to_string(),
+        ("u32", 4) => decode_primitive!(u32, bytes, endianness).to_string(),
+        ("u64", 8) => decode_primitive!(u64, bytes, endianness).to_string(),
+        ("u128", 16) => decode_primitive!(u128, bytes, endianness).to_string(),
+        ("i8", 1) => decode_primitive!(i8, bytes, endianness).to_string(),
+        ("i16", 2) => decode_primitive!(i16, bytes, endianness).to_string(),
+        ("i32", 4) => decode_primitive!(i32, bytes, endianness).to_string(),
+        ("i64", 8) => decode_primitive!(i64, bytes, endianness).to_string(),
+        ("i128", 16) => decode_primitive!(i128, bytes, endianness).to_string(),
+        ("f32", 4) => decode_primitive!(f32, bytes, endianness).to_string(),
+        ("f64", 8) => decode_primitive!(f64, bytes, endianness).to_string(),
+        ("()", 0) => "()".to_string(),
+        _ => return hex(bytes),
+    };
+    match type_name {
+        "bool as u8" => match value.as_str() {
+            "0" => "false".to_string(),
+            "1" => "true".to_string(),
+            _ => format!("{} (invalid bool)", value),
+        },
+        "char as u32" => match value.parse().ok().and_then(char::from_u32) {
+            Some(c) => format!("{:?}", c),
+            None => format!("{} (invalid char)", value),
+        },
+        _ => value,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 3);
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            result.push(' ');
+        }
+        write!(result, "{:02x}", byte).unwrap();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!("258", decode_value("u16", &[1, 2], EndianKind::Big));
+        assert_eq!("513", decode_value("u16", &[1, 2], EndianKind::Little));
+        assert_eq!(
+            "-2",
+            decode_value("i32", &[254, 255, 255, 255], EndianKind::Little)
+        );
+        assert_eq!(
+            "5",
+            decode_value(
+                "core::num::NonZeroU64",
+                &[0, 0, 0, 0, 0, 0, 0, 5],
+                EndianKind::Big
+            )
+        );
+        assert_eq!("7", decode_value("MyEnum as u8", &[7], EndianKind::Big));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(
+            "1.5",
+            decode_value("f32", &1.5f32.to_le_bytes(), EndianKind::Little)
+        );
+        assert_eq!(
+            "-0.25",
+            decode_value("f64", &(-0.25f64).to_be_bytes(), EndianKind::Big)
+        );
+    }
+
+    #[test]
+    fn bool_and_char() {
+        assert_eq!("true", decode_value("bool as u8", &[1], EndianKind::Big));
+        assert_eq!("false", decode_value("bool as u8", &[0], EndianKind::Big));
+        assert_eq!(
+            "2 (invalid bool)",
+            decode_value("bool as u8", &[2], EndianKind::Big)
+        );
+        assert_eq!(
+            "'a'",
+            decode_value("char as u32", &[0x61, 0, 0, 0], EndianKind::Little)
+        );
+        assert_eq!(
+            "55296 (invalid char)",
+            decode_value("char as u32", &[0, 0, 0xd8, 0], EndianKind::Big)
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(
+            "01 ab",
+            decode_value("[u8; 2]", &[1, 0xab], EndianKind::Big)
+        );
+        assert_eq!("", decode_value("[u8]", &[], EndianKind::Big));
+        assert_eq!(
+            "00 01",
+            decode_value("nested::NestedView", &[0, 1], EndianKind::Big)
+        );
+    }
+
+    #[test]
+    fn data_too_short() {
+        let layout = Layout {
+            name: "my_layout".to_string(),
+            endianness: EndianKind::Big,
+            size: None,
+            fields: vec![
+                Field {
+                    name: "field1".to_string(),
+                    type_name: "u16".to_string(),
+                    offset: 0,
+                    size: Some(2),
+                },
+                Field {
+                    name: "tail".to_string(),
+                    type_name: "[u8]".to_string(),
+                    offset: 2,
+                    size: None,
+                },
+            ],
+        };
+        assert_eq!(
+            Err(DecodeError::DataTooShort {
+                expected: 2,
+                actual: 1
+            }),
+            decode(&layout, &[1])
+        );
+        let decoded = decode(&layout, &[1, 2, 3]).unwrap();
+        assert_eq!("258", decoded[0].value);
+        assert_eq!("03", decoded[1].value);
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        let field = |name: &str, type_name: &str, offset, size| Field {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            offset,
+            size,
+        };
+        let layout = Layout {
+            name: "message".to_string(),
+            endianness: EndianKind::Big,
+            size: None,
+            fields: vec![
+                field("name", "LengthPrefixed<u16>", 0, None),
+                field("delta", "ZigZag<i32>", 2, None),
+                field("crc", "u16", 3, Some(2)),
+            ],
+        };
+        let decoded = decode(&layout, &[0, 2, b'a', b'b', 0x81, 0x01, 0, 7]).unwrap();
+        assert_eq!("00 02 61 62", decoded[0].value);
+        assert_eq!("81 01", decoded[1].value);
+        assert_eq!("7", decoded[2].value);
+        assert_eq!(
+            Err(DecodeError::DataTooShort {
+                expected: 8,
+                actual: 7
+            }),
+            decode(&layout, &[0, 2, b'a', b'b', 0x81, 0x01, 0])
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! A minimal JSON reader, just powerful enough to read the layout descriptions written by
+//! [export::json](binary_layout::export::json).
+
+use binary_layout::EndianKind;
+use thiserror::Error;
+
+/// The error being thrown when a layout description can't be read.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum JsonError {
+    /// The document ended before all values were closed.
+    #[error("Unexpected end of JSON document")]
+    UnexpectedEof,
+    /// The document isn't well-formed at the given byte position.
+    #[error("Malformed JSON at byte {0}")]
+    Malformed(usize),
+    /// The document is valid JSON but doesn't describe a layout.
+    #[error("Invalid layout description: {0}")]
+    InvalidLayout(String),
+}
+
+/// A layout read from a JSON description, see [LayoutInfo](binary_layout::LayoutInfo).
+#[derive(Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub name: String,
+    pub endianness: EndianKind,
+    pub size: Option<usize>,
+    pub fields: Vec<Field>,
+}
+
+/// A field read from a JSON description, see [FieldInfo](binary_layout::FieldInfo).
+#[derive(Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub type_name: String,
+    pub offset: usize,
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool,
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn member(&self, name: &str) -> Result<&Value, JsonError> {
+        match self {
+            Value::Object(members) => members
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value)
+                .ok_or_else(|| JsonError::InvalidLayout(format!("Missing `{}`", name))),
+            _ => Err(JsonError::InvalidLayout(format!(
+                "Expected an object containing `{}`",
+                name
+            ))),
+        }
+    }
+
+    fn as_str(&self, name: &str) -> Result<&str, JsonError> {
+        match self.member(name)? {
+            Value::String(value) => Ok(value),
+            _ => Err(JsonError::InvalidLayout(format!(
+                "`{}` must be a string",
+                name
+            ))),
+        }
+    }
+
+    fn as_size(&self, name: &str) -> Result<Option<usize>, JsonError> {
+        match self.member(name)? {
+            Value::Null => Ok(None),
+            Value::Number(value) => value.parse().map(Some).map_err(|_| {
+                JsonError::InvalidLayout(format!("`{}` must be a non-negative integer", name))
+            }),
+            _ => Err(JsonError::InvalidLayout(format!(
+                "`{}` must be a number or null",
+                name
+            ))),
+        }
+    }
+}
+
+/// Read a layout description as written by [export::json::to_json](binary_layout::export::json::to_json).
+pub fn parse_layout(json: &str) -> Result<Layout, JsonError> {
+    let mut parser = Parser {
+        input: json.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.input.len() {
+        return Err(JsonError::Malformed(parser.pos));
+    }
+
+    let endianness = match value.as_str("endianness")? {
+        "big" => EndianKind::Big,
+        "little" => EndianKind::Little,
+        "native" => EndianKind::Native,
+        other => {
+            return Err(JsonError::InvalidLayout(format!(
+                "Unknown endianness `{}`",
+                other
+            )))
+        }
+    };
+    let fields = match value.member("fields")? {
+        Value::Array(fields) => fields
+            .iter()
+            .map(|field| {
+                Ok(Field {
+                    name: field.as_str("name")?.to_string(),
+                    type_name: field.as_str("type")?.to_string(),
+                    offset: field.as_size("offset")?.ok_or_else(|| {
+                        JsonError::InvalidLayout("`offset` must not be null".to_string())
+                    })?,
+                    size: field.as_size("size")?,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => {
+            return Err(JsonError::InvalidLayout(
+                "`fields` must be an array".to_string(),
+            ))
+        }
+    };
+    Ok(Layout {
+        name: value.as_str("name")?.to_string(),
+        endianness,
+        size: value.as_size("size")?,
+        fields,
+    })
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Result<u8, JsonError> {
+        self.input
+            .get(self.pos)
+            .copied()
+            .ok_or(JsonError::UnexpectedEof)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Result<(), JsonError> {
+        if self.input.len() < self.pos + expected.len() {
+            return Err(JsonError::UnexpectedEof);
+        }
+        if &self.input[self.pos..self.pos + expected.len()] != expected {
+            return Err(JsonError::Malformed(self.pos));
+        }
+        self.pos += expected.len();
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<Value, JsonError> {
+        self.skip_whitespace();
+        match self.peek()? {
+            b'n' => self.expect(b"null").map(|()| Value::Null),
+            b't' => self.expect(b"true").map(|()| Value::Bool),
+            b'f' => self.expect(b"false").map(|()| Value::Bool),
+            b'"' => self.parse_string().map(Value::String),
+            b'[' => self.parse_array(),
+            b'{' => self.parse_object(),
+            b'-' | b'0'..=b'9' => Ok(self.parse_number()),
+            _ => Err(JsonError::Malformed(self.pos)),
+        }
+    }
+
+    fn parse_number(&mut self) -> Value {
+        let start = self.pos;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+        Value::Number(String::from_utf8_lossy(&self.input[start..self.pos]).into_owned())
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.expect(b"\"")?;
+        let mut result = Vec::new();
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return String::from_utf8(result).map_err(|_| JsonError::Malformed(self.pos));
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    let escaped = match self.peek()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let digits = self
+                                .input
+                                .get(self.pos + 1..self.pos + 5)
+                                .ok_or(JsonError::UnexpectedEof)?;
+                            let code = std::str::from_utf8(digits)
+                                .ok()
+                                .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or(JsonError::Malformed(self.pos))?;
+                            self.pos += 4;
+                            code
+                        }
+                        _ => return Err(JsonError::Malformed(self.pos)),
+                    };
+                    self.pos += 1;
+                    let mut buffer = [0; 4];
+                    result.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                byte => {
+                    self.pos += 1;
+                    result.push(byte);
+                }
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, JsonError> {
+        self.expect(b"[")?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Ok(Value::Array(elements));
+        }
+        loop {
+            elements.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Ok(Value::Array(elements));
+                }
+                _ => return Err(JsonError::Malformed(self.pos)),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Value, JsonError> {
+        self.expect(b"{")?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b":")?;
+            members.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                _ => return Err(JsonError::Malformed(self.pos)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binary_layout::{binary_layout, export::json::to_json};
+
+    #[test]
+    fn reads_exported_layout() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: bool as u8,
+            tail: [u8],
+        });
+        assert_eq!(
+            Ok(Layout {
+                name: "my_layout".to_string(),
+                endianness: EndianKind::Big,
+                size: None,
+                fields: vec![
+                    Field {
+                        name: "field1".to_string(),
+                        type_name: "u16".to_string(),
+                        offset: 0,
+                        size: Some(2),
+                    },
+                    Field {
+                        name: "field2".to_string(),
+                        type_name: "bool as u8".to_string(),
+                        offset: 2,
+                        size: Some(1),
+                    },
+                    Field {
+                        name: "tail".to_string(),
+                        type_name: "[u8]".to_string(),
+                        offset: 3,
+                        size: None,
+                    },
+                ],
+            }),
+            parse_layout(&to_json(&my_layout::LAYOUT))
+        );
+    }
+
+    #[test]
+    fn reads_escaped_strings() {
+        let layout = parse_layout(
+            r#"{"name": "a\"b\u0041", "endianness": "little", "size": 0, "fields": []}"#,
+        )
+        .unwrap();
+        assert_eq!("a\"bA", layout.name);
+        assert_eq!(Some(0), layout.size);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(Err(JsonError::UnexpectedEof), parse_layout(r#"{"name": "#));
+        assert_eq!(Err(JsonError::Malformed(9)), parse_layout(r#"{"name": x}"#));
+        assert_eq!(
+            Err(JsonError::Malformed(14)),
+            parse_layout(r#"{"name": "a"} }"#)
+        );
+    }
+
+    #[test]
+    fn invalid_layout() {
+        assert_eq!(
+            Err(JsonError::InvalidLayout("Missing `endianness`".to_string())),
+            parse_layout(r#"{"name": "a"}"#)
+        );
+        assert_eq!(
+            Err(JsonError::InvalidLayout(
+                "Unknown endianness `middle`".to_string()
+            )),
+            parse_layout(r#"{"name": "a", "endianness": "middle"}"#)
+        );
+    }
+}
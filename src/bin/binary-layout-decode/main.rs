@@ -0,0 +1,161 @@
+//! Command line tool printing the field values of binary data, given a layout description
+//! exported with [export::json](binary_layout::export::json).
+//!
+//! Usage: `binary-layout-decode <LAYOUT_JSON> (<DATA_FILE> | --hex <HEX>)`
+//!
+//! This tool is only built when the `cli` feature is enabled, e.g. `cargo install binary-layout --features cli`.
+
+mod decode;
+mod json;
+
+const USAGE: &str = "Usage: binary-layout-decode <LAYOUT_JSON> (<DATA_FILE> | --hex <HEX>)";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => print!("{}", output),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let (layout_path, data) = match args {
+        [layout_path, flag, hex_data] if flag == "--hex" => (layout_path, parse_hex(hex_data)?),
+        [layout_path, data_path] if !data_path.starts_with("--") => (
+            layout_path,
+            std::fs::read(data_path)
+                .map_err(|err| format!("Couldn't read `{}`: {}", data_path, err))?,
+        ),
+        _ => return Err(USAGE.to_string()),
+    };
+    let layout_json = std::fs::read_to_string(layout_path)
+        .map_err(|err| format!("Couldn't read `{}`: {}", layout_path, err))?;
+    let layout = json::parse_layout(&layout_json).map_err(|err| err.to_string())?;
+    let decoded = decode::decode(&layout, &data).map_err(|err| err.to_string())?;
+    Ok(format_table(&layout, &decoded))
+}
+
+/// Parse hex data. Bytes may be separated by whitespace or colons and the data may start with `0x`.
+fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = hex
+        .trim()
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':')
+        .collect();
+    if digits.len() % 2 != 0 {
+        return Err("Hex data must have an even number of digits".to_string());
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair: String = pair.iter().collect();
+            u8::from_str_radix(&pair, 16).map_err(|_| format!("Invalid hex byte `{}`", pair))
+        })
+        .collect()
+}
+
+fn format_table(layout: &json::Layout, decoded: &[decode::DecodedField]) -> String {
+    let header = ["offset", "size", "name", "type", "value"];
+    let rows: Vec<[String; 5]> = decoded
+        .iter()
+        .map(|decoded| {
+            [
+                decoded.field.offset.to_string(),
+                decoded
+                    .field
+                    .size
+                    .map_or_else(|| "-".to_string(), |size| size.to_string()),
+                decoded.field.name.clone(),
+                decoded.field.type_name.clone(),
+                decoded.value.clone(),
+            ]
+        })
+        .collect();
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = format!("{}\n", layout.name);
+    let mut write_row = |row: [&str; 5]| {
+        let line = format!(
+            "{:>w0$}  {:>w1$}  {:w2$}  {:w3$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    };
+    write_row(header);
+    for row in &rows {
+        write_row([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex() {
+        assert_eq!(Ok(vec![0x01, 0xab, 0xff]), parse_hex("01abff"));
+        assert_eq!(Ok(vec![0x01, 0xab, 0xff]), parse_hex("0x01 AB:ff\n"));
+        assert_eq!(Ok(vec![]), parse_hex(""));
+        assert_eq!(
+            Err("Hex data must have an even number of digits".to_string()),
+            parse_hex("abc")
+        );
+        assert_eq!(Err("Invalid hex byte `zz`".to_string()), parse_hex("zz"));
+    }
+
+    #[test]
+    fn table() {
+        let layout = json::parse_layout(
+            r#"{
+  "name": "my_layout",
+  "endianness": "big",
+  "size": null,
+  "fields": [
+    {"name": "field1", "type": "u16", "offset": 0, "size": 2},
+    {"name": "flag", "type": "bool as u8", "offset": 2, "size": 1},
+    {"name": "tail", "type": "[u8]", "offset": 3, "size": null}
+  ]
+}
+"#,
+        )
+        .unwrap();
+        let decoded = decode::decode(&layout, &[1, 2, 1, 0xab, 0xcd]).unwrap();
+        assert_eq!(
+            r#"my_layout
+offset  size  name    type        value
+     0     2  field1  u16         258
+     2     1  flag    bool as u8  true
+     3     -  tail    [u8]        ab cd
+"#,
+            format_table(&layout, &decoded)
+        );
+    }
+
+    #[test]
+    fn usage() {
+        assert_eq!(Err(USAGE.to_string()), run(&[]));
+        assert_eq!(
+            Err(USAGE.to_string()),
+            run(&["layout.json".to_string(), "--hex".to_string()])
+        );
+    }
+}
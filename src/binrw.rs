@@ -0,0 +1,173 @@
+//! Support for the [binrw](https://docs.rs/binrw) crate, enabled by the `binrw` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) implements [BinRead](binrw::BinRead) for `View<Vec<u8>>`
+//! and [BinWrite](binrw::BinWrite) for all views of each layout, so layouts can be used as fields of binrw based parsers
+//! for surrounding container formats. The endianness binrw passes in is ignored since layouts define their own endianness.
+//!
+//! Reading a layout with a fixed size reads exactly that many bytes, and so does reading a layout whose size is given by its
+//! [LengthPrefixed](crate::LengthPrefixed) or varint fields. Reading a layout ending in another open ended field reads until the end of the reader.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use binrw::{binrw, io::Cursor, BinRead, BinWrite};
+//!
+//! binary_layout!(header, BigEndian, {
+//!   version: u8,
+//!   length: u16,
+//! });
+//!
+//! #[binrw]
+//! #[brw(little, magic = b"PKG")]
+//! struct Package {
+//!     header: header::View<Vec<u8>>,
+//!     checksum: u32,
+//! }
+//!
+//! let package = Package::read(&mut Cursor::new(b"PKG\x01\x00\x05\x78\x56\x34\x12")).unwrap();
+//! assert_eq!(1, package.header.version().read());
+//! assert_eq!(5, package.header.length().read());
+//! assert_eq!(0x12345678, package.checksum);
+//!
+//! let mut written = Cursor::new(Vec::new());
+//! package.write(&mut written).unwrap();
+//! assert_eq!(b"PKG\x01\x00\x05\x78\x56\x34\x12", &written.into_inner()[..]);
+//! ```
+
+use binrw::io::{Read, Seek, SeekFrom};
+use binrw::BinResult;
+
+use crate::LayoutInfo;
+
+/// Internal function. Don't use this in user code.
+/// Reads the storage for a view of the layout. On errors, the reader is moved back to where it was.
+pub fn read_storage<R: Read + Seek>(layout: &LayoutInfo, reader: &mut R) -> BinResult<Vec<u8>> {
+    let pos = reader.stream_position()?;
+    read_storage_at(layout, reader, pos).or_else(|err| {
+        reader.seek(SeekFrom::Start(pos))?;
+        Err(err)
+    })
+}
+
+fn read_storage_at<R: Read + Seek>(
+    layout: &LayoutInfo,
+    reader: &mut R,
+    pos: u64,
+) -> BinResult<Vec<u8>> {
+    match layout.size {
+        Some(size) => {
+            let mut storage = vec![0; size];
+            reader.read_exact(&mut storage)?;
+            Ok(storage)
+        }
+        None => {
+            let mut storage = Vec::new();
+            reader.read_to_end(&mut storage)?;
+            // The data of LengthPrefixed and varint fields decides where the layout ends
+            let size = layout
+                .size_in(&storage)
+                .map_err(|error| binrw::Error::AssertFail {
+                    pos,
+                    message: format!(
+                        "`{}` doesn't fit into the remaining bytes: {}",
+                        layout.name, error
+                    ),
+                })?;
+            if size < storage.len() {
+                storage.truncate(size);
+                reader.seek(SeekFrom::Start(pos + size as u64))?;
+            }
+            Ok(storage)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binrw::io::Cursor;
+    use binrw::{BinRead, BinWrite, Endian};
+
+    use crate::prelude::*;
+
+    binary_layout!(sized, BigEndian, {
+        first: u16,
+        second: u8,
+    });
+    binary_layout!(open_ended, LittleEndian, {
+        length: u16,
+        payload: [u8],
+    });
+
+    #[test]
+    fn read_sized() {
+        let mut reader = Cursor::new(vec![1, 2, 3, 4]);
+        let view = sized::View::<Vec<u8>>::read_options(&mut reader, Endian::Little, ()).unwrap();
+        assert_eq!(0x0102, view.first().read());
+        assert_eq!(3, view.second().read());
+        assert_eq!(3, reader.position());
+    }
+
+    #[test]
+    fn read_open_ended() {
+        let mut reader = Cursor::new(vec![1, 0, 5, 6]);
+        let view = open_ended::View::<Vec<u8>>::read_be(&mut reader).unwrap();
+        assert_eq!(1, view.length().read());
+        assert_eq!(&[5, 6], view.payload());
+        assert_eq!(4, reader.position());
+    }
+
+    #[test]
+    fn read_too_short() {
+        let mut reader = Cursor::new(vec![9, 1, 2]);
+        reader.set_position(1);
+        assert!(sized::View::<Vec<u8>>::read_be(&mut reader).is_err());
+        assert_eq!(1, reader.position());
+
+        reader.set_position(2);
+        let result = open_ended::View::<Vec<u8>>::read_le(&mut reader);
+        assert!(matches!(
+            result,
+            Err(binrw::Error::AssertFail { pos: 2, .. })
+        ));
+        assert_eq!(2, reader.position());
+    }
+
+    binary_layout!(named, BigEndian, {
+        kind: u8,
+        name: crate::LengthPrefixed<u8>,
+        crc: u16,
+    });
+
+    #[test]
+    fn read_dynamic_length() {
+        let mut reader = Cursor::new(vec![1, 2, b'a', b'b', 0x12, 0x34, 0xff]);
+        let view = named::View::<Vec<u8>>::read_be(&mut reader).unwrap();
+        assert_eq!(Ok(&b"ab"[..]), view.name().try_data());
+        assert_eq!(0x1234, view.crc().read());
+        assert_eq!(6, view.into_storage().len());
+        assert_eq!(6, reader.position());
+    }
+
+    #[test]
+    fn read_corrupt_length_prefix() {
+        // The length prefix points past the end of the reader
+        let mut reader = Cursor::new(vec![1, 200, b'a', b'b', 0x12, 0x34]);
+        let result = named::View::<Vec<u8>>::read_be(&mut reader);
+        assert!(matches!(
+            result,
+            Err(binrw::Error::AssertFail { pos: 0, .. })
+        ));
+        assert_eq!(0, reader.position());
+    }
+
+    #[test]
+    fn write() {
+        let mut writer = Cursor::new(Vec::new());
+        let data = [1, 2, 3];
+        sized::View::new(&data[..]).write_le(&mut writer).unwrap();
+        open_ended::View::new(vec![4, 5, 6])
+            .write_be(&mut writer)
+            .unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], writer.into_inner());
+    }
+}
@@ -0,0 +1,231 @@
+//! Support for the [bitvec](https://docs.rs/bitvec) crate, enabled by the `bitvec` feature.
+//!
+//! Views need byte slices as storage, but in bitstreams, e.g. in compressed container formats, a byte oriented layout
+//! can start at a position that isn't at a byte boundary. [BitStorage] and [BitStorageMut] make such a bit slice usable
+//! as storage for a view. They copy the bits into a byte buffer, so that each byte holds 8 consecutive bits of the slice
+//! in the same order they'd have in an aligned byte of the same [BitOrder]. Bits at the end of the slice that don't
+//! make up a whole byte are not part of the storage. [BitStorageMut] writes changes back to the bit slice when
+//! [BitStorageMut::write_back] is called or when it is dropped.
+//!
+//! # Example
+//! ```
+//! use binary_layout::{prelude::*, bitvec::{BitStorage, BitStorageMut}};
+//! use bitvec::prelude::*;
+//!
+//! binary_layout!(header, BigEndian, {
+//!   kind: u8,
+//!   length: u16,
+//! });
+//!
+//! // A bitstream with a 3 bit tag followed by a header
+//! let mut stream = bitvec![u8, Msb0; 0; 3 + 24];
+//! stream[3..].copy_from_bitslice(&[0x01u8, 0x00, 0x05].view_bits::<Msb0>());
+//!
+//! let view = header::View::new(BitStorage::new(&stream[3..]));
+//! assert_eq!(1, view.kind().read());
+//! assert_eq!(5, view.length().read());
+//!
+//! let mut view = header::View::new(BitStorageMut::new(&mut stream[3..]));
+//! view.length_mut().write(6);
+//! drop(view);
+//! assert_eq!(&[0x01u8, 0x00, 0x06].view_bits::<Msb0>()[..], &stream[3..]);
+//! ```
+
+use bitvec::order::BitOrder;
+use bitvec::slice::BitSlice;
+use bitvec::view::BitView;
+use core::convert::Infallible;
+use core::ops::Range;
+
+use crate::FlushableStorage;
+
+fn bits_to_bytes<O: BitOrder>(bits: &BitSlice<u8, O>) -> Vec<u8> {
+    let len = bits.len() / 8;
+    let mut bytes = vec![0; len];
+    bytes
+        .view_bits_mut::<O>()
+        .copy_from_bitslice(&bits[..len * 8]);
+    bytes
+}
+
+/// Read-only storage for a view over a bit slice that doesn't have to start at a byte boundary, see [the module documentation](crate::bitvec).
+pub struct BitStorage<'a, O: BitOrder> {
+    bits: &'a BitSlice<u8, O>,
+    bytes: Vec<u8>,
+}
+
+impl<'a, O: BitOrder> BitStorage<'a, O> {
+    /// Create the storage, copying the bits into a byte buffer
+    pub fn new(bits: &'a BitSlice<u8, O>) -> Self {
+        Self {
+            bytes: bits_to_bytes(bits),
+            bits,
+        }
+    }
+
+    /// Return the bit slice this storage was created from
+    pub fn bits(&self) -> &'a BitSlice<u8, O> {
+        self.bits
+    }
+}
+
+impl<'a, O: BitOrder> AsRef<[u8]> for BitStorage<'a, O> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Storage for a view over a mutable bit slice that doesn't have to start at a byte boundary, see [the module documentation](crate::bitvec).
+/// Changes are written back to the bit slice by [BitStorageMut::write_back] and when the storage is dropped.
+pub struct BitStorageMut<'a, O: BitOrder> {
+    bits: &'a mut BitSlice<u8, O>,
+    bytes: Vec<u8>,
+}
+
+impl<'a, O: BitOrder> BitStorageMut<'a, O> {
+    /// Create the storage, copying the bits into a byte buffer
+    pub fn new(bits: &'a mut BitSlice<u8, O>) -> Self {
+        Self {
+            bytes: bits_to_bytes(bits),
+            bits,
+        }
+    }
+
+    /// Return the bit slice this storage was created from. It only contains changes to the storage after [BitStorageMut::write_back].
+    pub fn bits(&self) -> &BitSlice<u8, O> {
+        self.bits
+    }
+
+    /// Write the bytes of the storage back into the bit slice
+    pub fn write_back(&mut self) {
+        self.write_back_range(0..self.bytes.len());
+    }
+
+    fn write_back_range(&mut self, range: Range<usize>) {
+        self.bits[range.start * 8..range.end * 8]
+            .copy_from_bitslice(self.bytes[range].view_bits::<O>());
+    }
+}
+
+impl<'a, O: BitOrder> AsRef<[u8]> for BitStorageMut<'a, O> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<'a, O: BitOrder> AsMut<[u8]> for BitStorageMut<'a, O> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+/// Flushing writes the given bytes back into the bit slice, so `View::flush_fields` only writes back the bits of some fields
+impl<'a, O: BitOrder> FlushableStorage for BitStorageMut<'a, O> {
+    type FlushError = Infallible;
+
+    fn flush_range(&mut self, range: Range<usize>) -> Result<(), Infallible> {
+        self.write_back_range(range);
+        Ok(())
+    }
+}
+
+impl<'a, O: BitOrder> Drop for BitStorageMut<'a, O> {
+    fn drop(&mut self) {
+        self.write_back();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::*;
+
+    use super::{BitStorage, BitStorageMut};
+    use crate::prelude::*;
+
+    binary_layout!(record, LittleEndian, {
+        first: u8,
+        second: u16,
+        tail: [u8],
+    });
+
+    #[test]
+    fn aligned_bits_are_the_underlying_bytes() {
+        let data = [1u8, 2, 3, 4];
+        let storage = BitStorage::new(data.view_bits::<Lsb0>());
+        assert_eq!(&data, storage.as_ref());
+        let storage = BitStorage::new(data.view_bits::<Msb0>());
+        assert_eq!(&data, storage.as_ref());
+    }
+
+    #[test]
+    fn read_unaligned_msb0() {
+        let mut bits = bitvec![u8, Msb0; 1; 5];
+        bits.extend_from_bitslice([0x12u8, 0x34, 0x56, 0x78].view_bits::<Msb0>());
+        bits.extend_from_bitslice(bits![u8, Msb0; 1, 0, 1]);
+
+        let view = record::View::new(BitStorage::new(&bits[5..]));
+        assert_eq!(0x12, view.first().read());
+        assert_eq!(0x5634, view.second().read());
+        assert_eq!(&[0x78], view.tail());
+        assert_eq!(8 * 4 + 3, view.into_storage().bits().len());
+    }
+
+    #[test]
+    fn read_unaligned_lsb0() {
+        let mut bits = bitvec![u8, Lsb0; 0; 2];
+        bits.extend_from_bitslice([0xabu8, 0xcd, 0xef].view_bits::<Lsb0>());
+
+        let view = record::View::new(BitStorage::new(&bits[2..]));
+        assert_eq!(0xab, view.first().read());
+        assert_eq!(0xefcd, view.second().read());
+        assert!(view.tail().is_empty());
+    }
+
+    #[test]
+    fn write_unaligned() {
+        let mut bits = bitvec![u8, Msb0; 0; 4 + 32 + 4];
+        let mut view = record::View::new(BitStorageMut::new(&mut bits[4..]));
+        view.first_mut().write(0xff);
+        view.second_mut().write(0x0102);
+        view.tail_mut()[0] = 0x80;
+        let mut storage = view.into_storage();
+        assert!(storage.bits().not_any());
+        storage.write_back();
+        assert_eq!(
+            [0xffu8, 0x02, 0x01, 0x80].view_bits::<Msb0>(),
+            &storage.bits()[..32]
+        );
+        drop(storage);
+
+        let mut expected = bitvec![u8, Msb0; 0; 4];
+        expected.extend_from_bitslice([0xffu8, 0x02, 0x01, 0x80].view_bits::<Msb0>());
+        expected.extend_from_bitslice(bits![u8, Msb0; 0; 4]);
+        assert_eq!(expected, bits);
+    }
+
+    #[test]
+    fn write_back_on_drop() {
+        let mut bits = bitvec![u8, Lsb0; 1; 3 + 24];
+        {
+            let mut view = record::View::new(BitStorageMut::new(&mut bits[3..]));
+            view.second_mut().write(0);
+        }
+        assert!(bits[..11].all());
+        assert!(bits[11..].not_any());
+    }
+
+    #[test]
+    fn flush_fields() {
+        let mut bits = bitvec![u8, Msb0; 0; 1 + 32];
+        let mut view = record::View::new(BitStorageMut::new(&mut bits[1..]));
+        view.first_mut().write(0xff);
+        view.second_mut().write(0xffff);
+        view.flush_fields(&[record::FieldId::second]).unwrap();
+        let storage = view.into_storage();
+        assert!(storage.bits()[8..24].all());
+        assert!(storage.bits()[..8].not_any());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::FieldOwnedAccess;
+
+/// Type state of a field in the `Builder` that the `builder` option of [binary_layout!](crate::binary_layout!) generates,
+/// for fields that were written or don't need to be written, like [Padding](crate::Padding) and [Magic](crate::Magic) fields.
+pub struct FieldSet;
+
+/// Type state of a field in the `Builder` that the `builder` option of [binary_layout!](crate::binary_layout!) generates,
+/// for fields that still need to be written before `build()` can be called.
+pub struct FieldUnset;
+
+/// Internal trait. Don't use this in user code.
+/// Gives the type state a field starts with in a `Builder` and what `build()` writes into it.
+pub trait BuilderField: FieldOwnedAccess {
+    /// [FieldUnset] for fields that have to be set, [FieldSet] for fields that can be left out
+    type Initial;
+
+    /// Called by `build()` for each field, e.g. to write the value of a magic field
+    #[inline(always)]
+    fn finish(_storage: &mut [u8]) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{Magic, Optional, Padding};
+
+    binary_layout!(point, BigEndian, value_struct = Point, {
+        x: i16,
+        y: i16,
+    });
+
+    binary_layout!(header, LittleEndian, builder, {
+        magic: Magic<u8, 0x7f>,
+        version: u8,
+        reserved: Padding<2>,
+        length: u32,
+        origin: point::NestedView,
+        extension: Optional<u16>,
+    });
+
+    #[test]
+    fn build() {
+        let view = header::Builder::new([0xff; 14])
+            .set_length(0x01020304)
+            .set_version(2)
+            .set_origin(point::Point { x: 1, y: -1 })
+            .build();
+        assert_eq!(2, view.version().read());
+        assert_eq!(
+            [0x7f, 2, 0, 0, 4, 3, 2, 1, 0, 1, 0xff, 0xff, 0xff, 0xff],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn build_with_optional_field() {
+        let view = header::Builder::new([0; 14])
+            .set_version(1)
+            .set_length(0)
+            .set_origin(point::Point { x: 0, y: 0 })
+            .set_extension(Some(0x0a0b))
+            .set_version(3)
+            .build();
+        assert_eq!(3, view.version().read());
+        assert_eq!(Some(0x0a0b), view.extension().map(|field| field.read()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn build_to() {
+        let mut written = Vec::new();
+        let view = header::Builder::new(header::View::new_zeroed().into_storage())
+            .set_version(2)
+            .set_length(7)
+            .set_origin(point::Point { x: 0, y: 3 })
+            .build_to(&mut written)
+            .unwrap();
+        assert_eq!(vec![0x7f, 2, 0, 0, 7, 0, 0, 0, 0, 0, 0, 3, 0, 0], written);
+        assert_eq!(written, view.into_storage());
+    }
+}
@@ -0,0 +1,146 @@
+//! Support for the [bytes](https://docs.rs/bytes) crate, enabled by the `bytes` feature.
+//!
+//! [Bytes] and [BytesMut] can be used as storage for views like any other `AsRef<[u8]>` type, e.g. `my_layout::View::new(bytes)`.
+//! Accessors like `into_${field_name}()` then return a [Data] over the same buffer, and this module adds [Data::into_bytes] and
+//! [Data::into_bytes_mut] to turn it into a [Bytes] or [BytesMut] of just the field. Neither copies the data, so the payload of
+//! a packet can be passed on without copying it.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use bytes::Bytes;
+//!
+//! binary_layout!(packet, BigEndian, {
+//!   kind: u8,
+//!   payload: [u8],
+//! });
+//!
+//! let buffer = Bytes::from(vec![1, 0xa, 0xb, 0xc]);
+//! let view = packet::View::new(buffer.clone());
+//! assert_eq!(1, view.kind().read());
+//! let payload: Bytes = view.into_payload().into_bytes();
+//! assert_eq!(&[0xa, 0xb, 0xc], &payload[..]);
+//! assert_eq!(buffer[1..].as_ptr(), payload.as_ptr());
+//! ```
+
+use bytes::{Bytes, BytesMut};
+
+use crate::Data;
+
+impl Data<Bytes> {
+    /// Return a [Bytes] instance of just the subregion of this [Data] instance.
+    /// This doesn't copy the data, the returned instance shares the buffer of the original [Bytes].
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        let region = self.region();
+        self.into_inner().slice(region)
+    }
+}
+
+impl From<Data<Bytes>> for Bytes {
+    /// See [Data::into_bytes]
+    #[inline]
+    fn from(data: Data<Bytes>) -> Bytes {
+        data.into_bytes()
+    }
+}
+
+impl Data<BytesMut> {
+    /// Return a [BytesMut] instance of just the subregion of this [Data] instance.
+    /// This doesn't copy the data, the bytes outside of the subregion are cut off the original [BytesMut].
+    #[inline]
+    pub fn into_bytes_mut(self) -> BytesMut {
+        let region = self.region();
+        let mut storage = self.into_inner();
+        storage.truncate(region.end);
+        storage.split_off(region.start)
+    }
+
+    /// Make the storage immutable, keeping the subregion, see [BytesMut::freeze].
+    /// This doesn't copy the data.
+    #[inline]
+    pub fn freeze(self) -> Data<Bytes> {
+        self.into_bytes_mut().freeze().into()
+    }
+}
+
+impl From<Data<BytesMut>> for BytesMut {
+    /// See [Data::into_bytes_mut]
+    #[inline]
+    fn from(data: Data<BytesMut>) -> BytesMut {
+        data.into_bytes_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use crate::prelude::*;
+    use crate::Data;
+
+    binary_layout!(header, BigEndian, {
+        kind: u8,
+        length: u16,
+    });
+    binary_layout!(packet, BigEndian, {
+        head: header::NestedView,
+        checksum: [u8; 2],
+        payload: [u8],
+    });
+
+    #[test]
+    fn bytes_storage() {
+        let buffer = Bytes::from(vec![1, 0, 3, 0xfe, 0xff, 0xa, 0xb, 0xc]);
+        let view = packet::View::new(buffer.clone());
+        assert_eq!(3, view.head().length().read());
+        assert_eq!(&[0xfe, 0xff], view.checksum());
+
+        let payload = view.into_payload().into_bytes();
+        assert_eq!(&[0xa, 0xb, 0xc], &payload[..]);
+        assert_eq!(buffer[5..].as_ptr(), payload.as_ptr());
+    }
+
+    #[test]
+    fn bytes_of_fixed_size_field() {
+        let buffer = Bytes::from(vec![1, 0, 3, 0xfe, 0xff, 0xa]);
+        let checksum = Bytes::from(packet::View::new(buffer.clone()).into_checksum());
+        assert_eq!(&[0xfe, 0xff], &checksum[..]);
+        assert_eq!(buffer[3..].as_ptr(), checksum.as_ptr());
+    }
+
+    #[test]
+    fn nested_view_of_bytes() {
+        let buffer = Bytes::from(vec![7, 0, 3, 0, 0]);
+        let head = packet::View::new(buffer).into_head();
+        assert_eq!(7, head.kind().read());
+        assert_eq!(&[7, 0, 3], &head.into_storage().into_bytes()[..]);
+    }
+
+    #[test]
+    fn bytes_mut_storage() {
+        let mut buffer = BytesMut::from(&[0; 8][..]);
+        buffer.reserve(64);
+        let capacity_ptr = buffer.as_ptr();
+        let mut view = packet::View::new(buffer);
+        view.head_mut().kind_mut().write(2);
+        view.payload_mut().copy_from_slice(&[4, 5, 6]);
+
+        let mut payload = view.into_payload().into_bytes_mut();
+        assert_eq!(&[4, 5, 6], &payload[..]);
+        assert_eq!(capacity_ptr.wrapping_add(5), payload.as_ptr());
+        payload[0] = 9;
+        assert_eq!(&[9, 5, 6], &payload[..]);
+    }
+
+    #[test]
+    fn freeze() {
+        let buffer = BytesMut::from(&[1, 2, 3, 4][..]);
+        let ptr = buffer.as_ptr();
+        let data: Data<Bytes> = Data::from(buffer).into_subregion(1..3).freeze();
+        assert_eq!(&[2, 3], &data[..]);
+        assert_eq!(0..2, data.region());
+        let bytes = data.into_bytes();
+        assert_eq!(ptr.wrapping_add(1), bytes.as_ptr());
+    }
+}
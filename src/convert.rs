@@ -0,0 +1,589 @@
+use core::fmt;
+
+use crate::fields::bit_field::{read_bits, write_bits};
+use crate::{FieldKind, LayoutInfo, ViewError};
+
+/// This error is thrown by the `copy_converting` function that [binary_layout!](crate::binary_layout!) generates
+/// on views when the data can't be copied into the destination view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CopyConvertingError {
+    /// The destination layout doesn't have the same fields as the source layout.
+    LayoutMismatch {
+        /// Name of the source layout
+        src_layout: &'static str,
+        /// Name of the destination layout
+        dst_layout: &'static str,
+    },
+    /// The destination storage is too short for the data of the source view.
+    StorageTooShort {
+        /// Number of bytes the data of the source view needs
+        expected: usize,
+        /// Number of bytes in the destination storage
+        actual: usize,
+    },
+}
+
+impl fmt::Display for CopyConvertingError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyConvertingError::LayoutMismatch {
+                src_layout,
+                dst_layout,
+            } => write!(
+                fmt,
+                "The layouts `{}` and `{}` don't have the same fields",
+                src_layout, dst_layout
+            ),
+            CopyConvertingError::StorageTooShort { expected, actual } => write!(
+                fmt,
+                "The data needs {} bytes but the destination storage only has {} bytes",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CopyConvertingError {}
+
+/// Two layouts are compatible if their fields only differ in the byte order they're stored with,
+/// so nested layouts are compared by their fields as well.
+fn is_compatible(src: &LayoutInfo, dst: &LayoutInfo) -> bool {
+    src.size == dst.size
+        && src.fields.len() == dst.fields.len()
+        && src.fields.iter().zip(dst.fields).all(|(src, dst)| {
+            src.name == dst.name
+                && src.offset == dst.offset
+                && src.size == dst.size
+                && src.optional == dst.optional
+                && is_compatible_kind(src.kind, dst.kind)
+        })
+}
+
+fn is_compatible_kind(src: FieldKind, dst: FieldKind) -> bool {
+    match (src, dst) {
+        (FieldKind::Nested(src), FieldKind::Nested(dst)) => is_compatible(src, dst),
+        (
+            FieldKind::Array {
+                element: src,
+                element_size: src_size,
+            },
+            FieldKind::Array {
+                element: dst,
+                element_size: dst_size,
+            },
+        ) => src_size == dst_size && is_compatible_kind(*src, *dst),
+        (src, dst) => src == dst,
+    }
+}
+
+fn copy_fields(src_layout: &LayoutInfo, src: &[u8], dst_layout: &LayoutInfo, dst: &mut [u8]) {
+    let src_big_endian = src_layout.endianness.is_big_endian();
+    let dst_big_endian = dst_layout.endianness.is_big_endian();
+    let swap = src_big_endian != dst_big_endian;
+    for (index, (src_field, dst_field)) in
+        src_layout.fields.iter().zip(dst_layout.fields).enumerate()
+    {
+        // Only the last field covers the bytes after its data, the bytes after other fields belong to the fields after them
+        let range = if index + 1 < src_layout.fields.len() {
+            src_layout.field_range_in(src_field, src)
+        } else {
+            src_layout.stored_range(src_field, src)
+        };
+        // Optional fields are at the end, so none of the following fields are stored either
+        let range = match range {
+            Some(range) => range,
+            None => break,
+        };
+        let (src, dst) = (&src[range.clone()], &mut dst[range]);
+        match (src_field.kind, dst_field.kind) {
+            (FieldKind::Nested(src_nested), FieldKind::Nested(dst_nested)) => {
+                copy_fields(src_nested, src, dst_nested, dst)
+            }
+            (
+                FieldKind::UnsignedInteger
+                | FieldKind::SignedInteger
+                | FieldKind::Float
+                | FieldKind::BFloat16
+                | FieldKind::Bcd { .. }
+                | FieldKind::Magic { .. },
+                _,
+            ) if swap => dst
+                .iter_mut()
+                .zip(src.iter().rev())
+                .for_each(|(dst, src)| *dst = *src),
+            (
+                FieldKind::Array {
+                    element: FieldKind::Nested(src_nested),
+                    element_size,
+                },
+                FieldKind::Array {
+                    element: FieldKind::Nested(dst_nested),
+                    ..
+                },
+            ) if element_size > 0 => {
+                // Bytes after the last whole element don't belong to any element and are copied unchanged
+                let whole = src.len() / element_size * element_size;
+                dst[whole..].copy_from_slice(&src[whole..]);
+                dst[..whole]
+                    .chunks_mut(element_size)
+                    .zip(src[..whole].chunks(element_size))
+                    .for_each(|(dst, src)| copy_fields(src_nested, src, dst_nested, dst))
+            }
+            (FieldKind::Array { element_size, .. }, _) if swap && element_size > 0 => {
+                let whole = src.len() / element_size * element_size;
+                dst[whole..].copy_from_slice(&src[whole..]);
+                dst[..whole]
+                    .chunks_mut(element_size)
+                    .zip(src[..whole].chunks(element_size))
+                    .for_each(|(dst, src)| {
+                        dst.iter_mut()
+                            .zip(src.iter().rev())
+                            .for_each(|(dst, src)| *dst = *src)
+                    })
+            }
+            // Only the length prefix is a number, the data after it is copied unchanged
+            (FieldKind::LengthPrefixed { prefix_size }, _) if swap => {
+                let (dst_prefix, dst_data) = dst.split_at_mut(prefix_size);
+                let (src_prefix, src_data) = src.split_at(prefix_size);
+                dst_prefix
+                    .iter_mut()
+                    .zip(src_prefix.iter().rev())
+                    .for_each(|(dst, src)| *dst = *src);
+                dst_data.copy_from_slice(src_data);
+            }
+            // Bit fields share bytes with other bit fields, so only their own bits are written
+            (FieldKind::Bits { bit_offset, bits }, _) if swap => {
+                let value = read_bits(src, src_big_endian, bit_offset, bits);
+                write_bits(dst, dst_big_endian, bit_offset, bits, value);
+            }
+            _ => dst.copy_from_slice(src),
+        }
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Copies the bytes of a layout from the source data into the destination storage, i.e. as many bytes as the layout
+/// takes up in the source data, including the data of an open ended field at the end.
+/// Nothing is written if an error is returned.
+pub fn copy_layout(layout: &LayoutInfo, src: &[u8], dst: &mut [u8]) -> Result<(), ViewError> {
+    let size = layout.size_in(src)?;
+    if dst.len() < size {
+        return Err(ViewError::StorageTooShort {
+            expected: size,
+            actual: dst.len(),
+        });
+    }
+    dst[..size].copy_from_slice(&src[..size]);
+    Ok(())
+}
+
+/// Internal function. Don't use this in user code.
+/// Copies the data of a view into the storage of a view of a compatible layout, converting the byte order of each field
+/// if the layouts have different endianness. Nothing is written if an error is returned.
+///
+/// # Panics
+/// If the source storage is too short for the layout.
+pub fn copy_converting(
+    src_layout: &LayoutInfo,
+    src: &[u8],
+    dst_layout: &LayoutInfo,
+    dst: &mut [u8],
+) -> Result<(), CopyConvertingError> {
+    if !is_compatible(src_layout, dst_layout) {
+        return Err(CopyConvertingError::LayoutMismatch {
+            src_layout: src_layout.name,
+            dst_layout: dst_layout.name,
+        });
+    }
+    // Layouts with optional fields only need the bytes of the fields the source stores
+    let expected = src_layout
+        .size
+        .map_or(src.len(), |size| src_layout.size_in(src).unwrap_or(size));
+    if dst.len() < expected {
+        return Err(CopyConvertingError::StorageTooShort {
+            expected,
+            actual: dst.len(),
+        });
+    }
+    copy_fields(
+        src_layout,
+        &src[..expected],
+        dst_layout,
+        &mut dst[..expected],
+    );
+    Ok(())
+}
+
+/// Internal function. Don't use this in user code.
+/// Converts the data of a view in place into the byte order of a compatible layout, like [copy_converting].
+/// Nothing is written if an error is returned.
+///
+/// # Panics
+/// If the storage is too short for the layout.
+#[cfg(feature = "std")]
+pub fn convert_in_place(
+    src_layout: &LayoutInfo,
+    dst_layout: &LayoutInfo,
+    data: &mut [u8],
+) -> Result<(), CopyConvertingError> {
+    // Bit fields share bytes with each other, so all fields are read from a copy of the original data
+    let src = data.to_vec();
+    copy_converting(src_layout, &src, dst_layout, data)
+}
+
+/// Internal function. Don't use this in user code.
+/// Writes the bytes of a layout in the data to the writer, i.e. as many bytes as the layout takes up in the data,
+/// including the data of an open ended field at the end. Fails with [std::io::ErrorKind::InvalidInput] if the data
+/// is too short for the layout.
+#[cfg(feature = "std")]
+pub fn write_layout<W: std::io::Write>(
+    layout: &LayoutInfo,
+    data: &[u8],
+    mut writer: W,
+) -> std::io::Result<()> {
+    let size = layout
+        .size_in(data)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+    writer.write_all(&data[..size])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CopyConvertingError;
+    use crate::prelude::*;
+    use crate::ViewError;
+
+    binary_layout!(header_be, BigEndian, {
+        kind: u8,
+        length: u16,
+    });
+    binary_layout!(header_le, LittleEndian, {
+        kind: u8,
+        length: u16,
+    });
+
+    binary_layout!(packet_be, BigEndian, {
+        header: header_be::NestedView,
+        id: u32,
+        offset: i16,
+        ratio: f32,
+        valid: bool as u8,
+        checksum: [u8; 2],
+        payload: [u8],
+    });
+    binary_layout!(packet_le, LittleEndian, {
+        header: header_le::NestedView,
+        id: u32,
+        offset: i16,
+        ratio: f32,
+        valid: bool as u8,
+        checksum: [u8; 2],
+        payload: [u8],
+    });
+    binary_layout!(packet_renamed, LittleEndian, {
+        header: header_le::NestedView,
+        identifier: u32,
+        offset: i16,
+        ratio: f32,
+        valid: bool as u8,
+        checksum: [u8; 2],
+        payload: [u8],
+    });
+
+    #[test]
+    fn big_to_little_endian() {
+        let mut src = packet_be::View::new([0; 19]);
+        src.header_mut().kind_mut().write(7);
+        src.header_mut().length_mut().write(0x0102);
+        src.id_mut().write(0x01020304);
+        src.offset_mut().write(-2);
+        src.ratio_mut().write(1.5);
+        src.valid_mut().write(true);
+        src.checksum_mut().copy_from_slice(&[0xab, 0xcd]);
+        src.payload_mut().copy_from_slice(&[1, 2, 3]);
+
+        let mut dst = packet_le::View::new([0; 19]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!(7, dst.header().kind().read());
+        assert_eq!(0x0102, dst.header().length().read());
+        assert_eq!(0x01020304, dst.id().read());
+        assert_eq!(-2, dst.offset().read());
+        assert_eq!(1.5, dst.ratio().read());
+        assert!(dst.valid().try_read().unwrap());
+        assert_eq!(&[0xab, 0xcd], dst.checksum());
+        assert_eq!(&[1, 2, 3], dst.payload());
+        assert_eq!(
+            [7, 2, 1, 4, 3, 2, 1, 0xfe, 0xff, 0, 0, 0xc0, 0x3f, 1, 0xab, 0xcd, 1, 2, 3],
+            dst.into_storage()
+        );
+    }
+
+    #[test]
+    fn little_to_big_endian() {
+        let src = header_le::View::new([1, 0x34, 0x12]);
+        let mut dst = header_be::View::new([0; 3]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!([1, 0x12, 0x34], dst.into_storage());
+    }
+
+    binary_layout!(flags_be, BigEndian, {
+        kind: u8 bits(3),
+        urgent: bool bits(1),
+        offset: u16 bits(12),
+    });
+    binary_layout!(flags_le, LittleEndian, {
+        kind: u8 bits(3),
+        urgent: bool bits(1),
+        offset: u16 bits(12),
+    });
+
+    #[test]
+    fn bit_fields() {
+        let mut src = flags_be::View::new([0; 2]);
+        src.kind_mut().try_write(5).unwrap();
+        src.urgent_mut().write(true);
+        src.offset_mut().try_write(0x123).unwrap();
+        let mut dst = flags_le::View::new([0; 2]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!(5, dst.kind().read());
+        assert!(dst.urgent().read());
+        assert_eq!(0x123, dst.offset().read());
+        assert_eq!([0b0011_1101, 0x12], dst.into_storage());
+        assert_eq!([0b1011_0001, 0x23], src.into_storage());
+    }
+
+    binary_layout!(samples_be, BigEndian, {
+        samples: [u16; 3],
+        bytes: [u8; 2],
+    });
+    binary_layout!(samples_le, LittleEndian, {
+        samples: [u16; 3],
+        bytes: [u8; 2],
+    });
+
+    #[test]
+    fn arrays() {
+        let mut src = samples_le::View::new([0; 8]);
+        src.samples_mut().write([1, 0x0203, 0xfffe]);
+        src.bytes_mut().copy_from_slice(&[4, 5]);
+        let mut dst = samples_be::View::new([0; 8]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!([1, 0x0203, 0xfffe], dst.samples().read());
+        assert_eq!([0, 1, 2, 3, 0xff, 0xfe, 4, 5], dst.into_storage());
+    }
+
+    binary_layout!(records_be, BigEndian, {
+        values: [u16],
+    });
+    binary_layout!(records_le, LittleEndian, {
+        values: [u16],
+    });
+    binary_layout!(table_be, BigEndian, {
+        count: u8,
+        headers: [header_be::NestedView],
+    });
+    binary_layout!(table_le, LittleEndian, {
+        count: u8,
+        headers: [header_le::NestedView],
+    });
+
+    #[test]
+    fn open_ended_arrays() {
+        let src = records_le::View::new([1, 0, 3, 2, 0xff]);
+        let mut dst = records_be::View::new([0; 5]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!(0x0203, dst.values().get(1));
+        assert_eq!([0, 1, 2, 3, 0xff], dst.into_storage());
+
+        let src = table_le::View::new([2, 1, 0x34, 0x12, 2, 0x78, 0x56, 0xff]);
+        let mut dst = table_be::View::new([0; 8]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!(0x5678, dst.headers().get(1).unwrap().length().read());
+        assert_eq!([2, 1, 0x12, 0x34, 2, 0x56, 0x78, 0xff], dst.into_storage());
+    }
+
+    binary_layout!(message_be, BigEndian, {
+        payload: crate::LengthPrefixed<u16>,
+    });
+    binary_layout!(message_le, LittleEndian, {
+        payload: crate::LengthPrefixed<u16>,
+    });
+
+    #[test]
+    fn length_prefixed() {
+        let src = message_le::View::new([2, 0, 0xab, 0xcd, 0xff]);
+        let mut dst = message_be::View::new([0; 5]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!(Ok(&[0xab, 0xcd][..]), dst.payload().try_data());
+        assert_eq!([0, 2, 0xab, 0xcd, 0xff], dst.into_storage());
+    }
+
+    binary_layout!(signed_be, BigEndian, {
+        payload: crate::LengthPrefixed<u16>,
+        crc: u16,
+    });
+    binary_layout!(signed_le, LittleEndian, {
+        payload: crate::LengthPrefixed<u16>,
+        crc: u16,
+    });
+
+    #[test]
+    fn fields_after_length_prefixed() {
+        let src = signed_le::View::new([2, 0, 0xab, 0xcd, 0x34, 0x12, 0xff]);
+        let mut dst = signed_be::View::new([0; 7]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!(0x1234, dst.crc().read());
+        assert_eq!([0, 2, 0xab, 0xcd, 0x12, 0x34, 0], dst.into_storage());
+    }
+
+    #[test]
+    fn same_endianness() {
+        let src = header_le::View::new([1, 0x34, 0x12]);
+        let mut dst = header_le::View::new([0; 4]);
+        src.copy_converting(&mut dst).unwrap();
+        assert_eq!([1, 0x34, 0x12, 0], dst.into_storage());
+    }
+
+    #[test]
+    fn open_ended_layout_copies_the_payload() {
+        let src = packet_le::View::new([0; 19]);
+        let mut dst = packet_be::View::new([0xff; 20]);
+        src.copy_converting(&mut dst).unwrap();
+        let storage = dst.into_storage();
+        assert_eq!([0; 19], storage[..19]);
+        assert_eq!(0xff, storage[19]);
+    }
+
+    #[test]
+    fn layout_mismatch() {
+        let src = packet_le::View::new([0; 16]);
+        let mut dst = packet_renamed::View::new([0xff; 16]);
+        assert_eq!(
+            Err(CopyConvertingError::LayoutMismatch {
+                src_layout: "packet_le",
+                dst_layout: "packet_renamed",
+            }),
+            src.copy_converting(&mut dst)
+        );
+        let mut dst = header_be::View::new([0xff; 16]);
+        assert!(matches!(
+            src.copy_converting(&mut dst),
+            Err(CopyConvertingError::LayoutMismatch { .. })
+        ));
+        assert_eq!([0xff; 16], dst.into_storage());
+    }
+
+    #[test]
+    fn storage_too_short() {
+        let src = packet_be::View::new([0; 18]);
+        let mut dst = packet_le::View::new([0xff; 17]);
+        assert_eq!(
+            Err(CopyConvertingError::StorageTooShort {
+                expected: 18,
+                actual: 17
+            }),
+            src.copy_converting(&mut dst)
+        );
+        assert_eq!([0xff; 17], dst.into_storage());
+
+        let src = header_be::View::new([0; 3]);
+        let mut dst = header_le::View::new([0xff; 2]);
+        assert_eq!(
+            Err(CopyConvertingError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            }),
+            src.copy_converting(&mut dst)
+        );
+    }
+
+    #[test]
+    fn copy_from() {
+        let src = header_be::View::new([1, 2, 3, 4]);
+        let mut dst = header_be::View::new([0xff; 4]);
+        dst.copy_from(&src).unwrap();
+        assert_eq!([1, 2, 3, 0xff], dst.into_storage());
+    }
+
+    #[test]
+    fn copy_from_open_ended_layout() {
+        let src = packet_be::View::new([7; 20]);
+        let mut dst = packet_be::View::new([0; 21]);
+        dst.copy_from(&src).unwrap();
+        assert_eq!(&[7, 7, 7, 7, 0], dst.payload());
+
+        let mut dst = packet_be::View::new([0; 19]);
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 20,
+                actual: 19
+            }),
+            dst.copy_from(&src)
+        );
+        assert_eq!([0; 19], dst.into_storage());
+    }
+
+    #[test]
+    fn copy_from_bytes() {
+        let mut dst = message_be::View::new([0; 5]);
+        dst.copy_from_bytes(&[0, 1, 0xab, 0xcd]).unwrap();
+        assert_eq!([0, 1, 0xab, 0, 0], dst.into_storage());
+
+        let mut dst = header_le::View::new([0; 3]);
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            }),
+            dst.copy_from_bytes(&[1, 2])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn convert_endianness() {
+        let mut view = packet_be::View::new([
+            1, 0, 2, 0, 0, 0, 3, 0xff, 0xfe, 0x3f, 0x80, 0, 0, 1, 0xaa, 0xbb, 9, 8,
+        ]);
+        view.convert_endianness::<packet_le::NestedView>().unwrap();
+        let view = packet_le::View::new(view.into_storage());
+        assert_eq!(2, view.header().length().read());
+        assert_eq!(3, view.id().read());
+        assert_eq!(-2, view.offset().read());
+        assert_eq!(1.0, view.ratio().read());
+        assert_eq!(&[0xaa, 0xbb], view.checksum());
+        assert_eq!(&[9, 8], view.payload());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn convert_endianness_of_bit_fields() {
+        binary_layout!(flags_be, BigEndian, {
+            high: u16 bits(4),
+            low: u16 bits(12),
+        });
+        binary_layout!(flags_le, LittleEndian, {
+            high: u16 bits(4),
+            low: u16 bits(12),
+        });
+        let mut view = flags_be::View::new([0x12, 0x34]);
+        let (high, low) = (view.high().read(), view.low().read());
+        view.convert_endianness::<flags_le::NestedView>().unwrap();
+        let view = flags_le::View::new(view.into_storage());
+        assert_eq!(high, view.high().read());
+        assert_eq!(low, view.low().read());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn convert_endianness_layout_mismatch() {
+        let mut view = header_be::View::new([1, 2, 3]);
+        assert!(matches!(
+            view.convert_endianness::<packet_le::NestedView>(),
+            Err(CopyConvertingError::LayoutMismatch { .. })
+        ));
+        assert_eq!([1, 2, 3], view.into_storage());
+    }
+}
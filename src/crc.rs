@@ -0,0 +1,165 @@
+//! Support for the [crc](https://docs.rs/crc) crate, enabled by the `crc` feature.
+//!
+//! This module offers common CRC algorithms as [Checksum] implementations for checksum fields.
+//! A CRC usually covers only some fields of a layout, which is declared with a range of fields like
+//! `crc: u32 checksum(Crc32, magic..=payload)` or a range of bytes like `crc: u32 checksum(Crc32, 0..12)`.
+//! For such fields, the generated `View` gets `compute_<<field>>()`, `verify_<<field>>()` and `update_<<field>>()` functions,
+//! and the generated `View::finalize()` fills them along with the other checksum fields.
+//!
+//! # Example
+//! ```
+//! use binary_layout::{crc::Crc32, prelude::*};
+//!
+//! binary_layout!(chunk, BigEndian, {
+//!   length: u32 length_of(data),
+//!   kind: [u8; 4],
+//!   data: [u8; 3],
+//!   crc: u32 checksum(Crc32, kind..=data),
+//! });
+//!
+//! fn main() {
+//!     let mut view = chunk::View::new([0; 15]);
+//!     view.kind_mut().copy_from_slice(b"tEXt");
+//!     view.data_mut().copy_from_slice(b"abc");
+//!     assert!(!view.verify_crc());
+//!     view.update_crc();
+//!     assert!(view.verify_crc());
+//!     assert_eq!(0xfc85_9b88, view.crc().read());
+//! }
+//! ```
+//!
+//! Other algorithms of the `crc` crate can be used by implementing [Checksum] for them:
+//! ```
+//! use binary_layout::{prelude::*, Checksum};
+//!
+//! pub struct Crc16Kermit;
+//!
+//! impl Checksum for Crc16Kermit {
+//!     type Output = u16;
+//!
+//!     fn checksum(data: &[u8]) -> u16 {
+//!         const CRC: crc::Crc<u16> = crc::Crc::<u16>::new(&crc::CRC_16_KERMIT);
+//!         CRC.checksum(data)
+//!     }
+//! }
+//!
+//! binary_layout!(frame, LittleEndian, {
+//!   payload: [u8; 8],
+//!   crc: u16 checksum(Crc16Kermit, payload..=payload),
+//! });
+//! # fn main() {}
+//! ```
+
+use crate::Checksum;
+
+macro_rules! crc_checksum {
+    ($(#[$doc: meta])* $name: ident, $width: ty, $algorithm: ident) => {
+        $(#[$doc])*
+        pub struct $name;
+
+        impl Checksum for $name {
+            type Output = $width;
+
+            #[inline]
+            fn checksum(data: &[u8]) -> $width {
+                static CRC: ::crc::Crc<$width> = ::crc::Crc::<$width>::new(&::crc::$algorithm);
+                CRC.checksum(data)
+            }
+        }
+    };
+}
+
+crc_checksum!(
+    /// The CRC-16/ARC algorithm, often just called CRC-16, which is used by the ARC archiver and by LHA.
+    Crc16Arc,
+    u16,
+    CRC_16_ARC
+);
+crc_checksum!(
+    /// The CRC-16/XMODEM algorithm used by XMODEM, Bluetooth and many serial protocols.
+    Crc16Xmodem,
+    u16,
+    CRC_16_XMODEM
+);
+crc_checksum!(
+    /// The CRC-32/ISO-HDLC algorithm, often just called CRC-32, which is used by Ethernet, zlib, PNG and ZIP.
+    Crc32,
+    u32,
+    CRC_32_ISO_HDLC
+);
+crc_checksum!(
+    /// The CRC-32C algorithm with the Castagnoli polynomial, which is used by iSCSI, SCTP, ext4 and btrfs.
+    Crc32c,
+    u32,
+    CRC_32_ISCSI
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{Crc16Arc, Crc16Xmodem, Crc32, Crc32c};
+    use crate::prelude::*;
+    use crate::Checksum;
+
+    binary_layout!(packet, LittleEndian, {
+        header_crc: u16 checksum(Crc16Arc, 2..6),
+        kind: u16,
+        length: u16 length_of(payload),
+        payload: [u8],
+    });
+
+    binary_layout!(record, BigEndian, {
+        magic: u16,
+        value: u32,
+        crc32: u32 checksum(Crc32, magic..=value),
+        crc32c: u32 checksum(Crc32c, magic..=crc32),
+    });
+
+    #[test]
+    fn check_values() {
+        // The check values of the algorithms in the CRC catalogue
+        assert_eq!(0xbb3d, Crc16Arc::checksum(b"123456789"));
+        assert_eq!(0x31c3, Crc16Xmodem::checksum(b"123456789"));
+        assert_eq!(0xcbf4_3926, Crc32::checksum(b"123456789"));
+        assert_eq!(0xe306_9283, Crc32c::checksum(b"123456789"));
+    }
+
+    #[test]
+    fn byte_range() {
+        let mut view = packet::View::new(vec![0, 0, 1, 0, 0, 0, 10, 20, 30]);
+        assert!(!view.verify_header_crc());
+        view.finalize().unwrap();
+        assert_eq!(3, view.length().read());
+        assert!(view.verify_header_crc());
+        assert_eq!(Crc16Arc::checksum(&[1, 0, 3, 0]), view.header_crc().read());
+        // The payload isn't covered
+        view.payload_mut()[0] = 0;
+        assert!(view.verify_header_crc());
+        view.kind_mut().write(2);
+        assert!(!view.verify_header_crc());
+    }
+
+    #[test]
+    fn field_range() {
+        let mut view = record::View::new([0; 14]);
+        view.magic_mut().write(0x1234);
+        view.value_mut().write(0xdead_beef);
+        assert_eq!(
+            Crc32::checksum(&[0x12, 0x34, 0xde, 0xad, 0xbe, 0xef]),
+            view.compute_crc32()
+        );
+        view.update_crc32();
+        assert!(view.verify_crc32());
+        assert!(!view.verify_crc32c());
+
+        // finalize() computes checksums in the order of declaration, so crc32c covers crc32
+        view.crc32_mut().write(0);
+        view.finalize().unwrap();
+        assert!(view.verify_crc32());
+        assert!(view.verify_crc32c());
+        let storage = view.into_storage();
+        assert_eq!(
+            Crc32c::checksum(&storage[..10]),
+            u32::from_be_bytes(storage[10..].try_into().unwrap())
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{FieldInfo, FieldValue, LayoutInfo};
+
+/// A field whose value differs between two buffers of the same layout, see [LayoutInfo::diff].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff<'a, 'b> {
+    /// Path of the field. For fields of nested layouts, this includes the names of the nesting fields, e.g. `header.checksum`.
+    pub path: String,
+    /// The field whose value differs
+    pub field: &'static FieldInfo,
+    /// Value of the field in the first buffer
+    pub left: FieldValue<'a>,
+    /// Value of the field in the second buffer
+    pub right: FieldValue<'b>,
+}
+
+impl LayoutInfo {
+    /// Compare the fields of two buffers of this layout and return the fields whose values differ, in layout order.
+    /// Nested layouts are compared field by field, so only the fields of nested layouts that actually differ are returned.
+    ///
+    /// Fields are compared by their binary representation, i.e. a float field storing `NaN` in both buffers isn't reported as different.
+    /// Open ended fields are different if their lengths differ.
+    ///
+    /// Each layout generated by [binary_layout!](crate::binary_layout!) also has a `diff` function comparing two of its views.
+    ///
+    /// # Panics
+    /// If any of the buffers is too short for the layout.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FieldValue};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   seq_no: u16,
+    ///   flags: u8,
+    ///   payload: [u8],
+    /// });
+    ///
+    /// let diffs = my_layout::LAYOUT.diff(&[0, 1, 5, 1, 2], &[0, 2, 5, 1, 2]);
+    /// assert_eq!(1, diffs.len());
+    /// assert_eq!("seq_no", diffs[0].path);
+    /// assert_eq!(FieldValue::UnsignedInteger(1), diffs[0].left);
+    /// assert_eq!(FieldValue::UnsignedInteger(2), diffs[0].right);
+    ///
+    /// let left = my_layout::View::new(vec![0, 1, 5]);
+    /// let right = my_layout::View::new(vec![0, 1, 6, 7]);
+    /// let paths: Vec<String> = my_layout::diff(&left, &right).into_iter().map(|diff| diff.path).collect();
+    /// assert_eq!(vec!["flags", "payload"], paths);
+    /// ```
+    pub fn diff<'a, 'b>(&self, left: &'a [u8], right: &'b [u8]) -> Vec<FieldDiff<'a, 'b>> {
+        let mut result = Vec::new();
+        self.diff_into(&mut result, "", left, right);
+        result
+    }
+
+    fn diff_into<'a, 'b>(
+        &self,
+        result: &mut Vec<FieldDiff<'a, 'b>>,
+        prefix: &str,
+        left: &'a [u8],
+        right: &'b [u8],
+    ) {
+        // Optional fields are only compared if both sides store them
+        let stored_fields = self.fields.iter().filter(|field| {
+            self.stored_range(field, left).is_some() && self.stored_range(field, right).is_some()
+        });
+        for field in stored_fields {
+            let path = format!("{}{}", prefix, field.name);
+            match (self.read_field(field, left), self.read_field(field, right)) {
+                (FieldValue::Nested(nested, left), FieldValue::Nested(_, right)) => {
+                    if left != right {
+                        nested.diff_into(result, &format!("{}.", path), left, right);
+                    }
+                }
+                (left_value, right_value) => {
+                    if self.field_bytes(field, left) != self.field_bytes(field, right) {
+                        result.push(FieldDiff {
+                            path,
+                            field,
+                            left: left_value,
+                            right: right_value,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// The bytes of a stored field, i.e. until the end of the data for fields whose end isn't known
+    fn field_bytes<'a>(&self, field: &FieldInfo, data: &'a [u8]) -> &'a [u8] {
+        let range = self.field_range_in(field, data);
+        match range.or_else(|| self.stored_range(field, data)) {
+            Some(range) => &data[range],
+            None => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::FieldValue;
+
+    binary_layout!(deep, LittleEndian, {
+        value: u16,
+        float: f32,
+    });
+    binary_layout!(packet, BigEndian, {
+        seq_no: u32,
+        first: deep::NestedView,
+        second: deep::NestedView,
+        payload: [u8],
+    });
+
+    fn packet(seq_no: u32, second_value: u16, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; packet::payload::OFFSET + payload.len()];
+        let mut view = packet::View::new(&mut data);
+        view.seq_no_mut().write(seq_no);
+        view.first_mut().value_mut().write(1);
+        view.second_mut().value_mut().write(second_value);
+        view.payload_mut().copy_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn equal() {
+        let left = packet::View::new(packet(1, 2, &[3]));
+        let right = packet::View::new(packet(1, 2, &[3]));
+        assert_eq!(Vec::<crate::FieldDiff>::new(), packet::diff(&left, &right));
+    }
+
+    #[test]
+    fn different_primitive_fields() {
+        let left = packet(1, 2, &[3]);
+        let right = packet(10, 2, &[3]);
+        let diffs = packet::LAYOUT.diff(&left, &right);
+        assert_eq!(1, diffs.len());
+        assert_eq!("seq_no", diffs[0].path);
+        assert_eq!(&packet::LAYOUT.fields[0], diffs[0].field);
+        assert_eq!(FieldValue::UnsignedInteger(1), diffs[0].left);
+        assert_eq!(FieldValue::UnsignedInteger(10), diffs[0].right);
+    }
+
+    #[test]
+    fn different_nested_fields() {
+        let left = packet(1, 2, &[3]);
+        let right = packet(1, 20, &[3]);
+        let diffs = packet::LAYOUT.diff(&left, &right);
+        assert_eq!(1, diffs.len());
+        assert_eq!("second.value", diffs[0].path);
+        assert_eq!(&deep::LAYOUT.fields[0], diffs[0].field);
+        assert_eq!(FieldValue::UnsignedInteger(2), diffs[0].left);
+        assert_eq!(FieldValue::UnsignedInteger(20), diffs[0].right);
+    }
+
+    #[test]
+    fn different_open_ended_fields() {
+        let left = packet(1, 2, &[3]);
+        let right = packet(1, 2, &[3, 4]);
+        let diffs = packet::LAYOUT.diff(&left, &right);
+        assert_eq!(1, diffs.len());
+        assert_eq!("payload", diffs[0].path);
+        assert_eq!(FieldValue::Bytes(&[3]), diffs[0].left);
+        assert_eq!(FieldValue::Bytes(&[3, 4]), diffs[0].right);
+    }
+
+    #[test]
+    fn floats_are_compared_by_representation() {
+        let mut left = packet(1, 2, &[]);
+        let mut right = packet(1, 2, &[]);
+        packet::View::new(&mut left)
+            .first_mut()
+            .float_mut()
+            .write(f32::NAN);
+        packet::View::new(&mut right)
+            .first_mut()
+            .float_mut()
+            .write(f32::NAN);
+        assert!(packet::LAYOUT.diff(&left, &right).is_empty());
+
+        packet::View::new(&mut right)
+            .first_mut()
+            .float_mut()
+            .write(-0.0);
+        packet::View::new(&mut left)
+            .first_mut()
+            .float_mut()
+            .write(0.0);
+        let diffs = packet::LAYOUT.diff(&left, &right);
+        assert_eq!(1, diffs.len());
+        assert_eq!("first.float", diffs[0].path);
+    }
+}
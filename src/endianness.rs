@@ -1,7 +1,11 @@
 /// An enum representing the endianness used in a layout for accessing primitive integer fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EndianKind {
+    /// See [BigEndian]
     Big,
+    /// See [LittleEndian]
     Little,
+    /// See [NativeEndian]
     Native,
 }
 
@@ -45,6 +49,10 @@ impl Endianness for LittleEndian {
 
 /// This is a marker type to mark layouts using native endian encoding. The alternative is [BigEndian] and [LittleEndian] encoding.
 ///
+/// Fields are stored in the byte order of the target the code is compiled for, so this is meant for data that doesn't leave the host,
+/// like in-memory structures or messages between processes on the same machine. It avoids keeping a big endian and a little endian copy
+/// of a layout behind `#[cfg(target_endian = ...)]`.
+///
 /// # Example
 /// ```
 /// use binary_layout::prelude::*;
@@ -58,3 +66,15 @@ pub struct NativeEndian {}
 impl Endianness for NativeEndian {
     const KIND: EndianKind = EndianKind::Native;
 }
+
+impl EndianKind {
+    /// Returns true for big endian, and for native endian on big endian targets
+    #[inline(always)]
+    pub(crate) const fn is_big_endian(self) -> bool {
+        match self {
+            EndianKind::Big => true,
+            EndianKind::Little => false,
+            EndianKind::Native => cfg!(target_endian = "big"),
+        }
+    }
+}
@@ -0,0 +1,758 @@
+//! Exports layouts as C header files.
+//!
+//! For each layout, the generated header contains `#define`s for the offset and size of each field
+//! and a packed struct definition that C code can use to access the same data.
+//! C doesn't have a way to specify the byte order of struct members, so the header notes the endianness
+//! of each layout in a comment and C code needs to convert values accordingly, e.g. using `be32toh()`.
+//!
+//! Fields are mapped to C types as follows:
+//! - Integers map to the `<stdint.h>` type of the same size, e.g. `u16` becomes `uint16_t`.
+//!   128 bit integers aren't supported by standard C, so they're exported as `uint8_t[16]`.
+//! - `f32` and `f64` become `float` and `double`.
+//! - Arrays of numbers like `[u16; 8]` become C arrays of the element type, e.g. `uint16_t samples[8]`.
+//!   Open ended arrays like `[u32]` or `[my_layout::NestedView]` become flexible array members, e.g. `struct my_layout records[]`.
+//! - Byte arrays `[u8; N]` become `uint8_t[N]` and open ended byte slices `[u8]` become flexible array members `uint8_t[]`.
+//! - Varints like `Leb128<u32>` have no C equivalent and become flexible array members `uint8_t[]` with the varint type noted in a comment.
+//! - Length prefixed fields like `payload: LengthPrefixed<u16>` become a `uint16_t payload_len` member followed by a flexible array member `uint8_t payload[]`.
+//! - Fields after length prefixed fields or varints don't have a fixed offset, so the struct ends with the flexible array member
+//!   and only notes them in a comment. Their `_OFFSET` `#define`s are the offsets when the fields before them have their minimal size.
+//! - C bit fields don't have a portable bit order, so each group of consecutive bit fields becomes a single byte array member
+//!   named after all fields in the group, e.g. `uint8_t version_ihl[1]`. The bit offset and width of each bit field
+//!   within its bytes is given by `#define`s.
+//! - Nested layouts become nested structs, which are defined in the same header.
+//! - Fields using a custom type via [LayoutAs](crate::LayoutAs) are exported as their underlying type,
+//!   with the custom type noted in a comment.
+//! - `()` fields don't take up any space and are only mentioned in a comment.
+//! - Gaps between fields, e.g. before fields pinned to an explicit offset or padding inserted by `aligned` layouts,
+//!   and the padding at the end of the layout become `uint8_t reserved_<offset>[N]` members,
+//!   so each member is at the offset given by the `#define`s and the size of the struct is the size of the layout.
+//!
+//! C structs need at least one member with a fixed size, so layouts without any, e.g. layouts that only consist of an open ended
+//! byte slice, don't get a struct definition but only the `#define`s and a comment.
+
+use std::fmt::Write;
+
+use super::{collect_nested_layouts, first_relative_field};
+use crate::{EndianKind, FieldInfo, FieldKind, LayoutInfo};
+
+/// Return a C header describing the given layout and all layouts nested into it.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::c_header, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"/* Generated by binary-layout. Do not edit. */
+/// #ifndef MY_LAYOUT_H
+/// #define MY_LAYOUT_H
+///
+/// #include <stdint.h>
+///
+/// /* my_layout: all fields are big endian */
+/// #define MY_LAYOUT_FIELD1_OFFSET 0
+/// #define MY_LAYOUT_FIELD1_SIZE 2
+/// #define MY_LAYOUT_FIELD2_OFFSET 2
+/// #define MY_LAYOUT_FIELD2_SIZE 1
+/// #define MY_LAYOUT_TAIL_OFFSET 3
+///
+/// #pragma pack(push, 1)
+/// struct my_layout {
+///     uint16_t field1;
+///     uint8_t field2; /* bool as u8 */
+///     uint8_t tail[];
+/// };
+/// #pragma pack(pop)
+///
+/// #endif /* MY_LAYOUT_H */
+/// "#,
+///     c_header::to_c_header(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_c_header(layout: &LayoutInfo) -> String {
+    let guard = format!("{}_H", layout.name.to_uppercase());
+    let mut out = String::new();
+    out.push_str("/* Generated by binary-layout. Do not edit. */\n");
+    writeln!(out, "#ifndef {}", guard).unwrap();
+    writeln!(out, "#define {}", guard).unwrap();
+    out.push('\n');
+    out.push_str("#include <stdint.h>\n");
+
+    let mut nested_layouts = Vec::new();
+    collect_nested_layouts(layout, &mut nested_layouts);
+    for nested in nested_layouts {
+        write_layout(&mut out, nested);
+    }
+    write_layout(&mut out, layout);
+
+    out.push('\n');
+    writeln!(out, "#endif /* {} */", guard).unwrap();
+    out
+}
+
+fn write_layout(out: &mut String, layout: &LayoutInfo) {
+    let prefix = layout.name.to_uppercase();
+    out.push('\n');
+    writeln!(
+        out,
+        "/* {}: all fields are {} */",
+        layout.name,
+        endianness(layout.endianness)
+    )
+    .unwrap();
+    if let Some(size) = layout.size {
+        writeln!(out, "#define {}_SIZE {}", prefix, size).unwrap();
+    }
+    let first_relative = first_relative_field(layout);
+    for (index, field) in layout.fields.iter().enumerate() {
+        let name = field.name.to_uppercase();
+        if index >= first_relative {
+            writeln!(
+                out,
+                "#define {}_{}_OFFSET {} /* if the fields before have their minimal size */",
+                prefix, name, field.offset
+            )
+            .unwrap();
+        } else {
+            writeln!(out, "#define {}_{}_OFFSET {}", prefix, name, field.offset).unwrap();
+        }
+        if let Some(size) = field.size {
+            writeln!(out, "#define {}_{}_SIZE {}", prefix, name, size).unwrap();
+        }
+        if let FieldKind::Bits { bit_offset, bits } = field.kind {
+            writeln!(out, "#define {}_{}_BIT_OFFSET {}", prefix, name, bit_offset).unwrap();
+            writeln!(out, "#define {}_{}_BITS {}", prefix, name, bits).unwrap();
+        }
+    }
+    out.push('\n');
+
+    let mut members = String::new();
+    let mut has_sized_member = false;
+    let mut end = 0;
+    let (mut remaining, relative) = layout.fields.split_at(first_relative);
+    while let Some(field) = remaining.first() {
+        if field.offset > end {
+            write_reserved(&mut members, end, field.offset - end);
+            has_sized_member = true;
+        }
+        let group_len = remaining
+            .iter()
+            .take_while(|field| matches!(field.kind, FieldKind::Bits { .. }))
+            .count();
+        if group_len == 0 {
+            has_sized_member |= write_member(&mut members, field);
+        } else {
+            write_bit_field_group(&mut members, &remaining[..group_len]);
+            has_sized_member = true;
+        }
+        let group_len = group_len.max(1);
+        for field in &remaining[..group_len] {
+            end = end.max(field.offset + field.size.unwrap_or(0));
+        }
+        remaining = &remaining[group_len..];
+    }
+    if let Some(size) = layout.size {
+        if size > end {
+            write_reserved(&mut members, end, size - end);
+            has_sized_member = true;
+        }
+    }
+    if !relative.is_empty() {
+        let names: Vec<&str> = relative.iter().map(|field| field.name).collect();
+        writeln!(
+            members,
+            "    /* followed by {}, which don't have a fixed offset */",
+            names.join(", ")
+        )
+        .unwrap();
+    }
+    if !has_sized_member {
+        writeln!(
+            out,
+            "/* struct {} is omitted since it has no members with a fixed size, which C requires */",
+            layout.name
+        )
+        .unwrap();
+        return;
+    }
+    out.push_str("#pragma pack(push, 1)\n");
+    writeln!(out, "struct {} {{", layout.name).unwrap();
+    out.push_str(&members);
+    out.push_str("};\n");
+    out.push_str("#pragma pack(pop)\n");
+}
+
+/// Writes the struct member for a field, returning whether it is a member with a fixed size,
+/// i.e. not a flexible array member or a comment
+fn write_member(out: &mut String, field: &FieldInfo) -> bool {
+    let mut comment = if field.type_name.contains(" as ") {
+        Some(field.type_name)
+    } else {
+        None
+    };
+    let declaration = match (field.kind, field.size) {
+        (FieldKind::Unit, _) => {
+            writeln!(out, "    /* {}: () takes no space */", field.name).unwrap();
+            return false;
+        }
+        (FieldKind::Nested(nested), Some(0)) => {
+            // There is no struct for layouts without fields, see write_layout
+            writeln!(
+                out,
+                "    /* {}: {} takes no space */",
+                field.name, nested.name
+            )
+            .unwrap();
+            return false;
+        }
+        (FieldKind::Nested(nested), _) => format!("struct {} {}", nested.name, field.name),
+        (FieldKind::LengthPrefixed { prefix_size }, _) => {
+            // The length prefix is stored before the data, so it becomes its own member
+            writeln!(out, "    uint{}_t {}_len;", prefix_size * 8, field.name).unwrap();
+            comment = Some(field.type_name);
+            format!("uint8_t {}[]", field.name)
+        }
+        (FieldKind::Bcd { .. } | FieldKind::BFloat16, Some(size)) => {
+            comment = Some(field.type_name);
+            format!("uint8_t {}[{}]", field.name, size)
+        }
+        (FieldKind::Varint(_), _) => {
+            comment = Some(field.type_name);
+            format!("uint8_t {}[]", field.name)
+        }
+        (FieldKind::Magic { .. }, Some(size)) => {
+            comment = Some(field.type_name);
+            match number_type(FieldKind::UnsignedInteger, size) {
+                Some(c_type) => format!("{} {}", c_type, field.name),
+                None => format!("uint8_t {}[{}]", field.name, size),
+            }
+        }
+        (FieldKind::UnsignedInteger | FieldKind::SignedInteger | FieldKind::Float, Some(size)) => {
+            match number_type(field.kind, size) {
+                Some(c_type) => format!("{} {}", c_type, field.name),
+                None => {
+                    comment = Some(field.type_name);
+                    format!("uint8_t {}[{}]", field.name, size)
+                }
+            }
+        }
+        (
+            FieldKind::Array {
+                element: FieldKind::Nested(nested),
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => format!(
+            "struct {} {}[{}]",
+            nested.name,
+            field.name,
+            array_len(size, element_size)
+        ),
+        (
+            FieldKind::Array {
+                element,
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => match number_type(*element, element_size) {
+            Some(c_type) => format!(
+                "{} {}[{}]",
+                c_type,
+                field.name,
+                array_len(size, element_size)
+            ),
+            None => {
+                comment = Some(field.type_name);
+                format!(
+                    "uint8_t {}[{}]",
+                    field.name,
+                    size.map_or(String::new(), |size| size.to_string())
+                )
+            }
+        },
+        (_, Some(size)) => format!("uint8_t {}[{}]", field.name, size),
+        (_, None) => format!("uint8_t {}[]", field.name),
+    };
+    match comment {
+        Some(comment) => writeln!(out, "    {}; /* {} */", declaration, comment).unwrap(),
+        None => writeln!(out, "    {};", declaration).unwrap(),
+    }
+    // Length prefixed fields always have the member for the length prefix
+    !declaration.ends_with("[]") || matches!(field.kind, FieldKind::LengthPrefixed { .. })
+}
+
+/// Writes a member for bytes that aren't part of any field
+fn write_reserved(out: &mut String, offset: usize, size: usize) {
+    writeln!(out, "    uint8_t reserved_{:#x}[{}];", offset, size).unwrap();
+}
+
+/// Returns the number of elements of an array, or an empty string for open ended arrays, which become flexible array members
+fn array_len(size: Option<usize>, element_size: usize) -> String {
+    size.map_or(String::new(), |size| (size / element_size).to_string())
+}
+
+/// Returns the C type for a number of the given kind and size, or `None` if standard C doesn't have one
+fn number_type(kind: FieldKind, size: usize) -> Option<String> {
+    match (kind, size) {
+        (FieldKind::UnsignedInteger, 1 | 2 | 4 | 8) => Some(format!("uint{}_t", size * 8)),
+        (FieldKind::SignedInteger, 1 | 2 | 4 | 8) => Some(format!("int{}_t", size * 8)),
+        (FieldKind::Float, 4) => Some("float".to_string()),
+        (FieldKind::Float, 8) => Some("double".to_string()),
+        _ => None,
+    }
+}
+
+fn write_bit_field_group(out: &mut String, fields: &[FieldInfo]) {
+    let first = &fields[0];
+    let end = fields
+        .iter()
+        .map(|field| field.offset + field.size.unwrap())
+        .max()
+        .unwrap();
+    let names: Vec<&str> = fields.iter().map(|field| field.name).collect();
+    let types: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.type_name))
+        .collect();
+    writeln!(
+        out,
+        "    uint8_t {}[{}]; /* {} */",
+        names.join("_"),
+        end - first.offset,
+        types.join(", ")
+    )
+    .unwrap();
+}
+
+fn endianness(endianness: EndianKind) -> &'static str {
+    match endianness {
+        EndianKind::Big => "big endian",
+        EndianKind::Little => "little endian",
+        EndianKind::Native => "native endian (same as the platform)",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::{Leb128, LengthPrefixed, Magic};
+
+    #[test]
+    fn empty_layout() {
+        binary_layout!(empty, LittleEndian, {});
+        assert_eq!(
+            r#"/* Generated by binary-layout. Do not edit. */
+#ifndef EMPTY_H
+#define EMPTY_H
+
+#include <stdint.h>
+
+/* empty: all fields are little endian */
+#define EMPTY_SIZE 0
+
+/* struct empty is omitted since it has no members with a fixed size, which C requires */
+
+#endif /* EMPTY_H */
+"#,
+            to_c_header(&empty::LAYOUT)
+        );
+    }
+
+    binary_layout!(no_fields, LittleEndian, {});
+    binary_layout!(container, LittleEndian, {
+        nothing: no_fields::NestedView,
+        id: u8,
+    });
+
+    #[test]
+    fn only_open_ended_fields() {
+        binary_layout!(tail_only, LittleEndian, {
+            nothing: (),
+            tail: [u8],
+        });
+        assert!(to_c_header(&tail_only::LAYOUT).contains(
+            r#"/* tail_only: all fields are little endian */
+#define TAIL_ONLY_NOTHING_OFFSET 0
+#define TAIL_ONLY_NOTHING_SIZE 0
+#define TAIL_ONLY_TAIL_OFFSET 0
+
+/* struct tail_only is omitted since it has no members with a fixed size, which C requires */
+
+#endif"#
+        ));
+
+        binary_layout!(prefixed_only, LittleEndian, {
+            payload: LengthPrefixed<u8>,
+        });
+        assert!(to_c_header(&prefixed_only::LAYOUT).contains(
+            r#"struct prefixed_only {
+    uint8_t payload_len;
+    uint8_t payload[]; /* LengthPrefixed<u8> */
+};
+"#
+        ));
+
+        assert!(to_c_header(&container::LAYOUT).contains(
+            r#"struct container {
+    /* nothing: no_fields takes no space */
+    uint8_t id;
+};
+"#
+        ));
+    }
+
+    #[test]
+    fn pinned_fields() {
+        binary_layout!(regs, LittleEndian, {
+            id: u32,
+            control @ 0x10: u32,
+            flags @ 0x18: u8 bits(4),
+            mode: u8 bits(4),
+        });
+        let header = to_c_header(&regs::LAYOUT);
+        assert!(header.contains("#define REGS_SIZE 25\n"));
+        assert!(header.contains("#define REGS_CONTROL_OFFSET 16\n"));
+        assert!(header.contains(
+            r#"struct regs {
+    uint32_t id;
+    uint8_t reserved_0x4[12];
+    uint32_t control;
+    uint8_t reserved_0x14[4];
+    uint8_t flags_mode[1]; /* flags: u8 bits(4), mode: u8 bits(4) */
+};
+"#
+        ));
+
+        binary_layout!(late_tail, LittleEndian, {
+            tail @ 4: [u8],
+        });
+        assert!(to_c_header(&late_tail::LAYOUT).contains(
+            r#"struct late_tail {
+    uint8_t reserved_0x0[4];
+    uint8_t tail[];
+};
+"#
+        ));
+    }
+
+    #[test]
+    fn aligned_layouts() {
+        binary_layout!(entry, LittleEndian, aligned, {
+            kind: u8,
+            id: u32,
+            flags: u16,
+        });
+        let header = to_c_header(&entry::LAYOUT);
+        assert!(header.contains("#define ENTRY_SIZE 12\n"));
+        assert!(header.contains("#define ENTRY_ID_OFFSET 4\n"));
+        assert!(header.contains(
+            r#"struct entry {
+    uint8_t kind;
+    uint8_t reserved_0x1[3];
+    uint32_t id;
+    uint16_t flags;
+    uint8_t reserved_0xa[2];
+};
+"#
+        ));
+    }
+
+    #[test]
+    fn padded_layouts() {
+        binary_layout!(sector, LittleEndian, pad_to = 16, {
+            id: u32,
+            flags: u16,
+        });
+        let header = to_c_header(&sector::LAYOUT);
+        assert!(header.contains("#define SECTOR_SIZE 16\n"));
+        assert!(header.contains(
+            r#"struct sector {
+    uint32_t id;
+    uint16_t flags;
+    uint8_t reserved_0x6[10];
+};
+"#
+        ));
+
+        binary_layout!(blank, LittleEndian, pad_to = 4, {});
+        assert!(to_c_header(&blank::LAYOUT).contains(
+            r#"struct blank {
+    uint8_t reserved_0x0[4];
+};
+"#
+        ));
+    }
+
+    #[test]
+    fn primitive_types() {
+        binary_layout!(my_layout, NativeEndian, {
+            field1: i8,
+            field2: u32,
+            field3: i64,
+            field4: f32,
+            field5: f64,
+            field6: u128,
+            field7: core::num::NonZeroU16,
+            field8: (),
+            field9: [u8; 3],
+        });
+        let header = to_c_header(&my_layout::LAYOUT);
+        assert!(header.contains(
+            r#"/* my_layout: all fields are native endian (same as the platform) */
+#define MY_LAYOUT_SIZE 46
+#define MY_LAYOUT_FIELD1_OFFSET 0
+#define MY_LAYOUT_FIELD1_SIZE 1
+"#
+        ));
+        assert!(header.contains(
+            r#"struct my_layout {
+    int8_t field1;
+    uint32_t field2;
+    int64_t field3;
+    float field4;
+    double field5;
+    uint8_t field6[16]; /* u128 */
+    uint16_t field7;
+    /* field8: () takes no space */
+    uint8_t field9[3];
+};
+"#
+        ));
+    }
+
+    #[test]
+    fn arrays() {
+        binary_layout!(my_layout, LittleEndian, {
+            samples: [u16; 8],
+            matrix: [f32; 16],
+            big: [i128; 2],
+        });
+        assert!(to_c_header(&my_layout::LAYOUT).contains(
+            r#"struct my_layout {
+    uint16_t samples[8];
+    float matrix[16];
+    uint8_t big[32]; /* [i128; 2] */
+};
+"#
+        ));
+    }
+
+    binary_layout!(record, LittleEndian, {
+        id: u16,
+    });
+    binary_layout!(table, LittleEndian, {
+        count: u8,
+        records: [record::NestedView],
+    });
+
+    #[test]
+    fn open_ended_arrays() {
+        binary_layout!(numbers, LittleEndian, {
+            count: u8,
+            values: [u32],
+        });
+        assert!(to_c_header(&numbers::LAYOUT).contains(
+            r#"struct numbers {
+    uint8_t count;
+    uint32_t values[];
+};
+"#
+        ));
+        let header = to_c_header(&table::LAYOUT);
+        assert!(header.contains("struct record {\n    uint16_t id;\n};\n"));
+        assert!(header.contains(
+            r#"struct table {
+    uint8_t count;
+    struct record records[];
+};
+"#
+        ));
+    }
+
+    binary_layout!(message, LittleEndian, {
+        kind: u8,
+        payload: LengthPrefixed<u16>,
+    });
+
+    #[test]
+    fn length_prefixed() {
+        assert!(to_c_header(&message::LAYOUT).contains(
+            r#"struct message {
+    uint8_t kind;
+    uint16_t payload_len;
+    uint8_t payload[]; /* LengthPrefixed<u16> */
+};
+"#
+        ));
+    }
+
+    binary_layout!(named, LittleEndian, {
+        kind: u8,
+        name: LengthPrefixed<u8>,
+        delta: crate::ZigZag<i32>,
+        crc: u32,
+    });
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        let header = to_c_header(&named::LAYOUT);
+        assert!(header.contains(
+            r#"#define NAMED_NAME_OFFSET 1
+#define NAMED_DELTA_OFFSET 2 /* if the fields before have their minimal size */
+#define NAMED_CRC_OFFSET 3 /* if the fields before have their minimal size */
+#define NAMED_CRC_SIZE 4
+"#
+        ));
+        assert!(header.contains(
+            r#"struct named {
+    uint8_t kind;
+    uint8_t name_len;
+    uint8_t name[]; /* LengthPrefixed<u8> */
+    /* followed by delta, crc, which don't have a fixed offset */
+};
+"#
+        ));
+    }
+
+    binary_layout!(tag, LittleEndian, {
+        kind: u8,
+        value: Leb128<u32>,
+    });
+
+    #[test]
+    fn varint() {
+        assert!(to_c_header(&tag::LAYOUT).contains(
+            r#"struct tag {
+    uint8_t kind;
+    uint8_t value[]; /* Leb128<u32> */
+};
+"#
+        ));
+    }
+
+    binary_layout!(header, BigEndian, {
+        magic: Magic<u32, 0x6d795f66>,
+    });
+
+    #[test]
+    fn magic() {
+        assert!(to_c_header(&header::LAYOUT).contains(
+            r#"struct header {
+    uint32_t magic; /* Magic<u32, 0x6d795f66> */
+};
+"#
+        ));
+    }
+
+    #[test]
+    fn bit_fields() {
+        binary_layout!(my_layout, BigEndian, {
+            version: u8 bits(4),
+            ihl: u8 bits(4),
+            length: u16,
+            flags: u8 bits(3),
+            fragment_offset: u16 bits(13),
+        });
+        let header = to_c_header(&my_layout::LAYOUT);
+        assert!(header.contains(
+            r#"#define MY_LAYOUT_IHL_OFFSET 0
+#define MY_LAYOUT_IHL_SIZE 1
+#define MY_LAYOUT_IHL_BIT_OFFSET 4
+#define MY_LAYOUT_IHL_BITS 4
+"#
+        ));
+        assert!(header.contains(
+            r#"#define MY_LAYOUT_FRAGMENT_OFFSET_OFFSET 3
+#define MY_LAYOUT_FRAGMENT_OFFSET_SIZE 2
+#define MY_LAYOUT_FRAGMENT_OFFSET_BIT_OFFSET 3
+#define MY_LAYOUT_FRAGMENT_OFFSET_BITS 13
+"#
+        ));
+        assert!(header.contains(
+            r#"struct my_layout {
+    uint8_t version_ihl[1]; /* version: u8 bits(4), ihl: u8 bits(4) */
+    uint16_t length;
+    uint8_t flags_fragment_offset[2]; /* flags: u8 bits(3), fragment_offset: u16 bits(13) */
+};
+"#
+        ));
+    }
+
+    mod nested {
+        use crate::prelude::*;
+
+        binary_layout!(deep, LittleEndian, {
+            value: u16,
+        });
+        binary_layout!(header, BigEndian, {
+            deep1: deep::NestedView,
+            deep2: deep::NestedView,
+        });
+        binary_layout!(packet, BigEndian, {
+            other: deep::NestedView,
+            head: header::NestedView,
+            payload: [u8],
+        });
+    }
+
+    #[test]
+    fn nested_layouts() {
+        assert_eq!(
+            r#"/* Generated by binary-layout. Do not edit. */
+#ifndef PACKET_H
+#define PACKET_H
+
+#include <stdint.h>
+
+/* deep: all fields are little endian */
+#define DEEP_SIZE 2
+#define DEEP_VALUE_OFFSET 0
+#define DEEP_VALUE_SIZE 2
+
+#pragma pack(push, 1)
+struct deep {
+    uint16_t value;
+};
+#pragma pack(pop)
+
+/* header: all fields are big endian */
+#define HEADER_SIZE 4
+#define HEADER_DEEP1_OFFSET 0
+#define HEADER_DEEP1_SIZE 2
+#define HEADER_DEEP2_OFFSET 2
+#define HEADER_DEEP2_SIZE 2
+
+#pragma pack(push, 1)
+struct header {
+    struct deep deep1;
+    struct deep deep2;
+};
+#pragma pack(pop)
+
+/* packet: all fields are big endian */
+#define PACKET_OTHER_OFFSET 0
+#define PACKET_OTHER_SIZE 2
+#define PACKET_HEAD_OFFSET 2
+#define PACKET_HEAD_SIZE 4
+#define PACKET_PAYLOAD_OFFSET 6
+
+#pragma pack(push, 1)
+struct packet {
+    struct deep other;
+    struct header head;
+    uint8_t payload[];
+};
+#pragma pack(pop)
+
+#endif /* PACKET_H */
+"#,
+            to_c_header(&nested::packet::LAYOUT)
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! Exports debugger visualizations for the [View](crate::binary_layout!) types of layouts,
+//! so debuggers show the decoded field values of a view instead of its raw storage.
+//!
+//! - [to_gdb_pretty_printer] generates a Python pretty-printer for GDB (and `rust-gdb`).
+//!   It supports views over byte slices, byte arrays, `Vec<u8>` and nested views.
+//! - [to_natvis] generates a `.natvis` file for the Visual Studio debugger and other debuggers supporting natvis.
+//!   It supports views over byte slices, i.e. `View<&[u8]>` and `View<&mut [u8]>`.
+//!   Natvis expressions can't follow the length of [LengthPrefixed](crate::LengthPrefixed) fields or varints,
+//!   so the fields after them don't have a fixed offset and are only mentioned in a comment.
+//!
+//! Both need to know the full path of the module generated by [binary_layout!](crate::binary_layout!),
+//! e.g. `my_crate::packets::icmp_packet`, so they can match the view type.
+
+use std::fmt::Write;
+
+use super::{first_relative_field, python};
+use crate::{EndianKind, FieldInfo, FieldKind, LayoutInfo};
+
+/// Return a GDB pretty-printer, written in Python, for views of the given layout.
+/// `module_path` is the full path of the module generated for the layout, e.g. `my_crate::packets::icmp_packet`.
+///
+/// Load the generated script with `source my_layout.py` in GDB or add that command to your `.gdbinit`.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::debugger, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   tail: [u8],
+/// });
+///
+/// let script = debugger::to_gdb_pretty_printer(&my_layout::LAYOUT, "my_crate::my_layout");
+/// assert!(script.contains(r#"re.compile(r"^my_crate::my_layout::View<.*>$")"#));
+/// ```
+pub fn to_gdb_pretty_printer(layout: &LayoutInfo, module_path: &str) -> String {
+    let mut out = String::new();
+    out.push_str("# Generated by binary-layout. Do not edit.\n");
+    out.push_str("import re\n");
+    out.push_str("import struct\n");
+    out.push_str("import sys\n");
+    out.push('\n');
+    out.push_str("import gdb\n");
+    python::write_classes(&mut out, layout);
+    out.push_str(GDB_STORAGE_BYTES);
+    writeln!(
+        out,
+        r#"
+
+class _{name}_printer:
+    def __init__(self, value):
+        self.value = value
+
+    def to_string(self):
+        return "{name}"
+
+    def children(self):
+        value = {name}.unpack(_storage_bytes(self.value["storage"]))
+        for name in value.FIELDS:
+            field = getattr(value, name)
+            if isinstance(field, (int, float)):
+                yield name, field
+            else:
+                yield name, repr(field)
+
+
+_{name}_type = re.compile(r"^{path}::View<.*>$")
+
+
+def _{name}_lookup(value):
+    if _{name}_type.match(str(value.type.strip_typedefs().unqualified())):
+        return _{name}_printer(value)
+    return None
+
+
+gdb.pretty_printers.append(_{name}_lookup)"#,
+        name = layout.name,
+        path = module_path,
+    )
+    .unwrap();
+    out
+}
+
+const GDB_STORAGE_BYTES: &str = r#"
+
+def _storage_bytes(value):
+    """Returns the bytes stored in the storage of a view"""
+    type_ = value.type.strip_typedefs()
+    if type_.code in (gdb.TYPE_CODE_PTR, gdb.TYPE_CODE_REF):
+        return _storage_bytes(value.dereference())
+    if type_.code == gdb.TYPE_CODE_ARRAY:
+        return _read_memory(value.address, type_.sizeof)
+    fields = [field.name for field in type_.fields()]
+    if "data_ptr" in fields and "length" in fields:
+        # slice
+        return _read_memory(value["data_ptr"], value["length"])
+    if "storage" in fields and "region" in fields:
+        # binary_layout::Data, used by nested views
+        data = _storage_bytes(value["storage"])
+        return data[int(value["region"]["start"]):int(value["region"]["end"])]
+    if "buf" in fields and "len" in fields:
+        # Vec<u8>, the data pointer is the first pointer nested in its buffer
+        pointer = value["buf"]
+        while pointer.type.strip_typedefs().code != gdb.TYPE_CODE_PTR:
+            pointer = pointer[pointer.type.strip_typedefs().fields()[0]]
+        return _read_memory(pointer, value["len"])
+    raise gdb.GdbError("Unsupported storage type " + str(type_))
+
+
+def _read_memory(pointer, length):
+    return bytes(gdb.selected_inferior().read_memory(int(pointer), int(length)))"#;
+
+/// Return a natvis file for views of the given layout.
+/// `module_path` is the full path of the module generated for the layout, e.g. `my_crate::packets::icmp_packet`.
+///
+/// Add the generated file to your debugger configuration or embed it into your binary
+/// using the `#[debugger_visualizer(natvis_file = "...")]` attribute.
+/// Fields of nested layouts are shown inline with their full path, e.g. `header.field1`.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::debugger, prelude::*};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   field1: u16,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"<?xml version="1.0" encoding="utf-8"?>
+/// <!-- Generated by binary-layout. Do not edit. -->
+/// <AutoVisualizer xmlns="http://schemas.microsoft.com/vstudio/debugger/natvis/2010">
+///   <Type Name="my_crate::my_layout::View&lt;*&gt;">
+///     <DisplayString>my_layout</DisplayString>
+///     <Expand>
+///       <Item Name="field1">*(unsigned short*)(storage.data_ptr + 0)</Item>
+///       <Synthetic Name="tail">
+///         <Expand>
+///           <ArrayItems>
+///             <Size>storage.length - 2</Size>
+///             <ValuePointer>storage.data_ptr + 2</ValuePointer>
+///           </ArrayItems>
+///         </Expand>
+///       </Synthetic>
+///     </Expand>
+///   </Type>
+/// </AutoVisualizer>
+/// "#,
+///     debugger::to_natvis(&my_layout::LAYOUT, "my_crate::my_layout"),
+/// );
+/// ```
+pub fn to_natvis(layout: &LayoutInfo, module_path: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<!-- Generated by binary-layout. Do not edit. -->\n");
+    out.push_str(
+        "<AutoVisualizer xmlns=\"http://schemas.microsoft.com/vstudio/debugger/natvis/2010\">\n",
+    );
+    writeln!(out, "  <Type Name=\"{}::View&lt;*&gt;\">", module_path).unwrap();
+    writeln!(out, "    <DisplayString>{}</DisplayString>", layout.name).unwrap();
+    out.push_str("    <Expand>\n");
+    write_natvis_items(&mut out, layout, "", 0);
+    out.push_str("    </Expand>\n");
+    out.push_str("  </Type>\n");
+    out.push_str("</AutoVisualizer>\n");
+    out
+}
+
+fn write_natvis_items(out: &mut String, layout: &LayoutInfo, prefix: &str, offset: usize) {
+    let first_relative = first_relative_field(layout);
+    let (fields, relative) = layout.fields.split_at(first_relative);
+    for field in fields {
+        let name = format!("{}{}", prefix, field.name);
+        let offset = offset + field.offset;
+        if let FieldKind::Nested(nested) = field.kind {
+            write_natvis_items(out, nested, &format!("{}.", name), offset);
+            continue;
+        }
+        match natvis_expression(field, layout.endianness, offset) {
+            Some(expression) => {
+                writeln!(out, "      <Item Name=\"{}\">{}</Item>", name, expression).unwrap()
+            }
+            None if field.kind == FieldKind::Unit => {}
+            None => {
+                let size = match field.size {
+                    Some(size) => size.to_string(),
+                    None => format!("storage.length - {}", offset),
+                };
+                writeln!(out, "      <Synthetic Name=\"{}\">", name).unwrap();
+                out.push_str("        <Expand>\n");
+                out.push_str("          <ArrayItems>\n");
+                writeln!(out, "            <Size>{}</Size>", size).unwrap();
+                writeln!(
+                    out,
+                    "            <ValuePointer>storage.data_ptr + {}</ValuePointer>",
+                    offset
+                )
+                .unwrap();
+                out.push_str("          </ArrayItems>\n");
+                out.push_str("        </Expand>\n");
+                out.push_str("      </Synthetic>\n");
+            }
+        }
+    }
+    if !relative.is_empty() {
+        let names: Vec<String> = relative
+            .iter()
+            .map(|field| format!("{}{}", prefix, field.name))
+            .collect();
+        writeln!(
+            out,
+            "      <!-- followed by {}, which don't have a fixed offset -->",
+            names.join(", ")
+        )
+        .unwrap();
+    }
+}
+
+/// Returns a natvis expression reading the field as a number,
+/// or `None` if the field needs to be shown as a byte array.
+fn natvis_expression(field: &FieldInfo, endianness: EndianKind, offset: usize) -> Option<String> {
+    let (c_type, unsigned_c_type) = match (field.kind, field.size) {
+        (FieldKind::UnsignedInteger, Some(1)) => ("unsigned char", "unsigned char"),
+        (FieldKind::UnsignedInteger, Some(2)) => ("unsigned short", "unsigned short"),
+        (FieldKind::UnsignedInteger, Some(4)) => ("unsigned int", "unsigned int"),
+        (FieldKind::UnsignedInteger, Some(8)) => ("unsigned __int64", "unsigned __int64"),
+        (FieldKind::SignedInteger, Some(1)) => ("signed char", "unsigned char"),
+        (FieldKind::SignedInteger, Some(2)) => ("short", "unsigned short"),
+        (FieldKind::SignedInteger, Some(4)) => ("int", "unsigned int"),
+        (FieldKind::SignedInteger, Some(8)) => ("__int64", "unsigned __int64"),
+        // Debuggers using natvis run on little endian platforms, so big endian floats can't be reinterpreted
+        (FieldKind::Float, Some(4)) if endianness != EndianKind::Big => ("float", "float"),
+        (FieldKind::Float, Some(8)) if endianness != EndianKind::Big => ("double", "double"),
+        _ => return None,
+    };
+    let size = field.size.unwrap();
+    if endianness != EndianKind::Big || size == 1 {
+        return Some(format!("*({}*)(storage.data_ptr + {})", c_type, offset));
+    }
+    let bytes: Vec<String> = (0..size)
+        .map(|index| {
+            format!(
+                "(({})storage.data_ptr[{}] &lt;&lt; {})",
+                unsigned_c_type,
+                offset + index,
+                (size - 1 - index) * 8
+            )
+        })
+        .collect();
+    Some(format!("({})({})", c_type, bytes.join(" | ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    mod nested {
+        use crate::prelude::*;
+
+        binary_layout!(deep, LittleEndian, {
+            value: u16,
+        });
+        binary_layout!(packet, BigEndian, {
+            field1: i16,
+            field2: f32,
+            field3: u128,
+            unit: (),
+            other: deep::NestedView,
+            payload: [u8; 2],
+        });
+    }
+
+    #[test]
+    fn natvis() {
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- Generated by binary-layout. Do not edit. -->
+<AutoVisualizer xmlns="http://schemas.microsoft.com/vstudio/debugger/natvis/2010">
+  <Type Name="my_crate::packet::View&lt;*&gt;">
+    <DisplayString>packet</DisplayString>
+    <Expand>
+      <Item Name="field1">(short)(((unsigned short)storage.data_ptr[0] &lt;&lt; 8) | ((unsigned short)storage.data_ptr[1] &lt;&lt; 0))</Item>
+      <Synthetic Name="field2">
+        <Expand>
+          <ArrayItems>
+            <Size>4</Size>
+            <ValuePointer>storage.data_ptr + 2</ValuePointer>
+          </ArrayItems>
+        </Expand>
+      </Synthetic>
+      <Synthetic Name="field3">
+        <Expand>
+          <ArrayItems>
+            <Size>16</Size>
+            <ValuePointer>storage.data_ptr + 6</ValuePointer>
+          </ArrayItems>
+        </Expand>
+      </Synthetic>
+      <Item Name="other.value">*(unsigned short*)(storage.data_ptr + 22)</Item>
+      <Synthetic Name="payload">
+        <Expand>
+          <ArrayItems>
+            <Size>2</Size>
+            <ValuePointer>storage.data_ptr + 24</ValuePointer>
+          </ArrayItems>
+        </Expand>
+      </Synthetic>
+    </Expand>
+  </Type>
+</AutoVisualizer>
+"#,
+            to_natvis(&nested::packet::LAYOUT, "my_crate::packet")
+        );
+    }
+
+    #[test]
+    fn natvis_little_endian() {
+        binary_layout!(my_layout, NativeEndian, {
+            field1: i8,
+            field2: u64,
+            field3: f64,
+        });
+        let natvis = to_natvis(&my_layout::LAYOUT, "my_layout");
+        assert!(natvis.contains(
+            r#"      <Item Name="field1">*(signed char*)(storage.data_ptr + 0)</Item>
+      <Item Name="field2">*(unsigned __int64*)(storage.data_ptr + 1)</Item>
+      <Item Name="field3">*(double*)(storage.data_ptr + 9)</Item>
+"#
+        ));
+    }
+
+    #[test]
+    fn natvis_fields_after_length_prefixed_field() {
+        binary_layout!(named, LittleEndian, {
+            kind: u8,
+            name: crate::LengthPrefixed<u16>,
+            crc: u32,
+        });
+        let natvis = to_natvis(&named::LAYOUT, "named");
+        assert!(natvis.contains(
+            r#"            <Size>storage.length - 1</Size>
+            <ValuePointer>storage.data_ptr + 1</ValuePointer>
+          </ArrayItems>
+        </Expand>
+      </Synthetic>
+      <!-- followed by crc, which don't have a fixed offset -->
+    </Expand>
+"#
+        ));
+        assert!(!natvis.contains("<Item Name=\"crc\">"));
+    }
+
+    #[test]
+    fn gdb_pretty_printer() {
+        let script = to_gdb_pretty_printer(&nested::packet::LAYOUT, "my_crate::packet");
+        assert!(script.starts_with("# Generated by binary-layout. Do not edit.\nimport re\n"));
+        assert!(script.contains("\nclass deep:\n"));
+        assert!(script.contains("\nclass packet:\n"));
+        assert!(script.contains("\ndef _storage_bytes(value):\n"));
+        assert!(script
+            .contains("        value = packet.unpack(_storage_bytes(self.value[\"storage\"]))\n"));
+        assert!(script.contains("_packet_type = re.compile(r\"^my_crate::packet::View<.*>$\")\n"));
+        assert!(script.ends_with("gdb.pretty_printers.append(_packet_lookup)\n"));
+    }
+}
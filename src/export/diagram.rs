@@ -0,0 +1,537 @@
+//! Exports layouts as packet diagrams in the style used by RFCs, with 32 bits per row.
+//!
+//! - [to_ascii_diagram] generates the ASCII art diagram known from RFCs.
+//! - [to_svg_diagram] generates the same diagram as an SVG image.
+//!
+//! Fields spanning multiple rows are drawn as one box reaching over those rows. If a field wraps around
+//! to the next row without the parts lining up, the part in the next row is labeled with `(cont.)`.
+//! Open ended fields like `[u8]` fill the rest of their row and are labeled with a trailing `...`.
+//! [LengthPrefixed](crate::LengthPrefixed) fields and varints like [Leb128](crate::Leb128) that are followed by other fields
+//! are drawn with their minimal size, i.e. the length prefix or a single byte, and labeled with a trailing `...` as well.
+//! Nested layouts are drawn as one field, and `()` fields don't take up any space and are skipped.
+//! Bit fields are drawn with their width in bits, in the order they're declared in.
+//! Gaps between fields, e.g. before fields pinned to an explicit offset or padding inserted by `aligned` layouts,
+//! and the padding at the end of the layout are drawn as boxes labeled `reserved`.
+
+use std::fmt::Write;
+
+use crate::layout_info::has_dynamic_len_kind;
+use crate::macro_binary_layout::next_field_offset;
+use crate::{FieldKind, LayoutInfo};
+
+const BITS_PER_ROW: usize = 32;
+
+/// Return an ASCII art diagram of the given layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::diagram, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   checksum: u32,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#" 0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |            field1             |    field2     |   checksum    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |               checksum (cont.)                |   tail ...    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// "#,
+///     diagram::to_ascii_diagram(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_ascii_diagram(layout: &LayoutInfo) -> String {
+    let rows = rows(layout);
+    let mut out = String::new();
+
+    let mut tens = String::from(" ");
+    for bit in 0..BITS_PER_ROW {
+        if bit % 10 == 0 {
+            write!(tens, "{:<2}", bit / 10).unwrap();
+        } else {
+            tens.push_str("  ");
+        }
+    }
+    writeln!(out, "{}", tens.trim_end()).unwrap();
+    for bit in 0..BITS_PER_ROW {
+        write!(out, " {}", bit % 10).unwrap();
+    }
+    out.push('\n');
+
+    if let Some(first) = rows.first() {
+        write_separator(&mut out, &[], first);
+    }
+    for (index, row) in rows.iter().enumerate() {
+        out.push('|');
+        for segment in row {
+            let width = 2 * (segment.end_bit - segment.start_bit) - 1;
+            let label: String = segment
+                .label
+                .as_deref()
+                .unwrap_or("")
+                .chars()
+                .take(width)
+                .collect();
+            write!(out, "{:^width$}|", label, width = width).unwrap();
+        }
+        out.push('\n');
+        let next = rows.get(index + 1).map(Vec::as_slice).unwrap_or(&[]);
+        write_separator(&mut out, row, next);
+    }
+    out
+}
+
+/// Return an SVG image showing the diagram of the given layout.
+/// The byte offset of each row is shown to its left.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::diagram, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   tail: [u8],
+/// });
+///
+/// let svg = diagram::to_svg_diagram(&my_layout::LAYOUT);
+/// assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#));
+/// assert!(svg.contains(r#">field1</text>"#));
+/// ```
+pub fn to_svg_diagram(layout: &LayoutInfo) -> String {
+    const BIT_WIDTH: usize = 16;
+    const ROW_HEIGHT: usize = 32;
+    const HEADER_HEIGHT: usize = 20;
+    const OFFSET_WIDTH: usize = 40;
+
+    let rows = rows(layout);
+    let width = OFFSET_WIDTH + BITS_PER_ROW * BIT_WIDTH + 1;
+    let height = HEADER_HEIGHT + rows.len() * ROW_HEIGHT + 1;
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="12">"#,
+        width, height
+    )
+    .unwrap();
+    writeln!(out, "  <title>{}</title>", layout.name).unwrap();
+    for bit in (0..BITS_PER_ROW).step_by(8) {
+        writeln!(
+            out,
+            r#"  <text x="{}" y="{}">{}</text>"#,
+            OFFSET_WIDTH + bit * BIT_WIDTH + 2,
+            HEADER_HEIGHT - 6,
+            bit
+        )
+        .unwrap();
+    }
+    for (index, row) in rows.iter().enumerate() {
+        let y = HEADER_HEIGHT + index * ROW_HEIGHT;
+        writeln!(
+            out,
+            r#"  <text x="{}" y="{}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+            OFFSET_WIDTH - 6,
+            y + ROW_HEIGHT / 2,
+            index * BITS_PER_ROW / 8
+        )
+        .unwrap();
+        for segment in row {
+            let x = OFFSET_WIDTH + segment.start_bit * BIT_WIDTH;
+            let segment_width = (segment.end_bit - segment.start_bit) * BIT_WIDTH;
+            writeln!(
+                out,
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+                x, y, segment_width, ROW_HEIGHT
+            )
+            .unwrap();
+            if let Some(label) = &segment.label {
+                writeln!(
+                    out,
+                    r#"  <text x="{}" y="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+                    x + segment_width / 2,
+                    y + ROW_HEIGHT / 2,
+                    label
+                )
+                .unwrap();
+            }
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// The part of a box that falls into one row of the diagram.
+struct Segment {
+    /// Index of the box, i.e. field or gap, the segment belongs to
+    region_index: usize,
+    /// First bit of the segment within its row
+    start_bit: usize,
+    /// Bit after the end of the segment within its row
+    end_bit: usize,
+    /// Segments continuing the box of the segment above them aren't labeled
+    label: Option<String>,
+}
+
+/// A field or a gap between fields, spanning the bits from `start` to `end`
+struct Region {
+    start: usize,
+    end: usize,
+    label: String,
+    /// Name used for the parts of the box that need their own label
+    name: &'static str,
+}
+
+/// Returns the boxes to draw for the fields of the layout, with boxes for the gaps between them.
+fn regions(layout: &LayoutInfo) -> Vec<Region> {
+    let mut regions: Vec<Region> = Vec::new();
+    let mut end = 0;
+    for (index, field) in layout.fields.iter().enumerate() {
+        let start = field.offset * 8;
+        let followed = index + 1 < layout.fields.len();
+        let (start, field_end, label) = match (field.kind, field.size) {
+            (FieldKind::Bits { bit_offset, bits }, _) => (
+                start + bit_offset,
+                start + bit_offset + bits,
+                field.name.to_string(),
+            ),
+            (_, Some(size)) => (start, start + size * 8, field.name.to_string()),
+            // Fields after a length prefixed field or varint are drawn at the offsets they have if it has its minimal size
+            (kind, None) if followed && has_dynamic_len_kind(kind) => {
+                let end = next_field_offset(field.offset, None, kind).unwrap_or(field.offset);
+                (start, end * 8, format!("{} ...", field.name))
+            }
+            (_, None) => (
+                start,
+                (start / BITS_PER_ROW + 1) * BITS_PER_ROW,
+                format!("{} ...", field.name),
+            ),
+        };
+        if start > end {
+            regions.push(Region {
+                start: end,
+                end: start,
+                label: "reserved".to_string(),
+                name: "reserved",
+            });
+        }
+        end = end.max(field_end);
+        regions.push(Region {
+            start,
+            end: field_end,
+            label,
+            name: field.name,
+        });
+    }
+    if let Some(size) = layout.size {
+        if size * 8 > end {
+            regions.push(Region {
+                start: end,
+                end: size * 8,
+                label: "reserved".to_string(),
+                name: "reserved",
+            });
+        }
+    }
+    regions
+}
+
+/// Splits the boxes of the layout into rows of [BITS_PER_ROW] bits each.
+fn rows(layout: &LayoutInfo) -> Vec<Vec<Segment>> {
+    let mut rows: Vec<Vec<Segment>> = Vec::new();
+    for (
+        region_index,
+        Region {
+            start,
+            end,
+            label,
+            name,
+        },
+    ) in regions(layout).into_iter().enumerate()
+    {
+        let mut label = Some(label);
+        let mut previous: Option<(usize, usize)> = None;
+        let mut pos = start;
+        while pos < end {
+            let row = pos / BITS_PER_ROW;
+            let row_start = row * BITS_PER_ROW;
+            let row_end = end.min(row_start + BITS_PER_ROW);
+            let (start_bit, end_bit) = (pos - row_start, row_end - row_start);
+            if let Some((previous_start, previous_end)) = previous {
+                if previous_end <= start_bit || end_bit <= previous_start {
+                    // The segment isn't drawn as part of the box above it, so it needs its own label
+                    label = Some(format!("{} (cont.)", name));
+                }
+            }
+            if rows.len() <= row {
+                rows.resize_with(row + 1, Vec::new);
+            }
+            rows[row].push(Segment {
+                region_index,
+                start_bit,
+                end_bit,
+                label: label.take(),
+            });
+            previous = Some((start_bit, end_bit));
+            pos = row_end;
+        }
+    }
+    rows
+}
+
+/// Writes the line between two rows. Fields continuing from the row above into the row below aren't separated.
+fn write_separator(out: &mut String, above: &[Segment], below: &[Segment]) {
+    let bits = row_bits(above).max(row_bits(below));
+    let continues = |bit: usize| match (field_at(above, bit), field_at(below, bit)) {
+        (Some(above), Some(below)) if above == below => Some(above),
+        _ => None,
+    };
+    out.push('+');
+    for bit in 0..bits {
+        let current = continues(bit);
+        out.push(if current.is_some() { ' ' } else { '-' });
+        let joined = current.is_some() && bit + 1 < bits && continues(bit + 1) == current;
+        out.push(if joined { ' ' } else { '+' });
+    }
+    out.push('\n');
+}
+
+fn row_bits(row: &[Segment]) -> usize {
+    row.last().map(|segment| segment.end_bit).unwrap_or(0)
+}
+
+fn field_at(row: &[Segment], bit: usize) -> Option<usize> {
+    row.iter()
+        .find(|segment| segment.start_bit <= bit && bit < segment.end_bit)
+        .map(|segment| segment.region_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    const HEADER: &str = r#" 0                   1                   2                   3
+ 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+"#;
+
+    #[test]
+    fn empty_layout() {
+        binary_layout!(empty, LittleEndian, {});
+        assert_eq!(HEADER, to_ascii_diagram(&empty::LAYOUT));
+    }
+
+    #[test]
+    fn partial_last_row() {
+        binary_layout!(my_layout, LittleEndian, {
+            field1: u32,
+            field2: u8,
+            field3: (),
+            field4: u8,
+        });
+        assert_eq!(
+            format!(
+                "{}{}",
+                HEADER,
+                r#"+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                            field1                             |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|    field2     |    field4     |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+            ),
+            to_ascii_diagram(&my_layout::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn multi_row_fields() {
+        binary_layout!(my_layout, BigEndian, {
+            address: [u8; 8],
+            field_with_a_long_name: u8,
+            tail: [u8],
+        });
+        assert_eq!(
+            format!(
+                "{}{}",
+                HEADER,
+                r#"+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                            address                            |
++                                                               +
+|                                                               |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|field_with_a_lo|                   tail ...                    |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+            ),
+            to_ascii_diagram(&my_layout::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn pinned_fields() {
+        binary_layout!(regs, LittleEndian, {
+            id: u32,
+            control @ 0x10: u32,
+            status @ 0x16: u16,
+        });
+        assert_eq!(
+            format!(
+                "{}{}",
+                HEADER,
+                r#"+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                              id                               |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                           reserved                            |
++                                                               +
+|                                                               |
++                                                               +
+|                                                               |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                            control                            |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|           reserved            |            status             |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+            ),
+            to_ascii_diagram(&regs::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn aligned_layouts() {
+        binary_layout!(entry, LittleEndian, aligned, {
+            kind: u8,
+            id: u32,
+            flags: u16,
+        });
+        assert_eq!(
+            format!(
+                "{}{}",
+                HEADER,
+                r#"+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|     kind      |                   reserved                    |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                              id                               |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|             flags             |           reserved            |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+            ),
+            to_ascii_diagram(&entry::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn padded_layouts() {
+        binary_layout!(sector, LittleEndian, pad_to = 8, {
+            id: u16,
+        });
+        assert_eq!(
+            format!(
+                "{}{}",
+                HEADER,
+                r#"+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|              id               |           reserved            |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+                               +
+|                                                               |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+            ),
+            to_ascii_diagram(&sector::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn bit_fields() {
+        binary_layout!(ipv4, BigEndian, {
+            version: u8 bits(4),
+            ihl: u8 bits(4),
+            tos: u8,
+            total_length: u16,
+            identification: u16,
+            flags: u8 bits(3),
+            fragment_offset: u16 bits(13),
+        });
+        assert_eq!(
+            format!(
+                "{}{}",
+                HEADER,
+                r#"+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|version|  ihl  |      tos      |         total_length          |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|        identification         |flags|     fragment_offset     |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+            ),
+            to_ascii_diagram(&ipv4::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn open_ended_field_at_row_start() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u32,
+            tail: [u8],
+        });
+        assert!(to_ascii_diagram(&my_layout::LAYOUT).ends_with(
+            r#"|                            field1                             |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                           tail ...                            |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#
+        ));
+    }
+
+    #[test]
+    fn svg() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: [u8; 4],
+        });
+        assert_eq!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="553" height="85" font-family="monospace" font-size="12">
+  <title>my_layout</title>
+  <text x="42" y="14">0</text>
+  <text x="170" y="14">8</text>
+  <text x="298" y="14">16</text>
+  <text x="426" y="14">24</text>
+  <text x="34" y="36" text-anchor="end" dominant-baseline="middle">0</text>
+  <rect x="40" y="20" width="256" height="32" fill="none" stroke="black"/>
+  <text x="168" y="36" text-anchor="middle" dominant-baseline="middle">field1</text>
+  <rect x="296" y="20" width="256" height="32" fill="none" stroke="black"/>
+  <text x="424" y="36" text-anchor="middle" dominant-baseline="middle">field2</text>
+  <text x="34" y="68" text-anchor="end" dominant-baseline="middle">4</text>
+  <rect x="40" y="52" width="256" height="32" fill="none" stroke="black"/>
+  <text x="168" y="68" text-anchor="middle" dominant-baseline="middle">field2 (cont.)</text>
+</svg>
+"#,
+            to_svg_diagram(&my_layout::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        binary_layout!(named, BigEndian, {
+            kind: u8,
+            name: crate::LengthPrefixed<u16>,
+            delta: crate::ZigZag<i32>,
+            crc: u32,
+        });
+        assert_eq!(
+            r#" 0                   1                   2                   3
+ 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|     kind      |           name ...            |   delta ...   |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+|                              crc                              |
++-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+"#,
+            to_ascii_diagram(&named::LAYOUT)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Exports layouts as machine-readable JSON.
+//!
+//! The generated document has the following structure:
+//! ```json
+//! {
+//!   "name": "icmp_packet",
+//!   "endianness": "big",
+//!   "size": null,
+//!   "fields": [
+//!     {"name": "packet_type", "type": "u8", "offset": 0, "size": 1},
+//!     {"name": "data_section", "type": "[u8]", "offset": 8, "size": null}
+//!   ]
+//! }
+//! ```
+//! `endianness` is one of `"big"`, `"little"` or `"native"`. `size` is `null` for open ended fields
+//! and for layouts ending in an open ended field. `type` is the field type as written in the layout definition.
+//! The `offset` of fields after a [LengthPrefixed](crate::LengthPrefixed) field or a varint like [Leb128](crate::Leb128)
+//! assumes that those fields have their minimal size, i.e. an empty payload or a single byte.
+//! In the data, these fields are moved back by the number of bytes those fields take up beyond it.
+
+use std::fmt::Write;
+
+use crate::{EndianKind, LayoutInfo};
+
+/// Return a JSON document describing the given layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::json, prelude::*};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   field1: u16,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"{
+///   "name": "my_layout",
+///   "endianness": "little",
+///   "size": null,
+///   "fields": [
+///     {"name": "field1", "type": "u16", "offset": 0, "size": 2},
+///     {"name": "tail", "type": "[u8]", "offset": 2, "size": null}
+///   ]
+/// }
+/// "#,
+///     json::to_json(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_json(layout: &LayoutInfo) -> String {
+    let mut out = String::new();
+    out.push_str("{\n");
+    writeln!(out, "  \"name\": {},", string(layout.name)).unwrap();
+    writeln!(
+        out,
+        "  \"endianness\": \"{}\",",
+        endianness(layout.endianness)
+    )
+    .unwrap();
+    writeln!(out, "  \"size\": {},", size(layout.size)).unwrap();
+    if layout.fields.is_empty() {
+        out.push_str("  \"fields\": []\n");
+    } else {
+        out.push_str("  \"fields\": [\n");
+        for (index, field) in layout.fields.iter().enumerate() {
+            write!(
+                out,
+                "    {{\"name\": {}, \"type\": {}, \"offset\": {}, \"size\": {}}}",
+                string(field.name),
+                string(field.type_name),
+                field.offset,
+                size(field.size),
+            )
+            .unwrap();
+            if index + 1 < layout.fields.len() {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        out.push_str("  ]\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn endianness(endianness: EndianKind) -> &'static str {
+    match endianness {
+        EndianKind::Big => "big",
+        EndianKind::Little => "little",
+        EndianKind::Native => "native",
+    }
+}
+
+fn size(size: Option<usize>) -> String {
+    match size {
+        Some(size) => size.to_string(),
+        None => "null".to_string(),
+    }
+}
+
+/// Quote and escape a string. The result is also a valid double-quoted YAML string.
+pub(super) fn string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn empty_layout() {
+        binary_layout!(empty, BigEndian, {});
+        assert_eq!(
+            "{\n  \"name\": \"empty\",\n  \"endianness\": \"big\",\n  \"size\": 0,\n  \"fields\": []\n}\n",
+            to_json(&empty::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn sized_layout() {
+        binary_layout!(my_layout, NativeEndian, {
+            field1: u8,
+            field2: char as u32,
+            field3: [u8; 3],
+        });
+        assert_eq!(
+            r#"{
+  "name": "my_layout",
+  "endianness": "native",
+  "size": 8,
+  "fields": [
+    {"name": "field1", "type": "u8", "offset": 0, "size": 1},
+    {"name": "field2", "type": "char as u32", "offset": 1, "size": 4},
+    {"name": "field3", "type": "[u8; 3]", "offset": 5, "size": 3}
+  ]
+}
+"#,
+            to_json(&my_layout::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(r#""a\"b\\c\n\u0001""#, string("a\"b\\c\n\u{1}"));
+    }
+}
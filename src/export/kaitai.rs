@@ -0,0 +1,599 @@
+//! Exports layouts as [Kaitai Struct](https://kaitai.io) definitions.
+//!
+//! The generated `.ksy` files can be loaded into the Kaitai Web IDE or any other Kaitai tooling
+//! to inspect binary data written with your layouts.
+//!
+//! Fields are mapped to Kaitai types as follows:
+//! - Integers and floats map to the Kaitai type of the same size, e.g. `u16` becomes `u2` and `f64` becomes `f8`.
+//!   Kaitai doesn't support 128 bit integers, so these are exported as raw 16 byte fields.
+//! - Arrays of numbers like `[u16; 8]` become repeated fields of the element type, e.g. `type: u2` with `repeat-expr: 8`.
+//!   Open ended arrays like `[u32]` or `[my_layout::NestedView]` are repeated until the end of the stream with `repeat: eos`.
+//! - Byte arrays `[u8; N]` become raw fields with `size: N` and open ended byte slices `[u8]` become raw fields with `size-eos: true`.
+//! - Length prefixed fields like `payload: LengthPrefixed<u16>` become a `payload_len` field of the prefix type followed by a raw field with `size: payload_len`.
+//! - Varints like `Leb128<u32>` or `Vlq<u32>` need Kaitai's `vlq_base128_le` or `vlq_base128_be` library types, so they are exported as raw fields with `size-eos: true`
+//!   and the varint type noted in the field's `doc`. Varints followed by other fields are exported as their bytes instead, i.e. `type: u1` repeated until
+//!   the first byte without the continuation bit.
+//! - Fields after length prefixed fields and varints follow them in the `seq`, so they are read from their actual offset.
+//! - Bit fields become Kaitai bit-sized integers, e.g. `u8 bits(4)` becomes `b4` in big endian layouts and `b4le` in little endian layouts.
+//! - Nested layouts are exported as user types in the `types` section of the generated file.
+//! - Fields using a custom type via [LayoutAs](crate::LayoutAs) are exported as their underlying type,
+//!   with the custom type noted in the field's `doc`.
+//! - Gaps between fields, e.g. before fields pinned to an explicit offset or padding inserted by `aligned` layouts,
+//!   and the padding at the end of the layout become raw `reserved_<offset>` fields, so each field is read from its offset
+//!   and the size of the type is the size of the layout.
+//!
+//! Kaitai doesn't have a native endianness, so layouts using [NativeEndian](crate::NativeEndian)
+//! are exported with the endianness of the platform running the export.
+
+use std::fmt::Write;
+
+use super::{collect_nested_layouts, json::string};
+use crate::macro_binary_layout::next_field_offset;
+use crate::{EndianKind, FieldInfo, FieldKind, LayoutInfo};
+
+/// Return a Kaitai Struct definition (`.ksy` file content) for the given layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::kaitai, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"meta:
+///   id: my_layout
+///   endian: be
+/// seq:
+///   - id: field1
+///     type: u2
+///   - id: field2
+///     type: u1
+///     doc: "bool as u8"
+///   - id: tail
+///     size-eos: true
+/// "#,
+///     kaitai::to_ksy(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_ksy(layout: &LayoutInfo) -> String {
+    let mut out = String::new();
+    out.push_str("meta:\n");
+    writeln!(out, "  id: {}", layout.name).unwrap();
+    writeln!(out, "  endian: {}", endianness(layout.endianness)).unwrap();
+    write_seq(&mut out, layout, "");
+
+    let mut nested_layouts = Vec::new();
+    collect_nested_layouts(layout, &mut nested_layouts);
+    if !nested_layouts.is_empty() {
+        out.push_str("types:\n");
+        for nested in nested_layouts {
+            writeln!(out, "  {}:", nested.name).unwrap();
+            out.push_str("    meta:\n");
+            writeln!(out, "      endian: {}", endianness(nested.endianness)).unwrap();
+            write_seq(&mut out, nested, "    ");
+        }
+    }
+    out
+}
+
+fn write_seq(out: &mut String, layout: &LayoutInfo, indent: &str) {
+    if layout.fields.is_empty() && layout.size.unwrap_or(0) == 0 {
+        writeln!(out, "{}seq: []", indent).unwrap();
+        return;
+    }
+    writeln!(out, "{}seq:", indent).unwrap();
+    let mut end = 0;
+    for (index, field) in layout.fields.iter().enumerate() {
+        // The offsets of fields after length prefixed fields and varints are the offsets when those have their minimal size,
+        // which is also what their end is counted as
+        if field.offset > end {
+            write_reserved(out, indent, end, field.offset - end);
+        }
+        let min_end = next_field_offset(field.offset, field.size, field.kind);
+        end = end.max(min_end.unwrap_or(field.offset));
+        if let FieldKind::LengthPrefixed { prefix_size } = field.kind {
+            let prefix_type = number_type(FieldKind::UnsignedInteger, prefix_size).unwrap();
+            writeln!(out, "{}  - id: {}_len", indent, field.name).unwrap();
+            writeln!(out, "{}    type: {}", indent, prefix_type).unwrap();
+            writeln!(out, "{}  - id: {}", indent, field.name).unwrap();
+            writeln!(out, "{}    size: {}_len", indent, field.name).unwrap();
+            continue;
+        }
+        writeln!(out, "{}  - id: {}", indent, field.name).unwrap();
+        if let (FieldKind::Varint(_), true) = (field.kind, index + 1 < layout.fields.len()) {
+            writeln!(out, "{}    type: u1", indent).unwrap();
+            writeln!(out, "{}    repeat: until", indent).unwrap();
+            writeln!(out, "{}    repeat-until: (_ & 0x80) == 0", indent).unwrap();
+            writeln!(out, "{}    doc: {}", indent, string(field.type_name)).unwrap();
+            continue;
+        }
+        let (attribute, unsupported_size) = field_type(field, layout.endianness);
+        writeln!(out, "{}    {}", indent, attribute).unwrap();
+        if let (FieldKind::Magic { value }, false) = (field.kind, unsupported_size) {
+            writeln!(out, "{}    valid: {:#x}", indent, value).unwrap();
+        }
+        if let (FieldKind::Array { element_size, .. }, false) = (field.kind, unsupported_size) {
+            match field.size {
+                Some(size) => {
+                    writeln!(out, "{}    repeat: expr", indent).unwrap();
+                    writeln!(out, "{}    repeat-expr: {}", indent, size / element_size).unwrap();
+                }
+                None => writeln!(out, "{}    repeat: eos", indent).unwrap(),
+            }
+        }
+        let is_wrapped = field.type_name.contains(" as ");
+        if is_wrapped || unsupported_size {
+            writeln!(out, "{}    doc: {}", indent, string(field.type_name)).unwrap();
+        }
+    }
+    if let Some(size) = layout.size {
+        if size > end {
+            write_reserved(out, indent, end, size - end);
+        }
+    }
+}
+
+/// Writes a raw field for bytes that aren't part of any field
+fn write_reserved(out: &mut String, indent: &str, offset: usize, size: usize) {
+    writeln!(out, "{}  - id: reserved_{:#x}", indent, offset).unwrap();
+    writeln!(out, "{}    size: {}", indent, size).unwrap();
+}
+
+/// Returns the Kaitai attribute describing the field's type, and whether Kaitai
+/// lacks a matching type so the field had to be exported as raw bytes.
+fn field_type(field: &FieldInfo, layout_endianness: EndianKind) -> (String, bool) {
+    match (field.kind, field.size) {
+        (FieldKind::Bits { bits, .. }, _) => match endianness(layout_endianness) {
+            "be" => (format!("type: b{}", bits), false),
+            _ => (format!("type: b{}le", bits), false),
+        },
+        (FieldKind::Nested(nested), _) => (format!("type: {}", nested.name), false),
+        (FieldKind::Varint(_), _) => ("size-eos: true".to_string(), true),
+        (FieldKind::Bcd { .. } | FieldKind::BFloat16, Some(size)) => {
+            (format!("size: {}", size), true)
+        }
+        (FieldKind::Magic { .. }, Some(size)) => {
+            match number_type(FieldKind::UnsignedInteger, size) {
+                Some(kaitai_type) => (format!("type: {}", kaitai_type), false),
+                None => (format!("size: {}", size), true),
+            }
+        }
+        (FieldKind::UnsignedInteger | FieldKind::SignedInteger | FieldKind::Float, Some(size)) => {
+            match number_type(field.kind, size) {
+                Some(kaitai_type) => (format!("type: {}", kaitai_type), false),
+                None => (format!("size: {}", size), true),
+            }
+        }
+        (
+            FieldKind::Array {
+                element: FieldKind::Nested(nested),
+                element_size,
+            },
+            _,
+        ) if element_size > 0 => (format!("type: {}", nested.name), false),
+        (
+            FieldKind::Array {
+                element,
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => match (number_type(*element, element_size), size) {
+            (Some(kaitai_type), _) => (format!("type: {}", kaitai_type), false),
+            (None, Some(size)) => (format!("size: {}", size), true),
+            (None, None) => ("size-eos: true".to_string(), true),
+        },
+        (_, Some(size)) => (format!("size: {}", size), false),
+        (_, None) => ("size-eos: true".to_string(), false),
+    }
+}
+
+/// Returns the Kaitai type for a number of the given kind and size, or `None` if Kaitai doesn't have one
+fn number_type(kind: FieldKind, size: usize) -> Option<String> {
+    match (kind, size) {
+        (FieldKind::UnsignedInteger, 1 | 2 | 4 | 8) => Some(format!("u{}", size)),
+        (FieldKind::SignedInteger, 1 | 2 | 4 | 8) => Some(format!("s{}", size)),
+        (FieldKind::Float, 4 | 8) => Some(format!("f{}", size)),
+        _ => None,
+    }
+}
+
+fn endianness(endianness: EndianKind) -> &'static str {
+    match endianness {
+        EndianKind::Big => "be",
+        EndianKind::Little => "le",
+        EndianKind::Native => {
+            if cfg!(target_endian = "big") {
+                "be"
+            } else {
+                "le"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::{Leb128, Magic};
+
+    #[test]
+    fn empty_layout() {
+        binary_layout!(empty, LittleEndian, {});
+        assert_eq!(
+            "meta:\n  id: empty\n  endian: le\nseq: []\n",
+            to_ksy(&empty::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn primitive_types() {
+        binary_layout!(my_layout, LittleEndian, {
+            field1: i8,
+            field2: u32,
+            field3: i64,
+            field4: f32,
+            field5: f64,
+            field6: u128,
+            field7: core::num::NonZeroU16,
+            field8: (),
+            field9: [u8; 3],
+        });
+        assert_eq!(
+            r#"meta:
+  id: my_layout
+  endian: le
+seq:
+  - id: field1
+    type: s1
+  - id: field2
+    type: u4
+  - id: field3
+    type: s8
+  - id: field4
+    type: f4
+  - id: field5
+    type: f8
+  - id: field6
+    size: 16
+    doc: "u128"
+  - id: field7
+    type: u2
+  - id: field8
+    size: 0
+  - id: field9
+    size: 3
+"#,
+            to_ksy(&my_layout::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn pinned_fields() {
+        binary_layout!(regs, LittleEndian, {
+            id: u32,
+            control @ 0x10: u32,
+        });
+        assert_eq!(
+            r#"meta:
+  id: regs
+  endian: le
+seq:
+  - id: id
+    type: u4
+  - id: reserved_0x4
+    size: 12
+  - id: control
+    type: u4
+"#,
+            to_ksy(&regs::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn aligned_layouts() {
+        binary_layout!(entry, BigEndian, aligned, {
+            kind: u8,
+            id: u32,
+            flags: u16,
+        });
+        assert_eq!(
+            r#"meta:
+  id: entry
+  endian: be
+seq:
+  - id: kind
+    type: u1
+  - id: reserved_0x1
+    size: 3
+  - id: id
+    type: u4
+  - id: flags
+    type: u2
+  - id: reserved_0xa
+    size: 2
+"#,
+            to_ksy(&entry::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn padded_layouts() {
+        binary_layout!(sector, LittleEndian, pad_to = 16, {
+            id: u32,
+            flags: u16,
+        });
+        assert_eq!(
+            r#"meta:
+  id: sector
+  endian: le
+seq:
+  - id: id
+    type: u4
+  - id: flags
+    type: u2
+  - id: reserved_0x6
+    size: 10
+"#,
+            to_ksy(&sector::LAYOUT)
+        );
+
+        binary_layout!(blank, LittleEndian, pad_to = 4, {});
+        assert_eq!(
+            r#"meta:
+  id: blank
+  endian: le
+seq:
+  - id: reserved_0x0
+    size: 4
+"#,
+            to_ksy(&blank::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        binary_layout!(my_layout, BigEndian, {
+            samples: [u16; 8],
+            big: [u128; 2],
+        });
+        assert_eq!(
+            r#"meta:
+  id: my_layout
+  endian: be
+seq:
+  - id: samples
+    type: u2
+    repeat: expr
+    repeat-expr: 8
+  - id: big
+    size: 32
+    doc: "[u128; 2]"
+"#,
+            to_ksy(&my_layout::LAYOUT)
+        );
+    }
+
+    binary_layout!(record, BigEndian, {
+        id: u16,
+    });
+    binary_layout!(table, BigEndian, {
+        records: [record::NestedView],
+    });
+
+    #[test]
+    fn open_ended_arrays() {
+        binary_layout!(numbers, BigEndian, {
+            values: [u32],
+        });
+        assert_eq!(
+            r#"meta:
+  id: numbers
+  endian: be
+seq:
+  - id: values
+    type: u4
+    repeat: eos
+"#,
+            to_ksy(&numbers::LAYOUT)
+        );
+        assert_eq!(
+            r#"meta:
+  id: table
+  endian: be
+seq:
+  - id: records
+    type: record
+    repeat: eos
+types:
+  record:
+    meta:
+      endian: be
+    seq:
+      - id: id
+        type: u2
+"#,
+            to_ksy(&table::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn length_prefixed() {
+        binary_layout!(message, LittleEndian, {
+            kind: u8,
+            payload: crate::LengthPrefixed<u32>,
+        });
+        assert_eq!(
+            r#"meta:
+  id: message
+  endian: le
+seq:
+  - id: kind
+    type: u1
+  - id: payload_len
+    type: u4
+  - id: payload
+    size: payload_len
+"#,
+            to_ksy(&message::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        binary_layout!(message, BigEndian, {
+            name: crate::LengthPrefixed<u16>,
+            delta: crate::ZigZag<i32>,
+            crc: u32,
+        });
+        assert_eq!(
+            r#"meta:
+  id: message
+  endian: be
+seq:
+  - id: name_len
+    type: u2
+  - id: name
+    size: name_len
+  - id: delta
+    type: u1
+    repeat: until
+    repeat-until: (_ & 0x80) == 0
+    doc: "crate::ZigZag<i32>"
+  - id: crc
+    type: u4
+"#,
+            to_ksy(&message::LAYOUT)
+        );
+    }
+
+    binary_layout!(tag, LittleEndian, {
+        value: Leb128<u64>,
+    });
+
+    #[test]
+    fn varint() {
+        assert_eq!(
+            r#"meta:
+  id: tag
+  endian: le
+seq:
+  - id: value
+    size-eos: true
+    doc: "Leb128<u64>"
+"#,
+            to_ksy(&tag::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn magic() {
+        binary_layout!(header, BigEndian, {
+            magic: Magic<u32, 0x6d795f66>,
+        });
+        assert_eq!(
+            r#"meta:
+  id: header
+  endian: be
+seq:
+  - id: magic
+    type: u4
+    valid: 0x6d795f66
+"#,
+            to_ksy(&header::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn bit_fields() {
+        binary_layout!(big, BigEndian, {
+            version: u8 bits(4),
+            ihl: u8 bits(4),
+            length: u16,
+        });
+        binary_layout!(little, LittleEndian, {
+            flags: u8 bits(3),
+            offset: u16 bits(13),
+        });
+        assert_eq!(
+            r#"meta:
+  id: big
+  endian: be
+seq:
+  - id: version
+    type: b4
+  - id: ihl
+    type: b4
+  - id: length
+    type: u2
+"#,
+            to_ksy(&big::LAYOUT)
+        );
+        assert_eq!(
+            r#"meta:
+  id: little
+  endian: le
+seq:
+  - id: flags
+    type: b3le
+  - id: offset
+    type: b13le
+"#,
+            to_ksy(&little::LAYOUT)
+        );
+    }
+
+    mod nested {
+        use crate::prelude::*;
+
+        binary_layout!(deep, LittleEndian, {
+            value: u16,
+        });
+        binary_layout!(header, BigEndian, {
+            deep1: deep::NestedView,
+            deep2: deep::NestedView,
+        });
+        binary_layout!(packet, BigEndian, {
+            head: header::NestedView,
+            other: deep::NestedView,
+            payload: [u8],
+        });
+    }
+
+    #[test]
+    fn nested_layouts() {
+        assert_eq!(
+            r#"meta:
+  id: packet
+  endian: be
+seq:
+  - id: head
+    type: header
+  - id: other
+    type: deep
+  - id: payload
+    size-eos: true
+types:
+  deep:
+    meta:
+      endian: le
+    seq:
+      - id: value
+        type: u2
+  header:
+    meta:
+      endian: be
+    seq:
+      - id: deep1
+        type: deep
+      - id: deep2
+        type: deep
+"#,
+            to_ksy(&nested::packet::LAYOUT)
+        );
+    }
+}
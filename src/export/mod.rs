@@ -0,0 +1,105 @@
+//! This module contains exporters that describe layouts in formats other tools can consume.
+//!
+//! The exporters work on the [LayoutInfo](crate::LayoutInfo) constant that
+//! [binary_layout!](crate::binary_layout!) generates for each layout as `LAYOUT`.
+//!
+//! Exporters that define nested layouts separately, i.e. all but [json] and [diagram], name these definitions after the layouts.
+//! They panic if a layout nests two different layouts with the same name, e.g. `first::header` and `second::header`.
+
+pub mod c_header;
+pub mod debugger;
+pub mod diagram;
+pub mod json;
+pub mod kaitai;
+pub mod python;
+pub mod typescript;
+pub mod wireshark;
+
+use crate::layout_info::has_dynamic_len_kind;
+use crate::{FieldKind, LayoutInfo};
+
+/// Index of the first field after a length prefixed field or varint, or the number of fields if there is none.
+/// The offsets of this field and the fields after it depend on the data.
+fn first_relative_field(layout: &LayoutInfo) -> usize {
+    layout
+        .fields
+        .iter()
+        .position(|field| has_dynamic_len_kind(field.kind))
+        .map_or(layout.fields.len(), |index| index + 1)
+}
+
+/// Collects all layouts nested into the given layout, directly or as array elements, transitively and without duplicates.
+/// Layouts are ordered so that each layout comes after all layouts nested into it.
+///
+/// The exporters name the definitions of nested layouts after the layouts, so this panics if two different layouts
+/// have the same name, e.g. layouts declared in different modules.
+fn collect_nested_layouts(layout: &LayoutInfo, result: &mut Vec<&'static LayoutInfo>) {
+    for field in layout.fields {
+        if let FieldKind::Nested(nested)
+        | FieldKind::Array {
+            element: &FieldKind::Nested(nested),
+            ..
+        } = field.kind
+        {
+            // The same layout can be at different addresses since `LAYOUT` is a constant, so compare the layouts as well
+            match result.iter().find(|known| known.name == nested.name) {
+                Some(known) if core::ptr::eq(*known, nested) || **known == *nested => {}
+                Some(_) => panic!(
+                    "Layout {} nests two different layouts named {}, which can't be exported with the same name",
+                    layout.name, nested.name
+                ),
+                None => {
+                    collect_nested_layouts(nested, result);
+                    result.push(nested);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    mod first {
+        use crate::prelude::*;
+
+        binary_layout!(header, BigEndian, {
+            kind: u8,
+        });
+    }
+
+    mod second {
+        use crate::prelude::*;
+
+        binary_layout!(header, BigEndian, {
+            length: u16,
+        });
+    }
+
+    binary_layout!(packet, BigEndian, {
+        head: first::header::NestedView,
+        tail: first::header::NestedView,
+        heads: [first::header::NestedView],
+    });
+
+    binary_layout!(mixed, BigEndian, {
+        left: first::header::NestedView,
+        right: second::header::NestedView,
+    });
+
+    #[test]
+    fn nested_layouts_are_collected_once() {
+        let mut layouts = Vec::new();
+        collect_nested_layouts(&packet::LAYOUT, &mut layouts);
+        assert_eq!(1, layouts.len());
+        assert_eq!(first::header::LAYOUT, *layouts[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "two different layouts named header")]
+    fn different_layouts_with_the_same_name() {
+        collect_nested_layouts(&mixed::LAYOUT, &mut Vec::new());
+    }
+}
@@ -0,0 +1,912 @@
+//! Exports layouts as Python code.
+//!
+//! For each layout, the generated module contains a class with one attribute per field,
+//! an `unpack(data, offset=0)` class method parsing the layout from a `bytes`-like object
+//! and a `pack()` method serializing it back. It only depends on the Python standard library.
+//!
+//! Fields are mapped to Python values as follows:
+//! - Integers and floats become `int` and `float` and are read with the [struct](https://docs.python.org/3/library/struct.html) module.
+//!   128 bit integers aren't supported by the struct module and are read with `int.from_bytes()` instead.
+//! - Bit fields become `int` and are extracted from the bytes they share with other bit fields by shifting and masking.
+//! - Arrays of numbers like `[u16; 8]` become a `list` of `int` or `float`.
+//!   Arrays of 128 bit integers aren't supported by the struct module and become `bytes`.
+//!   Open ended arrays like `[u32]` contain as many elements as fit into the rest of the data.
+//! - Open ended arrays of nested layouts like `[my_layout::NestedView]` become a `list` of instances of the class generated for the nested layout.
+//! - Byte arrays `[u8; N]` and open ended byte slices `[u8]` become `bytes`.
+//!   Length prefixed fields like `LengthPrefixed<u16>` become `bytes` as well, their length prefix is read and written along with the data.
+//! - Varints like `Leb128<u32>` aren't decoded, they become `bytes` with the rest of the data and the varint type noted in a comment.
+//!   Varints followed by other fields become `bytes` up to the first byte without the continuation bit.
+//! - Fields after length prefixed fields and varints don't have a fixed offset, so `unpack()` moves the offset
+//!   by the number of bytes those take up beyond their minimal size.
+//! - Nested layouts become instances of the class generated for the nested layout.
+//! - Fields using a custom type via [LayoutAs](crate::LayoutAs) are exported as their underlying type,
+//!   with the custom type noted in a comment.
+//! - `()` fields become `None`.
+//!
+//! Gaps between fields, e.g. before fields pinned to an explicit offset or padding inserted by `aligned` layouts,
+//! and the padding at the end of the layout are skipped by `unpack()` and filled with zeros by `pack()`.
+//!
+//! Field names that are Python keywords get a `_` appended, e.g. a field `from` becomes the attribute `from_`.
+
+use std::fmt::Write;
+
+use super::{collect_nested_layouts, first_relative_field};
+use crate::macro_binary_layout::next_field_offset;
+use crate::{EndianKind, FieldInfo, FieldKind, LayoutInfo};
+
+/// Return Python source code for a class parsing and serializing the given layout.
+/// Classes for all layouts nested into it are included in the generated code.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::python, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"# Generated by binary-layout. Do not edit.
+/// import struct
+/// import sys
+///
+///
+/// class my_layout:
+///     """Layout `my_layout`, all fields are big endian"""
+///
+///     SIZE = None
+///     FIELDS = ("field1", "field2", "tail")
+///
+///     def __init__(self, field1, field2, tail):
+///         self.field1 = field1
+///         self.field2 = field2  # bool as u8
+///         self.tail = tail
+///
+///     @classmethod
+///     def unpack(cls, data, offset=0):
+///         return cls(
+///             field1=struct.unpack_from(">H", data, offset + 0)[0],
+///             field2=struct.unpack_from(">B", data, offset + 2)[0],
+///             tail=bytes(data[offset + 3:]),
+///         )
+///
+///     def pack(self):
+///         return b"".join([
+///             struct.pack(">H", self.field1),
+///             struct.pack(">B", self.field2),
+///             bytes(self.tail),
+///         ])
+///
+///     def __repr__(self):
+///         return "my_layout(" + ", ".join(name + "=" + repr(getattr(self, name)) for name in self.FIELDS) + ")"
+///
+///     def __eq__(self, other):
+///         return type(self) is type(other) and all(getattr(self, name) == getattr(other, name) for name in self.FIELDS)
+/// "#,
+///     python::to_python(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_python(layout: &LayoutInfo) -> String {
+    let mut out = String::new();
+    out.push_str("# Generated by binary-layout. Do not edit.\n");
+    out.push_str("import struct\n");
+    out.push_str("import sys\n");
+    write_classes(&mut out, layout);
+    out
+}
+
+/// Writes the classes for the given layout and all layouts nested into it.
+/// The generated code expects the `struct` and `sys` modules to be imported.
+pub(super) fn write_classes(out: &mut String, layout: &LayoutInfo) {
+    let mut nested_layouts = Vec::new();
+    collect_nested_layouts(layout, &mut nested_layouts);
+    for nested in nested_layouts {
+        write_class(out, nested);
+    }
+    write_class(out, layout);
+}
+
+fn write_class(out: &mut String, layout: &LayoutInfo) {
+    let names: Vec<String> = layout.fields.iter().map(|f| identifier(f.name)).collect();
+
+    out.push_str("\n\n");
+    writeln!(out, "class {}:", layout.name).unwrap();
+    writeln!(
+        out,
+        "    \"\"\"Layout `{}`, all fields are {}\"\"\"",
+        layout.name,
+        endianness_description(layout.endianness)
+    )
+    .unwrap();
+    out.push('\n');
+    match layout.size {
+        Some(size) => writeln!(out, "    SIZE = {}", size).unwrap(),
+        None => out.push_str("    SIZE = None\n"),
+    }
+    let quoted: Vec<String> = names.iter().map(|name| format!("\"{}\"", name)).collect();
+    match quoted.len() {
+        1 => writeln!(out, "    FIELDS = ({},)", quoted[0]).unwrap(),
+        _ => writeln!(out, "    FIELDS = ({})", quoted.join(", ")).unwrap(),
+    }
+
+    out.push('\n');
+    if names.is_empty() {
+        out.push_str("    def __init__(self):\n");
+        out.push_str("        pass\n");
+    } else {
+        writeln!(out, "    def __init__(self, {}):", names.join(", ")).unwrap();
+        for (field, name) in layout.fields.iter().zip(&names) {
+            match comment(field) {
+                Some(comment) => {
+                    writeln!(out, "        self.{0} = {0}  # {1}", name, comment).unwrap()
+                }
+                None => writeln!(out, "        self.{0} = {0}", name).unwrap(),
+            }
+        }
+    }
+
+    let order = byte_order(layout.endianness);
+    out.push('\n');
+    out.push_str("    @classmethod\n");
+    out.push_str("    def unpack(cls, data, offset=0):\n");
+    if first_relative_field(layout) < layout.fields.len() {
+        write_relative_unpack(out, layout, &names, order);
+    } else {
+        out.push_str("        return cls(\n");
+        for (field, name) in layout.fields.iter().zip(&names) {
+            writeln!(
+                out,
+                "            {}={},",
+                name,
+                unpack_expression(field, order, false)
+            )
+            .unwrap();
+        }
+        out.push_str("        )\n");
+    }
+
+    out.push('\n');
+    out.push_str("    def pack(self):\n");
+    out.push_str("        return b\"\".join([\n");
+    let mut end = 0;
+    let mut index = 0;
+    while index < layout.fields.len() {
+        let offset = layout.fields[index].offset;
+        if offset > end {
+            writeln!(out, "            bytes({}),", offset - end).unwrap();
+        }
+        let group_len = layout.fields[index..]
+            .iter()
+            .take_while(|field| matches!(field.kind, FieldKind::Bits { .. }))
+            .count();
+        if group_len == 0 {
+            let value = format!("self.{}", names[index]);
+            writeln!(
+                out,
+                "            {},",
+                pack_expression(&layout.fields[index], order, &value)
+            )
+            .unwrap();
+            // Length prefixed fields and varints take up at least their minimal size, which the offsets of the fields after them are based on
+            let field = &layout.fields[index];
+            let min_end = next_field_offset(field.offset, field.size, field.kind);
+            end = end.max(min_end.unwrap_or(offset));
+            index += 1;
+        } else {
+            let group = index..(index + group_len);
+            writeln!(
+                out,
+                "            {},",
+                pack_bit_field_group(&layout.fields[group.clone()], &names[group.clone()], order)
+            )
+            .unwrap();
+            for field in &layout.fields[group] {
+                end = end.max(field.offset + field.size.unwrap());
+            }
+            index += group_len;
+        }
+    }
+    if let Some(size) = layout.size {
+        if size > end {
+            writeln!(out, "            bytes({}),", size - end).unwrap();
+        }
+    }
+    out.push_str("        ])\n");
+
+    out.push('\n');
+    out.push_str("    def __repr__(self):\n");
+    writeln!(
+        out,
+        "        return \"{}(\" + \", \".join(name + \"=\" + repr(getattr(self, name)) for name in self.FIELDS) + \")\"",
+        layout.name
+    )
+    .unwrap();
+    out.push('\n');
+    out.push_str("    def __eq__(self, other):\n");
+    out.push_str("        return type(self) is type(other) and all(getattr(self, name) == getattr(other, name) for name in self.FIELDS)\n");
+}
+
+/// Writes the body of `unpack()` for layouts with fields after length prefixed fields or varints,
+/// which moves the offset by the number of bytes those take up beyond their minimal size after reading them
+fn write_relative_unpack(
+    out: &mut String,
+    layout: &LayoutInfo,
+    names: &[String],
+    order: ByteOrder,
+) {
+    out.push_str("        values = []\n");
+    for (index, (field, name)) in layout.fields.iter().zip(names).enumerate() {
+        let is_last = index + 1 == layout.fields.len();
+        writeln!(
+            out,
+            "        values.append({})  # {}",
+            unpack_expression(field, order, !is_last),
+            name
+        )
+        .unwrap();
+        match field.kind {
+            FieldKind::LengthPrefixed { .. } if !is_last => {
+                out.push_str("        offset += len(values[-1])\n")
+            }
+            FieldKind::Varint(_) if !is_last => {
+                out.push_str("        offset += len(values[-1]) - 1\n")
+            }
+            _ => {}
+        }
+    }
+    out.push_str("        return cls(*values)\n");
+}
+
+/// How the generated code reads and writes a field
+enum Access {
+    /// Use the struct module with the given format character
+    Struct(char),
+    /// Use the struct module with the given format character repeated for each element of a list.
+    /// Open ended arrays don't have a fixed number of elements.
+    StructArray(char, Option<usize>),
+    /// Use the class generated for a nested layout for each element of a list
+    NestedArray {
+        nested: &'static LayoutInfo,
+        element_size: usize,
+        len: Option<usize>,
+    },
+    /// Use `int.from_bytes()` and `int.to_bytes()`
+    Int {
+        signed: bool,
+        size: usize,
+    },
+    /// Use `int.from_bytes()` on the bytes spanned by a bit field and extract its bits
+    Bits {
+        bit_offset: usize,
+        bits: usize,
+        size: usize,
+    },
+    Bytes(Option<usize>),
+    /// The bytes of a varint up to the first byte without the continuation bit
+    Varint,
+    /// Use the struct module with the given format character for the length prefix, followed by that many bytes
+    LengthPrefixed {
+        format: char,
+        prefix_size: usize,
+    },
+    Nested(&'static LayoutInfo),
+    Unit,
+}
+
+fn access(field: &FieldInfo) -> Access {
+    match (field.kind, field.size) {
+        (FieldKind::Bits { bit_offset, bits }, Some(size)) => Access::Bits {
+            bit_offset,
+            bits,
+            size,
+        },
+        (FieldKind::Nested(nested), _) => Access::Nested(nested),
+        (FieldKind::Unit, _) => Access::Unit,
+        (FieldKind::LengthPrefixed { prefix_size }, _) => Access::LengthPrefixed {
+            format: struct_format(FieldKind::UnsignedInteger, prefix_size).unwrap(),
+            prefix_size,
+        },
+        (
+            FieldKind::Array {
+                element: FieldKind::Nested(nested),
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => Access::NestedArray {
+            nested,
+            element_size,
+            len: size.map(|size| size / element_size),
+        },
+        (
+            FieldKind::Array {
+                element,
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => match struct_format(*element, element_size) {
+            Some(format) => Access::StructArray(format, size.map(|size| size / element_size)),
+            None => Access::Bytes(size),
+        },
+        (kind, Some(size)) if struct_format(kind, size).is_some() => {
+            Access::Struct(struct_format(kind, size).unwrap())
+        }
+        (FieldKind::UnsignedInteger, Some(size)) => Access::Int {
+            signed: false,
+            size,
+        },
+        (FieldKind::SignedInteger, Some(size)) => Access::Int { signed: true, size },
+        (_, size) => Access::Bytes(size),
+    }
+}
+
+/// Returns the format character of the struct module for a number of the given kind and size
+fn struct_format(kind: FieldKind, size: usize) -> Option<char> {
+    match (kind, size) {
+        (FieldKind::UnsignedInteger, 1) => Some('B'),
+        (FieldKind::UnsignedInteger, 2) => Some('H'),
+        (FieldKind::UnsignedInteger, 4) => Some('I'),
+        (FieldKind::UnsignedInteger, 8) => Some('Q'),
+        (FieldKind::SignedInteger, 1) => Some('b'),
+        (FieldKind::SignedInteger, 2) => Some('h'),
+        (FieldKind::SignedInteger, 4) => Some('i'),
+        (FieldKind::SignedInteger, 8) => Some('q'),
+        (FieldKind::Float, 2) => Some('e'),
+        (FieldKind::Float, 4) => Some('f'),
+        (FieldKind::Float, 8) => Some('d'),
+        _ => None,
+    }
+}
+
+/// Returns an expression reading the field. Varints followed by other fields are read up to their last byte instead of the end of the data.
+fn unpack_expression(field: &FieldInfo, order: ByteOrder, followed: bool) -> String {
+    let start = format!("offset + {}", field.offset);
+    let access = match (access(field), field.kind) {
+        (Access::Bytes(None), FieldKind::Varint(_)) if followed => Access::Varint,
+        (access, _) => access,
+    };
+    match access {
+        Access::Struct(format) => format!(
+            "struct.unpack_from(\"{}{}\", data, {})[0]",
+            order.struct_prefix, format, start
+        ),
+        Access::StructArray(format, Some(len)) => format!(
+            "list(struct.unpack_from(\"{}{}{}\", data, {}))",
+            order.struct_prefix, len, format, start
+        ),
+        Access::StructArray(format, None) => format!(
+            "list(struct.unpack_from(\"{0}%d{1}\" % ((len(data) - ({2})) // struct.calcsize(\"{0}{1}\")), data, {2}))",
+            order.struct_prefix, format, start
+        ),
+        Access::NestedArray {
+            nested,
+            element_size,
+            len,
+        } => format!(
+            "[{}.unpack(data, {} + index * {}) for index in range({})]",
+            nested.name,
+            start,
+            element_size,
+            match len {
+                Some(len) => len.to_string(),
+                None => format!("(len(data) - ({})) // {}", start, element_size),
+            }
+        ),
+        Access::Int { signed, size } => format!(
+            "int.from_bytes(data[{0}:{0} + {1}], {2}, signed={3})",
+            start,
+            size,
+            order.int_byteorder,
+            if signed { "True" } else { "False" }
+        ),
+        Access::Bits {
+            bit_offset,
+            bits,
+            size,
+        } => format!(
+            "(int.from_bytes(data[{0}:{0} + {1}], {2}) >> {3}) & {4:#x}",
+            start,
+            size,
+            order.int_byteorder,
+            bit_shift(order, size * 8, bit_offset, bits),
+            mask(bits)
+        ),
+        Access::Bytes(Some(size)) => format!("bytes(data[{0}:{0} + {1}])", start, size),
+        Access::Bytes(None) => format!("bytes(data[{}:])", start),
+        Access::Varint => format!(
+            "bytes(data[{0}:next((index for index in range({0}, len(data)) if data[index] < 0x80), len(data) - 1) + 1])",
+            start
+        ),
+        Access::LengthPrefixed {
+            format,
+            prefix_size,
+        } => format!(
+            "bytes(data[{0} + {1}:{0} + {1} + struct.unpack_from(\"{2}{3}\", data, {0})[0]])",
+            start, prefix_size, order.struct_prefix, format
+        ),
+        Access::Nested(nested) => format!("{}.unpack(data, {})", nested.name, start),
+        Access::Unit => "None".to_string(),
+    }
+}
+
+fn pack_expression(field: &FieldInfo, order: ByteOrder, value: &str) -> String {
+    match access(field) {
+        Access::Struct(format) => {
+            format!(
+                "struct.pack(\"{}{}\", {})",
+                order.struct_prefix, format, value
+            )
+        }
+        Access::StructArray(format, Some(len)) => format!(
+            "struct.pack(\"{}{}{}\", *{})",
+            order.struct_prefix, len, format, value
+        ),
+        Access::StructArray(format, None) => format!(
+            "struct.pack(\"{}%d{}\" % len({2}), *{2})",
+            order.struct_prefix, format, value
+        ),
+        Access::NestedArray { .. } => {
+            format!("b\"\".join(element.pack() for element in {})", value)
+        }
+        Access::Int { signed, size } => format!(
+            "{}.to_bytes({}, {}, signed={})",
+            value,
+            size,
+            order.int_byteorder,
+            if signed { "True" } else { "False" }
+        ),
+        Access::Bits { .. } => unreachable!("Bit fields are packed by pack_bit_field_group"),
+        Access::Bytes(Some(size)) => format!("struct.pack(\"{}s\", {})", size, value),
+        Access::Bytes(None) | Access::Varint => format!("bytes({})", value),
+        Access::LengthPrefixed { format, .. } => format!(
+            "struct.pack(\"{0}{1}\", len({2})) + bytes({2})",
+            order.struct_prefix, format, value
+        ),
+        Access::Nested(_) => format!("{}.pack()", value),
+        Access::Unit => "b\"\"".to_string(),
+    }
+}
+
+/// Returns an expression packing a group of consecutive bit fields into the bytes they span
+fn pack_bit_field_group(fields: &[FieldInfo], names: &[String], order: ByteOrder) -> String {
+    let start = fields[0].offset;
+    let size = fields
+        .iter()
+        .map(|field| field.offset + field.size.unwrap())
+        .max()
+        .unwrap()
+        - start;
+    let parts: Vec<String> = fields
+        .iter()
+        .zip(names)
+        .map(|(field, name)| match field.kind {
+            FieldKind::Bits { bit_offset, bits } => format!(
+                "((self.{} & {:#x}) << {})",
+                name,
+                mask(bits),
+                bit_shift(
+                    order,
+                    size * 8,
+                    (field.offset - start) * 8 + bit_offset,
+                    bits
+                )
+            ),
+            _ => unreachable!("Only bit fields are packed as a group"),
+        })
+        .collect();
+    format!(
+        "({}).to_bytes({}, {})",
+        parts.join(" | "),
+        size,
+        order.int_byteorder
+    )
+}
+
+/// Returns an expression for how far a bit field is shifted within the integer read from the bytes it spans.
+/// Big endian bit fields are numbered starting at the most significant bit, little endian bit fields at the least significant bit.
+fn bit_shift(order: ByteOrder, total_bits: usize, bit_offset: usize, bits: usize) -> String {
+    let big_endian = total_bits - bit_offset - bits;
+    match order.struct_prefix {
+        '>' => big_endian.to_string(),
+        '<' => bit_offset.to_string(),
+        _ => format!(
+            "({} if sys.byteorder == \"big\" else {})",
+            big_endian, bit_offset
+        ),
+    }
+}
+
+fn mask(bits: usize) -> u128 {
+    u128::MAX >> (128 - bits)
+}
+
+fn comment(field: &FieldInfo) -> Option<&'static str> {
+    if field.type_name.contains(" as ")
+        || matches!(
+            field.kind,
+            FieldKind::Array { .. }
+                | FieldKind::Bcd { .. }
+                | FieldKind::BFloat16
+                | FieldKind::LengthPrefixed { .. }
+                | FieldKind::Varint(_)
+        )
+        || matches!(access(field), Access::Int { .. } | Access::Bits { .. })
+    {
+        Some(field.type_name)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ByteOrder {
+    /// Byte order character for format strings of the struct module
+    struct_prefix: char,
+    /// Python expression for the byteorder argument of `int.from_bytes()` and `int.to_bytes()`
+    int_byteorder: &'static str,
+}
+
+fn byte_order(endianness: EndianKind) -> ByteOrder {
+    match endianness {
+        EndianKind::Big => ByteOrder {
+            struct_prefix: '>',
+            int_byteorder: "\"big\"",
+        },
+        EndianKind::Little => ByteOrder {
+            struct_prefix: '<',
+            int_byteorder: "\"little\"",
+        },
+        EndianKind::Native => ByteOrder {
+            struct_prefix: '=',
+            int_byteorder: "sys.byteorder",
+        },
+    }
+}
+
+fn endianness_description(endianness: EndianKind) -> &'static str {
+    match endianness {
+        EndianKind::Big => "big endian",
+        EndianKind::Little => "little endian",
+        EndianKind::Native => "native endian (same as the platform)",
+    }
+}
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+fn identifier(name: &str) -> String {
+    if PYTHON_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::LengthPrefixed;
+
+    #[test]
+    fn primitive_types() {
+        binary_layout!(my_layout, LittleEndian, {
+            field1: i8,
+            field2: u32,
+            field3: i64,
+            field4: f32,
+            field5: f64,
+            field6: i128,
+            field7: core::num::NonZeroU16,
+            field8: (),
+            field9: [u8; 3],
+        });
+        let code = to_python(&my_layout::LAYOUT);
+        assert!(code.contains("    SIZE = 46\n"));
+        assert!(code.contains(
+            r#"        return cls(
+            field1=struct.unpack_from("<b", data, offset + 0)[0],
+            field2=struct.unpack_from("<I", data, offset + 1)[0],
+            field3=struct.unpack_from("<q", data, offset + 5)[0],
+            field4=struct.unpack_from("<f", data, offset + 13)[0],
+            field5=struct.unpack_from("<d", data, offset + 17)[0],
+            field6=int.from_bytes(data[offset + 25:offset + 25 + 16], "little", signed=True),
+            field7=struct.unpack_from("<H", data, offset + 41)[0],
+            field8=None,
+            field9=bytes(data[offset + 43:offset + 43 + 3]),
+        )
+"#
+        ));
+        assert!(code.contains(
+            r#"        return b"".join([
+            struct.pack("<b", self.field1),
+            struct.pack("<I", self.field2),
+            struct.pack("<q", self.field3),
+            struct.pack("<f", self.field4),
+            struct.pack("<d", self.field5),
+            self.field6.to_bytes(16, "little", signed=True),
+            struct.pack("<H", self.field7),
+            b"",
+            struct.pack("3s", self.field9),
+        ])
+"#
+        ));
+    }
+
+    #[test]
+    fn native_endian() {
+        binary_layout!(my_layout, NativeEndian, {
+            field1: u16,
+            field2: u128,
+        });
+        let code = to_python(&my_layout::LAYOUT);
+        assert!(code.contains("struct.unpack_from(\"=H\", data, offset + 0)[0]"));
+        assert!(code.contains(
+            "int.from_bytes(data[offset + 2:offset + 2 + 16], sys.byteorder, signed=False)"
+        ));
+    }
+
+    #[test]
+    fn pinned_fields() {
+        binary_layout!(regs, LittleEndian, {
+            id: u32,
+            control @ 0x10: u32,
+        });
+        let code = to_python(&regs::LAYOUT);
+        assert!(code.contains("    SIZE = 20\n"));
+        assert!(code
+            .contains("            control=struct.unpack_from(\"<I\", data, offset + 16)[0],\n"));
+        assert!(code.contains(
+            r#"        return b"".join([
+            struct.pack("<I", self.id),
+            bytes(12),
+            struct.pack("<I", self.control),
+        ])
+"#
+        ));
+    }
+
+    #[test]
+    fn aligned_layouts() {
+        binary_layout!(entry, LittleEndian, aligned, {
+            kind: u8,
+            id: u32,
+            flags: u16,
+        });
+        let code = to_python(&entry::LAYOUT);
+        assert!(code.contains("    SIZE = 12\n"));
+        assert!(code.contains("            id=struct.unpack_from(\"<I\", data, offset + 4)[0],\n"));
+        assert!(code.contains(
+            r#"        return b"".join([
+            struct.pack("<B", self.kind),
+            bytes(3),
+            struct.pack("<I", self.id),
+            struct.pack("<H", self.flags),
+            bytes(2),
+        ])
+"#
+        ));
+    }
+
+    #[test]
+    fn padded_layouts() {
+        binary_layout!(sector, LittleEndian, pad_to = 16, {
+            id: u32,
+            flags: u16,
+        });
+        let code = to_python(&sector::LAYOUT);
+        assert!(code.contains("    SIZE = 16\n"));
+        assert!(code.contains(
+            r#"        return b"".join([
+            struct.pack("<I", self.id),
+            struct.pack("<H", self.flags),
+            bytes(10),
+        ])
+"#
+        ));
+    }
+
+    #[test]
+    fn arrays() {
+        binary_layout!(my_layout, BigEndian, {
+            samples: [u16; 8],
+            matrix: [f32; 4],
+            big: [u128; 2],
+        });
+        let code = to_python(&my_layout::LAYOUT);
+        assert!(code.contains("        self.samples = samples  # [u16; 8]\n"));
+        assert!(code.contains(
+            r#"        return cls(
+            samples=list(struct.unpack_from(">8H", data, offset + 0)),
+            matrix=list(struct.unpack_from(">4f", data, offset + 16)),
+            big=bytes(data[offset + 32:offset + 32 + 32]),
+        )
+"#
+        ));
+        assert!(code.contains(
+            r#"        return b"".join([
+            struct.pack(">8H", *self.samples),
+            struct.pack(">4f", *self.matrix),
+            struct.pack("32s", self.big),
+        ])
+"#
+        ));
+    }
+
+    binary_layout!(record, BigEndian, {
+        id: u16,
+        flags: u8,
+    });
+    binary_layout!(table, BigEndian, {
+        records: [record::NestedView],
+    });
+
+    #[test]
+    fn open_ended_arrays() {
+        binary_layout!(numbers, LittleEndian, {
+            values: [u32],
+        });
+        let code = to_python(&numbers::LAYOUT);
+        assert!(code.contains(
+            r#"values=list(struct.unpack_from("<%dI" % ((len(data) - (offset + 0)) // struct.calcsize("<I")), data, offset + 0)),"#
+        ));
+        assert!(code.contains(r#"struct.pack("<%dI" % len(self.values), *self.values),"#));
+        let code = to_python(&table::LAYOUT);
+        assert!(code.contains("class record:"));
+        assert!(code.contains(
+            "records=[record.unpack(data, offset + 0 + index * 3) for index in range((len(data) - (offset + 0)) // 3)],"
+        ));
+        assert!(code.contains(r#"b"".join(element.pack() for element in self.records),"#));
+    }
+
+    binary_layout!(message, BigEndian, {
+        kind: u8,
+        payload: LengthPrefixed<u16>,
+    });
+
+    #[test]
+    fn length_prefixed() {
+        let code = to_python(&message::LAYOUT);
+        assert!(code.contains("        self.payload = payload  # LengthPrefixed<u16>\n"));
+        assert!(code.contains(
+            r#"payload=bytes(data[offset + 1 + 2:offset + 1 + 2 + struct.unpack_from(">H", data, offset + 1)[0]]),"#
+        ));
+        assert!(code.contains(r#"struct.pack(">H", len(self.payload)) + bytes(self.payload),"#));
+    }
+
+    binary_layout!(named, BigEndian, {
+        kind: u8,
+        name: LengthPrefixed<u8>,
+        delta: crate::ZigZag<i32>,
+        flags: u8 bits(4),
+        level: u8 bits(4),
+        crc: u32,
+    });
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        let code = to_python(&named::LAYOUT);
+        assert!(code.contains(
+            r#"    def unpack(cls, data, offset=0):
+        values = []
+        values.append(struct.unpack_from(">B", data, offset + 0)[0])  # kind
+        values.append(bytes(data[offset + 1 + 1:offset + 1 + 1 + struct.unpack_from(">B", data, offset + 1)[0]]))  # name
+        offset += len(values[-1])
+        values.append(bytes(data[offset + 2:next((index for index in range(offset + 2, len(data)) if data[index] < 0x80), len(data) - 1) + 1]))  # delta
+        offset += len(values[-1]) - 1
+        values.append((int.from_bytes(data[offset + 3:offset + 3 + 1], "big") >> 4) & 0xf)  # flags
+        values.append((int.from_bytes(data[offset + 3:offset + 3 + 1], "big") >> 0) & 0xf)  # level
+        values.append(struct.unpack_from(">I", data, offset + 4)[0])  # crc
+        return cls(*values)
+"#
+        ));
+        assert!(code.contains(
+            r#"        return b"".join([
+            struct.pack(">B", self.kind),
+            struct.pack(">B", len(self.name)) + bytes(self.name),
+            bytes(self.delta),
+            (((self.flags & 0xf) << 4) | ((self.level & 0xf) << 0)).to_bytes(1, "big"),
+            struct.pack(">I", self.crc),
+        ])
+"#
+        ));
+    }
+
+    #[test]
+    fn bit_fields() {
+        binary_layout!(my_layout, BigEndian, {
+            version: u8 bits(4),
+            ihl: u8 bits(4),
+            length: u16,
+            flags: u8 bits(3),
+            fragment_offset: u16 bits(13),
+        });
+        let code = to_python(&my_layout::LAYOUT);
+        assert!(code.contains("        self.ihl = ihl  # u8 bits(4)\n"));
+        assert!(code.contains(
+            r#"        return cls(
+            version=(int.from_bytes(data[offset + 0:offset + 0 + 1], "big") >> 4) & 0xf,
+            ihl=(int.from_bytes(data[offset + 0:offset + 0 + 1], "big") >> 0) & 0xf,
+            length=struct.unpack_from(">H", data, offset + 1)[0],
+            flags=(int.from_bytes(data[offset + 3:offset + 3 + 1], "big") >> 5) & 0x7,
+            fragment_offset=(int.from_bytes(data[offset + 3:offset + 3 + 2], "big") >> 0) & 0x1fff,
+        )
+"#
+        ));
+        assert!(code.contains(
+            r#"        return b"".join([
+            (((self.version & 0xf) << 4) | ((self.ihl & 0xf) << 0)).to_bytes(1, "big"),
+            struct.pack(">H", self.length),
+            (((self.flags & 0x7) << 13) | ((self.fragment_offset & 0x1fff) << 0)).to_bytes(2, "big"),
+        ])
+"#
+        ));
+
+        binary_layout!(native, NativeEndian, {
+            low: u8 bits(3),
+            high: u8 bits(5),
+        });
+        let code = to_python(&native::LAYOUT);
+        assert!(code.contains(
+            "low=(int.from_bytes(data[offset + 0:offset + 0 + 1], sys.byteorder) >> (5 if sys.byteorder == \"big\" else 0)) & 0x7,"
+        ));
+    }
+
+    #[test]
+    fn keywords_and_single_field() {
+        binary_layout!(my_layout, BigEndian, {
+            lambda: u8,
+        });
+        let code = to_python(&my_layout::LAYOUT);
+        assert!(code.contains("    FIELDS = (\"lambda_\",)\n"));
+        assert!(code.contains("        self.lambda_ = lambda_\n"));
+    }
+
+    mod nested {
+        use crate::prelude::*;
+
+        binary_layout!(deep, LittleEndian, {
+            value: u16,
+        });
+        binary_layout!(header, BigEndian, {
+            deep1: deep::NestedView,
+            deep2: deep::NestedView,
+        });
+        binary_layout!(packet, BigEndian, {
+            other: deep::NestedView,
+            head: header::NestedView,
+            payload: [u8],
+        });
+    }
+
+    #[test]
+    fn nested_layouts() {
+        let code = to_python(&nested::packet::LAYOUT);
+        let deep = code.find("class deep:").unwrap();
+        let header = code.find("class header:").unwrap();
+        let packet = code.find("class packet:").unwrap();
+        assert!(deep < header && header < packet);
+        assert_eq!(1, code.matches("class deep:").count());
+        assert!(code.contains(
+            r#"        return cls(
+            other=deep.unpack(data, offset + 0),
+            head=header.unpack(data, offset + 2),
+            payload=bytes(data[offset + 6:]),
+        )
+"#
+        ));
+        assert!(code.contains(
+            r#"        return b"".join([
+            self.other.pack(),
+            self.head.pack(),
+            bytes(self.payload),
+        ])
+"#
+        ));
+    }
+}
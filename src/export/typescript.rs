@@ -0,0 +1,832 @@
+//! Exports layouts as TypeScript code.
+//!
+//! For each layout, the generated module contains
+//! - an interface with one property per field,
+//! - a `decode<Layout>(view, offset = 0)` function reading the layout from a [DataView](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/DataView),
+//! - an `encode<Layout>(value, view, offset = 0)` function writing the layout into a `DataView`,
+//! - a `<LAYOUT>_SIZE` constant with the size of the layout, or `null` for layouts ending in an open ended field.
+//!
+//! Fields are mapped to TypeScript types as follows:
+//! - Integers up to 32 bits and floats become `number`. 64 and 128 bit integers become `bigint`.
+//! - Arrays of numbers like `[u16; 8]` become `number[]`, or `bigint[]` for 64 bit integers.
+//!   Arrays of 128 bit integers are exported as `Uint8Array`.
+//!   Open ended arrays like `[u32]` contain as many elements as fit into the rest of the `DataView`.
+//! - Open ended arrays of nested layouts like `[my_layout::NestedView]` become an array of the interface generated for the nested layout.
+//! - Byte arrays `[u8; N]` and open ended byte slices `[u8]` become `Uint8Array`.
+//!   Length prefixed fields like `LengthPrefixed<u16>` become `Uint8Array` as well, their length prefix is read and written along with the data.
+//! - Bit fields are exported as a `Uint8Array` of all bytes the bit field spans, which it may share with other bit fields.
+//! - Varints like `Leb128<u32>` aren't decoded, they become a `Uint8Array` with the rest of the `DataView`.
+//!   Varints followed by other fields become a `Uint8Array` up to the first byte without the continuation bit.
+//! - Fields after length prefixed fields and varints don't have a fixed offset, so the generated functions move the offset
+//!   by the number of bytes those take up beyond their minimal size.
+//! - Nested layouts become the interface generated for the nested layout.
+//! - Fields using a custom type via [LayoutAs](crate::LayoutAs) are exported as their underlying type,
+//!   with the custom type noted in a comment.
+//! - `()` fields become `null`.
+
+use std::fmt::Write;
+
+use super::{collect_nested_layouts, first_relative_field};
+use crate::{EndianKind, FieldInfo, FieldKind, LayoutInfo};
+
+/// Return TypeScript source code with an interface, decoder and encoder for the given layout.
+/// Code for all layouts nested into it is included in the generated code.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::typescript, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"// Generated by binary-layout. Do not edit.
+///
+/// /** Layout `my_layout`, all fields are big endian */
+/// export interface MyLayout {
+///   field1: number;
+///   field2: number; // bool as u8
+///   tail: Uint8Array;
+/// }
+///
+/// export const MY_LAYOUT_SIZE: number | null = null;
+///
+/// export function decodeMyLayout(view: DataView, offset = 0): MyLayout {
+///   return {
+///     field1: view.getUint16(offset + 0, false),
+///     field2: view.getUint8(offset + 2),
+///     tail: new Uint8Array(view.buffer, view.byteOffset + offset + 3, view.byteLength - (offset + 3)).slice(),
+///   };
+/// }
+///
+/// export function encodeMyLayout(value: MyLayout, view: DataView, offset = 0): void {
+///   view.setUint16(offset + 0, value.field1, false);
+///   view.setUint8(offset + 2, value.field2);
+///   new Uint8Array(view.buffer, view.byteOffset + offset + 3, value.tail.length).set(value.tail);
+/// }
+/// "#,
+///     typescript::to_typescript(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_typescript(layout: &LayoutInfo) -> String {
+    let mut nested_layouts = Vec::new();
+    collect_nested_layouts(layout, &mut nested_layouts);
+    let mut layouts: Vec<&LayoutInfo> = nested_layouts;
+    layouts.push(layout);
+
+    let mut out = String::new();
+    out.push_str("// Generated by binary-layout. Do not edit.\n");
+    if layouts
+        .iter()
+        .any(|layout| layout.endianness == EndianKind::Native)
+    {
+        out.push('\n');
+        out.push_str(
+            "const NATIVE_LITTLE_ENDIAN = new Uint8Array(new Uint16Array([1]).buffer)[0] === 1;\n",
+        );
+    }
+    if layouts.iter().any(|layout| has_followed_varint(layout)) {
+        out.push('\n');
+        out.push_str("/** Number of bytes of the varint at the given position, up to the first byte without the continuation bit */\n");
+        out.push_str("function varintLength(view: DataView, position: number): number {\n");
+        out.push_str("  let length = 1;\n");
+        out.push_str("  while (position + length < view.byteLength && view.getUint8(position + length - 1) >= 0x80) {\n");
+        out.push_str("    length++;\n");
+        out.push_str("  }\n");
+        out.push_str("  return length;\n");
+        out.push_str("}\n");
+    }
+    for layout in layouts {
+        write_layout(&mut out, layout);
+    }
+    out
+}
+
+fn write_layout(out: &mut String, layout: &LayoutInfo) {
+    let type_name = pascal_case(layout.name);
+    let little_endian = match layout.endianness {
+        EndianKind::Big => "false",
+        EndianKind::Little => "true",
+        EndianKind::Native => "NATIVE_LITTLE_ENDIAN",
+    };
+
+    out.push('\n');
+    writeln!(
+        out,
+        "/** Layout `{}`, all fields are {} */",
+        layout.name,
+        endianness_description(layout.endianness)
+    )
+    .unwrap();
+    writeln!(out, "export interface {} {{", type_name).unwrap();
+    for field in layout.fields {
+        let ts_type = match access(field) {
+            Access::Number(_) => "number".to_string(),
+            Access::BigInt(_) | Access::BigInt128 { .. } => "bigint".to_string(),
+            Access::Array { bigint: false, .. } => "number[]".to_string(),
+            Access::Array { bigint: true, .. } => "bigint[]".to_string(),
+            Access::NestedArray { nested, .. } => format!("{}[]", pascal_case(nested.name)),
+            Access::Bytes(_) | Access::LengthPrefixed { .. } => "Uint8Array".to_string(),
+            Access::Nested(nested) => pascal_case(nested.name),
+            Access::Unit => "null".to_string(),
+        };
+        if field.type_name.contains(" as ") {
+            writeln!(out, "  {}: {}; // {}", field.name, ts_type, field.type_name).unwrap();
+        } else {
+            writeln!(out, "  {}: {};", field.name, ts_type).unwrap();
+        }
+    }
+    out.push_str("}\n");
+
+    out.push('\n');
+    match layout.size {
+        Some(size) => writeln!(
+            out,
+            "export const {}_SIZE: number | null = {};",
+            layout.name.to_uppercase(),
+            size
+        )
+        .unwrap(),
+        None => writeln!(
+            out,
+            "export const {}_SIZE: number | null = null;",
+            layout.name.to_uppercase()
+        )
+        .unwrap(),
+    }
+
+    out.push('\n');
+    writeln!(
+        out,
+        "export function decode{0}(view: DataView, offset = 0): {0} {{",
+        type_name
+    )
+    .unwrap();
+    if first_relative_field(layout) < layout.fields.len() {
+        // The offset moves after length prefixed fields and varints, so the fields are decoded one statement at a time
+        writeln!(out, "  const result = {{}} as {};", type_name).unwrap();
+        for (index, field) in layout.fields.iter().enumerate() {
+            let followed = index + 1 < layout.fields.len();
+            writeln!(
+                out,
+                "  result.{} = {};",
+                field.name,
+                decode_expression(field, little_endian, followed)
+            )
+            .unwrap();
+            if followed {
+                write_offset_move(out, field, "result");
+            }
+        }
+        out.push_str("  return result;\n");
+    } else {
+        out.push_str("  return {\n");
+        for field in layout.fields {
+            writeln!(
+                out,
+                "    {}: {},",
+                field.name,
+                decode_expression(field, little_endian, false)
+            )
+            .unwrap();
+        }
+        out.push_str("  };\n");
+    }
+    out.push_str("}\n");
+
+    out.push('\n');
+    writeln!(
+        out,
+        "export function encode{0}(value: {0}, view: DataView, offset = 0): void {{",
+        type_name
+    )
+    .unwrap();
+    for (index, field) in layout.fields.iter().enumerate() {
+        write_encode_statements(out, field, little_endian);
+        if index + 1 < layout.fields.len() {
+            write_offset_move(out, field, "value");
+        }
+    }
+    out.push_str("}\n");
+}
+
+/// Whether the layout has a varint followed by other fields, which needs the `varintLength()` helper
+fn has_followed_varint(layout: &LayoutInfo) -> bool {
+    let fields = layout.fields;
+    fields
+        .iter()
+        .take(fields.len().saturating_sub(1))
+        .any(|field| matches!(field.kind, FieldKind::Varint(_)))
+}
+
+/// Moves the offset after a length prefixed field or varint by the number of bytes it takes up beyond its minimal size,
+/// which the offsets of the fields after it are based on
+fn write_offset_move(out: &mut String, field: &FieldInfo, object: &str) {
+    match field.kind {
+        FieldKind::LengthPrefixed { .. } => {
+            writeln!(out, "  offset += {}.{}.length;", object, field.name).unwrap()
+        }
+        FieldKind::Varint(_) => {
+            writeln!(out, "  offset += {}.{}.length - 1;", object, field.name).unwrap()
+        }
+        _ => {}
+    }
+}
+
+/// How the generated code reads and writes a field
+enum Access {
+    /// Use the `DataView` getter/setter with the given name suffix, e.g. `Uint16`
+    Number(&'static str),
+    /// Use the `DataView` getter/setter with the given name suffix, e.g. `BigUint64`
+    BigInt(&'static str),
+    /// Combine two 64 bit halves
+    BigInt128 {
+        signed: bool,
+    },
+    /// Use the `DataView` getter/setter with the given name suffix for each element.
+    /// Open ended arrays don't have a fixed number of elements.
+    Array {
+        name: &'static str,
+        bigint: bool,
+        element_size: usize,
+        len: Option<usize>,
+    },
+    /// Use the functions generated for a nested layout for each element
+    NestedArray {
+        nested: &'static LayoutInfo,
+        element_size: usize,
+        len: Option<usize>,
+    },
+    Bytes(Option<usize>),
+    /// Use the `DataView` getter/setter for an unsigned integer of the given size for the length prefix, followed by that many bytes
+    LengthPrefixed {
+        prefix_size: usize,
+    },
+    Nested(&'static LayoutInfo),
+    Unit,
+}
+
+fn access(field: &FieldInfo) -> Access {
+    match (field.kind, field.size) {
+        (FieldKind::Nested(nested), _) => Access::Nested(nested),
+        (FieldKind::Unit, _) => Access::Unit,
+        (FieldKind::LengthPrefixed { prefix_size }, _) => Access::LengthPrefixed { prefix_size },
+        (FieldKind::UnsignedInteger, Some(16)) => Access::BigInt128 { signed: false },
+        (FieldKind::SignedInteger, Some(16)) => Access::BigInt128 { signed: true },
+        (
+            FieldKind::Array {
+                element: FieldKind::Nested(nested),
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => Access::NestedArray {
+            nested,
+            element_size,
+            len: size.map(|size| size / element_size),
+        },
+        (
+            FieldKind::Array {
+                element,
+                element_size,
+            },
+            size,
+        ) if element_size > 0 => {
+            let len = size.map(|size| size / element_size);
+            match number_access(*element, element_size) {
+                Some(Access::Number(name)) => Access::Array {
+                    name,
+                    bigint: false,
+                    element_size,
+                    len,
+                },
+                Some(Access::BigInt(name)) => Access::Array {
+                    name,
+                    bigint: true,
+                    element_size,
+                    len,
+                },
+                _ => Access::Bytes(size),
+            }
+        }
+        (kind, Some(size)) => number_access(kind, size).unwrap_or(Access::Bytes(Some(size))),
+        (_, None) => Access::Bytes(None),
+    }
+}
+
+/// Returns how to access a number of the given kind and size with a single `DataView` getter/setter, if possible
+fn number_access(kind: FieldKind, size: usize) -> Option<Access> {
+    match (kind, size) {
+        (FieldKind::UnsignedInteger, 1) => Some(Access::Number("Uint8")),
+        (FieldKind::UnsignedInteger, 2) => Some(Access::Number("Uint16")),
+        (FieldKind::UnsignedInteger, 4) => Some(Access::Number("Uint32")),
+        (FieldKind::UnsignedInteger, 8) => Some(Access::BigInt("BigUint64")),
+        (FieldKind::SignedInteger, 1) => Some(Access::Number("Int8")),
+        (FieldKind::SignedInteger, 2) => Some(Access::Number("Int16")),
+        (FieldKind::SignedInteger, 4) => Some(Access::Number("Int32")),
+        (FieldKind::SignedInteger, 8) => Some(Access::BigInt("BigInt64")),
+        (FieldKind::Float, 4) => Some(Access::Number("Float32")),
+        (FieldKind::Float, 8) => Some(Access::Number("Float64")),
+        _ => None,
+    }
+}
+
+/// Returns an expression reading the field. Varints followed by other fields are read up to their last byte instead of the end of the view.
+fn decode_expression(field: &FieldInfo, little_endian: &str, followed: bool) -> String {
+    let position = format!("offset + {}", field.offset);
+    if let (FieldKind::Varint(_), true) = (field.kind, followed) {
+        return format!(
+            "new Uint8Array(view.buffer, view.byteOffset + {0}, varintLength(view, {0})).slice()",
+            position
+        );
+    }
+    match access(field) {
+        Access::Number("Uint8") | Access::Number("Int8") => {
+            format!("view.get{}({})", access_name(field), position)
+        }
+        Access::Number(name) | Access::BigInt(name) => {
+            format!("view.get{}({}, {})", name, position, little_endian)
+        }
+        Access::BigInt128 { signed } => {
+            let (low, high) = halves(field.offset, little_endian);
+            let value = format!(
+                "(view.getBigUint64({}, {le}) << 64n) | view.getBigUint64({}, {le})",
+                high,
+                low,
+                le = little_endian
+            );
+            if signed {
+                format!("BigInt.asIntN(128, {})", value)
+            } else {
+                value
+            }
+        }
+        Access::Array {
+            name,
+            element_size,
+            len,
+            ..
+        } => format!(
+            "Array.from({{ length: {} }}, (_, index) => view.get{}({}))",
+            array_len(&position, element_size, len),
+            name,
+            element_arguments(name, field.offset, element_size, little_endian, None)
+        ),
+        Access::NestedArray {
+            nested,
+            element_size,
+            len,
+        } => format!(
+            "Array.from({{ length: {} }}, (_, index) => decode{}(view, {} + index * {}))",
+            array_len(&position, element_size, len),
+            pascal_case(nested.name),
+            position,
+            element_size
+        ),
+        Access::Bytes(Some(size)) => format!(
+            "new Uint8Array(view.buffer, view.byteOffset + {}, {}).slice()",
+            position, size
+        ),
+        Access::Bytes(None) => format!(
+            "new Uint8Array(view.buffer, view.byteOffset + {0}, view.byteLength - ({0})).slice()",
+            position
+        ),
+        Access::LengthPrefixed { prefix_size } => {
+            let len = match prefix_size {
+                1 => format!("view.getUint8({})", position),
+                8 => format!("Number(view.getBigUint64({}, {}))", position, little_endian),
+                _ => format!(
+                    "view.getUint{}({}, {})",
+                    prefix_size * 8,
+                    position,
+                    little_endian
+                ),
+            };
+            format!(
+                "new Uint8Array(view.buffer, view.byteOffset + {} + {}, {}).slice()",
+                position, prefix_size, len
+            )
+        }
+        Access::Nested(nested) => format!("decode{}(view, {})", pascal_case(nested.name), position),
+        Access::Unit => "null".to_string(),
+    }
+}
+
+fn write_encode_statements(out: &mut String, field: &FieldInfo, little_endian: &str) {
+    let position = format!("offset + {}", field.offset);
+    let value = format!("value.{}", field.name);
+    match access(field) {
+        Access::Number("Uint8") | Access::Number("Int8") => writeln!(
+            out,
+            "  view.set{}({}, {});",
+            access_name(field),
+            position,
+            value
+        )
+        .unwrap(),
+        Access::Number(name) | Access::BigInt(name) => writeln!(
+            out,
+            "  view.set{}({}, {}, {});",
+            name, position, value, little_endian
+        )
+        .unwrap(),
+        Access::BigInt128 { .. } => {
+            let (low, high) = halves(field.offset, little_endian);
+            writeln!(
+                out,
+                "  view.setBigUint64({}, BigInt.asUintN(64, {}), {});",
+                low, value, little_endian
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "  view.setBigUint64({}, BigInt.asUintN(64, {} >> 64n), {});",
+                high, value, little_endian
+            )
+            .unwrap();
+        }
+        Access::Array {
+            name, element_size, ..
+        } => writeln!(
+            out,
+            "  {}.forEach((element, index) => view.set{}({}));",
+            value,
+            name,
+            element_arguments(
+                name,
+                field.offset,
+                element_size,
+                little_endian,
+                Some("element")
+            )
+        )
+        .unwrap(),
+        Access::NestedArray {
+            nested,
+            element_size,
+            ..
+        } => writeln!(
+            out,
+            "  {}.forEach((element, index) => encode{}(element, view, {} + index * {}));",
+            value,
+            pascal_case(nested.name),
+            position,
+            element_size
+        )
+        .unwrap(),
+        Access::Bytes(Some(size)) => writeln!(
+            out,
+            "  new Uint8Array(view.buffer, view.byteOffset + {}, {}).set({});",
+            position, size, value
+        )
+        .unwrap(),
+        Access::Bytes(None) => writeln!(
+            out,
+            "  new Uint8Array(view.buffer, view.byteOffset + {}, {1}.length).set({1});",
+            position, value
+        )
+        .unwrap(),
+        Access::LengthPrefixed { prefix_size } => {
+            match prefix_size {
+                1 => writeln!(out, "  view.setUint8({}, {}.length);", position, value),
+                8 => writeln!(
+                    out,
+                    "  view.setBigUint64({}, BigInt({}.length), {});",
+                    position, value, little_endian
+                ),
+                _ => writeln!(
+                    out,
+                    "  view.setUint{}({}, {}.length, {});",
+                    prefix_size * 8,
+                    position,
+                    value,
+                    little_endian
+                ),
+            }
+            .unwrap();
+            writeln!(
+                out,
+                "  new Uint8Array(view.buffer, view.byteOffset + {} + {}, {2}.length).set({2});",
+                position, prefix_size, value
+            )
+            .unwrap();
+        }
+        Access::Nested(nested) => writeln!(
+            out,
+            "  encode{}({}, view, {});",
+            pascal_case(nested.name),
+            value,
+            position
+        )
+        .unwrap(),
+        Access::Unit => {}
+    }
+}
+
+fn access_name(field: &FieldInfo) -> &'static str {
+    match access(field) {
+        Access::Number(name) | Access::BigInt(name) => name,
+        _ => unreachable!("Field doesn't use a DataView accessor"),
+    }
+}
+
+/// Returns an expression for the number of elements of an array,
+/// which for open ended arrays are all whole elements fitting into the rest of the view
+fn array_len(position: &str, element_size: usize, len: Option<usize>) -> String {
+    match len {
+        Some(len) => len.to_string(),
+        None => format!(
+            "Math.floor((view.byteLength - ({})) / {})",
+            position, element_size
+        ),
+    }
+}
+
+/// Returns the arguments for the `DataView` getter or setter of the array element at `index`,
+/// with the given value for setters and without endianness for single byte elements
+fn element_arguments(
+    name: &str,
+    offset: usize,
+    element_size: usize,
+    little_endian: &str,
+    value: Option<&str>,
+) -> String {
+    let mut arguments = format!("offset + {} + index * {}", offset, element_size);
+    if let Some(value) = value {
+        write!(arguments, ", {}", value).unwrap();
+    }
+    if !matches!(name, "Uint8" | "Int8") {
+        write!(arguments, ", {}", little_endian).unwrap();
+    }
+    arguments
+}
+
+/// Returns expressions for the positions of the lower and upper 64 bit half of a 128 bit field
+fn halves(offset: usize, little_endian: &str) -> (String, String) {
+    let first = format!("offset + {}", offset);
+    let second = format!("offset + {}", offset + 8);
+    match little_endian {
+        "true" => (first, second),
+        "false" => (second, first),
+        _ => (
+            format!("offset + ({} ? {} : {})", little_endian, offset, offset + 8),
+            format!("offset + ({} ? {} : {})", little_endian, offset + 8, offset),
+        ),
+    }
+}
+
+fn endianness_description(endianness: EndianKind) -> &'static str {
+    match endianness {
+        EndianKind::Big => "big endian",
+        EndianKind::Little => "little endian",
+        EndianKind::Native => "native endian (same as the platform)",
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn primitive_types() {
+        binary_layout!(my_layout, LittleEndian, {
+            field1: i8,
+            field2: u32,
+            field3: i64,
+            field4: f32,
+            field5: f64,
+            field6: i128,
+            field7: core::num::NonZeroU16,
+            field8: (),
+            field9: [u8; 3],
+        });
+        let code = to_typescript(&my_layout::LAYOUT);
+        assert!(!code.contains("NATIVE_LITTLE_ENDIAN"));
+        assert!(code.contains(
+            r#"export interface MyLayout {
+  field1: number;
+  field2: number;
+  field3: bigint;
+  field4: number;
+  field5: number;
+  field6: bigint;
+  field7: number;
+  field8: null;
+  field9: Uint8Array;
+}
+
+export const MY_LAYOUT_SIZE: number | null = 46;
+"#
+        ));
+        assert!(code.contains(
+            r#"  return {
+    field1: view.getInt8(offset + 0),
+    field2: view.getUint32(offset + 1, true),
+    field3: view.getBigInt64(offset + 5, true),
+    field4: view.getFloat32(offset + 13, true),
+    field5: view.getFloat64(offset + 17, true),
+    field6: BigInt.asIntN(128, (view.getBigUint64(offset + 33, true) << 64n) | view.getBigUint64(offset + 25, true)),
+    field7: view.getUint16(offset + 41, true),
+    field8: null,
+    field9: new Uint8Array(view.buffer, view.byteOffset + offset + 43, 3).slice(),
+  };
+"#
+        ));
+        assert!(code.contains(
+            r#"export function encodeMyLayout(value: MyLayout, view: DataView, offset = 0): void {
+  view.setInt8(offset + 0, value.field1);
+  view.setUint32(offset + 1, value.field2, true);
+  view.setBigInt64(offset + 5, value.field3, true);
+  view.setFloat32(offset + 13, value.field4, true);
+  view.setFloat64(offset + 17, value.field5, true);
+  view.setBigUint64(offset + 25, BigInt.asUintN(64, value.field6), true);
+  view.setBigUint64(offset + 33, BigInt.asUintN(64, value.field6 >> 64n), true);
+  view.setUint16(offset + 41, value.field7, true);
+  new Uint8Array(view.buffer, view.byteOffset + offset + 43, 3).set(value.field9);
+}
+"#
+        ));
+    }
+
+    #[test]
+    fn arrays() {
+        binary_layout!(my_layout, BigEndian, {
+            samples: [u16; 8],
+            counters: [u64; 2],
+            small: [i8; 3],
+        });
+        let code = to_typescript(&my_layout::LAYOUT);
+        assert!(code.contains("  samples: number[];\n  counters: bigint[];\n"));
+        assert!(code.contains(
+            r#"  return {
+    samples: Array.from({ length: 8 }, (_, index) => view.getUint16(offset + 0 + index * 2, false)),
+    counters: Array.from({ length: 2 }, (_, index) => view.getBigUint64(offset + 16 + index * 8, false)),
+    small: Array.from({ length: 3 }, (_, index) => view.getInt8(offset + 32 + index * 1)),
+  };
+"#
+        ));
+        assert!(code.contains(
+            r#"  value.samples.forEach((element, index) => view.setUint16(offset + 0 + index * 2, element, false));
+  value.counters.forEach((element, index) => view.setBigUint64(offset + 16 + index * 8, element, false));
+  value.small.forEach((element, index) => view.setInt8(offset + 32 + index * 1, element));
+"#
+        ));
+    }
+
+    binary_layout!(record, BigEndian, {
+        id: u16,
+        flags: u8,
+    });
+    binary_layout!(table, BigEndian, {
+        records: [record::NestedView],
+    });
+
+    #[test]
+    fn open_ended_arrays() {
+        binary_layout!(numbers, LittleEndian, {
+            values: [u32],
+        });
+        let code = to_typescript(&numbers::LAYOUT);
+        assert!(code.contains(
+            "values: Array.from({ length: Math.floor((view.byteLength - (offset + 0)) / 4) }, (_, index) => view.getUint32(offset + 0 + index * 4, true)),"
+        ));
+        assert!(code.contains(
+            "value.values.forEach((element, index) => view.setUint32(offset + 0 + index * 4, element, true));"
+        ));
+        let code = to_typescript(&table::LAYOUT);
+        assert!(code.contains("export function decodeRecord("));
+        assert!(code.contains("  records: Record[];\n"));
+        assert!(code.contains(
+            "records: Array.from({ length: Math.floor((view.byteLength - (offset + 0)) / 3) }, (_, index) => decodeRecord(view, offset + 0 + index * 3)),"
+        ));
+        assert!(code.contains(
+            "value.records.forEach((element, index) => encodeRecord(element, view, offset + 0 + index * 3));"
+        ));
+    }
+
+    #[test]
+    fn length_prefixed() {
+        binary_layout!(message, LittleEndian, {
+            kind: u8,
+            payload: crate::LengthPrefixed<u16>,
+        });
+        let code = to_typescript(&message::LAYOUT);
+        assert!(code.contains("  payload: Uint8Array;\n"));
+        assert!(code.contains(
+            "payload: new Uint8Array(view.buffer, view.byteOffset + offset + 1 + 2, view.getUint16(offset + 1, true)).slice(),"
+        ));
+        assert!(code.contains(
+            r#"  view.setUint16(offset + 1, value.payload.length, true);
+  new Uint8Array(view.buffer, view.byteOffset + offset + 1 + 2, value.payload.length).set(value.payload);
+"#
+        ));
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        binary_layout!(named, BigEndian, {
+            kind: u8,
+            name: crate::LengthPrefixed<u16>,
+            delta: crate::ZigZag<i32>,
+            crc: u32,
+        });
+        let code = to_typescript(&named::LAYOUT);
+        assert!(
+            code.contains("function varintLength(view: DataView, position: number): number {\n")
+        );
+        assert!(code.contains(
+            r#"  const result = {} as Named;
+  result.kind = view.getUint8(offset + 0);
+  result.name = new Uint8Array(view.buffer, view.byteOffset + offset + 1 + 2, view.getUint16(offset + 1, false)).slice();
+  offset += result.name.length;
+  result.delta = new Uint8Array(view.buffer, view.byteOffset + offset + 3, varintLength(view, offset + 3)).slice();
+  offset += result.delta.length - 1;
+  result.crc = view.getUint32(offset + 4, false);
+  return result;
+"#
+        ));
+        assert!(code.contains(
+            r#"  offset += value.name.length;
+  new Uint8Array(view.buffer, view.byteOffset + offset + 3, value.delta.length).set(value.delta);
+  offset += value.delta.length - 1;
+  view.setUint32(offset + 4, value.crc, false);
+"#
+        ));
+    }
+
+    #[test]
+    fn native_endian() {
+        binary_layout!(my_layout, NativeEndian, {
+            field1: u16,
+            field2: u128,
+        });
+        let code = to_typescript(&my_layout::LAYOUT);
+        assert!(code.contains("const NATIVE_LITTLE_ENDIAN = "));
+        assert!(code.contains("view.getUint16(offset + 0, NATIVE_LITTLE_ENDIAN)"));
+        assert!(code.contains(
+            "view.setBigUint64(offset + (NATIVE_LITTLE_ENDIAN ? 2 : 10), BigInt.asUintN(64, value.field2), NATIVE_LITTLE_ENDIAN);"
+        ));
+    }
+
+    mod nested {
+        use crate::prelude::*;
+
+        binary_layout!(deep, LittleEndian, {
+            value: u16,
+        });
+        binary_layout!(header, BigEndian, {
+            deep1: deep::NestedView,
+            deep2: deep::NestedView,
+        });
+        binary_layout!(packet, BigEndian, {
+            other: deep::NestedView,
+            head: header::NestedView,
+            payload: [u8],
+        });
+    }
+
+    #[test]
+    fn nested_layouts() {
+        let code = to_typescript(&nested::packet::LAYOUT);
+        let deep = code.find("export interface Deep {").unwrap();
+        let header = code.find("export interface Header {").unwrap();
+        let packet = code.find("export interface Packet {").unwrap();
+        assert!(deep < header && header < packet);
+        assert_eq!(1, code.matches("export interface Deep {").count());
+        assert!(code.contains(
+            r#"  return {
+    other: decodeDeep(view, offset + 0),
+    head: decodeHeader(view, offset + 2),
+    payload: new Uint8Array(view.buffer, view.byteOffset + offset + 6, view.byteLength - (offset + 6)).slice(),
+  };
+"#
+        ));
+        assert!(code.contains(
+            r#"  encodeDeep(value.other, view, offset + 0);
+  encodeHeader(value.head, view, offset + 2);
+  new Uint8Array(view.buffer, view.byteOffset + offset + 6, value.payload.length).set(value.payload);
+"#
+        ));
+    }
+
+    #[test]
+    fn converts_names_to_pascal_case() {
+        assert_eq!("IcmpPacket", pascal_case("icmp_packet"));
+        assert_eq!("Header", pascal_case("header"));
+    }
+}
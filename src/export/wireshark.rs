@@ -0,0 +1,504 @@
+//! Exports layouts as [Wireshark](https://www.wireshark.org) dissectors written in Lua.
+//!
+//! The generated dissector declares one protocol field per layout field and adds all of them to the
+//! packet details tree, so captured traffic can be inspected and filtered by field, e.g. `my_layout.field1 == 5`.
+//! It's meant as a starting point: registering it for the right port or encapsulation is up to you,
+//! and so is adding value strings for fields that represent enums.
+//!
+//! Fields are mapped to Wireshark field types as follows:
+//! - Integers up to 64 bits and floats map to the Wireshark type of the same size, e.g. `u16` becomes `uint16` and `f64` becomes `double`.
+//!   128 bit integers are exported as `bytes`.
+//! - Byte arrays `[u8; N]` and open ended byte slices `[u8]` become `bytes`.
+//! - Arrays of numbers like `[u16; 8]` or `[u32]` are exported as `bytes`, with the array type noted in a comment.
+//! - Arrays of nested layouts like `[my_layout::NestedView]` become subtrees with a subtree for each element.
+//! - Bit fields are exported as `bytes` covering all bytes the bit field spans, with the bit field's type noted in a comment.
+//! - Nested layouts become subtrees with their own fields.
+//! - Fields using a custom type via [LayoutAs](crate::LayoutAs) are exported as their underlying type,
+//!   with the custom type noted in a comment.
+//! - `()` fields don't take up any space and are skipped.
+//! - [LengthPrefixed](crate::LengthPrefixed) fields and varints like [Leb128](crate::Leb128) become `bytes` covering the whole field.
+//!   If other fields follow them, the dissector reads their length from the packet and moves the offset of the following fields accordingly.
+//!
+//! Lua dissectors don't have a native endianness, so layouts using [NativeEndian](crate::NativeEndian)
+//! are exported with the endianness of the platform running the export.
+
+use std::fmt::Write;
+
+use super::collect_nested_layouts;
+use crate::{EndianKind, FieldInfo, FieldKind, LayoutInfo};
+
+/// Return a Lua dissector for the given layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{export::wireshark, prelude::*};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"-- Generated by binary-layout. Do not edit.
+/// local my_layout_proto = Proto("my_layout", "my_layout")
+///
+/// local f_my_layout_field1 = ProtoField.uint16("my_layout.field1", "field1", base.DEC)
+/// local f_my_layout_field2 = ProtoField.uint8("my_layout.field2", "field2", base.DEC) -- bool as u8
+/// local f_my_layout_tail = ProtoField.bytes("my_layout.tail", "tail")
+///
+/// my_layout_proto.fields = {
+///     f_my_layout_field1,
+///     f_my_layout_field2,
+///     f_my_layout_tail,
+/// }
+///
+/// local function dissect_my_layout(buffer, offset, tree)
+///     tree:add(f_my_layout_field1, buffer(offset + 0, 2))
+///     tree:add(f_my_layout_field2, buffer(offset + 2, 1))
+///     tree:add(f_my_layout_tail, buffer(offset + 3))
+/// end
+///
+/// function my_layout_proto.dissector(buffer, pinfo, tree)
+///     pinfo.cols.protocol = my_layout_proto.name
+///     local subtree = tree:add(my_layout_proto, buffer(), "my_layout")
+///     dissect_my_layout(buffer, 0, subtree)
+/// end
+///
+/// -- Register the dissector, e.g. for a UDP port:
+/// -- DissectorTable.get("udp.port"):add(12345, my_layout_proto)
+/// "#,
+///     wireshark::to_lua_dissector(&my_layout::LAYOUT),
+/// );
+/// ```
+pub fn to_lua_dissector(layout: &LayoutInfo) -> String {
+    let mut nested_layouts = Vec::new();
+    collect_nested_layouts(layout, &mut nested_layouts);
+    let mut layouts: Vec<&LayoutInfo> = nested_layouts;
+    layouts.push(layout);
+
+    let proto = format!("{}_proto", layout.name);
+    let mut out = String::new();
+    out.push_str("-- Generated by binary-layout. Do not edit.\n");
+    writeln!(
+        out,
+        "local {} = Proto(\"{}\", \"{}\")",
+        proto, layout.name, layout.name
+    )
+    .unwrap();
+
+    out.push('\n');
+    for current in &layouts {
+        for field in current.fields {
+            write_proto_field(&mut out, layout, current, field);
+        }
+    }
+
+    out.push('\n');
+    writeln!(out, "{}.fields = {{", proto).unwrap();
+    for current in &layouts {
+        for field in current.fields {
+            if field_type(field).is_some() {
+                writeln!(out, "    {},", variable(current, field)).unwrap();
+            }
+        }
+    }
+    out.push_str("}\n");
+
+    if layouts.iter().any(|current| has_followed_varint(current)) {
+        out.push('\n');
+        out.push_str("-- Number of bytes of the varint at the given position, up to the first byte without the continuation bit\n");
+        out.push_str("local function varint_length(buffer, position)\n");
+        out.push_str("    local length = 1\n");
+        out.push_str("    while position + length < buffer:len() and buffer(position + length - 1, 1):uint() >= 0x80 do\n");
+        out.push_str("        length = length + 1\n");
+        out.push_str("    end\n");
+        out.push_str("    return length\n");
+        out.push_str("end\n");
+    }
+    for current in &layouts {
+        write_dissect_function(&mut out, current);
+    }
+
+    out.push('\n');
+    writeln!(out, "function {}.dissector(buffer, pinfo, tree)", proto).unwrap();
+    writeln!(out, "    pinfo.cols.protocol = {}.name", proto).unwrap();
+    writeln!(
+        out,
+        "    local subtree = tree:add({}, buffer(), \"{}\")",
+        proto, layout.name
+    )
+    .unwrap();
+    writeln!(out, "    dissect_{}(buffer, 0, subtree)", layout.name).unwrap();
+    out.push_str("end\n");
+
+    out.push('\n');
+    out.push_str("-- Register the dissector, e.g. for a UDP port:\n");
+    writeln!(
+        out,
+        "-- DissectorTable.get(\"udp.port\"):add(12345, {})",
+        proto
+    )
+    .unwrap();
+    out
+}
+
+fn write_proto_field(out: &mut String, root: &LayoutInfo, layout: &LayoutInfo, field: &FieldInfo) {
+    let field_type = match field_type(field) {
+        Some(field_type) => field_type,
+        None => return,
+    };
+    let filter_name = if layout.name == root.name {
+        format!("{}.{}", root.name, field.name)
+    } else {
+        format!("{}.{}.{}", root.name, layout.name, field.name)
+    };
+    let arguments = match field_type {
+        "uint8" | "uint16" | "uint32" | "uint64" | "int8" | "int16" | "int32" | "int64" => {
+            ", base.DEC"
+        }
+        _ => "",
+    };
+    write!(
+        out,
+        "local {} = ProtoField.{}(\"{}\", \"{}\"{})",
+        variable(layout, field),
+        field_type,
+        filter_name,
+        field.name,
+        arguments
+    )
+    .unwrap();
+    if field.type_name.contains(" as ") || is_unsupported_integer(field) {
+        write!(out, " -- {}", field.type_name).unwrap();
+    }
+    out.push('\n');
+}
+
+fn write_dissect_function(out: &mut String, layout: &LayoutInfo) {
+    let add = match endianness(layout.endianness) {
+        EndianKind::Little => "add_le",
+        _ => "add",
+    };
+    out.push('\n');
+    writeln!(
+        out,
+        "local function dissect_{}(buffer, offset, tree)",
+        layout.name
+    )
+    .unwrap();
+    for (index, field) in layout.fields.iter().enumerate() {
+        let followed = index + 1 < layout.fields.len();
+        let range = match (field.kind, field.size) {
+            (_, Some(size)) => format!("buffer(offset + {}, {})", field.offset, size),
+            (FieldKind::LengthPrefixed { prefix_size }, None) if followed => {
+                let read = match endianness(layout.endianness) {
+                    EndianKind::Little => "le_uint",
+                    _ => "uint",
+                };
+                writeln!(
+                    out,
+                    "    local {}_length = buffer(offset + {}, {}):{}()",
+                    field.name, field.offset, prefix_size, read
+                )
+                .unwrap();
+                format!(
+                    "buffer(offset + {}, {} + {}_length)",
+                    field.offset, prefix_size, field.name
+                )
+            }
+            (FieldKind::Varint(_), None) if followed => {
+                writeln!(
+                    out,
+                    "    local {}_length = varint_length(buffer, offset + {})",
+                    field.name, field.offset
+                )
+                .unwrap();
+                format!("buffer(offset + {}, {}_length)", field.offset, field.name)
+            }
+            (_, None) => format!("buffer(offset + {})", field.offset),
+        };
+        match field.kind {
+            FieldKind::Unit => {}
+            FieldKind::Nested(nested) => {
+                writeln!(
+                    out,
+                    "    dissect_{}(buffer, offset + {}, tree:add({}, \"{}\"))",
+                    nested.name, field.offset, range, field.name
+                )
+                .unwrap();
+            }
+            FieldKind::Array {
+                element: &FieldKind::Nested(nested),
+                element_size,
+            } if element_size > 0 => {
+                // Each element gets its own subtree, below a subtree for the whole array
+                let count = match field.size {
+                    Some(size) => (size / element_size).to_string(),
+                    None => format!(
+                        "math.floor((buffer:len() - offset - {}) / {})",
+                        field.offset, element_size
+                    ),
+                };
+                let element = format!("offset + {} + i * {}", field.offset, element_size);
+                writeln!(
+                    out,
+                    "    local {}_tree = tree:add({}, \"{}\")",
+                    field.name, range, field.name
+                )
+                .unwrap();
+                writeln!(out, "    for i = 0, {} - 1 do", count).unwrap();
+                writeln!(
+                    out,
+                    "        dissect_{}(buffer, {}, {}_tree:add(buffer({}, {}), \"{}[\" .. i .. \"]\"))",
+                    nested.name, element, field.name, element, element_size, field.name
+                )
+                .unwrap();
+                out.push_str("    end\n");
+            }
+            _ => {
+                writeln!(
+                    out,
+                    "    tree:{}({}, {})",
+                    add,
+                    variable(layout, field),
+                    range
+                )
+                .unwrap();
+            }
+        }
+        // The offsets of the following fields assume the minimal size, so move them by the bytes beyond it
+        if followed {
+            match field.kind {
+                FieldKind::LengthPrefixed { .. } => {
+                    writeln!(out, "    offset = offset + {}_length", field.name).unwrap();
+                }
+                FieldKind::Varint(_) => {
+                    writeln!(out, "    offset = offset + {}_length - 1", field.name).unwrap();
+                }
+                _ => {}
+            }
+        }
+    }
+    out.push_str("end\n");
+}
+
+/// Whether the layout has a varint that isn't its last field, so its length needs to be computed
+fn has_followed_varint(layout: &LayoutInfo) -> bool {
+    let fields = layout.fields;
+    fields
+        .iter()
+        .take(fields.len().saturating_sub(1))
+        .any(|field| matches!(field.kind, FieldKind::Varint(_)))
+}
+
+/// Returns the name of the `ProtoField` constructor for the field,
+/// or `None` if the field doesn't get its own protocol field.
+fn field_type(field: &FieldInfo) -> Option<&'static str> {
+    match (field.kind, field.size) {
+        (FieldKind::Unit, _)
+        | (FieldKind::Nested(_), _)
+        | (
+            FieldKind::Array {
+                element: FieldKind::Nested(_),
+                ..
+            },
+            _,
+        ) => None,
+        (FieldKind::UnsignedInteger, Some(1)) => Some("uint8"),
+        (FieldKind::UnsignedInteger, Some(2)) => Some("uint16"),
+        (FieldKind::UnsignedInteger, Some(4)) => Some("uint32"),
+        (FieldKind::UnsignedInteger, Some(8)) => Some("uint64"),
+        (FieldKind::SignedInteger, Some(1)) => Some("int8"),
+        (FieldKind::SignedInteger, Some(2)) => Some("int16"),
+        (FieldKind::SignedInteger, Some(4)) => Some("int32"),
+        (FieldKind::SignedInteger, Some(8)) => Some("int64"),
+        (FieldKind::Float, Some(4)) => Some("float"),
+        (FieldKind::Float, Some(8)) => Some("double"),
+        _ => Some("bytes"),
+    }
+}
+
+/// Whether the field is an integer that Wireshark doesn't have a field type for
+fn is_unsupported_integer(field: &FieldInfo) -> bool {
+    matches!(
+        field.kind,
+        FieldKind::UnsignedInteger
+            | FieldKind::SignedInteger
+            | FieldKind::Float
+            | FieldKind::Bits { .. }
+            | FieldKind::Array { .. }
+    ) && field_type(field) == Some("bytes")
+}
+
+fn variable(layout: &LayoutInfo, field: &FieldInfo) -> String {
+    format!("f_{}_{}", layout.name, field.name)
+}
+
+/// Resolves native endianness to the endianness of the platform running the export
+fn endianness(endianness: EndianKind) -> EndianKind {
+    match endianness {
+        EndianKind::Native if cfg!(target_endian = "big") => EndianKind::Big,
+        EndianKind::Native => EndianKind::Little,
+        endianness => endianness,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn primitive_types() {
+        binary_layout!(my_layout, LittleEndian, {
+            field1: i8,
+            field2: u32,
+            field3: i64,
+            field4: f32,
+            field5: f64,
+            field6: i128,
+            field7: core::num::NonZeroU16,
+            field8: (),
+            field9: [u8; 3],
+        });
+        let code = to_lua_dissector(&my_layout::LAYOUT);
+        assert!(code.contains(
+            r#"local f_my_layout_field1 = ProtoField.int8("my_layout.field1", "field1", base.DEC)
+local f_my_layout_field2 = ProtoField.uint32("my_layout.field2", "field2", base.DEC)
+local f_my_layout_field3 = ProtoField.int64("my_layout.field3", "field3", base.DEC)
+local f_my_layout_field4 = ProtoField.float("my_layout.field4", "field4")
+local f_my_layout_field5 = ProtoField.double("my_layout.field5", "field5")
+local f_my_layout_field6 = ProtoField.bytes("my_layout.field6", "field6") -- i128
+local f_my_layout_field7 = ProtoField.uint16("my_layout.field7", "field7", base.DEC)
+local f_my_layout_field9 = ProtoField.bytes("my_layout.field9", "field9")
+"#
+        ));
+        assert!(code.contains(
+            r#"local function dissect_my_layout(buffer, offset, tree)
+    tree:add_le(f_my_layout_field1, buffer(offset + 0, 1))
+    tree:add_le(f_my_layout_field2, buffer(offset + 1, 4))
+    tree:add_le(f_my_layout_field3, buffer(offset + 5, 8))
+    tree:add_le(f_my_layout_field4, buffer(offset + 13, 4))
+    tree:add_le(f_my_layout_field5, buffer(offset + 17, 8))
+    tree:add_le(f_my_layout_field6, buffer(offset + 25, 16))
+    tree:add_le(f_my_layout_field7, buffer(offset + 41, 2))
+    tree:add_le(f_my_layout_field9, buffer(offset + 43, 3))
+end
+"#
+        ));
+        assert!(!code.contains("field8"));
+    }
+
+    mod nested {
+        use crate::prelude::*;
+
+        binary_layout!(deep, LittleEndian, {
+            value: u16,
+        });
+        binary_layout!(header, BigEndian, {
+            deep1: deep::NestedView,
+            deep2: deep::NestedView,
+        });
+        binary_layout!(packet, BigEndian, {
+            other: deep::NestedView,
+            head: header::NestedView,
+            payload: [u8],
+        });
+    }
+
+    #[test]
+    fn nested_layouts() {
+        assert_eq!(
+            r#"-- Generated by binary-layout. Do not edit.
+local packet_proto = Proto("packet", "packet")
+
+local f_deep_value = ProtoField.uint16("packet.deep.value", "value", base.DEC)
+local f_packet_payload = ProtoField.bytes("packet.payload", "payload")
+
+packet_proto.fields = {
+    f_deep_value,
+    f_packet_payload,
+}
+
+local function dissect_deep(buffer, offset, tree)
+    tree:add_le(f_deep_value, buffer(offset + 0, 2))
+end
+
+local function dissect_header(buffer, offset, tree)
+    dissect_deep(buffer, offset + 0, tree:add(buffer(offset + 0, 2), "deep1"))
+    dissect_deep(buffer, offset + 2, tree:add(buffer(offset + 2, 2), "deep2"))
+end
+
+local function dissect_packet(buffer, offset, tree)
+    dissect_deep(buffer, offset + 0, tree:add(buffer(offset + 0, 2), "other"))
+    dissect_header(buffer, offset + 2, tree:add(buffer(offset + 2, 4), "head"))
+    tree:add(f_packet_payload, buffer(offset + 6))
+end
+
+function packet_proto.dissector(buffer, pinfo, tree)
+    pinfo.cols.protocol = packet_proto.name
+    local subtree = tree:add(packet_proto, buffer(), "packet")
+    dissect_packet(buffer, 0, subtree)
+end
+
+-- Register the dissector, e.g. for a UDP port:
+-- DissectorTable.get("udp.port"):add(12345, packet_proto)
+"#,
+            to_lua_dissector(&nested::packet::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        binary_layout!(named, LittleEndian, {
+            kind: u8,
+            name: crate::LengthPrefixed<u16>,
+            delta: crate::ZigZag<i32>,
+            crc: u32,
+        });
+        let code = to_lua_dissector(&named::LAYOUT);
+        assert!(code.contains("local function varint_length(buffer, position)\n"));
+        assert!(code.contains(
+            r#"local function dissect_named(buffer, offset, tree)
+    tree:add_le(f_named_kind, buffer(offset + 0, 1))
+    local name_length = buffer(offset + 1, 2):le_uint()
+    tree:add_le(f_named_name, buffer(offset + 1, 2 + name_length))
+    offset = offset + name_length
+    local delta_length = varint_length(buffer, offset + 3)
+    tree:add_le(f_named_delta, buffer(offset + 3, delta_length))
+    offset = offset + delta_length - 1
+    tree:add_le(f_named_crc, buffer(offset + 4, 4))
+end
+"#
+        ));
+    }
+
+    binary_layout!(entry, BigEndian, {
+        id: u16,
+        flags: u8,
+    });
+    binary_layout!(table, BigEndian, {
+        count: u8,
+        entries: [entry::NestedView],
+    });
+
+    #[test]
+    fn arrays_of_nested_layouts() {
+        let code = to_lua_dissector(&table::LAYOUT);
+        assert!(code.contains(
+            "local f_entry_id = ProtoField.uint16(\"table.entry.id\", \"id\", base.DEC)\n"
+        ));
+        assert!(!code.contains("f_table_entries"));
+        assert!(code.contains(
+            r#"local function dissect_table(buffer, offset, tree)
+    tree:add(f_table_count, buffer(offset + 0, 1))
+    local entries_tree = tree:add(buffer(offset + 1), "entries")
+    for i = 0, math.floor((buffer:len() - offset - 1) / 3) - 1 do
+        dissect_entry(buffer, offset + 1 + i * 3, entries_tree:add(buffer(offset + 1 + i * 3, 3), "entries[" .. i .. "]"))
+    end
+end
+"#
+        ));
+    }
+}
@@ -0,0 +1,417 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use super::primitive::{FieldCopyAccess, FieldView};
+use super::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+use crate::IntegerOutOfRangeError;
+
+/// A [BitField] is a [Field] that takes up only some bits of the storage, e.g. one of the two 4 bit fields packed into the
+/// first byte of an IPv4 header. [binary_layout!](crate::binary_layout!) uses it for fields declared like `version: u8 bits(4)`.
+/// Bit fields can have any unsigned integer type or [bool] and read and write values of that type.
+/// Writing a value that doesn't fit into the bits of the field fails with [IntegerOutOfRangeError].
+///
+/// `OFFSET_` is the offset of the byte that holds the first bit of the field, `BIT_OFFSET` is the position of that bit in the byte
+/// and `BITS` is the number of bits of the field. Consecutive bit fields of a layout are packed the way C compilers do it for the
+/// byte order of the layout, so the layout matches a C struct with bit fields on a platform with that byte order:
+/// - In big endian layouts, the bits are numbered from the most significant bit of each byte and the most significant bit of a value comes first.
+///   E.g. the 4 bit fields `version` and `ihl` take up the upper and lower half of the byte, and a 3 bit field followed by a 13 bit field
+///   split a big endian `u16` into its upper 3 and lower 13 bits.
+/// - In little endian layouts, the bits are numbered from the least significant bit of each byte and the least significant bit of a value comes first.
+///   E.g. a 3 bit field followed by a 13 bit field split a little endian `u16` into its lower 3 and upper 13 bits.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(ipv4_header, BigEndian, {
+///   version: u8 bits(4),
+///   ihl: u8 bits(4),
+///   dscp: u8 bits(6),
+///   ecn: u8 bits(2),
+///   total_length: u16,
+///   identification: u16,
+///   reserved: bool bits(1),
+///   dont_fragment: bool bits(1),
+///   more_fragments: bool bits(1),
+///   fragment_offset: u16 bits(13),
+/// });
+///
+/// let mut view = ipv4_header::View::new([0; 8]);
+/// view.version_mut().try_write(4).unwrap();
+/// view.ihl_mut().try_write(5).unwrap();
+/// view.dont_fragment_mut().write(true);
+/// view.fragment_offset_mut().try_write(0x123).unwrap();
+/// assert_eq!(4, view.version().read());
+/// assert!(view.ihl_mut().try_write(16).is_err());
+/// assert_eq!([0x45, 0, 0, 0, 0, 0, 0x41, 0x23], view.into_storage());
+/// ```
+pub struct BitField<
+    T,
+    E: Endianness,
+    const OFFSET_: usize,
+    const BIT_OFFSET: usize,
+    const BITS: usize,
+> {
+    _p1: PhantomData<T>,
+    _p2: PhantomData<E>,
+}
+
+/// Returns the number of bytes spanned by a bit field, checking that it's a valid bit field for a type with `type_bits` bits.
+const fn bit_field_size(bit_offset: usize, bits: usize, type_bits: u32) -> Option<usize> {
+    assert!(
+        bit_offset < 8,
+        "The bit offset of a bit field must be less than 8"
+    );
+    assert!(
+        bits > 0 && bits <= type_bits as usize,
+        "A bit field must have at least one bit and at most as many bits as its type"
+    );
+    Some((bit_offset + bits + 7) / 8)
+}
+
+/// Read `bits` bits starting at bit `bit_offset` of `storage`, with bits numbered like for a [BitField].
+pub(crate) fn read_bits(storage: &[u8], big_endian: bool, bit_offset: usize, bits: usize) -> u128 {
+    (0..bits).fold(0, |value, index| {
+        let position = bit_offset + index;
+        let byte = storage[position / 8];
+        if big_endian {
+            (value << 1) | u128::from((byte >> (7 - position % 8)) & 1)
+        } else {
+            value | (u128::from((byte >> (position % 8)) & 1) << index)
+        }
+    })
+}
+
+/// Write the lower `bits` bits of `value` into the bits starting at bit `bit_offset` of `storage`, with bits numbered like for a [BitField].
+/// The other bits of `storage` are left unchanged.
+pub(crate) fn write_bits(
+    storage: &mut [u8],
+    big_endian: bool,
+    bit_offset: usize,
+    bits: usize,
+    value: u128,
+) {
+    for index in 0..bits {
+        let position = bit_offset + index;
+        let (bit, mask) = if big_endian {
+            ((value >> (bits - 1 - index)) & 1, 0x80 >> (position % 8))
+        } else {
+            ((value >> index) & 1, 1 << (position % 8))
+        };
+        if bit == 1 {
+            storage[position / 8] |= mask;
+        } else {
+            storage[position / 8] &= !mask;
+        }
+    }
+}
+
+macro_rules! impl_bit_field_traits {
+    ($type: ty, $type_bits: expr) => {
+        impl<E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize> Field
+            for BitField<$type, E, OFFSET_, BIT_OFFSET, BITS>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]. This is the offset of the byte holding the first bit of the field.
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]. This is the number of bytes holding bits of the field.
+            const SIZE: Option<usize> = bit_field_size(BIT_OFFSET, BITS, $type_bits);
+        }
+
+        impl<E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+            FieldKindInfo for BitField<$type, E, OFFSET_, BIT_OFFSET, BITS>
+        {
+            const KIND: FieldKind = FieldKind::Bits {
+                bit_offset: BIT_OFFSET,
+                bits: BITS,
+            };
+        }
+
+        impl<
+                'a,
+                E: Endianness,
+                const OFFSET_: usize,
+                const BIT_OFFSET: usize,
+                const BITS: usize,
+            > StorageToFieldView<&'a [u8]> for BitField<$type, E, OFFSET_, BIT_OFFSET, BITS>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<
+                'a,
+                E: Endianness,
+                const OFFSET_: usize,
+                const BIT_OFFSET: usize,
+                const BITS: usize,
+            > StorageToFieldView<&'a mut [u8]> for BitField<$type, E, OFFSET_, BIT_OFFSET, BITS>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<
+                S: AsRef<[u8]>,
+                E: Endianness,
+                const OFFSET_: usize,
+                const BIT_OFFSET: usize,
+                const BITS: usize,
+            > StorageIntoFieldView<S> for BitField<$type, E, OFFSET_, BIT_OFFSET, BITS>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+macro_rules! int_bit_field {
+    ($type: ty) => {
+        impl<E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+            FieldCopyAccess for BitField<$type, E, OFFSET_, BIT_OFFSET, BITS>
+        {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = IntegerOutOfRangeError;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $type;
+
+            /// Read the bit field from a given data region, assuming the defined layout, using the [Field] API.
+            #[inline]
+            fn try_read(storage: &[u8]) -> Result<$type, Infallible> {
+                let value = read_bits(
+                    &storage[OFFSET_..],
+                    E::KIND.is_big_endian(),
+                    BIT_OFFSET,
+                    BITS,
+                );
+                Ok(value as $type)
+            }
+
+            /// Write the bit field to a given data region, assuming the defined layout, using the [Field] API.
+            /// This fails with [IntegerOutOfRangeError] if the value doesn't fit into the bits of the field.
+            #[inline]
+            fn try_write(storage: &mut [u8], value: $type) -> Result<(), IntegerOutOfRangeError> {
+                let value = value as u128;
+                if value.checked_shr(BITS as u32).unwrap_or(0) != 0 {
+                    return Err(IntegerOutOfRangeError(()));
+                }
+                write_bits(
+                    &mut storage[OFFSET_..],
+                    E::KIND.is_big_endian(),
+                    BIT_OFFSET,
+                    BITS,
+                    value,
+                );
+                Ok(())
+            }
+        }
+
+        impl_bit_field_traits!($type, <$type>::BITS);
+    };
+}
+
+int_bit_field!(u8);
+int_bit_field!(u16);
+int_bit_field!(u32);
+int_bit_field!(u64);
+int_bit_field!(u128);
+
+impl<E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+    FieldCopyAccess for BitField<bool, E, OFFSET_, BIT_OFFSET, BITS>
+{
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = Infallible;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = Infallible;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = bool;
+
+    /// Read the bit field from a given data region, assuming the defined layout, using the [Field] API.
+    #[inline]
+    fn try_read(storage: &[u8]) -> Result<bool, Infallible> {
+        let value = read_bits(
+            &storage[OFFSET_..],
+            E::KIND.is_big_endian(),
+            BIT_OFFSET,
+            BITS,
+        );
+        Ok(value == 1)
+    }
+
+    /// Write the bit field to a given data region, assuming the defined layout, using the [Field] API.
+    #[inline]
+    fn try_write(storage: &mut [u8], value: bool) -> Result<(), Infallible> {
+        write_bits(
+            &mut storage[OFFSET_..],
+            E::KIND.is_big_endian(),
+            BIT_OFFSET,
+            BITS,
+            u128::from(value),
+        );
+        Ok(())
+    }
+}
+
+impl_bit_field_traits!(bool, 1);
+
+#[cfg(test)]
+mod tests {
+    use super::{read_bits, write_bits, BitField};
+    use crate::prelude::*;
+    use crate::{FieldKind, IntegerOutOfRangeError};
+
+    binary_layout!(big, BigEndian, round_trip_tests, {
+        version: u8 bits(4),
+        ihl: u8 bits(4),
+        length: u16,
+        flag: bool bits(1),
+        offset: u16 bits(13),
+        rest: u8 bits(2),
+        tail: [u8],
+    });
+
+    binary_layout!(little, LittleEndian, {
+        version: u8 bits(4),
+        ihl: u8 bits(4),
+        length: u16,
+        flag: bool bits(1),
+        offset: u16 bits(13),
+        rest: u8 bits(2),
+        tail: [u8],
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = BitField<u16, BigEndian, 5, 3, 13>;
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(2), Field1::SIZE);
+        assert_eq!(Some(1), BitField::<u32, BigEndian, 0, 2, 6>::SIZE);
+        assert_eq!(Some(5), BitField::<u64, BigEndian, 0, 7, 33>::SIZE);
+
+        assert_eq!(0, big::ihl::OFFSET);
+        assert_eq!(1, big::length::OFFSET);
+        assert_eq!(3, big::flag::OFFSET);
+        assert_eq!(3, big::offset::OFFSET);
+        assert_eq!(Some(2), big::offset::SIZE);
+        assert_eq!(4, big::rest::OFFSET);
+        assert_eq!(5, big::tail::OFFSET);
+        assert_eq!(
+            FieldKind::Bits {
+                bit_offset: 4,
+                bits: 4
+            },
+            big::Fields::ihl.kind
+        );
+        assert_eq!(
+            FieldKind::Bits {
+                bit_offset: 6,
+                bits: 2
+            },
+            big::Fields::rest.kind
+        );
+        assert_eq!("u16 bits(13)", big::Fields::offset.type_name);
+    }
+
+    #[test]
+    fn bits() {
+        let data = [0b1010_0110, 0b0101_1100];
+        assert_eq!(0b101, read_bits(&data, true, 0, 3));
+        assert_eq!(0b0110_0101, read_bits(&data, true, 4, 8));
+        assert_eq!(0b110, read_bits(&data, false, 0, 3));
+        assert_eq!(0b1100_1010, read_bits(&data, false, 4, 8));
+
+        let mut data = [0xff, 0x00];
+        write_bits(&mut data, true, 6, 4, 0b0110);
+        assert_eq!([0b1111_1101, 0b1000_0000], data);
+        write_bits(&mut data, false, 6, 4, 0b0110);
+        assert_eq!([0b1011_1101, 0b1000_0001], data);
+    }
+
+    #[test]
+    fn viewapi_big_endian() {
+        let mut view = big::View::new([0; 6]);
+        view.version_mut().try_write(4).unwrap();
+        view.ihl_mut().try_write(5).unwrap();
+        view.length_mut().write(0x1234);
+        view.flag_mut().write(true);
+        view.offset_mut().try_write(0x1abc).unwrap();
+        view.rest_mut().try_write(3).unwrap();
+        assert_eq!(4, view.version().read());
+        assert_eq!(5, view.ihl().read());
+        assert!(view.flag().read());
+        assert_eq!(0x1abc, view.offset().read());
+        assert_eq!(3, view.rest().read());
+        assert_eq!(
+            [0x45, 0x12, 0x34, 0b1110_1010, 0b1111_0011, 0],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn viewapi_little_endian() {
+        let mut view = little::View::new([0; 6]);
+        view.version_mut().try_write(4).unwrap();
+        view.ihl_mut().try_write(5).unwrap();
+        view.length_mut().write(0x1234);
+        view.flag_mut().write(true);
+        view.offset_mut().try_write(0x1abc).unwrap();
+        view.rest_mut().try_write(3).unwrap();
+        assert_eq!(4, view.version().read());
+        assert_eq!(5, view.ihl().read());
+        assert!(view.flag().read());
+        assert_eq!(0x1abc, view.offset().read());
+        assert_eq!(3, view.rest().read());
+        assert_eq!(
+            [0x54, 0x34, 0x12, 0b0111_1001, 0b1111_0101, 0],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xff; 3];
+        type Field1 = BitField<u32, LittleEndian, 1, 2, 9>;
+        Field1::try_write(&mut storage, 0).unwrap();
+        assert_eq!([0xff, 0b0000_0011, 0b1111_1000], storage);
+        assert_eq!(0, Field1::read(&storage));
+        assert!(matches!(
+            Field1::try_write(&mut storage, 1 << 9),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        type Field2 = BitField<u128, BigEndian, 0, 0, 128>;
+        let mut storage = [0; 16];
+        Field2::try_write(&mut storage, u128::MAX).unwrap();
+        assert_eq!(u128::MAX, Field2::read(&storage));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut view = big::View::new([0; 6]);
+        assert!(matches!(
+            view.version_mut().try_write(16),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        assert!(matches!(
+            view.offset_mut().try_write(1 << 13),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        assert_eq!([0; 6], view.into_storage());
+    }
+}
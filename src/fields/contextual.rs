@@ -0,0 +1,286 @@
+use core::marker::PhantomData;
+
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+use super::{
+    primitive::{FieldCopyAccess, FieldView},
+    wrapped::WrappedFieldError,
+    Field, StorageIntoFieldView, StorageToFieldView,
+};
+
+/// Implementing the [LayoutAsWith] trait for a custom type allows that custom type to be used as the type of a
+/// layout field whose conversion needs runtime context, e.g. a scale factor from a calibration field elsewhere
+/// in the buffer or a codec selected when the view was created. It works like [LayoutAs](crate::LayoutAs),
+/// but the conversion functions get a reference to a context object of type `C` that is passed in on each access.
+///
+/// Such fields are declared with `with <<ContextType>>` after the underlying type, and the field type and
+/// underlying type have to be plain type names in that case. Their accessors offer `read_with(&context)` and
+/// `write_with(&context)` (or `try_read_with` and `try_write_with` if the conversion can fail) instead of `read()` and `write()`.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutAsWith};
+/// use core::convert::Infallible;
+///
+/// struct Calibration {
+///     millikelvin_per_step: i32,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Millikelvin(i32);
+/// impl LayoutAsWith<i16, Calibration> for Millikelvin {
+///   type ReadError = Infallible;
+///   type WriteError = Infallible;
+///
+///   fn try_read_with(v: i16, calibration: &Calibration) -> Result<Millikelvin, Infallible> {
+///     Ok(Millikelvin(i32::from(v) * calibration.millikelvin_per_step))
+///   }
+///
+///   fn try_write_with(v: Millikelvin, calibration: &Calibration) -> Result<i16, Infallible> {
+///     Ok((v.0 / calibration.millikelvin_per_step) as i16)
+///   }
+/// }
+///
+/// binary_layout!(sensor, LittleEndian, {
+///   millikelvin_per_step: u8,
+///   temperature: Millikelvin as i16 with Calibration,
+/// });
+///
+/// fn func(storage_data: &mut [u8]) {
+///   let mut view = sensor::View::new(storage_data);
+///   let calibration = Calibration {
+///       millikelvin_per_step: view.millikelvin_per_step().read().into(),
+///   };
+///   view.temperature_mut().write_with(Millikelvin(300), &calibration);
+///   assert_eq!(Millikelvin(300), view.temperature().read_with(&calibration));
+/// }
+///
+/// # fn main() {
+/// #   let mut storage = [5, 0, 0];
+/// #   func(&mut storage);
+/// #   assert_eq!([5, 60, 0], storage);
+/// # }
+/// ```
+pub trait LayoutAsWith<U, C>: Sized {
+    /// See [LayoutAs::ReadError](crate::LayoutAs::ReadError).
+    type ReadError;
+    /// See [LayoutAs::WriteError](crate::LayoutAs::WriteError).
+    type WriteError;
+
+    /// Implement this to define how the custom type is constructed from the underlying type
+    /// after it was read from a layouted binary slice, given the context.
+    fn try_read_with(v: U, context: &C) -> Result<Self, Self::ReadError>;
+
+    /// Implement this to define how the custom type is converted into the underlying type
+    /// so it can be written into a layouted binary slice, given the context.
+    fn try_write_with(v: Self, context: &C) -> Result<U, Self::WriteError>;
+}
+
+/// This trait is implemented for fields whose values can only be read and written given a context object,
+/// i.e. fields using a [LayoutAsWith] type. See [FieldCopyAccess] for fields that don't need a context.
+pub trait FieldContextAccess: Field {
+    /// The type of the context object that reading and writing needs
+    type Context;
+    /// Error type for reading the field, see [FieldCopyAccess::ReadError]
+    type ReadError;
+    /// Error type for writing the field, see [FieldCopyAccess::WriteError]
+    type WriteError;
+    /// The type of the field values, see [FieldCopyAccess::HighLevelType]
+    type HighLevelType;
+
+    /// Read the field from a given data region, assuming the defined layout, using the [Field] API.
+    fn try_read_with(
+        storage: &[u8],
+        context: &Self::Context,
+    ) -> Result<Self::HighLevelType, Self::ReadError>;
+
+    /// Write the field to a given data region, assuming the defined layout, using the [Field] API.
+    fn try_write_with(
+        storage: &mut [u8],
+        v: Self::HighLevelType,
+        context: &Self::Context,
+    ) -> Result<(), Self::WriteError>;
+}
+
+/// A [ContextualField] is a [Field] that represents a type that can be converted to/from a primitive type using
+/// the [LayoutAsWith] trait, which needs a context object of type `C` on each access.
+/// See [LayoutAsWith] for an example.
+pub struct ContextualField<U, T: LayoutAsWith<U, C>, C, F: Field> {
+    _p1: PhantomData<U>,
+    _p2: PhantomData<T>,
+    _p3: PhantomData<C>,
+    _p4: PhantomData<F>,
+}
+
+impl<U, T: LayoutAsWith<U, C>, C, F: Field> Field for ContextualField<U, T, C, F> {
+    /// See [Field::Endian]
+    type Endian = F::Endian;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = F::OFFSET;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = F::SIZE;
+}
+
+impl<U, T: LayoutAsWith<U, C>, C, F: Field + FieldKindInfo> FieldKindInfo
+    for ContextualField<U, T, C, F>
+{
+    const KIND: FieldKind = F::KIND;
+}
+
+impl<
+        'a,
+        U,
+        T: LayoutAsWith<U, C>,
+        C,
+        F: FieldCopyAccess<HighLevelType = U> + StorageToFieldView<&'a [u8]>,
+    > StorageToFieldView<&'a [u8]> for ContextualField<U, T, C, F>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        'a,
+        U,
+        T: LayoutAsWith<U, C>,
+        C,
+        F: FieldCopyAccess<HighLevelType = U> + StorageToFieldView<&'a mut [u8]>,
+    > StorageToFieldView<&'a mut [u8]> for ContextualField<U, T, C, F>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        U,
+        S: AsRef<[u8]>,
+        T: LayoutAsWith<U, C>,
+        C,
+        F: FieldCopyAccess<HighLevelType = U> + StorageIntoFieldView<S>,
+    > StorageIntoFieldView<S> for ContextualField<U, T, C, F>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<U, T: LayoutAsWith<U, C>, C, F: FieldCopyAccess<HighLevelType = U>> FieldContextAccess
+    for ContextualField<U, T, C, F>
+{
+    /// See [FieldContextAccess::Context]
+    type Context = C;
+    /// See [FieldContextAccess::ReadError]
+    type ReadError = WrappedFieldError<F::ReadError, T::ReadError>;
+    /// See [FieldContextAccess::WriteError]
+    type WriteError = WrappedFieldError<F::WriteError, T::WriteError>;
+    /// See [FieldContextAccess::HighLevelType]
+    type HighLevelType = T;
+
+    #[inline(always)]
+    fn try_read_with(storage: &[u8], context: &C) -> Result<T, Self::ReadError> {
+        let v = F::try_read(storage).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        let value = T::try_read_with(v, context).map_err(WrappedFieldError::LayoutAsError)?;
+        Ok(value)
+    }
+
+    #[inline(always)]
+    fn try_write_with(storage: &mut [u8], v: T, context: &C) -> Result<(), Self::WriteError> {
+        let v = T::try_write_with(v, context).map_err(WrappedFieldError::LayoutAsError)?;
+        F::try_write(storage, v).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{FieldContextAccess, FieldKind, LayoutAsWith, WrappedFieldError};
+
+    pub struct Scale(u32);
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Scaled(u32);
+    impl LayoutAsWith<u16, Scale> for Scaled {
+        type ReadError = &'static str;
+        type WriteError = &'static str;
+
+        fn try_read_with(v: u16, scale: &Scale) -> Result<Self, Self::ReadError> {
+            u32::from(v)
+                .checked_mul(scale.0)
+                .map(Scaled)
+                .ok_or("overflow")
+        }
+
+        fn try_write_with(v: Self, scale: &Scale) -> Result<u16, Self::WriteError> {
+            if v.0 % scale.0 != 0 {
+                return Err("not a multiple of the scale");
+            }
+            Ok((v.0 / scale.0) as u16)
+        }
+    }
+
+    binary_layout!(scaled, BigEndian, {
+        scale: u8,
+        value: Scaled as u16 with Scale,
+        tail: [u8],
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, scaled::value::OFFSET);
+        assert_eq!(Some(2), scaled::value::SIZE);
+        assert_eq!(3, scaled::tail::OFFSET);
+        assert_eq!("Scaled as u16 with Scale", scaled::Fields::value.type_name);
+        assert_eq!(FieldKind::UnsignedInteger, scaled::Fields::value.kind);
+    }
+
+    #[test]
+    fn field_api() {
+        let mut storage = [0; 4];
+        scaled::value::try_write_with(&mut storage, Scaled(60), &Scale(3)).unwrap();
+        assert_eq!([0, 0, 20, 0], storage);
+        assert!(matches!(
+            scaled::value::try_read_with(&storage, &Scale(2)),
+            Ok(Scaled(40))
+        ));
+    }
+
+    #[test]
+    fn view_api() {
+        let mut view = scaled::View::new([4, 0, 0, 7]);
+        let scale = Scale(view.scale().read().into());
+        view.value_mut()
+            .try_write_with(Scaled(400), &scale)
+            .unwrap();
+        assert_eq!(Scaled(400), view.value().try_read_with(&scale).unwrap());
+        assert_eq!(&[7], view.tail());
+        assert_eq!([4, 0, 100, 7], view.into_storage());
+    }
+
+    #[test]
+    fn errors() {
+        let mut view = scaled::View::new([0, 0xff, 0xff]);
+        assert!(matches!(
+            view.value().try_read_with(&Scale(u32::MAX)),
+            Err(WrappedFieldError::LayoutAsError("overflow"))
+        ));
+        assert!(matches!(
+            view.value_mut().try_write_with(Scaled(5), &Scale(2)),
+            Err(WrappedFieldError::LayoutAsError(
+                "not a multiple of the scale"
+            ))
+        ));
+        assert_eq!([0, 0xff, 0xff], view.into_storage());
+    }
+}
@@ -1,8 +1,11 @@
 use super::endianness::Endianness;
 
+pub mod bit_field;
 pub mod bool;
 pub mod char;
+pub mod contextual;
 pub mod primitive;
+pub mod relative;
 pub mod wrapped;
 
 ///
@@ -56,6 +59,8 @@ pub trait Field {
     type Endian: Endianness;
 
     /// The offset of the field in the layout.
+    /// For fields after [LengthPrefixed](crate::LengthPrefixed) or varint fields, it's the offset when those fields have their minimal size,
+    /// see [RelativeField](crate::RelativeField).
     ///
     /// # Example
     /// ```
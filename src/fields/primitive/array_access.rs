@@ -0,0 +1,464 @@
+use core::convert::Infallible;
+
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldCopyAccess, FieldReadExt, FieldView, FieldWriteExt, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// This trait is implemented for fields storing an array of numbers like `[u16; 8]` or `[f32; 16]`,
+/// i.e. fields whose elements are read/write one at a time by copying them from/to the storage,
+/// converting each of them from/to the endianness of the layout.
+///
+/// Open ended arrays like `[u32]` are supported as the last field of a layout. They contain as many elements
+/// as fit into the rest of the storage, see [FieldArrayAccess::len].
+///
+/// Byte arrays `[u8; N]` don't implement this trait, they offer [FieldSliceAccess](crate::FieldSliceAccess) instead.
+///
+/// # Example:
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   //... other fields ...
+///   samples: [u16; 8],
+///   //... other fields ...
+/// });
+///
+/// fn func(storage_data: &mut [u8]) {
+///   my_layout::samples::set(storage_data, 3, 1000);
+///   let sample: u16 = my_layout::samples::get(storage_data, 3);
+///   let all_samples: [u16; 8] = my_layout::samples::read(storage_data);
+/// }
+/// ```
+pub trait FieldArrayAccess: Field {
+    /// The type of the elements of the array
+    type Element;
+
+    /// The number of elements of the array in the given data region.
+    /// For open ended arrays, these are all whole elements fitting into the data after the offset of the field.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   count: u8,
+    ///   records: [u32],
+    /// });
+    ///
+    /// assert_eq!(2, my_layout::records::len(&[0; 10]));
+    /// ```
+    fn len(storage: &[u8]) -> usize;
+
+    /// Read the element with the given index from a given data region, assuming the defined layout, using the [Field] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldArrayAccess::len].
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   samples: [i32; 4],
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   let sample: i32 = my_layout::samples::get(storage_data, 2);
+    /// }
+    /// ```
+    fn get(storage: &[u8], index: usize) -> Self::Element;
+
+    /// Write the element with the given index to a given data region, assuming the defined layout, using the [Field] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldArrayAccess::len].
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   samples: [i32; 4],
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///   my_layout::samples::set(storage_data, 2, -5);
+    /// }
+    /// ```
+    fn set(storage: &mut [u8], index: usize, value: Self::Element);
+}
+
+macro_rules! array_field {
+    ($type: ty, $element_kind: expr) => {
+        impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldArrayAccess
+            for PrimitiveField<[$type; N], E, OFFSET_>
+        {
+            type Element = $type;
+
+            #[inline(always)]
+            fn len(_storage: &[u8]) -> usize {
+                N
+            }
+
+            #[inline(always)]
+            fn get(storage: &[u8], index: usize) -> $type {
+                check_index(index, N);
+                PrimitiveField::<$type, E, 0>::read(&storage[element_offset::<$type>(Self::OFFSET, index)..])
+            }
+
+            #[inline(always)]
+            fn set(storage: &mut [u8], index: usize, value: $type) {
+                check_index(index, N);
+                PrimitiveField::<$type, E, 0>::write(&mut storage[element_offset::<$type>(Self::OFFSET, index)..], value)
+            }
+        }
+
+        impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldCopyAccess
+            for PrimitiveField<[$type; N], E, OFFSET_>
+        {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = Infallible;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = [$type; N];
+
+            /// Read all elements of the array field from a given data region, assuming the defined layout, using the [Field] API.
+            #[inline(always)]
+            fn try_read(storage: &[u8]) -> Result<[$type; N], Infallible> {
+                let mut value = [<$type>::default(); N];
+                for (index, element) in value.iter_mut().enumerate() {
+                    *element = Self::get(storage, index);
+                }
+                Ok(value)
+            }
+
+            /// Write all elements of the array field to a given data region, assuming the defined layout, using the [Field] API.
+            #[inline(always)]
+            fn try_write(storage: &mut [u8], value: [$type; N]) -> Result<(), Infallible> {
+                for (index, element) in value.iter().enumerate() {
+                    Self::set(storage, index, *element);
+                }
+                Ok(())
+            }
+        }
+
+        impl<E: Endianness, const N: usize, const OFFSET_: usize> Field
+            for PrimitiveField<[$type; N], E, OFFSET_>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = Some(N * core::mem::size_of::<$type>());
+        }
+
+        impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<[$type; N], E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::Array {
+                element: $element_kind,
+                element_size: core::mem::size_of::<$type>(),
+            };
+        }
+
+        impl_field_view_traits!([$type; N], const N: usize);
+
+        impl<E: Endianness, const OFFSET_: usize> FieldArrayAccess
+            for PrimitiveField<[$type], E, OFFSET_>
+        {
+            type Element = $type;
+
+            #[inline(always)]
+            fn len(storage: &[u8]) -> usize {
+                storage.len().saturating_sub(Self::OFFSET) / core::mem::size_of::<$type>()
+            }
+
+            #[inline(always)]
+            fn get(storage: &[u8], index: usize) -> $type {
+                check_index(index, Self::len(storage));
+                PrimitiveField::<$type, E, 0>::read(&storage[element_offset::<$type>(Self::OFFSET, index)..])
+            }
+
+            #[inline(always)]
+            fn set(storage: &mut [u8], index: usize, value: $type) {
+                check_index(index, Self::len(storage));
+                PrimitiveField::<$type, E, 0>::write(&mut storage[element_offset::<$type>(Self::OFFSET, index)..], value)
+            }
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> Field for PrimitiveField<[$type], E, OFFSET_> {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = None;
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<[$type], E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::Array {
+                element: $element_kind,
+                element_size: core::mem::size_of::<$type>(),
+            };
+        }
+
+        impl_field_view_traits!([$type]);
+    };
+}
+
+macro_rules! impl_field_view_traits {
+    ($type: ty $(, const $n: ident: usize)?) => {
+        impl<'a, E: Endianness, $(const $n: usize,)? const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+            for PrimitiveField<$type, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, $(const $n: usize,)? const OFFSET_: usize>
+            StorageToFieldView<&'a mut [u8]> for PrimitiveField<$type, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, $(const $n: usize,)? const OFFSET_: usize>
+            StorageIntoFieldView<S> for PrimitiveField<$type, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+#[inline(always)]
+fn check_index(index: usize, len: usize) {
+    assert!(
+        index < len,
+        "Index {} out of bounds for array of length {}",
+        index,
+        len
+    );
+}
+
+#[inline(always)]
+fn element_offset<T>(offset: usize, index: usize) -> usize {
+    offset + index * core::mem::size_of::<T>()
+}
+
+array_field!(i8, &FieldKind::SignedInteger);
+array_field!(i16, &FieldKind::SignedInteger);
+array_field!(i32, &FieldKind::SignedInteger);
+array_field!(i64, &FieldKind::SignedInteger);
+array_field!(i128, &FieldKind::SignedInteger);
+array_field!(u16, &FieldKind::UnsignedInteger);
+array_field!(u32, &FieldKind::UnsignedInteger);
+array_field!(u64, &FieldKind::UnsignedInteger);
+array_field!(u128, &FieldKind::UnsignedInteger);
+array_field!(f32, &FieldKind::Float);
+array_field!(f64, &FieldKind::Float);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)]
+    use super::FieldArrayAccess;
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(little, LittleEndian, {
+        kind: u8,
+        samples: [u16; 4],
+        matrix: [f32; 4],
+        offsets: [i64; 2],
+    });
+
+    binary_layout!(big, BigEndian, {
+        kind: u8,
+        samples: [u16; 4],
+        matrix: [f32; 4],
+        offsets: [i64; 2],
+    });
+
+    binary_layout!(open_ended, BigEndian, {
+        count: u8,
+        records: [u32],
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<[u32; 3], LittleEndian, 5>;
+        assert_eq!(Some(12), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(3, Field1::len(&[]));
+        assert_eq!(Some(41), little::SIZE);
+        assert_eq!(9, little::matrix::OFFSET);
+        assert_eq!(25, little::offsets::OFFSET);
+        assert_eq!(
+            FieldKind::Array {
+                element: &FieldKind::UnsignedInteger,
+                element_size: 2
+            },
+            little::Fields::samples.kind
+        );
+        assert_eq!(
+            FieldKind::Array {
+                element: &FieldKind::Float,
+                element_size: 4
+            },
+            little::Fields::matrix.kind
+        );
+        assert_eq!("[u16; 4]", little::Fields::samples.type_name);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 16];
+        type Field1 = PrimitiveField<[u16; 2], LittleEndian, 1>;
+        type Field2 = PrimitiveField<[i16; 2], BigEndian, 5>;
+        type Field3 = PrimitiveField<[u32; 1], NativeEndian, 9>;
+        Field1::set(&mut storage, 0, 0x0102);
+        Field1::set(&mut storage, 1, 0x0304);
+        Field2::write(&mut storage, [-2, 0x0506]);
+        Field3::set(&mut storage, 0, 0x0708_090a);
+        assert_eq!(0x0102, Field1::get(&storage, 0));
+        assert_eq!(0x0304, Field1::get(&storage, 1));
+        assert_eq!([0x0102, 0x0304], Field1::read(&storage));
+        assert_eq!(-2, Field2::get(&storage, 0));
+        assert_eq!([-2, 0x0506], Field2::read(&storage));
+        assert_eq!(0x0708_090a, Field3::get(&storage, 0));
+        assert_eq!([0, 2, 1, 4, 3, 0xff, 0xfe, 5, 6], storage[..9]);
+        assert_eq!(0x0708_090au32.to_ne_bytes(), storage[9..13]);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = little::View::new([0; 41]);
+        view.samples_mut().set(0, 1);
+        view.samples_mut().set(3, 0xabcd);
+        view.matrix_mut().write([1.0, 0.5, -2.0, 0.0]);
+        view.offsets_mut().set(1, -1);
+        assert_eq!(0xabcd, view.samples().get(3));
+        assert_eq!([1, 0, 0, 0xabcd], view.samples().read());
+        assert_eq!(0.5, view.matrix().get(1));
+        assert_eq!([1.0, 0.5, -2.0, 0.0], view.matrix().read());
+        assert_eq!([0, -1], view.offsets().read());
+        let storage = view.into_storage();
+        assert_eq!([0, 1, 0, 0, 0, 0, 0, 0xcd, 0xab], storage[..9]);
+        assert_eq!(0.5f32.to_le_bytes(), storage[13..17]);
+        assert_eq!([0xff; 8], storage[33..41]);
+
+        let mut view = big::View::new([0; 41]);
+        view.samples_mut().set(0, 1);
+        view.samples_mut().set(3, 0xabcd);
+        view.matrix_mut().set(1, 0.5);
+        view.offsets_mut().write([-2, 3]);
+        assert_eq!([1, 0, 0, 0xabcd], view.samples().read());
+        assert_eq!([0.0, 0.5, 0.0, 0.0], view.matrix().read());
+        assert_eq!(-2, view.offsets().get(0));
+        let storage = view.into_storage();
+        assert_eq!([0, 0, 1, 0, 0, 0, 0, 0xab, 0xcd], storage[..9]);
+        assert_eq!(0.5f32.to_be_bytes(), storage[13..17]);
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 3], storage[33..41]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 4 out of bounds for array of length 4")]
+    fn get_out_of_bounds() {
+        let view = little::View::new([0; 41]);
+        view.samples().get(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 2 out of bounds for array of length 2")]
+    fn set_out_of_bounds() {
+        let mut storage = [0; 64];
+        little::offsets::set(&mut storage, 2, 1);
+    }
+
+    #[test]
+    fn open_ended_metadata() {
+        type Field1 = PrimitiveField<[i16], LittleEndian, 3>;
+        assert_eq!(None, Field1::SIZE);
+        assert_eq!(3, Field1::OFFSET);
+        assert_eq!(0, Field1::len(&[0; 4]));
+        assert_eq!(2, Field1::len(&[0; 8]));
+        assert_eq!(None, open_ended::SIZE);
+        assert_eq!(
+            FieldKind::Array {
+                element: &FieldKind::UnsignedInteger,
+                element_size: 4
+            },
+            open_ended::Fields::records.kind
+        );
+        assert_eq!("[u32]", open_ended::Fields::records.type_name);
+    }
+
+    #[test]
+    fn open_ended_fieldapi() {
+        let mut storage = [0; 8];
+        type Field1 = PrimitiveField<[i16], LittleEndian, 3>;
+        Field1::set(&mut storage, 0, -2);
+        Field1::set(&mut storage, 1, 0x0102);
+        assert_eq!(-2, Field1::get(&storage, 0));
+        assert_eq!(0x0102, Field1::get(&storage, 1));
+        assert_eq!([0, 0, 0, 0xfe, 0xff, 2, 1, 0], storage);
+    }
+
+    #[test]
+    fn open_ended_viewapi() {
+        let mut view = open_ended::View::new([0; 14]);
+        assert_eq!(3, view.records().len());
+        assert!(!view.records().is_empty());
+        view.records_mut().set(0, 1);
+        view.records_mut().set(2, 0x0102_0304);
+        assert_eq!(0x0102_0304, view.records().get(2));
+        let mut records = [0; 3];
+        view.records()
+            .iter()
+            .zip(records.iter_mut())
+            .for_each(|(value, record)| *record = value);
+        assert_eq!([1, 0, 0x0102_0304], records);
+        let storage = view.into_storage();
+        assert_eq!([0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 2, 3, 4, 0], storage);
+
+        let view = open_ended::View::new(&[5, 0, 0][..]);
+        assert_eq!(0, view.records().len());
+        assert!(view.records().is_empty());
+        assert_eq!(None, view.records().iter().next());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 1 out of bounds for array of length 1")]
+    fn open_ended_get_out_of_bounds() {
+        let view = open_ended::View::new([0; 8]);
+        view.records().get(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 2 out of bounds for array of length 2")]
+    fn open_ended_set_out_of_bounds() {
+        let mut storage = [0; 11];
+        open_ended::records::set(&mut storage, 2, 1);
+    }
+}
@@ -0,0 +1,258 @@
+use super::super::bit_field::{read_bits, write_bits};
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldView, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// Field type for an array of `BITS` boolean flags packed into bits, e.g. `present: BitArray<64>` taking up 8 bytes,
+/// like the bitmaps of allocation tables. It takes up `BITS / 8` bytes, rounded up, and [FieldBitArrayAccess] reads and writes single bits.
+///
+/// Bit `index` is stored in byte `index / 8` of the field. Like for [BitField](crate::BitField)s, the bits of each byte are numbered
+/// from the most significant bit in big endian layouts and from the least significant bit in little endian layouts.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, BitArray};
+///
+/// binary_layout!(block_group, LittleEndian, {
+///   free_blocks: u16,
+///   allocated: BitArray<16>,
+/// });
+///
+/// fn main() {
+///     let mut view = block_group::View::new([0; 4]);
+///     view.allocated_mut().set_bit(0, true);
+///     view.allocated_mut().set_bit(9, true);
+///     assert!(view.allocated().get_bit(9));
+///     assert!(!view.allocated().get_bit(8));
+///     assert_eq!([0, 0, 0b0000_0001, 0b0000_0010], view.into_storage());
+/// }
+/// ```
+pub struct BitArray<const BITS: usize>;
+
+/// This trait is implemented for [BitArray] fields, which store boolean flags in single bits.
+pub trait FieldBitArrayAccess: Field {
+    /// The number of bits of the field
+    const BITS: usize;
+
+    /// Read the bit with the given index from a given data region, assuming the defined layout, using the [Field] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldBitArrayAccess::BITS].
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, BitArray};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   //... other fields ...
+    ///   flags: BitArray<12>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     assert!(my_layout::flags::get_bit(&[0b1000_0000, 0], 0));
+    ///     assert!(my_layout::flags::get_bit(&[0, 0b0001_0000], 11));
+    /// }
+    /// ```
+    fn get_bit(storage: &[u8], index: usize) -> bool;
+
+    /// Write the bit with the given index to a given data region, assuming the defined layout, using the [Field] API.
+    /// The other bits are left unchanged.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldBitArrayAccess::BITS].
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, BitArray};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   flags: BitArray<8>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     let mut storage = [0b0000_0001];
+    ///     my_layout::flags::set_bit(&mut storage, 3, true);
+    ///     my_layout::flags::set_bit(&mut storage, 0, false);
+    ///     assert_eq!([0b0000_1000], storage);
+    /// }
+    /// ```
+    fn set_bit(storage: &mut [u8], index: usize, value: bool);
+}
+
+impl<E: Endianness, const BITS: usize, const OFFSET_: usize> FieldBitArrayAccess
+    for PrimitiveField<BitArray<BITS>, E, OFFSET_>
+{
+    const BITS: usize = BITS;
+
+    #[inline]
+    fn get_bit(storage: &[u8], index: usize) -> bool {
+        assert!(
+            index < BITS,
+            "Bit index {} is out of range for a BitArray<{}>",
+            index,
+            BITS
+        );
+        read_bits(&storage[Self::OFFSET..], E::KIND.is_big_endian(), index, 1) == 1
+    }
+
+    #[inline]
+    fn set_bit(storage: &mut [u8], index: usize, value: bool) {
+        assert!(
+            index < BITS,
+            "Bit index {} is out of range for a BitArray<{}>",
+            index,
+            BITS
+        );
+        write_bits(
+            &mut storage[Self::OFFSET..],
+            E::KIND.is_big_endian(),
+            index,
+            1,
+            u128::from(value),
+        );
+    }
+}
+
+impl<E: Endianness, const BITS: usize, const OFFSET_: usize> Field
+    for PrimitiveField<BitArray<BITS>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = Some((BITS + 7) / 8);
+}
+
+impl<E: Endianness, const BITS: usize, const OFFSET_: usize> FieldKindInfo
+    for PrimitiveField<BitArray<BITS>, E, OFFSET_>
+{
+    const KIND: FieldKind = FieldKind::BitArray { bits: BITS };
+}
+
+impl<'a, E: Endianness, const BITS: usize, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<BitArray<BITS>, E, OFFSET_>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, E: Endianness, const BITS: usize, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+    for PrimitiveField<BitArray<BITS>, E, OFFSET_>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, E: Endianness, const BITS: usize, const OFFSET_: usize> StorageIntoFieldView<S>
+    for PrimitiveField<BitArray<BITS>, E, OFFSET_>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitArray;
+    use crate::prelude::*;
+    use crate::{FieldKind, FieldValue, PrimitiveField};
+
+    binary_layout!(bitmap_be, BigEndian, {
+        kind: u8,
+        present: BitArray<64>,
+        flags: BitArray<3>,
+    });
+
+    binary_layout!(bitmap_le, LittleEndian, {
+        kind: u8,
+        present: BitArray<64>,
+        flags: BitArray<3>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<BitArray<9>, LittleEndian, 5>;
+        assert_eq!(Some(2), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(64, <bitmap_be::present as FieldBitArrayAccess>::BITS);
+        assert_eq!(Some(10), bitmap_be::SIZE);
+        assert_eq!(9, bitmap_be::flags::OFFSET);
+        assert_eq!(
+            FieldKind::BitArray { bits: 3 },
+            bitmap_le::Fields::flags.kind
+        );
+    }
+
+    #[test]
+    fn fieldapi_big_endian() {
+        let mut storage = [0xff; 10];
+        bitmap_be::present::set_bit(&mut storage, 0, false);
+        bitmap_be::present::set_bit(&mut storage, 63, false);
+        bitmap_be::flags::set_bit(&mut storage, 2, false);
+        assert!(!bitmap_be::present::get_bit(&storage, 0));
+        assert!(bitmap_be::present::get_bit(&storage, 1));
+        assert!(!bitmap_be::present::get_bit(&storage, 63));
+        assert!(!bitmap_be::flags::get_bit(&storage, 2));
+        assert_eq!(
+            [0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xdf],
+            storage
+        );
+    }
+
+    #[test]
+    fn fieldapi_little_endian() {
+        let mut storage = [0; 10];
+        bitmap_le::present::set_bit(&mut storage, 0, true);
+        bitmap_le::present::set_bit(&mut storage, 63, true);
+        bitmap_le::flags::set_bit(&mut storage, 2, true);
+        assert!(bitmap_le::present::get_bit(&storage, 0));
+        assert!(!bitmap_le::present::get_bit(&storage, 1));
+        assert!(bitmap_le::present::get_bit(&storage, 63));
+        assert!(bitmap_le::flags::get_bit(&storage, 2));
+        assert_eq!([0, 0x01, 0, 0, 0, 0, 0, 0, 0x80, 0x04], storage);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = bitmap_le::View::new([0; 10]);
+        for index in (0..64).step_by(3) {
+            view.present_mut().set_bit(index, true);
+        }
+        for index in 0..64 {
+            assert_eq!(index % 3 == 0, view.present().get_bit(index));
+        }
+        assert_eq!(0, view.kind().read());
+        assert!(!view.flags().get_bit(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit index 3 is out of range for a BitArray<3>")]
+    fn index_out_of_range() {
+        bitmap_le::flags::get_bit(&[0; 10], 3);
+    }
+
+    #[test]
+    fn layout_info() {
+        let layout = &bitmap_be::LAYOUT;
+        assert_eq!(
+            FieldValue::Bytes(&[0xa0]),
+            layout.read_field(&layout.fields[2], &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0xa0])
+        );
+    }
+}
@@ -64,14 +64,23 @@ pub trait FieldCopyAccess: Field {
 }
 
 macro_rules! impl_field_traits {
-    ($type: ty) => {
+    ($type: ty, $kind: expr) => {
+        impl_field_traits!($type, $kind, core::mem::size_of::<$type>());
+    };
+    ($type: ty, $kind: expr, $size: expr) => {
         impl<E: Endianness, const OFFSET_: usize> Field for PrimitiveField<$type, E, OFFSET_> {
             /// See [Field::Endian]
             type Endian = E;
             /// See [Field::OFFSET]
             const OFFSET: usize = OFFSET_;
             /// See [Field::SIZE]
-            const SIZE: Option<usize> = Some(core::mem::size_of::<$type>());
+            const SIZE: Option<usize> = Some($size);
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<$type, E, OFFSET_>
+        {
+            const KIND: FieldKind = $kind;
         }
 
         impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
@@ -109,11 +118,30 @@ macro_rules! impl_field_traits {
     };
 }
 
+mod primitive_bcd;
+mod primitive_bool;
 mod primitive_float;
+#[cfg(feature = "half")]
+mod primitive_half;
 mod primitive_int;
+#[cfg(feature = "std")]
+mod primitive_ip_addr;
 mod primitive_nonzero_int;
+mod primitive_odd_width_int;
+#[cfg(feature = "std")]
+mod primitive_time;
 mod primitive_unit;
 mod read_write_ext;
 
+pub(crate) use primitive_bcd::decode_bcd;
+#[cfg(feature = "serde")]
+pub(crate) use primitive_bcd::encode_bcd;
+pub use primitive_bcd::{Bcd, InvalidBcdError};
 pub use primitive_nonzero_int::NonZeroIsZeroError;
+pub use primitive_odd_width_int::{i24, i40, i48, i56, u24, u40, u48, u56, IntegerOutOfRangeError};
+#[cfg(feature = "std")]
+pub use primitive_time::{
+    DurationMicros, DurationMillis, DurationNanos, DurationSeconds, TimeOutOfRangeError,
+    UnixMillis, UnixSeconds,
+};
 pub use read_write_ext::{FieldReadExt, FieldWriteExt};
@@ -0,0 +1,291 @@
+use core::marker::PhantomData;
+
+use super::{FieldCopyAccess, IntegerOutOfRangeError, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// Field type for an unsigned integer stored as packed binary-coded decimal (BCD) with `DIGITS` decimal digits,
+/// as used by smart card and telecom formats. Each byte stores two digits, the more significant one in its high nibble.
+/// The field takes up `(DIGITS + 1) / 2` bytes, which are stored in the endianness of the layout, i.e. big endian layouts store the most significant digits first.
+/// For an odd number of digits, the unused most significant nibble is zero.
+///
+/// `Bcd<T, DIGITS>` reads and writes values of type `T`, which can be [u8] with up to 2 digits, [u16] with up to 4 digits,
+/// [u32] with up to 9 digits or [u64] with up to 19 digits, so that all values of the field fit into `T`.
+///
+/// Reading fails with [InvalidBcdError] if a nibble isn't a decimal digit, writing fails with [IntegerOutOfRangeError]
+/// for values with more than `DIGITS` digits, i.e. larger than [Bcd::MAX](struct.Bcd.html#associatedconstant.MAX).
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Bcd};
+///
+/// binary_layout!(card_record, BigEndian, {
+///   account: Bcd<u32, 6>,
+///   checksum: u8,
+/// });
+///
+/// fn main() {
+///     let mut view = card_record::View::new([0; 4]);
+///     view.account_mut().try_write(123_456).unwrap();
+///     assert_eq!(123_456, view.account().try_read().unwrap());
+///     assert!(view.account_mut().try_write(1_000_000).is_err());
+///     assert_eq!([0x12, 0x34, 0x56, 0], view.into_storage());
+/// }
+/// ```
+pub struct Bcd<T, const DIGITS: usize> {
+    _p: PhantomData<T>,
+}
+
+/// This error is thrown when trying to read a [Bcd] field that has a nibble that isn't a decimal digit,
+/// or a nonzero unused nibble for an odd number of digits.
+#[derive(Debug)]
+pub struct InvalidBcdError(pub(crate) ());
+
+impl core::fmt::Display for InvalidBcdError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "InvalidBcdError")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidBcdError {}
+
+/// Decodes packed BCD with the given number of digits from the bytes it's stored in.
+/// Returns `None` if a nibble isn't a decimal digit or the unused nibble of an odd number of digits isn't zero.
+pub(crate) fn decode_bcd(bytes: &[u8], big_endian: bool, digits: usize) -> Option<u128> {
+    let mut value: u128 = 0;
+    let mut read_byte = |index: usize, byte: u8| {
+        let nibbles = [byte >> 4, byte & 0x0f];
+        let skip = if index == 0 {
+            2 * bytes.len() - digits
+        } else {
+            0
+        };
+        nibbles
+            .iter()
+            .enumerate()
+            .try_for_each(|(position, nibble)| match (position < skip, nibble) {
+                (true, 0) => Some(()),
+                (false, 0..=9) => {
+                    value = value * 10 + u128::from(*nibble);
+                    Some(())
+                }
+                _ => None,
+            })
+    };
+    if big_endian {
+        bytes
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, byte)| read_byte(index, *byte))?;
+    } else {
+        bytes
+            .iter()
+            .rev()
+            .enumerate()
+            .try_for_each(|(index, byte)| read_byte(index, *byte))?;
+    }
+    Some(value)
+}
+
+/// Encodes the value as packed BCD into all of the given bytes, assuming it has at most `2 * bytes.len()` digits.
+pub(crate) fn encode_bcd(bytes: &mut [u8], big_endian: bool, mut value: u128) {
+    let mut write_byte = |byte: &mut u8| {
+        let low = (value % 10) as u8;
+        let high = (value / 10 % 10) as u8;
+        *byte = (high << 4) | low;
+        value /= 100;
+    };
+    if big_endian {
+        bytes.iter_mut().rev().for_each(&mut write_byte);
+    } else {
+        bytes.iter_mut().for_each(&mut write_byte);
+    }
+}
+
+macro_rules! bcd_field {
+    ($type: ty, $max_digits: literal) => {
+        impl<const DIGITS: usize> Bcd<$type, DIGITS> {
+            /// The largest value that can be stored in the field
+            pub const MAX: $type = {
+                assert!(
+                    DIGITS > 0 && DIGITS <= $max_digits,
+                    concat!(
+                        "Bcd<",
+                        stringify!($type),
+                        ", DIGITS> supports 1 to ",
+                        $max_digits,
+                        " digits"
+                    )
+                );
+                (10 as $type).pow(DIGITS as u32) - 1
+            };
+
+            const BYTES: usize = (DIGITS + 1) / 2;
+        }
+
+        impl<E: Endianness, const DIGITS: usize, const OFFSET_: usize> FieldCopyAccess
+            for PrimitiveField<Bcd<$type, DIGITS>, E, OFFSET_>
+        {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = InvalidBcdError;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = IntegerOutOfRangeError;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $type;
+
+            #[inline]
+            fn try_read(storage: &[u8]) -> Result<$type, InvalidBcdError> {
+                let bytes = &storage[Self::OFFSET..(Self::OFFSET + Bcd::<$type, DIGITS>::BYTES)];
+                match decode_bcd(bytes, E::KIND.is_big_endian(), DIGITS) {
+                    Some(value) => Ok(value as $type),
+                    None => Err(InvalidBcdError(())),
+                }
+            }
+
+            #[inline]
+            fn try_write(storage: &mut [u8], value: $type) -> Result<(), IntegerOutOfRangeError> {
+                if value > Bcd::<$type, DIGITS>::MAX {
+                    return Err(IntegerOutOfRangeError(()));
+                }
+                let bytes =
+                    &mut storage[Self::OFFSET..(Self::OFFSET + Bcd::<$type, DIGITS>::BYTES)];
+                encode_bcd(bytes, E::KIND.is_big_endian(), u128::from(value));
+                Ok(())
+            }
+        }
+
+        impl<E: Endianness, const DIGITS: usize, const OFFSET_: usize> Field
+            for PrimitiveField<Bcd<$type, DIGITS>, E, OFFSET_>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = {
+                // Check the number of digits when the layout is defined, not only when the field is accessed
+                let _ = Bcd::<$type, DIGITS>::MAX;
+                Some(Bcd::<$type, DIGITS>::BYTES)
+            };
+        }
+
+        impl<E: Endianness, const DIGITS: usize, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<Bcd<$type, DIGITS>, E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::Bcd { digits: DIGITS };
+        }
+
+        impl<'a, E: Endianness, const DIGITS: usize, const OFFSET_: usize>
+            StorageToFieldView<&'a [u8]> for PrimitiveField<Bcd<$type, DIGITS>, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, const DIGITS: usize, const OFFSET_: usize>
+            StorageToFieldView<&'a mut [u8]> for PrimitiveField<Bcd<$type, DIGITS>, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, const DIGITS: usize, const OFFSET_: usize>
+            StorageIntoFieldView<S> for PrimitiveField<Bcd<$type, DIGITS>, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+bcd_field!(u8, 2);
+bcd_field!(u16, 4);
+bcd_field!(u32, 9);
+bcd_field!(u64, 19);
+
+#[cfg(test)]
+mod tests {
+    use super::Bcd;
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(timestamp, BigEndian, {
+        year: Bcd<u16, 4>,
+        month: Bcd<u8, 2>,
+        serial: Bcd<u32, 5>,
+    });
+
+    binary_layout!(little, LittleEndian, {
+        value: Bcd<u64, 19>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<Bcd<u32, 5>, BigEndian, 2>;
+        assert_eq!(Some(3), Field1::SIZE);
+        assert_eq!(Some(6), timestamp::SIZE);
+        assert_eq!(3, timestamp::serial::OFFSET);
+        assert_eq!(FieldKind::Bcd { digits: 5 }, timestamp::Fields::serial.kind);
+        assert_eq!(99, Bcd::<u8, 2>::MAX);
+        assert_eq!(99_999, Bcd::<u32, 5>::MAX);
+        assert_eq!(9_999_999_999_999_999_999, Bcd::<u64, 19>::MAX);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 6];
+        timestamp::year::try_write(&mut storage, 2024).unwrap();
+        timestamp::month::try_write(&mut storage, 7).unwrap();
+        timestamp::serial::try_write(&mut storage, 12_345).unwrap();
+        assert_eq!([0x20, 0x24, 0x07, 0x01, 0x23, 0x45], storage);
+        assert_eq!(2024, timestamp::year::try_read(&storage).unwrap());
+        assert_eq!(7, timestamp::month::try_read(&storage).unwrap());
+        assert_eq!(12_345, timestamp::serial::try_read(&storage).unwrap());
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = little::View::new([0; 10]);
+        view.value_mut()
+            .try_write(1_234_567_890_123_456_789)
+            .unwrap();
+        assert_eq!(1_234_567_890_123_456_789, view.value().try_read().unwrap());
+        assert_eq!(
+            [0x89, 0x67, 0x45, 0x23, 0x01, 0x89, 0x67, 0x45, 0x23, 0x01],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn invalid_nibbles() {
+        assert!(timestamp::year::try_read(&[0x20, 0x2a, 0, 0, 0, 0]).is_err());
+        assert!(timestamp::month::try_read(&[0, 0, 0xf1, 0, 0, 0]).is_err());
+        // The unused nibble of an odd number of digits has to be zero
+        assert!(timestamp::serial::try_read(&[0, 0, 0, 0x11, 0x23, 0x45]).is_err());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut storage = [0; 6];
+        assert!(timestamp::month::try_write(&mut storage, 100).is_err());
+        assert!(timestamp::serial::try_write(&mut storage, 100_000).is_err());
+        assert_eq!([0; 6], storage);
+        timestamp::serial::try_write(&mut storage, 99_999).unwrap();
+        assert_eq!(99_999, timestamp::serial::try_read(&storage).unwrap());
+    }
+}
@@ -0,0 +1,124 @@
+use core::convert::Infallible;
+
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::fields::bool::InvalidBoolError;
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<bool, E, OFFSET_> {
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = InvalidBoolError;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = Infallible;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = bool;
+
+    /// Read the bool field from a given data region, assuming the defined layout, using the [Field] API.
+    /// The field is stored as a single byte, and reading any byte other than `0` or `1` fails with [InvalidBoolError].
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///     //... other fields ...
+    ///     some_bool_field: bool
+    ///     //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) -> Result<bool, InvalidBoolError> {
+    ///     let read: bool = my_layout::some_bool_field::try_read(storage_data)?;
+    ///     Ok(read)
+    /// }
+    /// ```
+    #[inline(always)]
+    fn try_read(storage: &[u8]) -> Result<bool, InvalidBoolError> {
+        match storage[Self::OFFSET] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(InvalidBoolError(())),
+        }
+    }
+
+    /// Write the bool field to a given data region, assuming the defined layout, using the [Field] API.
+    /// `false` is stored as `0` and `true` as `1`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///     //... other fields ...
+    ///     some_bool_field: bool
+    ///     //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///     my_layout::some_bool_field::write(storage_data, true);
+    /// }
+    /// ```
+    #[inline(always)]
+    fn try_write(storage: &mut [u8], value: bool) -> Result<(), Infallible> {
+        storage[Self::OFFSET] = u8::from(value);
+        Ok(())
+    }
+}
+
+impl_field_traits!(bool, FieldKind::UnsignedInteger);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{FieldKind, InvalidBoolError, PrimitiveField};
+
+    binary_layout!(flags, BigEndian, {
+        first: bool,
+        second: bool,
+        legacy: bool as u8,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<bool, LittleEndian, 5>;
+        assert_eq!(Some(1), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(1, flags::second::OFFSET);
+        assert_eq!(Some(3), flags::SIZE);
+        assert_eq!(FieldKind::UnsignedInteger, flags::Fields::first.kind);
+        assert_eq!("bool", flags::Fields::first.type_name);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xff; 8];
+        type Field1 = PrimitiveField<bool, LittleEndian, 2>;
+        type Field2 = PrimitiveField<bool, BigEndian, 3>;
+        type Field3 = PrimitiveField<bool, NativeEndian, 4>;
+        Field1::write(&mut storage, true);
+        Field2::write(&mut storage, false);
+        assert!(Field1::try_read(&storage).unwrap());
+        assert!(!Field2::try_read(&storage).unwrap());
+        assert!(matches!(
+            Field3::try_read(&storage),
+            Err(InvalidBoolError(_))
+        ));
+        assert_eq!([0xff, 0xff, 1, 0, 0xff, 0xff, 0xff, 0xff], storage);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = flags::View::new([0, 2, 1]);
+        assert!(!view.first().try_read().unwrap());
+        assert!(matches!(view.second().try_read(), Err(InvalidBoolError(_))));
+        view.first_mut().write(true);
+        view.second_mut().write(false);
+        assert!(view.first().try_read().unwrap());
+        assert!(!view.second().try_read().unwrap());
+        assert!(view.legacy().try_read().unwrap());
+        assert_eq!([1, 0, 1], view.into_storage());
+    }
+}
@@ -4,6 +4,7 @@ use super::{FieldCopyAccess, PrimitiveField};
 use crate::endianness::{EndianKind, Endianness};
 use crate::fields::primitive::view::FieldView;
 use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
 
 macro_rules! float_field {
     ($type:ty) => {
@@ -97,7 +98,7 @@ macro_rules! float_field {
             }
         }
 
-        impl_field_traits!($type);
+        impl_field_traits!($type, FieldKind::Float);
     };
 }
 
@@ -0,0 +1,162 @@
+use core::convert::Infallible;
+use half::{bf16, f16};
+
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::{EndianKind, Endianness};
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+macro_rules! half_float_field {
+    ($type:ident, $kind: expr) => {
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<$type, E, OFFSET_> {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = Infallible;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $type;
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the half precision float field from a given data region, assuming the defined layout, using the [Field] API.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+                use half::", stringify!($type), ";
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_float_field: half::", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &[u8]) -> ", stringify!($type), " {
+                    let read: ", stringify!($type), " = my_layout::some_float_field::try_read(storage_data).unwrap();
+                    read
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_read(storage: &[u8]) -> Result<$type, Infallible> {
+                    let value: [u8; 2] = storage[Self::OFFSET..(Self::OFFSET + 2)].try_into().unwrap();
+                    let value = match E::KIND {
+                        EndianKind::Big => <$type>::from_be_bytes(value),
+                        EndianKind::Little => <$type>::from_le_bytes(value),
+                        EndianKind::Native => <$type>::from_ne_bytes(value),
+                    };
+                    Ok(value)
+                }
+            }
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Write the half precision float field to a given data region, assuming the defined layout, using the [Field] API.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+                use half::", stringify!($type), ";
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_float_field: half::", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &mut [u8]) {
+                    my_layout::some_float_field::try_write(storage_data, ", stringify!($type), "::from_f32(10.0)).unwrap();
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_write(storage: &mut [u8], value: $type) -> Result<(), Infallible> {
+                    let value_as_bytes = match E::KIND {
+                        EndianKind::Big => value.to_be_bytes(),
+                        EndianKind::Little => value.to_le_bytes(),
+                        EndianKind::Native => value.to_ne_bytes(),
+                    };
+                    storage[Self::OFFSET..(Self::OFFSET + 2)].copy_from_slice(&value_as_bytes);
+                    Ok(())
+                }
+            }
+        }
+
+        impl_field_traits!($type, $kind);
+    };
+}
+
+half_float_field!(f16, FieldKind::Float);
+half_float_field!(bf16, FieldKind::BFloat16);
+
+#[cfg(test)]
+mod tests {
+    use half::{bf16, f16};
+
+    use crate::prelude::*;
+    use crate::{FieldKind, LayoutInfo, PrimitiveField};
+
+    binary_layout!(weights, BigEndian, {
+        scale: f16,
+        weight: bf16,
+    });
+
+    binary_layout!(little_weights, LittleEndian, {
+        scale: f16,
+        weight: bf16,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<f16, LittleEndian, 5>;
+        type Field2 = PrimitiveField<bf16, BigEndian, 7>;
+        assert_eq!(Some(2), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(2), Field2::SIZE);
+        assert_eq!(7, Field2::OFFSET);
+        assert_eq!(Some(4), weights::SIZE);
+        assert_eq!(FieldKind::Float, weights::Fields::scale.kind);
+        assert_eq!(FieldKind::BFloat16, weights::Fields::weight.kind);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 4];
+        weights::scale::write(&mut storage, f16::from_f32(1.5));
+        weights::weight::write(&mut storage, bf16::from_f32(-2.0));
+        assert_eq!([0x3e, 0x00, 0xc0, 0x00], storage);
+        assert_eq!(f16::from_f32(1.5), weights::scale::read(&storage));
+        assert_eq!(bf16::from_f32(-2.0), weights::weight::read(&storage));
+
+        little_weights::scale::write(&mut storage, f16::from_f32(1.5));
+        little_weights::weight::write(&mut storage, bf16::from_f32(-2.0));
+        assert_eq!([0x00, 0x3e, 0x00, 0xc0], storage);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = little_weights::View::new([0; 4]);
+        view.scale_mut().write(f16::MAX);
+        view.weight_mut().write(bf16::from_f32(0.25));
+        assert_eq!(f16::MAX, view.scale().read());
+        assert_eq!(bf16::from_f32(0.25), view.weight().read());
+        assert_eq!([0xff, 0x7b, 0x80, 0x3e], view.into_storage());
+    }
+
+    #[test]
+    fn layout_info() {
+        let layout: &LayoutInfo = &weights::LAYOUT;
+        let data = [0x3e, 0x00, 0xc0, 0x00];
+        assert_eq!(
+            crate::FieldValue::Float(1.5),
+            layout.read_field(&layout.fields[0], &data)
+        );
+        assert_eq!(
+            crate::FieldValue::Float(-2.0),
+            layout.read_field(&layout.fields[1], &data)
+        );
+    }
+}
@@ -4,6 +4,7 @@ use super::{FieldCopyAccess, PrimitiveField};
 use crate::endianness::{EndianKind, Endianness};
 use crate::fields::primitive::view::FieldView;
 use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
 
 macro_rules! int_field {
     ($type:ty) => {
@@ -83,7 +84,54 @@ macro_rules! int_field {
             }
         }
 
-        impl_field_traits!($type);
+        impl<E: Endianness, const OFFSET_: usize> PrimitiveField<$type, E, OFFSET_> {
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the integer field from a byte array in a const context, assuming the defined layout, using the [Field] API.
+                This allows parsing data known at compile time, e.g. from `include_bytes!`, into constants.
+                If the array is too short for the field, this fails to compile when evaluated in a const context.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_integer_field: ", stringify!($type), "
+                    //... other fields ...
+                });
+
+                const DATA: [u8; core::mem::size_of::<", stringify!($type), ">()] = [1; core::mem::size_of::<", stringify!($type), ">()];
+                const VALUE: ", stringify!($type), " = my_layout::some_integer_field::read_const(&DATA);
+                # fn main() {}
+                ```
+                "},
+                #[inline(always)]
+                pub const fn read_const<const N: usize>(storage: &[u8; N]) -> $type {
+                    let mut value = [0; core::mem::size_of::<$type>()];
+                    let mut index = 0;
+                    while index < value.len() {
+                        value[index] = storage[OFFSET_ + index];
+                        index += 1;
+                    }
+                    match E::KIND {
+                        EndianKind::Big => <$type>::from_be_bytes(value),
+                        EndianKind::Little => <$type>::from_le_bytes(value),
+                        EndianKind::Native => <$type>::from_ne_bytes(value),
+                    }
+                }
+            }
+        }
+
+        impl_field_traits!(
+            $type,
+            if <$type>::MIN == 0 {
+                FieldKind::UnsignedInteger
+            } else {
+                FieldKind::SignedInteger
+            }
+        );
     };
 }
 
@@ -234,4 +282,30 @@ mod tests {
     test_int!(u32, 4, 10u32.pow(8), (10u32.pow(7)));
     test_int!(u64, 8, 10u64.pow(15), (10u64.pow(14)));
     test_int!(u128, 16, 10u128.pow(30), (10u128.pow(28)));
+
+    #[test]
+    fn read_const() {
+        binary_layout!(config, BigEndian, {
+            version: u8,
+            flags: u16,
+            offset: i32,
+        });
+        binary_layout!(le_config, LittleEndian, {
+            version: u8,
+            flags: u16,
+            offset: i32,
+        });
+        const DATA: [u8; 8] = [2, 0x01, 0x02, 0xff, 0xff, 0xff, 0xfe, 0xaa];
+        const VERSION: u8 = config::version::read_const(&DATA);
+        const FLAGS: u16 = config::flags::read_const(&DATA);
+        const OFFSET: i32 = config::offset::read_const(&DATA);
+        const LE_FLAGS: u16 = le_config::flags::read_const(&DATA);
+        const LE_OFFSET: i32 = le_config::offset::read_const(&DATA);
+        assert_eq!(2, VERSION);
+        assert_eq!(0x0102, FLAGS);
+        assert_eq!(-2, OFFSET);
+        assert_eq!(0x0201, LE_FLAGS);
+        assert_eq!(-16_777_217, LE_OFFSET);
+        assert_eq!(OFFSET, config::offset::read(&DATA));
+    }
 }
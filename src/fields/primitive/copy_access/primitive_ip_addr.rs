@@ -0,0 +1,146 @@
+use core::convert::Infallible;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+macro_rules! ip_addr_field {
+    ($type:ident, $size: expr) => {
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<$type, E, OFFSET_> {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = Infallible;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $type;
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the IP address field from a given data region, assuming the defined layout, using the [Field] API.
+                IP addresses are always stored in network byte order, independent of the endianness of the layout.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+                use std::net::", stringify!($type), ";
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_address_field: std::net::", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &[u8]) -> ", stringify!($type), " {
+                    let read: ", stringify!($type), " = my_layout::some_address_field::try_read(storage_data).unwrap();
+                    read
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_read(storage: &[u8]) -> Result<$type, Infallible> {
+                    let octets: [u8; $size] = storage[Self::OFFSET..(Self::OFFSET + $size)].try_into().unwrap();
+                    Ok($type::from(octets))
+                }
+            }
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Write the IP address field to a given data region, assuming the defined layout, using the [Field] API.
+                IP addresses are always stored in network byte order, independent of the endianness of the layout.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+                use std::net::", stringify!($type), ";
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_address_field: std::net::", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &mut [u8]) {
+                    my_layout::some_address_field::try_write(storage_data, ", stringify!($type), "::LOCALHOST).unwrap();
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_write(storage: &mut [u8], value: $type) -> Result<(), Infallible> {
+                    storage[Self::OFFSET..(Self::OFFSET + $size)].copy_from_slice(&value.octets());
+                    Ok(())
+                }
+            }
+        }
+
+        impl_field_traits!($type, FieldKind::Bytes);
+    };
+}
+
+ip_addr_field!(Ipv4Addr, 4);
+ip_addr_field!(Ipv6Addr, 16);
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(route, LittleEndian, {
+        metric: u16,
+        next_hop: Ipv4Addr,
+        prefix: Ipv6Addr,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<Ipv4Addr, BigEndian, 5>;
+        type Field2 = PrimitiveField<Ipv6Addr, LittleEndian, 7>;
+        assert_eq!(Some(4), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(16), Field2::SIZE);
+        assert_eq!(7, Field2::OFFSET);
+        assert_eq!(Some(22), route::SIZE);
+        assert_eq!(FieldKind::Bytes, route::Fields::next_hop.kind);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 22];
+        route::next_hop::write(&mut storage, Ipv4Addr::new(192, 168, 0, 1));
+        route::prefix::write(&mut storage, Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        // Addresses are stored in network byte order even in little endian layouts
+        assert_eq!([192, 168, 0, 1], storage[2..6]);
+        assert_eq!(
+            [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            storage[6..]
+        );
+        assert_eq!(
+            Ipv4Addr::new(192, 168, 0, 1),
+            route::next_hop::read(&storage)
+        );
+        assert_eq!(
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+            route::prefix::read(&storage)
+        );
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = route::View::new([0; 22]);
+        view.metric_mut().write(10);
+        view.next_hop_mut().write(Ipv4Addr::BROADCAST);
+        view.prefix_mut().write(Ipv6Addr::LOCALHOST);
+        assert_eq!(10, view.metric().read());
+        assert_eq!(Ipv4Addr::BROADCAST, view.next_hop().read());
+        assert_eq!(Ipv6Addr::LOCALHOST, view.prefix().read());
+        let storage = view.into_storage();
+        assert_eq!([255; 4], storage[2..6]);
+        assert_eq!(1, storage[21]);
+    }
+}
@@ -4,6 +4,7 @@ use super::{FieldCopyAccess, PrimitiveField};
 use crate::endianness::{EndianKind, Endianness};
 use crate::fields::primitive::view::FieldView;
 use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
 
 macro_rules! nonzero_int_field {
     ($type:ty, $zero_type:ty) => {
@@ -84,7 +85,102 @@ macro_rules! nonzero_int_field {
             }
         }
 
-        impl_field_traits!($type);
+        impl_field_traits!(
+            $type,
+            if <$zero_type>::MIN == 0 {
+                FieldKind::UnsignedInteger
+            } else {
+                FieldKind::SignedInteger
+            }
+        );
+
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<Option<$type>, E, OFFSET_> {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = Infallible;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = Option<$type>;
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the integer field from a given data region, assuming the defined layout, using the [Field] API.
+                A zero value is read as `None`.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_integer_field: Option<", stringify!($type), ">
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &[u8]) -> Option<",stringify!($type), "> {
+                    let read: Option<", stringify!($type), "> = my_layout::some_integer_field::read(storage_data);
+                    read
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_read(storage: &[u8]) -> Result<Option<$type>, Infallible> {
+                    let value: [u8; core::mem::size_of::<$type>()] = storage[Self::OFFSET..(Self::OFFSET + core::mem::size_of::<$type>())].try_into().unwrap();
+                    let value = match E::KIND {
+                        EndianKind::Big => <$zero_type>::from_be_bytes(value),
+                        EndianKind::Little => <$zero_type>::from_le_bytes(value),
+                        EndianKind::Native => <$zero_type>::from_ne_bytes(value)
+                    };
+                    Ok(<$type>::new(value))
+                }
+            }
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Write the integer field to a given data region, assuming the defined layout, using the [Field] API.
+                `None` is written as zero.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_integer_field: Option<", stringify!($type), ">
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &mut [u8]) {
+                    my_layout::some_integer_field::write(storage_data, ", stringify!($type), "::new(10));
+                    my_layout::some_integer_field::write(storage_data, None);
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_write(storage: &mut [u8], value: Option<$type>) -> Result<(), Infallible> {
+                    let value = value.map_or(0, <$type>::get);
+                    let value_as_bytes = match E::KIND {
+                        EndianKind::Big => value.to_be_bytes(),
+                        EndianKind::Little => value.to_le_bytes(),
+                        EndianKind::Native => value.to_ne_bytes(),
+                    };
+                    storage[Self::OFFSET..(Self::OFFSET + core::mem::size_of::<$type>())]
+                        .copy_from_slice(&value_as_bytes);
+                    Ok(())
+                }
+            }
+        }
+
+        impl_field_traits!(
+            Option<$type>,
+            if <$zero_type>::MIN == 0 {
+                FieldKind::UnsignedInteger
+            } else {
+                FieldKind::SignedInteger
+            }
+        );
     };
 }
 
@@ -246,6 +342,57 @@ mod tests {
                     assert_eq!(value2, $type::new($underlying_type::$endian_fn((&storage[$expected_size..(2*$expected_size)]).try_into().unwrap())).unwrap());
                     assert_eq!(0, $underlying_type::$endian_fn((&storage[2*$expected_size..(3*$expected_size)]).try_into().unwrap()));
                 }
+
+                #[allow(non_snake_case)]
+                #[test]
+                fn [<test_option_ $type _ $endian endian_fieldapi>]() {
+                    let mut storage = [0xff; 1024];
+
+                    let value1 = <$type>::new($value1);
+
+                    type Field1 = PrimitiveField<Option<$type>, $endian_type, 5>;
+                    type Field2 = PrimitiveField<Option<$type>, $endian_type, 123>;
+
+                    assert_eq!(Some($expected_size), Field1::SIZE);
+                    assert_eq!(123, Field2::OFFSET);
+
+                    Field1::write(&mut storage, value1);
+                    Field2::write(&mut storage, None);
+
+                    assert_eq!(value1, Field1::read(&storage));
+                    assert_eq!(None, Field2::read(&storage));
+
+                    assert_eq!($value1, $underlying_type::$endian_fn((&storage[5..(5+$expected_size)]).try_into().unwrap()));
+                    assert_eq!(0, $underlying_type::$endian_fn((&storage[123..(123+$expected_size)]).try_into().unwrap()));
+                }
+
+                #[allow(non_snake_case)]
+                #[test]
+                fn [<test_option_ $type _ $endian endian_viewapi>]() {
+                    binary_layout!(layout, $endian_type, {
+                        field1: Option<$type>,
+                        field2: Option<$type>,
+                        field3: Option<$type>,
+                    });
+                    let mut storage = [0; 1024];
+                    let mut view = layout::View::new(&mut storage);
+
+                    let value1 = <$type>::new($value1);
+                    let value2 = <$type>::new($value2);
+
+                    view.field1_mut().write(value1);
+                    view.field2_mut().try_write(value2).infallible_unwrap();
+                    // don't write Field3, that should leave it at zero
+
+                    assert_eq!(value1, view.field1().read());
+                    assert_eq!(value2, view.field2().try_read().infallible_unwrap());
+                    assert_eq!(None, view.field3().read());
+
+                    view.field2_mut().write(None);
+                    assert_eq!(None, view.field2().read());
+                    assert_eq!($value1, $underlying_type::$endian_fn((&storage[0..($expected_size)]).try_into().unwrap()));
+                    assert_eq!(0, $underlying_type::$endian_fn((&storage[$expected_size..(2*$expected_size)]).try_into().unwrap()));
+                }
             }
         };
     }
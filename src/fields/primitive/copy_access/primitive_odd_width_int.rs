@@ -0,0 +1,290 @@
+use core::convert::Infallible;
+
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// This error is thrown when trying to write a value into an integer field that is narrower than the type it is written as,
+/// i.e. a field with an odd byte width like [u24] or a [BitField](crate::BitField), but the value doesn't fit into the field.
+#[derive(Debug)]
+pub struct IntegerOutOfRangeError(pub(crate) ());
+
+impl core::fmt::Display for IntegerOutOfRangeError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "IntegerOutOfRangeError")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IntegerOutOfRangeError {}
+
+macro_rules! odd_width_int_field {
+    ($type: ident, $high_level_type: ident, $size: literal, $bits: literal, $signedness: literal) => {
+        doc_comment::doc_comment! {
+            concat! {"
+            A ", $bits, " bit ", $signedness, " integer, to be used as a field type in [binary_layout!](crate::binary_layout!).
+            The field is stored in ", $size, " bytes and read and written as [", stringify!($high_level_type), "].
+
+            Writing a value outside of the range from [", stringify!($type), "::MIN] to [", stringify!($type), "::MAX] fails with [IntegerOutOfRangeError].
+            Because of this, [FieldWriteExt::write](crate::FieldWriteExt::write) and [FieldView::write] are not available for those fields
+            and you need to use [FieldCopyAccess::try_write] and [FieldView::try_write].
+
+            # Example:
+
+            ```
+            use binary_layout::{prelude::*, ", stringify!($type), "};
+
+            binary_layout!(my_layout, BigEndian, {
+                //... other fields ...
+                some_integer_field: ", stringify!($type), "
+                //... other fields ...
+            });
+
+            fn func(storage_data: &mut [u8]) -> Result<", stringify!($high_level_type), ", IntegerOutOfRangeError> {
+                my_layout::some_integer_field::try_write(storage_data, 10)?;
+                let read: ", stringify!($high_level_type), " = my_layout::some_integer_field::read(storage_data);
+                Ok(read)
+            }
+
+            # fn main() {}
+            ```
+            "},
+            #[allow(non_camel_case_types)]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub enum $type {}
+        }
+
+        impl $type {
+            /// The smallest value that can be stored in the field
+            pub const MIN: $high_level_type = <$high_level_type>::MIN >> Self::SHIFT;
+            /// The largest value that can be stored in the field
+            pub const MAX: $high_level_type = <$high_level_type>::MAX >> Self::SHIFT;
+
+            const SHIFT: u32 = ((core::mem::size_of::<$high_level_type>() - $size) * 8) as u32;
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<$type, E, OFFSET_> {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = IntegerOutOfRangeError;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $high_level_type;
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the integer field from a given data region, assuming the defined layout, using the [Field] API.
+
+                # Example:
+
+                ```
+                use binary_layout::{prelude::*, ", stringify!($type), "};
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_integer_field: ", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &[u8]) -> ", stringify!($high_level_type), " {
+                    let read: ", stringify!($high_level_type), " = my_layout::some_integer_field::try_read(storage_data).unwrap();
+                    read
+                }
+
+                # fn main() {}
+                ```
+                "},
+                #[inline(always)]
+                fn try_read(storage: &[u8]) -> Result<$high_level_type, Infallible> {
+                    let data = &storage[Self::OFFSET..(Self::OFFSET + $size)];
+                    let mut bytes = [0; core::mem::size_of::<$high_level_type>()];
+                    let value = if E::KIND.is_big_endian() {
+                        bytes[(core::mem::size_of::<$high_level_type>() - $size)..].copy_from_slice(data);
+                        <$high_level_type>::from_be_bytes(bytes)
+                    } else {
+                        bytes[..$size].copy_from_slice(data);
+                        <$high_level_type>::from_le_bytes(bytes)
+                    };
+                    // Shifting back and forth sign extends signed values
+                    Ok((value << $type::SHIFT) >> $type::SHIFT)
+                }
+            }
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Write the integer field to a given data region, assuming the defined layout, using the [Field] API.
+
+                # Example:
+
+                ```
+                use binary_layout::{prelude::*, ", stringify!($type), "};
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_integer_field: ", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &mut [u8]) -> Result<(), IntegerOutOfRangeError> {
+                    my_layout::some_integer_field::try_write(storage_data, 10)?;
+                    Ok(())
+                }
+
+                # fn main() {}
+                ```
+                "},
+                #[inline(always)]
+                fn try_write(storage: &mut [u8], value: $high_level_type) -> Result<(), IntegerOutOfRangeError> {
+                    if (value << $type::SHIFT) >> $type::SHIFT != value {
+                        return Err(IntegerOutOfRangeError(()));
+                    }
+                    let data = &mut storage[Self::OFFSET..(Self::OFFSET + $size)];
+                    if E::KIND.is_big_endian() {
+                        data.copy_from_slice(&value.to_be_bytes()[(core::mem::size_of::<$high_level_type>() - $size)..]);
+                    } else {
+                        data.copy_from_slice(&value.to_le_bytes()[..$size]);
+                    }
+                    Ok(())
+                }
+            }
+        }
+
+        impl_field_traits!(
+            $type,
+            if <$high_level_type>::MIN == 0 {
+                FieldKind::UnsignedInteger
+            } else {
+                FieldKind::SignedInteger
+            },
+            $size
+        );
+    };
+}
+
+odd_width_int_field!(i24, i32, 3, 24, "signed");
+odd_width_int_field!(i40, i64, 5, 40, "signed");
+odd_width_int_field!(i48, i64, 6, 48, "signed");
+odd_width_int_field!(i56, i64, 7, 56, "signed");
+odd_width_int_field!(u24, u32, 3, 24, "unsigned");
+odd_width_int_field!(u40, u64, 5, 40, "unsigned");
+odd_width_int_field!(u48, u64, 6, 48, "unsigned");
+odd_width_int_field!(u56, u64, 7, 56, "unsigned");
+
+#[cfg(test)]
+mod tests {
+    use super::{i24, i48, u24, u40, u48, u56, IntegerOutOfRangeError};
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(little, LittleEndian, {
+        sample: i24,
+        length: u24,
+        timestamp: u48,
+        delta: i48,
+    });
+
+    binary_layout!(big, BigEndian, {
+        sample: i24,
+        length: u24,
+        timestamp: u48,
+        delta: i48,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<u40, LittleEndian, 5>;
+        assert_eq!(Some(5), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(18), little::SIZE);
+        assert_eq!(6, little::timestamp::OFFSET);
+        assert_eq!(12, little::delta::OFFSET);
+        assert_eq!(FieldKind::SignedInteger, little::Fields::sample.kind);
+        assert_eq!(FieldKind::UnsignedInteger, little::Fields::length.kind);
+        assert_eq!("u48", little::Fields::timestamp.type_name);
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(0, u24::MIN);
+        assert_eq!(0xff_ffff, u24::MAX);
+        assert_eq!(-0x80_0000, i24::MIN);
+        assert_eq!(0x7f_ffff, i24::MAX);
+        assert_eq!(0xff_ffff_ffff_ffff, u56::MAX);
+        assert_eq!(-0x8000_0000_0000, i48::MIN);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 16];
+        type Field1 = PrimitiveField<u24, LittleEndian, 1>;
+        type Field2 = PrimitiveField<i24, BigEndian, 4>;
+        type Field3 = PrimitiveField<u40, NativeEndian, 8>;
+        Field1::try_write(&mut storage, 0x010203).unwrap();
+        Field2::try_write(&mut storage, -2).unwrap();
+        Field3::try_write(&mut storage, 0x01_0203_0405).unwrap();
+        assert_eq!(0x010203, Field1::read(&storage));
+        assert_eq!(-2, Field2::read(&storage));
+        assert_eq!(0x01_0203_0405, Field3::read(&storage));
+        assert_eq!([0, 3, 2, 1, 0xff, 0xff, 0xfe, 0], storage[..8]);
+        if cfg!(target_endian = "big") {
+            assert_eq!([1, 2, 3, 4, 5], storage[8..13]);
+        } else {
+            assert_eq!([5, 4, 3, 2, 1], storage[8..13]);
+        }
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = little::View::new([0; 18]);
+        view.sample_mut().try_write(-1000).unwrap();
+        view.length_mut().try_write(u24::MAX).unwrap();
+        view.timestamp_mut().try_write(0x0102_0304_0506).unwrap();
+        view.delta_mut().try_write(i48::MIN).unwrap();
+        assert_eq!(-1000, view.sample().read());
+        assert_eq!(u24::MAX, view.length().read());
+        assert_eq!(0x0102_0304_0506, view.timestamp().read());
+        assert_eq!(i48::MIN, view.delta().read());
+        assert_eq!(
+            [0x18, 0xfc, 0xff, 0xff, 0xff, 0xff, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0, 0, 0x80],
+            view.into_storage()
+        );
+
+        let mut view = big::View::new([0; 18]);
+        view.sample_mut().try_write(-1000).unwrap();
+        view.length_mut().try_write(0x010203).unwrap();
+        view.timestamp_mut().try_write(0x0102_0304_0506).unwrap();
+        view.delta_mut().try_write(-1).unwrap();
+        assert_eq!(-1000, view.sample().read());
+        assert_eq!(0x010203, view.length().read());
+        assert_eq!(0x0102_0304_0506, view.timestamp().read());
+        assert_eq!(-1, view.delta().read());
+        assert_eq!(
+            [0xff, 0xfc, 0x18, 1, 2, 3, 1, 2, 3, 4, 5, 6, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut view = little::View::new([0; 18]);
+        assert!(matches!(
+            view.length_mut().try_write(u24::MAX + 1),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        assert!(matches!(
+            view.sample_mut().try_write(i24::MIN - 1),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        assert!(matches!(
+            view.sample_mut().try_write(i24::MAX + 1),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        assert!(matches!(
+            view.timestamp_mut().try_write(1 << 48),
+            Err(IntegerOutOfRangeError(_))
+        ));
+        assert_eq!([0; 18], view.into_storage());
+    }
+}
@@ -0,0 +1,585 @@
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
+
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+use crate::InfallibleResultExt;
+
+/// Field type for a point in time stored as whole seconds since the unix epoch in an integer of type `T`,
+/// which can be [u32], [u64] or [i64]. It is read and written as `Time`, which defaults to [SystemTime].
+/// With the `chrono` feature, `Time` can also be `chrono::DateTime<chrono::Utc>`, and with the `time` feature `time::OffsetDateTime`.
+///
+/// Reading fails with [TimeOutOfRangeError] if the stored value can't be represented as [SystemTime] on this platform,
+/// writing fails with it if the time doesn't fit into `T`, e.g. times before the epoch for unsigned types.
+/// Fractions of a second are truncated towards the epoch when writing.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, UnixSeconds};
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+///
+/// binary_layout!(log_entry, BigEndian, {
+///   timestamp: UnixSeconds<u32>,
+///   level: u8,
+/// });
+///
+/// fn main() {
+///     let mut view = log_entry::View::new([0; 5]);
+///     let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+///     view.timestamp_mut().try_write(time).unwrap();
+///     assert_eq!(time, view.timestamp().try_read().unwrap());
+///     assert!(view.timestamp_mut().try_write(UNIX_EPOCH - Duration::from_secs(1)).is_err());
+/// }
+/// ```
+pub struct UnixSeconds<T, Time = SystemTime> {
+    _p1: PhantomData<T>,
+    _p2: PhantomData<Time>,
+}
+
+/// Field type for a point in time stored as whole milliseconds since the unix epoch in an integer of type `T`,
+/// which can be [u32], [u64] or [i64]. It is read and written as `Time`, which defaults to [SystemTime], see [UnixSeconds].
+pub struct UnixMillis<T, Time = SystemTime> {
+    _p1: PhantomData<T>,
+    _p2: PhantomData<Time>,
+}
+
+/// Field type for a time span stored as whole seconds in an integer of type `T`,
+/// which can be [u32], [u64] or [i64]. It is read and written as [Duration].
+///
+/// Reading fails with [TimeOutOfRangeError] if the stored value is negative, writing fails with it if the duration doesn't fit into `T`.
+/// Fractions of a second are truncated when writing.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, DurationMillis};
+/// use std::time::Duration;
+///
+/// binary_layout!(job, LittleEndian, {
+///   timeout: DurationMillis<u32>,
+/// });
+///
+/// fn main() {
+///     let mut view = job::View::new([0; 4]);
+///     view.timeout_mut().try_write(Duration::from_millis(1500)).unwrap();
+///     assert_eq!(Duration::from_millis(1500), view.timeout().try_read().unwrap());
+///     assert_eq!([0xdc, 0x05, 0, 0], view.into_storage());
+/// }
+/// ```
+pub struct DurationSeconds<T> {
+    _p: PhantomData<T>,
+}
+
+/// Field type for a time span stored as whole milliseconds in an integer of type `T`,
+/// which can be [u32], [u64] or [i64]. It is read and written as [Duration], see [DurationSeconds].
+pub struct DurationMillis<T> {
+    _p: PhantomData<T>,
+}
+
+/// Field type for a time span stored as whole microseconds in an integer of type `T`,
+/// which can be [u32], [u64] or [i64]. It is read and written as [Duration], see [DurationSeconds].
+pub struct DurationMicros<T> {
+    _p: PhantomData<T>,
+}
+
+/// Field type for a time span stored as whole nanoseconds in an integer of type `T`,
+/// which can be [u32], [u64] or [i64]. It is read and written as [Duration], see [DurationSeconds].
+///
+/// Note that a [u32] only holds a little over 4 seconds in nanoseconds.
+pub struct DurationNanos<T> {
+    _p: PhantomData<T>,
+}
+
+/// This error is thrown when a time field like [UnixSeconds] or [DurationSeconds] is read and the stored value
+/// can't be represented as its [SystemTime], [Duration] or other time type, or when it is written and the value doesn't fit into the field.
+#[derive(Debug)]
+pub struct TimeOutOfRangeError(pub(crate) ());
+
+impl core::fmt::Display for TimeOutOfRangeError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "TimeOutOfRangeError")
+    }
+}
+
+impl std::error::Error for TimeOutOfRangeError {}
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+fn duration_to_units(duration: Duration, units_per_sec: i128) -> i128 {
+    i128::from(duration.as_secs()) * units_per_sec
+        + i128::from(duration.subsec_nanos()) * units_per_sec / NANOS_PER_SEC
+}
+
+fn units_to_duration(units: i128, units_per_sec: i128) -> Option<Duration> {
+    let secs = u64::try_from(units / units_per_sec).ok()?;
+    // A negative remainder means the units were negative, which `u64::try_from` only catches for a full second or more
+    let nanos = u32::try_from(units % units_per_sec * (NANOS_PER_SEC / units_per_sec)).ok()?;
+    Some(Duration::new(secs, nanos))
+}
+
+fn system_time_to_units(time: SystemTime, units_per_sec: i128) -> i128 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => duration_to_units(since_epoch, units_per_sec),
+        Err(err) => -duration_to_units(err.duration(), units_per_sec),
+    }
+}
+
+fn units_to_system_time(units: i128, units_per_sec: i128) -> Option<SystemTime> {
+    if units >= 0 {
+        UNIX_EPOCH.checked_add(units_to_duration(units, units_per_sec)?)
+    } else {
+        UNIX_EPOCH.checked_sub(units_to_duration(-units, units_per_sec)?)
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_to_units(time: DateTime<Utc>, units_per_sec: i128) -> i128 {
+    let nanos =
+        i128::from(time.timestamp()) * NANOS_PER_SEC + i128::from(time.timestamp_subsec_nanos());
+    // Division truncates towards the epoch, like for SystemTime
+    nanos / (NANOS_PER_SEC / units_per_sec)
+}
+
+#[cfg(feature = "chrono")]
+fn units_to_chrono(units: i128, units_per_sec: i128) -> Option<DateTime<Utc>> {
+    let nanos = units * (NANOS_PER_SEC / units_per_sec);
+    let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+    let subsec_nanos = u32::try_from(nanos.rem_euclid(NANOS_PER_SEC)).ok()?;
+    DateTime::from_timestamp(secs, subsec_nanos)
+}
+
+#[cfg(feature = "time")]
+fn offset_date_time_to_units(time: OffsetDateTime, units_per_sec: i128) -> i128 {
+    time.unix_timestamp_nanos() / (NANOS_PER_SEC / units_per_sec)
+}
+
+#[cfg(feature = "time")]
+fn units_to_offset_date_time(units: i128, units_per_sec: i128) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(units * (NANOS_PER_SEC / units_per_sec)).ok()
+}
+
+macro_rules! time_field {
+    ($marker: ty, $type: ty, $kind: expr, $high_level_type: ty, $units_per_sec: expr, $to_units: ident, $from_units: ident) => {
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess
+            for PrimitiveField<$marker, E, OFFSET_>
+        {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = TimeOutOfRangeError;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = TimeOutOfRangeError;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $high_level_type;
+
+            #[inline]
+            fn try_read(storage: &[u8]) -> Result<$high_level_type, TimeOutOfRangeError> {
+                let value =
+                    <PrimitiveField<$type, E, OFFSET_> as FieldCopyAccess>::try_read(storage)
+                        .infallible_unwrap();
+                $from_units(i128::from(value), $units_per_sec).ok_or(TimeOutOfRangeError(()))
+            }
+
+            #[inline]
+            fn try_write(
+                storage: &mut [u8],
+                value: $high_level_type,
+            ) -> Result<(), TimeOutOfRangeError> {
+                let value = <$type>::try_from($to_units(value, $units_per_sec))
+                    .map_err(|_| TimeOutOfRangeError(()))?;
+                <PrimitiveField<$type, E, OFFSET_> as FieldCopyAccess>::try_write(storage, value)
+                    .infallible_unwrap();
+                Ok(())
+            }
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> Field for PrimitiveField<$marker, E, OFFSET_> {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = Some(core::mem::size_of::<$type>());
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<$marker, E, OFFSET_>
+        {
+            const KIND: FieldKind = $kind;
+        }
+
+        impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+            for PrimitiveField<$marker, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+            for PrimitiveField<$marker, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, const OFFSET_: usize> StorageIntoFieldView<S>
+            for PrimitiveField<$marker, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+macro_rules! time_fields {
+    ($marker: ident$(<$time: ty>)?, $high_level_type: ty, $units_per_sec: expr, $to_units: ident, $from_units: ident) => {
+        time_field!(
+            $marker<u32 $(, $time)?>,
+            u32,
+            FieldKind::UnsignedInteger,
+            $high_level_type,
+            $units_per_sec,
+            $to_units,
+            $from_units
+        );
+        time_field!(
+            $marker<u64 $(, $time)?>,
+            u64,
+            FieldKind::UnsignedInteger,
+            $high_level_type,
+            $units_per_sec,
+            $to_units,
+            $from_units
+        );
+        time_field!(
+            $marker<i64 $(, $time)?>,
+            i64,
+            FieldKind::SignedInteger,
+            $high_level_type,
+            $units_per_sec,
+            $to_units,
+            $from_units
+        );
+    };
+}
+
+time_fields!(
+    UnixSeconds,
+    SystemTime,
+    1,
+    system_time_to_units,
+    units_to_system_time
+);
+time_fields!(
+    UnixMillis,
+    SystemTime,
+    1000,
+    system_time_to_units,
+    units_to_system_time
+);
+#[cfg(feature = "chrono")]
+time_fields!(
+    UnixSeconds<DateTime<Utc>>,
+    DateTime<Utc>,
+    1,
+    chrono_to_units,
+    units_to_chrono
+);
+#[cfg(feature = "chrono")]
+time_fields!(
+    UnixMillis<DateTime<Utc>>,
+    DateTime<Utc>,
+    1000,
+    chrono_to_units,
+    units_to_chrono
+);
+#[cfg(feature = "time")]
+time_fields!(
+    UnixSeconds<OffsetDateTime>,
+    OffsetDateTime,
+    1,
+    offset_date_time_to_units,
+    units_to_offset_date_time
+);
+#[cfg(feature = "time")]
+time_fields!(
+    UnixMillis<OffsetDateTime>,
+    OffsetDateTime,
+    1000,
+    offset_date_time_to_units,
+    units_to_offset_date_time
+);
+time_fields!(
+    DurationSeconds,
+    Duration,
+    1,
+    duration_to_units,
+    units_to_duration
+);
+time_fields!(
+    DurationMillis,
+    Duration,
+    1000,
+    duration_to_units,
+    units_to_duration
+);
+time_fields!(
+    DurationMicros,
+    Duration,
+    1_000_000,
+    duration_to_units,
+    units_to_duration
+);
+time_fields!(
+    DurationNanos,
+    Duration,
+    1_000_000_000,
+    duration_to_units,
+    units_to_duration
+);
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        DurationMicros, DurationMillis, DurationNanos, DurationSeconds, UnixMillis, UnixSeconds,
+    };
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(record, LittleEndian, {
+        created: UnixSeconds<u32>,
+        modified: UnixMillis<i64>,
+        ttl: DurationSeconds<i64>,
+        latency: DurationMillis<u64>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<UnixSeconds<u32>, BigEndian, 5>;
+        assert_eq!(Some(4), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(28), record::SIZE);
+        assert_eq!(FieldKind::UnsignedInteger, record::Fields::created.kind);
+        assert_eq!(FieldKind::SignedInteger, record::Fields::modified.kind);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 28];
+        let created = UNIX_EPOCH + Duration::from_secs(0x0102_0304);
+        let modified = UNIX_EPOCH - Duration::from_millis(1500);
+        record::created::try_write(&mut storage, created).unwrap();
+        record::modified::try_write(&mut storage, modified).unwrap();
+        record::ttl::try_write(&mut storage, Duration::from_secs(60)).unwrap();
+        record::latency::try_write(&mut storage, Duration::from_micros(2500)).unwrap();
+
+        assert_eq!([4, 3, 2, 1], storage[0..4]);
+        assert_eq!(
+            -1500,
+            i64::from_le_bytes(storage[4..12].try_into().unwrap())
+        );
+        assert_eq!(60, i64::from_le_bytes(storage[12..20].try_into().unwrap()));
+        // Fractions of the unit are truncated
+        assert_eq!(2, u64::from_le_bytes(storage[20..28].try_into().unwrap()));
+
+        assert_eq!(created, record::created::try_read(&storage).unwrap());
+        assert_eq!(modified, record::modified::try_read(&storage).unwrap());
+        assert_eq!(
+            Duration::from_secs(60),
+            record::ttl::try_read(&storage).unwrap()
+        );
+        assert_eq!(
+            Duration::from_millis(2),
+            record::latency::try_read(&storage).unwrap()
+        );
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = record::View::new([0; 28]);
+        let modified = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        view.modified_mut().try_write(modified).unwrap();
+        view.latency_mut()
+            .try_write(Duration::from_millis(250))
+            .unwrap();
+        assert_eq!(modified, view.modified().try_read().unwrap());
+        assert_eq!(
+            Duration::from_millis(250),
+            view.latency().try_read().unwrap()
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut storage = [0; 28];
+        // Unsigned fields can't store times before the epoch
+        assert!(
+            record::created::try_write(&mut storage, UNIX_EPOCH - Duration::from_secs(1)).is_err()
+        );
+        assert!(record::created::try_write(
+            &mut storage,
+            UNIX_EPOCH + Duration::from_secs(u64::from(u32::MAX) + 1)
+        )
+        .is_err());
+        assert!(record::ttl::try_write(&mut storage, Duration::MAX).is_err());
+        assert_eq!([0; 28], storage);
+
+        // Negative durations can't be read
+        storage[12..20].copy_from_slice(&(-1i64).to_le_bytes());
+        assert!(record::ttl::try_read(&storage).is_err());
+    }
+
+    binary_layout!(heartbeat, BigEndian, {
+        interval: DurationMicros<u32>,
+        jitter: DurationNanos<u32>,
+        timeout: DurationNanos<i64>,
+    });
+
+    #[test]
+    fn sub_millisecond_units() {
+        let mut view = heartbeat::View::new([0; 16]);
+        view.interval_mut()
+            .try_write(Duration::from_nanos(1_500_999))
+            .unwrap();
+        view.jitter_mut()
+            .try_write(Duration::from_nanos(123_456))
+            .unwrap();
+        view.timeout_mut().try_write(Duration::new(30, 5)).unwrap();
+        assert_eq!(
+            Duration::from_micros(1500),
+            view.interval().try_read().unwrap()
+        );
+        assert_eq!(
+            Duration::from_nanos(123_456),
+            view.jitter().try_read().unwrap()
+        );
+        assert_eq!(Duration::new(30, 5), view.timeout().try_read().unwrap());
+        assert_eq!(
+            [0, 0, 0x05, 0xdc, 0, 0x01, 0xe2, 0x40],
+            view.into_storage()[0..8]
+        );
+
+        let mut storage = [0; 16];
+        // A u32 holds a little over 4 seconds in nanoseconds
+        assert!(heartbeat::jitter::try_write(&mut storage, Duration::from_secs(5)).is_err());
+        assert!(heartbeat::timeout::try_write(&mut storage, Duration::MAX).is_err());
+        assert!(heartbeat::interval::try_write(&mut storage, Duration::from_secs(4295)).is_err());
+        assert_eq!([0; 16], storage);
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod chrono_tests {
+    use chrono::{DateTime, Utc};
+
+    use super::{UnixMillis, UnixSeconds};
+    use crate::prelude::*;
+
+    binary_layout!(record, LittleEndian, {
+        created: UnixSeconds<u32, DateTime<Utc>>,
+        modified: UnixMillis<i64, DateTime<Utc>>,
+    });
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 12];
+        let created = DateTime::from_timestamp(0x0102_0304, 0).unwrap();
+        let modified = DateTime::from_timestamp(-2, 500_000_000).unwrap();
+        record::created::try_write(&mut storage, created).unwrap();
+        record::modified::try_write(&mut storage, modified).unwrap();
+        assert_eq!([4, 3, 2, 1], storage[0..4]);
+        assert_eq!(
+            -1500,
+            i64::from_le_bytes(storage[4..12].try_into().unwrap())
+        );
+        assert_eq!(created, record::created::try_read(&storage).unwrap());
+        assert_eq!(modified, record::modified::try_read(&storage).unwrap());
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = record::View::new([0; 12]);
+        let modified = DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+        view.modified_mut().try_write(modified).unwrap();
+        assert_eq!(modified, view.modified().try_read().unwrap());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut storage = [0; 12];
+        let before_epoch = DateTime::from_timestamp(-1, 0).unwrap();
+        assert!(record::created::try_write(&mut storage, before_epoch).is_err());
+        storage[4..12].copy_from_slice(&i64::MAX.to_le_bytes());
+        assert!(record::modified::try_read(&storage).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "time"))]
+mod time_tests {
+    use time::OffsetDateTime;
+
+    use super::{UnixMillis, UnixSeconds};
+    use crate::prelude::*;
+
+    binary_layout!(record, BigEndian, {
+        created: UnixSeconds<u64, OffsetDateTime>,
+        modified: UnixMillis<i64, OffsetDateTime>,
+    });
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 16];
+        let created = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let modified = OffsetDateTime::from_unix_timestamp_nanos(-1_500_000_000).unwrap();
+        record::created::try_write(&mut storage, created).unwrap();
+        record::modified::try_write(&mut storage, modified).unwrap();
+        assert_eq!(
+            1_700_000_000,
+            u64::from_be_bytes(storage[0..8].try_into().unwrap())
+        );
+        assert_eq!(
+            -1500,
+            i64::from_be_bytes(storage[8..16].try_into().unwrap())
+        );
+        assert_eq!(created, record::created::try_read(&storage).unwrap());
+        assert_eq!(modified, record::modified::try_read(&storage).unwrap());
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = record::View::new([0; 16]);
+        let created = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        view.created_mut().try_write(created).unwrap();
+        assert_eq!(created, view.created().try_read().unwrap());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut storage = [0; 16];
+        let before_epoch = OffsetDateTime::from_unix_timestamp(-1).unwrap();
+        assert!(record::created::try_write(&mut storage, before_epoch).is_err());
+        storage[0..8].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(record::created::try_read(&storage).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use super::{FieldCopyAccess, PrimitiveField};
 use crate::endianness::Endianness;
 use crate::fields::primitive::view::FieldView;
 use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::layout_info::{FieldKind, FieldKindInfo};
 
 impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<(), E, OFFSET_> {
     /// See [FieldCopyAccess::ReadError]
@@ -81,7 +82,7 @@ impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<(),
     }
 }
 
-impl_field_traits!(());
+impl_field_traits!((), FieldKind::Unit);
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,523 @@
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use core::str::Utf8Error;
+
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldCopyAccess, FieldStringAccess, FieldView, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+use crate::InfallibleResultExt;
+
+/// Field type for bytes preceded by their length, e.g. `LengthPrefixed<u16>` stores a [u16] length in the endianness
+/// of the layout, followed by that many bytes. The length prefix can be [u8], [u16], [u32] or [u64].
+///
+/// The offsets of fields after a length prefixed field depend on the data, so they are wrapped into a [RelativeField](crate::RelativeField),
+/// which reads the length prefix to find them. A length prefixed field at the end of a layout doesn't cover the bytes after its data,
+/// so data that continues after it can be read with another layout, e.g. using a [LayoutCursor](crate::LayoutCursor),
+/// which advances past the end of the length prefixed data. See [FieldLengthPrefixedAccess] for how to access these fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutCursor, LengthPrefixed};
+///
+/// binary_layout!(request_head, BigEndian, {
+///   id: u32,
+///   method: LengthPrefixed<u8>,
+/// });
+/// binary_layout!(request_tail, BigEndian, {
+///   flags: u8,
+///   body: LengthPrefixed<u16>,
+/// });
+///
+/// fn main() {
+///     let data = [0, 0, 0, 7, 3, b'g', b'e', b't', 1, 0, 2, 0xab, 0xcd];
+///     let mut cursor = LayoutCursor::new(&data);
+///     let head = cursor.next::<request_head::NestedView>().unwrap();
+///     assert_eq!(7, head.id().read());
+///     assert_eq!(Ok(&b"get"[..]), head.method().try_data());
+///     let tail = cursor.next::<request_tail::NestedView>().unwrap();
+///     assert_eq!(1, tail.flags().read());
+///     assert_eq!(Ok(&[0xab, 0xcd][..]), tail.body().try_data());
+///     assert!(cursor.is_empty());
+/// }
+/// ```
+pub struct LengthPrefixed<P> {
+    _p: PhantomData<P>,
+}
+
+/// Field type for a UTF-8 string preceded by its length in bytes, e.g. `LengthPrefixedStr<u16>` stores a [u16] length in the
+/// endianness of the layout, followed by that many bytes of UTF-8. It is stored like a [LengthPrefixed] field and has the same restrictions,
+/// but [FieldStringAccess] reads it as a `&str`, checking that it is valid UTF-8, and writes a `&str` along with its length.
+/// The raw bytes are still accessible with [FieldLengthPrefixedAccess].
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LengthPrefixedStr, LengthPrefixedStrError};
+///
+/// binary_layout!(rpc_request, BigEndian, {
+///   id: u32,
+///   method: LengthPrefixedStr<u16>,
+/// });
+///
+/// fn main() {
+///     let mut view = rpc_request::View::new([0; 16]);
+///     view.id_mut().write(1);
+///     view.method_mut().try_write_str("status").unwrap();
+///     assert_eq!(Ok("status"), view.method().try_read_str());
+///     assert_eq!(b"\0\x06status", &view.into_storage()[4..12]);
+///
+///     let invalid = rpc_request::View::new([0, 0, 0, 2, 0, 1, 0xff]);
+///     assert!(matches!(invalid.method().try_read_str(), Err(LengthPrefixedStrError::InvalidUtf8(_))));
+/// }
+/// ```
+pub struct LengthPrefixedStr<P> {
+    _p: PhantomData<P>,
+}
+
+/// This error is returned when accessing a [LengthPrefixed] or [LengthPrefixedStr] field fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LengthPrefixedError {
+    /// The storage ends before the end of the data, as given by the length prefix or by the data that is written.
+    StorageTooShort {
+        /// Number of bytes the storage needs for the length prefix and the data
+        expected: usize,
+        /// Number of bytes in the storage
+        actual: usize,
+    },
+    /// The data written to the field is longer than the length prefix can represent.
+    TooLong {
+        /// The maximal length the length prefix can represent
+        max_len: usize,
+        /// The length of the data that was written
+        len: usize,
+    },
+}
+
+impl core::fmt::Display for LengthPrefixedError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LengthPrefixedError::StorageTooShort { expected, actual } => write!(
+                fmt,
+                "The length prefixed field needs {} bytes of storage but the storage only has {} bytes",
+                expected, actual
+            ),
+            LengthPrefixedError::TooLong { max_len, len } => write!(
+                fmt,
+                "Data of {} bytes is longer than the length prefix allows ({} bytes)",
+                len, max_len
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthPrefixedError {}
+
+/// This error is returned when reading a [LengthPrefixedStr] field fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LengthPrefixedStrError {
+    /// The data of the field couldn't be accessed, e.g. because the storage ends before the end of the string.
+    Data(LengthPrefixedError),
+    /// The string isn't valid UTF-8.
+    InvalidUtf8(Utf8Error),
+}
+
+impl core::fmt::Display for LengthPrefixedStrError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LengthPrefixedStrError::Data(error) => write!(fmt, "{}", error),
+            LengthPrefixedStrError::InvalidUtf8(error) => write!(fmt, "Invalid UTF-8: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthPrefixedStrError {}
+
+/// This trait is implemented for [LengthPrefixed] and [LengthPrefixedStr] fields, i.e. fields storing bytes preceded by their length.
+/// Reading borrows the data from the storage without a copy.
+pub trait FieldLengthPrefixedAccess: Field {
+    /// Borrow the data of the field with read access using the [Field] API, i.e. the number of bytes given by the length prefix.
+    /// Fails if the storage ends before the end of the data.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, LengthPrefixed};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   payload: LengthPrefixed<u16>,
+    /// });
+    ///
+    /// fn main() {
+    ///     let storage = [2, 0, 0xab, 0xcd, 0xff];
+    ///     assert_eq!(Ok(&[0xab, 0xcd][..]), my_layout::payload::try_data(&storage));
+    /// }
+    /// ```
+    fn try_data(storage: &[u8]) -> Result<&[u8], LengthPrefixedError>;
+
+    /// Borrow the data of the field with write access using the [Field] API, i.e. the number of bytes given by the length prefix.
+    /// Fails if the storage ends before the end of the data.
+    fn try_data_mut(storage: &mut [u8]) -> Result<&mut [u8], LengthPrefixedError>;
+
+    /// Write the length prefix and the data to a given data region, assuming the defined layout, using the [Field] API.
+    /// Nothing is written if the data doesn't fit.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, LengthPrefixed};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   //... other fields ...
+    ///   payload: LengthPrefixed<u16>,
+    /// });
+    ///
+    /// fn main() {
+    ///     let mut storage = [0; 8];
+    ///     my_layout::payload::try_write_data(&mut storage, b"abc").unwrap();
+    ///     assert_eq!(&[0, 3, b'a', b'b', b'c'], &storage[..5]);
+    /// }
+    /// ```
+    fn try_write_data(storage: &mut [u8], data: &[u8]) -> Result<(), LengthPrefixedError>;
+}
+
+macro_rules! length_prefixed_field {
+    ($marker: ident<$type: ty>) => {
+        impl<E: Endianness, const OFFSET_: usize> PrimitiveField<$marker<$type>, E, OFFSET_> {
+            /// Returns the range of the data in the storage, after checking that the storage is long enough for it
+            #[inline]
+            fn data_range(storage: &[u8]) -> Result<core::ops::Range<usize>, LengthPrefixedError> {
+                let start = Self::OFFSET + core::mem::size_of::<$type>();
+                if storage.len() < start {
+                    return Err(LengthPrefixedError::StorageTooShort {
+                        expected: start,
+                        actual: storage.len(),
+                    });
+                }
+                let len =
+                    PrimitiveField::<$type, E, OFFSET_>::try_read(storage).infallible_unwrap();
+                let end = start.saturating_add(usize::try_from(len).unwrap_or(usize::MAX));
+                if storage.len() < end {
+                    return Err(LengthPrefixedError::StorageTooShort {
+                        expected: end,
+                        actual: storage.len(),
+                    });
+                }
+                Ok(start..end)
+            }
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldLengthPrefixedAccess
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            #[inline]
+            fn try_data(storage: &[u8]) -> Result<&[u8], LengthPrefixedError> {
+                Ok(&storage[Self::data_range(storage)?])
+            }
+
+            #[inline]
+            fn try_data_mut(storage: &mut [u8]) -> Result<&mut [u8], LengthPrefixedError> {
+                let range = Self::data_range(storage)?;
+                Ok(&mut storage[range])
+            }
+
+            #[inline]
+            fn try_write_data(storage: &mut [u8], data: &[u8]) -> Result<(), LengthPrefixedError> {
+                let len =
+                    <$type>::try_from(data.len()).map_err(|_| LengthPrefixedError::TooLong {
+                        max_len: usize::try_from(<$type>::MAX).unwrap_or(usize::MAX),
+                        len: data.len(),
+                    })?;
+                let start = Self::OFFSET + core::mem::size_of::<$type>();
+                let end = start + data.len();
+                if storage.len() < end {
+                    return Err(LengthPrefixedError::StorageTooShort {
+                        expected: end,
+                        actual: storage.len(),
+                    });
+                }
+                PrimitiveField::<$type, E, OFFSET_>::try_write(storage, len).infallible_unwrap();
+                storage[start..end].copy_from_slice(data);
+                Ok(())
+            }
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> Field
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = None;
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::LengthPrefixed {
+                prefix_size: core::mem::size_of::<$type>(),
+            };
+        }
+
+        impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, const OFFSET_: usize> StorageIntoFieldView<S>
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+length_prefixed_field!(LengthPrefixed<u8>);
+length_prefixed_field!(LengthPrefixed<u16>);
+length_prefixed_field!(LengthPrefixed<u32>);
+length_prefixed_field!(LengthPrefixed<u64>);
+length_prefixed_field!(LengthPrefixedStr<u8>);
+length_prefixed_field!(LengthPrefixedStr<u16>);
+length_prefixed_field!(LengthPrefixedStr<u32>);
+length_prefixed_field!(LengthPrefixedStr<u64>);
+
+impl<P, E: Endianness, const OFFSET_: usize> FieldStringAccess
+    for PrimitiveField<LengthPrefixedStr<P>, E, OFFSET_>
+where
+    Self: FieldLengthPrefixedAccess,
+{
+    /// See [FieldStringAccess::ReadError]
+    type ReadError = LengthPrefixedStrError;
+    /// See [FieldStringAccess::WriteError]
+    type WriteError = LengthPrefixedError;
+
+    #[inline]
+    fn try_read_str(storage: &[u8]) -> Result<&str, LengthPrefixedStrError> {
+        let data = Self::try_data(storage).map_err(LengthPrefixedStrError::Data)?;
+        core::str::from_utf8(data).map_err(LengthPrefixedStrError::InvalidUtf8)
+    }
+
+    #[inline]
+    fn try_write_str(storage: &mut [u8], value: &str) -> Result<(), LengthPrefixedError> {
+        Self::try_write_data(storage, value.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FieldLengthPrefixedAccess, LengthPrefixed, LengthPrefixedError, LengthPrefixedStr,
+        LengthPrefixedStrError,
+    };
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(message, BigEndian, {
+        kind: u8,
+        payload: LengthPrefixed<u16>,
+    });
+
+    binary_layout!(little, LittleEndian, {
+        payload: LengthPrefixed<u32>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<LengthPrefixed<u16>, LittleEndian, 5>;
+        assert_eq!(None, Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(None, message::SIZE);
+        assert_eq!(
+            FieldKind::LengthPrefixed { prefix_size: 2 },
+            message::Fields::payload.kind
+        );
+        assert_eq!("LengthPrefixed<u16>", message::Fields::payload.type_name);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xff; 8];
+        message::payload::try_write_data(&mut storage, &[1, 2, 3]).unwrap();
+        assert_eq!([0xff, 0, 3, 1, 2, 3, 0xff, 0xff], storage);
+        assert_eq!(Ok(&[1, 2, 3][..]), message::payload::try_data(&storage));
+        message::payload::try_data_mut(&mut storage).unwrap()[1] = 5;
+        assert_eq!(Ok(&[1, 5, 3][..]), message::payload::try_data(&storage));
+
+        let mut storage = [0; 6];
+        little::payload::try_write_data(&mut storage, &[7, 8]).unwrap();
+        assert_eq!([2, 0, 0, 0, 7, 8], storage);
+        little::payload::try_write_data(&mut storage, &[]).unwrap();
+        assert_eq!(Ok(&[][..]), little::payload::try_data(&storage));
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = message::View::new([0; 10]);
+        view.kind_mut().write(1);
+        view.payload_mut().try_write_data(b"hello").unwrap();
+        view.payload_mut().try_data_mut().unwrap()[0] = b'j';
+        assert_eq!(Ok(&b"jello"[..]), view.payload().try_data());
+        assert_eq!(
+            [1, 0, 5, b'j', b'e', b'l', b'l', b'o', 0, 0],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn storage_too_short() {
+        assert_eq!(
+            Err(LengthPrefixedError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            }),
+            message::payload::try_data(&[0, 0])
+        );
+        assert_eq!(
+            Err(LengthPrefixedError::StorageTooShort {
+                expected: 7,
+                actual: 6
+            }),
+            message::payload::try_data(&[0, 0, 4, 1, 2, 3])
+        );
+        let mut storage = [0; 4];
+        assert_eq!(
+            Err(LengthPrefixedError::StorageTooShort {
+                expected: 5,
+                actual: 4
+            }),
+            message::payload::try_write_data(&mut storage, &[1, 2])
+        );
+        assert_eq!([0; 4], storage);
+    }
+
+    #[test]
+    fn try_new_checks_the_length_prefix() {
+        assert!(message::View::try_new(&[1, 0, 2, 5, 6][..]).is_ok());
+        assert_eq!(
+            Some(crate::ViewError::StorageTooShort {
+                expected: 5,
+                actual: 4
+            }),
+            message::View::try_new(&[1, 0, 2, 5][..]).err()
+        );
+    }
+
+    #[test]
+    fn too_long() {
+        binary_layout!(short, BigEndian, {
+            payload: LengthPrefixed<u8>,
+        });
+        let mut storage = [0; 300];
+        assert_eq!(
+            Err(LengthPrefixedError::TooLong {
+                max_len: 255,
+                len: 256
+            }),
+            short::payload::try_write_data(&mut storage, &[1; 256])
+        );
+        assert_eq!([0; 300], storage);
+        short::payload::try_write_data(&mut storage, &[1; 255]).unwrap();
+        assert_eq!(255, short::payload::try_data(&storage).unwrap().len());
+    }
+
+    binary_layout!(envelope, LittleEndian, {
+        id: u16,
+        method: LengthPrefixedStr<u8>,
+    });
+
+    #[test]
+    fn str_metadata() {
+        assert_eq!(None, envelope::SIZE);
+        assert_eq!(
+            FieldKind::LengthPrefixed { prefix_size: 1 },
+            envelope::Fields::method.kind
+        );
+        assert_eq!("LengthPrefixedStr<u8>", envelope::Fields::method.type_name);
+    }
+
+    #[test]
+    fn str_fieldapi() {
+        let mut storage = [0; 8];
+        envelope::method::try_write_str(&mut storage, "ping").unwrap();
+        assert_eq!([0, 0, 4, b'p', b'i', b'n', b'g', 0], storage);
+        assert_eq!(Ok("ping"), envelope::method::try_read_str(&storage));
+        assert_eq!(Ok(&b"ping"[..]), envelope::method::try_data(&storage));
+        envelope::method::try_write_str(&mut storage, "").unwrap();
+        assert_eq!(Ok(""), envelope::method::try_read_str(&storage));
+    }
+
+    #[test]
+    fn str_viewapi() {
+        let mut view = envelope::View::new([0; 12]);
+        view.method_mut().try_write_str("grün").unwrap();
+        assert_eq!(Ok("grün"), view.method().try_read_str());
+        assert_eq!(Ok("grün".as_bytes()), view.method().try_data());
+        assert_eq!(5, view.into_storage()[2]);
+    }
+
+    #[test]
+    fn str_invalid_utf8() {
+        let storage = [0, 0, 2, 0xc3, 0x28];
+        match envelope::method::try_read_str(&storage) {
+            Err(LengthPrefixedStrError::InvalidUtf8(error)) => assert_eq!(0, error.valid_up_to()),
+            result => panic!("Expected invalid UTF-8, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn str_storage_too_short() {
+        assert_eq!(
+            Err(LengthPrefixedStrError::Data(
+                LengthPrefixedError::StorageTooShort {
+                    expected: 6,
+                    actual: 5
+                }
+            )),
+            envelope::method::try_read_str(&[0, 0, 3, b'a', b'b'])
+        );
+        let mut storage = [0; 5];
+        assert_eq!(
+            Err(LengthPrefixedError::StorageTooShort {
+                expected: 6,
+                actual: 5
+            }),
+            envelope::method::try_write_str(&mut storage, "abc")
+        );
+        assert_eq!([0; 5], storage);
+        let long = core::str::from_utf8(&[b'a'; 256]).unwrap();
+        let mut storage = [0; 300];
+        assert_eq!(
+            Err(LengthPrefixedError::TooLong {
+                max_len: 255,
+                len: 256
+            }),
+            envelope::method::try_write_str(&mut storage, long)
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use core::marker::PhantomData;
+
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldCopyAccess, FieldView, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+use crate::InfallibleResultExt;
+
+/// Field type for a constant "magic number" like the signature at the beginning of many file formats, e.g. `magic: Magic<u32, 0x6D795F66>`.
+/// It is stored as an unsigned integer of type `T` in the endianness of the layout and can be [u8], [u16], [u32], [u64] or [u128].
+///
+/// Instead of reading and writing arbitrary values, [FieldMagicAccess] checks whether the field stores `VALUE` and writes `VALUE` into it.
+/// The `View::validate()` function generated by [binary_layout!](crate::binary_layout!) checks all magic fields of a layout and its nested layouts at once.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Magic, ViewError};
+///
+/// binary_layout!(file_header, BigEndian, {
+///   magic: Magic<u32, 0x6D795F66>,
+///   version: u16,
+/// });
+///
+/// fn main() {
+///     let mut view = file_header::View::new([0; 6]);
+///     assert!(!view.magic().is_valid());
+///     assert!(matches!(view.validate(), Err(ViewError::InvalidMagic { field: "magic", .. })));
+///
+///     view.magic_mut().write_magic();
+///     assert!(view.magic().is_valid());
+///     assert_eq!(Ok(()), view.validate());
+///     assert_eq!(b"my_f", &view.into_storage()[..4]);
+/// }
+/// ```
+pub struct Magic<T, const VALUE: u128> {
+    _p: PhantomData<T>,
+}
+
+/// This trait is implemented for [Magic] fields, which store a constant value.
+pub trait FieldMagicAccess: Field {
+    /// The value the field has to store
+    const VALUE: u128;
+
+    /// Check whether the field stores its constant value, using the [Field] API.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, Magic};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   magic: Magic<u16, 0xcafe>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     assert!(my_layout::magic::is_valid(&[0xfe, 0xca]));
+    ///     assert!(!my_layout::magic::is_valid(&[0xca, 0xfe]));
+    /// }
+    /// ```
+    fn is_valid(storage: &[u8]) -> bool;
+
+    /// Write the constant value into the field, using the [Field] API.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, Magic};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   magic: Magic<u16, 0xcafe>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     let mut storage = [0; 2];
+    ///     my_layout::magic::write_magic(&mut storage);
+    ///     assert_eq!([0xca, 0xfe], storage);
+    /// }
+    /// ```
+    fn write_magic(storage: &mut [u8]);
+}
+
+macro_rules! magic_field {
+    ($type: ty) => {
+        impl<const VALUE: u128> Magic<$type, VALUE> {
+            const TYPED_VALUE: $type = {
+                assert!(
+                    VALUE <= <$type>::MAX as u128,
+                    concat!(
+                        "The value of a Magic<",
+                        stringify!($type),
+                        ", VALUE> field doesn't fit into ",
+                        stringify!($type)
+                    )
+                );
+                VALUE as $type
+            };
+        }
+
+        impl<E: Endianness, const VALUE: u128, const OFFSET_: usize> FieldMagicAccess
+            for PrimitiveField<Magic<$type, VALUE>, E, OFFSET_>
+        {
+            const VALUE: u128 = VALUE;
+
+            #[inline]
+            fn is_valid(storage: &[u8]) -> bool {
+                let value =
+                    <PrimitiveField<$type, E, OFFSET_> as FieldCopyAccess>::try_read(storage)
+                        .infallible_unwrap();
+                value == Magic::<$type, VALUE>::TYPED_VALUE
+            }
+
+            #[inline]
+            fn write_magic(storage: &mut [u8]) {
+                <PrimitiveField<$type, E, OFFSET_> as FieldCopyAccess>::try_write(
+                    storage,
+                    Magic::<$type, VALUE>::TYPED_VALUE,
+                )
+                .infallible_unwrap();
+            }
+        }
+
+        impl<E: Endianness, const VALUE: u128, const OFFSET_: usize> Field
+            for PrimitiveField<Magic<$type, VALUE>, E, OFFSET_>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = {
+                // Check the value when the layout is defined, not only when the field is accessed
+                let _ = Magic::<$type, VALUE>::TYPED_VALUE;
+                Some(core::mem::size_of::<$type>())
+            };
+        }
+
+        impl<E: Endianness, const VALUE: u128, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<Magic<$type, VALUE>, E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::Magic { value: VALUE };
+        }
+
+        impl<'a, E: Endianness, const VALUE: u128, const OFFSET_: usize>
+            StorageToFieldView<&'a [u8]> for PrimitiveField<Magic<$type, VALUE>, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, const VALUE: u128, const OFFSET_: usize>
+            StorageToFieldView<&'a mut [u8]> for PrimitiveField<Magic<$type, VALUE>, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, const VALUE: u128, const OFFSET_: usize>
+            StorageIntoFieldView<S> for PrimitiveField<Magic<$type, VALUE>, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+magic_field!(u8);
+magic_field!(u16);
+magic_field!(u32);
+magic_field!(u64);
+magic_field!(u128);
+
+#[cfg(test)]
+mod tests {
+    use super::Magic;
+    use crate::prelude::*;
+    use crate::{FieldKind, FieldValue, PrimitiveField, ViewError};
+
+    binary_layout!(header, LittleEndian, {
+        magic: Magic<u32, 0x4d42_4c54>,
+        len: u16,
+    });
+
+    binary_layout!(container, BigEndian, {
+        signature: Magic<u8, 0x7f>,
+        inner: header::NestedView,
+        trailer: Magic<u16, 0xbeef>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<Magic<u64, 1>, BigEndian, 5>;
+        assert_eq!(Some(8), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(6), header::SIZE);
+        assert_eq!(
+            FieldKind::Magic { value: 0x4d42_4c54 },
+            header::Fields::magic.kind
+        );
+        assert_eq!(0xbeef, <container::trailer as FieldMagicAccess>::VALUE);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 6];
+        assert!(!header::magic::is_valid(&storage));
+        header::magic::write_magic(&mut storage);
+        assert!(header::magic::is_valid(&storage));
+        assert_eq!([0x54, 0x4c, 0x42, 0x4d, 0, 0], storage);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = container::View::new([0; 9]);
+        view.signature_mut().write_magic();
+        view.trailer_mut().write_magic();
+        assert!(view.signature().is_valid());
+        assert!(view.trailer().is_valid());
+        assert_eq!([0x7f, 0, 0, 0, 0, 0, 0, 0xbe, 0xef], view.into_storage());
+    }
+
+    #[test]
+    fn validate() {
+        let mut view = container::View::new([0; 9]);
+        assert_eq!(
+            Err(ViewError::InvalidMagic {
+                field: "signature",
+                expected: 0x7f,
+                actual: 0,
+            }),
+            view.validate()
+        );
+        view.signature_mut().write_magic();
+        view.trailer_mut().write_magic();
+        // Magic fields of nested layouts are checked as well
+        assert_eq!(
+            Err(ViewError::InvalidMagic {
+                field: "magic",
+                expected: 0x4d42_4c54,
+                actual: 0,
+            }),
+            view.validate()
+        );
+        view.inner_mut().magic_mut().write_magic();
+        assert_eq!(Ok(()), view.validate());
+
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 6,
+                actual: 4,
+            }),
+            header::View::new([0x54, 0x4c, 0x42, 0x4d]).validate()
+        );
+    }
+
+    #[test]
+    fn layout_info() {
+        let layout = &header::LAYOUT;
+        assert_eq!(
+            FieldValue::UnsignedInteger(0x4d42_4c54),
+            layout.read_field(&layout.fields[0], &[0x54, 0x4c, 0x42, 0x4d, 0, 0])
+        );
+    }
+}
@@ -2,15 +2,56 @@ use core::marker::PhantomData;
 
 use crate::endianness::Endianness;
 
+mod array_access;
+mod bit_array_access;
 mod copy_access;
+mod length_prefixed_access;
+mod magic_access;
 mod nested_access;
+mod optional_access;
+mod owned_access;
+mod padding_access;
 mod slice_access;
+mod string_access;
+mod varint_access;
 mod view;
+#[cfg(feature = "volatile")]
+mod volatile_access;
 
-pub use copy_access::{FieldCopyAccess, FieldReadExt, FieldWriteExt, NonZeroIsZeroError};
+pub use array_access::FieldArrayAccess;
+pub use bit_array_access::{BitArray, FieldBitArrayAccess};
+pub(crate) use copy_access::decode_bcd;
+#[cfg(feature = "serde")]
+pub(crate) use copy_access::encode_bcd;
+pub use copy_access::{
+    i24, i40, i48, i56, u24, u40, u48, u56, Bcd, FieldCopyAccess, FieldReadExt, FieldWriteExt,
+    IntegerOutOfRangeError, InvalidBcdError, NonZeroIsZeroError,
+};
+#[cfg(feature = "std")]
+pub use copy_access::{
+    DurationMicros, DurationMillis, DurationNanos, DurationSeconds, TimeOutOfRangeError,
+    UnixMillis, UnixSeconds,
+};
+pub use length_prefixed_access::{
+    FieldLengthPrefixedAccess, LengthPrefixed, LengthPrefixedError, LengthPrefixedStr,
+    LengthPrefixedStrError,
+};
+pub use magic_access::{FieldMagicAccess, Magic};
 pub use nested_access::{BorrowingNestedView, NestedViewInfo, OwningNestedView};
+pub use optional_access::Optional;
+pub use owned_access::{FieldOwnedAccess, NestedValueStruct};
+pub use padding_access::{FieldPaddingAccess, Padding};
 pub use slice_access::FieldSliceAccess;
+pub use string_access::{
+    CStrReadError, CStrWriteError, FieldStringAccess, FixedCStr, FixedString, StringTooLongError,
+};
+pub(crate) use varint_access::{decode_varint, varint_len, zigzag_decode};
+#[cfg(feature = "serde")]
+pub(crate) use varint_access::{encode_varint, zigzag_encode, MAX_VARINT_LEN};
+pub use varint_access::{Leb128, VarintError, Vlq, ZigZag};
 pub use view::FieldView;
+#[cfg(feature = "volatile")]
+pub use volatile_access::FieldVolatileAccess;
 
 /// A [PrimitiveField] is a [Field](crate::Field) that directly represents a primitive type like [u8], [i16], ...
 /// See [Field](crate::Field) for more info on this API.
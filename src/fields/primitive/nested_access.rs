@@ -1,12 +1,15 @@
 use super::super::{StorageIntoFieldView, StorageToFieldView};
 use super::{Endianness, PrimitiveField};
+use crate::layout_info::{FieldKind, FieldKindInfo, LayoutInfo};
 use crate::utils::data::Data;
-use crate::Field;
+use crate::{Field, Layout, LayoutSlice};
 
 // Nesting generally works by having the binary_layout! macro implement [OwningNestedView], [BorrowingNestedView]
 // and [NestedViewInfo] for a marker type "NestedLayout" it creates in the layout's generated code.
 // Then, the code in this module here creates implementations of [Field], [StorageToFieldView]
 // and [StorageIntoFieldView] for it so that it can be used as a field in other layouts.
+// Open ended slices of a nested layout, e.g. `[my_layout::NestedView]`, are accessed as a [LayoutSlice]
+// over the rest of the storage.
 
 /// Internal type. Don't use this in user code.
 /// S is expected to be a non-reference type that can own things, e.g. `Data<S>`
@@ -35,6 +38,9 @@ pub trait BorrowingNestedView<S> {
 pub trait NestedViewInfo {
     /// Size of the nested field
     const SIZE: Option<usize>;
+
+    /// Description of the nested layout
+    const LAYOUT: &'static LayoutInfo;
 }
 
 // TODO FieldNestedAccess may be useful for the field API, but commented out for now since the field API doesn't support nesting yet
@@ -111,6 +117,12 @@ impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> Field
     const SIZE: Option<usize> = N::SIZE;
 }
 
+impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> FieldKindInfo
+    for PrimitiveField<N, E, OFFSET_>
+{
+    const KIND: FieldKind = FieldKind::Nested(N::LAYOUT);
+}
+
 impl<'a, N: BorrowingNestedView<&'a [u8]>, E: Endianness, const OFFSET_: usize>
     StorageToFieldView<&'a [u8]> for PrimitiveField<N, E, OFFSET_>
 where
@@ -160,3 +172,59 @@ where
         }
     }
 }
+
+impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> Field
+    for PrimitiveField<[N], E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = None;
+}
+
+impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> FieldKindInfo
+    for PrimitiveField<[N], E, OFFSET_>
+{
+    const KIND: FieldKind = FieldKind::Array {
+        element: &FieldKind::Nested(N::LAYOUT),
+        element_size: match N::SIZE {
+            Some(size) => size,
+            None => 0,
+        },
+    };
+}
+
+impl<'a, N: NestedViewInfo + Layout, E: Endianness, const OFFSET_: usize>
+    StorageToFieldView<&'a [u8]> for PrimitiveField<[N], E, OFFSET_>
+{
+    type View = LayoutSlice<N, &'a [u8]>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        LayoutSlice::new(&storage[Self::OFFSET..])
+    }
+}
+
+impl<'a, N: NestedViewInfo + Layout, E: Endianness, const OFFSET_: usize>
+    StorageToFieldView<&'a mut [u8]> for PrimitiveField<[N], E, OFFSET_>
+{
+    type View = LayoutSlice<N, &'a mut [u8]>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        LayoutSlice::new(&mut storage[Self::OFFSET..])
+    }
+}
+
+impl<S: AsRef<[u8]>, N: NestedViewInfo + Layout, E: Endianness, const OFFSET_: usize>
+    StorageIntoFieldView<S> for PrimitiveField<[N], E, OFFSET_>
+{
+    type View = LayoutSlice<N, Data<S>>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        LayoutSlice::new(Data::from(storage).into_subregion(Self::OFFSET..))
+    }
+}
@@ -0,0 +1,238 @@
+use core::marker::PhantomData;
+
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// Field type for a field of type `T` that storage may end before, e.g. `extension: Optional<u32>`, like fields that
+/// later versions of a protocol appended and that older peers omit. Optional fields have to be at the end of a layout
+/// and need a fixed size. `View::try_new` accepts storage that ends before the optional fields, and the accessors
+/// of the `View` return `None` for fields that aren't fully stored.
+///
+/// With the [Field](crate::Field) API, fields of types that can be read and written with [FieldCopyAccess] read
+/// an `Option<T>`, which is `None` if the field isn't stored. Writing `Some(value)` writes the value, which panics
+/// like for other fields if the storage is too short. Writing `None` leaves the storage unchanged.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Optional};
+///
+/// binary_layout!(hello, BigEndian, {
+///   version: u8,
+///   flags: u16,
+///   max_frame_size: Optional<u32>,
+///   padding: Optional<[u8; 2]>,
+/// });
+///
+/// fn main() {
+///     let view = hello::View::try_new([1, 0, 3]).unwrap();
+///     assert_eq!(3, view.flags().read());
+///     assert!(view.max_frame_size().is_none());
+///
+///     let mut view = hello::View::try_new([1, 0, 3, 0, 0, 0, 0]).unwrap();
+///     view.max_frame_size_mut().unwrap().write(16384);
+///     assert_eq!(Some(16384), view.max_frame_size().map(|field| field.read()));
+///     assert!(view.padding().is_none());
+///     assert_eq!([1, 0, 3, 0, 0, 0x40, 0], view.into_storage());
+/// }
+/// ```
+pub struct Optional<T> {
+    _p: PhantomData<T>,
+}
+
+/// Checks whether the storage is long enough for a field
+#[inline(always)]
+fn is_stored<F: Field>(storage: &[u8]) -> bool {
+    match F::SIZE {
+        Some(size) => storage.len() >= F::OFFSET + size,
+        None => storage.len() >= F::OFFSET,
+    }
+}
+
+impl<T, E: Endianness, const OFFSET_: usize> Field for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    PrimitiveField<T, E, OFFSET_>: Field,
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = match <PrimitiveField<T, E, OFFSET_> as Field>::SIZE {
+        Some(size) => Some(size),
+        None => panic!("Optional fields need a fixed size"),
+    };
+}
+
+impl<T, E: Endianness, const OFFSET_: usize> FieldKindInfo
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    PrimitiveField<T, E, OFFSET_>: FieldKindInfo,
+{
+    const KIND: FieldKind = <PrimitiveField<T, E, OFFSET_> as FieldKindInfo>::KIND;
+    const OPTIONAL: bool = true;
+}
+
+impl<T, E: Endianness, const OFFSET_: usize> FieldCopyAccess
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    PrimitiveField<T, E, OFFSET_>: FieldCopyAccess,
+{
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = <PrimitiveField<T, E, OFFSET_> as FieldCopyAccess>::ReadError;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = <PrimitiveField<T, E, OFFSET_> as FieldCopyAccess>::WriteError;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = Option<<PrimitiveField<T, E, OFFSET_> as FieldCopyAccess>::HighLevelType>;
+
+    /// Read the field from a given data region, returning `None` if the data region ends before the end of the field.
+    #[inline]
+    fn try_read(storage: &[u8]) -> Result<Self::HighLevelType, Self::ReadError> {
+        if is_stored::<Self>(storage) {
+            PrimitiveField::<T, E, OFFSET_>::try_read(storage).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Write the value to the field if it is `Some`, otherwise leave the data region unchanged.
+    #[inline]
+    fn try_write(storage: &mut [u8], value: Self::HighLevelType) -> Result<(), Self::WriteError> {
+        match value {
+            Some(value) => PrimitiveField::<T, E, OFFSET_>::try_write(storage, value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, T, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    PrimitiveField<T, E, OFFSET_>: StorageToFieldView<&'a [u8]>,
+    Self: Field,
+{
+    type View = Option<<PrimitiveField<T, E, OFFSET_> as StorageToFieldView<&'a [u8]>>::View>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        is_stored::<Self>(storage).then(|| PrimitiveField::<T, E, OFFSET_>::view(storage))
+    }
+}
+
+impl<'a, T, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    PrimitiveField<T, E, OFFSET_>: StorageToFieldView<&'a mut [u8]>,
+    Self: Field,
+{
+    type View = Option<<PrimitiveField<T, E, OFFSET_> as StorageToFieldView<&'a mut [u8]>>::View>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        if is_stored::<Self>(storage) {
+            Some(PrimitiveField::<T, E, OFFSET_>::view(storage))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: AsRef<[u8]>, T, E: Endianness, const OFFSET_: usize> StorageIntoFieldView<S>
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    PrimitiveField<T, E, OFFSET_>: StorageIntoFieldView<S>,
+    Self: Field,
+{
+    type View = Option<<PrimitiveField<T, E, OFFSET_> as StorageIntoFieldView<S>>::View>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        if is_stored::<Self>(storage.as_ref()) {
+            Some(PrimitiveField::<T, E, OFFSET_>::into_view(storage))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Optional;
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField, ViewError};
+
+    binary_layout!(inner, LittleEndian, {
+        value: u16,
+    });
+
+    binary_layout!(record, LittleEndian, round_trip_tests, {
+        id: u16,
+        extension: Optional<u32>,
+        nested: Optional<inner::NestedView>,
+        bytes: Optional<[u8; 2]>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<Optional<u32>, BigEndian, 5>;
+        assert_eq!(Some(4), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(10), record::SIZE);
+        assert_eq!(2, record::LAYOUT.min_size());
+        assert_eq!(FieldKind::UnsignedInteger, record::Fields::extension.kind);
+        let optional = record::LAYOUT.fields.iter().map(|field| field.optional);
+        assert!(optional.eq([false, true, true, true]));
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 4];
+        record::id::write(&mut storage, 7);
+        assert_eq!(None, record::extension::read(&storage));
+        // Writing None leaves the storage unchanged
+        record::extension::write(&mut storage, None);
+        assert_eq!([7, 0, 0, 0], storage);
+
+        let mut storage = [0; 6];
+        record::extension::write(&mut storage, Some(0x01020304));
+        assert_eq!(Some(0x01020304), record::extension::read(&storage));
+        assert_eq!([0, 0, 4, 3, 2, 1], storage);
+    }
+
+    #[test]
+    fn viewapi() {
+        let view = record::View::try_new([1, 0, 2, 0, 0, 0, 3, 0, 4]).unwrap();
+        assert_eq!(1, view.id().read());
+        assert_eq!(2, view.extension().unwrap().read());
+        assert_eq!(3, view.nested().unwrap().value().read());
+        assert!(view.bytes().is_none());
+
+        let mut view = record::View::new([0; 10]);
+        view.nested_mut().unwrap().value_mut().write(5);
+        view.bytes_mut().unwrap().copy_from_slice(&[6, 7]);
+        assert_eq!([6, 7], *view.into_bytes().unwrap());
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(record::View::try_new([0; 2]).is_ok());
+        assert!(record::View::try_new([0; 5]).is_ok());
+        assert!(record::View::try_new([0; 10]).is_ok());
+        assert!(matches!(
+            record::View::try_new([0; 1]),
+            Err(ViewError::StorageTooShort {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn size_in() {
+        assert_eq!(Ok(2), record::LAYOUT.size_in(&[0; 5]));
+        assert_eq!(Ok(6), record::LAYOUT.size_in(&[0; 6]));
+        assert_eq!(Ok(8), record::LAYOUT.size_in(&[0; 9]));
+        assert_eq!(Ok(10), record::LAYOUT.size_in(&[0; 12]));
+    }
+}
@@ -0,0 +1,396 @@
+use super::super::Field;
+use super::{
+    i24, i40, i48, i56, u24, u40, u48, u56, FieldMagicAccess, FieldPaddingAccess, FieldReadExt,
+    FieldSliceAccess, FieldWriteExt, Magic, NestedViewInfo, Optional, Padding, PrimitiveField,
+};
+use crate::builder::{BuilderField, FieldSet, FieldUnset};
+use crate::endianness::Endianness;
+use crate::fields::bit_field::BitField;
+use crate::fields::wrapped::{LayoutAs, WrappedField};
+
+/// This trait is implemented for fields whose value can be copied out of the storage and back into it without failing,
+/// so that they can be part of the value struct that the `value_struct` option of [binary_layout!](crate::binary_layout!)
+/// generates for a layout.
+///
+/// This includes the fields that can be read and written with [read](FieldReadExt::read) and [write](FieldWriteExt::write),
+/// byte arrays, nested layouts that have a value struct themselves and [Optional] fields of these types.
+/// [Padding] and [Magic] fields have the value `()`, writing them clears the padding or writes the magic value.
+///
+/// # Example:
+/// ```
+/// use binary_layout::{prelude::*, FieldOwnedAccess};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   //... other fields ...
+///   id: [u8; 4],
+///   //... other fields ...
+/// });
+///
+/// fn func(storage_data: &mut [u8]) {
+///   my_layout::id::write_owned(storage_data, &[1, 2, 3, 4]);
+///   let id: [u8; 4] = my_layout::id::read_owned(storage_data);
+/// }
+/// ```
+pub trait FieldOwnedAccess: Field {
+    /// The owned value of the field
+    type Owned;
+
+    /// Read a copy of the value of the field from a given data region, using the [Field] API.
+    fn read_owned(storage: &[u8]) -> Self::Owned;
+
+    /// Write the value of the field into a given data region, using the [Field] API.
+    fn write_owned(storage: &mut [u8], value: &Self::Owned);
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the `NestedView` of layouts with a value struct so they can be nested into other value structs.
+pub trait NestedValueStruct {
+    /// The value struct of the nested layout
+    type Value;
+
+    /// Read the value struct from storage that starts at the nested layout
+    fn read_value(storage: &[u8]) -> Self::Value;
+
+    /// Write the value struct into storage that starts at the nested layout
+    fn write_value(storage: &mut [u8], value: &Self::Value);
+}
+
+macro_rules! owned_copy_field {
+    ([$($generics: tt)*] $field: ty) => {
+        impl<$($generics)*> FieldOwnedAccess for $field
+        where
+            Self: FieldReadExt + FieldWriteExt<HighLevelType = <Self as FieldReadExt>::HighLevelType>,
+            <Self as FieldReadExt>::HighLevelType: Clone,
+        {
+            type Owned = <Self as FieldReadExt>::HighLevelType;
+
+            #[inline]
+            fn read_owned(storage: &[u8]) -> Self::Owned {
+                Self::read(storage)
+            }
+
+            #[inline]
+            fn write_owned(storage: &mut [u8], value: &Self::Owned) {
+                Self::write(storage, value.clone())
+            }
+        }
+
+        impl<$($generics)*> BuilderField for $field
+        where
+            Self: FieldOwnedAccess,
+        {
+            type Initial = FieldUnset;
+        }
+    };
+    ($($type: ty),* $(,)?) => {
+        $(
+            owned_copy_field!([E: Endianness, const OFFSET_: usize] PrimitiveField<$type, E, OFFSET_>);
+        )*
+    };
+}
+
+owned_copy_field!(
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    f32,
+    f64,
+    (),
+    i24,
+    i40,
+    i48,
+    i56,
+    u24,
+    u40,
+    u48,
+    u56,
+);
+owned_copy_field!(
+    Option<core::num::NonZeroI8>,
+    Option<core::num::NonZeroI16>,
+    Option<core::num::NonZeroI32>,
+    Option<core::num::NonZeroI64>,
+    Option<core::num::NonZeroI128>,
+    Option<core::num::NonZeroU8>,
+    Option<core::num::NonZeroU16>,
+    Option<core::num::NonZeroU32>,
+    Option<core::num::NonZeroU64>,
+    Option<core::num::NonZeroU128>,
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[i8; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[i16; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[i32; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[i64; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[i128; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[u16; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[u32; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[u64; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[u128; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[f32; N], E, OFFSET_>
+);
+owned_copy_field!(
+    [E: Endianness, const N: usize, const OFFSET_: usize] PrimitiveField<[f64; N], E, OFFSET_>
+);
+owned_copy_field!([U, T: LayoutAs<U>, F: Field] WrappedField<U, T, F>);
+owned_copy_field!(
+    [T, E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize]
+    BitField<T, E, OFFSET_, BIT_OFFSET, BITS>
+);
+#[cfg(feature = "half")]
+owned_copy_field!(half::f16, half::bf16);
+#[cfg(feature = "std")]
+owned_copy_field!(std::net::Ipv4Addr, std::net::Ipv6Addr);
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldOwnedAccess
+    for PrimitiveField<[u8; N], E, OFFSET_>
+{
+    type Owned = [u8; N];
+
+    #[inline]
+    fn read_owned(storage: &[u8]) -> [u8; N] {
+        *Self::data(storage)
+    }
+
+    #[inline]
+    fn write_owned(storage: &mut [u8], value: &[u8; N]) {
+        Self::data_mut(storage).copy_from_slice(value);
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> BuilderField
+    for PrimitiveField<[u8; N], E, OFFSET_>
+{
+    type Initial = FieldUnset;
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldOwnedAccess
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    type Owned = ();
+
+    #[inline]
+    fn read_owned(_storage: &[u8]) {}
+
+    /// Set the padding bytes to zero.
+    #[inline]
+    fn write_owned(storage: &mut [u8], _value: &()) {
+        Self::clear(storage);
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> BuilderField
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    type Initial = FieldSet;
+
+    #[inline]
+    fn finish(storage: &mut [u8]) {
+        Self::clear(storage);
+    }
+}
+
+impl<T, E: Endianness, const VALUE: u128, const OFFSET_: usize> FieldOwnedAccess
+    for PrimitiveField<Magic<T, VALUE>, E, OFFSET_>
+where
+    Self: FieldMagicAccess,
+{
+    type Owned = ();
+
+    #[inline]
+    fn read_owned(_storage: &[u8]) {}
+
+    /// Write the magic value into the field.
+    #[inline]
+    fn write_owned(storage: &mut [u8], _value: &()) {
+        Self::write_magic(storage);
+    }
+}
+
+impl<T, E: Endianness, const VALUE: u128, const OFFSET_: usize> BuilderField
+    for PrimitiveField<Magic<T, VALUE>, E, OFFSET_>
+where
+    Self: FieldMagicAccess,
+{
+    type Initial = FieldSet;
+
+    #[inline]
+    fn finish(storage: &mut [u8]) {
+        Self::write_magic(storage);
+    }
+}
+
+impl<T, E: Endianness, const OFFSET_: usize> FieldOwnedAccess
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    Self: Field,
+    PrimitiveField<T, E, OFFSET_>: FieldOwnedAccess,
+{
+    type Owned = Option<<PrimitiveField<T, E, OFFSET_> as FieldOwnedAccess>::Owned>;
+
+    /// Read the value of the field, returning `None` if the data region ends before the end of the field.
+    #[inline]
+    fn read_owned(storage: &[u8]) -> Self::Owned {
+        let size = Self::SIZE.expect("Optional fields have a fixed size");
+        (storage.len() >= OFFSET_ + size)
+            .then(|| PrimitiveField::<T, E, OFFSET_>::read_owned(storage))
+    }
+
+    /// Write the value to the field if it is `Some`, otherwise leave the data region unchanged.
+    #[inline]
+    fn write_owned(storage: &mut [u8], value: &Self::Owned) {
+        if let Some(value) = value {
+            PrimitiveField::<T, E, OFFSET_>::write_owned(storage, value);
+        }
+    }
+}
+
+// Optional fields can be left out, the storage may end before them
+impl<T, E: Endianness, const OFFSET_: usize> BuilderField
+    for PrimitiveField<Optional<T>, E, OFFSET_>
+where
+    Self: FieldOwnedAccess,
+{
+    type Initial = FieldSet;
+}
+
+impl<N: NestedViewInfo + NestedValueStruct, E: Endianness, const OFFSET_: usize> FieldOwnedAccess
+    for PrimitiveField<N, E, OFFSET_>
+{
+    type Owned = N::Value;
+
+    #[inline]
+    fn read_owned(storage: &[u8]) -> N::Value {
+        match N::SIZE {
+            Some(size) => N::read_value(&storage[OFFSET_..(OFFSET_ + size)]),
+            None => N::read_value(&storage[OFFSET_..]),
+        }
+    }
+
+    #[inline]
+    fn write_owned(storage: &mut [u8], value: &N::Value) {
+        match N::SIZE {
+            Some(size) => N::write_value(&mut storage[OFFSET_..(OFFSET_ + size)], value),
+            None => N::write_value(&mut storage[OFFSET_..], value),
+        }
+    }
+}
+
+impl<N: NestedViewInfo + NestedValueStruct, E: Endianness, const OFFSET_: usize> BuilderField
+    for PrimitiveField<N, E, OFFSET_>
+{
+    type Initial = FieldUnset;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FieldOwnedAccess;
+    use crate::prelude::*;
+    use crate::{Magic, Optional, Padding};
+
+    binary_layout!(point, BigEndian, value_struct = Point, {
+        x: i16,
+        y: i16,
+    });
+
+    binary_layout!(record, LittleEndian, value_struct = Record, {
+        magic: Magic<u16, 0xcafe>,
+        id: u32,
+        reserved: Padding<2>,
+        origin: point::NestedView,
+        samples: [u16; 2],
+        name: [u8; 3],
+        extension: Optional<u8>,
+    });
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xff; 20];
+        record::magic::write_owned(&mut storage, &());
+        record::reserved::write_owned(&mut storage, &());
+        record::origin::write_owned(&mut storage, &point::Point { x: 1, y: 2 });
+        record::name::write_owned(&mut storage, &[1, 2, 3]);
+        assert_eq!(
+            [0xfe, 0xca, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 1, 0, 2],
+            storage[..12]
+        );
+        assert_eq!(
+            point::Point { x: 1, y: 2 },
+            record::origin::read_owned(&storage)
+        );
+        assert_eq!([1, 2, 3], record::name::read_owned(&storage));
+        assert_eq!(Some(0xff), record::extension::read_owned(&storage));
+        assert_eq!(None, record::extension::read_owned(&storage[..19]));
+    }
+
+    #[test]
+    fn read_all() {
+        let view = record::View::new([
+            0xfe, 0xca, 1, 0, 0, 0, 9, 9, 0, 2, 0xff, 0xfd, 3, 0, 4, 0, b'a', b'b', b'c', 5,
+        ]);
+        assert_eq!(
+            record::Record {
+                magic: (),
+                id: 1,
+                reserved: (),
+                origin: point::Point { x: 2, y: -3 },
+                samples: [3, 4],
+                name: *b"abc",
+                extension: Some(5),
+            },
+            view.read_all()
+        );
+
+        let view = record::View::try_new(&[0; 19][..]).unwrap();
+        assert_eq!(None, view.read_all().extension);
+    }
+
+    #[test]
+    fn write_all() {
+        let value = record::Record {
+            magic: (),
+            id: 0x01020304,
+            reserved: (),
+            origin: point::Point { x: 1, y: 2 },
+            samples: [5, 6],
+            name: *b"xyz",
+            extension: None,
+        };
+        let mut view = record::View::new([0xff; 20]);
+        view.write_all(&value);
+        assert_eq!(
+            [0xfe, 0xca, 4, 3, 2, 1, 0, 0, 0, 1, 0, 2, 5, 0, 6, 0, b'x', b'y', b'z', 0xff],
+            view.into_storage()
+        );
+
+        // Without the optional field
+        let mut storage = [0; 19];
+        value.write_to(&mut storage);
+        assert_eq!(value, record::View::new(&storage).read_all());
+    }
+}
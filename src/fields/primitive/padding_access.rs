@@ -0,0 +1,189 @@
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldView, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// Field type for `N` reserved or padding bytes, e.g. `reserved: Padding<7>`. The field takes up space in the layout
+/// so that the fields after it have the right offsets, but its value can't be read or written.
+/// Instead, [FieldPaddingAccess] can check that the bytes are zero and set them to zero.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Padding};
+///
+/// binary_layout!(header, LittleEndian, {
+///   version: u8,
+///   reserved: Padding<3>,
+///   len: u32,
+/// });
+///
+/// fn main() {
+///     let mut view = header::View::new([0xff; 8]);
+///     assert_eq!(4, header::len::OFFSET);
+///     assert!(!view.reserved().is_zero());
+///     view.reserved_mut().clear();
+///     assert!(view.reserved().is_zero());
+///     assert_eq!([0xff, 0, 0, 0, 0xff, 0xff, 0xff, 0xff], view.into_storage());
+/// }
+/// ```
+pub struct Padding<const N: usize>;
+
+/// This trait is implemented for [Padding] fields, whose value isn't meant to be read or written.
+pub trait FieldPaddingAccess: Field {
+    /// Check whether all bytes of the field are zero, using the [Field] API.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, Padding};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   reserved: Padding<2>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     assert!(my_layout::reserved::is_zero(&[0, 0]));
+    ///     assert!(!my_layout::reserved::is_zero(&[0, 1]));
+    /// }
+    /// ```
+    fn is_zero(storage: &[u8]) -> bool;
+
+    /// Set all bytes of the field to zero, using the [Field] API.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, Padding};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   reserved: Padding<2>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     let mut storage = [1, 2];
+    ///     my_layout::reserved::clear(&mut storage);
+    ///     assert_eq!([0, 0], storage);
+    /// }
+    /// ```
+    fn clear(storage: &mut [u8]);
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldPaddingAccess
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    #[inline]
+    fn is_zero(storage: &[u8]) -> bool {
+        storage[Self::OFFSET..(Self::OFFSET + N)]
+            .iter()
+            .all(|byte| *byte == 0)
+    }
+
+    #[inline]
+    fn clear(storage: &mut [u8]) {
+        storage[Self::OFFSET..(Self::OFFSET + N)].fill(0);
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> Field
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = Some(N);
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldKindInfo
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    const KIND: FieldKind = FieldKind::Padding;
+}
+
+impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, E: Endianness, const N: usize, const OFFSET_: usize> StorageIntoFieldView<S>
+    for PrimitiveField<Padding<N>, E, OFFSET_>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Padding;
+    use crate::prelude::*;
+    use crate::{FieldKind, FieldValue, PrimitiveField};
+
+    binary_layout!(record, BigEndian, {
+        kind: u8,
+        reserved: Padding<3>,
+        id: u32,
+        trailer: Padding<0>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<Padding<7>, LittleEndian, 5>;
+        assert_eq!(Some(7), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(8), record::SIZE);
+        assert_eq!(4, record::id::OFFSET);
+        assert_eq!(FieldKind::Padding, record::Fields::reserved.kind);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xaa; 8];
+        assert!(!record::reserved::is_zero(&storage));
+        assert!(record::trailer::is_zero(&storage));
+        record::reserved::clear(&mut storage);
+        assert!(record::reserved::is_zero(&storage));
+        assert_eq!([0xaa, 0, 0, 0, 0xaa, 0xaa, 0xaa, 0xaa], storage);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = record::View::new([0, 0, 1, 0, 0, 0, 0, 5]);
+        assert!(!view.reserved().is_zero());
+        view.reserved_mut().clear();
+        assert!(view.reserved().is_zero());
+        assert_eq!(5, view.id().read());
+    }
+
+    #[test]
+    fn layout_info() {
+        let layout = &record::LAYOUT;
+        assert_eq!(
+            FieldValue::Bytes(&[0, 1, 0]),
+            layout.read_field(&layout.fields[1], &[0, 0, 1, 0, 0, 0, 0, 5])
+        );
+    }
+}
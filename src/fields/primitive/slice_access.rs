@@ -3,6 +3,7 @@ use core::convert::TryFrom;
 use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
 use super::PrimitiveField;
 use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
 use crate::utils::data::Data;
 
 /// This trait is implemented for fields with "slice access",
@@ -106,6 +107,9 @@ impl<E: Endianness, const OFFSET_: usize> Field for PrimitiveField<[u8], E, OFFS
     /// See [Field::SIZE]
     const SIZE: Option<usize> = None;
 }
+impl<E: Endianness, const OFFSET_: usize> FieldKindInfo for PrimitiveField<[u8], E, OFFSET_> {
+    const KIND: FieldKind = FieldKind::Bytes;
+}
 impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
     for PrimitiveField<[u8], E, OFFSET_>
 {
@@ -202,6 +206,11 @@ impl<E: Endianness, const N: usize, const OFFSET_: usize> Field
     /// See [Field::SIZE]
     const SIZE: Option<usize> = Some(N);
 }
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldKindInfo
+    for PrimitiveField<[u8; N], E, OFFSET_>
+{
+    const KIND: FieldKind = FieldKind::Bytes;
+}
 impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
     for PrimitiveField<[u8; N], E, OFFSET_>
 {
@@ -0,0 +1,492 @@
+use core::marker::PhantomData;
+use core::str::Utf8Error;
+
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldView, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo};
+
+/// Field type for a string stored in a fixed number of bytes, as found in file system and archive formats.
+/// Strings shorter than `N` bytes are padded with the `PAD` byte, which defaults to NUL. Use e.g. `FixedString<8, b' '>`
+/// for space padded strings. When reading, all trailing `PAD` bytes are removed.
+///
+/// Strings are stored as UTF-8, which includes ASCII. They don't depend on the endianness of the layout.
+/// See [FieldStringAccess] for how to access them.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FixedString};
+///
+/// binary_layout!(archive_entry, LittleEndian, {
+///   name: FixedString<16>,
+///   owner: FixedString<8, b' '>,
+///   size: u32,
+/// });
+///
+/// fn main() {
+///     let mut view = archive_entry::View::new([0; 28]);
+///     view.name_mut().try_write_str("readme.txt").unwrap();
+///     view.owner_mut().try_write_str("root").unwrap();
+///     assert_eq!(Ok("readme.txt"), view.name().try_read_str());
+///     assert_eq!(Ok("root"), view.owner().try_read_str());
+///     assert_eq!(b"root    ", &view.into_storage()[16..24]);
+/// }
+/// ```
+pub struct FixedString<const N: usize, const PAD: u8 = 0> {
+    _p: PhantomData<[u8; N]>,
+}
+
+/// This error is returned when trying to write a string that takes up more bytes than a [FixedString] field has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringTooLongError {
+    /// The number of bytes of the field
+    pub max_len: usize,
+    /// The number of bytes of the string that was written
+    pub len: usize,
+}
+
+impl core::fmt::Display for StringTooLongError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            fmt,
+            "String of {} bytes doesn't fit into a field of {} bytes",
+            self.len, self.max_len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StringTooLongError {}
+
+/// Field type for a NUL terminated string stored in a region of `N` bytes, like a `char[N]` member of a C struct.
+/// The string ends at the first NUL byte, which must be within the region. Bytes after it are ignored when reading
+/// and set to NUL when writing, so a string can take up at most `N - 1` bytes.
+///
+/// Strings are stored as UTF-8, which includes ASCII. They don't depend on the endianness of the layout.
+/// See [FieldStringAccess] for how to access them.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, CStrReadError, FixedCStr};
+///
+/// binary_layout!(device_info, LittleEndian, {
+///   id: u16,
+///   label: FixedCStr<8>,
+/// });
+///
+/// fn main() {
+///     let mut view = device_info::View::new([0xff; 10]);
+///     assert_eq!(Err(CStrReadError::MissingNulTerminator), view.label().try_read_str());
+///     view.label_mut().try_write_str("sda1").unwrap();
+///     assert_eq!(Ok("sda1"), view.label().try_read_str());
+///     assert_eq!(b"sda1\0\0\0\0", &view.into_storage()[2..]);
+/// }
+/// ```
+pub struct FixedCStr<const N: usize> {
+    _p: PhantomData<[u8; N]>,
+}
+
+/// This error is returned when reading a [FixedCStr] field fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CStrReadError {
+    /// There is no NUL byte in the field, so the string doesn't end within the field.
+    MissingNulTerminator,
+    /// The bytes before the NUL terminator aren't valid UTF-8.
+    InvalidUtf8(Utf8Error),
+}
+
+impl core::fmt::Display for CStrReadError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CStrReadError::MissingNulTerminator => {
+                write!(fmt, "The string field doesn't contain a NUL terminator")
+            }
+            CStrReadError::InvalidUtf8(error) => write!(fmt, "Invalid UTF-8: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CStrReadError {}
+
+/// This error is returned when writing a [FixedCStr] field fails. Nothing is written in that case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CStrWriteError {
+    /// The string and its NUL terminator don't fit into the field.
+    TooLong {
+        /// The maximal number of bytes of a string in the field, i.e. its size minus one byte for the NUL terminator
+        max_len: usize,
+        /// The number of bytes of the string that was written
+        len: usize,
+    },
+    /// The string contains a NUL byte, so it would be cut off there when reading it.
+    InteriorNul {
+        /// Position of the first NUL byte in the string
+        position: usize,
+    },
+}
+
+impl core::fmt::Display for CStrWriteError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CStrWriteError::TooLong { max_len, len } => write!(
+                fmt,
+                "String of {} bytes doesn't fit into a field for strings of up to {} bytes plus NUL terminator",
+                len, max_len
+            ),
+            CStrWriteError::InteriorNul { position } => {
+                write!(fmt, "String contains a NUL byte at position {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CStrWriteError {}
+
+/// This trait is implemented for string fields, i.e. [FixedString], [FixedCStr] and [LengthPrefixedStr](crate::LengthPrefixedStr) fields.
+/// Reading borrows the string from the storage without a copy.
+pub trait FieldStringAccess: Field {
+    /// Error type possibly returned from [FieldStringAccess::try_read_str]
+    type ReadError;
+    /// Error type possibly returned from [FieldStringAccess::try_write_str]
+    type WriteError;
+
+    /// Read the string from a given data region, assuming the defined layout, using the [Field] API.
+    /// Trailing pad bytes of [FixedString] fields are removed.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, FixedString};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   name: FixedString<8>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     assert_eq!(Ok("abc"), my_layout::name::try_read_str(b"abc\0\0\0\0\0"));
+    /// }
+    /// ```
+    fn try_read_str(storage: &[u8]) -> Result<&str, Self::ReadError>;
+
+    /// Write the string to a given data region, assuming the defined layout, using the [Field] API.
+    /// The remaining bytes of the field are filled with pad bytes, or with NUL bytes for [FixedCStr] fields.
+    /// [LengthPrefixedStr](crate::LengthPrefixedStr) fields store the length of the string in their length prefix instead.
+    /// Nothing is written if the string doesn't fit.
+    ///
+    /// Note that a string ending in the pad byte won't read back the same because trailing pad bytes are removed when reading.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, FixedString, StringTooLongError};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   name: FixedString<4, b' '>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn main() {
+    ///     let mut storage = [0; 4];
+    ///     my_layout::name::try_write_str(&mut storage, "ab").unwrap();
+    ///     assert_eq!(b"ab  ", &storage);
+    ///     assert_eq!(
+    ///         Err(StringTooLongError { max_len: 4, len: 5 }),
+    ///         my_layout::name::try_write_str(&mut storage, "abcde"),
+    ///     );
+    /// }
+    /// ```
+    fn try_write_str(storage: &mut [u8], value: &str) -> Result<(), Self::WriteError>;
+}
+
+impl<E: Endianness, const N: usize, const PAD: u8, const OFFSET_: usize> FieldStringAccess
+    for PrimitiveField<FixedString<N, PAD>, E, OFFSET_>
+{
+    /// See [FieldStringAccess::ReadError]
+    type ReadError = Utf8Error;
+    /// See [FieldStringAccess::WriteError]
+    type WriteError = StringTooLongError;
+
+    #[inline]
+    fn try_read_str(storage: &[u8]) -> Result<&str, Utf8Error> {
+        let data = &storage[Self::OFFSET..(Self::OFFSET + N)];
+        let len = data
+            .iter()
+            .rposition(|byte| *byte != PAD)
+            .map_or(0, |last| last + 1);
+        core::str::from_utf8(&data[..len])
+    }
+
+    #[inline]
+    fn try_write_str(storage: &mut [u8], value: &str) -> Result<(), StringTooLongError> {
+        let value = value.as_bytes();
+        if value.len() > N {
+            return Err(StringTooLongError {
+                max_len: N,
+                len: value.len(),
+            });
+        }
+        let (data, padding) = storage[Self::OFFSET..(Self::OFFSET + N)].split_at_mut(value.len());
+        data.copy_from_slice(value);
+        padding.fill(PAD);
+        Ok(())
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldStringAccess
+    for PrimitiveField<FixedCStr<N>, E, OFFSET_>
+{
+    /// See [FieldStringAccess::ReadError]
+    type ReadError = CStrReadError;
+    /// See [FieldStringAccess::WriteError]
+    type WriteError = CStrWriteError;
+
+    #[inline]
+    fn try_read_str(storage: &[u8]) -> Result<&str, CStrReadError> {
+        let data = &storage[Self::OFFSET..(Self::OFFSET + N)];
+        let len = data
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(CStrReadError::MissingNulTerminator)?;
+        core::str::from_utf8(&data[..len]).map_err(CStrReadError::InvalidUtf8)
+    }
+
+    #[inline]
+    fn try_write_str(storage: &mut [u8], value: &str) -> Result<(), CStrWriteError> {
+        let value = value.as_bytes();
+        if value.len() >= N {
+            return Err(CStrWriteError::TooLong {
+                max_len: N.saturating_sub(1),
+                len: value.len(),
+            });
+        }
+        if let Some(position) = value.iter().position(|byte| *byte == 0) {
+            return Err(CStrWriteError::InteriorNul { position });
+        }
+        let (data, padding) = storage[Self::OFFSET..(Self::OFFSET + N)].split_at_mut(value.len());
+        data.copy_from_slice(value);
+        padding.fill(0);
+        Ok(())
+    }
+}
+
+macro_rules! string_field {
+    ($type: ty $(, const $param: ident: $param_type: ty)*) => {
+        impl<E: Endianness, const N: usize, $(const $param: $param_type,)* const OFFSET_: usize> Field
+            for PrimitiveField<$type, E, OFFSET_>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = Some(N);
+        }
+
+        impl<E: Endianness, const N: usize, $(const $param: $param_type,)* const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<$type, E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::Bytes;
+        }
+
+        impl<'a, E: Endianness, const N: usize, $(const $param: $param_type,)* const OFFSET_: usize>
+            StorageToFieldView<&'a [u8]> for PrimitiveField<$type, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, const N: usize, $(const $param: $param_type,)* const OFFSET_: usize>
+            StorageToFieldView<&'a mut [u8]> for PrimitiveField<$type, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, const N: usize, $(const $param: $param_type,)* const OFFSET_: usize>
+            StorageIntoFieldView<S> for PrimitiveField<$type, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+string_field!(FixedString<N, PAD>, const PAD: u8);
+string_field!(FixedCStr<N>);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CStrReadError, CStrWriteError, FieldStringAccess, FixedCStr, FixedString,
+        StringTooLongError,
+    };
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField};
+
+    binary_layout!(entry, BigEndian, {
+        kind: u8,
+        name: FixedString<6>,
+        owner: FixedString<4, b' '>,
+        size: u16,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<FixedString<32>, LittleEndian, 5>;
+        assert_eq!(Some(32), Field1::SIZE);
+        assert_eq!(5, Field1::OFFSET);
+        assert_eq!(Some(13), entry::SIZE);
+        assert_eq!(7, entry::owner::OFFSET);
+        assert_eq!(FieldKind::Bytes, entry::Fields::name.kind);
+        assert_eq!("FixedString<4, b' '>", entry::Fields::owner.type_name);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xff; 13];
+        entry::name::try_write_str(&mut storage, "ab").unwrap();
+        entry::owner::try_write_str(&mut storage, "wxyz").unwrap();
+        assert_eq!(Ok("ab"), entry::name::try_read_str(&storage));
+        assert_eq!(Ok("wxyz"), entry::owner::try_read_str(&storage));
+        assert_eq!(b"\xffab\0\0\0\0wxyz\xff\xff", &storage);
+
+        entry::owner::try_write_str(&mut storage, "").unwrap();
+        assert_eq!(Ok(""), entry::owner::try_read_str(&storage));
+        assert_eq!(b"    ", &storage[7..11]);
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = entry::View::new([0; 13]);
+        view.name_mut().try_write_str("grün").unwrap();
+        view.owner_mut().try_write_str("me").unwrap();
+        view.size_mut().write(10);
+        assert_eq!(Ok("grün"), view.name().try_read_str());
+        assert_eq!(Ok("me"), view.owner().try_read_str());
+        assert_eq!(10, view.size().read());
+    }
+
+    #[test]
+    fn only_trailing_padding_is_removed() {
+        let storage = *b"\0a\0b\0\0\0 a  \0\0";
+        assert_eq!(Ok("a\0b"), entry::name::try_read_str(&storage));
+        assert_eq!(Ok(" a"), entry::owner::try_read_str(&storage));
+    }
+
+    #[test]
+    fn too_long() {
+        let mut storage = [0; 13];
+        entry::name::try_write_str(&mut storage, "a").unwrap();
+        assert_eq!(
+            Err(StringTooLongError { max_len: 6, len: 7 }),
+            entry::name::try_write_str(&mut storage, "abcdefg")
+        );
+        // Multi-byte characters count with their UTF-8 length
+        assert_eq!(
+            Err(StringTooLongError { max_len: 4, len: 6 }),
+            entry::owner::try_write_str(&mut storage, "äöü")
+        );
+        assert_eq!(Ok("a"), entry::name::try_read_str(&storage));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut storage = [0; 13];
+        storage[1..4].copy_from_slice(&[b'a', 0xc3, 0x28]);
+        let error = entry::name::try_read_str(&storage).unwrap_err();
+        assert_eq!(1, error.valid_up_to());
+    }
+
+    binary_layout!(device, LittleEndian, {
+        id: u8,
+        label: FixedCStr<5>,
+    });
+
+    #[test]
+    fn cstr_metadata() {
+        assert_eq!(Some(6), device::SIZE);
+        assert_eq!(Some(5), device::label::SIZE);
+        assert_eq!(FieldKind::Bytes, device::Fields::label.kind);
+        assert_eq!("FixedCStr<5>", device::Fields::label.type_name);
+    }
+
+    #[test]
+    fn cstr_fieldapi() {
+        let mut storage = [0xff; 6];
+        device::label::try_write_str(&mut storage, "ab").unwrap();
+        assert_eq!(Ok("ab"), device::label::try_read_str(&storage));
+        assert_eq!([0xff, b'a', b'b', 0, 0, 0], storage);
+        device::label::try_write_str(&mut storage, "abcd").unwrap();
+        assert_eq!(Ok("abcd"), device::label::try_read_str(&storage));
+        device::label::try_write_str(&mut storage, "").unwrap();
+        assert_eq!(Ok(""), device::label::try_read_str(&storage));
+    }
+
+    #[test]
+    fn cstr_viewapi() {
+        let mut view = device::View::new([0; 6]);
+        view.label_mut().try_write_str("ün").unwrap();
+        assert_eq!(Ok("ün"), view.label().try_read_str());
+    }
+
+    #[test]
+    fn cstr_bytes_after_terminator_are_ignored() {
+        let storage = [0, b'a', 0, b'b', 0xff, 0xff];
+        assert_eq!(Ok("a"), device::label::try_read_str(&storage));
+    }
+
+    #[test]
+    fn cstr_missing_terminator() {
+        let storage = [0, b'a', b'b', b'c', b'd', b'e'];
+        assert_eq!(
+            Err(CStrReadError::MissingNulTerminator),
+            device::label::try_read_str(&storage)
+        );
+    }
+
+    #[test]
+    fn cstr_invalid_utf8() {
+        let storage = [0, b'a', 0xc3, 0x28, 0, 0];
+        match device::label::try_read_str(&storage) {
+            Err(CStrReadError::InvalidUtf8(error)) => assert_eq!(1, error.valid_up_to()),
+            result => panic!("Expected invalid UTF-8, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn cstr_too_long() {
+        let mut storage = [0; 6];
+        assert_eq!(
+            Err(CStrWriteError::TooLong { max_len: 4, len: 5 }),
+            device::label::try_write_str(&mut storage, "abcde")
+        );
+        assert_eq!([0; 6], storage);
+    }
+
+    #[test]
+    fn cstr_interior_nul() {
+        let mut storage = [0; 6];
+        assert_eq!(
+            Err(CStrWriteError::InteriorNul { position: 1 }),
+            device::label::try_write_str(&mut storage, "a\0b")
+        );
+        assert_eq!([0; 6], storage);
+    }
+}
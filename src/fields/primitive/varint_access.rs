@@ -0,0 +1,643 @@
+use core::marker::PhantomData;
+
+use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::{FieldCopyAccess, FieldView, PrimitiveField};
+use crate::endianness::Endianness;
+use crate::layout_info::{FieldKind, FieldKindInfo, VarintEncoding};
+
+/// Maximal number of bytes of a varint, enough for any [u128] value
+pub(crate) const MAX_VARINT_LEN: usize = 19;
+
+/// Field type for an unsigned integer stored as [LEB128](https://en.wikipedia.org/wiki/LEB128) varint, as used by protobuf and DWARF.
+/// Each byte stores 7 bits of the value, starting with the least significant bits, and has its most significant bit set if more bytes follow.
+/// `Leb128<T>` reads and writes values of type `T`, which can be [u16], [u32] or [u64].
+/// Varints don't depend on the endianness of the layout.
+///
+/// The number of bytes of a varint depends on its value, so the fields after it are wrapped into a [RelativeField](crate::RelativeField),
+/// which decodes the varint to find them. The bytes after the varint aren't part of the field, so data that continues after a varint
+/// at the end of a layout can be read with another layout, e.g. using a [LayoutCursor](crate::LayoutCursor), which advances past its last byte.
+///
+/// Varint fields implement [FieldCopyAccess], so they are read and written with `try_read` and `try_write`,
+/// which fail with [VarintError].
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutCursor, Leb128};
+///
+/// binary_layout!(tag, LittleEndian, {
+///   value: Leb128<u32>,
+/// });
+///
+/// fn main() {
+///     let data = [0x96, 0x01, 0x05];
+///     let mut cursor = LayoutCursor::new(&data);
+///     assert_eq!(Ok(150), cursor.next::<tag::NestedView>().unwrap().value().try_read());
+///     assert_eq!(Ok(5), cursor.next::<tag::NestedView>().unwrap().value().try_read());
+///     assert!(cursor.is_empty());
+///
+///     let mut view = tag::View::new([0; 4]);
+///     view.value_mut().try_write(300).unwrap();
+///     assert_eq!([0xac, 0x02, 0, 0], view.into_storage());
+/// }
+/// ```
+pub struct Leb128<T> {
+    _p: PhantomData<T>,
+}
+
+/// Field type for a signed integer stored as [zigzag](https://protobuf.dev/programming-guides/encoding/#signed-ints) encoded
+/// [LEB128](https://en.wikipedia.org/wiki/LEB128) varint, like the `sint32` and `sint64` types of protobuf.
+/// Zigzag encoding maps values with a small magnitude to small unsigned values, i.e. 0, -1, 1, -2, ... to 0, 1, 2, 3, ...,
+/// which are then stored like a [Leb128] field, so negative values take up few bytes as well.
+/// `ZigZag<T>` reads and writes values of type `T`, which can be [i16], [i32] or [i64].
+///
+/// It has the same restrictions and API as [Leb128] fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, ZigZag};
+///
+/// binary_layout!(delta, LittleEndian, {
+///   value: ZigZag<i32>,
+/// });
+///
+/// fn main() {
+///     let mut view = delta::View::new([0; 2]);
+///     view.value_mut().try_write(-2).unwrap();
+///     assert_eq!(Ok(-2), view.value().try_read());
+///     view.value_mut().try_write(-65).unwrap();
+///     assert_eq!([0x81, 0x01], view.into_storage());
+/// }
+/// ```
+pub struct ZigZag<T> {
+    _p: PhantomData<T>,
+}
+
+/// Field type for an unsigned integer stored as variable length quantity (VLQ) like in MIDI files and several font formats.
+/// Each byte stores 7 bits of the value, starting with the most significant bits, and has its most significant bit set if more bytes follow.
+/// `Vlq<T>` reads and writes values of type `T`, which can be [u16], [u32] or [u64].
+/// Note that MIDI limits VLQs to 4 bytes, i.e. values up to `0x0FFF_FFFF`, while a `Vlq<u32>` accepts up to 5 bytes for values up to [u32::MAX].
+///
+/// It has the same restrictions and API as [Leb128] fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutCursor, Vlq};
+///
+/// binary_layout!(midi_event, BigEndian, {
+///   delta_time: Vlq<u32>,
+///   status: u8,
+///   note: u8,
+///   velocity: u8,
+/// });
+///
+/// fn main() {
+///     // Note on after 200 ticks, note off right after it
+///     let data = [0x81, 0x48, 0x90, 0x3c, 0x40, 0x00, 0x80, 0x3c, 0x00];
+///     let mut cursor = LayoutCursor::new(&data);
+///     let note_on = cursor.next::<midi_event::NestedView>().unwrap();
+///     assert_eq!(Ok(200), note_on.delta_time().try_read());
+///     assert_eq!(0x90, note_on.status().read());
+///     assert_eq!(0x40, note_on.velocity().read());
+///     let note_off = cursor.next::<midi_event::NestedView>().unwrap();
+///     assert_eq!(Ok(0), note_off.delta_time().try_read());
+///     assert_eq!(0x80, note_off.status().read());
+///     assert!(cursor.is_empty());
+///
+///     let mut view = midi_event::View::new([0; 7]);
+///     view.delta_time_mut().try_write(0x0FFF_FFFF).unwrap();
+///     view.status_mut().write(0x90);
+///     view.note_mut().write(0x3c);
+///     assert_eq!([0xff, 0xff, 0xff, 0x7f, 0x90, 0x3c, 0], view.into_storage());
+/// }
+/// ```
+pub struct Vlq<T> {
+    _p: PhantomData<T>,
+}
+
+/// This error is returned when reading or writing a varint field like [Leb128], [ZigZag] or [Vlq] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VarintError {
+    /// The storage ends before the last byte of the varint, i.e. before a byte without continuation bit.
+    Truncated,
+    /// The varint has more bytes than needed for the integer type or encodes a value that doesn't fit into it.
+    Overflow,
+    /// The storage is too short for the varint that is written.
+    StorageTooShort {
+        /// Number of bytes the storage needs for the varint
+        expected: usize,
+        /// Number of bytes in the storage
+        actual: usize,
+    },
+}
+
+impl core::fmt::Display for VarintError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VarintError::Truncated => write!(fmt, "The storage ends within the varint"),
+            VarintError::Overflow => {
+                write!(
+                    fmt,
+                    "The varint encodes a value that is too large for its type"
+                )
+            }
+            VarintError::StorageTooShort { expected, actual } => write!(
+                fmt,
+                "The varint needs {} bytes of storage but the storage only has {} bytes",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VarintError {}
+
+/// Returns the number of bytes of the varint at the beginning of the data, which ends with the first byte without continuation bit.
+/// Returns `None` if there is no such byte.
+pub(crate) fn varint_len(data: &[u8]) -> Option<usize> {
+    data.iter()
+        .position(|byte| byte & 0x80 == 0)
+        .map(|last| last + 1)
+}
+
+/// Decodes the varint at the beginning of the data, returning its value and number of bytes.
+/// Fails if it doesn't fit into the given number of bits.
+pub(crate) fn decode_varint(
+    encoding: VarintEncoding,
+    data: &[u8],
+    bits: u32,
+) -> Result<(u128, usize), VarintError> {
+    let len = varint_len(data).ok_or(VarintError::Truncated)?;
+    if len > (bits as usize + 6) / 7 {
+        return Err(VarintError::Overflow);
+    }
+    let groups = data[..len].iter().map(|byte| u128::from(byte & 0x7f));
+    let value = match encoding {
+        VarintEncoding::Leb128 | VarintEncoding::ZigZag => {
+            groups.rev().fold(0, |value, group| (value << 7) | group)
+        }
+        VarintEncoding::Vlq => groups.fold(0, |value, group| (value << 7) | group),
+    };
+    if bits < 128 && value >> bits != 0 {
+        return Err(VarintError::Overflow);
+    }
+    Ok((value, len))
+}
+
+/// Encodes the value as varint into the beginning of the storage, returning the number of bytes written.
+/// Nothing is written if the storage is too short.
+pub(crate) fn encode_varint(
+    encoding: VarintEncoding,
+    storage: &mut [u8],
+    value: u128,
+) -> Result<usize, VarintError> {
+    let mut encoded = [0; MAX_VARINT_LEN];
+    let mut len = 0;
+    let mut remaining = value;
+    loop {
+        encoded[len] = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+        len += 1;
+        if remaining == 0 {
+            break;
+        }
+    }
+    match encoding {
+        VarintEncoding::Leb128 | VarintEncoding::ZigZag => {}
+        // VLQ stores the most significant group first
+        VarintEncoding::Vlq => encoded[..len].reverse(),
+    }
+    encoded[..len - 1].iter_mut().for_each(|byte| *byte |= 0x80);
+    if storage.len() < len {
+        return Err(VarintError::StorageTooShort {
+            expected: len,
+            actual: storage.len(),
+        });
+    }
+    storage[..len].copy_from_slice(&encoded[..len]);
+    Ok(len)
+}
+
+/// Maps a signed value to an unsigned one so that values with a small magnitude map to small values, i.e. 0, -1, 1, -2, ... to 0, 1, 2, 3, ...
+pub(crate) fn zigzag_encode(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+/// Reverses [zigzag_encode]
+pub(crate) fn zigzag_decode(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+
+macro_rules! varint_field {
+    ($marker: ident<$type: ty>, $encoding: expr, $to_raw: expr, $from_raw: expr) => {
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = VarintError;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = VarintError;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = $type;
+
+            #[inline]
+            fn try_read(storage: &[u8]) -> Result<$type, VarintError> {
+                let storage = storage.get(Self::OFFSET..).ok_or(VarintError::Truncated)?;
+                let (value, _) = decode_varint($encoding, storage, <$type>::BITS)?;
+                Ok($from_raw(value))
+            }
+
+            #[inline]
+            fn try_write(storage: &mut [u8], value: $type) -> Result<(), VarintError> {
+                let actual = storage.len();
+                let storage =
+                    storage
+                        .get_mut(Self::OFFSET..)
+                        .ok_or(VarintError::StorageTooShort {
+                            expected: Self::OFFSET + 1,
+                            actual,
+                        })?;
+                encode_varint($encoding, storage, $to_raw(value))
+                    .map(drop)
+                    .map_err(|error| match error {
+                        VarintError::StorageTooShort { expected, .. } => {
+                            VarintError::StorageTooShort {
+                                expected: Self::OFFSET + expected,
+                                actual,
+                            }
+                        }
+                        error => error,
+                    })
+            }
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> Field
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            /// See [Field::Endian]
+            type Endian = E;
+            /// See [Field::OFFSET]
+            const OFFSET: usize = OFFSET_;
+            /// See [Field::SIZE]
+            const SIZE: Option<usize> = None;
+        }
+
+        impl<E: Endianness, const OFFSET_: usize> FieldKindInfo
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            const KIND: FieldKind = FieldKind::Varint($encoding);
+        }
+
+        impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            type View = FieldView<&'a [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            type View = FieldView<&'a mut [u8], Self>;
+
+            #[inline(always)]
+            fn view(storage: &'a mut [u8]) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+
+        impl<S: AsRef<[u8]>, E: Endianness, const OFFSET_: usize> StorageIntoFieldView<S>
+            for PrimitiveField<$marker<$type>, E, OFFSET_>
+        {
+            type View = FieldView<S, Self>;
+
+            #[inline(always)]
+            fn into_view(storage: S) -> Self::View {
+                Self::View::new(storage)
+            }
+        }
+    };
+}
+
+varint_field!(Leb128<u16>, VarintEncoding::Leb128, u128::from, |raw| raw
+    as u16);
+varint_field!(Leb128<u32>, VarintEncoding::Leb128, u128::from, |raw| raw
+    as u32);
+varint_field!(Leb128<u64>, VarintEncoding::Leb128, u128::from, |raw| raw
+    as u64);
+varint_field!(Vlq<u16>, VarintEncoding::Vlq, u128::from, |raw| raw as u16);
+varint_field!(Vlq<u32>, VarintEncoding::Vlq, u128::from, |raw| raw as u32);
+varint_field!(Vlq<u64>, VarintEncoding::Vlq, u128::from, |raw| raw as u64);
+varint_field!(
+    ZigZag<i16>,
+    VarintEncoding::ZigZag,
+    |value| zigzag_encode(i128::from(value)),
+    |raw| zigzag_decode(raw) as i16
+);
+varint_field!(
+    ZigZag<i32>,
+    VarintEncoding::ZigZag,
+    |value| zigzag_encode(i128::from(value)),
+    |raw| zigzag_decode(raw) as i32
+);
+varint_field!(
+    ZigZag<i64>,
+    VarintEncoding::ZigZag,
+    |value| zigzag_encode(i128::from(value)),
+    |raw| zigzag_decode(raw) as i64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{Leb128, VarintError, Vlq, ZigZag};
+    use crate::prelude::*;
+    use crate::{FieldKind, PrimitiveField, VarintEncoding};
+
+    binary_layout!(record, LittleEndian, {
+        kind: u8,
+        value: Leb128<u64>,
+    });
+
+    binary_layout!(short, BigEndian, {
+        value: Leb128<u16>,
+    });
+
+    #[test]
+    fn metadata() {
+        type Field1 = PrimitiveField<Leb128<u32>, LittleEndian, 3>;
+        assert_eq!(None, Field1::SIZE);
+        assert_eq!(3, Field1::OFFSET);
+        assert_eq!(None, record::SIZE);
+        assert_eq!(
+            FieldKind::Varint(VarintEncoding::Leb128),
+            record::Fields::value.kind
+        );
+        assert_eq!("Leb128<u64>", record::Fields::value.type_name);
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0xff; 12];
+        record::value::try_write(&mut storage, 624_485).unwrap();
+        assert_eq!([0xe5, 0x8e, 0x26, 0xff], storage[1..5]);
+        assert_eq!(Ok(624_485), record::value::try_read(&storage));
+
+        record::value::try_write(&mut storage, 0).unwrap();
+        assert_eq!(0, storage[1]);
+        assert_eq!(Ok(0), record::value::try_read(&storage));
+
+        record::value::try_write(&mut storage, u64::MAX).unwrap();
+        assert_eq!([0xff; 9], storage[1..10]);
+        assert_eq!(1, storage[10]);
+        assert_eq!(Ok(u64::MAX), record::value::try_read(&storage));
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = record::View::new([0; 4]);
+        view.value_mut().try_write(127).unwrap();
+        assert_eq!(Ok(127), view.value().try_read());
+        view.value_mut().try_write(128).unwrap();
+        assert_eq!(Ok(128), view.value().try_read());
+        assert_eq!([0, 0x80, 0x01, 0], view.into_storage());
+    }
+
+    #[test]
+    fn non_canonical_encoding_is_accepted() {
+        assert_eq!(Ok(1), short::value::try_read(&[0x81, 0x80, 0x00]));
+    }
+
+    #[test]
+    fn read_errors() {
+        assert_eq!(Err(VarintError::Truncated), record::value::try_read(&[0]));
+        assert_eq!(
+            Err(VarintError::Truncated),
+            record::value::try_read(&[0, 0x80, 0x80])
+        );
+        // 0x10000 doesn't fit into a u16
+        assert_eq!(
+            Err(VarintError::Overflow),
+            short::value::try_read(&[0x80, 0x80, 0x04])
+        );
+        // A u16 has at most 3 bytes
+        assert_eq!(
+            Err(VarintError::Overflow),
+            short::value::try_read(&[0x80, 0x80, 0x80, 0x00])
+        );
+    }
+
+    #[test]
+    fn write_errors() {
+        let mut storage = [0; 3];
+        assert_eq!(
+            Err(VarintError::StorageTooShort {
+                expected: 4,
+                actual: 3
+            }),
+            record::value::try_write(&mut storage, 0x4000)
+        );
+        assert_eq!([0; 3], storage);
+        assert_eq!(
+            Err(VarintError::StorageTooShort {
+                expected: 2,
+                actual: 1
+            }),
+            record::value::try_write(&mut [0], 1)
+        );
+    }
+
+    binary_layout!(tagged, BigEndian, {
+        kind: u8,
+        value: Leb128<u32>,
+        crc: u16,
+    });
+
+    #[test]
+    fn fields_after_varint() {
+        assert_eq!(2, tagged::crc::OFFSET);
+        assert_eq!(4, tagged::LAYOUT.min_size());
+        let mut view = tagged::View::new([0; 8]);
+        view.kind_mut().write(1);
+        view.value_mut().try_write(300).unwrap();
+        view.crc_mut().write(0xabcd);
+        assert_eq!(Ok(300), view.value().try_read());
+        assert_eq!(0xabcd, view.crc().read());
+        assert_eq!([1, 0xac, 0x02, 0xab, 0xcd, 0, 0, 0], view.into_storage());
+
+        let view = tagged::View::try_new(&[1, 0x05, 0x12, 0x34][..]).unwrap();
+        assert_eq!(Ok(5), view.value().try_read());
+        assert_eq!(0x1234, view.crc().read());
+        assert!(tagged::View::try_new(&[1, 0xac, 0x02, 0x12][..]).is_err());
+    }
+
+    binary_layout!(signed, LittleEndian, {
+        kind: u8,
+        value: ZigZag<i32>,
+    });
+
+    binary_layout!(signed_short, LittleEndian, {
+        value: ZigZag<i16>,
+    });
+
+    #[test]
+    fn zigzag_metadata() {
+        assert_eq!(None, signed::SIZE);
+        assert_eq!(
+            FieldKind::Varint(VarintEncoding::ZigZag),
+            signed::Fields::value.kind
+        );
+        assert_eq!("ZigZag<i32>", signed::Fields::value.type_name);
+    }
+
+    #[test]
+    fn zigzag_fieldapi() {
+        let mut storage = [0; 6];
+        for (value, encoded) in [
+            (0, &[0][..]),
+            (-1, &[1][..]),
+            (1, &[2][..]),
+            (-64, &[0x7f][..]),
+            (64, &[0x80, 0x01][..]),
+            (i32::MAX, &[0xfe, 0xff, 0xff, 0xff, 0x0f][..]),
+            (i32::MIN, &[0xff, 0xff, 0xff, 0xff, 0x0f][..]),
+        ] {
+            signed::value::try_write(&mut storage, value).unwrap();
+            assert_eq!(encoded, &storage[1..1 + encoded.len()]);
+            assert_eq!(Ok(value), signed::value::try_read(&storage));
+        }
+    }
+
+    #[test]
+    fn zigzag_viewapi() {
+        let mut view = signed_short::View::new([0; 3]);
+        view.value_mut().try_write(i16::MIN).unwrap();
+        assert_eq!(Ok(i16::MIN), view.value().try_read());
+        assert_eq!([0xff, 0xff, 0x03], view.into_storage());
+    }
+
+    binary_layout!(deltas, LittleEndian, {
+        x: ZigZag<i32>,
+        y: ZigZag<i16>,
+        flags: u8,
+    });
+
+    #[test]
+    fn zigzag_fields_after_varints() {
+        assert_eq!(1, deltas::y::OFFSET);
+        assert_eq!(2, deltas::flags::OFFSET);
+        let mut view = deltas::View::new([0; 6]);
+        view.x_mut().try_write(-65).unwrap();
+        view.y_mut().try_write(-300).unwrap();
+        view.flags_mut().write(0x80);
+        assert_eq!(Ok(-65), view.x().try_read());
+        assert_eq!(Ok(-300), view.y().try_read());
+        assert_eq!(0x80, view.flags().read());
+        assert_eq!([0x81, 0x01, 0xd7, 0x04, 0x80, 0], view.into_storage());
+
+        let view = deltas::View::try_new(&[0x01, 0x02, 0x07][..]).unwrap();
+        assert_eq!(Ok(-1), view.x().try_read());
+        assert_eq!(Ok(1), view.y().try_read());
+        assert_eq!(7, view.flags().read());
+        assert!(deltas::View::try_new(&[0x81, 0x01, 0x02][..]).is_err());
+    }
+
+    #[test]
+    fn zigzag_errors() {
+        // 65536 decodes to 32768, which doesn't fit into an i16
+        assert_eq!(
+            Err(VarintError::Overflow),
+            signed_short::value::try_read(&[0x80, 0x80, 0x04])
+        );
+        assert_eq!(
+            Err(VarintError::Truncated),
+            signed_short::value::try_read(&[0x80])
+        );
+        assert_eq!(
+            Err(VarintError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            }),
+            signed::value::try_write(&mut [0; 2], -65)
+        );
+    }
+
+    binary_layout!(midi, BigEndian, {
+        delta_time: Vlq<u32>,
+    });
+
+    #[test]
+    fn vlq_metadata() {
+        assert_eq!(None, midi::SIZE);
+        assert_eq!(
+            FieldKind::Varint(VarintEncoding::Vlq),
+            midi::Fields::delta_time.kind
+        );
+        assert_eq!("Vlq<u32>", midi::Fields::delta_time.type_name);
+    }
+
+    #[test]
+    fn vlq_fieldapi() {
+        // Examples from the Standard MIDI File specification
+        let mut storage = [0; 5];
+        for (value, encoded) in [
+            (0, &[0x00][..]),
+            (0x40, &[0x40][..]),
+            (0x7f, &[0x7f][..]),
+            (0x80, &[0x81, 0x00][..]),
+            (0x2000, &[0xc0, 0x00][..]),
+            (0x3fff, &[0xff, 0x7f][..]),
+            (0x4000, &[0x81, 0x80, 0x00][..]),
+            (0x0010_0000, &[0xc0, 0x80, 0x00][..]),
+            (0x0fff_ffff, &[0xff, 0xff, 0xff, 0x7f][..]),
+            (u32::MAX, &[0x8f, 0xff, 0xff, 0xff, 0x7f][..]),
+        ] {
+            midi::delta_time::try_write(&mut storage, value).unwrap();
+            assert_eq!(encoded, &storage[..encoded.len()]);
+            assert_eq!(Ok(value), midi::delta_time::try_read(&storage));
+        }
+    }
+
+    binary_layout!(event, BigEndian, {
+        delta_time: Vlq<u32>,
+        status: u8,
+        length: Vlq<u16>,
+        data: [u8],
+    });
+
+    #[test]
+    fn vlq_fields_after_varints() {
+        assert_eq!(1, event::status::OFFSET);
+        assert_eq!(3, event::data::OFFSET);
+        let view = event::View::try_new(&[0x81, 0x00, 0xff, 0x81, 0x00, 1, 2][..]).unwrap();
+        assert_eq!(Ok(0x80), view.delta_time().try_read());
+        assert_eq!(0xff, view.status().read());
+        assert_eq!(Ok(0x80), view.length().try_read());
+        assert_eq!(&[1, 2], view.data());
+        assert!(event::View::try_new(&[0x81, 0x00, 0xff, 0x81][..]).is_err());
+    }
+
+    #[test]
+    fn vlq_errors() {
+        assert_eq!(
+            Err(VarintError::Truncated),
+            midi::delta_time::try_read(&[0x81, 0x80])
+        );
+        // 0x1_0000_0000 doesn't fit into a u32
+        assert_eq!(
+            Err(VarintError::Overflow),
+            midi::delta_time::try_read(&[0x90, 0x80, 0x80, 0x80, 0x00])
+        );
+        assert_eq!(
+            Err(VarintError::StorageTooShort {
+                expected: 2,
+                actual: 1
+            }),
+            midi::delta_time::try_write(&mut [0], 0x80)
+        );
+    }
+}
@@ -1,6 +1,12 @@
 use core::marker::PhantomData;
 
-use crate::{Field, FieldCopyAccess, FieldReadExt, FieldWriteExt};
+#[cfg(feature = "volatile")]
+use crate::FieldVolatileAccess;
+use crate::{
+    Field, FieldArrayAccess, FieldBitArrayAccess, FieldContextAccess, FieldCopyAccess,
+    FieldLengthPrefixedAccess, FieldMagicAccess, FieldPaddingAccess, FieldReadExt,
+    FieldStringAccess, FieldWriteExt, InfallibleResultExt, LengthPrefixedError,
+};
 
 /// A field view represents the field metadata stored in a [Field] plus it stores the underlying
 /// storage data it operates on, either as a reference to a slice `&[u8]`, `&mut [u8]`, or as
@@ -157,3 +163,369 @@ impl<S: AsMut<[u8]>, F: FieldCopyAccess> FieldView<S, F> {
         F::try_write(self.storage.as_mut(), v)
     }
 }
+impl<S: AsRef<[u8]>, F: FieldArrayAccess> FieldView<S, F> {
+    /// Read the element with the given index from an array field, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldView::len].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   samples: [u16; 8],
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   let sample: u16 = view.samples().get(3);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> F::Element {
+        F::get(self.storage.as_ref(), index)
+    }
+
+    /// The number of elements of an array field, see [FieldArrayAccess::len].
+    /// For open ended arrays like `[u32]`, these are all whole elements fitting into the rest of the storage.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   count: u8,
+    ///   records: [u32],
+    /// });
+    ///
+    /// let storage = [0; 13];
+    /// let view = my_layout::View::new(&storage[..]);
+    /// assert_eq!(3, view.records().len());
+    /// ```
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        F::len(self.storage.as_ref())
+    }
+
+    /// Returns true if the array field doesn't have any elements
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the elements of an array field, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   count: u8,
+    ///   records: [u16],
+    /// });
+    ///
+    /// let storage = [2, 0, 1, 0, 2];
+    /// let view = my_layout::View::new(&storage[..]);
+    /// assert_eq!(3, view.records().iter().map(u32::from).sum::<u32>());
+    /// ```
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = F::Element> + '_ {
+        (0..self.len()).map(move |index| self.get(index))
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldArrayAccess> FieldView<S, F> {
+    /// Write the element with the given index to an array field, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldView::len].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   samples: [u16; 8],
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///   let mut view = my_layout::View::new(storage_data);
+    ///   view.samples_mut().set(3, 1000);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, value: F::Element) {
+        F::set(self.storage.as_mut(), index, value)
+    }
+}
+impl<S: AsRef<[u8]>, F: FieldBitArrayAccess> FieldView<S, F> {
+    /// Read the bit with the given index from a [BitArray](crate::BitArray) field, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldBitArrayAccess::BITS].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, BitArray};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   allocated: BitArray<64>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) -> bool {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   view.allocated().get_bit(17)
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn get_bit(&self, index: usize) -> bool {
+        F::get_bit(self.storage.as_ref(), index)
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldBitArrayAccess> FieldView<S, F> {
+    /// Write the bit with the given index of a [BitArray](crate::BitArray) field, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than [FieldBitArrayAccess::BITS].
+    #[inline(always)]
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        F::set_bit(self.storage.as_mut(), index, value)
+    }
+}
+impl<S: AsRef<[u8]>, F: FieldStringAccess> FieldView<S, F> {
+    /// Read a string field like [FixedString](crate::FixedString), [FixedCStr](crate::FixedCStr) or [LengthPrefixedStr](crate::LengthPrefixedStr), assuming the defined layout, using the [FieldView] API.
+    /// See [FieldStringAccess::try_read_str].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FixedString};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   name: FixedString<32>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   let name: &str = view.name().try_read_str().unwrap();
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn try_read_str(&self) -> Result<&str, F::ReadError> {
+        F::try_read_str(self.storage.as_ref())
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldStringAccess> FieldView<S, F> {
+    /// Write a string field like [FixedString](crate::FixedString), [FixedCStr](crate::FixedCStr) or [LengthPrefixedStr](crate::LengthPrefixedStr), assuming the defined layout, using the [FieldView] API.
+    /// See [FieldStringAccess::try_write_str].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FixedString};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   name: FixedString<32>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///   let mut view = my_layout::View::new(storage_data);
+    ///   view.name_mut().try_write_str("readme.txt").unwrap();
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn try_write_str(&mut self, value: &str) -> Result<(), F::WriteError> {
+        F::try_write_str(self.storage.as_mut(), value)
+    }
+}
+impl<S: AsRef<[u8]>, F: FieldLengthPrefixedAccess> FieldView<S, F> {
+    /// Borrow the data of a [LengthPrefixed](crate::LengthPrefixed) field with read access, assuming the defined layout, using the [FieldView] API.
+    /// See [FieldLengthPrefixedAccess::try_data].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, LengthPrefixed};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   payload: LengthPrefixed<u16>,
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   let payload: &[u8] = view.payload().try_data().unwrap();
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn try_data(&self) -> Result<&[u8], LengthPrefixedError> {
+        F::try_data(self.storage.as_ref())
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldLengthPrefixedAccess> FieldView<S, F> {
+    /// Borrow the data of a [LengthPrefixed](crate::LengthPrefixed) field with write access, assuming the defined layout, using the [FieldView] API.
+    /// See [FieldLengthPrefixedAccess::try_data_mut].
+    #[inline(always)]
+    pub fn try_data_mut(&mut self) -> Result<&mut [u8], LengthPrefixedError> {
+        F::try_data_mut(self.storage.as_mut())
+    }
+
+    /// Write the length prefix and the data of a [LengthPrefixed](crate::LengthPrefixed) field, assuming the defined layout, using the [FieldView] API.
+    /// See [FieldLengthPrefixedAccess::try_write_data].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, LengthPrefixed};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   payload: LengthPrefixed<u16>,
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///   let mut view = my_layout::View::new(storage_data);
+    ///   view.payload_mut().try_write_data(&[1, 2, 3]).unwrap();
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn try_write_data(&mut self, data: &[u8]) -> Result<(), LengthPrefixedError> {
+        F::try_write_data(self.storage.as_mut(), data)
+    }
+}
+impl<S: AsRef<[u8]>, F: FieldMagicAccess> FieldView<S, F> {
+    /// Check whether a [Magic](crate::Magic) field stores its constant value, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, Magic};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   magic: Magic<u32, 0x89504e47>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) -> bool {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   view.magic().is_valid()
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn is_valid(&self) -> bool {
+        F::is_valid(self.storage.as_ref())
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldMagicAccess> FieldView<S, F> {
+    /// Write the constant value into a [Magic](crate::Magic) field, assuming the defined layout, using the [FieldView] API.
+    #[inline(always)]
+    pub fn write_magic(&mut self) {
+        F::write_magic(self.storage.as_mut())
+    }
+}
+impl<S: AsRef<[u8]>, F: FieldPaddingAccess> FieldView<S, F> {
+    /// Check whether all bytes of a [Padding](crate::Padding) field are zero, assuming the defined layout, using the [FieldView] API.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, Padding};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   reserved: Padding<4>,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) -> bool {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   view.reserved().is_zero()
+    /// }
+    /// # fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn is_zero(&self) -> bool {
+        F::is_zero(self.storage.as_ref())
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldPaddingAccess> FieldView<S, F> {
+    /// Set all bytes of a [Padding](crate::Padding) field to zero, assuming the defined layout, using the [FieldView] API.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        F::clear(self.storage.as_mut())
+    }
+}
+impl<S: AsRef<[u8]>, F: FieldContextAccess> FieldView<S, F> {
+    /// Read the field from a given data region, assuming the defined layout, using the [FieldView] API.
+    /// The context is passed on to the [LayoutAsWith](crate::LayoutAsWith) conversion of the field.
+    /// See [LayoutAsWith](crate::LayoutAsWith) for an example.
+    #[inline(always)]
+    pub fn try_read_with(&self, context: &F::Context) -> Result<F::HighLevelType, F::ReadError> {
+        F::try_read_with(self.storage.as_ref(), context)
+    }
+
+    /// Read the field like [FieldView::try_read_with], for fields whose conversion cannot fail.
+    #[inline(always)]
+    pub fn read_with(&self, context: &F::Context) -> F::HighLevelType
+    where
+        Result<F::HighLevelType, F::ReadError>: InfallibleResultExt<F::HighLevelType>,
+    {
+        self.try_read_with(context).infallible_unwrap()
+    }
+}
+impl<S: AsMut<[u8]>, F: FieldContextAccess> FieldView<S, F> {
+    /// Write the field to a given data region, assuming the defined layout, using the [FieldView] API.
+    /// The context is passed on to the [LayoutAsWith](crate::LayoutAsWith) conversion of the field.
+    /// See [LayoutAsWith](crate::LayoutAsWith) for an example.
+    #[inline(always)]
+    pub fn try_write_with(
+        &mut self,
+        v: F::HighLevelType,
+        context: &F::Context,
+    ) -> Result<(), F::WriteError> {
+        F::try_write_with(self.storage.as_mut(), v, context)
+    }
+
+    /// Write the field like [FieldView::try_write_with], for fields whose conversion cannot fail.
+    #[inline(always)]
+    pub fn write_with(&mut self, v: F::HighLevelType, context: &F::Context)
+    where
+        Result<(), F::WriteError>: InfallibleResultExt<()>,
+    {
+        self.try_write_with(v, context).infallible_unwrap()
+    }
+}
+#[cfg(feature = "volatile")]
+impl<S: AsRef<[u8]>, F: FieldVolatileAccess> FieldView<S, F> {
+    /// Read the field with a single volatile memory access, assuming the defined layout, using the [FieldView] API.
+    /// See [FieldVolatileAccess::read_volatile].
+    ///
+    /// # Panics
+    /// If the field isn't aligned for its type in memory.
+    #[inline(always)]
+    pub fn read_volatile(&self) -> F::HighLevelType {
+        F::read_volatile(self.storage.as_ref())
+    }
+}
+#[cfg(feature = "volatile")]
+impl<S: AsMut<[u8]>, F: FieldVolatileAccess> FieldView<S, F> {
+    /// Write the field with a single volatile memory access, assuming the defined layout, using the [FieldView] API.
+    /// See [FieldVolatileAccess::write_volatile].
+    ///
+    /// # Panics
+    /// If the field isn't aligned for its type in memory.
+    #[inline(always)]
+    pub fn write_volatile(&mut self, v: F::HighLevelType) {
+        F::write_volatile(self.storage.as_mut(), v)
+    }
+}
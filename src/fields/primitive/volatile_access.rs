@@ -0,0 +1,308 @@
+#![allow(unsafe_code)]
+
+use super::super::Field;
+use super::PrimitiveField;
+use crate::endianness::{EndianKind, Endianness};
+
+/// This trait is implemented for primitive integer and float fields when the `volatile` feature is enabled.
+/// It reads and writes them with a single volatile memory access, so that layouts can describe memory-mapped
+/// hardware registers without the compiler eliding, merging or reordering the accesses.
+///
+/// Volatile accesses need the field to be aligned for its type in memory, e.g. a `u32` field has to start at
+/// an address that is a multiple of 4. Accessing an unaligned field panics.
+///
+/// [FieldVolatileAccess::read_volatile] and [FieldVolatileAccess::write_volatile] take the register block as a slice, which is convenient,
+/// but holding a `&[u8]` or `&mut [u8]` to memory-mapped registers allows the compiler to insert reads of that memory that the code
+/// never asked for, which registers with read side effects don't tolerate. For such registers, use [FieldVolatileAccess::read_volatile_ptr]
+/// and [FieldVolatileAccess::write_volatile_ptr] with a raw pointer to the register block instead.
+///
+/// Fields after [LengthPrefixed](crate::LengthPrefixed) or varint fields don't have a fixed offset and don't offer volatile accesses.
+pub trait FieldVolatileAccess: Field {
+    /// The data type that is returned from [FieldVolatileAccess::read_volatile] and has to be passed in to [FieldVolatileAccess::write_volatile]
+    type HighLevelType;
+
+    /// Read the field from a given data region with a single volatile memory access, assuming the defined layout, using the [Field] API.
+    ///
+    /// # Panics
+    /// If the field isn't aligned for its type in memory.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, FieldVolatileAccess};
+    ///
+    /// binary_layout!(registers, LittleEndian, {
+    ///   control: u32,
+    ///   status: u32,
+    /// });
+    ///
+    /// fn is_ready(register_block: &[u8]) -> bool {
+    ///   registers::status::read_volatile(register_block) & 1 != 0
+    /// }
+    /// # fn main() {}
+    /// ```
+    fn read_volatile(storage: &[u8]) -> Self::HighLevelType;
+
+    /// Write the field to a given data region with a single volatile memory access, assuming the defined layout, using the [Field] API.
+    ///
+    /// # Panics
+    /// If the field isn't aligned for its type in memory.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, FieldVolatileAccess};
+    ///
+    /// binary_layout!(registers, LittleEndian, {
+    ///   control: u32,
+    ///   status: u32,
+    /// });
+    ///
+    /// fn enable(register_block: &mut [u8]) {
+    ///   registers::control::write_volatile(register_block, 1);
+    /// }
+    /// # fn main() {}
+    /// ```
+    fn write_volatile(storage: &mut [u8], value: Self::HighLevelType);
+
+    /// Read the field with a single volatile memory access, given a raw pointer to the start of the layout, e.g. the base address of a register block.
+    /// Unlike [FieldVolatileAccess::read_volatile], this doesn't need a reference to the memory, so the compiler can't read it on its own.
+    ///
+    /// # Safety
+    /// `base` plus the offset of the field has to be [valid](core::ptr#safety) for reading the field, i.e. the whole field has to be
+    /// in a single allocated object or memory-mapped region that is readable.
+    ///
+    /// # Panics
+    /// If the field isn't aligned for its type in memory.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, FieldVolatileAccess};
+    ///
+    /// binary_layout!(registers, LittleEndian, {
+    ///   control: u32,
+    ///   status: u32,
+    /// });
+    ///
+    /// /// # Safety
+    /// /// `base` has to point to a register block of this layout
+    /// unsafe fn is_ready(base: *const u8) -> bool {
+    ///   registers::status::read_volatile_ptr(base) & 1 != 0
+    /// }
+    /// # fn main() {}
+    /// ```
+    unsafe fn read_volatile_ptr(base: *const u8) -> Self::HighLevelType;
+
+    /// Write the field with a single volatile memory access, given a raw pointer to the start of the layout, e.g. the base address of a register block.
+    /// Unlike [FieldVolatileAccess::write_volatile], this doesn't need a reference to the memory, so the compiler can't read it on its own.
+    ///
+    /// # Safety
+    /// `base` plus the offset of the field has to be [valid](core::ptr#safety) for writing the field, i.e. the whole field has to be
+    /// in a single allocated object or memory-mapped region that is writable.
+    ///
+    /// # Panics
+    /// If the field isn't aligned for its type in memory.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::{prelude::*, FieldVolatileAccess};
+    ///
+    /// binary_layout!(registers, LittleEndian, {
+    ///   control: u32,
+    ///   status: u32,
+    /// });
+    ///
+    /// /// # Safety
+    /// /// `base` has to point to a register block of this layout
+    /// unsafe fn enable(base: *mut u8) {
+    ///   registers::control::write_volatile_ptr(base, 1);
+    /// }
+    /// # fn main() {}
+    /// ```
+    unsafe fn write_volatile_ptr(base: *mut u8, value: Self::HighLevelType);
+}
+
+#[inline(always)]
+fn assert_aligned<T>(ptr: *const T) {
+    assert!(
+        ptr as usize % core::mem::align_of::<T>() == 0,
+        "Volatile accesses need the field to be aligned for its type in memory"
+    );
+}
+
+macro_rules! volatile_field {
+    ($type:ty) => {
+        impl<E: Endianness, const OFFSET_: usize> FieldVolatileAccess
+            for PrimitiveField<$type, E, OFFSET_>
+        {
+            type HighLevelType = $type;
+
+            #[inline(always)]
+            fn read_volatile(storage: &[u8]) -> $type {
+                let bytes = &storage[Self::OFFSET..(Self::OFFSET + core::mem::size_of::<$type>())];
+                // SAFETY: The field is valid for reading because it is in the slice, which we just checked.
+                unsafe { Self::read_volatile_ptr(bytes.as_ptr().sub(Self::OFFSET)) }
+            }
+
+            #[inline(always)]
+            fn write_volatile(storage: &mut [u8], value: $type) {
+                let bytes =
+                    &mut storage[Self::OFFSET..(Self::OFFSET + core::mem::size_of::<$type>())];
+                // SAFETY: The field is valid for writing because it is in the mutable slice, which we just checked.
+                unsafe { Self::write_volatile_ptr(bytes.as_mut_ptr().sub(Self::OFFSET), value) }
+            }
+
+            #[inline(always)]
+            unsafe fn read_volatile_ptr(base: *const u8) -> $type {
+                let ptr = base.wrapping_add(Self::OFFSET).cast::<$type>();
+                assert_aligned(ptr);
+                // SAFETY: The caller guarantees that the field is valid for reading, and it is aligned because we just checked that.
+                let value = unsafe { core::ptr::read_volatile(ptr) }.to_ne_bytes();
+                match E::KIND {
+                    EndianKind::Big => <$type>::from_be_bytes(value),
+                    EndianKind::Little => <$type>::from_le_bytes(value),
+                    EndianKind::Native => <$type>::from_ne_bytes(value),
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn write_volatile_ptr(base: *mut u8, value: $type) {
+                let value = <$type>::from_ne_bytes(match E::KIND {
+                    EndianKind::Big => value.to_be_bytes(),
+                    EndianKind::Little => value.to_le_bytes(),
+                    EndianKind::Native => value.to_ne_bytes(),
+                });
+                let ptr = base.wrapping_add(Self::OFFSET).cast::<$type>();
+                assert_aligned(ptr);
+                // SAFETY: The caller guarantees that the field is valid for writing, and it is aligned because we just checked that.
+                unsafe { core::ptr::write_volatile(ptr, value) };
+            }
+        }
+    };
+}
+
+volatile_field!(i8);
+volatile_field!(i16);
+volatile_field!(i32);
+volatile_field!(i64);
+volatile_field!(u8);
+volatile_field!(u16);
+volatile_field!(u32);
+volatile_field!(u64);
+volatile_field!(f32);
+volatile_field!(f64);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)]
+    use crate::prelude::*;
+    use crate::FieldVolatileAccess;
+
+    binary_layout!(registers, BigEndian, {
+        control: u8,
+        mode: i8,
+        flags: u16,
+        status: u32,
+        counter: u64,
+        scale: f32,
+        offset: i32,
+        ratio: f64,
+    });
+
+    #[repr(align(8))]
+    struct RegisterBlock([u8; 32]);
+
+    #[test]
+    fn read_write() {
+        let mut block = RegisterBlock([0; 32]);
+        registers::control::write_volatile(&mut block.0, 0xab);
+        registers::mode::write_volatile(&mut block.0, -2);
+        registers::flags::write_volatile(&mut block.0, 0x0102);
+        registers::status::write_volatile(&mut block.0, 0x03040506);
+        registers::counter::write_volatile(&mut block.0, 0x0708090a0b0c0d0e);
+        registers::scale::write_volatile(&mut block.0, 1.5);
+        registers::offset::write_volatile(&mut block.0, -1);
+        registers::ratio::write_volatile(&mut block.0, -0.25);
+
+        assert_eq!(0xab, registers::control::read_volatile(&block.0));
+        assert_eq!(-2, registers::mode::read_volatile(&block.0));
+        assert_eq!(0x0102, registers::flags::read_volatile(&block.0));
+        assert_eq!(0x03040506, registers::status::read_volatile(&block.0));
+        assert_eq!(
+            0x0708090a0b0c0d0e,
+            registers::counter::read_volatile(&block.0)
+        );
+        assert_eq!(1.5, registers::scale::read_volatile(&block.0));
+        assert_eq!(-1, registers::offset::read_volatile(&block.0));
+        assert_eq!(-0.25, registers::ratio::read_volatile(&block.0));
+
+        // The values are stored like with the non-volatile accessors
+        let view = registers::View::new(&block.0[..]);
+        assert_eq!(0x0102, view.flags().read());
+        assert_eq!(0x03040506, view.status().read());
+        assert_eq!(0x0708090a0b0c0d0e, view.counter().read());
+        assert_eq!(1.5, view.scale().read());
+        assert_eq!(-0.25, view.ratio().read());
+        assert_eq!(
+            [0xab, 0xfe, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06],
+            block.0[..8]
+        );
+    }
+
+    #[test]
+    fn view_read_write() {
+        let mut block = RegisterBlock([0; 32]);
+        let mut view = registers::View::new(&mut block.0[..]);
+        view.status_mut().write_volatile(0x11223344);
+        view.counter_mut().write_volatile(42);
+        assert_eq!(0x11223344, view.status().read_volatile());
+        assert_eq!(42, view.counter().read_volatile());
+        assert_eq!(0x11223344, view.status().read());
+    }
+
+    #[test]
+    fn little_endian() {
+        binary_layout!(le_registers, LittleEndian, {
+            value: u32,
+        });
+        let mut block = RegisterBlock([0; 32]);
+        le_registers::value::write_volatile(&mut block.0, 0x01020304);
+        assert_eq!([4, 3, 2, 1], block.0[..4]);
+        assert_eq!(0x01020304, le_registers::value::read_volatile(&block.0));
+    }
+
+    #[test]
+    fn raw_pointers() {
+        let mut block = RegisterBlock([0; 32]);
+        let base = block.0.as_mut_ptr();
+        // SAFETY: The register block is valid for reads and writes of all fields of the layout
+        unsafe {
+            registers::status::write_volatile_ptr(base, 0x11223344);
+            registers::ratio::write_volatile_ptr(base, 2.5);
+            assert_eq!(0x11223344, registers::status::read_volatile_ptr(base));
+            assert_eq!(2.5, registers::ratio::read_volatile_ptr(base));
+        }
+        assert_eq!([0x11, 0x22, 0x33, 0x44], block.0[4..8]);
+        assert_eq!(2.5, registers::ratio::read_volatile(&block.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "aligned")]
+    fn unaligned_pointer() {
+        let block = RegisterBlock([0; 32]);
+        // SAFETY: The register block is valid for reads of the field, which panics before reading because it isn't aligned
+        unsafe { registers::status::read_volatile_ptr(block.0.as_ptr().add(1)) };
+    }
+
+    #[test]
+    #[should_panic(expected = "aligned")]
+    fn unaligned_read() {
+        let block = RegisterBlock([0; 32]);
+        registers::status::read_volatile(&block.0[1..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "aligned")]
+    fn unaligned_write() {
+        let mut block = RegisterBlock([0; 32]);
+        registers::flags::write_volatile(&mut block.0[1..], 1);
+    }
+}
@@ -0,0 +1,415 @@
+use core::marker::PhantomData;
+
+use crate::builder::BuilderField;
+use crate::layout_info::{FieldKind, FieldKindInfo, Layout};
+use crate::utils::data::Data;
+
+use super::{
+    contextual::FieldContextAccess,
+    primitive::{
+        FieldArrayAccess, FieldBitArrayAccess, FieldCopyAccess, FieldLengthPrefixedAccess,
+        FieldMagicAccess, FieldOwnedAccess, FieldPaddingAccess, FieldSliceAccess,
+        FieldStringAccess, LengthPrefixedError,
+    },
+    Field, StorageIntoFieldView, StorageToFieldView,
+};
+
+/// A [RelativeField] is a field after a [LengthPrefixed](crate::LengthPrefixed) or varint field like [Leb128](crate::Leb128),
+/// whose offset depends on the data because the number of bytes of those fields does.
+/// [binary_layout!](crate::binary_layout!) wraps such fields into a [RelativeField] of their layout `L`, which offers the same API as the field `F`.
+///
+/// Its [Field::OFFSET] is the offset of the field when the [LengthPrefixed](crate::LengthPrefixed) fields before it are empty
+/// and the varint fields before it take up a single byte. Each access reads those fields from the storage to find the actual offset,
+/// so the fields of such a layout have to be written in order, and rewriting one of those fields with a different length
+/// moves all fields after it. Accessing a field panics if the storage ends before the end of one of those fields,
+/// which [View::try_new](crate::binary_layout!#the-view-api) rules out.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LengthPrefixedStr};
+///
+/// binary_layout!(message, BigEndian, {
+///   kind: u8,
+///   name: LengthPrefixedStr<u8>,
+///   crc: u32,
+/// });
+///
+/// fn main() {
+///     let mut view = message::View::new([0; 16]);
+///     view.kind_mut().write(1);
+///     view.name_mut().try_write_str("abc").unwrap();
+///     view.crc_mut().write(0xdeadbeef);
+///     assert_eq!(2, message::crc::OFFSET);
+///     assert_eq!(&[1, 3, b'a', b'b', b'c', 0xde, 0xad, 0xbe, 0xef], &view.into_storage()[..9]);
+///
+///     let view = message::View::try_new(&[2, 1, b'x', 0, 0, 0, 5][..]).unwrap();
+///     assert_eq!(Ok("x"), view.name().try_read_str());
+///     assert_eq!(5, view.crc().read());
+///     assert!(message::View::try_new(&[2, 1, b'x', 0, 0, 0][..]).is_err());
+/// }
+/// ```
+pub struct RelativeField<L, F> {
+    _p1: PhantomData<L>,
+    _p2: PhantomData<F>,
+}
+
+impl<L: Layout, F: Field> RelativeField<L, F> {
+    /// Number of bytes the [LengthPrefixed](crate::LengthPrefixed) and varint fields before the field take up in the storage beyond their minimal size
+    #[inline]
+    fn shift(storage: &[u8]) -> usize {
+        match L::LAYOUT.shift_in(F::OFFSET, storage) {
+            Ok(shift) => shift,
+            Err(_) => panic!(
+                "The storage is too short for the LengthPrefixed or varint fields of the layout {} before the field at offset {}",
+                L::NAME,
+                F::OFFSET,
+            ),
+        }
+    }
+}
+
+impl<L, F: Field> Field for RelativeField<L, F> {
+    /// See [Field::Endian]
+    type Endian = F::Endian;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = F::OFFSET;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = F::SIZE;
+}
+
+impl<L: Layout, F: Field + FieldKindInfo> FieldKindInfo for RelativeField<L, F> {
+    const KIND: FieldKind = F::KIND;
+    const OPTIONAL: bool = F::OPTIONAL;
+    const RELATIVE: bool = true;
+
+    #[inline]
+    fn offset_in(storage: &[u8]) -> Option<usize> {
+        let shift = L::LAYOUT.shift_in(F::OFFSET, storage).ok()?;
+        Some(F::OFFSET + shift)
+    }
+}
+
+impl<'a, L: Layout, F: Field + StorageToFieldView<&'a [u8]>> StorageToFieldView<&'a [u8]>
+    for RelativeField<L, F>
+{
+    type View = F::View;
+
+    #[inline]
+    fn view(storage: &'a [u8]) -> Self::View {
+        F::view(&storage[Self::shift(storage)..])
+    }
+}
+
+impl<'a, L: Layout, F: Field + StorageToFieldView<&'a mut [u8]>> StorageToFieldView<&'a mut [u8]>
+    for RelativeField<L, F>
+{
+    type View = F::View;
+
+    #[inline]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        let shift = Self::shift(storage);
+        F::view(&mut storage[shift..])
+    }
+}
+
+impl<S: AsRef<[u8]>, L: Layout, F: Field + StorageIntoFieldView<Data<S>>> StorageIntoFieldView<S>
+    for RelativeField<L, F>
+{
+    type View = F::View;
+
+    #[inline]
+    fn into_view(storage: S) -> Self::View {
+        let shift = Self::shift(storage.as_ref());
+        F::into_view(Data::from(storage).into_subregion(shift..))
+    }
+}
+
+impl<L: Layout, F: FieldCopyAccess> FieldCopyAccess for RelativeField<L, F> {
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = F::ReadError;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = F::WriteError;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = F::HighLevelType;
+
+    #[inline]
+    fn try_read(storage: &[u8]) -> Result<Self::HighLevelType, Self::ReadError> {
+        F::try_read(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn try_write(storage: &mut [u8], v: Self::HighLevelType) -> Result<(), Self::WriteError> {
+        let shift = Self::shift(storage);
+        F::try_write(&mut storage[shift..], v)
+    }
+}
+
+impl<L: Layout, F: FieldContextAccess> FieldContextAccess for RelativeField<L, F> {
+    type Context = F::Context;
+    type ReadError = F::ReadError;
+    type WriteError = F::WriteError;
+    type HighLevelType = F::HighLevelType;
+
+    #[inline]
+    fn try_read_with(
+        storage: &[u8],
+        context: &Self::Context,
+    ) -> Result<Self::HighLevelType, Self::ReadError> {
+        F::try_read_with(&storage[Self::shift(storage)..], context)
+    }
+
+    #[inline]
+    fn try_write_with(
+        storage: &mut [u8],
+        v: Self::HighLevelType,
+        context: &Self::Context,
+    ) -> Result<(), Self::WriteError> {
+        let shift = Self::shift(storage);
+        F::try_write_with(&mut storage[shift..], v, context)
+    }
+}
+
+impl<'a, L: Layout, F: FieldSliceAccess<'a>> FieldSliceAccess<'a> for RelativeField<L, F> {
+    type SliceType = F::SliceType;
+    type MutSliceType = F::MutSliceType;
+
+    #[inline]
+    fn data(storage: &'a [u8]) -> Self::SliceType {
+        F::data(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn data_mut(storage: &'a mut [u8]) -> Self::MutSliceType {
+        let shift = Self::shift(storage);
+        F::data_mut(&mut storage[shift..])
+    }
+}
+
+impl<L: Layout, F: FieldArrayAccess> FieldArrayAccess for RelativeField<L, F> {
+    type Element = F::Element;
+
+    #[inline]
+    fn len(storage: &[u8]) -> usize {
+        F::len(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn get(storage: &[u8], index: usize) -> Self::Element {
+        F::get(&storage[Self::shift(storage)..], index)
+    }
+
+    #[inline]
+    fn set(storage: &mut [u8], index: usize, value: Self::Element) {
+        let shift = Self::shift(storage);
+        F::set(&mut storage[shift..], index, value)
+    }
+}
+
+impl<L: Layout, F: FieldBitArrayAccess> FieldBitArrayAccess for RelativeField<L, F> {
+    const BITS: usize = F::BITS;
+
+    #[inline]
+    fn get_bit(storage: &[u8], index: usize) -> bool {
+        F::get_bit(&storage[Self::shift(storage)..], index)
+    }
+
+    #[inline]
+    fn set_bit(storage: &mut [u8], index: usize, value: bool) {
+        let shift = Self::shift(storage);
+        F::set_bit(&mut storage[shift..], index, value)
+    }
+}
+
+impl<L: Layout, F: FieldStringAccess> FieldStringAccess for RelativeField<L, F> {
+    type ReadError = F::ReadError;
+    type WriteError = F::WriteError;
+
+    #[inline]
+    fn try_read_str(storage: &[u8]) -> Result<&str, Self::ReadError> {
+        F::try_read_str(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn try_write_str(storage: &mut [u8], value: &str) -> Result<(), Self::WriteError> {
+        let shift = Self::shift(storage);
+        F::try_write_str(&mut storage[shift..], value)
+    }
+}
+
+impl<L: Layout, F: FieldLengthPrefixedAccess> FieldLengthPrefixedAccess for RelativeField<L, F> {
+    #[inline]
+    fn try_data(storage: &[u8]) -> Result<&[u8], LengthPrefixedError> {
+        F::try_data(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn try_data_mut(storage: &mut [u8]) -> Result<&mut [u8], LengthPrefixedError> {
+        let shift = Self::shift(storage);
+        F::try_data_mut(&mut storage[shift..])
+    }
+
+    #[inline]
+    fn try_write_data(storage: &mut [u8], data: &[u8]) -> Result<(), LengthPrefixedError> {
+        let shift = Self::shift(storage);
+        F::try_write_data(&mut storage[shift..], data)
+    }
+}
+
+impl<L: Layout, F: FieldMagicAccess> FieldMagicAccess for RelativeField<L, F> {
+    const VALUE: u128 = F::VALUE;
+
+    #[inline]
+    fn is_valid(storage: &[u8]) -> bool {
+        F::is_valid(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn write_magic(storage: &mut [u8]) {
+        let shift = Self::shift(storage);
+        F::write_magic(&mut storage[shift..])
+    }
+}
+
+impl<L: Layout, F: FieldPaddingAccess> FieldPaddingAccess for RelativeField<L, F> {
+    #[inline]
+    fn is_zero(storage: &[u8]) -> bool {
+        F::is_zero(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn clear(storage: &mut [u8]) {
+        let shift = Self::shift(storage);
+        F::clear(&mut storage[shift..])
+    }
+}
+
+impl<L: Layout, F: FieldOwnedAccess> FieldOwnedAccess for RelativeField<L, F> {
+    type Owned = F::Owned;
+
+    #[inline]
+    fn read_owned(storage: &[u8]) -> Self::Owned {
+        F::read_owned(&storage[Self::shift(storage)..])
+    }
+
+    #[inline]
+    fn write_owned(storage: &mut [u8], value: &Self::Owned) {
+        let shift = Self::shift(storage);
+        F::write_owned(&mut storage[shift..], value)
+    }
+}
+
+impl<L: Layout, F: BuilderField> BuilderField for RelativeField<L, F> {
+    type Initial = F::Initial;
+
+    #[inline]
+    fn finish(storage: &mut [u8]) {
+        let shift = Self::shift(storage);
+        F::finish(&mut storage[shift..])
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Selects the type of a field in [binary_layout!](crate::binary_layout!), which only knows whether a field is after
+/// a [LengthPrefixed](crate::LengthPrefixed) or varint field at compile time: fields after such fields are wrapped into
+/// a [RelativeField] of their layout `L`, other fields keep their type.
+pub trait SelectField<L, const RELATIVE: bool> {
+    /// The type of the field
+    type Field;
+}
+
+impl<L, F> SelectField<L, false> for F {
+    type Field = F;
+}
+
+impl<L, F> SelectField<L, true> for F {
+    type Field = RelativeField<L, F>;
+}
+
+#[cfg(test)]
+mod tests {
+    use core::marker::PhantomData;
+
+    use crate::prelude::*;
+    use crate::{FieldKind, LengthPrefixed, LengthPrefixedStr, PrimitiveField, RelativeField};
+
+    binary_layout!(message, BigEndian, {
+        kind: u8,
+        name: LengthPrefixed<u8>,
+        crc: u32,
+    });
+
+    binary_layout!(record, LittleEndian, {
+        first: LengthPrefixedStr<u16>,
+        flags: u8 bits(4),
+        level: u8 bits(4),
+        second: LengthPrefixed<u8>,
+        values: [u16; 2],
+        tail: [u8],
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(0, message::kind::OFFSET);
+        assert_eq!(1, message::name::OFFSET);
+        assert_eq!(2, message::crc::OFFSET);
+        assert_eq!(Some(4), message::crc::SIZE);
+        assert_eq!(None, message::SIZE);
+        assert_eq!(6, message::MIN_SIZE);
+        assert_eq!(FieldKind::UnsignedInteger, message::Fields::crc.kind);
+        let _: PhantomData<RelativeField<message::NestedView, PrimitiveField<u32, BigEndian, 2>>> =
+            PhantomData::<message::crc>;
+        let _: PhantomData<PrimitiveField<u8, BigEndian, 0>> = PhantomData::<message::kind>;
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 16];
+        message::kind::write(&mut storage, 7);
+        message::name::try_write_data(&mut storage, b"abc").unwrap();
+        message::crc::write(&mut storage, 0x01020304);
+        assert_eq!(&[7, 3, b'a', b'b', b'c', 1, 2, 3, 4], &storage[..9]);
+        assert_eq!(0x01020304, message::crc::read(&storage));
+        assert_eq!(Ok(&b"abc"[..]), message::name::try_data(&storage));
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = message::View::new([0; 16]);
+        view.kind_mut().write(7);
+        view.name_mut().try_write_data(b"ab").unwrap();
+        view.crc_mut().write(0x01020304);
+        assert_eq!(0x01020304, view.crc().read());
+        assert_eq!(Ok(&b"ab"[..]), view.name().try_data());
+        assert_eq!(0x01020304, view.into_crc().read());
+    }
+
+    #[test]
+    fn fields_after_several_dynamic_fields() {
+        let mut view = record::View::new([0; 32]);
+        view.first_mut().try_write_str("hi").unwrap();
+        view.flags_mut().try_write(0xa).unwrap();
+        view.level_mut().try_write(0x5).unwrap();
+        view.second_mut().try_write_data(&[0xff; 3]).unwrap();
+        view.values_mut().set(1, 0x1234);
+        view.tail_mut()[..2].copy_from_slice(&[8, 9]);
+        assert_eq!(
+            &[2, 0, b'h', b'i', 0x5a, 3, 0xff, 0xff, 0xff, 0, 0, 0x34, 0x12, 8, 9],
+            &view.into_storage()[..15]
+        );
+
+        let data = [0, 0, 0x12, 0, 0, 0, 0, 0, 7];
+        let view = record::View::try_new(&data[..]).unwrap();
+        assert_eq!(Ok(""), view.first().try_read_str());
+        assert_eq!(2, view.flags().read());
+        assert_eq!(1, view.level().read());
+        assert_eq!(0, view.values().get(1));
+        assert_eq!(&[7], view.tail());
+    }
+
+    #[test]
+    #[should_panic(expected = "The storage is too short for the LengthPrefixed or varint fields")]
+    fn storage_too_short_for_the_fields_before() {
+        let view = message::View::new(&[1, 10, 0, 0, 0, 0][..]);
+        view.crc().read();
+    }
+}
@@ -4,6 +4,7 @@ use core::marker::PhantomData;
 #[cfg(feature = "std")]
 use thiserror::Error;
 
+use crate::layout_info::{FieldKind, FieldKindInfo};
 use crate::utils::infallible::IsInfallible;
 
 use super::{
@@ -42,6 +43,45 @@ use super::{
 ///
 /// # fn main() {}
 /// ```
+///
+/// # Example (validating values):
+/// Conversions that can fail return an error instead of panicking or coercing the value.
+/// The error is returned from [FieldCopyAccess::try_read] or [FieldCopyAccess::try_write]
+/// wrapped in [WrappedFieldError::LayoutAsError].
+/// ```
+/// use binary_layout::{prelude::*, LayoutAs, WrappedFieldError};
+/// use core::convert::Infallible;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Percent(u8);
+/// impl LayoutAs<u8> for Percent {
+///   type ReadError = &'static str;
+///   type WriteError = &'static str;
+///
+///   fn try_read(v: u8) -> Result<Percent, &'static str> {
+///     if v <= 100 { Ok(Percent(v)) } else { Err("percentage out of range") }
+///   }
+///
+///   fn try_write(v: Percent) -> Result<u8, &'static str> {
+///     if v.0 <= 100 { Ok(v.0) } else { Err("percentage out of range") }
+///   }
+/// }
+///
+/// binary_layout!(battery, LittleEndian, {
+///   charge: Percent as u8,
+/// });
+///
+/// fn main() {
+///   let view = battery::View::new([42]);
+///   assert_eq!(Percent(42), view.charge().try_read().unwrap());
+///
+///   let view = battery::View::new([200]);
+///   assert!(matches!(
+///     view.charge().try_read(),
+///     Err(WrappedFieldError::<Infallible, _>::LayoutAsError("percentage out of range")),
+///   ));
+/// }
+/// ```
 pub trait LayoutAs<U>: Sized {
     /// See [FieldCopyAccess::ReadError].
     ///
@@ -187,6 +227,10 @@ impl<U, T: LayoutAs<U>, F: Field> Field for WrappedField<U, T, F> {
     const SIZE: Option<usize> = F::SIZE;
 }
 
+impl<U, T: LayoutAs<U>, F: Field + FieldKindInfo> FieldKindInfo for WrappedField<U, T, F> {
+    const KIND: FieldKind = F::KIND;
+}
+
 impl<
         'a,
         U,
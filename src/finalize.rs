@@ -0,0 +1,302 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::layout_info::FieldKindInfo;
+use crate::{Field, FieldInfo, LayoutInfo};
+
+/// Implement this to define a checksum algorithm for checksum fields, which are declared like `checksum: u16 checksum(MyChecksum)`.
+/// The generated `View::finalize()` computes the checksum over the data of the layout with the checksum field set to zero
+/// and writes it into the checksum field, so the `Output` type has to be the type of the field.
+/// Checksum fields declared with the range they cover, like `crc: u32 checksum(MyChecksum, header..=payload)`,
+/// compute the checksum over just these bytes instead.
+/// See [InternetChecksum] for an implementation and [binary_layout!](crate::binary_layout!) for an example.
+pub trait Checksum {
+    /// The type of the checksum
+    type Output;
+
+    /// Compute the checksum of the data
+    fn checksum(data: &[u8]) -> Self::Output;
+}
+
+/// The 16 bit ones' complement checksum used by IP, ICMP, UDP and TCP, see [RFC 1071](https://www.rfc-editor.org/rfc/rfc1071).
+/// The data is summed up as big endian 16 bit words, so checksum fields using it should be in [BigEndian](crate::BigEndian) layouts
+/// and at an even offset for the checksum of the finalized data to come out as zero.
+pub struct InternetChecksum;
+
+impl Checksum for InternetChecksum {
+    type Output = u16;
+
+    fn checksum(data: &[u8]) -> u16 {
+        let mut sum = data.chunks(2).fold(0u32, |sum, word| {
+            let word = u16::from_be_bytes([word[0], word.get(1).copied().unwrap_or(0)]);
+            let sum = sum + u32::from(word);
+            (sum & 0xffff) + (sum >> 16)
+        });
+        while sum > 0xffff {
+            sum = (sum & 0xffff) + (sum >> 16);
+        }
+        !(sum as u16)
+    }
+}
+
+/// This error is thrown by the `View::finalize` function that [binary_layout!](crate::binary_layout!) generates
+/// for layouts with length or checksum fields when a derived value can't be stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FinalizeError {
+    /// The length is too large for the type of the length field.
+    LengthOutOfRange {
+        /// Name of the length field
+        field: &'static str,
+        /// The length that should have been stored
+        length: usize,
+    },
+}
+
+impl fmt::Display for FinalizeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FinalizeError::LengthOutOfRange { field, length } => write!(
+                fmt,
+                "The length {} is out of range for the length field `{}`",
+                length, field
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FinalizeError {}
+
+/// Internal function. Don't use this in user code.
+/// Returns the number of bytes of the field's data, i.e. until the end of the storage for open ended fields.
+pub fn field_data_len<F: Field + FieldKindInfo>(storage: &[u8]) -> usize {
+    F::SIZE.unwrap_or_else(|| {
+        let offset = F::offset_in(storage).unwrap_or(F::OFFSET);
+        storage.len().saturating_sub(offset)
+    })
+}
+
+/// Internal function. Don't use this in user code.
+/// Zeroes the checksum field and computes the checksum over the data of the layout.
+pub fn compute_checksum<C: Checksum>(
+    layout: &LayoutInfo,
+    field: &FieldInfo,
+    storage: &mut [u8],
+) -> C::Output {
+    let size = field.size.expect("Checksum fields must have a fixed size");
+    let offset = field.offset
+        + layout
+            .shift_in(field.offset, storage)
+            .expect("The storage is too short for the fields before the checksum field");
+    storage[offset..offset + size].fill(0);
+    let end = layout.size.unwrap_or(storage.len());
+    C::checksum(&storage[..end])
+}
+
+/// Internal trait. Don't use this in user code.
+/// Describes the bytes covered by a checksum field that is declared with a range, like `crc: u32 checksum(Crc32, header..=payload)`.
+pub trait ChecksumRange {
+    /// Offset of the first covered byte
+    const START: usize;
+    /// Offset after the last covered byte, or `None` if the range reaches until the end of the storage
+    const END: Option<usize>;
+
+    /// The covered bytes in the given storage. This differs from `START..END` if there are
+    /// [LengthPrefixed](crate::LengthPrefixed) or varint fields before the covered fields.
+    fn range_in(storage: &[u8]) -> Range<usize> {
+        Self::START..Self::END.unwrap_or(storage.len())
+    }
+}
+
+/// Internal type. Don't use this in user code.
+/// The range from the start of the `First` field to the end of the `Last` field.
+pub struct FieldRange<First, Last> {
+    _p1: PhantomData<First>,
+    _p2: PhantomData<Last>,
+}
+
+impl<First: Field + FieldKindInfo, Last: Field + FieldKindInfo> ChecksumRange
+    for FieldRange<First, Last>
+{
+    const START: usize = First::OFFSET;
+    const END: Option<usize> = match Last::SIZE {
+        Some(size) => Some(Last::OFFSET + size),
+        None => None,
+    };
+
+    fn range_in(storage: &[u8]) -> Range<usize> {
+        let start = First::offset_in(storage).unwrap_or(First::OFFSET);
+        let end = match (Last::SIZE, Last::offset_in(storage)) {
+            (Some(size), Some(offset)) => offset + size,
+            (Some(size), None) => Last::OFFSET + size,
+            (None, _) => storage.len(),
+        };
+        start..end
+    }
+}
+
+/// Internal type. Don't use this in user code.
+/// The range `START..END` of bytes of the layout.
+pub struct ByteRange<const START: usize, const END: usize>;
+
+impl<const START: usize, const END: usize> ChecksumRange for ByteRange<START, END> {
+    const START: usize = START;
+    const END: Option<usize> = Some(END);
+}
+
+/// Internal function. Don't use this in user code.
+/// Fails compilation if the range covered by a checksum field is empty, reaches beyond a layout with a static size
+/// or contains the checksum field itself.
+pub const fn check_checksum_range(
+    field_offset: usize,
+    field_size: Option<usize>,
+    start: usize,
+    end: Option<usize>,
+    layout_size: Option<usize>,
+) {
+    let field_end = match field_size {
+        Some(size) => field_offset + size,
+        None => panic!("Checksum fields must have a fixed size"),
+    };
+    if let Some(end) = end {
+        assert!(
+            start < end,
+            "The range covered by a checksum field must not be empty"
+        );
+        if let Some(layout_size) = layout_size {
+            assert!(
+                end <= layout_size,
+                "The range covered by a checksum field must be within the layout"
+            );
+        }
+    }
+    let before_range = field_end <= start;
+    let after_range = match end {
+        Some(end) => field_offset >= end,
+        None => false,
+    };
+    assert!(
+        before_range || after_range,
+        "The range covered by a checksum field must not contain the checksum field"
+    );
+}
+
+/// Internal function. Don't use this in user code.
+/// Computes the checksum over the bytes covered by a checksum field that is declared with a range.
+pub fn compute_checksum_over<C: Checksum, R: ChecksumRange>(storage: &[u8]) -> C::Output {
+    C::checksum(&storage[R::range_in(storage)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Checksum, FinalizeError, InternetChecksum};
+    use crate::prelude::*;
+
+    pub struct Sum;
+    impl Checksum for Sum {
+        type Output = u8;
+
+        fn checksum(data: &[u8]) -> u8 {
+            data.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+        }
+    }
+
+    binary_layout!(packet, BigEndian, {
+        kind: u8,
+        length: u8 length_of(payload),
+        checksum: u16 checksum(InternetChecksum),
+        payload: [u8],
+    });
+
+    binary_layout!(record, LittleEndian, {
+        sum: u8 checksum(Sum),
+        data_length: u16 length_of(data),
+        data: [u8; 3],
+        inner_sum: u8 checksum(crate::finalize::tests::Sum),
+    });
+
+    #[test]
+    fn internet_checksum() {
+        // Example from RFC 1071
+        assert_eq!(
+            !0xddf2,
+            InternetChecksum::checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7])
+        );
+        assert_eq!(0xffff, InternetChecksum::checksum(&[]));
+        assert_eq!(!0x0100, InternetChecksum::checksum(&[1]));
+        assert_eq!(0, InternetChecksum::checksum(&[0xff, 0xff]));
+    }
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, packet::length::OFFSET);
+        assert_eq!(2, packet::checksum::OFFSET);
+        assert_eq!(4, packet::payload::OFFSET);
+        assert_eq!("u16", packet::Fields::checksum.type_name);
+        assert_eq!(Some(7), record::SIZE);
+    }
+
+    #[test]
+    fn finalize_open_ended() {
+        let mut view = packet::View::new([0x08, 0xaa, 0xaa, 0xaa, 1, 2, 3]);
+        view.finalize().unwrap();
+        assert_eq!(3, view.length().read());
+        let storage = view.into_storage();
+        assert_eq!(0, InternetChecksum::checksum(&storage));
+        assert_eq!(
+            InternetChecksum::checksum(&[0x08, 3, 0, 0, 1, 2, 3]),
+            u16::from_be_bytes([storage[2], storage[3]])
+        );
+    }
+
+    #[test]
+    fn finalize_sized() {
+        let mut view = record::View::new([0xff, 0xff, 0xff, 1, 2, 3, 0xff, 0xee]);
+        view.finalize().unwrap();
+        // Checksums are computed in the order of declaration, after the lengths
+        assert_eq!(3, view.data_length().read());
+        assert_eq!(8, view.sum().read());
+        assert_eq!(17, view.inner_sum().read());
+        assert_eq!([8, 3, 0, 1, 2, 3, 17, 0xee], view.into_storage());
+    }
+
+    binary_layout!(ranged, BigEndian, {
+        header_sum: u8 checksum(Sum, kind..=length),
+        kind: u8,
+        length: u8 length_of(data),
+        data: [u8; 2],
+        data_sum: u8 checksum(crate::finalize::tests::Sum, 3..5),
+        trailer_sum: u8 checksum(Sum, trailer..=trailer),
+        trailer: [u8],
+    });
+
+    #[test]
+    fn checksum_ranges() {
+        let mut view = ranged::View::new([0xff, 7, 0, 10, 20, 0xff, 0, 1, 2, 3]);
+        assert_eq!(7, view.compute_header_sum());
+        assert_eq!(30, view.compute_data_sum());
+        assert_eq!(6, view.compute_trailer_sum());
+        assert!(!view.verify_data_sum());
+        view.update_data_sum();
+        assert!(view.verify_data_sum());
+        assert_eq!(30, view.data_sum().read());
+
+        // The checksums cover the lengths that finalize() fills first
+        view.finalize().unwrap();
+        assert_eq!([9, 7, 2, 10, 20, 30, 6, 1, 2, 3], view.into_storage());
+    }
+
+    #[test]
+    fn length_out_of_range() {
+        let mut view = packet::View::new([0; 260]);
+        assert_eq!(
+            Err(FinalizeError::LengthOutOfRange {
+                field: "length",
+                length: 256
+            }),
+            view.finalize()
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use core::ops::Range;
+
+use crate::{Data, FieldInfo, LayoutInfo};
+
+/// Implement this for storage backends that cache writes and need an explicit synchronization point to
+/// make them visible elsewhere, e.g. file backed pages or memories attached to a bus. Views over such a storage
+/// get `View::flush()` to flush the bytes of the layout and `View::flush_fields(fields)` to only flush the bytes
+/// of some fields, e.g. the ones that were written.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FlushableStorage};
+/// use core::convert::Infallible;
+/// use core::ops::Range;
+///
+/// /// Storage that remembers which bytes were flushed
+/// struct Page {
+///     data: Vec<u8>,
+///     flushed: Vec<Range<usize>>,
+/// }
+///
+/// impl AsRef<[u8]> for Page {
+///     fn as_ref(&self) -> &[u8] {
+///         &self.data
+///     }
+/// }
+/// impl AsMut<[u8]> for Page {
+///     fn as_mut(&mut self) -> &mut [u8] {
+///         &mut self.data
+///     }
+/// }
+/// impl FlushableStorage for Page {
+///     type FlushError = Infallible;
+///
+///     fn flush_range(&mut self, range: Range<usize>) -> Result<(), Infallible> {
+///         self.flushed.push(range);
+///         Ok(())
+///     }
+/// }
+///
+/// binary_layout!(record, LittleEndian, {
+///   id: u32,
+///   length: u16,
+///   flags: u8,
+/// });
+///
+/// let mut view = record::View::new(Page { data: vec![0; 16], flushed: vec![] });
+/// view.length_mut().write(10);
+/// view.flags_mut().write(1);
+/// view.flush_fields(&[record::FieldId::length, record::FieldId::flags]).unwrap();
+/// view.flush().unwrap();
+/// assert_eq!(vec![4..6, 6..7, 0..7], view.into_storage().flushed);
+/// ```
+pub trait FlushableStorage: AsRef<[u8]> {
+    /// Error type for failed flushes
+    type FlushError;
+
+    /// Make the writes to the given byte range of the storage visible to the backend.
+    /// The range is always within the bounds of the storage when called from a view.
+    fn flush_range(&mut self, range: Range<usize>) -> Result<(), Self::FlushError>;
+
+    /// Make all writes to the storage visible to the backend
+    fn flush(&mut self) -> Result<(), Self::FlushError> {
+        let len = self.as_ref().len();
+        self.flush_range(0..len)
+    }
+}
+
+impl<S: FlushableStorage + ?Sized> FlushableStorage for &mut S {
+    type FlushError = S::FlushError;
+
+    #[inline(always)]
+    fn flush_range(&mut self, range: Range<usize>) -> Result<(), Self::FlushError> {
+        (**self).flush_range(range)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> Result<(), Self::FlushError> {
+        (**self).flush()
+    }
+}
+
+impl<S: FlushableStorage> FlushableStorage for Data<S> {
+    type FlushError = S::FlushError;
+
+    /// Flushes the range relative to the subregion of this [Data] instance
+    fn flush_range(&mut self, range: Range<usize>) -> Result<(), Self::FlushError> {
+        let region = self.region();
+        assert!(
+            range.start <= range.end && range.end <= region.len(),
+            "Tried to flush range {:?} of a Data instance of length {}",
+            range,
+            region.len(),
+        );
+        self.inner_mut()
+            .flush_range(region.start + range.start..region.start + range.end)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::FlushError> {
+        let region = self.region();
+        self.inner_mut().flush_range(region)
+    }
+}
+
+/// Range of the bytes in the storage that belong to a field, i.e. until the end of the storage for open ended fields,
+/// limited to the size of the storage
+fn field_range(layout: &LayoutInfo, field: &FieldInfo, storage: &[u8]) -> Range<usize> {
+    let storage_len = storage.len();
+    // Fields after LengthPrefixed or varint fields the storage is too short for aren't stored at all
+    let start = match layout.shift_in(field.offset, storage) {
+        Ok(shift) => field.offset + shift,
+        Err(_) => return storage_len..storage_len,
+    };
+    let end = match field.size {
+        Some(size) => start + size,
+        None => layout
+            .field_range_in(field, storage)
+            .map_or(storage_len, |range| range.end),
+    };
+    start.min(storage_len)..end.min(storage_len)
+}
+
+/// Internal function. Don't use this in user code.
+/// Flushes the bytes of the storage that belong to the layout.
+pub fn flush_layout<S: FlushableStorage>(
+    layout: &LayoutInfo,
+    storage: &mut S,
+) -> Result<(), S::FlushError> {
+    let len = storage.as_ref().len();
+    let end = layout.size.map_or(len, |size| size.min(len));
+    storage.flush_range(0..end)
+}
+
+/// Internal function. Don't use this in user code.
+/// Flushes the bytes of the storage that belong to the given fields.
+pub fn flush_fields<'a, S: FlushableStorage>(
+    layout: &LayoutInfo,
+    fields: impl Iterator<Item = &'a FieldInfo>,
+    storage: &mut S,
+) -> Result<(), S::FlushError> {
+    for field in fields {
+        let range = field_range(layout, field, storage.as_ref());
+        if !range.is_empty() {
+            storage.flush_range(range)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+
+    use super::FlushableStorage;
+    use crate::prelude::*;
+    use crate::Data;
+
+    /// Storage that marks the bytes that were flushed, failing if a flushed byte is 0xff
+    pub struct MarkingStorage {
+        data: [u8; 8],
+        flushed: [bool; 8],
+    }
+
+    impl MarkingStorage {
+        fn new(data: [u8; 8]) -> Self {
+            Self {
+                data,
+                flushed: [false; 8],
+            }
+        }
+    }
+
+    impl AsRef<[u8]> for MarkingStorage {
+        fn as_ref(&self) -> &[u8] {
+            &self.data
+        }
+    }
+
+    impl AsMut<[u8]> for MarkingStorage {
+        fn as_mut(&mut self) -> &mut [u8] {
+            &mut self.data
+        }
+    }
+
+    impl FlushableStorage for MarkingStorage {
+        type FlushError = usize;
+
+        fn flush_range(&mut self, range: Range<usize>) -> Result<(), usize> {
+            for index in range {
+                if self.data[index] == 0xff {
+                    return Err(index);
+                }
+                self.flushed[index] = true;
+            }
+            Ok(())
+        }
+    }
+
+    binary_layout!(sized, BigEndian, {
+        first: u8,
+        second: u16,
+        third: u8,
+    });
+
+    binary_layout!(open_ended, BigEndian, {
+        length: u16,
+        payload: [u8],
+    });
+
+    #[test]
+    fn flush_sized_layout() {
+        let mut view = sized::View::new(MarkingStorage::new([0; 8]));
+        view.flush().unwrap();
+        assert_eq!(
+            [true, true, true, true, false, false, false, false],
+            view.into_storage().flushed
+        );
+    }
+
+    #[test]
+    fn flush_open_ended_layout() {
+        let mut view = open_ended::View::new(MarkingStorage::new([0; 8]));
+        view.flush().unwrap();
+        assert_eq!([true; 8], view.into_storage().flushed);
+    }
+
+    #[test]
+    fn flush_fields() {
+        let mut view = sized::View::new(MarkingStorage::new([0; 8]));
+        view.second_mut().write(5);
+        view.flush_fields(&[sized::FieldId::second]).unwrap();
+        assert_eq!(
+            [false, true, true, false, false, false, false, false],
+            view.into_storage().flushed
+        );
+
+        let mut view = open_ended::View::new(MarkingStorage::new([0; 8]));
+        view.flush_fields(&[open_ended::FieldId::payload]).unwrap();
+        assert_eq!(
+            [false, false, true, true, true, true, true, true],
+            view.into_storage().flushed
+        );
+    }
+
+    #[test]
+    fn flush_borrowed_storage() {
+        let mut storage = MarkingStorage::new([0; 8]);
+        let mut view = sized::View::new(&mut storage);
+        view.flush_fields(&[sized::FieldId::third]).unwrap();
+        assert_eq!(
+            [false, false, false, true, false, false, false, false],
+            storage.flushed
+        );
+    }
+
+    #[test]
+    fn flush_at_offset() {
+        let mut view = sized::View::new_at(MarkingStorage::new([0; 8]), 3).unwrap();
+        view.flush().unwrap();
+        view.flush_fields(&[sized::FieldId::first]).unwrap();
+        assert_eq!(
+            [false, false, false, true, true, true, true, false],
+            view.into_storage().into_inner().flushed
+        );
+
+        let mut data = Data::from(MarkingStorage::new([0; 8])).into_subregion(2..5);
+        data.flush().unwrap();
+        assert_eq!(
+            [false, false, true, true, true, false, false, false],
+            data.into_inner().flushed
+        );
+    }
+
+    #[test]
+    fn storage_shorter_than_layout() {
+        let mut data = Data::from(MarkingStorage::new([0; 8])).into_subregion(6..);
+        let mut view = sized::View::new(&mut data);
+        view.flush().unwrap();
+        view.flush_fields(&[sized::FieldId::third]).unwrap();
+        assert_eq!(
+            [false, false, false, false, false, false, true, true],
+            data.into_inner().flushed
+        );
+    }
+
+    #[test]
+    fn flush_error() {
+        let mut view = sized::View::new(MarkingStorage::new([0, 0, 0xff, 0, 0, 0, 0, 0]));
+        assert_eq!(Err(2), view.flush());
+        assert_eq!(Ok(()), view.flush_fields(&[sized::FieldId::third]));
+    }
+}
@@ -0,0 +1,269 @@
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::fields::primitive::NestedViewInfo;
+use crate::layout_info::FieldKindInfo;
+use crate::{Endianness, Field, FieldCopyAccess, LayoutInfo, PrimitiveField, RelativeField};
+
+/// Internal type. Don't use this in user code.
+/// The `fuzz_round_trip` function generated by [binary_layout!](crate::binary_layout!) calls
+/// `(&&&RoundTripField::<F>::default()).round_trip(...)` for each field, which uses the first of
+/// [RoundTripNestedField], [RoundTripValueField] and [RoundTripBytesField] that is implemented for the field.
+pub struct RoundTripField<F>(PhantomData<F>);
+
+impl<F> Default for RoundTripField<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Re-encodes nested layouts field by field.
+pub trait RoundTripNestedField {
+    /// Read the field from `src` and write it to `dst`
+    fn round_trip(&self, field_name: &str, src: &[u8], dst: &mut [u8]);
+}
+
+impl<N: NestedViewInfo + RoundTripNested, E: Endianness, const OFFSET_: usize> RoundTripNestedField
+    for &&RoundTripField<PrimitiveField<N, E, OFFSET_>>
+{
+    fn round_trip(&self, _field_name: &str, src: &[u8], dst: &mut [u8]) {
+        if let Some(range) = field_range::<PrimitiveField<N, E, OFFSET_>>(src) {
+            N::round_trip(&src[range.clone()], &mut dst[range]);
+        }
+    }
+}
+
+impl<L, N: NestedViewInfo + RoundTripNested, E: Endianness, const OFFSET_: usize>
+    RoundTripNestedField for &&RoundTripField<RelativeField<L, PrimitiveField<N, E, OFFSET_>>>
+where
+    RelativeField<L, PrimitiveField<N, E, OFFSET_>>: Field + FieldKindInfo,
+{
+    fn round_trip(&self, _field_name: &str, src: &[u8], dst: &mut [u8]) {
+        let range = field_range::<RelativeField<L, PrimitiveField<N, E, OFFSET_>>>(src);
+        // The LengthPrefixed or varint fields before the nested layout may leave too little room for it
+        if let Some(range) = range.filter(|range| range.len() >= N::LAYOUT.min_size()) {
+            N::round_trip(&src[range.clone()], &mut dst[range]);
+        }
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Re-encodes fields by reading their value and writing it back.
+pub trait RoundTripValueField {
+    /// Read the field from `src` and write it to `dst`
+    fn round_trip(&self, field_name: &str, src: &[u8], dst: &mut [u8]);
+}
+
+impl<F: FieldCopyAccess + FieldKindInfo> RoundTripValueField for &RoundTripField<F> {
+    fn round_trip(&self, field_name: &str, src: &[u8], dst: &mut [u8]) {
+        // The LengthPrefixed or varint fields before the field may not leave room for it
+        if F::offset_in(src).is_none() || F::offset_in(dst).is_none() {
+            return;
+        }
+        // Fields that can't be read are invalid input that a parser would reject, so we keep their bytes.
+        match F::try_read(src) {
+            Ok(value) => {
+                if F::try_write(dst, value).is_err() {
+                    panic!(
+                        "Failed to write the value read from field `{}` back to the field",
+                        field_name
+                    );
+                }
+            }
+            Err(_) => copy_field::<F>(src, dst),
+        }
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Re-encodes all other fields by copying their bytes.
+pub trait RoundTripBytesField {
+    /// Read the field from `src` and write it to `dst`
+    fn round_trip(&self, field_name: &str, src: &[u8], dst: &mut [u8]);
+}
+
+impl<F: Field + FieldKindInfo> RoundTripBytesField for RoundTripField<F> {
+    fn round_trip(&self, _field_name: &str, src: &[u8], dst: &mut [u8]) {
+        copy_field::<F>(src, dst);
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the `NestedView` types generated by [binary_layout!](crate::binary_layout!).
+pub trait RoundTripNested {
+    /// Read all fields of the layout from `src` and write them to `dst`
+    fn round_trip(src: &[u8], dst: &mut [u8]);
+}
+
+/// Range of the field in the data, or `None` if it isn't stored, e.g. for optional fields
+fn field_range<F: Field + FieldKindInfo>(data: &[u8]) -> Option<Range<usize>> {
+    let offset = F::offset_in(data)?;
+    let end = match F::SIZE {
+        Some(size) => offset + size,
+        None => data.len(),
+    };
+    (offset <= end && end <= data.len()).then(|| offset..end)
+}
+
+fn copy_field<F: Field + FieldKindInfo>(src: &[u8], dst: &mut [u8]) {
+    if let Some(range) = field_range::<F>(src) {
+        dst[range.clone()].copy_from_slice(&src[range]);
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Implements the `fuzz_round_trip` function generated by [binary_layout!](crate::binary_layout!).
+pub fn check_round_trip(layout: &LayoutInfo, data: &[u8], round_trip: fn(&[u8], &mut [u8])) {
+    if data.len() < layout.min_size() {
+        return;
+    }
+    let mut encoded = vec![0; data.len()];
+    round_trip(data, &mut encoded);
+    let diffs = layout.diff(data, &encoded);
+    if !diffs.is_empty() {
+        let mut message = format!(
+            "Re-encoding the field values of `{}` changed {} fields:",
+            layout.name,
+            diffs.len()
+        );
+        for diff in diffs {
+            message += &format!(
+                "\n  {}: read = {}, written = {}",
+                diff.path, diff.left, diff.right
+            );
+        }
+        panic!("{}", message);
+    }
+}
+
+/// Number of pseudo random buffers the generated round trip tests check
+const ROUND_TRIP_TEST_ITERATIONS: usize = 100;
+
+/// Internal function. Don't use this in user code.
+/// Implements the tests generated by [binary_layout!](crate::binary_layout!) with the `round_trip_tests` flag.
+/// Checks the round trip for pseudo random data. Open ended layouts get data of different lengths.
+pub fn check_random_round_trips(layout: &LayoutInfo, fuzz_round_trip: fn(&[u8])) {
+    // xorshift64, which is good enough for test data and keeps the generated tests deterministic
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    for iteration in 0..ROUND_TRIP_TEST_ITERATIONS {
+        let len = layout
+            .size
+            .unwrap_or_else(|| layout.min_size() + iteration % 16);
+        let data: Vec<u8> = (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 32) as u8
+            })
+            .collect();
+        fuzz_round_trip(&data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+    use crate::prelude::*;
+    use crate::LayoutAs;
+
+    /// Reads any value, but always writes `0`, so it breaks the round trip.
+    #[derive(Debug)]
+    pub struct Lossy;
+    impl LayoutAs<u16> for Lossy {
+        type ReadError = ();
+        type WriteError = ();
+
+        fn try_read(_v: u16) -> Result<Self, ()> {
+            Ok(Lossy)
+        }
+
+        fn try_write(_v: Self) -> Result<u16, ()> {
+            Ok(0)
+        }
+    }
+
+    binary_layout!(inner, LittleEndian, {
+        lossy: Lossy as u16,
+        tail: [u8],
+    });
+    binary_layout!(packet, BigEndian, {
+        flag: bool as u8,
+        character: char as u32,
+        non_zero: core::num::NonZeroU32,
+        float: f32,
+        unit: (),
+        mac: [u8; 6],
+        samples: [u16; 3],
+        payload: [u8],
+    });
+    binary_layout!(outer, BigEndian, {
+        seq_no: u32,
+        nested: inner::NestedView,
+    });
+    binary_layout!(tested, LittleEndian, round_trip_tests, {
+        flag: bool as u8,
+        value: i64,
+        nested: packet::NestedView,
+    });
+    binary_layout!(message, BigEndian, round_trip_tests, {
+        name: crate::LengthPrefixed<u8>,
+        delta: crate::ZigZag<i32>,
+        flag: bool as u8,
+        nested: packet::NestedView,
+    });
+
+    #[test]
+    fn random_data_round_trips() {
+        for seed in 0..100 {
+            let mut data = vec![0; 32];
+            StdRng::seed_from_u64(seed).fill_bytes(&mut data);
+            packet::fuzz_round_trip(&data);
+        }
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields_round_trip() {
+        let mut data = vec![2, b'a', b'b', 0x81, 0x01, 1];
+        data.resize(data.len() + packet::payload::OFFSET, 0);
+        data[6 + packet::flag::OFFSET] = 2;
+        message::fuzz_round_trip(&data);
+        // The length prefix reaches beyond the data, so the fields after it aren't stored
+        data[0] = 200;
+        message::fuzz_round_trip(&data);
+    }
+
+    #[test]
+    fn invalid_fields_are_kept() {
+        let mut data = vec![0; packet::payload::OFFSET];
+        data[packet::flag::OFFSET] = 2;
+        packet::fuzz_round_trip(&data);
+    }
+
+    #[test]
+    fn short_data_is_ignored() {
+        packet::fuzz_round_trip(&[1, 2, 3]);
+        outer::fuzz_round_trip(&[]);
+    }
+
+    #[test]
+    fn lossy_fields_in_nested_layouts_are_reported() {
+        outer::fuzz_round_trip(&[0, 0, 0, 1, 0, 0, 5]);
+        let result = std::panic::catch_unwind(|| outer::fuzz_round_trip(&[0, 0, 0, 1, 1, 0, 5]));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(
+            "Re-encoding the field values of `outer` changed 1 fields:\n  nested.lossy: read = 1, written = 0",
+            message
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Re-encoding the field values of `outer` changed 1 fields:\n  nested.lossy"
+    )]
+    fn random_round_trips_detect_lossy_fields() {
+        super::check_random_round_trips(&outer::LAYOUT, outer::fuzz_round_trip);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Field, FieldInfo, FieldView};
+
+/// Type level marker for a field name. Use [field_name!](crate::field_name!) to get the marker for a name.
+///
+/// Layouts defined in different places don't share any types, but they share the markers of their field names,
+/// which allows generic code over all layouts having a field with a certain name, see [HasField].
+pub struct FieldName<const HASH: u128>;
+
+/// Expands to the [FieldName] marker type for the given field name, e.g. `field_name!(seq_no)`.
+#[macro_export]
+macro_rules! field_name {
+    ($name: ident) => {
+        $crate::FieldName<{ $crate::internal::field_name_hash(stringify!($name)) }>
+    };
+}
+
+/// Implemented by the `View` of each layout generated by [binary_layout!](crate::binary_layout!) for each of its fields,
+/// with `Name` being the marker [field_name!](crate::field_name!) returns for the field name and `F` being the field.
+/// This allows writing generic code that works on any layout with a field of a certain name, regardless of its offset.
+///
+/// The field is a type parameter instead of an associated type so that layouts can use private types via [LayoutAs](crate::LayoutAs).
+///
+/// # Example
+/// ```
+/// use binary_layout::{field_name, prelude::*, HasField, HasFieldMut};
+///
+/// binary_layout!(request, BigEndian, {
+///   seq_no: u32,
+///   payload: [u8],
+/// });
+/// binary_layout!(response, LittleEndian, {
+///   status: u8,
+///   seq_no: u32,
+/// });
+///
+/// fn next_seq_no<V, F>(view: &V) -> u32
+/// where
+///     V: HasField<field_name!(seq_no), F>,
+///     F: FieldReadExt<HighLevelType = u32>,
+/// {
+///     view.field().read() + 1
+/// }
+///
+/// fn set_seq_no<V, F>(view: &mut V, seq_no: u32)
+/// where
+///     V: HasFieldMut<field_name!(seq_no), F>,
+///     F: FieldWriteExt<HighLevelType = u32>,
+/// {
+///     view.field_mut().write(seq_no);
+/// }
+///
+/// let mut request = request::View::new(vec![0, 0, 0, 5, 1, 2]);
+/// let mut response = response::View::new(vec![0; 5]);
+/// assert_eq!(6, next_seq_no(&request));
+/// set_seq_no(&mut response, next_seq_no(&request));
+/// assert_eq!(6, response.seq_no().read());
+/// set_seq_no(&mut request, 8);
+/// assert_eq!(8, request.seq_no().read());
+/// ```
+pub trait HasField<Name, F: Field> {
+    /// Return a [FieldView] with read access to the field
+    fn field(&self) -> FieldView<&[u8], F>;
+}
+
+/// Like [HasField], but for views with write access, see [HasField] for an example.
+pub trait HasFieldMut<Name, F: Field>: HasField<Name, F> {
+    /// Return a [FieldView] with write access to the field
+    fn field_mut(&mut self) -> FieldView<&mut [u8], F>;
+}
+
+/// Implemented by the `NestedView` and the `View` of each layout generated by [binary_layout!](crate::binary_layout!) for each of its fields,
+/// with `Name` being the marker [field_name!](crate::field_name!) returns for the field name. This describes the field at the type level,
+/// so generic code can report which field failed, e.g. in error messages or logs, without passing its name around as a string.
+///
+/// The field types themselves don't know their name since fields of the same type at the same offset share the same type,
+/// even across layouts. Like for [HasField], the field is a type parameter so that layouts can use private types via [LayoutAs](crate::LayoutAs).
+///
+/// # Example
+/// ```
+/// use binary_layout::{field_name, prelude::*, FieldMetadata};
+///
+/// binary_layout!(request, BigEndian, {
+///   kind: u8,
+///   active: bool,
+/// });
+///
+/// fn read_checked<L, F>(data: &[u8]) -> Result<bool, String>
+/// where
+///     L: FieldMetadata<field_name!(active), F>,
+///     F: FieldCopyAccess<HighLevelType = bool>,
+///     F::ReadError: std::fmt::Display,
+/// {
+///     F::try_read(data).map_err(|error| {
+///         format!("Field {} at offset {} is invalid: {}", L::FIELD_NAME, L::FIELD_INFO.offset, error)
+///     })
+/// }
+///
+/// assert_eq!(Ok(true), read_checked::<request::NestedView, _>(&[0, 1]));
+/// assert!(read_checked::<request::NestedView, _>(&[0, 2]).unwrap_err().starts_with("Field active at offset 1 is invalid"));
+/// ```
+pub trait FieldMetadata<Name, F: Field> {
+    /// Name of the field
+    const FIELD_NAME: &'static str;
+
+    /// Runtime description of the field, the same as the entry in the `Fields` struct of the layout
+    const FIELD_INFO: &'static FieldInfo;
+}
+
+/// Internal function. Don't use this in user code.
+/// Hashes a field name to the parameter of its [FieldName] marker, using 128 bit FNV-1a.
+pub const fn field_name_hash(name: &str) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+    let bytes = name.as_bytes();
+    let mut hash = OFFSET_BASIS;
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u128;
+        hash = hash.wrapping_mul(PRIME);
+        index += 1;
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::field_name_hash;
+    use crate::prelude::*;
+    use crate::{FieldMetadata, HasField, HasFieldMut};
+
+    binary_layout!(first, BigEndian, {
+        header: u8,
+        value: u16,
+    });
+    binary_layout!(second, LittleEndian, {
+        value: u16,
+        tail: [u8],
+    });
+
+    fn increment<V, F>(view: &mut V)
+    where
+        V: HasFieldMut<field_name!(value), F>,
+        F: FieldReadExt<HighLevelType = u16> + FieldWriteExt<HighLevelType = u16>,
+    {
+        let value = view.field().read();
+        view.field_mut().write(value + 1);
+    }
+
+    #[test]
+    fn hashes_differ() {
+        assert_ne!(field_name_hash("value"), field_name_hash("values"));
+        assert_ne!(field_name_hash("ab"), field_name_hash("ba"));
+        assert_ne!(field_name_hash(""), field_name_hash("a"));
+    }
+
+    #[test]
+    fn generic_access() {
+        let mut first_data = [1, 0, 2];
+        let mut second_data = [2, 0, 3];
+        increment(&mut first::View::new(&mut first_data[..]));
+        increment(&mut second::View::new(&mut second_data[..]));
+        assert_eq!([1, 0, 3], first_data);
+        assert_eq!([3, 0, 3], second_data);
+    }
+
+    #[test]
+    fn field_types() {
+        let data = [1, 0, 2];
+        let view = first::View::new(&data[..]);
+        assert_eq!(1, HasField::<field_name!(header), _>::field(&view).read());
+        assert_eq!(2, HasField::<field_name!(value), _>::field(&view).read());
+    }
+
+    fn describe<V, F>(view: &V) -> (&'static str, usize, u16)
+    where
+        V: HasField<field_name!(value), F> + FieldMetadata<field_name!(value), F>,
+        F: FieldReadExt<HighLevelType = u16>,
+    {
+        (V::FIELD_NAME, V::FIELD_INFO.offset, view.field().read())
+    }
+
+    #[test]
+    fn field_metadata() {
+        assert_eq!(("value", 1, 2), describe(&first::View::new([1, 0, 2])));
+        assert_eq!(("value", 0, 3), describe(&second::View::new([3, 0])));
+        assert_eq!(
+            "header",
+            <first::NestedView as FieldMetadata<field_name!(header), _>>::FIELD_NAME
+        );
+        assert_eq!(
+            &second::Fields::tail,
+            <second::NestedView as FieldMetadata<field_name!(tail), _>>::FIELD_INFO
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use core::fmt;
+
+use crate::{FieldInfo, FieldKind, LayoutInfo};
+
+/// Number of bytes shown per line of a [HexDump]
+const BYTES_PER_LINE: usize = 16;
+
+/// Label of bytes that don't belong to any field, e.g. padding of aligned layouts or bytes skipped by explicit offsets
+const UNUSED_LABEL: &str = "(unused)";
+
+/// A hexdump of the data of a layout in which each line is labeled with the field its bytes belong to, see [LayoutInfo::hexdump].
+#[derive(Debug, Clone, Copy)]
+pub struct HexDump<'a> {
+    layout: &'a LayoutInfo,
+    data: &'a [u8],
+}
+
+impl LayoutInfo {
+    /// Return a hexdump of the data of this layout, which can be printed with `{}`. Each line shows the offset of its bytes,
+    /// up to 16 bytes and the field they belong to. Fields of nested layouts are shown with their path, e.g. `header.checksum`,
+    /// and bit fields that share their bytes are listed together. Bytes that don't belong to any field are labeled `(unused)`.
+    ///
+    /// The dump ends at the end of the layout in the data, or at the end of the data if it's too short for the layout.
+    /// Views generated by [binary_layout!](crate::binary_layout!) offer the same with `View::hexdump()`.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   version: u8 bits(4),
+    ///   flags: u8 bits(4),
+    ///   length: u16,
+    ///   payload: [u8],
+    /// });
+    ///
+    /// let view = my_layout::View::new([0x12, 0, 3, 0xaa, 0xbb, 0xcc]);
+    /// println!("{}", view.hexdump());
+    /// // 0000  12                                               version, flags
+    /// // 0001  00 03                                            length
+    /// // 0003  aa bb cc                                         payload
+    /// ```
+    pub fn hexdump<'a>(&'a self, data: &'a [u8]) -> HexDump<'a> {
+        HexDump { layout: self, data }
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.layout.size_in(self.data).unwrap_or(self.data.len());
+        dump_layout(f, self.layout, &self.data[..size], 0, None)
+    }
+}
+
+/// Path of a field, which includes the names of the fields of the layouts it is nested in
+struct Path<'a> {
+    parent: Option<&'a Path<'a>>,
+    name: &'static str,
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(parent) = self.parent {
+            write!(f, "{}.", parent)?;
+        }
+        f.write_str(self.name)
+    }
+}
+
+/// Label of a line, i.e. the paths of the fields sharing its bytes
+struct Label<'a> {
+    parent: Option<&'a Path<'a>>,
+    fields: &'static [FieldInfo],
+}
+
+impl fmt::Display for Label<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            let path = Path {
+                parent: self.parent,
+                name: field.name,
+            };
+            write!(f, "{}", path)?;
+        }
+        Ok(())
+    }
+}
+
+fn dump_layout(
+    f: &mut fmt::Formatter<'_>,
+    layout: &LayoutInfo,
+    data: &[u8],
+    base: usize,
+    parent: Option<&Path<'_>>,
+) -> fmt::Result {
+    let mut pos = 0;
+    let mut index = 0;
+    while let Some(field) = layout.fields.get(index) {
+        // Optional fields are at the end, so none of the following fields are stored either
+        let range = match layout.field_range_in(field, data) {
+            Some(range) => range,
+            None => {
+                let start = layout
+                    .shift_in(field.offset, data)
+                    .ok()
+                    .map(|shift| field.offset + shift)
+                    .filter(|start| *start < data.len());
+                if let Some(start) = start {
+                    // The data ends in the middle of the field
+                    if start > pos {
+                        dump_bytes(f, base + pos, &data[pos..start], UNUSED_LABEL)?;
+                    }
+                    let path = Path {
+                        parent,
+                        name: field.name,
+                    };
+                    let label = format_args!("{} (truncated)", path);
+                    dump_bytes(f, base + start, &data[start..], label)?;
+                }
+                return Ok(());
+            }
+        };
+        let start = range.start;
+        if start > pos {
+            dump_bytes(f, base + pos, &data[pos..start], UNUSED_LABEL)?;
+        }
+        let mut end = range.end;
+        // Bit fields share their bytes with the bit fields after them
+        let mut group_end = index + 1;
+        while let Some(next) = layout.fields.get(group_end) {
+            match layout.field_range_in(next, data) {
+                Some(next_range) if next_range.start < end => end = end.max(next_range.end),
+                _ => break,
+            }
+            group_end += 1;
+        }
+        match field.kind {
+            FieldKind::Nested(nested) if group_end == index + 1 => {
+                let path = Path {
+                    parent,
+                    name: field.name,
+                };
+                dump_layout(f, nested, &data[start..end], base + start, Some(&path))?;
+            }
+            _ => {
+                let label = Label {
+                    parent,
+                    fields: &layout.fields[index..group_end],
+                };
+                dump_bytes(f, base + start, &data[start..end], label)?;
+            }
+        }
+        pos = end;
+        index = group_end;
+    }
+    if pos < data.len() {
+        dump_bytes(f, base + pos, &data[pos..], UNUSED_LABEL)?;
+    }
+    Ok(())
+}
+
+/// Writes the bytes at the given offset, labeling the first line
+fn dump_bytes(
+    f: &mut fmt::Formatter<'_>,
+    offset: usize,
+    bytes: &[u8],
+    label: impl fmt::Display,
+) -> fmt::Result {
+    if bytes.is_empty() {
+        // Open ended fields can be empty, but should still show up in the dump
+        let width = BYTES_PER_LINE * 3 - 1;
+        return writeln!(f, "{:04x}  {:width$}  {}", offset, "", label, width = width);
+    }
+    for (line, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        write!(f, "{:04x} ", offset + line * BYTES_PER_LINE)?;
+        for byte in chunk {
+            write!(f, " {:02x}", byte)?;
+        }
+        if line == 0 {
+            let padding = (BYTES_PER_LINE - chunk.len()) * 3;
+            write!(f, "{:padding$}  {}", "", label, padding = padding)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(header, LittleEndian, {
+        kind: u8,
+        length: u16,
+    });
+
+    binary_layout!(packet, LittleEndian, {
+        head: header::NestedView,
+        version: u8 bits(3),
+        flags: u8 bits(5),
+        payload: [u8],
+    });
+
+    /// Removes the spaces that pad the bytes column before the labels
+    fn compact(dump: String) -> String {
+        dump.lines()
+            .map(|line| match line.get(..6 + 16 * 3 + 1) {
+                Some(bytes) => format!("{}| {}\n", bytes.trim_end(), &line[bytes.len()..]),
+                None => format!("{}\n", line),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hexdump() {
+        let mut data = vec![1, 0x10, 0, 0xff];
+        data.extend(0..20);
+        let view = packet::View::new(data);
+        assert_eq!(
+            "0000  01| head.kind\n\
+             0001  10 00| head.length\n\
+             0003  ff| version, flags\n\
+             0004  00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f| payload\n\
+             0014  10 11 12 13\n",
+            compact(view.hexdump().to_string())
+        );
+    }
+
+    #[test]
+    fn empty_open_ended_field() {
+        let view = packet::View::new([1, 0, 0, 0]);
+        assert_eq!(
+            "0000  01| head.kind\n\
+             0001  00 00| head.length\n\
+             0003  00| version, flags\n\
+             0004| payload\n",
+            compact(view.hexdump().to_string())
+        );
+    }
+
+    #[test]
+    fn unused_bytes() {
+        binary_layout!(aligned_layout, BigEndian, aligned, {
+            flag: u8,
+            value: u32,
+            tag: u8,
+        });
+        let view = aligned_layout::View::new([1, 0, 0, 0, 0, 0, 0, 2, 3, 0, 0, 0, 0xff]);
+        assert_eq!(
+            "0000  01| flag\n\
+             0001  00 00 00| (unused)\n\
+             0004  00 00 00 02| value\n\
+             0008  03| tag\n\
+             0009  00 00 00| (unused)\n",
+            compact(view.hexdump().to_string())
+        );
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_field() {
+        binary_layout!(message, BigEndian, {
+            kind: u8,
+            name: crate::LengthPrefixed<u8>,
+            crc: u32,
+        });
+        let view = message::View::new([1, 2, b'a', b'b', 0, 0, 0, 7]);
+        assert_eq!(
+            "0000  01| kind\n\
+             0001  02 61 62| name\n\
+             0004  00 00 00 07| crc\n",
+            compact(view.hexdump().to_string())
+        );
+        let view = message::View::new([1, 2, b'a', b'b', 0, 0]);
+        assert_eq!(
+            "0000  01| kind\n0001  02 61 62| name\n0004  00 00| crc (truncated)\n",
+            compact(view.hexdump().to_string())
+        );
+    }
+
+    #[test]
+    fn data_too_short() {
+        let view = header::View::new([1, 2]);
+        assert_eq!(
+            "0000  01| kind\n0001  02| length (truncated)\n",
+            compact(view.hexdump().to_string())
+        );
+    }
+}
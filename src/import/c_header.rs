@@ -0,0 +1,979 @@
+//! Converts packed C struct declarations into [binary_layout!](crate::binary_layout!) definitions.
+//!
+//! Only a constrained subset of C is supported. The converter understands
+//! - `struct name { ... };` and `typedef struct [name] { ... } alias;` definitions, including `__attribute__((...))` annotations,
+//! - `typedef` aliases for supported primitive types,
+//! - members of type `uint8_t`..`uint64_t`, `int8_t`..`int64_t`, the Linux `__u8`..`__u64` and `__s8`..`__s64` types,
+//!   `char`, `short`, `int`, `long long` (with `signed`/`unsigned` modifiers), `float`, `double`, `bool` and `_Bool`,
+//! - byte arrays (`uint8_t data[16]`, `char name[8][4]`) and a flexible byte array member as the last member (`uint8_t payload[]`),
+//! - members of a previously defined struct type, which become nested layouts.
+//!
+//! Preprocessor directives and comments are ignored, as are any top level declarations other than the ones listed above.
+//! The structs are assumed to be packed, i.e. no padding is inserted between members. `int` is assumed to be 32 bits wide
+//! and `short` to be 16 bits wide. Since the width of `long` differs between platforms, it isn't supported.
+//!
+//! # Example
+//! ```
+//! use binary_layout::{import::c_header, EndianKind};
+//!
+//! let header = r#"
+//!     #include <stdint.h>
+//!
+//!     struct __attribute__((packed)) packet_header {
+//!         uint8_t packet_type;
+//!         uint8_t code;
+//!         uint16_t checksum;
+//!         uint8_t payload[];
+//!     };
+//! "#;
+//!
+//! let layouts = c_header::convert(header, EndianKind::Big).unwrap();
+//! assert_eq!(
+//!     "binary_layout!(packet_header, BigEndian, {\n    packet_type: u8,\n    code: u8,\n    checksum: u16,\n    payload: [u8],\n});\n",
+//!     layouts,
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use thiserror::Error;
+
+use super::{endianness_type_name, rust_ident};
+use crate::endianness::EndianKind;
+
+/// The error being thrown when a C header can't be converted into layouts.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CHeaderError {
+    /// The input ended in the middle of a declaration.
+    #[error("Unexpected end of input")]
+    UnexpectedEof,
+    /// The input contained a token that wasn't expected at this position.
+    #[error("Unexpected token `{found}` in line {line}, expected {expected}")]
+    UnexpectedToken {
+        /// The token that was found
+        found: String,
+        /// Description of what was expected instead
+        expected: &'static str,
+        /// Line number (1-based) of the token in the input
+        line: usize,
+    },
+    /// A struct member uses a type that can't be represented.
+    #[error("Member `{member}` has unsupported type `{ty}`")]
+    UnsupportedType {
+        /// Name of the struct member
+        member: String,
+        /// C type of the struct member
+        ty: String,
+    },
+    /// A struct member is a bit-field.
+    #[error("Member `{0}` is a bit-field, which isn't supported")]
+    UnsupportedBitField(String),
+    /// A struct member is an array of elements that aren't bytes.
+    #[error("Member `{member}` is an array of `{ty}`, but only byte arrays are supported")]
+    UnsupportedArray {
+        /// Name of the struct member
+        member: String,
+        /// C type of the array elements
+        ty: String,
+    },
+    /// A flexible array member was followed by other members.
+    #[error("Flexible array member `{0}` must be the last member of the struct")]
+    FlexibleArrayNotLast(String),
+}
+
+/// Parse `header` and return Rust source code containing a [binary_layout!](crate::binary_layout!) invocation
+/// for each struct defined in it, using the given `endianness` for all fields.
+///
+/// The generated code expects `binary_layout::prelude::*` to be in scope.
+pub fn convert(header: &str, endianness: EndianKind) -> Result<String, CHeaderError> {
+    let tokens = tokenize(header);
+    let layouts = Parser::new(&tokens).parse()?;
+
+    let mut result = String::new();
+    for (index, layout) in layouts.iter().enumerate() {
+        if index > 0 {
+            result.push('\n');
+        }
+        write_layout(&mut result, layout, endianness);
+    }
+    Ok(result)
+}
+
+fn write_layout(out: &mut String, layout: &Layout, endianness: EndianKind) {
+    writeln!(
+        out,
+        "binary_layout!({}, {}, {{",
+        rust_ident(&layout.name),
+        endianness_type_name(endianness)
+    )
+    .unwrap();
+    for field in &layout.fields {
+        writeln!(out, "    {}: {},", rust_ident(&field.name), field.ty).unwrap();
+    }
+    out.push_str("});\n");
+}
+
+struct Layout {
+    name: String,
+    fields: Vec<LayoutField>,
+}
+
+struct LayoutField {
+    name: String,
+    ty: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Primitive {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bool,
+}
+
+impl Primitive {
+    fn rust_type(self) -> &'static str {
+        match self {
+            Primitive::U8 => "u8",
+            Primitive::U16 => "u16",
+            Primitive::U32 => "u32",
+            Primitive::U64 => "u64",
+            Primitive::I8 => "i8",
+            Primitive::I16 => "i16",
+            Primitive::I32 => "i32",
+            Primitive::I64 => "i64",
+            Primitive::F32 => "f32",
+            Primitive::F64 => "f64",
+            Primitive::Bool => "bool as u8",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "uint8_t" | "__u8" => Primitive::U8,
+            "uint16_t" | "__u16" => Primitive::U16,
+            "uint32_t" | "__u32" => Primitive::U32,
+            "uint64_t" | "__u64" => Primitive::U64,
+            "int8_t" | "__s8" => Primitive::I8,
+            "int16_t" | "__s16" => Primitive::I16,
+            "int32_t" | "__s32" => Primitive::I32,
+            "int64_t" | "__s64" => Primitive::I64,
+            "float" => Primitive::F32,
+            "double" => Primitive::F64,
+            "bool" | "_Bool" => Primitive::Bool,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum CType {
+    Primitive(Primitive),
+    Struct(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Ident(String),
+    Number(usize),
+    Punct(char),
+    StringLiteral,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match &self.kind {
+            TokenKind::Ident(ident) => ident.clone(),
+            TokenKind::Number(number) => number.to_string(),
+            TokenKind::Punct(punct) => punct.to_string(),
+            TokenKind::StringLiteral => "\"...\"".to_string(),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut at_line_start = true;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            at_line_start = true;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '#' && at_line_start {
+            // Preprocessor directive, skip until the end of the line, respecting line continuations
+            while i < chars.len() && chars[i] != '\n' {
+                if chars[i] == '\\' && chars.get(i + 1) == Some(&'\n') {
+                    line += 1;
+                    i += 1;
+                }
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' {
+            at_line_start = false;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token {
+                kind: TokenKind::StringLiteral,
+                line,
+            });
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            at_line_start = false;
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Ident(chars[start..i].iter().collect()),
+                line,
+            });
+        } else if c.is_ascii_digit() {
+            at_line_start = false;
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let literal = literal.trim_end_matches(['u', 'U', 'l', 'L']);
+            let number = if let Some(hex) = literal
+                .strip_prefix("0x")
+                .or_else(|| literal.strip_prefix("0X"))
+            {
+                usize::from_str_radix(hex, 16).ok()
+            } else if literal.len() > 1 && literal.starts_with('0') {
+                usize::from_str_radix(&literal[1..], 8).ok()
+            } else {
+                literal.parse().ok()
+            };
+            tokens.push(Token {
+                kind: match number {
+                    Some(number) => TokenKind::Number(number),
+                    None => TokenKind::Ident(literal.to_string()),
+                },
+                line,
+            });
+        } else {
+            at_line_start = false;
+            tokens.push(Token {
+                kind: TokenKind::Punct(c),
+                line,
+            });
+            i += 1;
+        }
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    // typedef names for primitive types
+    primitive_aliases: HashMap<String, Primitive>,
+    // struct tags and typedef names for structs, mapping to the name of the generated layout
+    struct_aliases: HashMap<String, String>,
+    layouts: Vec<Layout>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            primitive_aliases: HashMap::new(),
+            struct_aliases: HashMap::new(),
+            layouts: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<Layout>, CHeaderError> {
+        while let Some(token) = self.peek() {
+            match &token.kind {
+                TokenKind::Ident(ident) if ident == "typedef" => {
+                    self.pos += 1;
+                    self.parse_typedef()?;
+                }
+                TokenKind::Ident(ident) if ident == "struct" && self.is_struct_definition() => {
+                    self.pos += 1;
+                    self.parse_struct_definition(false)?;
+                }
+                TokenKind::Ident(ident) if ident == "extern" => {
+                    // `extern "C" {` blocks. The closing brace is skipped below.
+                    self.pos += 1;
+                    if self.peek_kind() == Some(&TokenKind::StringLiteral) {
+                        self.pos += 1;
+                        if self.peek_kind() == Some(&TokenKind::Punct('{')) {
+                            self.pos += 1;
+                        }
+                    } else {
+                        self.skip_statement();
+                    }
+                }
+                TokenKind::Punct('}') | TokenKind::Punct(';') => {
+                    self.pos += 1;
+                }
+                _ => self.skip_statement(),
+            }
+        }
+        Ok(self.layouts)
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_kind(&self) -> Option<&'a TokenKind> {
+        self.peek().map(|token| &token.kind)
+    }
+
+    fn next(&mut self) -> Result<&'a Token, CHeaderError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or(CHeaderError::UnexpectedEof)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn unexpected(token: &Token, expected: &'static str) -> CHeaderError {
+        CHeaderError::UnexpectedToken {
+            found: token.text(),
+            expected,
+            line: token.line,
+        }
+    }
+
+    fn expect_punct(&mut self, punct: char, expected: &'static str) -> Result<(), CHeaderError> {
+        let token = self.next()?;
+        if token.kind == TokenKind::Punct(punct) {
+            Ok(())
+        } else {
+            Err(Self::unexpected(token, expected))
+        }
+    }
+
+    fn expect_ident(&mut self, expected: &'static str) -> Result<&'a str, CHeaderError> {
+        let token = self.next()?;
+        match &token.kind {
+            TokenKind::Ident(ident) => Ok(ident),
+            _ => Err(Self::unexpected(token, expected)),
+        }
+    }
+
+    fn next_is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek_kind(), Some(TokenKind::Ident(found)) if found == ident)
+    }
+
+    /// Skip a statement we don't understand, e.g. a function declaration or an enum definition.
+    fn skip_statement(&mut self) {
+        let mut depth = 0usize;
+        while let Some(token) = self.peek() {
+            self.pos += 1;
+            match token.kind {
+                TokenKind::Punct('{') | TokenKind::Punct('(') | TokenKind::Punct('[') => depth += 1,
+                TokenKind::Punct('}') | TokenKind::Punct(')') | TokenKind::Punct(']') => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 && token.kind == TokenKind::Punct('}') {
+                        // Function definitions don't end with a semicolon
+                        if self.peek_kind() != Some(&TokenKind::Punct(';')) {
+                            return;
+                        }
+                    }
+                }
+                TokenKind::Punct(';') if depth == 0 => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip any `__attribute__((...))` or `__packed` annotations
+    fn skip_attributes(&mut self) -> Result<(), CHeaderError> {
+        loop {
+            if self.next_is_ident("__attribute__") {
+                self.pos += 1;
+                self.expect_punct('(', "`(`")?;
+                let mut depth = 1;
+                while depth > 0 {
+                    match self.next()?.kind {
+                        TokenKind::Punct('(') => depth += 1,
+                        TokenKind::Punct(')') => depth -= 1,
+                        _ => {}
+                    }
+                }
+            } else if self.next_is_ident("__packed") {
+                self.pos += 1;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Called when the next token is `struct`. Returns true if this is a struct definition with a body
+    /// and not just a forward declaration or a variable declaration.
+    fn is_struct_definition(&self) -> bool {
+        let mut pos = self.pos + 1;
+        let mut depth = 0;
+        while let Some(token) = self.tokens.get(pos) {
+            match token.kind {
+                TokenKind::Punct('(') => depth += 1,
+                TokenKind::Punct(')') => depth -= 1,
+                TokenKind::Punct('{') if depth == 0 => return true,
+                TokenKind::Ident(_) => {}
+                _ if depth > 0 => {}
+                _ => return false,
+            }
+            pos += 1;
+        }
+        false
+    }
+
+    fn parse_typedef(&mut self) -> Result<(), CHeaderError> {
+        if self.next_is_ident("struct") {
+            self.pos += 1;
+            if self.is_struct_body_ahead() {
+                let name = self.parse_struct_definition(true)?;
+                // `typedef struct tag {...} alias;`
+                self.skip_attributes()?;
+                let alias = self.expect_ident("typedef name")?.to_string();
+                self.skip_attributes()?;
+                self.expect_punct(';', "`;`")?;
+                self.rename_layout(&name, alias);
+            } else {
+                // `typedef struct tag alias;`
+                let tag = self.expect_ident("struct name")?;
+                let alias = self.expect_ident("typedef name")?;
+                self.expect_punct(';', "`;`")?;
+                if let Some(layout) = self.struct_aliases.get(tag).cloned() {
+                    self.struct_aliases.insert(alias.to_string(), layout);
+                }
+            }
+            return Ok(());
+        }
+
+        let start = self.pos;
+        match self.parse_type_specifier() {
+            Ok(CType::Primitive(primitive)) => {
+                let alias = self.expect_ident("typedef name")?;
+                if self.peek_kind() == Some(&TokenKind::Punct(';')) {
+                    self.pos += 1;
+                    self.primitive_aliases.insert(alias.to_string(), primitive);
+                    return Ok(());
+                }
+            }
+            Ok(CType::Struct(layout)) => {
+                let alias = self.expect_ident("typedef name")?;
+                if self.peek_kind() == Some(&TokenKind::Punct(';')) {
+                    self.pos += 1;
+                    self.struct_aliases.insert(alias.to_string(), layout);
+                    return Ok(());
+                }
+            }
+            Err(_) => {}
+        }
+        // Some typedef we don't understand, e.g. for a function pointer or enum. Ignore it.
+        self.pos = start;
+        self.skip_statement();
+        Ok(())
+    }
+
+    fn is_struct_body_ahead(&self) -> bool {
+        let mut pos = self.pos;
+        loop {
+            match self.tokens.get(pos).map(|token| &token.kind) {
+                Some(TokenKind::Punct('{')) => return true,
+                Some(TokenKind::Ident(ident)) if ident == "__attribute__" => {
+                    // skip over the attribute arguments
+                    let mut depth = 0;
+                    pos += 1;
+                    while let Some(token) = self.tokens.get(pos) {
+                        match token.kind {
+                            TokenKind::Punct('(') => depth += 1,
+                            TokenKind::Punct(')') => depth -= 1,
+                            _ => {}
+                        }
+                        pos += 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                Some(TokenKind::Ident(_)) => pos += 1,
+                _ => return false,
+            }
+        }
+    }
+
+    /// Parses a struct definition after the `struct` keyword and adds it to the list of layouts.
+    /// Returns the name of the generated layout.
+    /// If `in_typedef` is true, the struct may be anonymous and the trailing semicolon is left to the caller.
+    fn parse_struct_definition(&mut self, in_typedef: bool) -> Result<String, CHeaderError> {
+        self.skip_attributes()?;
+        let tag = match self.peek_kind() {
+            Some(TokenKind::Ident(ident)) => {
+                self.pos += 1;
+                Some(ident.clone())
+            }
+            _ => None,
+        };
+        self.skip_attributes()?;
+        self.expect_punct('{', "`{`")?;
+
+        let mut fields = Vec::new();
+        let mut flexible_array_member: Option<String> = None;
+        while self.peek_kind() != Some(&TokenKind::Punct('}')) {
+            for (name, ty) in self.parse_member()? {
+                if let Some(flexible) = flexible_array_member {
+                    return Err(CHeaderError::FlexibleArrayNotLast(flexible));
+                }
+                if ty == "[u8]" {
+                    flexible_array_member = Some(name.clone());
+                }
+                fields.push(LayoutField { name, ty });
+            }
+        }
+        self.expect_punct('}', "`}`")?;
+
+        let name = tag
+            .clone()
+            .unwrap_or_else(|| format!("anonymous_{}", self.layouts.len()));
+        if let Some(tag) = tag {
+            self.struct_aliases.insert(tag, name.clone());
+        }
+        self.layouts.push(Layout {
+            name: name.clone(),
+            fields,
+        });
+
+        if !in_typedef {
+            self.skip_attributes()?;
+            let token = self.next()?;
+            if token.kind != TokenKind::Punct(';') {
+                return Err(Self::unexpected(
+                    token,
+                    "`;` (variable declarations aren't supported)",
+                ));
+            }
+        }
+        Ok(name)
+    }
+
+    /// After `typedef struct tag {...} alias;`, the layout is named after the typedef alias.
+    fn rename_layout(&mut self, old_name: &str, new_name: String) {
+        if let Some(layout) = self.layouts.iter_mut().rev().find(|l| l.name == old_name) {
+            layout.name = new_name.clone();
+        }
+        for layout in self.struct_aliases.values_mut() {
+            if layout == old_name {
+                *layout = new_name.clone();
+            }
+        }
+        self.struct_aliases
+            .insert(new_name.clone(), new_name.clone());
+        for layout in &mut self.layouts {
+            for field in &mut layout.fields {
+                if field.ty == format!("{}::NestedView", old_name) {
+                    field.ty = format!("{}::NestedView", new_name);
+                }
+            }
+        }
+    }
+
+    /// Parses one member declaration, which can declare multiple members, e.g. `uint8_t a, b[2];`
+    fn parse_member(&mut self) -> Result<Vec<(String, String)>, CHeaderError> {
+        let type_start = self.pos;
+        let ty = self.parse_type_specifier()?;
+        let type_name = self.tokens[type_start..self.pos]
+            .iter()
+            .map(Token::text)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut members = Vec::new();
+        loop {
+            let name = self.expect_ident("member name")?.to_string();
+            let mut dimensions = Vec::new();
+            let mut flexible = false;
+            while self.peek_kind() == Some(&TokenKind::Punct('[')) {
+                self.pos += 1;
+                let token = self.next()?;
+                match token.kind {
+                    TokenKind::Number(number) => {
+                        dimensions.push(number);
+                        self.expect_punct(']', "`]`")?;
+                    }
+                    TokenKind::Punct(']') => flexible = true,
+                    _ => return Err(Self::unexpected(token, "array length")),
+                }
+            }
+            if self.peek_kind() == Some(&TokenKind::Punct(':')) {
+                return Err(CHeaderError::UnsupportedBitField(name));
+            }
+            self.skip_attributes()?;
+
+            let is_array = flexible || !dimensions.is_empty();
+            let rust_type = match &ty {
+                _ if is_array && !matches!(ty, CType::Primitive(Primitive::U8)) => {
+                    return Err(CHeaderError::UnsupportedArray {
+                        member: name,
+                        ty: type_name,
+                    });
+                }
+                _ if flexible => "[u8]".to_string(),
+                _ if is_array => format!("[u8; {}]", dimensions.iter().product::<usize>()),
+                CType::Primitive(primitive) => primitive.rust_type().to_string(),
+                CType::Struct(layout) => format!("{}::NestedView", rust_ident(layout)),
+            };
+            members.push((name, rust_type));
+
+            let token = self.next()?;
+            match token.kind {
+                TokenKind::Punct(',') => continue,
+                TokenKind::Punct(';') => return Ok(members),
+                _ => return Err(Self::unexpected(token, "`,` or `;`")),
+            }
+        }
+    }
+
+    fn parse_type_specifier(&mut self) -> Result<CType, CHeaderError> {
+        let mut signedness = None;
+        let mut words = Vec::new();
+        loop {
+            let token = self.peek().ok_or(CHeaderError::UnexpectedEof)?;
+            let ident = match &token.kind {
+                TokenKind::Ident(ident) => ident.as_str(),
+                _ => break,
+            };
+            match ident {
+                "const" | "volatile" => {}
+                "signed" | "unsigned" => signedness = Some(ident),
+                "char" | "short" | "int" | "long" => words.push(ident),
+                "struct" => {
+                    self.pos += 1;
+                    let tag = self.expect_ident("struct name")?;
+                    return match self.struct_aliases.get(tag) {
+                        Some(layout) => Ok(CType::Struct(layout.clone())),
+                        None => Err(CHeaderError::UnsupportedType {
+                            member: self.peek().map(Token::text).unwrap_or_default(),
+                            ty: format!("struct {}", tag),
+                        }),
+                    };
+                }
+                _ if words.is_empty() && signedness.is_none() => {
+                    self.pos += 1;
+                    if let Some(primitive) = Primitive::from_name(ident)
+                        .or_else(|| self.primitive_aliases.get(ident).copied())
+                    {
+                        return Ok(CType::Primitive(primitive));
+                    }
+                    if let Some(layout) = self.struct_aliases.get(ident) {
+                        return Ok(CType::Struct(layout.clone()));
+                    }
+                    return Err(CHeaderError::UnsupportedType {
+                        member: self.peek().map(Token::text).unwrap_or_default(),
+                        ty: ident.to_string(),
+                    });
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+
+        let unsigned = signedness == Some("unsigned");
+        let primitive = match (words.as_slice(), unsigned) {
+            (["char"], true) => Primitive::U8,
+            (["char"], false) if signedness.is_some() => Primitive::I8,
+            // plain `char` has implementation defined signedness, we treat it as a byte
+            (["char"], false) => Primitive::U8,
+            (["short"] | ["short", "int"], true) => Primitive::U16,
+            (["short"] | ["short", "int"], false) => Primitive::I16,
+            ([] | ["int"], true) if signedness.is_some() => Primitive::U32,
+            ([] | ["int"], false) if signedness.is_some() || !words.is_empty() => Primitive::I32,
+            (["long", "long"] | ["long", "long", "int"], true) => Primitive::U64,
+            (["long", "long"] | ["long", "long", "int"], false) => Primitive::I64,
+            _ => {
+                let mut ty = signedness.map(|s| vec![s]).unwrap_or_default();
+                ty.extend(words);
+                return Err(CHeaderError::UnsupportedType {
+                    member: self.peek().map(Token::text).unwrap_or_default(),
+                    ty: ty.join(" "),
+                });
+            }
+        };
+        Ok(CType::Primitive(primitive))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_le(header: &str) -> Result<String, CHeaderError> {
+        convert(header, EndianKind::Little)
+    }
+
+    #[test]
+    fn empty_header() {
+        assert_eq!("", convert_le("").unwrap());
+        assert_eq!(
+            "",
+            convert_le("#include <stdint.h>\n// comment\n/* comment */").unwrap()
+        );
+    }
+
+    #[test]
+    fn simple_struct() {
+        let header = r#"
+            struct my_struct {
+                uint8_t a;
+                int16_t b;
+                uint32_t c;
+                int64_t d;
+            };
+        "#;
+        assert_eq!(
+            "binary_layout!(my_struct, LittleEndian, {\n    a: u8,\n    b: i16,\n    c: u32,\n    d: i64,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn endianness() {
+        let header = "struct s { uint16_t a; };";
+        assert_eq!(
+            "binary_layout!(s, BigEndian, {\n    a: u16,\n});\n",
+            convert(header, EndianKind::Big).unwrap()
+        );
+        assert_eq!(
+            "binary_layout!(s, NativeEndian, {\n    a: u16,\n});\n",
+            convert(header, EndianKind::Native).unwrap()
+        );
+    }
+
+    #[test]
+    fn builtin_types() {
+        let header = r#"
+            struct s {
+                char a;
+                signed char b;
+                unsigned char c;
+                short d;
+                unsigned short int e;
+                int f;
+                unsigned g;
+                unsigned int h;
+                long long i;
+                unsigned long long j;
+                float k;
+                double l;
+                bool m;
+                _Bool n;
+                const volatile __u16 o;
+                __s32 p;
+            };
+        "#;
+        assert_eq!(
+            "binary_layout!(s, LittleEndian, {\n    a: u8,\n    b: i8,\n    c: u8,\n    d: i16,\n    e: u16,\n    f: i32,\n    g: u32,\n    h: u32,\n    i: i64,\n    j: u64,\n    k: f32,\n    l: f64,\n    m: bool as u8,\n    n: bool as u8,\n    o: u16,\n    p: i32,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let header = r#"
+            struct s {
+                uint8_t a[4];
+                char b[2][3];
+                unsigned char c[0x10], d;
+                uint8_t tail[];
+            };
+        "#;
+        assert_eq!(
+            "binary_layout!(s, LittleEndian, {\n    a: [u8; 4],\n    b: [u8; 6],\n    c: [u8; 16],\n    d: u8,\n    tail: [u8],\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn attributes_and_typedefs() {
+        let header = r#"
+            #pragma pack(push, 1)
+            typedef uint32_t my_id_t;
+            typedef struct __attribute__((packed)) {
+                my_id_t id;
+                uint8_t flags __attribute__((aligned(1)));
+            } header_t;
+            typedef struct message {
+                header_t header;
+                uint16_t len;
+            } __attribute__((packed)) message_t;
+            #pragma pack(pop)
+        "#;
+        assert_eq!(
+            "binary_layout!(header_t, LittleEndian, {\n    id: u32,\n    flags: u8,\n});\n\nbinary_layout!(message_t, LittleEndian, {\n    header: header_t::NestedView,\n    len: u16,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn nested_structs() {
+        let header = r#"
+            struct inner {
+                uint16_t a;
+            };
+            struct outer {
+                struct inner first;
+                uint8_t b;
+                struct inner second;
+            };
+        "#;
+        assert_eq!(
+            "binary_layout!(inner, LittleEndian, {\n    a: u16,\n});\n\nbinary_layout!(outer, LittleEndian, {\n    first: inner::NestedView,\n    b: u8,\n    second: inner::NestedView,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn ignores_other_declarations() {
+        let header = r#"
+            #ifdef __cplusplus
+            extern "C" {
+            #endif
+            enum color { RED, GREEN };
+            struct forward_declared;
+            int some_function(struct s *arg);
+            static inline int other_function(void) { return 0; }
+            typedef void (*callback_t)(int);
+            struct s { uint8_t a; };
+            #ifdef __cplusplus
+            }
+            #endif
+        "#;
+        assert_eq!(
+            "binary_layout!(s, LittleEndian, {\n    a: u8,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn keywords_are_escaped() {
+        let header = "struct match { uint8_t type; };";
+        assert_eq!(
+            "binary_layout!(match_, LittleEndian, {\n    type_: u8,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn reserved_keywords_are_escaped() {
+        let header = "struct s { uint8_t abstract; uint8_t yield; uint8_t dyn; uint8_t try; };";
+        assert_eq!(
+            "binary_layout!(s, LittleEndian, {\n    abstract_: u8,\n    yield_: u8,\n    dyn_: u8,\n    try_: u8,\n});\n",
+            convert_le(header).unwrap()
+        );
+    }
+
+    #[test]
+    fn unsupported_long() {
+        assert_eq!(
+            CHeaderError::UnsupportedType {
+                member: "a".to_string(),
+                ty: "unsigned long".to_string()
+            },
+            convert_le("struct s { unsigned long a; };").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unsupported_unknown_type() {
+        assert_eq!(
+            CHeaderError::UnsupportedType {
+                member: "a".to_string(),
+                ty: "size_t".to_string()
+            },
+            convert_le("struct s { size_t a; };").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unsupported_bitfield() {
+        assert_eq!(
+            CHeaderError::UnsupportedBitField("a".to_string()),
+            convert_le("struct s { uint8_t a : 4; uint8_t b : 4; };").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unsupported_non_byte_array() {
+        assert_eq!(
+            CHeaderError::UnsupportedArray {
+                member: "a".to_string(),
+                ty: "uint16_t".to_string()
+            },
+            convert_le("struct s { uint16_t a[4]; };").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn flexible_array_not_last() {
+        assert_eq!(
+            CHeaderError::FlexibleArrayNotLast("a".to_string()),
+            convert_le("struct s { uint8_t a[]; uint8_t b; };").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unexpected_token() {
+        assert_eq!(
+            CHeaderError::UnexpectedToken {
+                found: "}".to_string(),
+                expected: "`,` or `;`",
+                line: 3,
+            },
+            convert_le("struct s {\n uint8_t a\n};").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unexpected_eof() {
+        assert_eq!(
+            CHeaderError::UnexpectedEof,
+            convert_le("struct s { uint8_t a;").unwrap_err()
+        );
+    }
+}
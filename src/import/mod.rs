@@ -0,0 +1,60 @@
+//! This module contains converters that generate [binary_layout!](crate::binary_layout!) definitions
+//! from layout descriptions in other formats.
+//!
+//! The converters produce Rust source code, so they're meant to be called from a `build.rs` script.
+//! The generated code can then be pulled into your crate using [include!].
+//!
+//! # Example
+//! ```no_run
+//! // build.rs
+//! use binary_layout::{import::c_header, EndianKind};
+//!
+//! fn main() {
+//!     let header = std::fs::read_to_string("firmware/include/packets.h").unwrap();
+//!     let layouts = c_header::convert(&header, EndianKind::Little).unwrap();
+//!     let out_dir = std::env::var("OUT_DIR").unwrap();
+//!     std::fs::write(format!("{}/packets.rs", out_dir), layouts).unwrap();
+//!     println!("cargo:rerun-if-changed=firmware/include/packets.h");
+//! }
+//! ```
+//!
+//! ```ignore
+//! // src/lib.rs
+//! use binary_layout::prelude::*;
+//!
+//! include!(concat!(env!("OUT_DIR"), "/packets.rs"));
+//! ```
+
+use crate::endianness::EndianKind;
+
+pub mod c_header;
+pub mod svd;
+mod xml;
+
+pub use xml::XmlError;
+
+/// Returns the name of the marker type that needs to be passed to [binary_layout!](crate::binary_layout!) for the given endianness.
+pub(crate) fn endianness_type_name(endianness: EndianKind) -> &'static str {
+    match endianness {
+        EndianKind::Big => "BigEndian",
+        EndianKind::Little => "LittleEndian",
+        EndianKind::Native => "NativeEndian",
+    }
+}
+
+/// Identifiers from other formats can clash with Rust keywords, in which case we append an underscore.
+/// Raw identifiers wouldn't work since `self`, `Self`, `super` and `crate` can't be raw identifiers.
+pub(crate) fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+        "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+        "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+        "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try",
+        "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
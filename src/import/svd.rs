@@ -0,0 +1,1010 @@
+//! Converts [CMSIS-SVD](https://open-cmsis-pack.github.io/svd-spec/main/index.html) device descriptions into
+//! [binary_layout!](crate::binary_layout!) definitions for the register blocks of the device's peripherals.
+//!
+//! For each peripheral, this generates
+//! - a `<PERIPHERAL>_BASE_ADDRESS` constant with the address of the register block,
+//! - a layout containing a field for each register, sorted by address offset.
+//!   Gaps between registers are filled with `reserved_<offset>` byte arrays.
+//!   Registers using `dim` are expanded into one field per element.
+//!
+//! Registers with bitfields are split into [bit fields](crate::BitField) named `<register>_<bitfield>`, with
+//! single bit fields read as `bool` and the bits not covered by any bitfield filled with `<register>_reserved_<bit>` fields.
+//! The access policy and description of each register and the access policy of each bitfield are written as comments next to the fields.
+//!
+//! Peripherals using `derivedFrom` inherit the registers of the peripheral they're derived from.
+//! Registers declaring an `alternateRegister` or `alternateGroup` overlap with other registers and are skipped.
+//! Register clusters aren't supported.
+//!
+//! # Example
+//! ```
+//! use binary_layout::import::svd;
+//!
+//! let device = r#"
+//!     <device>
+//!       <name>MY_MCU</name>
+//!       <size>32</size>
+//!       <access>read-write</access>
+//!       <peripherals>
+//!         <peripheral>
+//!           <name>TIMER0</name>
+//!           <baseAddress>0x40010000</baseAddress>
+//!           <registers>
+//!             <register>
+//!               <name>CTRL</name>
+//!               <addressOffset>0x0</addressOffset>
+//!             </register>
+//!             <register>
+//!               <name>COUNT</name>
+//!               <addressOffset>0x8</addressOffset>
+//!               <access>read-only</access>
+//!             </register>
+//!           </registers>
+//!         </peripheral>
+//!       </peripherals>
+//!     </device>
+//! "#;
+//!
+//! let layouts = svd::convert(device).unwrap();
+//! assert_eq!(
+//!     "pub const TIMER0_BASE_ADDRESS: u64 = 0x4001_0000;\n\
+//!      binary_layout!(timer0, LittleEndian, {\n    \
+//!          // CTRL (read-write)\n    \
+//!          ctrl: u32,\n    \
+//!          reserved_0x4: [u8; 4],\n    \
+//!          // COUNT (read-only)\n    \
+//!          count: u32,\n\
+//!      });\n",
+//!     layouts,
+//! );
+//! ```
+
+use std::fmt::Write;
+use thiserror::Error;
+
+use super::rust_ident;
+use super::xml::{self, Element, XmlError};
+
+/// The error being thrown when an SVD file can't be converted into layouts.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SvdError {
+    /// The SVD file isn't well-formed XML.
+    #[error("Invalid XML: {0}")]
+    Xml(#[from] XmlError),
+    /// A required element is missing.
+    #[error("Missing element `<{element}>` in {context}")]
+    MissingElement {
+        /// Name of the missing element
+        element: &'static str,
+        /// Description of where it is missing
+        context: String,
+    },
+    /// An element contains a value that can't be interpreted.
+    #[error("Invalid value `{value}` for `<{element}>` in {context}")]
+    InvalidValue {
+        /// Name of the element
+        element: &'static str,
+        /// The value that couldn't be interpreted
+        value: String,
+        /// Description of where the element is
+        context: String,
+    },
+    /// Two registers of a peripheral overlap.
+    #[error("Register `{second}` overlaps with register `{first}` in peripheral `{peripheral}`")]
+    OverlappingRegisters {
+        /// Name of the peripheral
+        peripheral: String,
+        /// Name of the register at the lower offset
+        first: String,
+        /// Name of the register overlapping it
+        second: String,
+    },
+    /// A peripheral is derived from a peripheral that doesn't exist.
+    #[error("Peripheral `{peripheral}` is derived from unknown peripheral `{base}`")]
+    UnknownBasePeripheral {
+        /// Name of the derived peripheral
+        peripheral: String,
+        /// Name of the peripheral it is derived from
+        base: String,
+    },
+    /// Two bitfields of a register overlap.
+    #[error("Bitfield `{second}` overlaps with bitfield `{first}` in register `{register}`")]
+    OverlappingBitfields {
+        /// Name of the register, prefixed with the name of the peripheral
+        register: String,
+        /// Name of the bitfield at the lower bit
+        first: String,
+        /// Name of the bitfield overlapping it
+        second: String,
+    },
+    /// A peripheral uses register clusters.
+    #[error("Peripheral `{0}` uses register clusters, which aren't supported")]
+    UnsupportedCluster(String),
+}
+
+/// Parse the SVD device description in `svd` and return Rust source code containing a
+/// [binary_layout!](crate::binary_layout!) invocation for each peripheral.
+///
+/// The generated code expects `binary_layout::prelude::*` to be in scope.
+pub fn convert(svd: &str) -> Result<String, SvdError> {
+    let device = xml::parse(svd)?;
+    let defaults = Defaults::default().inherit(&device, "device")?;
+    let endianness = match device.child("cpu").and_then(|cpu| cpu.child_text("endian")) {
+        Some("big") => "BigEndian",
+        _ => "LittleEndian",
+    };
+
+    let peripherals: Vec<&Element> = device
+        .child("peripherals")
+        .map(|peripherals| peripherals.children("peripheral").collect())
+        .unwrap_or_default();
+
+    let mut result = String::new();
+    for (index, peripheral) in peripherals.iter().enumerate() {
+        let name = required_text(peripheral, "name", "peripheral")?;
+        let context = format!("peripheral `{}`", name);
+        let base_address = parse_number(
+            required_text(peripheral, "baseAddress", &context)?,
+            "baseAddress",
+            &context,
+        )?;
+
+        // Derived peripherals take their registers from the base peripheral
+        let register_source = match peripheral.attribute("derivedFrom") {
+            Some(base) if peripheral.child("registers").is_none() => peripherals
+                .iter()
+                .find(|p| p.child_text("name") == Some(base))
+                .ok_or_else(|| SvdError::UnknownBasePeripheral {
+                    peripheral: name.to_string(),
+                    base: base.to_string(),
+                })?,
+            _ => peripheral,
+        };
+        let defaults = defaults
+            .inherit(register_source, &context)?
+            .inherit(peripheral, &context)?;
+        let registers = collect_registers(name, register_source, &defaults)?;
+        if let Some(register) = registers.iter().find(|register| {
+            base_address
+                .checked_add(register.offset + register.size_bytes)
+                .is_none()
+        }) {
+            return Err(SvdError::InvalidValue {
+                element: "addressOffset",
+                value: format_hex(register.offset),
+                context: format!("register `{}.{}`", name, register.name),
+            });
+        }
+
+        if index > 0 {
+            result.push('\n');
+        }
+        writeln!(
+            result,
+            "pub const {}_BASE_ADDRESS: u64 = {};",
+            name.to_uppercase(),
+            format_hex(base_address)
+        )
+        .unwrap();
+        writeln!(
+            result,
+            "binary_layout!({}, {}, {{",
+            rust_ident(&ident(name)),
+            endianness
+        )
+        .unwrap();
+        let mut offset = 0;
+        for register in registers {
+            if register.offset > offset {
+                writeln!(
+                    result,
+                    "    reserved_{:#x}: [u8; {}],",
+                    offset,
+                    register.offset - offset
+                )
+                .unwrap();
+            }
+            write_register(&mut result, &register, endianness == "BigEndian");
+            offset = register.offset + register.size_bytes;
+        }
+        result.push_str("});\n");
+    }
+    Ok(result)
+}
+
+/// Settings that are inherited from the device to peripherals to registers
+#[derive(Clone, Default)]
+struct Defaults {
+    size_bits: Option<u64>,
+    access: Option<String>,
+}
+
+impl Defaults {
+    fn inherit(&self, element: &Element, context: &str) -> Result<Self, SvdError> {
+        let mut result = self.clone();
+        if let Some(size) = element.child_text("size") {
+            result.size_bits = Some(parse_number(size, "size", context)?);
+        }
+        if let Some(access) = element.child_text("access") {
+            result.access = Some(access.to_string());
+        }
+        Ok(result)
+    }
+}
+
+struct Register {
+    name: String,
+    offset: u64,
+    size_bytes: u64,
+    access: Option<String>,
+    description: Option<String>,
+    bitfields: Vec<Bitfield>,
+}
+
+#[derive(Clone)]
+struct Bitfield {
+    name: String,
+    lsb: u64,
+    msb: u64,
+    access: Option<String>,
+}
+
+fn collect_registers(
+    peripheral_name: &str,
+    peripheral: &Element,
+    defaults: &Defaults,
+) -> Result<Vec<Register>, SvdError> {
+    let registers = match peripheral.child("registers") {
+        Some(registers) => registers,
+        None => return Ok(Vec::new()),
+    };
+    if registers.child("cluster").is_some() {
+        return Err(SvdError::UnsupportedCluster(peripheral_name.to_string()));
+    }
+
+    let mut result = Vec::new();
+    for register in registers.children("register") {
+        if register.child("alternateRegister").is_some()
+            || register.child("alternateGroup").is_some()
+        {
+            continue;
+        }
+        let name = required_text(
+            register,
+            "name",
+            &format!("peripheral `{}`", peripheral_name),
+        )?;
+        let qualified_name = format!("{}.{}", peripheral_name, name);
+        let context = format!("register `{}`", qualified_name);
+        let defaults = defaults.inherit(register, &context)?;
+        let offset = parse_number(
+            required_text(register, "addressOffset", &context)?,
+            "addressOffset",
+            &context,
+        )?;
+        let size_bits = defaults.size_bits.ok_or_else(|| SvdError::MissingElement {
+            element: "size",
+            context: context.clone(),
+        })?;
+        if !matches!(size_bits, 8 | 16 | 32 | 64) {
+            return Err(SvdError::InvalidValue {
+                element: "size",
+                value: size_bits.to_string(),
+                context,
+            });
+        }
+        let bitfields = collect_bitfields(register, size_bits, &qualified_name, &context)?;
+        let description = register
+            .child_text("description")
+            .map(|description| description.split_whitespace().collect::<Vec<_>>().join(" "));
+
+        for (element_name, element_offset) in expand_dim(register, name, offset, &context)? {
+            if element_offset.checked_add(size_bits / 8).is_none() {
+                return Err(SvdError::InvalidValue {
+                    element: "addressOffset",
+                    value: format_hex(element_offset),
+                    context,
+                });
+            }
+            result.push(Register {
+                name: element_name,
+                offset: element_offset,
+                size_bytes: size_bits / 8,
+                access: defaults.access.clone(),
+                description: description.clone(),
+                bitfields: bitfields.clone(),
+            });
+        }
+    }
+
+    result.sort_by_key(|register| register.offset);
+    for pair in result.windows(2) {
+        if pair[0].offset + pair[0].size_bytes > pair[1].offset {
+            return Err(SvdError::OverlappingRegisters {
+                peripheral: peripheral_name.to_string(),
+                first: pair[0].name.clone(),
+                second: pair[1].name.clone(),
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Expand registers using `<dim>` into one register per element, returning a list of names and offsets.
+fn expand_dim(
+    register: &Element,
+    name: &str,
+    offset: u64,
+    context: &str,
+) -> Result<Vec<(String, u64)>, SvdError> {
+    let dim = match register.child_text("dim") {
+        Some(dim) => parse_number(dim, "dim", context)?,
+        None => return Ok(vec![(name.to_string(), offset)]),
+    };
+    let increment = parse_number(
+        required_text(register, "dimIncrement", context)?,
+        "dimIncrement",
+        context,
+    )?;
+    let indices: Vec<String> = match register.child_text("dimIndex") {
+        Some(dim_index) => parse_dim_index(dim_index).ok_or_else(|| SvdError::InvalidValue {
+            element: "dimIndex",
+            value: dim_index.to_string(),
+            context: context.to_string(),
+        })?,
+        None => (0..dim).map(|index| index.to_string()).collect(),
+    };
+    if indices.len() as u64 != dim {
+        return Err(SvdError::InvalidValue {
+            element: "dimIndex",
+            value: indices.join(","),
+            context: context.to_string(),
+        });
+    }
+    indices
+        .iter()
+        .enumerate()
+        .map(|(i, index)| {
+            let element_name = if name.contains("[%s]") {
+                name.replace("[%s]", index)
+            } else {
+                name.replace("%s", index)
+            };
+            let element_offset = (i as u64)
+                .checked_mul(increment)
+                .and_then(|distance| offset.checked_add(distance))
+                .ok_or_else(|| SvdError::InvalidValue {
+                    element: "dimIncrement",
+                    value: format_hex(increment),
+                    context: context.to_string(),
+                })?;
+            Ok((element_name, element_offset))
+        })
+        .collect()
+}
+
+fn parse_dim_index(dim_index: &str) -> Option<Vec<String>> {
+    if let Some((start, end)) = dim_index.split_once('-') {
+        if let (Ok(start), Ok(end)) = (start.trim().parse::<u64>(), end.trim().parse::<u64>()) {
+            return Some((start..=end).map(|index| index.to_string()).collect());
+        }
+        let (start, end) = (start.trim(), end.trim());
+        if start.len() == 1 && end.len() == 1 {
+            let (start, end) = (start.chars().next()?, end.chars().next()?);
+            return Some((start..=end).map(|index| index.to_string()).collect());
+        }
+        return None;
+    }
+    Some(
+        dim_index
+            .split(',')
+            .map(|index| index.trim().to_string())
+            .collect(),
+    )
+}
+
+fn collect_bitfields(
+    register: &Element,
+    size_bits: u64,
+    qualified_name: &str,
+    context: &str,
+) -> Result<Vec<Bitfield>, SvdError> {
+    let fields = match register.child("fields") {
+        Some(fields) => fields,
+        None => return Ok(Vec::new()),
+    };
+    let mut result = Vec::new();
+    for field in fields.children("field") {
+        let name = required_text(field, "name", context)?;
+        let (lsb, msb) = if let Some(bit_offset) = field.child_text("bitOffset") {
+            let lsb = parse_number(bit_offset, "bitOffset", context)?;
+            let width = parse_number(
+                required_text(field, "bitWidth", context)?,
+                "bitWidth",
+                context,
+            )?;
+            let msb = lsb
+                .checked_add(width.max(1) - 1)
+                .ok_or_else(|| SvdError::InvalidValue {
+                    element: "bitWidth",
+                    value: width.to_string(),
+                    context: context.to_string(),
+                })?;
+            (lsb, msb)
+        } else if let Some(lsb) = field.child_text("lsb") {
+            let msb = required_text(field, "msb", context)?;
+            (
+                parse_number(lsb, "lsb", context)?,
+                parse_number(msb, "msb", context)?,
+            )
+        } else {
+            let bit_range = required_text(field, "bitRange", context)?;
+            parse_bit_range(bit_range).ok_or_else(|| SvdError::InvalidValue {
+                element: "bitRange",
+                value: bit_range.to_string(),
+                context: context.to_string(),
+            })?
+        };
+        if lsb > msb || msb >= size_bits {
+            return Err(SvdError::InvalidValue {
+                element: "bitRange",
+                value: format!("[{}:{}]", msb, lsb),
+                context: format!("{} field `{}`", context, name),
+            });
+        }
+        result.push(Bitfield {
+            name: name.to_string(),
+            lsb,
+            msb,
+            access: field.child_text("access").map(str::to_string),
+        });
+    }
+    result.sort_by_key(|bitfield| bitfield.lsb);
+    for pair in result.windows(2) {
+        if pair[0].msb >= pair[1].lsb {
+            return Err(SvdError::OverlappingBitfields {
+                register: qualified_name.to_string(),
+                first: pair[0].name.clone(),
+                second: pair[1].name.clone(),
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Parses `[msb:lsb]`
+fn parse_bit_range(bit_range: &str) -> Option<(u64, u64)> {
+    let bit_range = bit_range.strip_prefix('[')?.strip_suffix(']')?;
+    let (msb, lsb) = bit_range.split_once(':')?;
+    Some((lsb.trim().parse().ok()?, msb.trim().parse().ok()?))
+}
+
+fn write_register(out: &mut String, register: &Register, big_endian: bool) {
+    write!(out, "    // {}", register.name).unwrap();
+    if let Some(access) = &register.access {
+        write!(out, " ({})", access).unwrap();
+    }
+    if let Some(description) = &register.description {
+        write!(out, ": {}", description).unwrap();
+    }
+    out.push('\n');
+    let name = ident(&register.name);
+    let size_bits = register.size_bytes * 8;
+    if register.bitfields.is_empty() {
+        writeln!(out, "    {}: u{},", rust_ident(&name), size_bits).unwrap();
+        return;
+    }
+
+    // Bits not covered by any bitfield become reserved bit fields, so the bit fields add up to the whole register
+    let mut slots: Vec<(u64, u64, Option<&Bitfield>)> = Vec::new();
+    let mut next_bit = 0;
+    for bitfield in &register.bitfields {
+        if bitfield.lsb > next_bit {
+            slots.push((next_bit, bitfield.lsb - 1, None));
+        }
+        slots.push((bitfield.lsb, bitfield.msb, Some(bitfield)));
+        next_bit = bitfield.msb + 1;
+    }
+    if next_bit < size_bits {
+        slots.push((next_bit, size_bits - 1, None));
+    }
+    // Big endian layouts pack bit fields starting at the most significant bit, see BitField
+    if big_endian {
+        slots.reverse();
+    }
+
+    for (lsb, msb, bitfield) in slots {
+        let bits = msb - lsb + 1;
+        let ty = if bits == 1 {
+            "bool".to_string()
+        } else {
+            format!("u{}", size_bits)
+        };
+        match bitfield {
+            Some(bitfield) => {
+                if let Some(access) = &bitfield.access {
+                    writeln!(out, "    // {} ({})", bitfield.name, access).unwrap();
+                }
+                writeln!(
+                    out,
+                    "    {}_{}: {} bits({}),",
+                    name,
+                    ident(&bitfield.name),
+                    ty,
+                    bits
+                )
+                .unwrap();
+            }
+            None => {
+                writeln!(out, "    {}_reserved_{}: {} bits({}),", name, lsb, ty, bits).unwrap();
+            }
+        }
+    }
+}
+
+fn required_text<'a>(
+    element: &'a Element,
+    name: &'static str,
+    context: &str,
+) -> Result<&'a str, SvdError> {
+    element
+        .child_text(name)
+        .ok_or_else(|| SvdError::MissingElement {
+            element: name,
+            context: context.to_string(),
+        })
+}
+
+/// SVD allows decimal, hexadecimal (`0x...`) and binary (`#...` or `0b...`) numbers
+fn parse_number(value: &str, element: &'static str, context: &str) -> Result<u64, SvdError> {
+    let parsed = if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(binary) = value.strip_prefix('#').or_else(|| value.strip_prefix("0b")) {
+        u64::from_str_radix(binary, 2)
+    } else {
+        value.parse()
+    };
+    parsed.map_err(|_| SvdError::InvalidValue {
+        element,
+        value: value.to_string(),
+        context: context.to_string(),
+    })
+}
+
+fn format_hex(value: u64) -> String {
+    let digits = format!("{:x}", value);
+    let mut groups: Vec<&str> = Vec::new();
+    let mut end = digits.len();
+    while end > 4 {
+        groups.push(&digits[end - 4..end]);
+        end -= 4;
+    }
+    groups.push(&digits[..end]);
+    groups.reverse();
+    format!("0x{}", groups.join("_"))
+}
+
+/// Register and peripheral names are usually upper case, layouts and fields are snake case.
+fn ident(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn device(peripherals: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\"?><device><name>TEST</name><size>32</size><access>read-write</access><peripherals>{}</peripherals></device>",
+            peripherals
+        )
+    }
+
+    #[test]
+    fn no_peripherals() {
+        assert_eq!("", convert("<device><name>TEST</name></device>").unwrap());
+    }
+
+    #[test]
+    fn registers_with_gaps_and_sizes() {
+        let svd = device(
+            r#"<peripheral>
+                <name>UART</name>
+                <baseAddress>0x4000C000</baseAddress>
+                <registers>
+                    <register><name>DATA</name><addressOffset>0</addressOffset><size>8</size></register>
+                    <register><name>STATUS</name><addressOffset>0x4</addressOffset><access>read-only</access></register>
+                    <register><name>BAUD</name><addressOffset>0x2</addressOffset><size>16</size></register>
+                    <register><name>TIMESTAMP</name><addressOffset>16</addressOffset><size>64</size></register>
+                </registers>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            "pub const UART_BASE_ADDRESS: u64 = 0x4000_c000;\n\
+             binary_layout!(uart, LittleEndian, {\n\
+             \x20   // DATA (read-write)\n\
+             \x20   data: u8,\n\
+             \x20   reserved_0x1: [u8; 1],\n\
+             \x20   // BAUD (read-write)\n\
+             \x20   baud: u16,\n\
+             \x20   // STATUS (read-only)\n\
+             \x20   status: u32,\n\
+             \x20   reserved_0x8: [u8; 8],\n\
+             \x20   // TIMESTAMP (read-write)\n\
+             \x20   timestamp: u64,\n\
+             });\n",
+            convert(&svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn big_endian_cpu() {
+        let svd = r#"<device><cpu><endian>big</endian></cpu><size>16</size><peripherals><peripheral>
+                <name>P</name><baseAddress>0</baseAddress>
+                <registers><register><name>R</name><addressOffset>0</addressOffset></register></registers>
+            </peripheral></peripherals></device>"#;
+        assert_eq!(
+            "pub const P_BASE_ADDRESS: u64 = 0x0;\nbinary_layout!(p, BigEndian, {\n    // R\n    r: u16,\n});\n",
+            convert(svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitfields_and_descriptions() {
+        let svd = device(
+            r#"<peripheral>
+                <name>GPIO</name>
+                <baseAddress>0x50000000</baseAddress>
+                <registers>
+                    <register>
+                        <name>CTRL</name>
+                        <description>Control
+                            register</description>
+                        <addressOffset>0</addressOffset>
+                        <fields>
+                            <field><name>MODE</name><bitRange>[3:1]</bitRange></field>
+                            <field><name>EN</name><bitOffset>0</bitOffset><bitWidth>1</bitWidth></field>
+                            <field><name>SPEED</name><lsb>8</lsb><msb>9</msb><access>write-only</access></field>
+                        </fields>
+                    </register>
+                </registers>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            "pub const GPIO_BASE_ADDRESS: u64 = 0x5000_0000;\n\
+             binary_layout!(gpio, LittleEndian, {\n\
+             \x20   // CTRL (read-write): Control register\n\
+             \x20   ctrl_en: bool bits(1),\n\
+             \x20   ctrl_mode: u32 bits(3),\n\
+             \x20   ctrl_reserved_4: u32 bits(4),\n\
+             \x20   // SPEED (write-only)\n\
+             \x20   ctrl_speed: u32 bits(2),\n\
+             \x20   ctrl_reserved_10: u32 bits(22),\n\
+             });\n",
+            convert(&svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated_bitfields_match_register_bits() {
+        // The output of `bitfields_and_descriptions`
+        binary_layout!(gpio, LittleEndian, {
+            ctrl_en: bool bits(1),
+            ctrl_mode: u32 bits(3),
+            ctrl_reserved_4: u32 bits(4),
+            ctrl_speed: u32 bits(2),
+            ctrl_reserved_10: u32 bits(22),
+        });
+        let view = gpio::View::new(0b10_0000_1011u32.to_le_bytes());
+        assert!(view.ctrl_en().read());
+        assert_eq!(0b101, view.ctrl_mode().read());
+        assert_eq!(0b10, view.ctrl_speed().read());
+    }
+
+    #[test]
+    fn big_endian_bitfields() {
+        let svd = r#"<device><cpu><endian>big</endian></cpu><size>16</size><peripherals><peripheral>
+                <name>P</name><baseAddress>0</baseAddress>
+                <registers><register><name>R</name><addressOffset>0</addressOffset><fields>
+                    <field><name>LOW</name><bitRange>[3:0]</bitRange></field>
+                    <field><name>TOP</name><bitRange>[15:15]</bitRange></field>
+                </fields></register></registers>
+            </peripheral></peripherals></device>"#;
+        assert_eq!(
+            "pub const P_BASE_ADDRESS: u64 = 0x0;\n\
+             binary_layout!(p, BigEndian, {\n\
+             \x20   // R\n\
+             \x20   r_top: bool bits(1),\n\
+             \x20   r_reserved_4: u16 bits(11),\n\
+             \x20   r_low: u16 bits(4),\n\
+             });\n",
+            convert(svd).unwrap()
+        );
+
+        binary_layout!(p, BigEndian, {
+            r_top: bool bits(1),
+            r_reserved_4: u16 bits(11),
+            r_low: u16 bits(4),
+        });
+        let view = p::View::new(0x8005u16.to_be_bytes());
+        assert!(view.r_top().read());
+        assert_eq!(5, view.r_low().read());
+    }
+
+    #[test]
+    fn keywords_are_escaped() {
+        let svd = device(
+            r#"<peripheral>
+                <name>MATCH</name>
+                <baseAddress>0</baseAddress>
+                <registers>
+                    <register><name>IN</name><addressOffset>0</addressOffset></register>
+                    <register><name>TYPE</name><addressOffset>4</addressOffset><fields>
+                        <field><name>LOOP</name><bitRange>[31:0]</bitRange></field>
+                    </fields></register>
+                </registers>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            "pub const MATCH_BASE_ADDRESS: u64 = 0x0;\n\
+             binary_layout!(match_, LittleEndian, {\n\
+             \x20   // IN (read-write)\n\
+             \x20   in_: u32,\n\
+             \x20   // TYPE (read-write)\n\
+             \x20   type_loop: u32 bits(32),\n\
+             });\n",
+            convert(&svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn overlapping_bitfields() {
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0</baseAddress><registers>
+                <register><name>A</name><addressOffset>0</addressOffset><fields>
+                    <field><name>X</name><bitRange>[3:0]</bitRange></field>
+                    <field><name>Y</name><bitRange>[4:3]</bitRange></field>
+                </fields></register>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::OverlappingBitfields {
+                register: "P.A".to_string(),
+                first: "X".to_string(),
+                second: "Y".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn bitfield_outside_of_register() {
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0</baseAddress><registers>
+                <register><name>A</name><addressOffset>0</addressOffset><size>8</size><fields>
+                    <field><name>X</name><bitOffset>4</bitOffset><bitWidth>8</bitWidth></field>
+                </fields></register>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::InvalidValue {
+                element: "bitRange",
+                value: "[11:4]".to_string(),
+                context: "register `P.A` field `X`".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn address_overflow() {
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0xFFFFFFFFFFFFFFF0</baseAddress><registers>
+                <register><name>A</name><addressOffset>0x10</addressOffset></register>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::InvalidValue {
+                element: "addressOffset",
+                value: "0x10".to_string(),
+                context: "register `P.A`".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0</baseAddress><registers>
+                <register><name>A</name><addressOffset>0xFFFFFFFFFFFFFFFE</addressOffset></register>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::InvalidValue {
+                element: "addressOffset",
+                value: "0xffff_ffff_ffff_fffe".to_string(),
+                context: "register `P.A`".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0</baseAddress><registers>
+                <register><name>A%s</name><dim>3</dim><dimIncrement>0x8000000000000000</dimIncrement><addressOffset>0</addressOffset></register>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::InvalidValue {
+                element: "dimIncrement",
+                value: "0x8000_0000_0000_0000".to_string(),
+                context: "register `P.A%s`".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn dim_registers() {
+        let svd = device(
+            r#"<peripheral>
+                <name>DMA</name>
+                <baseAddress>0</baseAddress>
+                <registers>
+                    <register><name>CH%s</name><dim>2</dim><dimIncrement>4</dimIncrement><addressOffset>0</addressOffset></register>
+                    <register><name>BUF[%s]</name><dim>2</dim><dimIncrement>8</dimIncrement><dimIndex>A-B</dimIndex><addressOffset>8</addressOffset></register>
+                </registers>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            "pub const DMA_BASE_ADDRESS: u64 = 0x0;\n\
+             binary_layout!(dma, LittleEndian, {\n\
+             \x20   // CH0 (read-write)\n\
+             \x20   ch0: u32,\n\
+             \x20   // CH1 (read-write)\n\
+             \x20   ch1: u32,\n\
+             \x20   // BUFA (read-write)\n\
+             \x20   bufa: u32,\n\
+             \x20   reserved_0xc: [u8; 4],\n\
+             \x20   // BUFB (read-write)\n\
+             \x20   bufb: u32,\n\
+             });\n",
+            convert(&svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn derived_peripherals() {
+        let svd = device(
+            r#"<peripheral>
+                <name>TIM0</name>
+                <baseAddress>0x1000</baseAddress>
+                <registers><register><name>CNT</name><addressOffset>0</addressOffset></register></registers>
+            </peripheral>
+            <peripheral derivedFrom="TIM0">
+                <name>TIM1</name>
+                <baseAddress>0x2000</baseAddress>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            "pub const TIM0_BASE_ADDRESS: u64 = 0x1000;\nbinary_layout!(tim0, LittleEndian, {\n    // CNT (read-write)\n    cnt: u32,\n});\n\n\
+             pub const TIM1_BASE_ADDRESS: u64 = 0x2000;\nbinary_layout!(tim1, LittleEndian, {\n    // CNT (read-write)\n    cnt: u32,\n});\n",
+            convert(&svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn alternate_registers_are_skipped() {
+        let svd = device(
+            r#"<peripheral>
+                <name>P</name>
+                <baseAddress>0</baseAddress>
+                <registers>
+                    <register><name>A</name><addressOffset>0</addressOffset></register>
+                    <register><name>B</name><alternateRegister>A</alternateRegister><addressOffset>0</addressOffset></register>
+                </registers>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            "pub const P_BASE_ADDRESS: u64 = 0x0;\nbinary_layout!(p, LittleEndian, {\n    // A (read-write)\n    a: u32,\n});\n",
+            convert(&svd).unwrap()
+        );
+    }
+
+    #[test]
+    fn overlapping_registers() {
+        let svd = device(
+            r#"<peripheral>
+                <name>P</name>
+                <baseAddress>0</baseAddress>
+                <registers>
+                    <register><name>A</name><addressOffset>0</addressOffset></register>
+                    <register><name>B</name><addressOffset>2</addressOffset></register>
+                </registers>
+            </peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::OverlappingRegisters {
+                peripheral: "P".to_string(),
+                first: "A".to_string(),
+                second: "B".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unknown_base_peripheral() {
+        let svd = device(
+            r#"<peripheral derivedFrom="X"><name>P</name><baseAddress>0</baseAddress></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::UnknownBasePeripheral {
+                peripheral: "P".to_string(),
+                base: "X".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn missing_base_address() {
+        let svd = device(r#"<peripheral><name>P</name></peripheral>"#);
+        assert_eq!(
+            SvdError::MissingElement {
+                element: "baseAddress",
+                context: "peripheral `P`".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn invalid_size() {
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0</baseAddress><registers>
+                <register><name>A</name><addressOffset>0</addressOffset><size>24</size></register>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::InvalidValue {
+                element: "size",
+                value: "24".to_string(),
+                context: "register `P.A`".to_string(),
+            },
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn clusters_are_unsupported() {
+        let svd = device(
+            r#"<peripheral><name>P</name><baseAddress>0</baseAddress><registers>
+                <cluster><name>C</name></cluster>
+            </registers></peripheral>"#,
+        );
+        assert_eq!(
+            SvdError::UnsupportedCluster("P".to_string()),
+            convert(&svd).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn invalid_xml() {
+        assert!(matches!(convert("<device>"), Err(SvdError::Xml(_))));
+    }
+}
@@ -0,0 +1,233 @@
+//! A minimal XML reader, just powerful enough to read the description files our importers understand.
+//! It doesn't validate documents and ignores comments, processing instructions and doctype declarations.
+
+use thiserror::Error;
+
+/// The error being thrown when an XML document is malformed.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum XmlError {
+    /// The document ended before all elements were closed.
+    #[error("Unexpected end of XML document")]
+    UnexpectedEof,
+    /// The document isn't well-formed at the given byte position.
+    #[error("Malformed XML at byte {0}")]
+    Malformed(usize),
+    /// A closing tag didn't match the element it closes.
+    #[error("Closing tag `</{found}>` doesn't match opening tag `<{expected}>`")]
+    MismatchedTag {
+        /// Name of the element that was open
+        expected: String,
+        /// Name used in the closing tag
+        found: String,
+    },
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Returns the trimmed text content of the child element with the given name
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.trim())
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader { input, pos: 0 };
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    Ok(root)
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn skip_until(&mut self, end: &str) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let index = rest.find(end).ok_or(XmlError::UnexpectedEof)?;
+        self.pos += index + end.len();
+        Ok(&rest[..index])
+    }
+
+    /// Skip whitespace, comments, processing instructions and doctype declarations
+    fn skip_misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.rest().starts_with("<!") && !self.rest().starts_with("<![CDATA[") {
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '='))
+            .ok_or(XmlError::UnexpectedEof)?;
+        if len == 0 {
+            return Err(XmlError::Malformed(self.pos));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn element(&mut self) -> Result<Element, XmlError> {
+        if !self.rest().starts_with('<') {
+            return Err(XmlError::Malformed(self.pos));
+        }
+        self.pos += 1;
+        let mut element = Element {
+            name: self.name()?.to_string(),
+            ..Element::default()
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let key = self.name()?.to_string();
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(XmlError::Malformed(self.pos));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = self.rest().chars().next().ok_or(XmlError::UnexpectedEof)?;
+            if quote != '"' && quote != '\'' {
+                return Err(XmlError::Malformed(self.pos));
+            }
+            self.pos += 1;
+            let value = self.skip_until(&quote.to_string())?;
+            element.attributes.push((key, unescape(value)));
+        }
+
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(XmlError::UnexpectedEof);
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(XmlError::MismatchedTag {
+                        expected: element.name,
+                        found: name.to_string(),
+                    });
+                }
+                self.skip_whitespace();
+                self.skip_until(">")?;
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let text = self.skip_until("]]>")?;
+                element.text.push_str(text);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.skip_misc()?;
+            } else if rest.starts_with('<') {
+                element.children.push(self.element()?);
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&unescape(&rest[..len]));
+                self.pos += len;
+            }
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_elements_attributes_and_text() {
+        let document = r#"<?xml version="1.0" encoding="utf-8"?>
+            <!-- comment -->
+            <root a="1" b='two &amp; three'>
+                <child>text &lt;1&gt;</child>
+                <empty/>
+                <child><![CDATA[<raw>]]></child>
+            </root>
+        "#;
+        let root = parse(document).unwrap();
+        assert_eq!("root", root.name);
+        assert_eq!(Some("1"), root.attribute("a"));
+        assert_eq!(Some("two & three"), root.attribute("b"));
+        assert_eq!(None, root.attribute("c"));
+        assert_eq!(3, root.children.len());
+        assert_eq!(Some("text <1>"), root.child_text("child"));
+        assert_eq!(
+            vec!["text <1>", "<raw>"],
+            root.children("child")
+                .map(|child| child.text.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(root.child("empty").unwrap().children.is_empty());
+    }
+
+    #[test]
+    fn mismatched_tag() {
+        assert_eq!(
+            XmlError::MismatchedTag {
+                expected: "a".to_string(),
+                found: "b".to_string()
+            },
+            parse("<a></b>").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unexpected_eof() {
+        assert_eq!(XmlError::UnexpectedEof, parse("<a><b></b>").unwrap_err());
+    }
+}
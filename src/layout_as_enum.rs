@@ -0,0 +1,175 @@
+/// This error is thrown when reading a field of an enum declared with [layout_as_enum!](crate::layout_as_enum!)
+/// and the stored value doesn't match the discriminant of any variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEnumValueError<U> {
+    /// The value that was stored in the field
+    pub value: U,
+}
+
+impl<U: core::fmt::Display> core::fmt::Display for InvalidEnumValueError<U> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "InvalidEnumValueError: {}", self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<U: core::fmt::Debug + core::fmt::Display> std::error::Error for InvalidEnumValueError<U> {}
+
+/// Declare a fieldless enum with a primitive representation like `#[repr(u8)]` and implement [LayoutAs](crate::LayoutAs)
+/// for it, so that it can be used as a field type like `packet_type: PacketType as u8` without writing the conversion by hand.
+///
+/// The enum is declared as written, so it can have other attributes, doc comments and explicit or implicit discriminants.
+/// Writing always succeeds, reading fails with [InvalidEnumValueError] if the stored value isn't the discriminant of a variant.
+/// This means the field has to be read with [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read).
+///
+/// # Example
+/// ```
+/// use binary_layout::{layout_as_enum, prelude::*, InvalidEnumValueError, WrappedFieldError};
+///
+/// layout_as_enum! {
+///     #[repr(u8)]
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     pub enum PacketType {
+///         Data = 1,
+///         Ack = 2,
+///         Nack,
+///     }
+/// }
+///
+/// binary_layout!(packet, BigEndian, {
+///   packet_type: PacketType as u8,
+///   len: u16,
+/// });
+///
+/// fn main() {
+///     let mut view = packet::View::new([0; 3]);
+///     view.packet_type_mut().write(PacketType::Nack);
+///     assert_eq!(PacketType::Nack, view.packet_type().try_read().unwrap());
+///     assert_eq!([3, 0, 0], view.into_storage());
+///
+///     let view = packet::View::new([7, 0, 0]);
+///     assert!(matches!(
+///         view.packet_type().try_read(),
+///         Err(WrappedFieldError::LayoutAsError(InvalidEnumValueError { value: 7 })),
+///     ));
+/// }
+/// ```
+#[macro_export]
+macro_rules! layout_as_enum {
+    (
+        #[repr($repr: ident)]
+        $(#[$meta: meta])*
+        $vis: vis enum $name: ident {
+            $($(#[$variant_meta: meta])* $variant: ident $(= $discriminant: expr)?),* $(,)?
+        }
+    ) => {
+        #[repr($repr)]
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant $(= $discriminant)?),*
+        }
+
+        impl $crate::LayoutAs<$repr> for $name {
+            type ReadError = $crate::InvalidEnumValueError<$repr>;
+            type WriteError = ::core::convert::Infallible;
+
+            fn try_read(v: $repr) -> ::core::result::Result<Self, Self::ReadError> {
+                $(
+                    if v == $name::$variant as $repr {
+                        return ::core::result::Result::Ok($name::$variant);
+                    }
+                )*
+                ::core::result::Result::Err($crate::InvalidEnumValueError { value: v })
+            }
+
+            fn try_write(v: Self) -> ::core::result::Result<$repr, Self::WriteError> {
+                ::core::result::Result::Ok(v as $repr)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{InvalidEnumValueError, LayoutAs, WrappedFieldError};
+
+    crate::layout_as_enum! {
+        #[repr(u8)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum PacketType {
+            Data = 1,
+            /// Acknowledges a packet
+            Ack = 2,
+            Nack,
+        }
+    }
+
+    crate::layout_as_enum! {
+        #[repr(i16)]
+        #[derive(Debug, PartialEq, Eq)]
+        pub enum Level {
+            Low = -1,
+            Zero,
+            High = 0x100,
+        }
+    }
+
+    binary_layout!(packet, LittleEndian, {
+        packet_type: PacketType as u8,
+        level: Level as i16,
+    });
+
+    #[test]
+    fn discriminants() {
+        assert_eq!(Ok(PacketType::Data), PacketType::try_read(1));
+        assert_eq!(Ok(PacketType::Ack), PacketType::try_read(2));
+        assert_eq!(Ok(PacketType::Nack), PacketType::try_read(3));
+        assert_eq!(
+            Err(InvalidEnumValueError { value: 0 }),
+            PacketType::try_read(0)
+        );
+        assert_eq!(Ok(0), <Level as LayoutAs<i16>>::try_write(Level::Zero));
+        assert_eq!(Ok(Level::Low), Level::try_read(-1));
+    }
+
+    #[test]
+    fn fieldapi() {
+        let mut storage = [0; 3];
+        packet::packet_type::write(&mut storage, PacketType::Ack);
+        packet::level::write(&mut storage, Level::High);
+        assert_eq!([2, 0, 1], storage);
+        assert_eq!(
+            PacketType::Ack,
+            packet::packet_type::try_read(&storage).unwrap()
+        );
+        assert_eq!(Level::High, packet::level::try_read(&storage).unwrap());
+    }
+
+    #[test]
+    fn viewapi() {
+        let mut view = packet::View::new([0xff, 0xff, 0xff]);
+        view.packet_type_mut().write(PacketType::Data);
+        view.level_mut().write(Level::Low);
+        assert_eq!(PacketType::Data, view.packet_type().try_read().unwrap());
+        assert_eq!(Level::Low, view.level().try_read().unwrap());
+        assert_eq!([1, 0xff, 0xff], view.into_storage());
+    }
+
+    #[test]
+    fn invalid_value() {
+        let view = packet::View::new([4, 2, 0]);
+        assert!(matches!(
+            view.packet_type().try_read(),
+            Err(WrappedFieldError::LayoutAsError(InvalidEnumValueError {
+                value: 4
+            }))
+        ));
+        assert!(matches!(
+            view.level().try_read(),
+            Err(WrappedFieldError::LayoutAsError(InvalidEnumValueError {
+                value: 2
+            }))
+        ));
+    }
+}
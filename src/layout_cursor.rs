@@ -0,0 +1,224 @@
+use crate::{LayoutView, ViewError};
+
+/// A [LayoutCursor] walks a byte buffer and reads consecutive instances of different layouts from it,
+/// e.g. for container formats made up of heterogeneous sections. It keeps track of the position in the buffer,
+/// and each call to [LayoutCursor::next] returns a view of the next layout and advances the position past it.
+///
+/// Layouts are given by their `NestedView` type. A layout ending in an open ended field takes all of the remaining bytes.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutCursor};
+///
+/// binary_layout!(header, BigEndian, {
+///   magic: [u8; 2],
+///   num_records: u8,
+/// });
+/// binary_layout!(record, BigEndian, {
+///   id: u8,
+///   value: u16,
+/// });
+///
+/// let data = [b'B', b'L', 2, 1, 0, 10, 2, 0, 20, 0xff];
+/// let mut cursor = LayoutCursor::new(&data);
+/// let header = cursor.next::<header::NestedView>().unwrap();
+/// assert_eq!(b"BL", header.magic());
+/// let mut sum = 0;
+/// for _ in 0..header.num_records().read() {
+///     let record = cursor.next::<record::NestedView>().unwrap();
+///     sum += record.value().read();
+/// }
+/// assert_eq!(30, sum);
+/// assert_eq!(9, cursor.position());
+/// assert_eq!(&[0xff], cursor.remaining());
+/// assert!(cursor.next::<record::NestedView>().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayoutCursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> LayoutCursor<'a> {
+    /// Create a cursor at the beginning of the data
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// The number of bytes read so far, i.e. the offset of the next layout in the data
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The bytes that weren't read yet
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+
+    /// Returns true if all bytes were read
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    /// Return a view of the layout at the current position and advance the position past it.
+    /// Layouts ending in a [LengthPrefixed](crate::LengthPrefixed) field end after its data, other open ended layouts take up all remaining bytes.
+    /// Fails with [ViewError::StorageTooShort] without advancing if the remaining bytes are too short for the layout.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<L: LayoutView<&'a [u8]>>(&mut self) -> Result<L::View, ViewError> {
+        let size = L::LAYOUT.size_in(self.remaining())?;
+        let view = L::new_view(&self.remaining()[..size]);
+        self.position += size;
+        Ok(view)
+    }
+
+    /// Return a view of the layout at the current position like [LayoutCursor::next], but without advancing the position
+    pub fn peek<L: LayoutView<&'a [u8]>>(&self) -> Result<L::View, ViewError> {
+        let remaining = self.remaining();
+        let size = L::LAYOUT.size_in(remaining)?;
+        Ok(L::new_view(&remaining[..size]))
+    }
+
+    /// Return the given number of bytes at the current position and advance the position past them, e.g. for a payload whose length
+    /// was read from a previous layout. Fails with [ViewError::StorageTooShort] without advancing if there aren't enough bytes left.
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], ViewError> {
+        let remaining = self.remaining();
+        if remaining.len() < len {
+            return Err(ViewError::StorageTooShort {
+                expected: len,
+                actual: remaining.len(),
+            });
+        }
+        self.position += len;
+        Ok(&remaining[..len])
+    }
+
+    /// Advance the position by the given number of bytes, e.g. to skip padding.
+    /// Fails with [ViewError::StorageTooShort] without advancing if there aren't enough bytes left.
+    pub fn skip(&mut self, len: usize) -> Result<(), ViewError> {
+        self.bytes(len).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutCursor;
+    use crate::prelude::*;
+    use crate::ViewError;
+
+    binary_layout!(header, LittleEndian, {
+        kind: u8,
+        length: u16,
+    });
+    binary_layout!(trailer, LittleEndian, {
+        kind: u8,
+        rest: [u8],
+    });
+
+    const DATA: [u8; 10] = [1, 2, 0, 0xaa, 0xbb, 2, 3, 0, 4, 5];
+
+    #[test]
+    fn next() {
+        let mut cursor = LayoutCursor::new(&DATA);
+        assert_eq!(0, cursor.position());
+        let first = cursor.next::<header::NestedView>().unwrap();
+        assert_eq!(1, first.kind().read());
+        assert_eq!(2, first.length().read());
+        assert_eq!(&[0xaa, 0xbb], cursor.bytes(2).unwrap());
+        let second = cursor.next::<header::NestedView>().unwrap();
+        assert_eq!(2, second.kind().read());
+        assert_eq!(3, second.length().read());
+        assert_eq!(8, cursor.position());
+        assert_eq!(&[4, 5], cursor.remaining());
+        assert!(!cursor.is_empty());
+    }
+
+    #[test]
+    fn open_ended_layout_takes_the_rest() {
+        let mut cursor = LayoutCursor::new(&DATA);
+        cursor.skip(7).unwrap();
+        let rest = cursor.next::<trailer::NestedView>().unwrap();
+        assert_eq!(0, rest.kind().read());
+        assert_eq!(&[4, 5], rest.rest());
+        assert_eq!(10, cursor.position());
+        assert!(cursor.is_empty());
+    }
+
+    binary_layout!(name, BigEndian, {
+        value: crate::LengthPrefixed<u8>,
+    });
+
+    #[test]
+    fn length_prefixed_layout_ends_after_its_data() {
+        let data = [2, b'a', b'b', 1, b'c', 0xff];
+        let mut cursor = LayoutCursor::new(&data);
+        let first = cursor.next::<name::NestedView>().unwrap();
+        assert_eq!(Ok(&b"ab"[..]), first.value().try_data());
+        assert_eq!(3, cursor.position());
+        assert_eq!(
+            Ok(&b"c"[..]),
+            cursor
+                .peek::<name::NestedView>()
+                .unwrap()
+                .value()
+                .try_data()
+        );
+        let second = cursor.next::<name::NestedView>().unwrap();
+        assert_eq!(Ok(&b"c"[..]), second.value().try_data());
+        assert_eq!(&[0xff], cursor.remaining());
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 256,
+                actual: 1
+            }),
+            cursor.next::<name::NestedView>().map(|_| ())
+        );
+        assert_eq!(5, cursor.position());
+    }
+
+    #[test]
+    fn peek() {
+        let mut cursor = LayoutCursor::new(&DATA);
+        assert_eq!(
+            1,
+            cursor.peek::<header::NestedView>().unwrap().kind().read()
+        );
+        assert_eq!(
+            1,
+            cursor.peek::<trailer::NestedView>().unwrap().kind().read()
+        );
+        assert_eq!(0, cursor.position());
+        assert_eq!(
+            2,
+            cursor.next::<header::NestedView>().unwrap().length().read()
+        );
+    }
+
+    #[test]
+    fn too_short() {
+        let mut cursor = LayoutCursor::new(&DATA);
+        cursor.skip(8).unwrap();
+        assert!(matches!(
+            cursor.next::<header::NestedView>(),
+            Err(ViewError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        assert_eq!(
+            ViewError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            },
+            cursor.bytes(3).unwrap_err()
+        );
+        assert!(cursor.skip(3).is_err());
+        assert_eq!(8, cursor.position());
+        cursor.skip(2).unwrap();
+        assert!(cursor.next::<trailer::NestedView>().is_err());
+        assert!(cursor.is_empty());
+    }
+}
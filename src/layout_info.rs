@@ -0,0 +1,1627 @@
+use core::fmt;
+use core::ops::Range;
+
+use crate::endianness::EndianKind;
+use crate::fields::bit_field::read_bits;
+use crate::fields::primitive::{decode_bcd, decode_varint, varint_len, zigzag_decode};
+use crate::ViewError;
+
+/// Runtime description of a layout. The [binary_layout!](crate::binary_layout!) macro generates a
+/// `LAYOUT` constant of this type for each layout, which allows tooling to inspect a layout
+/// without knowing it at compile time.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, EndianKind, FieldKind};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!("my_layout", my_layout::LAYOUT.name);
+/// assert_eq!(EndianKind::Big, my_layout::LAYOUT.endianness);
+/// assert_eq!(None, my_layout::LAYOUT.size);
+/// assert_eq!(3, my_layout::LAYOUT.fields.len());
+/// assert_eq!("field2", my_layout::LAYOUT.fields[1].name);
+/// assert_eq!("bool as u8", my_layout::LAYOUT.fields[1].type_name);
+/// assert_eq!(2, my_layout::LAYOUT.fields[1].offset);
+/// assert_eq!(Some(1), my_layout::LAYOUT.fields[1].size);
+/// assert_eq!(FieldKind::UnsignedInteger, my_layout::LAYOUT.fields[1].kind);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LayoutInfo {
+    /// Name of the layout, i.e. the name of the module generated by [binary_layout!](crate::binary_layout!)
+    pub name: &'static str,
+    /// Endianness of the layout
+    pub endianness: EndianKind,
+    /// Total size of the layout in number of bytes.
+    /// This is None if the layout ends with an open ended field like a byte slice.
+    pub size: Option<usize>,
+    /// The fields of the layout, in the order they were defined in
+    pub fields: &'static [FieldInfo],
+}
+
+/// Runtime description of a field in a layout, see [LayoutInfo].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldInfo {
+    /// Name of the field
+    pub name: &'static str,
+    /// The type of the field as written in the layout definition, e.g. `u16` or `bool as u8`
+    pub type_name: &'static str,
+    /// See [Field::OFFSET](crate::Field::OFFSET)
+    pub offset: usize,
+    /// See [Field::SIZE](crate::Field::SIZE)
+    pub size: Option<usize>,
+    /// What kind of data the field stores. For fields using a custom type via [LayoutAs](crate::LayoutAs),
+    /// this describes the underlying type.
+    pub kind: FieldKind,
+    /// Whether this is an [Optional](crate::Optional) field, which the data of the layout may end before
+    pub optional: bool,
+}
+
+impl FieldInfo {
+    /// Whether the data of the layout is long enough to store this field. This is only false for [Optional](crate::Optional)
+    /// fields in data that is long enough for the layout.
+    pub(crate) fn is_stored_in(&self, data: &[u8]) -> bool {
+        match self.size {
+            Some(size) => data.len() >= self.offset + size,
+            None => data.len() >= self.offset,
+        }
+    }
+}
+
+/// The kind of data stored in a field, see [FieldInfo::kind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldKind {
+    /// An unsigned integer like [u32] or [NonZeroU32](core::num::NonZeroU32)
+    UnsignedInteger,
+    /// A signed integer like [i32] or [NonZeroI32](core::num::NonZeroI32)
+    SignedInteger,
+    /// A floating point number like [f32], or an IEEE half precision `f16` with the `half` feature
+    Float,
+    /// A `bf16` brain floating point number with the `half` feature, i.e. the upper 16 bits of an [f32]
+    BFloat16,
+    /// The unit type `()`, which takes up zero bytes
+    Unit,
+    /// A byte array `[u8; N]` or an open ended byte slice `[u8]`
+    Bytes,
+    /// Reserved bytes that don't hold a value, see [Padding](crate::Padding)
+    Padding,
+    /// Boolean flags packed into bits, see [BitArray](crate::BitArray)
+    BitArray {
+        /// Number of flags
+        bits: usize,
+    },
+    /// A constant unsigned integer stored in the endianness of the layout, see [Magic](crate::Magic)
+    Magic {
+        /// The value the field has to store
+        value: u128,
+    },
+    /// An array of numbers like `[u16; 8]`, see [FieldArrayAccess](crate::FieldArrayAccess),
+    /// or an open ended array of numbers or nested layouts like `[u32]` or `[my_layout::NestedView]`.
+    /// Each element is stored with the endianness of the layout. Open ended arrays have a [FieldInfo::size] of `None`
+    /// and contain as many elements as fit into the rest of the storage.
+    Array {
+        /// The kind of the elements, i.e. [FieldKind::UnsignedInteger], [FieldKind::SignedInteger], [FieldKind::Float] or [FieldKind::Nested]
+        element: &'static FieldKind,
+        /// Number of bytes taken up by each element
+        element_size: usize,
+    },
+    /// A [LengthPrefixed](crate::LengthPrefixed) field, i.e. a length stored as an unsigned integer in the endianness of the layout,
+    /// followed by that many bytes. Like open ended fields, it has a [FieldInfo::size] of `None`.
+    LengthPrefixed {
+        /// Number of bytes of the length prefix
+        prefix_size: usize,
+    },
+    /// An unsigned integer stored as packed binary-coded decimal, see [Bcd](crate::Bcd).
+    /// Its bytes are stored in the endianness of the layout.
+    Bcd {
+        /// Number of decimal digits
+        digits: usize,
+    },
+    /// A variable length integer like [Leb128](crate::Leb128). Its number of bytes depends on its value,
+    /// so like open ended fields, it has a [FieldInfo::size] of `None`.
+    Varint(VarintEncoding),
+    /// A nested layout
+    Nested(&'static LayoutInfo),
+    /// A [BitField](crate::BitField) taking up some bits of the bytes at [FieldInfo::offset] and [FieldInfo::size].
+    /// Bits are numbered from the most significant bit of each byte in big endian layouts and from the least significant bit in little endian layouts.
+    Bits {
+        /// Position of the first bit of the field in the byte at [FieldInfo::offset]
+        bit_offset: usize,
+        /// Number of bits of the field
+        bits: usize,
+    },
+}
+
+/// How the value of a [FieldKind::Varint] field is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VarintEncoding {
+    /// Unsigned [LEB128](https://en.wikipedia.org/wiki/LEB128), see [Leb128](crate::Leb128)
+    Leb128,
+    /// Signed values mapped to unsigned ones with zigzag encoding and stored as LEB128, see [ZigZag](crate::ZigZag)
+    ZigZag,
+    /// Unsigned variable length quantity with the most significant bits first, see [Vlq](crate::Vlq)
+    Vlq,
+}
+
+/// A field value read at runtime based on its [FieldInfo], see [LayoutInfo::read_field].
+///
+/// Fields using a custom type via [LayoutAs](crate::LayoutAs) are read as their underlying type,
+/// e.g. `bool as u8` fields are read as [FieldValue::UnsignedInteger].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum FieldValue<'a> {
+    /// The value of a [FieldKind::UnsignedInteger], [FieldKind::Magic], [FieldKind::Bits], valid [FieldKind::Bcd] or unsigned [FieldKind::Varint] field
+    UnsignedInteger(u128),
+    /// The value of a [FieldKind::SignedInteger] or signed [FieldKind::Varint] field
+    SignedInteger(i128),
+    /// The value of a [FieldKind::Float] or [FieldKind::BFloat16] field. Values with less precision than [f64] are converted losslessly.
+    Float(f64),
+    /// The value of a [FieldKind::Unit] field
+    Unit,
+    /// The value of a [FieldKind::Bytes], [FieldKind::Padding] or [FieldKind::BitArray] field, or the data of a [FieldKind::LengthPrefixed] field
+    Bytes(&'a [u8]),
+    /// The value of a [FieldKind::Array] field
+    Array(ArrayValue<'a>),
+    /// The value of a [FieldKind::Nested] field, i.e. the nested layout and the data of the field
+    Nested(&'static LayoutInfo, &'a [u8]),
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by all field types so that [binary_layout!](crate::binary_layout!) can describe them in [FieldInfo::kind].
+pub trait FieldKindInfo {
+    /// The kind of data stored in the field
+    const KIND: FieldKind;
+    /// Whether the field is an [Optional](crate::Optional) field
+    const OPTIONAL: bool = false;
+    /// Whether the field is a [RelativeField](crate::RelativeField), i.e. after a [LengthPrefixed](crate::LengthPrefixed) or varint field
+    const RELATIVE: bool = false;
+
+    /// Offset of the field in the given storage. This is [Field::OFFSET](crate::Field::OFFSET), except for fields after
+    /// [LengthPrefixed](crate::LengthPrefixed) or varint fields, see [LayoutInfo::field_range_in].
+    /// Returns `None` if the storage ends before the end of one of those fields.
+    #[inline(always)]
+    fn offset_in(_storage: &[u8]) -> Option<usize>
+    where
+        Self: crate::Field,
+    {
+        Some(Self::OFFSET)
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Creates a [LayoutInfo], which [binary_layout!](crate::binary_layout!) can't do with a struct expression since it's `#[non_exhaustive]`.
+pub const fn new_layout_info(
+    name: &'static str,
+    endianness: EndianKind,
+    size: Option<usize>,
+    fields: &'static [FieldInfo],
+) -> LayoutInfo {
+    LayoutInfo {
+        name,
+        endianness,
+        size,
+        fields,
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Creates a [FieldInfo], which [binary_layout!](crate::binary_layout!) can't do with a struct expression since it's `#[non_exhaustive]`.
+pub const fn new_field_info(
+    name: &'static str,
+    type_name: &'static str,
+    offset: usize,
+    size: Option<usize>,
+    kind: FieldKind,
+    optional: bool,
+) -> FieldInfo {
+    FieldInfo {
+        name,
+        type_name,
+        offset,
+        size,
+        kind,
+        optional,
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Checks at compile time that the optional fields of a layout are at its end and don't follow fields whose length depends on the data.
+pub const fn check_optional_fields(layout: &LayoutInfo) {
+    let mut index = 1;
+    while index < layout.fields.len() {
+        assert!(
+            layout.fields[index].optional || !layout.fields[index - 1].optional,
+            "Optional fields must be at the end of a layout"
+        );
+        assert!(
+            !layout.fields[index].optional || !has_dynamic_len(&layout.fields[index - 1]),
+            "Error: Optional fields can't follow LengthPrefixed or varint fields"
+        );
+        index += 1;
+    }
+}
+
+/// Whether the number of bytes of the field depends on its data, i.e. whether it is a [FieldKind::LengthPrefixed] or [FieldKind::Varint] field.
+/// Fields after such fields are moved back by the bytes they take up beyond [dynamic_min_len].
+const fn has_dynamic_len(field: &FieldInfo) -> bool {
+    has_dynamic_len_kind(field.kind)
+}
+
+/// Internal function. Don't use this in user code.
+/// Whether the number of bytes of fields of this kind depends on their data, i.e. whether they are [FieldKind::LengthPrefixed] or [FieldKind::Varint] fields.
+pub const fn has_dynamic_len_kind(kind: FieldKind) -> bool {
+    matches!(
+        kind,
+        FieldKind::LengthPrefixed { .. } | FieldKind::Varint(_)
+    )
+}
+
+/// Internal function. Don't use this in user code.
+/// Whether the layout has fields after [FieldKind::LengthPrefixed] or [FieldKind::Varint] fields, whose offsets depend on the data.
+pub const fn has_fields_after_dynamic_len(layout: &LayoutInfo) -> bool {
+    let mut index = 1;
+    while index < layout.fields.len() {
+        if has_dynamic_len(&layout.fields[index - 1]) {
+            return true;
+        }
+        index += 1;
+    }
+    false
+}
+
+/// Number of bytes a field whose number of bytes depends on its data takes up at least, i.e. the length prefix of an empty
+/// [FieldKind::LengthPrefixed] field or the single byte of a small [FieldKind::Varint].
+const fn dynamic_min_len(kind: FieldKind) -> usize {
+    match kind {
+        FieldKind::LengthPrefixed { prefix_size } => prefix_size,
+        _ => 1,
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Natural alignment of a field in a layout declared as `aligned`, like `#[repr(C)]` would align it. Numbers are aligned to their size,
+/// arrays to their elements, nested layouts to their most aligned field and everything else to a single byte.
+pub const fn field_alignment(kind: FieldKind, size: Option<usize>) -> usize {
+    match (kind, size) {
+        (
+            FieldKind::UnsignedInteger
+            | FieldKind::SignedInteger
+            | FieldKind::Float
+            | FieldKind::BFloat16
+            | FieldKind::Magic { .. },
+            Some(size),
+        ) => {
+            // Odd sized numbers like u24 are aligned to the largest power of two their size is a multiple of
+            let mut alignment = 1;
+            while alignment < 16 && size % (alignment * 2) == 0 {
+                alignment *= 2;
+            }
+            alignment
+        }
+        (
+            FieldKind::Array {
+                element,
+                element_size,
+            },
+            _,
+        ) => field_alignment(*element, Some(element_size)),
+        (FieldKind::Nested(layout), _) => fields_alignment(layout.fields),
+        _ => 1,
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Largest natural alignment of the fields, see [field_alignment].
+pub const fn fields_alignment(fields: &[FieldInfo]) -> usize {
+    let mut alignment = 1;
+    let mut index = 0;
+    while index < fields.len() {
+        let field_alignment = field_alignment(fields[index].kind, fields[index].size);
+        if field_alignment > alignment {
+            alignment = field_alignment;
+        }
+        index += 1;
+    }
+    alignment
+}
+
+/// Internal function. Don't use this in user code.
+/// Number of bytes of the layout that aren't part of any field.
+pub const fn padding_size(layout: &LayoutInfo) -> usize {
+    let mut end = 0;
+    let mut padding = 0;
+    let mut index = 0;
+    while index < layout.fields.len() {
+        let field = &layout.fields[index];
+        if field.offset > end {
+            padding += field.offset - end;
+        }
+        if let Some(size) = field.size {
+            if field.offset + size > end {
+                end = field.offset + size;
+            }
+        }
+        index += 1;
+    }
+    if let Some(size) = layout.size {
+        if size > end {
+            padding += size - end;
+        }
+    }
+    padding
+}
+
+/// Implemented by the `NestedView` type that [binary_layout!](crate::binary_layout!) generates for each layout,
+/// which allows writing generic code over any layout, e.g. framers, storage engines or loggers.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Layout, LayoutView};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: u32,
+/// });
+///
+/// fn describe<L: Layout>() -> String {
+///     format!("{} has {} fields and {:?} bytes", L::NAME, L::FIELD_COUNT, L::SIZE)
+/// }
+///
+/// fn parse<'a, L: LayoutView<&'a [u8]>>(data: &'a [u8]) -> Option<L::View> {
+///     match L::SIZE {
+///         Some(size) if data.len() < size => None,
+///         _ => Some(L::new_view(data)),
+///     }
+/// }
+///
+/// assert_eq!("my_layout has 2 fields and Some(6) bytes", describe::<my_layout::NestedView>());
+/// let view = parse::<my_layout::NestedView>(&[0, 1, 0, 0, 0, 2]).unwrap();
+/// assert_eq!(2, view.field2().read());
+/// assert!(parse::<my_layout::NestedView>(&[0, 1]).is_none());
+/// ```
+pub trait Layout {
+    /// Name of the layout
+    const NAME: &'static str;
+
+    /// Size of the layout, or `None` if the layout is open ended
+    const SIZE: Option<usize>;
+
+    /// Number of fields of the layout
+    const FIELD_COUNT: usize;
+
+    /// Runtime description of the layout and its fields
+    const LAYOUT: &'static LayoutInfo;
+}
+
+/// Extends [Layout] with a constructor for views of the layout over storage of type `S`, see [Layout] for an example.
+pub trait LayoutView<S: AsRef<[u8]>>: Layout {
+    /// The `View` type of the layout
+    type View;
+
+    /// Create a view of the layout over the storage, like `View::new`
+    fn new_view(storage: S) -> Self::View;
+
+    /// Destroy the view and return the storage, like `View::into_storage`
+    fn into_storage(view: Self::View) -> S;
+}
+
+/// Extends [Layout] with the size of layouts that don't end with an open ended field as a `usize`, so generic code can require
+/// layouts with a static size instead of unwrapping [Layout::SIZE] at runtime.
+///
+/// Like [Layout], this is implemented by the `NestedView` of each layout, but using [SizedLayout::STATIC_SIZE] of a layout ending
+/// with an open ended field or with [LengthPrefixed](crate::LengthPrefixed) or varint fields fails to compile, so generic code using it
+/// can only be instantiated with layouts that have a static size.
+///
+/// Note that `macro_rules!` can't tell whether a layout has a static size, e.g. when it nests an open ended layout,
+/// so the trait is implemented for all layouts and an `L: SizedLayout` bound alone accepts any layout.
+/// Only evaluating [SizedLayout::STATIC_SIZE] for a layout without a static size is rejected, when the generic code is instantiated.
+/// Generic code that relies on the bound without using the size can evaluate it anyway, e.g. with `let _ = L::STATIC_SIZE;`.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutView, SizedLayout};
+///
+/// binary_layout!(header, BigEndian, {
+///   kind: u8,
+///   length: u16,
+/// });
+///
+/// fn read_header<L: SizedLayout + LayoutView<Vec<u8>>>(mut reader: impl std::io::Read) -> std::io::Result<L::View> {
+///     let mut storage = vec![0; L::STATIC_SIZE];
+///     reader.read_exact(&mut storage)?;
+///     Ok(L::new_view(storage))
+/// }
+///
+/// let view = read_header::<header::NestedView>(&[1, 0, 2, 0xff][..]).unwrap();
+/// assert_eq!(2, view.length().read());
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::{prelude::*, SizedLayout};
+///
+/// binary_layout!(packet, BigEndian, {
+///   length: u16,
+///   payload: [u8],
+/// });
+///
+/// fn size<L: SizedLayout>() -> usize {
+///     L::STATIC_SIZE
+/// }
+///
+/// // Fails to compile because packet ends with an open ended field
+/// size::<packet::NestedView>();
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::{prelude::*, LengthPrefixed, SizedLayout};
+///
+/// binary_layout!(message, BigEndian, {
+///   name: LengthPrefixed<u8>,
+///   crc: u32,
+/// });
+///
+/// fn require_sized<L: SizedLayout>() {
+///     let _ = L::STATIC_SIZE;
+/// }
+///
+/// // Fails to compile because the size of message depends on the length prefix
+/// require_sized::<message::NestedView>();
+/// ```
+pub trait SizedLayout: Layout {
+    /// Size of the layout
+    const STATIC_SIZE: usize;
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the `View` types generated by [binary_layout!](crate::binary_layout!) so that generic code can access their layout and data.
+pub trait ViewInfo {
+    /// The layout of the view
+    const LAYOUT: &'static LayoutInfo;
+
+    /// The data the view is based on
+    fn data(&self) -> &[u8];
+}
+
+/// Internal trait. Don't use this in user code.
+/// Like [ViewInfo], but for views with write access to their data.
+pub trait ViewInfoMut: ViewInfo {
+    /// The data the view is based on, with write access
+    fn data_mut(&mut self) -> &mut [u8];
+}
+
+impl LayoutInfo {
+    /// Look up a field by its name
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   field1: u16,
+    ///   field2: u32,
+    /// });
+    ///
+    /// assert_eq!(2, my_layout::LAYOUT.field("field2").unwrap().offset);
+    /// assert_eq!(None, my_layout::LAYOUT.field("field3"));
+    /// ```
+    pub fn field(&self, name: &str) -> Option<&'static FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Number of bytes a view of this layout needs at least, not counting the data of open ended fields or optional fields.
+    /// This is what `View::try_new` checks the storage against, and the same as the `MIN_SIZE` constant of the layout.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   field1: u16,
+    ///   tail: [u8],
+    /// });
+    ///
+    /// assert_eq!(2, my_layout::LAYOUT.min_size());
+    /// ```
+    pub const fn min_size(&self) -> usize {
+        // Optional fields are at the end, so the first one ends the data that has to be stored
+        let mut index = 0;
+        while index < self.fields.len() {
+            if self.fields[index].optional {
+                return self.fields[index].offset;
+            }
+            index += 1;
+        }
+        match (self.size, self.fields.last()) {
+            (Some(size), _) => size,
+            (None, Some(last)) => match last.kind {
+                FieldKind::Nested(nested) => last.offset + nested.min_size(),
+                FieldKind::LengthPrefixed { prefix_size } => last.offset + prefix_size,
+                FieldKind::Varint(_) => last.offset + 1,
+                // Layouts with fields after LengthPrefixed or varint fields don't have a static size either
+                _ => match last.size {
+                    Some(size) => last.offset + size,
+                    None => last.offset,
+                },
+            },
+            (None, None) => 0,
+        }
+    }
+
+    /// Checks that all [FieldKind::Magic] fields of this layout and its nested layouts store their value,
+    /// assuming the data is long enough for the layout.
+    pub(crate) fn check_magics(&self, data: &[u8]) -> Result<(), ViewError> {
+        let mut stored_fields = self
+            .fields
+            .iter()
+            .filter(|field| self.stored_range(field, data).is_some());
+        stored_fields.try_for_each(|field| match field.kind {
+            FieldKind::Magic { value } => match self.read_field(field, data) {
+                FieldValue::UnsignedInteger(actual) if actual != value => {
+                    Err(ViewError::InvalidMagic {
+                        field: field.name,
+                        expected: value,
+                        actual,
+                    })
+                }
+                _ => Ok(()),
+            },
+            FieldKind::Nested(nested) => match self.read_field(field, data) {
+                FieldValue::Nested(_, bytes) => nested.check_magics(bytes),
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        })
+    }
+
+    /// Number of bytes a view of this layout takes up in the given data, which starts at the beginning of the layout.
+    /// This is [LayoutInfo::size] for layouts with a static size. For layouts ending in a [FieldKind::LengthPrefixed] field,
+    /// it's the end of the length prefixed data, and for other open ended layouts, it's the length of the data.
+    /// For layouts with fields after [FieldKind::LengthPrefixed] or [FieldKind::Varint] fields, it's the end of the last field.
+    /// If the data ends before the [Optional](crate::Optional) fields of the layout, it's the end of the last optional field that is stored.
+    /// Fails if the data is too short for the layout.
+    pub(crate) fn size_in(&self, data: &[u8]) -> Result<usize, ViewError> {
+        let min_size = self.min_size();
+        if data.len() < min_size {
+            return Err(ViewError::StorageTooShort {
+                expected: min_size,
+                actual: data.len(),
+            });
+        }
+        match (self.size, self.fields.last()) {
+            (Some(size), _) if data.len() >= size => Ok(size),
+            (Some(_), _) => Ok(self
+                .fields
+                .iter()
+                .filter(|field| field.optional)
+                .take_while(|field| field.is_stored_in(data))
+                .last()
+                .map_or(min_size, |field| field.offset + field.size.unwrap_or(0))),
+            (None, Some(last)) => {
+                let offset = last.offset + self.shift_in(last.offset, data)?;
+                let end = match (last.kind, last.size) {
+                    (FieldKind::Nested(nested), None) => {
+                        return Ok(offset + nested.size_in(&data[offset..])?);
+                    }
+                    (FieldKind::LengthPrefixed { prefix_size }, None) => {
+                        length_prefixed_end(self.endianness, prefix_size, data, offset)?
+                    }
+                    (FieldKind::Varint(_), None) => varint_end(data, offset)?,
+                    (_, Some(size)) => offset + size,
+                    (_, None) => data.len().max(offset),
+                };
+                if data.len() < end {
+                    return Err(ViewError::StorageTooShort {
+                        expected: end,
+                        actual: data.len(),
+                    });
+                }
+                Ok(end)
+            }
+            (None, None) => Ok(data.len()),
+        }
+    }
+
+    /// Number of bytes that the [FieldKind::LengthPrefixed] and [FieldKind::Varint] fields before the given offset take up in the data
+    /// beyond their minimal size, i.e. how far the data moves fields at that offset back from their [FieldInfo::offset].
+    /// Fails if the data ends before the end of one of those fields.
+    pub(crate) fn shift_in(&self, offset: usize, data: &[u8]) -> Result<usize, ViewError> {
+        let mut shift = 0;
+        for field in self.fields.iter() {
+            if field.offset >= offset || !has_dynamic_len(field) {
+                continue;
+            }
+            let start = field.offset + shift;
+            let end = match field.kind {
+                FieldKind::LengthPrefixed { prefix_size } => {
+                    length_prefixed_end(self.endianness, prefix_size, data, start)?
+                }
+                _ => varint_end(data, start)?,
+            };
+            shift += end - start - dynamic_min_len(field.kind);
+        }
+        Ok(shift)
+    }
+
+    /// Byte range of a field in the given data like [LayoutInfo::field_range_in], but the range of fields without a static size
+    /// extends to the end of the data. Returns `None` if the data ends before the start of the field or before its end, if it has a static size.
+    pub(crate) fn stored_range(&self, field: &FieldInfo, data: &[u8]) -> Option<Range<usize>> {
+        let start = field.offset + self.shift_in(field.offset, data).ok()?;
+        let end = field.size.map_or(data.len(), |size| start + size);
+        (start <= end && end <= data.len()).then(|| start..end)
+    }
+
+    /// Byte range of a field of this layout in the given data, which starts at the beginning of the layout.
+    /// The range of [FieldKind::LengthPrefixed] and [FieldKind::Varint] fields ends after their data, the range of other fields
+    /// without a static size at the end of the data. Returns `None` if the data ends before the end of the field.
+    ///
+    /// The number of bytes of [FieldKind::LengthPrefixed] and [FieldKind::Varint] fields depends on their data,
+    /// so the fields after them start at [FieldInfo::offset] plus the number of bytes those take up beyond their minimal size.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, LengthPrefixed};
+    ///
+    /// binary_layout!(message, BigEndian, {
+    ///   kind: u8,
+    ///   name: LengthPrefixed<u8>,
+    ///   crc: u32,
+    /// });
+    ///
+    /// fn main() {
+    ///   let data = [1, 3, b'a', b'b', b'c', 0, 0, 0, 7];
+    ///   let layout = &message::LAYOUT;
+    ///   assert_eq!(2, layout.fields[2].offset);
+    ///   assert_eq!(Some(1..5), layout.field_range_in(&layout.fields[1], &data));
+    ///   assert_eq!(Some(5..9), layout.field_range_in(&layout.fields[2], &data));
+    ///   assert_eq!(None, layout.field_range_in(&layout.fields[2], &data[..8]));
+    /// }
+    /// ```
+    pub fn field_range_in(&self, field: &FieldInfo, data: &[u8]) -> Option<Range<usize>> {
+        let start = field.offset + self.shift_in(field.offset, data).ok()?;
+        let end = match (field.kind, field.size) {
+            (_, Some(size)) => start + size,
+            (FieldKind::LengthPrefixed { prefix_size }, None) => {
+                length_prefixed_end(self.endianness, prefix_size, data, start).ok()?
+            }
+            (FieldKind::Varint(_), None) => varint_end(data, start).ok()?,
+            (_, None) => data.len(),
+        };
+        (start <= end && end <= data.len()).then(|| start..end)
+    }
+
+    /// Look up the offset and size of a field by its name. The size is `None` for open ended fields.
+    ///
+    /// Unlike [LayoutInfo::field], this is a `const fn`, so it can compute offsets in const contexts.
+    /// Each layout generated by [binary_layout!](crate::binary_layout!) also has an `offset_of` function doing the same lookup.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   field1: u16,
+    ///   field2: u32,
+    ///   tail: [u8],
+    /// });
+    ///
+    /// const FIELD2: Option<(usize, Option<usize>)> = my_layout::LAYOUT.offset_of("field2");
+    /// assert_eq!(Some((2, Some(4))), FIELD2);
+    /// assert_eq!(Some((6, None)), my_layout::offset_of("tail"));
+    /// assert_eq!(None, my_layout::offset_of("field3"));
+    /// ```
+    pub const fn offset_of(&self, name: &str) -> Option<(usize, Option<usize>)> {
+        let mut index = 0;
+        while index < self.fields.len() {
+            let field = &self.fields[index];
+            if str_eq(field.name, name) {
+                return Some((field.offset, field.size));
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// Read the value of a field of this layout from the given data, which starts at the beginning of the layout.
+    ///
+    /// # Panics
+    /// If the data is too short to contain the field.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FieldValue};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   field1: u16,
+    ///   field2: i8,
+    ///   tail: [u8],
+    /// });
+    ///
+    /// let data = [1, 2, 0xff, 3, 4];
+    /// let layout = &my_layout::LAYOUT;
+    /// assert_eq!(FieldValue::UnsignedInteger(258), layout.read_field(&layout.fields[0], &data));
+    /// assert_eq!(FieldValue::SignedInteger(-1), layout.read_field(&layout.fields[1], &data));
+    /// assert_eq!(FieldValue::Bytes(&[3, 4]), layout.read_field(&layout.fields[2], &data));
+    /// ```
+    pub fn read_field<'a>(&self, field: &FieldInfo, data: &'a [u8]) -> FieldValue<'a> {
+        let offset = match self.shift_in(field.offset, data) {
+            Ok(shift) => field.offset + shift,
+            Err(_) => panic!(
+                "The data is too short for the fields before `{}`",
+                field.name
+            ),
+        };
+        let bytes = match field.size {
+            Some(size) => &data[offset..offset + size],
+            None => &data[offset..],
+        };
+        read_value(self.endianness, field.kind, bytes)
+    }
+
+    /// Look up a field by its name and read its value from the data at runtime like [LayoutInfo::read_field],
+    /// e.g. for tools that inspect data of layouts they don't know at compile time. Views offer the same as `View::field_value(name)`.
+    /// Returns `None` if the layout doesn't have a field of that name or the data ends before the end of the field,
+    /// e.g. for [Optional](crate::Optional) fields that aren't stored.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FieldValue};
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   field1: u16,
+    ///   field2: f32,
+    /// });
+    ///
+    /// let data = [1, 0, 0, 0, 0x80, 0x3f];
+    /// assert_eq!(Some(FieldValue::Float(1.0)), my_layout::LAYOUT.read_field_by_name("field2", &data));
+    /// assert_eq!(None, my_layout::LAYOUT.read_field_by_name("field3", &data));
+    /// assert_eq!(None, my_layout::LAYOUT.read_field_by_name("field2", &data[..4]));
+    /// ```
+    pub fn read_field_by_name<'a>(&self, name: &str, data: &'a [u8]) -> Option<FieldValue<'a>> {
+        let field = self.field(name)?;
+        self.stored_range(field, data)?;
+        Some(self.read_field(field, data))
+    }
+}
+
+/// Converts the bits of an IEEE half precision float, which can't lose precision
+fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = u64::from((bits >> 10) & 0x1f);
+    let mantissa = u64::from(bits & 0x3ff);
+    match exponent {
+        0 => sign * mantissa as f64 / (1u64 << 24) as f64,
+        0x1f if mantissa == 0 => sign * f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => sign * f64::from_bits(((exponent + 1023 - 15) << 52) | (mantissa << 42)),
+    }
+}
+
+/// Reads a value of the given kind from the bytes it's stored in
+fn read_value(endianness: EndianKind, kind: FieldKind, bytes: &[u8]) -> FieldValue<'_> {
+    match kind {
+        FieldKind::UnsignedInteger | FieldKind::Magic { .. } => {
+            FieldValue::UnsignedInteger(read_uint(endianness, bytes))
+        }
+        FieldKind::SignedInteger => {
+            let shift = 128 - 8 * bytes.len() as u32;
+            let value = read_uint(endianness, bytes);
+            FieldValue::SignedInteger(((value << shift) as i128) >> shift)
+        }
+        FieldKind::Float => {
+            let value = read_uint(endianness, bytes);
+            if bytes.len() == 2 {
+                FieldValue::Float(f16_to_f64(value as u16))
+            } else if bytes.len() == 4 {
+                FieldValue::Float(f32::from_bits(value as u32).into())
+            } else {
+                FieldValue::Float(f64::from_bits(value as u64))
+            }
+        }
+        FieldKind::BFloat16 => {
+            let value = read_uint(endianness, bytes) as u32;
+            FieldValue::Float(f32::from_bits(value << 16).into())
+        }
+        FieldKind::Unit => FieldValue::Unit,
+        FieldKind::Bytes | FieldKind::Padding | FieldKind::BitArray { .. } => {
+            FieldValue::Bytes(bytes)
+        }
+        FieldKind::Array {
+            element,
+            element_size,
+        } => FieldValue::Array(ArrayValue {
+            element,
+            element_size,
+            endianness,
+            data: bytes,
+        }),
+        FieldKind::LengthPrefixed { prefix_size } => {
+            let (prefix, data) = bytes.split_at(prefix_size);
+            let len = usize::try_from(read_uint(endianness, prefix)).unwrap_or(usize::MAX);
+            FieldValue::Bytes(&data[..len.min(data.len())])
+        }
+        // Invalid BCD data is shown as the bytes it's stored in
+        FieldKind::Bcd { digits } => match decode_bcd(bytes, endianness.is_big_endian(), digits) {
+            Some(value) => FieldValue::UnsignedInteger(value),
+            None => FieldValue::Bytes(bytes),
+        },
+        // Varints that can't be decoded are shown as the bytes they would be stored in
+        FieldKind::Varint(encoding) => match decode_varint(encoding, bytes, 128) {
+            Ok((value, _)) if encoding == VarintEncoding::ZigZag => {
+                FieldValue::SignedInteger(zigzag_decode(value))
+            }
+            Ok((value, _)) => FieldValue::UnsignedInteger(value),
+            Err(_) => FieldValue::Bytes(bytes),
+        },
+        FieldKind::Nested(layout) => FieldValue::Nested(layout, bytes),
+        FieldKind::Bits { bit_offset, bits } => FieldValue::UnsignedInteger(read_bits(
+            bytes,
+            endianness.is_big_endian(),
+            bit_offset,
+            bits,
+        )),
+    }
+}
+
+/// The value of a [FieldKind::Array] field, see [FieldValue::Array].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayValue<'a> {
+    element: &'static FieldKind,
+    element_size: usize,
+    endianness: EndianKind,
+    data: &'a [u8],
+}
+
+impl<'a> ArrayValue<'a> {
+    /// The number of elements in the array.
+    /// For open ended arrays, these are all whole elements fitting into the data of the field.
+    pub fn len(&self) -> usize {
+        self.data.len().checked_div(self.element_size).unwrap_or(0)
+    }
+
+    /// Returns true if the array doesn't have any elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The value of the element with the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<FieldValue<'a>> {
+        if index < self.len() {
+            let size = self.element_size;
+            let bytes = &self.data[index * size..(index + 1) * size];
+            Some(read_value(self.endianness, *self.element, bytes))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the values of all elements
+    pub fn iter(&self) -> impl Iterator<Item = FieldValue<'a>> {
+        let array = *self;
+        (0..self.len()).filter_map(move |index| array.get(index))
+    }
+}
+
+const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < lhs.len() {
+        if lhs[index] != rhs[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Formats the layout as a table listing offset, size, name and type of each field,
+/// which is useful in diagnostics and error reports.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   field1: u16,
+///   field2: bool as u8,
+///   tail: [u8],
+/// });
+///
+/// assert_eq!(
+///     r#"my_layout: big endian, open ended
+/// offset  size  name    type
+///      0     2  field1  u16
+///      2     1  field2  bool as u8
+///      3     -  tail    [u8]
+/// "#,
+///     format!("{}", my_layout::LAYOUT),
+/// );
+/// ```
+impl fmt::Display for LayoutInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let endianness = match self.endianness {
+            EndianKind::Big => "big endian",
+            EndianKind::Little => "little endian",
+            EndianKind::Native => "native endian",
+        };
+        match self.size {
+            Some(size) => writeln!(f, "{}: {}, {} bytes", self.name, endianness, size)?,
+            None => writeln!(f, "{}: {}, open ended", self.name, endianness)?,
+        }
+
+        let offset_width = self
+            .fields
+            .iter()
+            .map(|field| num_digits(field.offset))
+            .fold("offset".len(), usize::max);
+        let size_width = self
+            .fields
+            .iter()
+            .map(|field| field.size.map_or(1, num_digits))
+            .fold("size".len(), usize::max);
+        let name_width = self
+            .fields
+            .iter()
+            .map(|field| field.name.len())
+            .fold("name".len(), usize::max);
+
+        writeln!(
+            f,
+            "{:>ow$}  {:>sw$}  {:nw$}  type",
+            "offset",
+            "size",
+            "name",
+            ow = offset_width,
+            sw = size_width,
+            nw = name_width,
+        )?;
+        for field in self.fields {
+            write!(f, "{:>ow$}  ", field.offset, ow = offset_width)?;
+            match field.size {
+                Some(size) => write!(f, "{:>sw$}  ", size, sw = size_width)?,
+                None => write!(f, "{:>sw$}  ", "-", sw = size_width)?,
+            }
+            writeln!(
+                f,
+                "{:nw$}  {}",
+                field.name,
+                field.type_name,
+                nw = name_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the value like its [Debug](fmt::Debug) representation, but byte slices are shown as hex.
+impl fmt::Display for FieldValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::UnsignedInteger(value) => write!(f, "{}", value),
+            FieldValue::SignedInteger(value) => write!(f, "{}", value),
+            FieldValue::Float(value) => write!(f, "{:?}", value),
+            FieldValue::Unit => write!(f, "()"),
+            FieldValue::Array(array) => {
+                write!(f, "[")?;
+                for (index, element) in array.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            FieldValue::Bytes(bytes) | FieldValue::Nested(_, bytes) => {
+                write!(f, "[")?;
+                write_hex(f, bytes)?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Writes the bytes as hex separated by spaces
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+/// Internal function. Don't use this in user code.
+/// Implements the [PartialEq] implementation of the views generated by [binary_layout!](crate::binary_layout!),
+/// which compares the bytes of each field, so bytes that aren't part of any field, e.g. after the end of the layout, don't matter.
+pub fn views_eq(layout: &LayoutInfo, lhs: &[u8], rhs: &[u8]) -> bool {
+    // Data too short for the layout is compared as far as it goes
+    let lhs = &lhs[..layout.size_in(lhs).unwrap_or(lhs.len())];
+    let rhs = &rhs[..layout.size_in(rhs).unwrap_or(rhs.len())];
+    layout.fields.iter().all(|field| {
+        match (
+            layout.stored_range(field, lhs),
+            layout.stored_range(field, rhs),
+        ) {
+            (Some(lhs_range), Some(rhs_range)) => lhs[lhs_range] == rhs[rhs_range],
+            (lhs_range, rhs_range) => lhs_range.is_some() == rhs_range.is_some(),
+        }
+    })
+}
+
+/// Byte slices longer than this are abbreviated in the [Debug](fmt::Debug) output of views
+const DEBUG_MAX_BYTES: usize = 16;
+
+/// Internal function. Don't use this in user code.
+/// Implements the [Debug](fmt::Debug) output of the views generated by [binary_layout!](crate::binary_layout!),
+/// which shows the name and value of each field.
+pub fn fmt_view(layout: &LayoutInfo, data: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&DebugView { layout, data }, f)
+}
+
+struct DebugView<'a> {
+    layout: &'a LayoutInfo,
+    data: &'a [u8],
+}
+
+impl fmt::Debug for DebugView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Fields the data is too short for are left out instead of panicking
+        let long_enough = self.layout.size_in(self.data).is_ok();
+        let mut debug = f.debug_struct(self.layout.name);
+        let mut complete = true;
+        for field in self.layout.fields {
+            let stored = self.layout.stored_range(field, self.data).is_some();
+            if stored && (long_enough || field.size.is_some()) {
+                debug.field(
+                    field.name,
+                    &DebugValue(self.layout.read_field(field, self.data)),
+                );
+            } else {
+                complete = false;
+            }
+        }
+        if complete {
+            debug.finish()
+        } else {
+            debug.finish_non_exhaustive()
+        }
+    }
+}
+
+struct DebugValue<'a>(FieldValue<'a>);
+
+impl fmt::Debug for DebugValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            FieldValue::Array(array) => f
+                .debug_list()
+                .entries(array.iter().map(DebugValue))
+                .finish(),
+            FieldValue::Nested(layout, data) => fmt::Debug::fmt(&DebugView { layout, data }, f),
+            FieldValue::Bytes(bytes) if bytes.len() > DEBUG_MAX_BYTES => {
+                write!(f, "[")?;
+                write_hex(f, &bytes[..DEBUG_MAX_BYTES])?;
+                write!(f, " .. {} bytes]", bytes.len())
+            }
+            value => write!(f, "{}", value),
+        }
+    }
+}
+
+/// End of the data of the [FieldKind::LengthPrefixed] field at the offset, failing if the data ends before it
+fn length_prefixed_end(
+    endianness: EndianKind,
+    prefix_size: usize,
+    data: &[u8],
+    offset: usize,
+) -> Result<usize, ViewError> {
+    let start = offset + prefix_size;
+    if data.len() < start {
+        return Err(ViewError::StorageTooShort {
+            expected: start,
+            actual: data.len(),
+        });
+    }
+    let len = read_uint(endianness, &data[offset..start]);
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| start.checked_add(len))
+        .unwrap_or(usize::MAX);
+    if data.len() < end {
+        return Err(ViewError::StorageTooShort {
+            expected: end,
+            actual: data.len(),
+        });
+    }
+    Ok(end)
+}
+
+/// End of the [FieldKind::Varint] field at the offset, failing if the data ends before its last byte
+fn varint_end(data: &[u8], offset: usize) -> Result<usize, ViewError> {
+    match data.get(offset..).and_then(varint_len) {
+        Some(len) => Ok(offset + len),
+        None => Err(ViewError::StorageTooShort {
+            expected: data.len().max(offset) + 1,
+            actual: data.len(),
+        }),
+    }
+}
+
+fn read_uint(endianness: EndianKind, bytes: &[u8]) -> u128 {
+    let big_endian = match endianness {
+        EndianKind::Big => true,
+        EndianKind::Little => false,
+        EndianKind::Native => cfg!(target_endian = "big"),
+    };
+    let fold = |value: u128, byte: &u8| (value << 8) | u128::from(*byte);
+    if big_endian {
+        bytes.iter().fold(0, fold)
+    } else {
+        bytes.iter().rev().fold(0, fold)
+    }
+}
+
+fn num_digits(mut value: usize) -> usize {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{read_value, FieldKind, FieldValue};
+    use crate::prelude::*;
+    use crate::{EndianKind, ViewError};
+
+    binary_layout!(inner, BigEndian, {
+        value: u8,
+    });
+
+    #[test]
+    fn display_empty_layout() {
+        binary_layout!(empty, NativeEndian, {});
+        assert_eq!(
+            "empty: native endian, 0 bytes\noffset  size  name  type\n",
+            format!("{}", empty::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn display_aligns_columns() {
+        binary_layout!(my_layout, LittleEndian, {
+            a: [u8; 1234567],
+            field_with_long_name: u16,
+            b: u8,
+        });
+        assert_eq!(
+            r#"my_layout: little endian, 1234570 bytes
+ offset     size  name                  type
+      0  1234567  a                     [u8; 1234567]
+1234567        2  field_with_long_name  u16
+1234569        1  b                     u8
+"#,
+            format!("{}", my_layout::LAYOUT)
+        );
+    }
+
+    #[test]
+    fn debug_view() {
+        binary_layout!(my_layout, BigEndian, {
+            id: u16,
+            offset: i8,
+            ratio: f32,
+            values: [u16; 2],
+            nested: inner::NestedView,
+            payload: [u8],
+        });
+        let mut storage = [0; 27];
+        storage[..12].copy_from_slice(&[1, 2, 0xff, 0x3f, 0x80, 0, 0, 0, 3, 0, 4, 5]);
+        storage[12..]
+            .iter_mut()
+            .zip(1..)
+            .for_each(|(byte, value)| *byte = value);
+        assert_eq!(
+            "my_layout { id: 258, offset: -1, ratio: 1.0, values: [3, 4], nested: inner { value: 5 }, \
+             payload: [01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f] }",
+            format!("{:?}", my_layout::View::new(&storage[..]))
+        );
+        assert_eq!(
+            "my_layout { id: 258, offset: -1, ratio: 1.0, values: [3, 4], nested: inner { value: 5 }, \
+             payload: [01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f 10 .. 17 bytes] }",
+            format!("{:?}", my_layout::View::new([&storage[..], &[16, 17]].concat()))
+        );
+        assert_eq!(
+            "my_layout { id: 258, offset: -1, .. }",
+            format!("{:?}", my_layout::View::new(&storage[..4]))
+        );
+        assert!(format!("{:#?}", my_layout::View::new(&storage[..]))
+            .starts_with("my_layout {\n    id: 258,\n"));
+    }
+
+    #[test]
+    fn views_eq() {
+        binary_layout!(my_layout, LittleEndian, {
+            id: u16,
+            reserved @ 4: u8,
+            nested: inner::NestedView,
+            payload: [u8],
+        });
+        let view = my_layout::View::new([1, 2, 0, 0, 3, 4, 5]);
+        assert_eq!(view, my_layout::View::new(vec![1, 2, 0, 0, 3, 4, 5]));
+        // Bytes that aren't part of a field don't matter
+        assert_eq!(view, my_layout::View::new(&[1, 2, 9, 9, 3, 4, 5][..]));
+        assert_ne!(view, my_layout::View::new([1, 2, 0, 0, 3, 9, 5]));
+        assert_ne!(view, my_layout::View::new([1, 2, 0, 0, 3, 4, 5, 6]));
+        assert_ne!(view, my_layout::View::new([1, 2, 0, 0, 3]));
+        assert_eq!(
+            my_layout::View::new([1, 2, 0]),
+            my_layout::View::new([1, 2, 0, 0])
+        );
+    }
+
+    #[test]
+    fn read_field_values() {
+        binary_layout!(my_layout, LittleEndian, {
+            unsigned: u32,
+            signed: i16,
+            float32: f32,
+            float64: f64,
+            unit: (),
+            non_zero: core::num::NonZeroU16,
+            flag: bool as u8,
+            nested: inner::NestedView,
+            big: i128,
+        });
+        let mut data = vec![0; my_layout::SIZE.unwrap()];
+        let mut view = my_layout::View::new(&mut data);
+        view.unsigned_mut().write(0xdead_beef);
+        view.signed_mut().write(-300);
+        view.float32_mut().write(1.5);
+        view.float64_mut().write(-0.25);
+        view.non_zero_mut()
+            .try_write(core::num::NonZeroU16::new(7).unwrap())
+            .unwrap();
+        view.flag_mut().try_write(true).unwrap();
+        view.nested_mut().value_mut().write(5);
+        view.big_mut().write(i128::MIN);
+
+        let layout = &my_layout::LAYOUT;
+        let values: Vec<_> = layout
+            .fields
+            .iter()
+            .map(|field| layout.read_field(field, &data))
+            .collect();
+        assert_eq!(
+            vec![
+                FieldValue::UnsignedInteger(0xdead_beef),
+                FieldValue::SignedInteger(-300),
+                FieldValue::Float(1.5),
+                FieldValue::Float(-0.25),
+                FieldValue::Unit,
+                FieldValue::UnsignedInteger(7),
+                FieldValue::UnsignedInteger(1),
+                FieldValue::Nested(&inner::LAYOUT, &[5]),
+                FieldValue::SignedInteger(i128::MIN),
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn read_field_by_name() {
+        binary_layout!(my_layout, BigEndian, {
+            id: u16,
+            nested: inner::NestedView,
+            extension: crate::Optional<i8>,
+        });
+        let view = my_layout::View::new([1, 2, 3, 0xff]);
+        assert_eq!(
+            Some(FieldValue::UnsignedInteger(0x0102)),
+            view.field_value("id")
+        );
+        assert_eq!(
+            Some(FieldValue::Nested(&inner::LAYOUT, &[3])),
+            view.field_value("nested")
+        );
+        assert_eq!(
+            Some(FieldValue::SignedInteger(-1)),
+            view.field_value("extension")
+        );
+        assert_eq!(None, view.field_value("unknown"));
+
+        let layout = &my_layout::LAYOUT;
+        assert_eq!(None, layout.read_field_by_name("extension", &[1, 2, 3]));
+        assert_eq!(None, layout.read_field_by_name("id", &[1]));
+    }
+
+    #[test]
+    fn read_open_ended_field_by_name() {
+        binary_layout!(my_layout, BigEndian, {
+            id: u8,
+            tail: [u8],
+        });
+        let layout = &my_layout::LAYOUT;
+        assert_eq!(
+            Some(FieldValue::Bytes(&[2, 3])),
+            layout.read_field_by_name("tail", &[1, 2, 3])
+        );
+        assert_eq!(
+            Some(FieldValue::Bytes(&[])),
+            layout.read_field_by_name("tail", &[1])
+        );
+        assert_eq!(None, layout.read_field_by_name("tail", &[]));
+    }
+
+    #[test]
+    fn read_array_values() {
+        binary_layout!(my_layout, BigEndian, {
+            samples: [i16; 3],
+            matrix: [f32; 2],
+        });
+        let mut view = my_layout::View::new([0; 14]);
+        view.samples_mut().write([1, -2, 300]);
+        view.matrix_mut().write([0.5, -1.0]);
+        let data = view.into_storage();
+
+        let layout = &my_layout::LAYOUT;
+        let samples = match layout.read_field(&layout.fields[0], &data) {
+            FieldValue::Array(array) => array,
+            value => panic!("Expected an array, got {:?}", value),
+        };
+        assert_eq!(3, samples.len());
+        assert!(!samples.is_empty());
+        assert_eq!(Some(FieldValue::SignedInteger(-2)), samples.get(1));
+        assert_eq!(None, samples.get(3));
+        assert_eq!(
+            vec![
+                FieldValue::SignedInteger(1),
+                FieldValue::SignedInteger(-2),
+                FieldValue::SignedInteger(300)
+            ],
+            samples.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "[1, -2, 300]",
+            format!("{}", layout.read_field(&layout.fields[0], &data))
+        );
+        assert_eq!(
+            "[0.5, -1.0]",
+            format!("{}", layout.read_field(&layout.fields[1], &data))
+        );
+    }
+
+    #[test]
+    fn read_open_ended_array_values() {
+        binary_layout!(my_layout, LittleEndian, {
+            count: u8,
+            values: [u16],
+        });
+        let data = [2, 1, 0, 0xfe, 0xff, 7];
+        let layout = &my_layout::LAYOUT;
+        let values = match layout.read_field(&layout.fields[1], &data) {
+            FieldValue::Array(array) => array,
+            value => panic!("Expected an array, got {:?}", value),
+        };
+        assert_eq!(2, values.len());
+        assert_eq!(Some(FieldValue::UnsignedInteger(0xfffe)), values.get(1));
+        assert_eq!(None, values.get(2));
+        assert_eq!(
+            "[1, 65534]",
+            format!("{}", layout.read_field(&layout.fields[1], &data))
+        );
+    }
+
+    binary_layout!(message, BigEndian, {
+        kind: u8,
+        payload: crate::LengthPrefixed<u16>,
+    });
+    binary_layout!(envelope, BigEndian, {
+        version: u8,
+        inner: message::NestedView,
+    });
+
+    #[test]
+    fn read_length_prefixed_values() {
+        let data = [1, 0, 2, 0xab, 0xcd, 0xef];
+        let layout = &message::LAYOUT;
+        assert_eq!(
+            FieldValue::Bytes(&[0xab, 0xcd]),
+            layout.read_field(&layout.fields[1], &data)
+        );
+        // Data that is cut off is read as far as it goes
+        assert_eq!(
+            FieldValue::Bytes(&[0xab]),
+            layout.read_field(&layout.fields[1], &data[..4])
+        );
+    }
+
+    #[test]
+    fn size_in_ends_after_length_prefixed_data() {
+        let data = [1, 0, 2, 0xab, 0xcd, 0xef];
+        assert_eq!(3, message::LAYOUT.min_size());
+        assert_eq!(Ok(5), message::LAYOUT.size_in(&data));
+        assert_eq!(
+            Ok(6),
+            envelope::LAYOUT.size_in(&[7, 1, 0, 2, 0xab, 0xcd, 0xef])
+        );
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 5,
+                actual: 4
+            }),
+            message::LAYOUT.size_in(&data[..4])
+        );
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            }),
+            message::LAYOUT.size_in(&data[..2])
+        );
+    }
+
+    binary_layout!(tag, LittleEndian, {
+        kind: u8,
+        value: crate::Leb128<u32>,
+    });
+
+    binary_layout!(signed_tag, LittleEndian, {
+        value: crate::ZigZag<i64>,
+    });
+
+    #[test]
+    fn read_zigzag_values() {
+        let layout = &signed_tag::LAYOUT;
+        assert_eq!(
+            FieldValue::SignedInteger(-65),
+            layout.read_field(&layout.fields[0], &[0x81, 0x01])
+        );
+        assert_eq!(
+            FieldValue::SignedInteger(1),
+            layout.read_field(&layout.fields[0], &[0x02])
+        );
+    }
+
+    #[test]
+    fn read_half_float_values() {
+        assert_eq!(
+            FieldValue::Float(1.5),
+            read_value(EndianKind::Big, FieldKind::Float, &[0x3e, 0x00])
+        );
+        assert_eq!(
+            FieldValue::Float(-65504.0),
+            read_value(EndianKind::Little, FieldKind::Float, &[0xff, 0xfb])
+        );
+        assert_eq!(
+            FieldValue::Float(2f64.powi(-24)),
+            read_value(EndianKind::Big, FieldKind::Float, &[0x00, 0x01])
+        );
+        assert_eq!(
+            FieldValue::Float(f64::NEG_INFINITY),
+            read_value(EndianKind::Big, FieldKind::Float, &[0xfc, 0x00])
+        );
+        assert_eq!(
+            FieldValue::Float(-2.0),
+            read_value(EndianKind::Big, FieldKind::BFloat16, &[0xc0, 0x00])
+        );
+    }
+
+    binary_layout!(bcd_date, LittleEndian, {
+        year: crate::Bcd<u16, 4>,
+        day: crate::Bcd<u8, 2>,
+    });
+
+    #[test]
+    fn read_bcd_values() {
+        let layout = &bcd_date::LAYOUT;
+        assert_eq!(
+            FieldValue::UnsignedInteger(2024),
+            layout.read_field(&layout.fields[0], &[0x24, 0x20, 0x31])
+        );
+        assert_eq!(
+            FieldValue::UnsignedInteger(31),
+            layout.read_field(&layout.fields[1], &[0x24, 0x20, 0x31])
+        );
+        assert_eq!(
+            FieldValue::Bytes(&[0x3a]),
+            layout.read_field(&layout.fields[1], &[0x24, 0x20, 0x3a])
+        );
+    }
+
+    binary_layout!(vlq_tag, BigEndian, {
+        value: crate::Vlq<u32>,
+    });
+
+    #[test]
+    fn read_vlq_values() {
+        let layout = &vlq_tag::LAYOUT;
+        assert_eq!(
+            FieldValue::UnsignedInteger(0x80),
+            layout.read_field(&layout.fields[0], &[0x81, 0x00, 0xff])
+        );
+        assert_eq!(Ok(2), layout.size_in(&[0x81, 0x00, 0xff]));
+    }
+
+    #[test]
+    fn read_varint_values() {
+        let layout = &tag::LAYOUT;
+        assert_eq!(
+            FieldValue::UnsignedInteger(300),
+            layout.read_field(&layout.fields[1], &[1, 0xac, 0x02, 0xff])
+        );
+        assert_eq!(
+            FieldValue::Bytes(&[0x80, 0x80]),
+            layout.read_field(&layout.fields[1], &[1, 0x80, 0x80])
+        );
+        assert_eq!(2, layout.min_size());
+        assert_eq!(Ok(3), layout.size_in(&[1, 0xac, 0x02, 0xff]));
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 4,
+                actual: 3
+            }),
+            layout.size_in(&[1, 0x80, 0x80])
+        );
+    }
+
+    #[test]
+    fn display_field_values() {
+        assert_eq!("42", format!("{}", FieldValue::UnsignedInteger(42)));
+        assert_eq!("-42", format!("{}", FieldValue::SignedInteger(-42)));
+        assert_eq!("1.0", format!("{}", FieldValue::Float(1.0)));
+        assert_eq!("()", format!("{}", FieldValue::Unit));
+        assert_eq!("[]", format!("{}", FieldValue::Bytes(&[])));
+        assert_eq!("[01 ab]", format!("{}", FieldValue::Bytes(&[1, 0xab])));
+    }
+
+    #[test]
+    fn offset_of_in_const_context() {
+        binary_layout!(my_layout, BigEndian, {
+            value: u16,
+            values: [u8; 3],
+            val: u8,
+        });
+        const VALUES_OFFSET: usize = match my_layout::offset_of("values") {
+            Some((offset, _)) => offset,
+            None => panic!("field not found"),
+        };
+        let buffer = [0u8; VALUES_OFFSET];
+        assert_eq!(2, buffer.len());
+        assert_eq!(Some((5, Some(1))), my_layout::offset_of("val"));
+        assert_eq!(Some((0, Some(2))), my_layout::LAYOUT.offset_of("value"));
+        assert_eq!(None, my_layout::offset_of("valu"));
+        assert_eq!(None, my_layout::offset_of(""));
+    }
+
+    #[test]
+    fn field_constants() {
+        binary_layout!(my_layout, BigEndian, {
+            seq_no: u16,
+            flag: bool as u8,
+            nested: inner::NestedView,
+        });
+        assert_eq!("seq_no", my_layout::Fields::seq_no.name);
+        assert_eq!("u16", my_layout::Fields::seq_no.type_name);
+        assert_eq!("bool as u8", my_layout::Fields::flag.type_name);
+        assert_eq!(2, my_layout::Fields::flag.offset);
+        assert_eq!(
+            FieldKind::Nested(&inner::LAYOUT),
+            my_layout::Fields::nested.kind
+        );
+        assert_eq!(my_layout::LAYOUT.fields[1], my_layout::Fields::flag);
+    }
+}
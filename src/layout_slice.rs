@@ -0,0 +1,224 @@
+use core::marker::PhantomData;
+
+use crate::{Layout, LayoutView};
+
+/// A [LayoutSlice] treats its storage as an array of consecutive instances of a layout with a fixed size,
+/// e.g. for descriptor rings, page tables or files of fixed size records. It offers random access to
+/// views of the elements with [LayoutSlice::get] and [LayoutSlice::get_mut], and iteration over them.
+///
+/// The layout is given by its `NestedView` type. Bytes at the end of the storage that don't make up a whole
+/// element are not part of the slice, see [LayoutSlice::remainder].
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutSlice};
+///
+/// binary_layout!(descriptor, LittleEndian, {
+///   address: u32,
+///   length: u16,
+/// });
+///
+/// let mut ring = LayoutSlice::<descriptor::NestedView, _>::new(vec![0; 60]);
+/// assert_eq!(10, ring.len());
+///
+/// ring.get_mut(3).unwrap().length_mut().write(512);
+/// for (index, mut descriptor) in ring.iter_mut().enumerate() {
+///     descriptor.address_mut().write(0x1000 * index as u32);
+/// }
+///
+/// assert_eq!(512, ring.get(3).unwrap().length().read());
+/// assert_eq!(0x3000, ring.get(3).unwrap().address().read());
+/// assert!(ring.get(10).is_none());
+/// assert_eq!(512, ring.iter().map(|descriptor| descriptor.length().read()).sum::<u16>());
+/// ```
+pub struct LayoutSlice<L, S> {
+    storage: S,
+    _p: PhantomData<L>,
+}
+
+impl<L: Layout, S: AsRef<[u8]>> LayoutSlice<L, S> {
+    /// Create a slice of layout instances over the given storage.
+    ///
+    /// # Panics
+    /// If the layout doesn't have a fixed, non-zero size.
+    pub fn new(storage: S) -> Self {
+        match L::SIZE {
+            Some(size) if size > 0 => {}
+            _ => panic!(
+                "LayoutSlice needs a layout with a fixed non-zero size but `{}` has size {:?}",
+                L::NAME,
+                L::SIZE
+            ),
+        }
+        Self {
+            storage,
+            _p: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn element_size() -> usize {
+        // Can't fail because the constructor checked it
+        L::SIZE.unwrap()
+    }
+
+    /// Number of layout instances in the slice
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.as_ref().len() / Self::element_size()
+    }
+
+    /// Returns true if the storage is too short for even one instance of the layout
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Bytes at the end of the storage that don't make up a whole instance of the layout
+    #[inline]
+    pub fn remainder(&self) -> &[u8] {
+        let storage = self.storage.as_ref();
+        &storage[self.len() * Self::element_size()..]
+    }
+
+    /// Return a view of the instance with the given index, or `None` if the index is out of bounds
+    #[inline]
+    pub fn get<'a>(&'a self, index: usize) -> Option<<L as LayoutView<&'a [u8]>>::View>
+    where
+        L: LayoutView<&'a [u8]>,
+    {
+        let size = Self::element_size();
+        let start = index.checked_mul(size)?;
+        let element = self.storage.as_ref().get(start..start.checked_add(size)?)?;
+        Some(L::new_view(element))
+    }
+
+    /// Iterate over views of all instances in the slice
+    #[inline]
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = <L as LayoutView<&'a [u8]>>::View> + 'a
+    where
+        L: LayoutView<&'a [u8]> + 'a,
+    {
+        self.storage
+            .as_ref()
+            .chunks_exact(Self::element_size())
+            .map(L::new_view)
+    }
+
+    /// Destroy the [LayoutSlice] and return the storage
+    #[inline]
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
+
+impl<L: Layout, S: AsRef<[u8]> + AsMut<[u8]>> LayoutSlice<L, S> {
+    /// Return a view with write access to the instance with the given index, or `None` if the index is out of bounds
+    #[inline]
+    pub fn get_mut<'a>(&'a mut self, index: usize) -> Option<<L as LayoutView<&'a mut [u8]>>::View>
+    where
+        L: LayoutView<&'a mut [u8]>,
+    {
+        let size = Self::element_size();
+        let start = index.checked_mul(size)?;
+        let element = self
+            .storage
+            .as_mut()
+            .get_mut(start..start.checked_add(size)?)?;
+        Some(L::new_view(element))
+    }
+
+    /// Iterate over views with write access to all instances in the slice
+    #[inline]
+    pub fn iter_mut<'a>(
+        &'a mut self,
+    ) -> impl Iterator<Item = <L as LayoutView<&'a mut [u8]>>::View> + 'a
+    where
+        L: LayoutView<&'a mut [u8]> + 'a,
+    {
+        self.storage
+            .as_mut()
+            .chunks_exact_mut(Self::element_size())
+            .map(L::new_view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutSlice;
+    use crate::prelude::*;
+
+    binary_layout!(record, BigEndian, {
+        id: u16,
+        flags: u8,
+    });
+    binary_layout!(open_ended, BigEndian, {
+        id: u16,
+        payload: [u8],
+    });
+    binary_layout!(empty, BigEndian, {});
+
+    #[test]
+    fn len() {
+        let slice = LayoutSlice::<record::NestedView, _>::new([0; 10]);
+        assert_eq!(3, slice.len());
+        assert!(!slice.is_empty());
+        assert_eq!(&[0], slice.remainder());
+
+        let slice = LayoutSlice::<record::NestedView, _>::new([0; 2]);
+        assert_eq!(0, slice.len());
+        assert!(slice.is_empty());
+        assert_eq!(&[0, 0], slice.remainder());
+    }
+
+    #[test]
+    fn get() {
+        let slice = LayoutSlice::<record::NestedView, _>::new([0, 1, 2, 0, 3, 4, 5]);
+        assert_eq!(1, slice.get(0).unwrap().id().read());
+        assert_eq!(2, slice.get(0).unwrap().flags().read());
+        assert_eq!(3, slice.get(1).unwrap().id().read());
+        assert_eq!(4, slice.get(1).unwrap().flags().read());
+        assert!(slice.get(2).is_none());
+        assert!(slice.get(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut storage = [0; 6];
+        let mut slice = LayoutSlice::<record::NestedView, _>::new(&mut storage[..]);
+        slice.get_mut(1).unwrap().id_mut().write(0x0102);
+        slice.get_mut(0).unwrap().flags_mut().write(3);
+        assert!(slice.get_mut(2).is_none());
+        assert_eq!([0, 0, 3, 1, 2, 0], storage);
+    }
+
+    #[test]
+    fn iter() {
+        let mut slice = LayoutSlice::<record::NestedView, _>::new([0; 9]);
+        for (index, mut element) in slice.iter_mut().enumerate() {
+            element.id_mut().write(index as u16);
+            element.flags_mut().write(0xf0 | index as u8);
+        }
+        assert!(slice
+            .iter()
+            .map(|element| element.id().read())
+            .eq([0, 1, 2]));
+        assert_eq!([0, 0, 0xf0, 0, 1, 0xf1, 0, 2, 0xf2], slice.into_storage());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "LayoutSlice needs a layout with a fixed non-zero size but `open_ended` has size None"
+    )]
+    fn open_ended_layout() {
+        LayoutSlice::<open_ended::NestedView, _>::new([0; 4]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "LayoutSlice needs a layout with a fixed non-zero size but `empty` has size Some(0)"
+    )]
+    fn empty_layout() {
+        LayoutSlice::<empty::NestedView, _>::new([0; 4]);
+    }
+}
@@ -0,0 +1,212 @@
+/// Declare several alternative layouts over the same byte array field, like a C union, e.g. for hardware descriptor tables
+/// whose entries are interpreted differently depending on their type.
+///
+/// Each alternative gets a name and a layout, and adds three accessors to the `View` of the layout with the field:
+/// - `${field_name}_as_${alternative}()` returns a view of the field as that layout with read access
+/// - `${field_name}_as_${alternative}_mut()` returns a view of the field as that layout with write access
+/// - `into_${field_name}_as_${alternative}()` destroys the `View` and returns a view of the field as that layout owning the storage in a [Data](crate::Data) instance
+///
+/// The accessors check the field with `View::try_new` of the alternative layout and fail with its [ViewError](crate::ViewError),
+/// e.g. if the storage is too short. If the field is a fixed size byte array `[u8; N]`, each alternative layout has to fit into it,
+/// otherwise the union fails to compile. For an open ended byte array `[u8]`, any layout can be used.
+/// To have alternative layouts over a whole storage, declare a layout with a single byte array field.
+///
+/// # Example
+/// ```
+/// use binary_layout::{layout_union, prelude::*};
+///
+/// binary_layout!(descriptor, LittleEndian, {
+///   raw: [u8; 8],
+/// });
+///
+/// binary_layout!(segment, LittleEndian, {
+///   limit: u16,
+///   base: u32,
+///   access: u8,
+///   flags: u8,
+/// });
+///
+/// binary_layout!(gate, LittleEndian, {
+///   offset_low: u16,
+///   selector: u16,
+///   reserved: u8,
+///   access: u8,
+///   offset_high: u16,
+/// });
+///
+/// layout_union!(descriptor, raw, {
+///   segment: segment,
+///   gate: gate,
+/// });
+///
+/// fn main() {
+///     let mut view = descriptor::View::new([0; 8]);
+///     let mut gate = view.raw_as_gate_mut().unwrap();
+///     gate.selector_mut().write(0x0008);
+///     gate.access_mut().write(0x8e);
+///     view.raw_as_segment_mut().unwrap().limit_mut().write(0xffff);
+///     assert_eq!(0xffff, view.raw_as_gate().unwrap().offset_low().read());
+///     assert_eq!(0x8e00_0008, view.raw_as_segment().unwrap().base().read());
+///     assert_eq!([0xff, 0xff, 8, 0, 0, 0x8e, 0, 0], view.into_storage());
+/// }
+/// ```
+#[macro_export]
+macro_rules! layout_union {
+    ($($layout: ident)::+, $field: ident, {$($alternative: ident : $($alternative_layout: ident)::+),* $(,)?} $(,)?) => {
+        const _: () = {
+            let field_size = <$($layout)::+::$field as $crate::Field>::SIZE;
+            $(
+                $crate::internal::check_union_size(field_size, $($alternative_layout)::+::SIZE);
+            )*
+        };
+
+        $crate::layout_union!(@impl_alternatives {$($layout)::+}, $field, {$($alternative: $($alternative_layout)::+),*});
+    };
+
+    (@impl_alternatives $layout: tt, $field: ident, {}) => {};
+    (@impl_alternatives {$($layout: ident)::+}, $field: ident, {$alternative: ident : $($alternative_layout: ident)::+ $(, $($tail: tt)*)?}) => {
+        $crate::internal::paste!{
+            #[allow(dead_code)]
+            impl <S: AsRef<[u8]>> $($layout)::+::View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Return a view of the `", stringify!($field), "` field as a `", stringify!($($alternative_layout)::+), "` layout with read access"),
+                    pub fn [<$field _as_ $alternative>](&self) -> Result<$($alternative_layout)::+::View<&[u8]>, $crate::ViewError> {
+                        let storage = <Self as $crate::internal::ViewInfo>::data(self);
+                        let region = $crate::internal::field_region(&$($layout)::+::LAYOUT, &$($layout)::+::Fields::$field, storage)?;
+                        $($alternative_layout)::+::View::try_new(&storage[region])
+                    }
+                }
+
+                $crate::internal::doc_comment!{
+                    concat!("Destroy the `View` and return a view of the `", stringify!($field), "` field as a `", stringify!($($alternative_layout)::+), "` layout owning the storage"),
+                    pub fn [<into_ $field _as_ $alternative>](self) -> Result<$($alternative_layout)::+::View<$crate::Data<S>>, $crate::ViewError> {
+                        let region = $crate::internal::field_region(&$($layout)::+::LAYOUT, &$($layout)::+::Fields::$field, <Self as $crate::internal::ViewInfo>::data(&self))?;
+                        $($alternative_layout)::+::View::try_new($crate::Data::from(self.into_storage()).into_subregion(region))
+                    }
+                }
+            }
+
+            #[allow(dead_code)]
+            impl <S: AsRef<[u8]> + AsMut<[u8]>> $($layout)::+::View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Return a view of the `", stringify!($field), "` field as a `", stringify!($($alternative_layout)::+), "` layout with write access"),
+                    pub fn [<$field _as_ $alternative _mut>](&mut self) -> Result<$($alternative_layout)::+::View<&mut [u8]>, $crate::ViewError> {
+                        let storage = <Self as $crate::internal::ViewInfoMut>::data_mut(self);
+                        let region = $crate::internal::field_region(&$($layout)::+::LAYOUT, &$($layout)::+::Fields::$field, storage)?;
+                        $($alternative_layout)::+::View::try_new(&mut storage[region])
+                    }
+                }
+            }
+        }
+
+        $crate::layout_union!(@impl_alternatives {$($layout)::+}, $field, {$($($tail)*)?});
+    };
+}
+
+/// Internal function. Don't use this in user code.
+/// Checks at compile time that a layout of a [layout_union!](crate::layout_union!) fits into the field.
+pub const fn check_union_size(field_size: Option<usize>, layout_size: Option<usize>) {
+    if let (Some(field_size), Some(layout_size)) = (field_size, layout_size) {
+        assert!(
+            layout_size <= field_size,
+            "A layout of the union is larger than the field"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::ViewError;
+
+    binary_layout!(entry, BigEndian, {
+        kind: u8,
+        data: [u8; 4],
+        tail: [u8],
+    });
+
+    binary_layout!(words, BigEndian, {
+        high: u16,
+        low: u16,
+    });
+
+    binary_layout!(number, BigEndian, {
+        value: i32,
+    });
+
+    binary_layout!(short, LittleEndian, {
+        value: u16,
+    });
+
+    binary_layout!(versioned, BigEndian, {
+        version: u8 in 1..=1,
+        bytes: [u8],
+    });
+
+    crate::layout_union!(entry, data, {
+        words: words,
+        number: self::number,
+        short: short,
+    });
+
+    crate::layout_union!(self::entry, tail, {
+        number: number,
+        versioned: versioned,
+    });
+
+    #[test]
+    fn alternatives_share_storage() {
+        let mut storage = [0; 7];
+        let mut view = entry::View::new(&mut storage);
+        view.data_as_number_mut().unwrap().value_mut().write(-2);
+        assert_eq!(0xffff, view.data_as_words().unwrap().high().read());
+        assert_eq!(0xfffe, view.data_as_words().unwrap().low().read());
+        view.data_as_words_mut().unwrap().low_mut().write(0x0102);
+        assert_eq!(0xffff, view.data_as_short().unwrap().value().read());
+        assert_eq!(-0xfefe, view.data_as_number().unwrap().value().read());
+        assert_eq!([0, 0xff, 0xff, 0x01, 0x02, 0, 0], storage);
+    }
+
+    #[test]
+    fn into_alternative() {
+        let view = entry::View::new([0, 1, 2, 3, 4, 5, 6]);
+        let words = view.into_data_as_words().unwrap();
+        assert_eq!(0x0304, words.low().read());
+        let storage = words.into_storage();
+        assert_eq!(1..5, storage.region());
+        assert_eq!([0, 1, 2, 3, 4, 5, 6], storage.into_inner());
+    }
+
+    #[test]
+    fn open_ended_field() {
+        let view = entry::View::new([0, 0, 0, 0, 0, 1, 7]);
+        assert_eq!(&[7], view.tail_as_versioned().unwrap().bytes());
+        assert!(matches!(
+            view.tail_as_number(),
+            Err(ViewError::StorageTooShort {
+                expected: 4,
+                actual: 2
+            })
+        ));
+        let view = entry::View::new([0, 0, 0, 0, 0, 2, 7]);
+        assert!(matches!(
+            view.into_tail_as_versioned(),
+            Err(ViewError::UnsupportedVersion {
+                field: "version",
+                version: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn storage_too_short() {
+        let view = entry::View::new([0, 0, 0]);
+        assert!(matches!(
+            view.data_as_short(),
+            Err(ViewError::StorageTooShort {
+                expected: 5,
+                actual: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+/// Declare an enum of the layouts a byte array field can store, depending on the value of a tag field before it,
+/// like the payload of a message whose type is given by a message type byte.
+///
+/// Given the layout, the tag field and the field storing the variants, this declares an enum with one variant per layout
+/// wrapping its `View`, e.g. `Body::Request(request::View<S>)`, and adds three accessors to the `View` of the layout:
+/// - `${field_name}_variant()` returns the enum with read access to the field
+/// - `${field_name}_variant_mut()` returns the enum with write access to the field
+/// - `into_${field_name}_variant()` destroys the `View` and returns the enum owning the storage in a [Data](crate::Data) instance
+///
+/// The variants are chosen with `match` patterns on the value of the tag field, so several values or ranges can map to the same layout.
+/// The accessors fail with [ViewError::UnknownVariant](crate::ViewError::UnknownVariant) if no pattern matches, or with the error of `View::try_new` of the chosen layout,
+/// e.g. if the field is too short for it. The enum also offers `try_new(tag, storage)` to pick the variant for a storage given separately.
+///
+/// The field can be a fixed size byte array `[u8; N]` or an open ended byte array `[u8]`. The tag field has to be a primitive integer field.
+///
+/// # Example
+/// ```
+/// use binary_layout::{layout_variants, prelude::*, ViewError};
+///
+/// binary_layout!(message, BigEndian, {
+///   msg_type: u8,
+///   seq_no: u16,
+///   body: [u8],
+/// });
+///
+/// binary_layout!(request, BigEndian, {
+///   method: u8,
+///   path: [u8],
+/// });
+///
+/// binary_layout!(response, BigEndian, {
+///   status: u16,
+/// });
+///
+/// layout_variants!(pub Body, message, msg_type => body, {
+///   1 => Request(request),
+///   2 | 3 => Response(response),
+/// });
+///
+/// fn main() {
+///     let mut view = message::View::new(vec![2, 0, 7, 0, 0]);
+///     if let Body::Response(mut response) = view.body_variant_mut().unwrap() {
+///         response.status_mut().write(404);
+///     }
+///     match view.body_variant().unwrap() {
+///         Body::Request(request) => panic!("Unexpected request with method {}", request.method().read()),
+///         Body::Response(response) => assert_eq!(404, response.status().read()),
+///     }
+///     assert_eq!(vec![2, 0, 7, 0x01, 0x94], view.into_storage());
+///
+///     let view = message::View::new([9, 0, 7, 0, 0]);
+///     assert!(matches!(
+///         view.body_variant(),
+///         Err(ViewError::UnknownVariant { field: "msg_type", value: 9 }),
+///     ));
+/// }
+/// ```
+#[macro_export]
+macro_rules! layout_variants {
+    (
+        $(#[$meta: meta])*
+        $vis: vis $name: ident, $($layout: ident)::+, $tag_field: ident => $field: ident,
+        {$($tag: pat => $variant: ident($($variant_layout: ident)::+)),* $(,)?} $(,)?
+    ) => {
+        $crate::internal::doc_comment!{
+            concat!("The layouts stored in the `", stringify!($field), "` field of `", stringify!($($layout)::+), "`, depending on the `", stringify!($tag_field), "` field"),
+            $(#[$meta])*
+            $vis enum $name<S: AsRef<[u8]>> {
+                $(
+                    #[doc = concat!("A `", stringify!($($variant_layout)::+), "` layout")]
+                    $variant($($variant_layout)::+::View<S>),
+                )*
+            }
+        }
+
+        #[allow(dead_code)]
+        impl <S: AsRef<[u8]>> $name<S> {
+            /// Create a view of the layout that the value of the tag field selects over the given storage,
+            /// checking the storage with `View::try_new` of that layout.
+            pub fn try_new(tag: <$($layout)::+::$tag_field as $crate::FieldCopyAccess>::HighLevelType, storage: S) -> Result<Self, $crate::ViewError> {
+                match tag {
+                    $(
+                        $tag => Ok($name::$variant($($variant_layout)::+::View::try_new(storage)?)),
+                    )*
+                    #[allow(unreachable_patterns)]
+                    _ => Err($crate::ViewError::UnknownVariant {
+                        field: stringify!($tag_field),
+                        value: tag as i128,
+                    }),
+                }
+            }
+
+            /// This destroys the view of the variant and returns the underlying storage back to you.
+            pub fn into_storage(self) -> S {
+                match self {
+                    $(
+                        $name::$variant(view) => view.into_storage(),
+                    )*
+                }
+            }
+        }
+
+        $crate::internal::paste!{
+            #[allow(dead_code)]
+            impl <S: AsRef<[u8]>> $($layout)::+::View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Return the layout stored in the `", stringify!($field), "` field with read access, see [", stringify!($name), "]"),
+                    pub fn [<$field _variant>](&self) -> Result<$name<&[u8]>, $crate::ViewError> {
+                        let storage = <Self as $crate::internal::ViewInfo>::data(self);
+                        let region = $crate::internal::field_region(&$($layout)::+::LAYOUT, &$($layout)::+::Fields::$field, storage)?;
+                        let tag = <$($layout)::+::$tag_field as $crate::FieldReadExt>::read(storage);
+                        $name::try_new(tag, &storage[region])
+                    }
+                }
+
+                $crate::internal::doc_comment!{
+                    concat!("Destroy the `View` and return the layout stored in the `", stringify!($field), "` field owning the storage, see [", stringify!($name), "]"),
+                    pub fn [<into_ $field _variant>](self) -> Result<$name<$crate::Data<S>>, $crate::ViewError> {
+                        let storage = <Self as $crate::internal::ViewInfo>::data(&self);
+                        let region = $crate::internal::field_region(&$($layout)::+::LAYOUT, &$($layout)::+::Fields::$field, storage)?;
+                        let tag = <$($layout)::+::$tag_field as $crate::FieldReadExt>::read(storage);
+                        $name::try_new(tag, $crate::Data::from(self.into_storage()).into_subregion(region))
+                    }
+                }
+            }
+
+            #[allow(dead_code)]
+            impl <S: AsRef<[u8]> + AsMut<[u8]>> $($layout)::+::View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Return the layout stored in the `", stringify!($field), "` field with write access, see [", stringify!($name), "]"),
+                    pub fn [<$field _variant_mut>](&mut self) -> Result<$name<&mut [u8]>, $crate::ViewError> {
+                        let storage = <Self as $crate::internal::ViewInfoMut>::data_mut(self);
+                        let region = $crate::internal::field_region(&$($layout)::+::LAYOUT, &$($layout)::+::Fields::$field, storage)?;
+                        let tag = <$($layout)::+::$tag_field as $crate::FieldReadExt>::read(storage);
+                        $name::try_new(tag, &mut storage[region])
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::ViewError;
+
+    binary_layout!(message, LittleEndian, {
+        msg_type: u16,
+        body: [u8],
+    });
+
+    binary_layout!(frame, LittleEndian, {
+        kind: i8,
+        payload: [u8; 4],
+        crc: u8,
+    });
+
+    binary_layout!(ping, LittleEndian, {
+        token: u32,
+    });
+
+    binary_layout!(data, LittleEndian, {
+        version: u8 in 1..=2,
+        length: u8,
+        bytes: [u8],
+    });
+
+    crate::layout_variants!(
+        /// Message bodies
+        pub Body, message, msg_type => body, {
+            1 => Ping(ping),
+            0x100..=0x1ff => Data(data),
+        }
+    );
+
+    crate::layout_variants!(pub Payload, self::frame, kind => payload, {
+        -1 => Ping(ping),
+        0 | 1 => Data(self::data),
+    });
+
+    #[test]
+    fn read_variant() {
+        let view = message::View::new([1, 0, 4, 3, 2, 1]);
+        match view.body_variant().unwrap() {
+            Body::Ping(ping) => assert_eq!(0x01020304, ping.token().read()),
+            Body::Data(_) => panic!("Expected a ping"),
+        }
+
+        let view = message::View::new([0x20, 0x01, 1, 2, 7, 8]);
+        match view.body_variant().unwrap() {
+            Body::Data(data) => {
+                assert_eq!(2, data.length().read());
+                assert_eq!(&[7, 8], data.bytes());
+            }
+            Body::Ping(_) => panic!("Expected data"),
+        }
+    }
+
+    #[test]
+    fn write_variant() {
+        let mut storage = [0xff, 0, 0, 0, 0, 0];
+        let mut view = frame::View::new(&mut storage);
+        match view.payload_variant_mut().unwrap() {
+            Payload::Ping(mut ping) => ping.token_mut().write(0x0a0b0c0d),
+            Payload::Data(_) => panic!("Expected a ping"),
+        }
+        assert_eq!([0xff, 0x0d, 0x0c, 0x0b, 0x0a, 0], storage);
+    }
+
+    #[test]
+    fn into_variant() {
+        let view = frame::View::new([1, 2, 2, 5, 6, 9]);
+        let Payload::Data(data) = view.into_payload_variant().unwrap() else {
+            panic!("Expected data");
+        };
+        assert_eq!(&[5, 6], data.bytes());
+        let storage = data.into_storage();
+        assert_eq!(1..5, storage.region());
+        assert_eq!([1, 2, 2, 5, 6, 9], storage.into_inner());
+    }
+
+    #[test]
+    fn try_new() {
+        let body = Body::try_new(1, [1, 0, 0, 0]).unwrap();
+        assert!(matches!(body, Body::Ping(_)));
+        assert_eq!([1, 0, 0, 0], body.into_storage());
+    }
+
+    #[test]
+    fn unknown_variant() {
+        let view = message::View::new([2, 0, 0, 0, 0, 0]);
+        assert!(matches!(
+            view.body_variant(),
+            Err(ViewError::UnknownVariant {
+                field: "msg_type",
+                value: 2
+            })
+        ));
+        let mut view = frame::View::new([0x80, 0, 0, 0, 0, 0]);
+        assert!(matches!(
+            view.payload_variant_mut(),
+            Err(ViewError::UnknownVariant {
+                field: "kind",
+                value: -128
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_variant_storage() {
+        // The body is too short for a ping
+        let view = message::View::new([1, 0, 4, 3, 2]);
+        assert!(matches!(
+            view.body_variant(),
+            Err(ViewError::StorageTooShort {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        // The data has an unsupported version
+        let view = frame::View::new([0, 3, 0, 0, 0, 0]);
+        assert!(matches!(
+            view.payload_variant(),
+            Err(ViewError::UnsupportedVersion {
+                field: "version",
+                version: 3
+            })
+        ));
+        // The storage is too short for the frame
+        let view = frame::View::new([0, 1, 0, 0]);
+        assert!(matches!(
+            view.into_payload_variant(),
+            Err(ViewError::StorageTooShort {
+                expected: 6,
+                actual: 4
+            })
+        ));
+    }
+}
@@ -70,7 +70,8 @@
 //!    The only data type you can get a reference to is byte arrays, and they only require an alignment of 1 which is trivially always fulfilled.
 //!
 //! ## When not to use this library?
-//! - You need dynamic data structures, e.g. a list that can change size. This library only supports static data layouts (with the exception of open ended byte arrays at the end of a layout).
+//! - You need dynamic data structures, e.g. a list that can change size. This library only supports static data layouts (with the exception of open ended byte arrays at the end of a layout,
+//!   and of length prefixed fields and varints, which move the fields after them).
 //! - Not all of your layout fits into the memory and you need to process streams of data.
 //!   Note that this crate can still be helpful if you have smaller layouted packets as part of a larger stream, as long as any one layouted packet fits into memory.
 //!
@@ -86,6 +87,8 @@
 //!    This is not the API used in the example above, see [trait@Field] for an API example.
 //! 2. The [struct@FieldView] API that wraps a slice of storage data and remembers it in a `View` object, allowing access to the fields without having to pass in the packed data slice each time. This is the API used in the example above. See [struct@FieldView] for another example.
 //!
+//! The [offset_of!] and [size_of!] macros return the offset and size of a field like `offset_of!(icmp_packet::checksum)` and can be used in const contexts, e.g. for array lengths or FFI shims.
+//!
 //! ## Supported field types
 //!
 //! ### Primitive integer types
@@ -93,9 +96,43 @@
 //! - [i8](https://doc.rust-lang.org/stable/core/primitive.i8.html), [i16](https://doc.rust-lang.org/stable/core/primitive.i16.html), [i32](https://doc.rust-lang.org/stable/core/primitive.i32.html), [i64](https://doc.rust-lang.org/stable/core/primitive.i64.html), [i128](https://doc.rust-lang.org/stable/core/primitive.i128.html)
 //!
 //! For these fields, the [trait@Field] API offers [FieldReadExt::read], [FieldWriteExt::write], [FieldCopyAccess::try_read], [FieldCopyAccess::try_write] and the [struct@FieldView] API offers [FieldView::read] and [FieldView::write].
+//! They can also be read in const contexts with `read_const()`, which takes a byte array, e.g. to parse data from `include_bytes!` into constants.
+//!
+//! ### Integer types with an odd number of bytes
+//! - [u24], [u40], [u48], [u56]
+//! - [i24], [i40], [i48], [i56]
+//!
+//! These are stored in 3, 5, 6 or 7 bytes and read and written as the next larger primitive integer type, e.g. [u24] as [u32](https://doc.rust-lang.org/stable/core/primitive.u32.html).
+//! Writing a value that doesn't fit into the field will throw an error. Because of this, [FieldWriteExt::write] and [FieldView::write] are not available for those types and you need to use [FieldCopyAccess::try_write] and [FieldView::try_write].
 //!
 //! ### Primitive float types
 //! - [f32](https://doc.rust-lang.org/core/primitive.f32.html), [f64](https://doc.rust-lang.org/core/primitive.f64.html)
+//! - [half::f16](https://docs.rs/half/latest/half/struct.f16.html), [half::bf16](https://docs.rs/half/latest/half/struct.bf16.html) with the `half` feature
+//!
+//! With the `volatile` feature, the primitive integer types up to 64 bits and [f32](https://doc.rust-lang.org/core/primitive.f32.html), [f64](https://doc.rust-lang.org/core/primitive.f64.html)
+//! also offer `read_volatile()` and `write_volatile()` through the `FieldVolatileAccess` trait and the [struct@FieldView] API.
+//! They access the field with a single volatile memory access, so layouts can describe memory-mapped hardware registers.
+//! For registers with read side effects, the unsafe `read_volatile_ptr()` and `write_volatile_ptr()` take a raw pointer to the register block
+//! instead of a slice, since the compiler may read memory that a reference points to at any time.
+//! This feature and the `memmap2` feature are the only ones that need unsafe code, so the crate relaxes `forbid(unsafe_code)` to `deny(unsafe_code)`
+//! when one of them is enabled and only allows it in the modules implementing them. `volatile` needs it for the raw pointer accesses of
+//! [read_volatile](core::ptr::read_volatile) and [write_volatile](core::ptr::write_volatile), and `memmap2` because mapping a file is only sound
+//! as long as nobody else modifies the file.
+//!
+//! ### IP addresses
+//! - [Ipv4Addr](https://doc.rust-lang.org/std/net/struct.Ipv4Addr.html), [Ipv6Addr](https://doc.rust-lang.org/std/net/struct.Ipv6Addr.html) with the `std` feature
+//!
+//! These are stored in 4 or 16 bytes in network byte order, independent of the endianness of the layout.
+//!
+//! ### Timestamps and durations: `UnixSeconds<T>`, `UnixMillis<T>`, `DurationSeconds<T>`, `DurationMillis<T>`, `DurationMicros<T>`, `DurationNanos<T>` with the `std` feature
+//! These store whole seconds, milliseconds, microseconds or nanoseconds in an integer `T` of type [u32](https://doc.rust-lang.org/core/primitive.u32.html),
+//! [u64](https://doc.rust-lang.org/core/primitive.u64.html) or [i64](https://doc.rust-lang.org/core/primitive.i64.html),
+//! and are read and written as [SystemTime](std::time::SystemTime) since the unix epoch for [UnixSeconds] and [UnixMillis],
+//! or as [Duration](core::time::Duration) for [DurationSeconds], [DurationMillis], [DurationMicros] and [DurationNanos].
+//! The conversions are checked and fail with [TimeOutOfRangeError] if the value doesn't fit, so you need to use [FieldCopyAccess::try_read] and [FieldCopyAccess::try_write].
+//! With the `chrono` feature, timestamps can instead be read and written as [chrono::DateTime<Utc>](https://docs.rs/chrono/latest/chrono/struct.DateTime.html),
+//! e.g. `UnixSeconds<i64, chrono::DateTime<chrono::Utc>>`, and with the `time` feature as [time::OffsetDateTime](https://docs.rs/time/latest/time/struct.OffsetDateTime.html),
+//! e.g. `UnixMillis<u64, time::OffsetDateTime>`. `OffsetDateTime` values are stored as the instant they describe and read back in UTC.
 //!
 //! ### Non-zero primitive integer types
 //! - [NonZeroU8](https://doc.rust-lang.org/core/num/struct.NonZeroU8.html), [NonZeroU16](https://doc.rust-lang.org/core/num/struct.NonZeroU16.html), [NonZeroU32](https://doc.rust-lang.org/core/num/struct.NonZeroU32.html), [NonZeroU64](https://doc.rust-lang.org/core/num/struct.NonZeroU64.html), [NonZeroU128](https://doc.rust-lang.org/core/num/struct.NonZeroU128.html)
@@ -103,11 +140,21 @@
 //!
 //! Reading a zero values will throw an error. Because of this, [FieldReadExt::read] and [FieldView::read] are not available for those types and you need to use [FieldCopyAccess::try_read] and [FieldView::try_read].
 //!
+//! To store optional values with zero meaning "no value", declare the field as e.g. `Option<NonZeroU32>`. Those fields have the same size as the underlying integer,
+//! read a zero value as `None` and write `None` as zero. Reading them can't fail, so [FieldReadExt::read] and [FieldView::read] are available.
+//!
 //! ### bool, char
-//! [bool](https://doc.rust-lang.org/stable/core/primitive.bool.html) and [char](https://doc.rust-lang.org/stable/core/primitive.char.html) are supported using the `bool as u8` and `char as u32` data type notation.
+//! [bool](https://doc.rust-lang.org/stable/core/primitive.bool.html) fields are stored as a single byte, `0` for `false` and `1` for `true`.
+//! They can be declared as `bool` or, equivalently, using the `bool as u8` data type notation.
+//! [char](https://doc.rust-lang.org/stable/core/primitive.char.html) is supported using the `char as u32` data type notation.
 //!
 //! Note that not only `0u8` and `1u8` are valid boolean values and not all [u32](https://doc.rust-lang.org/stable/core/primitive.u32.html) values are valid unicode code points.
-//! Reading invalid values will throw an error. Because of this, [FieldReadExt::read] and [FieldView::read] are not available for those types and you need to use [FieldCopyAccess::try_read] and [FieldView::try_read].
+//! Reading invalid values will throw an error, e.g. [InvalidBoolError]. Because of this, [FieldReadExt::read] and [FieldView::read] are not available for those types and you need to use [FieldCopyAccess::try_read] and [FieldView::try_read].
+//!
+//! ### Bit fields
+//! Unsigned integer and [bool](https://doc.rust-lang.org/stable/core/primitive.bool.html) fields can take up only some bits of their bytes,
+//! e.g. `version: u8 bits(4)`, see [BitField] and [binary_layout!]. Consecutive bit fields share bytes and have to add up to whole bytes.
+//! Writing a value that doesn't fit into the number of bits will throw an error, so for integer bit fields you need to use [FieldCopyAccess::try_write] and [FieldView::try_write].
 //!
 //! ### Primitive Zero-Sized Types (ZSTs)
 //!
@@ -118,35 +165,154 @@
 //! ### Fixed size byte arrays: `[u8; N]`.
 //! For these fields, the [trait@Field] API offers [FieldSliceAccess::data], [FieldSliceAccess::data_mut], and the [struct@FieldView] API returns a slice.
 //!
+//! ### Fixed size arrays of numbers: `[u16; N]`, `[f32; N]`, ...
+//! Arrays of the primitive integer types other than [u8](https://doc.rust-lang.org/stable/core/primitive.u8.html) and of the primitive float types are stored
+//! element by element, each with the endianness of the layout.
+//! For these fields, the [trait@Field] API offers [FieldArrayAccess::get] and [FieldArrayAccess::set] to access single elements and [FieldReadExt::read] and [FieldWriteExt::write] to access the whole array,
+//! and the [struct@FieldView] API offers [FieldView::get], [FieldView::set], [FieldView::read] and [FieldView::write].
+//!
+//! ### Bit arrays: `BitArray<BITS>`
+//! A [BitArray] field packs `BITS` boolean flags into `BITS / 8` bytes, rounded up, like the bitmaps of allocation tables.
+//! The [trait@Field] API offers [FieldBitArrayAccess::get_bit] and [FieldBitArrayAccess::set_bit] and the [struct@FieldView] API offers [FieldView::get_bit] and [FieldView::set_bit]
+//! to access single bits. The bits of each byte are numbered like for [bit fields](BitField), from the most significant bit in big endian layouts
+//! and from the least significant bit in little endian layouts.
+//!
 //! ### Open ended byte arrays: `[u8]`.
 //! This field type can only occur as the last field of a layout and will mach the remaining data until the end of the storage.
 //! This field has a dynamic size, depending on how large the packet data is.
 //! For these fields, the [trait@Field] API offers [FieldSliceAccess::data], [FieldSliceAccess::data_mut] and the [struct@FieldView] API returns a slice.
 //!
+//! ### Open ended arrays of numbers or nested layouts: `[u32]`, `[my_layout::NestedView]`, ...
+//! Like open ended byte arrays, these can only occur as the last field of a layout. They contain as many elements as fit into the rest of the storage,
+//! bytes after the last whole element are ignored.
+//! Arrays of numbers offer the same API as fixed size arrays of numbers plus [FieldView::len] and [FieldView::iter], but no way to read or write the whole array at once.
+//! For arrays of nested layouts, the [struct@FieldView] API returns a [LayoutSlice] with views of the elements.
+//!
+//! ```
+//! use binary_layout::prelude::*;
+//!
+//! binary_layout!(record, LittleEndian, {
+//!   id: u16,
+//!   flags: u8,
+//! });
+//!
+//! binary_layout!(table, LittleEndian, {
+//!   count: u8,
+//!   records: [record::NestedView],
+//! });
+//!
+//! fn main() {
+//!     let storage = [2, 1, 0, 0xf0, 2, 0, 0x0f];
+//!     let view = table::View::new(&storage[..]);
+//!     assert_eq!(2, view.records().len());
+//!     assert_eq!(3, view.records().iter().map(|record| record.id().read()).sum::<u16>());
+//! }
+//! ```
+//!
+//! ### Fixed size strings: `FixedString<N>`, `FixedString<N, PAD>`.
+//! A [FixedString] field stores a UTF-8 (or ASCII) string in `N` bytes, padded with the `PAD` byte, which defaults to NUL.
+//! For these fields, the [trait@Field] API offers [FieldStringAccess::try_read_str] and [FieldStringAccess::try_write_str] and the [struct@FieldView] API offers
+//! [FieldView::try_read_str] and [FieldView::try_write_str]. Reading removes trailing pad bytes and fails for invalid UTF-8,
+//! writing adds pad bytes and fails with [StringTooLongError] for strings longer than `N` bytes.
+//!
+//! ### NUL terminated strings: `FixedCStr<N>`.
+//! A [FixedCStr] field stores a NUL terminated UTF-8 string in a region of `N` bytes, like a `char[N]` member of a C struct.
+//! It offers the same API as [FixedString]. Reading fails with [CStrReadError] if there is no NUL byte in the region or the string isn't valid UTF-8,
+//! writing fails with [CStrWriteError] if the string and its terminator don't fit or the string contains a NUL byte.
+//!
+//! ### Length prefixed byte slices: `LengthPrefixed<u8>`, `LengthPrefixed<u16>`, `LengthPrefixed<u32>`, `LengthPrefixed<u64>`.
+//! A [LengthPrefixed] field stores a length as an unsigned integer with the endianness of the layout, followed by that many bytes.
+//! For these fields, the [trait@Field] API offers [FieldLengthPrefixedAccess::try_data], [FieldLengthPrefixedAccess::try_data_mut] and
+//! [FieldLengthPrefixedAccess::try_write_data] and the [struct@FieldView] API offers [FieldView::try_data], [FieldView::try_data_mut] and [FieldView::try_write_data].
+//! They fail with [LengthPrefixedError] if the storage ends before the data does or if the data is too long for the length prefix.
+//!
+//! Its end is resolved at runtime, so `View::try_new` checks that the storage contains all of its data, and a [LayoutCursor]
+//! advances exactly past it, which allows reading more layouts from the bytes that follow.
+//! Fields after a length prefixed field don't have a fixed offset. They become [RelativeField]s, whose accessors
+//! read the length prefixes before them to find their offset in the storage.
+//!
+//! ```
+//! use binary_layout::{prelude::*, LayoutCursor, LengthPrefixed};
+//!
+//! binary_layout!(entry, LittleEndian, {
+//!   tag: u8,
+//!   value: LengthPrefixed<u16>,
+//! });
+//!
+//! fn main() {
+//!     let storage = [1, 2, 0, 0xab, 0xcd, 2, 0, 0];
+//!     let mut cursor = LayoutCursor::new(&storage);
+//!     let first = cursor.next::<entry::NestedView>().unwrap();
+//!     assert_eq!(Ok(&[0xab, 0xcd][..]), first.value().try_data());
+//!     let second = cursor.next::<entry::NestedView>().unwrap();
+//!     assert_eq!(2, second.tag().read());
+//!     assert_eq!(Ok(&[][..]), second.value().try_data());
+//! }
+//! ```
+//!
+//! ### Length prefixed strings: `LengthPrefixedStr<u8>`, `LengthPrefixedStr<u16>`, `LengthPrefixedStr<u32>`, `LengthPrefixedStr<u64>`.
+//! A [LengthPrefixedStr] field is stored like a [LengthPrefixed] field, but its data is a UTF-8 string. It offers the same API as [FixedString] to read and write it as a `&str`.
+//! Reading fails with [LengthPrefixedStrError] if the storage ends before the string does or the string isn't valid UTF-8,
+//! writing fails with [LengthPrefixedError] if the string doesn't fit into the storage or is too long for the length prefix.
+//!
+//! ### Variable length integers: `Leb128<u16>`, `Leb128<u32>`, `Leb128<u64>`, `Vlq<u16>`, `Vlq<u32>`, `Vlq<u64>`, `ZigZag<i16>`, `ZigZag<i32>`, `ZigZag<i64>`.
+//! A [Leb128] field stores an unsigned integer as [LEB128](https://en.wikipedia.org/wiki/LEB128) varint, which takes up between one byte and
+//! as many bytes as needed for the integer type, e.g. up to 10 bytes for [u64]. They are read and written with [FieldCopyAccess::try_read] and
+//! [FieldCopyAccess::try_write], which fail with [VarintError] if the varint is cut off by the end of the storage, the value doesn't fit into
+//! the integer type, or there isn't enough storage to write it.
+//! A [Vlq] field stores an unsigned integer as variable length quantity like in MIDI files, i.e. like [Leb128] but with the most significant bits first.
+//! A [ZigZag] field stores a signed integer like the `sint32` and `sint64` types of protobuf, i.e. zigzag encoded so that
+//! values with a small magnitude take up few bytes, and then stored like a [Leb128] field.
+//! Like for [LengthPrefixed] fields, a [LayoutCursor] advances exactly past them, and the fields after them are [RelativeField]s,
+//! whose offsets are found by decoding the varints before them.
+//!
+//! ### Binary-coded decimal: `Bcd<u8, DIGITS>`, `Bcd<u16, DIGITS>`, `Bcd<u32, DIGITS>`, `Bcd<u64, DIGITS>`.
+//! A [Bcd] field stores an unsigned integer with `DIGITS` decimal digits as packed BCD, i.e. two digits per byte, like timestamps in GSM or smart card records.
+//! Reading fails with [InvalidBcdError] if a nibble isn't a decimal digit, writing fails with [IntegerOutOfRangeError] if the value has more than `DIGITS` digits.
+//!
+//! ### Reserved bytes: `Padding<N>`
+//! A [Padding] field takes up `N` bytes so that the fields after it have the right offsets, but its value can't be read or written.
+//! The [trait@Field] API offers [FieldPaddingAccess::is_zero] and [FieldPaddingAccess::clear] and the [struct@FieldView] API offers [FieldView::is_zero] and [FieldView::clear]
+//! to check that the reserved bytes are zero or to zero them. Layouts can also skip bytes by pinning a field to an [explicit offset](crate::binary_layout!#explicit-offsets)
+//! or have the padding inserted for them by being [aligned](crate::binary_layout!#aligned-layouts).
+//! The [no_gaps](crate::binary_layout!#checking-for-gaps) option makes sure that all such bytes are declared as [Padding] fields instead.
+//!
+//! ### Magic numbers: `Magic<T, VALUE>`
+//! A [Magic] field stores the constant `VALUE` as an unsigned integer `T`, like the signature at the beginning of a file format.
+//! The [trait@Field] API offers [FieldMagicAccess::is_valid] and [FieldMagicAccess::write_magic] and the [struct@FieldView] API offers [FieldView::is_valid] and [FieldView::write_magic].
+//! The generated `View::validate()` checks all magic fields of a layout and its nested layouts, failing with [ViewError::InvalidMagic].
+//!
+//! ### Optional trailing fields: `Optional<T>`
+//! An [Optional] field may be missing from the end of the storage, like fields that later versions of a protocol appended.
+//! Optional fields have to be at the end of a layout. `View::try_new` accepts storage that ends before them and their accessors return an `Option`.
+//!
 //! ### Custom field types
 //! You can define your own custom types as long as they implement the [trait@LayoutAs] trait to define how to convert them from/to a primitive type.
+//! These conversions can fail, e.g. to reject out-of-range values, in which case the field is read with [FieldCopyAccess::try_read] and the error is returned in [WrappedFieldError::LayoutAsError].
+//! For fieldless enums like `#[repr(u8)] enum PacketType`, the [layout_as_enum!] macro implements it for you, so they can be used as `packet_type: PacketType as u8`.
+//! Reading such a field fails with [InvalidEnumValueError] if the stored value doesn't match any variant.
 //!
 //! # Data types maybe supported in the future
 //! These data types aren't supported yet, but they could be added in theory and might be added in future versions.
-//! - bit fields / [bool](https://doc.rust-lang.org/stable/core/primitive.bool.html) stored as 1 bit
 //!
 //! ### Data types with dynamic length
 //! This crate relies on a static layout, it cannot support data types with dynamic length.
 //! In theory, types with dynamic length could be supported if they either
-//! - are the last field of a layout, an already implemented example of this are open ended byte arrays.
+//! - are the last field of a layout, already implemented examples of this are open ended arrays like `[u8]` or `[u32]`,
+//! - or store their length in the data, so the offsets of the fields after them can be found at runtime. Already implemented examples of this are
+//!   [LengthPrefixed] fields and varints like [Leb128].
 //! - or they may be in the middle of the packet but have a maximal size defined and will always reserve storage for their maximal size, even if smaller.
 //!   This way, the fields after it would still have a constant offset.
 //!
 //! Both of these, however, would be some effort to implement and it is unclear if that will ever happen (unless somebody opens a PR for it).
 //!
-//! ### Strings
-//! For strings, note that even fixed-size UTF-8 strings take a variable number of bytes because of the UTF-8 encoding and that brings all the issues of data types with dynamic length with it.
-//! This is why strings aren't supported yet.
+//! ### Strings with dynamic length
+//! Strings stored in a fixed number of bytes are supported with [FixedString] and [FixedCStr], and strings with a length prefix at the end of a layout with [LengthPrefixedStr],
+//! but strings with a length prefix or terminator in the middle of a layout bring all the issues of data types with dynamic length with them. This is why they aren't supported yet.
 //!
-//! ### Fixed-size arrays other than `[u8; N]`
-//! Say we wanted to have a `[u32; N]` field. The API couldn't just return a zero-copy `&[u32; N]` to the caller because that would use the system byte order (i.e. endianness) which might be different from the byte order defined in the packet layout.
-//! To make this cross-platform compatible, we'd have to wrap these slices into our own slice type that enforces the correct byte order and return that from the API.
-//! This complexity is why it wasn't implemented yet, but feel free to open a PR if you need this.
+//! ### Fixed-size arrays of types other than numbers
+//! Arrays of numbers like `[u32; N]` are supported, but fixed size arrays of other field types, e.g. of nested layouts or custom types like `[bool as u8; N]`, aren't.
+//! Open ended arrays of nested layouts already exist, so this would mostly be a matter of giving their view a fixed length, but feel free to open a PR if you need this.
 //!
 //! # Nesting
 //! Layouts can be nested within each other by using the `NestedView` type created by the [binary_layout!] macro for one layout as a field type in another layout.
@@ -200,28 +366,126 @@
 //! });
 //! # fn main() {}
 //! ```
+//!
+//! # Tagged unions
+//! If the layout stored in a byte array field depends on the value of a tag field before it, like the payload after a message type byte,
+//! the [layout_variants!] macro declares an enum of the possible layouts and adds accessors like `body_variant()` returning the enum to the `View`.
+//!
+//! # Unions
+//! If a byte array field stores data that can be read as several layouts at the same time, like a C union,
+//! the [layout_union!] macro adds accessors like `raw_as_segment()` for each of the layouts to the `View`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
-#![forbid(unsafe_code)]
+#![cfg_attr(
+    not(any(feature = "memmap2", feature = "volatile")),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(any(feature = "memmap2", feature = "volatile"), deny(unsafe_code))]
 #![deny(missing_docs)]
+// The continuation lines of the `#[repr(packed)]` list in the crate docs are indented to match README.md
+#![allow(clippy::doc_overindented_list_items)]
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "std")]
+mod assert;
+#[cfg(feature = "binrw")]
+pub mod binrw;
+#[cfg(feature = "bitvec")]
+pub mod bitvec;
+mod builder;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+mod convert;
+#[cfg(feature = "crc")]
+pub mod crc;
+#[cfg(feature = "std")]
+mod diff;
 mod endianness;
 mod fields;
+mod finalize;
+mod flush;
+#[cfg(feature = "std")]
+mod fuzz;
+mod has_field;
+mod hexdump;
+mod layout_as_enum;
+mod layout_cursor;
+mod layout_info;
+mod layout_slice;
+mod layout_union;
+mod layout_variants;
 mod macro_binary_layout;
+mod map_struct;
+mod offset_of;
 mod utils;
+mod view_error;
 
 pub mod example;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "std")]
+pub mod import;
+#[cfg(feature = "memmap2")]
+pub mod memmap2;
+#[cfg(feature = "nom")]
+pub mod nom;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "tokio-util")]
+pub mod tokio_util;
+#[cfg(feature = "uom")]
+pub mod uom;
 
-pub use endianness::{BigEndian, Endianness, LittleEndian, NativeEndian};
+pub use builder::{FieldSet, FieldUnset};
+pub use convert::CopyConvertingError;
+#[cfg(feature = "std")]
+pub use diff::FieldDiff;
+pub use endianness::{BigEndian, EndianKind, Endianness, LittleEndian, NativeEndian};
+#[cfg(feature = "volatile")]
+pub use fields::primitive::FieldVolatileAccess;
+#[cfg(feature = "std")]
+pub use fields::primitive::{
+    DurationMicros, DurationMillis, DurationNanos, DurationSeconds, TimeOutOfRangeError,
+    UnixMillis, UnixSeconds,
+};
 pub use fields::{
+    bit_field::BitField,
+    bool::InvalidBoolError,
+    contextual::{ContextualField, FieldContextAccess, LayoutAsWith},
     primitive::{
-        FieldCopyAccess, FieldReadExt, FieldSliceAccess, FieldView, FieldWriteExt,
-        NonZeroIsZeroError, PrimitiveField,
+        i24, i40, i48, i56, u24, u40, u48, u56, Bcd, BitArray, CStrReadError, CStrWriteError,
+        FieldArrayAccess, FieldBitArrayAccess, FieldCopyAccess, FieldLengthPrefixedAccess,
+        FieldMagicAccess, FieldOwnedAccess, FieldPaddingAccess, FieldReadExt, FieldSliceAccess,
+        FieldStringAccess, FieldView, FieldWriteExt, FixedCStr, FixedString,
+        IntegerOutOfRangeError, InvalidBcdError, Leb128, LengthPrefixed, LengthPrefixedError,
+        LengthPrefixedStr, LengthPrefixedStrError, Magic, NonZeroIsZeroError, Optional, Padding,
+        PrimitiveField, StringTooLongError, VarintError, Vlq, ZigZag,
     },
+    relative::RelativeField,
     wrapped::{LayoutAs, WrappedField, WrappedFieldError},
     Field,
 };
+pub use finalize::{Checksum, FinalizeError, InternetChecksum};
+pub use flush::FlushableStorage;
+pub use has_field::{FieldMetadata, FieldName, HasField, HasFieldMut};
+pub use hexdump::HexDump;
+pub use layout_as_enum::InvalidEnumValueError;
+pub use layout_cursor::LayoutCursor;
+pub use layout_info::{
+    ArrayValue, FieldInfo, FieldKind, FieldValue, Layout, LayoutInfo, LayoutView, SizedLayout,
+    VarintEncoding,
+};
+pub use layout_slice::LayoutSlice;
+pub use map_struct::WriteFrom;
 pub use utils::{data::Data, infallible::InfallibleResultExt};
+pub use view_error::ViewError;
 
 /// Import this to get everything into scope that you need for defining and using layouts.
 ///
@@ -231,8 +495,11 @@ pub use utils::{data::Data, infallible::InfallibleResultExt};
 /// ```
 pub mod prelude {
     pub use super::{
-        BigEndian, Field, FieldCopyAccess, FieldReadExt, FieldSliceAccess, FieldWriteExt,
-        InfallibleResultExt, LittleEndian, NativeEndian, NonZeroIsZeroError,
+        BigEndian, Field, FieldArrayAccess, FieldBitArrayAccess, FieldContextAccess,
+        FieldCopyAccess, FieldLengthPrefixedAccess, FieldMagicAccess, FieldPaddingAccess,
+        FieldReadExt, FieldSliceAccess, FieldStringAccess, FieldWriteExt, InfallibleResultExt,
+        IntegerOutOfRangeError, InvalidBoolError, LittleEndian, NativeEndian, NonZeroIsZeroError,
+        WriteFrom,
     };
     pub use crate::binary_layout;
     #[allow(deprecated)]
@@ -242,11 +509,98 @@ pub mod prelude {
 /// Internal things that need to be exported so our macros can use them. Don't use directly!
 #[doc(hidden)]
 pub mod internal {
+    #[cfg(feature = "arbitrary")]
+    pub use crate::arbitrary::{
+        arbitrary_storage, ArbitraryBitsField, ArbitraryBytesField, ArbitraryField,
+        ArbitraryNested, ArbitraryNestedField, ArbitraryValueField,
+    };
+    #[cfg(feature = "std")]
+    pub use crate::assert::{
+        assert_fields_match, assert_views_eq, parse_hex, Mismatch, ReadForAssert, WriteForAssert,
+    };
+    #[cfg(feature = "binrw")]
+    pub use crate::binrw::read_storage;
+    pub use crate::builder::BuilderField;
+    #[cfg(feature = "std")]
+    pub use crate::convert::{convert_in_place, write_layout};
+    pub use crate::convert::{copy_converting, copy_layout};
     pub use crate::fields::{
-        primitive::{BorrowingNestedView, NestedViewInfo, OwningNestedView},
+        primitive::{BorrowingNestedView, NestedValueStruct, NestedViewInfo, OwningNestedView},
+        relative::SelectField,
         StorageIntoFieldView, StorageToFieldView,
     };
-    pub use crate::macro_binary_layout::{option_usize_add, unwrap_field_size};
+    pub use crate::finalize::{
+        check_checksum_range, compute_checksum, compute_checksum_over, field_data_len, ByteRange,
+        ChecksumRange, FieldRange,
+    };
+    pub use crate::flush::{flush_fields, flush_layout};
+    #[cfg(feature = "std")]
+    pub use crate::fuzz::{
+        check_random_round_trips, check_round_trip, RoundTripBytesField, RoundTripField,
+        RoundTripNested, RoundTripNestedField, RoundTripValueField,
+    };
+    pub use crate::has_field::field_name_hash;
+    pub use crate::layout_info::{
+        check_optional_fields, field_alignment, fields_alignment, fmt_view, has_dynamic_len_kind,
+        has_fields_after_dynamic_len, new_field_info, new_layout_info, padding_size, views_eq,
+        FieldKindInfo, ViewInfo, ViewInfoMut,
+    };
+    pub use crate::layout_union::check_union_size;
+    pub use crate::macro_binary_layout::{
+        align_offset, next_field_offset, option_align_offset, pad_to_size, pin_field_offset,
+        static_layout_size, unwrap_field_size, unwrap_layout_size,
+    };
+    #[cfg(feature = "memmap2")]
+    pub use crate::memmap2::{map_file, map_file_mut};
+    #[cfg(feature = "nom")]
+    pub use crate::nom::parse_storage;
+    pub use crate::offset_of::static_field_size;
+    #[cfg(feature = "proptest")]
+    pub use crate::proptest::{
+        storage_strategy, BitsStrategyField, BytesStrategyField, LayoutStrategy,
+        NestedStrategyField, StrategyField, ValueStrategyField,
+    };
+    #[cfg(feature = "serde")]
+    pub use crate::serde::{deserialize_storage, serialize_view};
+    #[cfg(feature = "tokio")]
+    pub use crate::tokio::{read_storage_async, write_layout_async};
+    #[cfg(feature = "tokio-util")]
+    pub use crate::tokio_util::{
+        decode_frame, dynamic_frame_len, encode_frame, length_field_frame_len,
+    };
+    #[cfg(feature = "uom")]
+    pub use crate::uom::{QuantityConversion, QuantityStorage};
+    pub use crate::view_error::{check_storage_size, field_region, storage_at, validate};
+    pub use crate::{
+        if_arbitrary, if_binrw, if_memmap2, if_nom, if_proptest, if_rkyv, if_serde, if_std,
+        if_tokio, if_tokio_util,
+    };
+    #[cfg(feature = "arbitrary")]
+    pub use arbitrary;
+    #[cfg(feature = "binrw")]
+    pub use binrw;
     pub use doc_comment::doc_comment;
+    #[cfg(feature = "memmap2")]
+    pub use memmap2;
+    #[cfg(feature = "nom")]
+    pub use nom;
     pub use paste::paste;
+    #[cfg(feature = "proptest")]
+    pub use proptest;
+    #[cfg(feature = "rkyv")]
+    pub use rkyv;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "std")]
+    pub use std::io;
+    #[cfg(feature = "std")]
+    pub use std::path::Path;
+    #[cfg(feature = "std")]
+    pub use std::vec::Vec;
+    #[cfg(feature = "tokio")]
+    pub use tokio;
+    #[cfg(feature = "tokio-util")]
+    pub use tokio_util;
+    #[cfg(feature = "uom")]
+    pub use uom;
 }
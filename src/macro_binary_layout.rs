@@ -2,7 +2,7 @@
 ///
 /// Data layouts define
 /// - a name for the layout
-/// - and endianness for its fields ([BigEndian](crate::BigEndian) or [LittleEndian](crate::LittleEndian))
+/// - and endianness for its fields ([BigEndian](crate::BigEndian), [LittleEndian](crate::LittleEndian) or [NativeEndian](crate::NativeEndian) for the byte order of the target)
 /// - and an ordered collection of typed fields.
 ///
 /// See [supported field types](crate#supported-field-types) for a list of supported field types.
@@ -33,6 +33,325 @@
 /// });
 /// ```
 ///
+/// ## Round trip tests
+/// Adding `round_trip_tests` after the endianness generates a `#[test]` function named `<<Name>>_round_trip` next to the layout.
+/// It reads the values of all fields from pseudo random data, writes them back and checks that reading them again returns the same values,
+/// see the generated `fuzz_round_trip` function. This gives each layout baseline test coverage, e.g. for custom [LayoutAs](crate::LayoutAs) types.
+/// The test is only generated with the `std` feature.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(icmp_packet, BigEndian, round_trip_tests, {
+///   packet_type: u8,
+///   code: u8,
+///   checksum: u16,
+///   rest_of_header: [u8; 4],
+///   data_section: [u8],
+/// });
+/// ```
+///
+/// ## Version fields
+/// One integer field can declare the versions of the layout that are supported, e.g. `version: u8 in 1..=3`.
+/// The generated `View::try_new` then rejects storage with any other version with [ViewError::UnsupportedVersion](crate::ViewError::UnsupportedVersion),
+/// and the module gets `SUPPORTED_VERSIONS`, `MIN_VERSION` and `MAX_VERSION` constants.
+/// ```
+/// use binary_layout::{prelude::*, ViewError};
+///
+/// binary_layout!(message, BigEndian, {
+///   version: u8 in 1..=3,
+///   length: u16,
+///   payload: [u8],
+/// });
+///
+/// assert_eq!(1..=3, message::SUPPORTED_VERSIONS);
+/// let view = message::View::try_new(vec![2, 0, 1, 42]).unwrap();
+/// assert_eq!(&[42], view.payload());
+/// assert!(matches!(
+///     message::View::try_new(vec![4, 0, 1, 42]),
+///     Err(ViewError::UnsupportedVersion { version: 4, .. })
+/// ));
+/// ```
+///
+/// ## Unit fields
+/// Fields of primitive number types can declare a unit, e.g. `timeout_ms: u16 unit(Milliseconds)` or `voltage: u16 unit(Millivolts, "mV")`.
+/// This generates a transparent newtype `Milliseconds(pub u16)` in the layout module, which converts from and into `u16`,
+/// displays the value with the unit (the name of the newtype, or the given symbol) and is what the field reads and writes.
+/// This keeps values in different units from being mixed up at API boundaries. Each unit can only be declared once per layout,
+/// other fields can use it as `Milliseconds as u16`.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(config, LittleEndian, {
+///   timeout_ms: u16 unit(Milliseconds),
+///   retry_delay_ms: Milliseconds as u16,
+///   voltage: u16 unit(Millivolts, "mV"),
+/// });
+///
+/// let mut view = config::View::new(vec![0; 6]);
+/// view.timeout_ms_mut().write(config::Milliseconds(500));
+/// view.voltage_mut().write(3300.into());
+/// assert_eq!(500, u16::from(view.timeout_ms().read()));
+/// assert_eq!("500 Milliseconds", view.timeout_ms().read().to_string());
+/// assert_eq!("3300 mV", view.voltage().read().to_string());
+/// ```
+///
+/// ## Quantity fields
+/// With the `uom` feature, fields of primitive number types can store a physical quantity in a given unit,
+/// e.g. `voltage: u16 quantity(ElectricPotential in millivolt)`, so they read and write `uom` quantities, see [uom](crate::uom).
+///
+/// ## Fields with context
+/// Custom types whose conversion needs runtime context, e.g. a scale factor from another field, implement [LayoutAsWith](crate::LayoutAsWith)
+/// and are declared like `temperature: Millikelvin as i16 with Calibration`. They're accessed with `read_with(&calibration)` and `write_with(value, &calibration)`.
+///
+/// ## Length and checksum fields
+/// Integer fields can be declared to store the length of another field in bytes, e.g. `length: u16 length_of(payload)`,
+/// or a checksum computed with an implementation of [Checksum](crate::Checksum), e.g. `checksum: u16 checksum(InternetChecksum)`.
+/// After writing the other fields, the generated `View::finalize()` fills them, first the lengths and then the checksums.
+/// The checksum is computed over the data of the layout with the checksum field set to zero.
+/// Checksum fields can instead cover a range of fields, e.g. `crc: u32 checksum(Crc32, header..=payload)`, or of bytes, e.g. `crc: u32 checksum(Crc32, 0..12)`,
+/// which must not contain the checksum field itself. For those, the generated `View` also offers `compute_<<field>>()`, `verify_<<field>>()`
+/// and `update_<<field>>()`. The `crc` feature offers common CRC algorithms, see [crc](crate::crc).
+/// ```
+/// use binary_layout::{prelude::*, Checksum, InternetChecksum};
+///
+/// binary_layout!(packet, BigEndian, {
+///   kind: u8,
+///   length: u8 length_of(payload),
+///   checksum: u16 checksum(InternetChecksum),
+///   payload: [u8],
+/// });
+///
+/// fn func(storage_data: &mut [u8]) {
+///   let mut view = packet::View::new(storage_data);
+///   view.kind_mut().write(8);
+///   view.payload_mut().copy_from_slice(&[1, 2]);
+///   view.finalize().unwrap();
+///   assert_eq!(2, view.length().read());
+/// }
+///
+/// # fn main() {
+/// #   let mut storage = [0; 6];
+/// #   func(&mut storage);
+/// #   assert_eq!(0, InternetChecksum::checksum(&storage));
+/// # }
+/// ```
+///
+/// ## Bit fields
+/// Unsigned integer and `bool` fields can be declared to only take up some bits, e.g. `version: u8 bits(4)`, see [BitField](crate::BitField).
+/// Consecutive bit fields are packed into the same bytes, in big endian layouts starting at the most significant bit
+/// and in little endian layouts starting at the least significant bit. Each group of consecutive bit fields has to add up to whole bytes,
+/// otherwise the layout fails to compile.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(ipv4_header, BigEndian, {
+///   version: u8 bits(4),
+///   ihl: u8 bits(4),
+///   dscp: u8 bits(6),
+///   ecn: u8 bits(2),
+///   total_length: u16,
+/// });
+///
+/// let mut view = ipv4_header::View::new([0; 4]);
+/// view.version_mut().try_write(4).unwrap();
+/// view.ihl_mut().try_write(5).unwrap();
+/// view.ecn_mut().try_write(1).unwrap();
+/// view.total_length_mut().write(20);
+/// assert_eq!(5, view.ihl().read());
+/// assert!(view.dscp_mut().try_write(64).is_err());
+/// assert_eq!([0x45, 0x01, 0, 20], view.into_storage());
+/// ```
+///
+/// ## Explicit offsets
+/// Fields can be pinned to an absolute byte offset, e.g. `control @ 0x40: u32`, which is handy for register maps and headers with large reserved gaps.
+/// The bytes between the end of the previous field and the pinned offset are skipped like a [Padding](crate::Padding) field,
+/// but they don't show up as a field. The fields after a pinned field follow it as usual. If the fields before it end after
+/// the pinned offset, the layout fails to compile.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(registers, LittleEndian, {
+///   id: u32,
+///   status: u16,
+///   control @ 0x40: u32,
+///   interrupt_mask: u32,
+///   data @ 0x80: [u8; 16],
+/// });
+///
+/// assert_eq!(0x40, registers::control::OFFSET);
+/// assert_eq!(0x44, registers::interrupt_mask::OFFSET);
+/// assert_eq!(Some(0x90), registers::SIZE);
+/// ```
+///
+/// ## Aligned layouts
+/// Adding `aligned` after the endianness inserts padding before each field so that it is naturally aligned, like `#[repr(C)]` does,
+/// which makes it easy to mirror C structs. Numbers are aligned to their size, arrays to their elements, nested layouts to their most aligned field
+/// and everything else, e.g. byte arrays and bit fields, to a single byte. The size of the layout is rounded up to a multiple of its alignment.
+/// The module of an aligned layout gets `ALIGNMENT` and `PADDING` constants with the alignment of the layout and the number of padding bytes inserted.
+/// Fields with an explicit offset keep it, which has to be aligned for the field.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// // struct entry { uint8_t kind; uint32_t id; uint16_t flags; uint8_t name[3]; uint64_t timestamp; };
+/// binary_layout!(entry, LittleEndian, aligned, {
+///   kind: u8,
+///   id: u32,
+///   flags: u16,
+///   name: [u8; 3],
+///   timestamp: u64,
+/// });
+///
+/// assert_eq!(4, entry::id::OFFSET);
+/// assert_eq!(8, entry::flags::OFFSET);
+/// assert_eq!(16, entry::timestamp::OFFSET);
+/// assert_eq!(Some(24), entry::SIZE);
+/// assert_eq!(8, entry::ALIGNMENT);
+/// assert_eq!(6, entry::PADDING);
+/// ```
+///
+/// ## Padding to a size
+/// Adding `pad_to = <<Size>>` after the endianness pads the layout to the given total size, e.g. for structures that fill a disk sector or flash page.
+/// `SIZE` is then the padded size, and if the fields are larger than it, the layout fails to compile. Layouts ending with an open ended field can't be padded.
+/// The options after the endianness, i.e. `aligned`, `pad_to = <<Size>>`, `value_struct = <<Name>>`, `builder`, `round_trip_tests` and `no_gaps`, can be combined in any order.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(boot_sector, LittleEndian, pad_to = 512, {
+///   jump: [u8; 3],
+///   oem_name: [u8; 8],
+///   bytes_per_sector: u16,
+/// });
+///
+/// assert_eq!(Some(512), boot_sector::SIZE);
+/// assert!(boot_sector::View::try_new([0; 512]).is_ok());
+/// assert!(boot_sector::View::try_new([0; 13]).is_err());
+/// ```
+///
+/// ## Checking for gaps
+/// Adding `no_gaps` after the endianness makes the layout fail to compile if any of its bytes aren't covered by a field, e.g. because a field
+/// is pinned to an [explicit offset](#explicit-offsets) after the end of the previous field, or because of the padding of [aligned](#aligned-layouts)
+/// and [padded](#padding-to-a-size) layouts. This catches mistakes when transcribing large register maps from a datasheet. Reserved bytes have to be
+/// declared as [Padding](crate::Padding) fields then. Fields overlapping each other always fail to compile, with or without `no_gaps`.
+/// ```
+/// use binary_layout::{prelude::*, Padding};
+///
+/// binary_layout!(registers, LittleEndian, no_gaps, {
+///   id: u32,
+///   reserved: Padding<4>,
+///   control @ 0x08: u32,
+/// });
+///
+/// fn main() {
+///     assert_eq!(Some(12), registers::SIZE);
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // The bytes between status and control aren't covered by a field
+/// binary_layout!(registers, LittleEndian, no_gaps, {
+///   id: u32,
+///   status: u16,
+///   control @ 0x08: u32,
+/// });
+/// ```
+///
+/// ## Value structs
+/// Adding `value_struct = <<Name>>` after the endianness generates a plain struct with a public member for each field,
+/// holding the values of all fields by value. `View::read_all()` copies them out of the storage and `View::write_all(&value)`
+/// or `value.write_to(&mut storage)` writes them back. The fields need to implement [FieldOwnedAccess](crate::FieldOwnedAccess),
+/// i.e. reading and writing them can't fail, and their values need to implement `Debug`, `Clone` and `PartialEq`.
+/// [Padding](crate::Padding) and [Magic](crate::Magic) fields have the value `()`, and nested layouts need a value struct themselves.
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(point, LittleEndian, value_struct = Point, {
+///   x: i32,
+///   y: i32,
+/// });
+///
+/// binary_layout!(line, LittleEndian, value_struct = Line, {
+///   color: [u8; 3],
+///   from: point::NestedView,
+///   to: point::NestedView,
+/// });
+///
+/// fn main() {
+///     let view = line::View::new([0xff, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0]);
+///     let mut value = view.read_all();
+///     assert_eq!(point::Point { x: 1, y: 2 }, value.from);
+///
+///     value.to.y = -1;
+///     let mut storage = [0; 19];
+///     value.write_to(&mut storage);
+///     assert_eq!(value, line::View::new(&storage).read_all());
+/// }
+/// ```
+///
+/// ## Builders
+/// Adding `builder` after the endianness generates a `Builder` that writes the fields into a storage with `set_${field_name}(value)`
+/// and returns a `View` with `build()`. The builder tracks which fields were set in its type, so `build()` only compiles
+/// once every field was set. [Padding](crate::Padding), [Magic](crate::Magic) and [Optional](crate::Optional) fields don't need to be set,
+/// `build()` zeroes the padding and writes the magic values. Like for [value structs](#value-structs), the fields need to implement
+/// [FieldOwnedAccess](crate::FieldOwnedAccess).
+/// ```
+/// use binary_layout::{prelude::*, Magic};
+///
+/// binary_layout!(header, BigEndian, builder, {
+///   magic: Magic<u16, 0xcafe>,
+///   version: u8,
+///   length: u16,
+/// });
+///
+/// fn main() {
+///     let view = header::Builder::new([0; 5])
+///         .set_version(1)
+///         .set_length(512)
+///         .build();
+///     assert_eq!([0xca, 0xfe, 1, 2, 0], view.into_storage());
+/// }
+/// ```
+///
+/// With the `std` feature, `build_to(writer)` also writes the bytes of the built view to a [std::io::Write], e.g. to encode a message
+/// into a socket with `header::Builder::new([0; 5]).set_version(1).set_length(512).build_to(&mut socket)?`.
+///
+/// Forgetting to set a field fails to compile:
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(header, BigEndian, builder, {
+///   version: u8,
+///   length: u16,
+/// });
+///
+/// let view = header::Builder::new([0; 3]).set_version(1).build();
+/// ```
+///
+/// ## Layouts of different sizes
+/// Layouts can't be generic over const parameters like `block<const N: usize>`. The offsets of the fields are const generic
+/// parameters of their types, and stable Rust can't compute those from another const parameter, e.g. the offset `N` of a field after `[u8; N]`.
+/// If only the last field differs in size, declare it as an open ended field like `data: [u8]`.
+/// Otherwise, declare one layout per size, e.g. with a small macro around [binary_layout!](crate::binary_layout!):
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// macro_rules! block {
+///     ($name: ident, $size: literal) => {
+///         binary_layout!($name, LittleEndian, {
+///             data: [u8; $size],
+///             crc: u32,
+///         });
+///     };
+/// }
+///
+/// block!(block_512, 512);
+/// block!(block_4096, 4096);
+///
+/// assert_eq!(512, block_512::crc::OFFSET);
+/// assert_eq!(Some(4100), block_4096::SIZE);
+/// ```
+///
 /// # Generated code
 /// See [icmp_packet](crate::example::icmp_packet) for an example.
 ///
@@ -41,6 +360,32 @@
 ///   - metadata like [OFFSET](crate::Field::OFFSET) and [SIZE](crate::Field::SIZE) as rust `const`s
 ///   - data accessors for the [Field](crate::Field) API
 /// - The module will also contain a `View` struct that offers the [FieldView](crate::FieldView) API.
+/// - A `SIZE` constant has the size of the layout, which is `None` if it ends with an open ended field,
+///   and a `MIN_SIZE` constant has the number of bytes a view needs at least, see [LayoutInfo::min_size](crate::LayoutInfo::min_size).
+/// - A `LAYOUT` constant describes the layout and its fields at runtime, see [LayoutInfo](crate::LayoutInfo).
+///   Printing it, e.g. `println!("{}", my_layout::LAYOUT)`, shows a table of its fields.
+/// - A `Fields` struct has a [FieldInfo](crate::FieldInfo) constant for each field, e.g. `my_layout::Fields::field1.name`,
+///   which allows referring to field names and types without hard coding them as strings.
+/// - `View::subview(range)`, `View::subview_mut(range)` and `View::into_subview(range)` return a byte range of the storage, e.g. for another layout to interpret the payload region.
+/// - A `FieldId` enum has a variant for each field, with `FieldId::iter()` and the offset and size of each field, for exhaustive `match`es over the fields.
+/// - An `offset_of` `const fn` looks up the offset and size of a field by its name, see [LayoutInfo::offset_of](crate::LayoutInfo::offset_of).
+/// - With the `value_struct = <<Name>>` option, a struct of that name holds the values of all fields, see [Value structs](#value-structs).
+/// - With the `builder` option, a `Builder` writes all fields before returning a `View`, see [Builders](#builders).
+/// - `NestedView` implements [Layout](crate::Layout) and [LayoutView](crate::LayoutView), which allows generic code over any layout,
+///   and [SizedLayout](crate::SizedLayout), which allows generic code to require layouts with a static size.
+/// - `View` implements [HasField](crate::HasField) and [HasFieldMut](crate::HasFieldMut) for each field, which allows generic code over all layouts with a field of a certain name.
+/// - `NestedView` and `View` implement [FieldMetadata](crate::FieldMetadata) for each field, which describes the field at the type level, e.g. its name.
+/// - With the `std` feature, a `diff` function compares two views and returns the fields whose values differ, see [LayoutInfo::diff](crate::LayoutInfo::diff).
+/// - With the `std` feature, a `fuzz_round_trip` function checks that re-encoding the field values read from some data reproduces the data, which makes it a ready-to-use fuzz target.
+/// - With the `arbitrary` feature, `View<Vec<u8>>` implements `arbitrary::Arbitrary`, see [arbitrary](crate::arbitrary).
+/// - With the `binrw` feature, `View<Vec<u8>>` implements `binrw::BinRead` and all views implement `binrw::BinWrite`, see [binrw](crate::binrw).
+/// - With the `memmap2` feature, `View::map_file(path)`, `View::map_file_mut(path)` and `View::map_file_mut_with_len(path, len)` return views over memory-mapped files, see [memmap2](crate::memmap2).
+/// - With the `nom` feature, a `parse` function is a nom parser returning a view of the layout, see [nom](crate::nom).
+/// - With the `proptest` feature, a `strategy` function returns a proptest strategy generating the storage of valid views, see [proptest](crate::proptest).
+/// - With the `rkyv` feature, `View<Vec<u8>>` implements `rkyv::Archive`, `rkyv::Serialize` and `rkyv::Deserialize`, see [rkyv](crate::rkyv).
+/// - With the `serde` feature, all views implement `serde::Serialize` and `View<Vec<u8>>` implements `serde::Deserialize`, see [serde](crate::serde).
+/// - With the `tokio-util` feature, a `Codec` implements the `tokio_util::codec` traits to decode and encode views of the layout, see [tokio_util](crate::tokio_util).
+/// - With the `uom` feature, a marker type per quantity field converts between the quantity and its stored unit, see [uom](crate::uom).
 ///
 /// This macro will also generate rustdoc documentation for everything it generates. One of the best ways to figure out
 /// how to use the generated layouts is to read the rustdoc documentation that was generated for them.
@@ -67,7 +412,25 @@
 ///
 /// The generated `View` struct will offer
 /// - `View::new(storage)` to create a `View`
+/// - `View::new_zeroed()` to create a `View` owning a `Vec<u8>` of `SIZE` zero bytes, if the layout has a static size and the `std` feature is enabled
+/// - `View::read_from(reader)` and `View::read_from_with_len(reader, len)` to create a `View` owning a `Vec<u8>` of `SIZE` or `len` bytes read from a [std::io::Read], if the `std` feature is enabled
+/// - `View::write_to(writer)` to write the bytes of the layout to a [std::io::Write], if the `std` feature is enabled
+/// - `View::read_from_async(reader)`, `View::read_from_with_len_async(reader, len)` and `View::write_to_async(writer)` doing the same with the async I/O traits of tokio, if the `tokio` feature is enabled, see [tokio](crate::tokio)
+/// - `View::try_new(storage)` to create a `View` after checking that the storage is long enough and, if the layout has a version field, stores a supported version
+/// - `View::validate()` to check that the storage is long enough and all [Magic](crate::Magic) fields, also of nested layouts, store their value
+/// - `View::new_at(storage, offset)` to create a `View` of the layout starting at a byte offset of the storage, with the same checks as `View::try_new`
 /// - `View::into_storage(self)` to destroy a `View` and return the storage held
+/// - `View::read_all()` and `View::write_all(&value)` to copy the values of all fields into or out of the value struct, if the layout has one
+/// - `View::field_value(name)` to read the value of a field given by its name at runtime as a [FieldValue](crate::FieldValue), e.g. for generic inspection tools
+/// - `View::hexdump()` to print the bytes of the view with the field each of them belongs to
+/// - `View::copy_from(&other_view)` and `View::copy_from_bytes(data)` to copy the bytes of the layout, including the data of an open ended field, from another view or buffer
+/// - `View::copy_converting(&mut dst_view)` to copy the data into a view of the same layout with a different endianness, converting the byte order of each field
+/// - With the `std` feature, `View::convert_endianness::<OtherLayout>()` to convert the data in place into the byte order of the same layout with a different endianness
+/// - `View::finalize()` to fill the length and checksum fields, if the layout has any
+/// - `View::compute_${field_name}()`, `View::verify_${field_name}()` and `View::update_${field_name}()` for checksum fields that cover a range
+/// - `View::flush()` and `View::flush_fields(fields)` to synchronize the bytes of the layout or of some fields if the storage implements [FlushableStorage](crate::FlushableStorage)
+/// - `PartialEq` and `Eq` implementations comparing the bytes of each field with those of another view of the same layout, e.g. `assert_eq!(expected_view, view)`
+/// - A `Debug` implementation showing the name and value of each field, e.g. `my_layout { field1: 5, field2: [01 02] }`, with byte arrays longer than 16 bytes abbreviated
 ///
 /// and it will offer the following accessors for each field
 /// - `${field_name}()`: Read access. This returns a [FieldView](crate::FieldView) instance with read access.
@@ -76,12 +439,113 @@
 #[macro_export]
 macro_rules! binary_layout {
     ($name: ident, $endianness: ident, {$($field_name: ident : $field_type: ty $(as $underlying_type: ty)?),* $(,)?}) => {
+        $crate::binary_layout!(@layout $name, $endianness, {packed {} {} {}}, {$($field_name : $field_type $(as $underlying_type)?),*}, {}, {}, {{} {}});
+    };
+    ($name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, {packed {} {} {}}, {}, {}, {}, {{} {}}, {$($fields)*});
+    };
+    ($name: ident, $endianness: ident, $($options: tt)+) => {
+        $crate::binary_layout!(@options $name, $endianness, packed {} {} {} {} {}, {$($options)+});
+    };
+
+    // Collects the options between the endianness and the fields, i.e. the packing of the fields, the size to pad to,
+    // the name of the value struct, whether to generate a builder and tests and which lints to check
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt $tests: tt $lints: tt, {aligned, $($rest: tt)*}) => {
+        $crate::binary_layout!(@options $name, $endianness, aligned $pad_to $value_struct $builder $tests $lints, {$($rest)*});
+    };
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt $tests: tt $lints: tt, {pad_to = $size: literal, $($rest: tt)*}) => {
+        $crate::binary_layout!(@options $name, $endianness, $packing {$size} $value_struct $builder $tests $lints, {$($rest)*});
+    };
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt $tests: tt $lints: tt, {value_struct = $struct_name: ident, $($rest: tt)*}) => {
+        $crate::binary_layout!(@options $name, $endianness, $packing $pad_to {$struct_name} $builder $tests $lints, {$($rest)*});
+    };
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt $tests: tt $lints: tt, {builder, $($rest: tt)*}) => {
+        $crate::binary_layout!(@options $name, $endianness, $packing $pad_to $value_struct {builder} $tests $lints, {$($rest)*});
+    };
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt $tests: tt $lints: tt, {round_trip_tests, $($rest: tt)*}) => {
+        $crate::binary_layout!(@options $name, $endianness, $packing $pad_to $value_struct $builder {round_trip_tests} $lints, {$($rest)*});
+    };
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt $tests: tt $lints: tt, {no_gaps, $($rest: tt)*}) => {
+        $crate::binary_layout!(@options $name, $endianness, $packing $pad_to $value_struct $builder $tests {no_gaps}, {$($rest)*});
+    };
+    (@options $name: ident, $endianness: ident, $packing: ident $pad_to: tt $value_struct: tt $builder: tt {$($tests: ident)?} {$($lints: ident)?}, {{$($fields: tt)*}}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, {$packing $pad_to $value_struct $builder}, {}, {}, {}, {{} {}}, {$($fields)*});
+        $(
+            $crate::binary_layout!(@round_trip_test $name, $tests);
+        )?
+        $(
+            $crate::binary_layout!(@lint $name, $lints);
+        )?
+    };
+
+    (@lint $name: ident, no_gaps) => {
+        const _: () = assert!(
+            $crate::internal::padding_size(&$name::LAYOUT) == 0,
+            concat!("Error: The layout ", stringify!($name), " has bytes that aren't covered by a field. Use Padding fields for reserved bytes.")
+        );
+    };
+
+    (@round_trip_test $name: ident, round_trip_tests) => {
+        $crate::internal::if_std!{
+            $crate::internal::paste!{
+                #[cfg(test)]
+                #[test]
+                fn [<$name _round_trip>]() {
+                    $crate::internal::check_random_round_trips(&$name::LAYOUT, $name::fuzz_round_trip);
+                }
+            }
+        }
+    };
+
+    // Normalizes the field list, taking the supported versions off a version field, the unit newtypes off unit fields
+    // and the derived values off length and checksum fields. Explicit offsets are kept as `@ offset` before the field.
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {}) => {
+        $crate::binary_layout!(@layout $name, $endianness, $options, {$($parsed)*}, {$($version)*}, {$($units)*}, $derived);
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident @ $offset: literal : $($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* @ $offset}, {$($version)*}, {$($units)*}, $derived, {$field_name: $($tail)*});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: ident in $min_version: literal ..= $max_version: literal $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type,}, {$field_name: $field_type in $min_version ..= $max_version}, {$($units)*}, $derived, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: ident unit($unit: ident) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $unit as $field_type,}, {$($version)*}, {$($units)* $unit: $field_type = stringify!($unit);}, $derived, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: ident unit($unit: ident, $symbol: literal) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $unit as $field_type,}, {$($version)*}, {$($units)* $unit: $field_type = $symbol;}, $derived, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: ident quantity($quantity: ident in $quantity_unit: ident) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $quantity as $field_type => $quantity in $quantity_unit,}, {$($version)*}, {$($units)*}, $derived, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, {{$($lengths: tt)*} $checksums: tt}, {$field_name: ident : $field_type: ident length_of($target: ident) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type,}, {$($version)*}, {$($units)*}, {{$($lengths)* $field_name: $field_type = $target;} $checksums}, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, {$lengths: tt {$($checksums: tt)*}}, {$field_name: ident : $field_type: ident checksum($algorithm: path, $first: ident ..= $last: ident) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type,}, {$($version)*}, {$($units)*}, {$lengths {$($checksums)* $field_name = $algorithm, $crate::internal::FieldRange<$first, $last>;}}, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, {$lengths: tt {$($checksums: tt)*}}, {$field_name: ident : $field_type: ident checksum($algorithm: path, $start: literal .. $end: literal) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type,}, {$($version)*}, {$($units)*}, {$lengths {$($checksums)* $field_name = $algorithm, $crate::internal::ByteRange<$start, $end>;}}, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, {$lengths: tt {$($checksums: tt)*}}, {$field_name: ident : $field_type: ident checksum($algorithm: path) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type,}, {$($version)*}, {$($units)*}, {$lengths {$($checksums)* $field_name = $algorithm;}}, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: ident bits($bits: literal) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type; bits $bits,}, {$($version)*}, {$($units)*}, $derived, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: path as $underlying_type: ident with $context: ty $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type as $underlying_type where $context,}, {$($version)*}, {$($units)*}, $derived, {$($($tail)*)?});
+    };
+    (@parse_fields $name: ident, $endianness: ident, $options: tt, {$($parsed: tt)*}, {$($version: tt)*}, {$($units: tt)*}, $derived: tt, {$field_name: ident : $field_type: ty $(as $underlying_type: ty)? $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields $name, $endianness, $options, {$($parsed)* $field_name: $field_type $(as $underlying_type)?,}, {$($version)*}, {$($units)*}, $derived, {$($($tail)*)?});
+    };
+
+    (@layout $name: ident, $endianness: ident, $options: tt, {$($(@ $offset: literal)? $field_name: ident : $field_type: ty $(as $underlying_type: ty)? $(=> $quantity: ident in $quantity_unit: ident)? $(where $context: ty)? $(; bits $bits: literal)?),* $(,)?}, {$($version_field: ident : $version_type: ident in $min_version: literal ..= $max_version: literal)?}, {$($unit: ident : $unit_type: ident = $unit_symbol: expr;)*}, {{$($length_field: ident : $length_type: ident = $length_target: ident;)*} {$($checksum_field: ident = $checksum_algorithm: path $(, $checksum_range: ty)?;)*}}) => {
         $crate::internal::doc_comment!{
             concat!{"
             This module is autogenerated. It defines a layout using the [binary_layout] crate based on the following definition:
             ```ignore
             binary_layout!(", stringify!($name), ", ", stringify!($endianness), ", {", $("
-                ", stringify!($field_name), ": ", stringify!($field_type), $(" as ", stringify!($underlying_type), )? ",", )* "
+                ", stringify!($field_name), $(" @ ", stringify!($offset), )? ": ", stringify!($field_type), $(" in ", stringify!($quantity_unit), )? $(" as ", stringify!($underlying_type), )? $(" with ", stringify!($context), )? $(" bits(", stringify!($bits), ")", )? ",", )* "
             });
             ```
             "},
@@ -90,7 +554,56 @@ macro_rules! binary_layout {
                 #[allow(unused_imports)]
                 use super::*;
 
-                $crate::binary_layout!(@impl_fields $crate::$endianness, Some(0), {$($field_name : $field_type $(as $underlying_type)?),*});
+                $crate::binary_layout!(@impl_fields $crate::$endianness, $options, Some(0), false, {$($(@ $offset)? $field_name : $field_type $(as $underlying_type)? $(=> $quantity in $quantity_unit)? $(where $context)? $(; bits $bits)?),*});
+
+                $(
+                    $crate::binary_layout!(@impl_unit $unit, $unit_type, $unit_symbol);
+                )*
+
+                $(
+                    /// Versions of this layout that [View::try_new] accepts in the version field
+                    pub const SUPPORTED_VERSIONS: ::core::ops::RangeInclusive<$version_type> = MIN_VERSION..=MAX_VERSION;
+                    /// Lowest version of this layout that [View::try_new] accepts in the version field
+                    pub const MIN_VERSION: $version_type = $min_version;
+                    /// Highest version of this layout that [View::try_new] accepts in the version field
+                    pub const MAX_VERSION: $version_type = $max_version;
+                )?
+
+                /// Runtime description of this layout and its fields, see [LayoutInfo](crate::LayoutInfo).
+                pub const LAYOUT: $crate::LayoutInfo = $crate::internal::new_layout_info(
+                    stringify!($name),
+                    <$crate::$endianness as $crate::Endianness>::KIND,
+                    SIZE,
+                    &[$(Fields::$field_name),*],
+                );
+                const _: () = $crate::internal::check_optional_fields(&LAYOUT);
+
+                /// Number of bytes a view of this layout needs at least, not counting the data of an open ended field at the end
+                /// or of [Optional](crate::Optional) fields. For other layouts, this is [SIZE]. [View::try_new] rejects shorter storage.
+                pub const MIN_SIZE: usize = LAYOUT.min_size();
+                $crate::binary_layout!(@impl_alignment $options, {$($field_name),*});
+
+                /// Runtime description of each field of this layout, e.g. `Fields::my_field.name` and `Fields::my_field.type_name`.
+                /// Use these instead of hard coding field names in logging, error messages or tooling, so they don't drift
+                /// when fields are renamed. They are the same as the entries of [LAYOUT]`.fields`.
+                pub struct Fields;
+
+                #[allow(non_upper_case_globals)]
+                impl Fields {
+                    $(
+                        $crate::internal::doc_comment!{
+                            concat!("Runtime description of the `", stringify!($field_name), "` field"),
+                            pub const $field_name: $crate::FieldInfo = $crate::internal::new_field_info(
+                                stringify!($field_name),
+                                concat!(stringify!($field_type) $(, " in ", stringify!($quantity_unit))? $(, " as ", stringify!($underlying_type))? $(, " with ", stringify!($context))? $(, " bits(", stringify!($bits), ")")?),
+                                <$field_name as $crate::Field>::OFFSET,
+                                <$field_name as $crate::Field>::SIZE,
+                                <$field_name as $crate::internal::FieldKindInfo>::KIND,
+                                <$field_name as $crate::internal::FieldKindInfo>::OPTIONAL,
+                            );
+                        }
+                    )*
+                }
 
                 $crate::internal::doc_comment!{
                     concat!{"
@@ -101,7 +614,7 @@ macro_rules! binary_layout {
                     This view is based on the following layout definition:
                     ```ignore
                     binary_layout!(", stringify!($name), ", ", stringify!($endianness), ", {", $("
-                        ", stringify!($field_name), ": ", stringify!($field_type), $(" as ", stringify!($underlying_type), )? ",",)* "
+                        ", stringify!($field_name), $(" @ ", stringify!($offset), )? ": ", stringify!($field_type), $(" in ", stringify!($quantity_unit), )? $(" as ", stringify!($underlying_type), )? $(" with ", stringify!($context), )? $(" bits(", stringify!($bits), ")", )? ",",)* "
                     });
                     ```
                     "},
@@ -109,6 +622,22 @@ macro_rules! binary_layout {
                         storage: S,
                     }
                 }
+                /// Shows the name and value of each field, with long byte arrays abbreviated
+                impl <S: AsRef<[u8]>> ::core::fmt::Debug for View<S> {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        $crate::internal::fmt_view(&LAYOUT, self.storage.as_ref(), f)
+                    }
+                }
+
+                /// Compares the bytes of each field, but not bytes that aren't part of a field, e.g. after the end of the layout
+                impl <S: AsRef<[u8]>, R: AsRef<[u8]>> ::core::cmp::PartialEq<View<R>> for View<S> {
+                    fn eq(&self, other: &View<R>) -> bool {
+                        $crate::internal::views_eq(&LAYOUT, self.storage.as_ref(), other.storage.as_ref())
+                    }
+                }
+
+                impl <S: AsRef<[u8]>> ::core::cmp::Eq for View<S> {}
+
                 impl <S: AsRef<[u8]>> View<S> {
                     /// You can create views over a storage by calling [View::new].
                     ///
@@ -116,6 +645,9 @@ macro_rules! binary_layout {
                     /// - Immutable borrowed storage: `&[u8]`
                     /// - Mutable borrowed storage: `&mut [u8]`
                     /// - Owning storage: impl `AsRef<u8>` (for example: `Vec<u8>`)
+                    ///
+                    /// This doesn't check the storage, so accessing a field that the storage is too short for panics.
+                    /// Use [View::try_new] to check the storage once when creating the view instead.
                     #[inline]
                     pub fn new(storage: S) -> Self {
                         Self {storage}
@@ -129,14 +661,562 @@ macro_rules! binary_layout {
                         self.storage
                     }
 
+                    /// This destroys the view and returns just the given byte range of the underlying storage, e.g. the payload region of a container layout
+                    /// that another layout interprets with `other_layout::View::new(view.into_subview(range))`. This doesn't copy the data. The returned
+                    /// [Data](crate::Data) instance behaves like a storage of only the bytes in the range, see [Data::into_subregion](crate::Data::into_subregion).
+                    ///
+                    /// # Panics
+                    /// If the range is out of bounds of the storage.
+                    #[inline]
+                    pub fn into_subview(self, range: impl ::core::ops::RangeBounds<usize> + ::core::fmt::Debug) -> $crate::Data<S> {
+                        $crate::Data::from(self.storage).into_subregion(range)
+                    }
+
+                    /// Create a view like [View::new], but check that the storage is valid for this layout first.
+                    /// It has to be long enough for all fields, i.e. at least [MIN_SIZE] bytes, not counting the data of open ended fields but including the data
+                    /// of [LengthPrefixed](crate::LengthPrefixed) fields and all bytes of varints, which also move the fields after them,
+                    /// and if the layout has a version field, it has to store one of the `SUPPORTED_VERSIONS`.
+                    /// Otherwise this fails with a [ViewError](crate::ViewError), e.g. [ViewError::StorageTooShort](crate::ViewError::StorageTooShort).
+                    pub fn try_new(storage: S) -> Result<Self, $crate::ViewError> {
+                        $crate::internal::check_storage_size(&LAYOUT, storage.as_ref())?;
+                        $(
+                            let version = <$version_field as $crate::FieldReadExt>::read(storage.as_ref());
+                            if !SUPPORTED_VERSIONS.contains(&version) {
+                                return Err($crate::ViewError::UnsupportedVersion {
+                                    field: stringify!($version_field),
+                                    version: version as i128,
+                                });
+                            }
+                        )?
+                        Ok(Self::new(storage))
+                    }
+
+                    /// Check that the storage is long enough for this layout and that all [Magic](crate::Magic) fields of this layout
+                    /// and its nested layouts store their value.
+                    pub fn validate(&self) -> Result<(), $crate::ViewError> {
+                        $crate::internal::validate(&LAYOUT, self.storage.as_ref())
+                    }
+
+                    /// Create a view of this layout starting at the given byte offset of the storage, checking that the storage
+                    /// is valid for this layout from there on like [View::try_new]. The view is over a [Data](crate::Data) instance,
+                    /// which keeps the whole storage so the bytes around the layout stay accessible, e.g. with `view.into_storage().into_inner()`.
+                    pub fn new_at(storage: S, offset: usize) -> Result<View<$crate::Data<S>>, $crate::ViewError> {
+                        View::try_new($crate::internal::storage_at(&LAYOUT, storage, offset)?)
+                    }
+
+                    /// Copy the data of this view into a view of the same layout with a different endianness, e.g. one defined with
+                    /// the same fields but `LittleEndian` instead of `BigEndian`, rewriting each field with the byte order of the destination.
+                    /// Byte array and slice fields are copied as they are, and the data of an open ended field is copied into the beginning of
+                    /// the destination field. Fails without writing anything if the destination layout doesn't have the same field names, offsets,
+                    /// sizes and kinds or if its storage is too short for the data.
+                    pub fn copy_converting<D: $crate::internal::ViewInfoMut>(&self, dst: &mut D) -> Result<(), $crate::CopyConvertingError> {
+                        $crate::internal::copy_converting(&LAYOUT, self.storage.as_ref(), D::LAYOUT, dst.data_mut())
+                    }
+
+                    /// Read the value of the field with the given name at runtime, see [LayoutInfo::read_field_by_name](crate::LayoutInfo::read_field_by_name).
+                    /// Returns `None` if this layout doesn't have a field of that name or the storage ends before the end of the field.
+                    pub fn field_value(&self, name: &str) -> Option<$crate::FieldValue<'_>> {
+                        LAYOUT.read_field_by_name(name, self.storage.as_ref())
+                    }
+
+                    /// Return a hexdump of the data of this view in which each line is labeled with its field, e.g. for `println!("{}", view.hexdump())`,
+                    /// see [LayoutInfo::hexdump](crate::LayoutInfo::hexdump).
+                    pub fn hexdump(&self) -> $crate::HexDump<'_> {
+                        LAYOUT.hexdump(self.storage.as_ref())
+                    }
+
+                    /// Return the given byte range of the storage, e.g. the payload region of a container layout that another layout interprets
+                    /// with `other_layout::View::new(view.subview(range))`, without handing out the rest of the storage, see [View::into_subview].
+                    ///
+                    /// # Panics
+                    /// If the range is out of bounds of the storage.
+                    #[inline]
+                    pub fn subview(&self, range: impl ::core::ops::RangeBounds<usize> + ::core::fmt::Debug) -> &[u8] {
+                        $crate::Data::from(self.storage.as_ref()).into_subregion(range).into_slice()
+                    }
+
+                    $crate::internal::if_std!{
+                        /// Write the bytes of this layout to the given writer, e.g. a file or a socket. For open ended layouts, this includes
+                        /// all the data of the open ended field. Fails with [InvalidInput](std::io::ErrorKind::InvalidInput) if the storage
+                        /// is too short for this layout.
+                        pub fn write_to<W: $crate::internal::io::Write>(&self, writer: W) -> $crate::internal::io::Result<()> {
+                            $crate::internal::write_layout(&LAYOUT, self.storage.as_ref(), writer)
+                        }
+                    }
+
+                    $crate::internal::if_tokio!{
+                        /// Write the bytes of this layout to the given async writer, like [View::write_to], see [tokio](crate::tokio).
+                        pub async fn write_to_async<W: $crate::internal::tokio::io::AsyncWrite + Unpin>(&self, writer: W) -> $crate::internal::io::Result<()> {
+                            $crate::internal::write_layout_async(&LAYOUT, self.storage.as_ref(), writer).await
+                        }
+                    }
+
                     $crate::binary_layout!(@impl_view_into {$($field_name),*});
                 }
                 impl <S: AsRef<[u8]>> View<S> {
                     $crate::binary_layout!(@impl_view_asref {$($field_name),*});
                 }
                 impl <S: AsRef<[u8]> + AsMut<[u8]>> View<S> {
+                    /// Copy the bytes of another view of this layout into the storage of this view, e.g. to copy a record between buffers.
+                    /// For open ended layouts, this includes all the data of the open ended field of the other view.
+                    /// Fails with [ViewError::StorageTooShort](crate::ViewError::StorageTooShort) without writing anything if the storage
+                    /// of the other view is too short for this layout or the storage of this view is too short for the copied bytes.
+                    pub fn copy_from<S2: AsRef<[u8]>>(&mut self, other: &View<S2>) -> Result<(), $crate::ViewError> {
+                        $crate::internal::copy_layout(&LAYOUT, other.storage.as_ref(), self.storage.as_mut())
+                    }
+
+                    /// Copy the bytes of this layout from the beginning of the given data into the storage of this view, like [View::copy_from].
+                    pub fn copy_from_bytes(&mut self, data: &[u8]) -> Result<(), $crate::ViewError> {
+                        $crate::internal::copy_layout(&LAYOUT, data, self.storage.as_mut())
+                    }
+
+                    /// Return the given byte range of the storage with write access, see [View::subview].
+                    ///
+                    /// # Panics
+                    /// If the range is out of bounds of the storage.
+                    #[inline]
+                    pub fn subview_mut(&mut self, range: impl ::core::ops::RangeBounds<usize> + ::core::fmt::Debug) -> &mut [u8] {
+                        $crate::Data::from(self.storage.as_mut()).into_subregion(range).into_slice()
+                    }
+
+                    $crate::internal::if_std!{
+                        /// Rewrite the data of this view in place into the byte order of layout `L`, which has the same fields but a different endianness,
+                        /// e.g. to migrate stored data between big endian and little endian machines. Afterwards, the storage can be read with a view of `L`,
+                        /// e.g. `other_layout::View::new(view.into_storage())`. This converts the fields like [View::copy_converting] and fails
+                        /// without writing anything if `L` doesn't have the same field names, offsets, sizes and kinds.
+                        pub fn convert_endianness<L: $crate::Layout>(&mut self) -> Result<(), $crate::CopyConvertingError> {
+                            $crate::internal::convert_in_place(&LAYOUT, L::LAYOUT, self.storage.as_mut())
+                        }
+                    }
+
                     $crate::binary_layout!(@impl_view_asmut {$($field_name),*});
                 }
+                $crate::binary_layout!(@impl_finalize {$($length_field: $length_type = $length_target;)*} {$($checksum_field = $checksum_algorithm $(, $checksum_range)?;)*});
+                $crate::binary_layout!(@impl_value_struct $options, {$($field_name),*});
+                $crate::binary_layout!(@impl_builder $options, {$($field_name),*});
+                impl <S: $crate::FlushableStorage> View<S> {
+                    /// Flush the bytes of this layout in the storage, see [FlushableStorage](crate::FlushableStorage).
+                    /// For open ended layouts, this flushes everything until the end of the storage.
+                    pub fn flush(&mut self) -> Result<(), S::FlushError> {
+                        $crate::internal::flush_layout(&LAYOUT, &mut self.storage)
+                    }
+
+                    /// Flush only the bytes of the given fields in the storage, e.g. the ones that were written,
+                    /// see [FlushableStorage](crate::FlushableStorage).
+                    pub fn flush_fields(&mut self, fields: &[FieldId]) -> Result<(), S::FlushError> {
+                        $crate::internal::flush_fields(&LAYOUT, fields.iter().copied().map(FieldId::info), &mut self.storage)
+                    }
+                }
+
+                impl <S: AsRef<[u8]>> $crate::internal::ViewInfo for View<S> {
+                    const LAYOUT: &'static $crate::LayoutInfo = &LAYOUT;
+
+                    #[inline]
+                    fn data(&self) -> &[u8] {
+                        self.storage.as_ref()
+                    }
+                }
+
+                impl <S: AsRef<[u8]> + AsMut<[u8]>> $crate::internal::ViewInfoMut for View<S> {
+                    #[inline]
+                    fn data_mut(&mut self) -> &mut [u8] {
+                        self.storage.as_mut()
+                    }
+                }
+
+                /// Look up the offset and size of a field of this layout by its name, see [LayoutInfo::offset_of](crate::LayoutInfo::offset_of).
+                pub const fn offset_of(name: &str) -> Option<(usize, Option<usize>)> {
+                    LAYOUT.offset_of(name)
+                }
+
+                /// Identifies a field of this layout, with one variant per field in the order of the layout.
+                /// This allows table driven processing of fields, e.g. applying patches given as `(FieldId, value)` pairs,
+                /// with exhaustive `match`es that the compiler checks when fields are added or removed.
+                #[allow(non_camel_case_types)]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+                pub enum FieldId {
+                    $(
+                        #[doc = concat!("The `", stringify!($field_name), "` field")]
+                        $field_name,
+                    )*
+                }
+
+                impl FieldId {
+                    /// All fields of this layout in the order of the layout
+                    pub const ALL: &'static [FieldId] = &[$(FieldId::$field_name),*];
+
+                    /// Iterate over all fields of this layout in the order of the layout
+                    pub fn iter() -> impl Iterator<Item = FieldId> {
+                        Self::ALL.iter().copied()
+                    }
+
+                    /// Look up a field by its name
+                    pub fn from_name(name: &str) -> Option<FieldId> {
+                        LAYOUT.fields.iter().position(|field| field.name == name).map(|index| Self::ALL[index])
+                    }
+
+                    /// Runtime description of the field, the same as the entry in [LAYOUT]`.fields`
+                    pub const fn info(self) -> &'static $crate::FieldInfo {
+                        &LAYOUT.fields[self as usize]
+                    }
+
+                    /// Name of the field
+                    pub const fn name(self) -> &'static str {
+                        self.info().name
+                    }
+
+                    /// Offset of the field in the layout, see [Field::OFFSET](crate::Field::OFFSET)
+                    pub const fn offset(self) -> usize {
+                        self.info().offset
+                    }
+
+                    /// Size of the field, see [Field::SIZE](crate::Field::SIZE)
+                    pub const fn size(self) -> Option<usize> {
+                        self.info().size
+                    }
+                }
+
+                impl ::core::fmt::Display for FieldId {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        fmt.write_str(self.name())
+                    }
+                }
+
+                $(
+                    impl <S: AsRef<[u8]>> $crate::HasField<$crate::field_name!($field_name), $field_name> for View<S> {
+                        #[inline]
+                        fn field(&self) -> $crate::FieldView<&[u8], $field_name> {
+                            $crate::FieldView::new(self.storage.as_ref())
+                        }
+                    }
+                    impl <S: AsRef<[u8]> + AsMut<[u8]>> $crate::HasFieldMut<$crate::field_name!($field_name), $field_name> for View<S> {
+                        #[inline]
+                        fn field_mut(&mut self) -> $crate::FieldView<&mut [u8], $field_name> {
+                            $crate::FieldView::new(self.storage.as_mut())
+                        }
+                    }
+                    impl $crate::FieldMetadata<$crate::field_name!($field_name), $field_name> for NestedView {
+                        const FIELD_NAME: &'static str = stringify!($field_name);
+                        const FIELD_INFO: &'static $crate::FieldInfo = &Fields::$field_name;
+                    }
+                    impl <S: AsRef<[u8]>> $crate::FieldMetadata<$crate::field_name!($field_name), $field_name> for View<S> {
+                        const FIELD_NAME: &'static str = stringify!($field_name);
+                        const FIELD_INFO: &'static $crate::FieldInfo = &Fields::$field_name;
+                    }
+                )*
+
+                $crate::internal::if_std!{
+                    impl View<$crate::internal::Vec<u8>> {
+                        /// Create a view owning a new storage of [SIZE] bytes that are all zero, e.g. to build a new message field by field.
+                        ///
+                        /// # Panics
+                        /// If the layout ends with an open ended field and therefore doesn't have a static size.
+                        pub fn new_zeroed() -> Self {
+                            let size = SIZE.expect("View::new_zeroed() needs a layout with a static size, i.e. without an open ended field at the end");
+                            let mut storage = $crate::internal::Vec::new();
+                            storage.resize(size, 0);
+                            Self::new(storage)
+                        }
+
+                        /// Create a view owning a new storage of [SIZE] bytes read from the given reader, e.g. a file or a socket.
+                        /// Fails like [read_exact](std::io::Read::read_exact) if the reader ends before all bytes were read.
+                        ///
+                        /// # Panics
+                        /// If the layout ends with an open ended field and therefore doesn't have a static size, see [View::read_from_with_len] for those.
+                        pub fn read_from<R: $crate::internal::io::Read>(reader: R) -> $crate::internal::io::Result<Self> {
+                            let size = SIZE.expect("View::read_from() needs a layout with a static size, i.e. without an open ended field at the end");
+                            Self::read_from_with_len(reader, size)
+                        }
+
+                        /// Create a view owning a new storage of `len` bytes read from the given reader, e.g. for layouts ending with an open ended field
+                        /// whose length is known from a previously read header. Fails like [read_exact](std::io::Read::read_exact) if the reader ends
+                        /// before all bytes were read.
+                        pub fn read_from_with_len<R: $crate::internal::io::Read>(mut reader: R, len: usize) -> $crate::internal::io::Result<Self> {
+                            let mut storage = $crate::internal::Vec::new();
+                            storage.resize(len, 0);
+                            reader.read_exact(&mut storage)?;
+                            Ok(Self::new(storage))
+                        }
+
+                        $crate::internal::if_tokio!{
+                            /// Create a view owning a new storage of [SIZE] bytes read from the given async reader, like [View::read_from], see [tokio](crate::tokio).
+                            ///
+                            /// # Panics
+                            /// If the layout ends with an open ended field and therefore doesn't have a static size, see [View::read_from_with_len_async] for those.
+                            pub async fn read_from_async<R: $crate::internal::tokio::io::AsyncRead + Unpin>(reader: R) -> $crate::internal::io::Result<Self> {
+                                let size = SIZE.expect("View::read_from_async() needs a layout with a static size, i.e. without an open ended field at the end");
+                                Self::read_from_with_len_async(reader, size).await
+                            }
+
+                            /// Create a view owning a new storage of `len` bytes read from the given async reader, like [View::read_from_with_len], see [tokio](crate::tokio).
+                            pub async fn read_from_with_len_async<R: $crate::internal::tokio::io::AsyncRead + Unpin>(reader: R, len: usize) -> $crate::internal::io::Result<Self> {
+                                Ok(Self::new($crate::internal::read_storage_async(reader, len).await?))
+                            }
+                        }
+                    }
+
+                    /// Compare two views of this layout and return the fields whose values differ, see [LayoutInfo::diff](crate::LayoutInfo::diff).
+                    pub fn diff<'a, 'b, SA: AsRef<[u8]>, SB: AsRef<[u8]>>(left: &'a View<SA>, right: &'b View<SB>) -> $crate::internal::Vec<$crate::FieldDiff<'a, 'b>> {
+                        LAYOUT.diff(left.storage.as_ref(), right.storage.as_ref())
+                    }
+
+                    /// Check that re-encoding the field values read from the data reproduces the data, which makes a ready-to-use
+                    /// [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) target for this layout:
+                    /// ```ignore
+                    /// fuzz_target!(|data: &[u8]| my_layout::fuzz_round_trip(data));
+                    /// ```
+                    /// Data that is too short for the layout is ignored and fields whose value can't be read keep their bytes.
+                    ///
+                    /// # Panics
+                    /// If a field value read from the data is written back differently, listing the affected fields.
+                    pub fn fuzz_round_trip(data: &[u8]) {
+                        $crate::internal::check_round_trip(&LAYOUT, data, <NestedView as $crate::internal::RoundTripNested>::round_trip);
+                    }
+
+                    impl $crate::internal::RoundTripNested for NestedView {
+                        #[allow(unused_variables)]
+                        fn round_trip(src: &[u8], dst: &mut [u8]) {
+                            #[allow(unused_imports)]
+                            use $crate::internal::{RoundTripBytesField, RoundTripNestedField, RoundTripValueField};
+                            $(
+                                (&&&$crate::internal::RoundTripField::<$field_name>::default()).round_trip(stringify!($field_name), src, dst);
+                            )*
+                        }
+                    }
+                }
+
+                $crate::internal::if_arbitrary!{
+                    #[allow(unused_variables)]
+                    fn fill_arbitrary(u: &mut $crate::internal::arbitrary::Unstructured<'_>, data: &mut [u8]) -> $crate::internal::arbitrary::Result<()> {
+                        #[allow(unused_imports)]
+                        use $crate::internal::{ArbitraryBitsField, ArbitraryBytesField, ArbitraryNestedField, ArbitraryValueField};
+                        $(
+                            (&&&$crate::internal::ArbitraryField::<$field_name>::default()).fill_arbitrary(u, &LAYOUT, data)?;
+                        )*
+                        Ok(())
+                    }
+
+                    impl<'a> $crate::internal::arbitrary::Arbitrary<'a> for View<$crate::internal::Vec<u8>> {
+                        fn arbitrary(u: &mut $crate::internal::arbitrary::Unstructured<'a>) -> $crate::internal::arbitrary::Result<Self> {
+                            let mut storage = $crate::internal::arbitrary_storage(&LAYOUT, u)?;
+                            fill_arbitrary(u, &mut storage)?;
+                            Ok(View::new(storage))
+                        }
+                    }
+
+                    impl $crate::internal::ArbitraryNested for NestedView {
+                        fn fill_arbitrary(u: &mut $crate::internal::arbitrary::Unstructured<'_>, data: &mut [u8]) -> $crate::internal::arbitrary::Result<()> {
+                            fill_arbitrary(u, data)
+                        }
+                    }
+                }
+
+                $crate::internal::if_binrw!{
+                    impl $crate::internal::binrw::BinRead for View<$crate::internal::Vec<u8>> {
+                        type Args<'a> = ();
+
+                        fn read_options<R: $crate::internal::binrw::io::Read + $crate::internal::binrw::io::Seek>(
+                            reader: &mut R,
+                            _endian: $crate::internal::binrw::Endian,
+                            _args: (),
+                        ) -> $crate::internal::binrw::BinResult<Self> {
+                            Ok(View::new($crate::internal::read_storage(&LAYOUT, reader)?))
+                        }
+                    }
+
+                    impl <S: AsRef<[u8]>> $crate::internal::binrw::BinWrite for View<S> {
+                        type Args<'a> = ();
+
+                        fn write_options<W: $crate::internal::binrw::io::Write + $crate::internal::binrw::io::Seek>(
+                            &self,
+                            writer: &mut W,
+                            _endian: $crate::internal::binrw::Endian,
+                            _args: (),
+                        ) -> $crate::internal::binrw::BinResult<()> {
+                            writer.write_all(self.storage.as_ref())?;
+                            Ok(())
+                        }
+                    }
+                }
+
+                $crate::internal::if_memmap2!{
+                    #[allow(unsafe_code)]
+                    impl View<$crate::internal::memmap2::Mmap> {
+                        /// Create a view over a read-only memory map of the file at the given path, see [memmap2](crate::memmap2).
+                        /// Fails with [InvalidData](std::io::ErrorKind::InvalidData) if the file is too short for this layout.
+                        ///
+                        /// # Safety
+                        /// The file must not be modified while it's mapped, e.g. by another process, see `memmap2::Mmap::map`.
+                        pub unsafe fn map_file<P: AsRef<$crate::internal::Path>>(path: P) -> $crate::internal::io::Result<Self> {
+                            Ok(Self::new(unsafe { $crate::internal::map_file(&LAYOUT, path.as_ref())? }))
+                        }
+                    }
+
+                    #[allow(unsafe_code)]
+                    impl View<$crate::internal::memmap2::MmapMut> {
+                        /// Create a view over a writable memory map of the file at the given path, see [memmap2](crate::memmap2).
+                        /// The file is created if it doesn't exist and grown to the size of this layout, or to the size of the fields before
+                        /// the open ended field for open ended layouts, if it's shorter.
+                        ///
+                        /// # Safety
+                        /// The file must not be modified by others while it's mapped, e.g. by another process, see `memmap2::MmapMut::map_mut`.
+                        pub unsafe fn map_file_mut<P: AsRef<$crate::internal::Path>>(path: P) -> $crate::internal::io::Result<Self> {
+                            unsafe { Self::map_file_mut_with_len(path, 0) }
+                        }
+
+                        /// Create a view over a writable memory map of the file at the given path like [View::map_file_mut], but grow the file
+                        /// to at least `len` bytes, e.g. to make room for the data of an open ended field.
+                        ///
+                        /// # Safety
+                        /// The file must not be modified by others while it's mapped, e.g. by another process, see `memmap2::MmapMut::map_mut`.
+                        pub unsafe fn map_file_mut_with_len<P: AsRef<$crate::internal::Path>>(path: P, len: usize) -> $crate::internal::io::Result<Self> {
+                            Ok(Self::new(unsafe { $crate::internal::map_file_mut(&LAYOUT, path.as_ref(), len)? }))
+                        }
+                    }
+                }
+
+                $crate::internal::if_nom!{
+                    /// A [nom](https://docs.rs/nom) parser returning a view of this layout, see [nom](crate::nom).
+                    pub fn parse(input: &[u8]) -> $crate::internal::nom::IResult<&[u8], View<&[u8]>> {
+                        let (rest, storage) = $crate::internal::parse_storage(&LAYOUT, input)?;
+                        Ok((rest, View::new(storage)))
+                    }
+                }
+
+                $crate::internal::if_tokio_util!{
+                    /// A codec splitting frames of this layout off a byte stream and writing views of this layout into one, see [tokio_util](crate::tokio_util).
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                    pub struct Codec {
+                        max_frame_length: usize,
+                    }
+
+                    impl Codec {
+                        /// Creates a codec rejecting frames longer than `binary_layout::tokio_util::DEFAULT_MAX_FRAME_LENGTH`.
+                        pub const fn new() -> Self {
+                            Self::with_max_frame_length($crate::tokio_util::DEFAULT_MAX_FRAME_LENGTH)
+                        }
+
+                        /// Creates a codec rejecting frames longer than `max_frame_length` bytes.
+                        pub const fn with_max_frame_length(max_frame_length: usize) -> Self {
+                            Self { max_frame_length }
+                        }
+
+                        /// The maximum number of bytes of a decoded frame.
+                        pub const fn max_frame_length(&self) -> usize {
+                            self.max_frame_length
+                        }
+
+                        /// Changes the maximum number of bytes of a decoded frame.
+                        pub fn set_max_frame_length(&mut self, max_frame_length: usize) {
+                            self.max_frame_length = max_frame_length;
+                        }
+                    }
+
+                    impl Default for Codec {
+                        fn default() -> Self {
+                            Self::new()
+                        }
+                    }
+
+                    impl $crate::internal::tokio_util::codec::Decoder for Codec {
+                        type Item = View<$crate::internal::tokio_util::bytes::BytesMut>;
+                        type Error = $crate::internal::io::Error;
+
+                        fn decode(&mut self, src: &mut $crate::internal::tokio_util::bytes::BytesMut) -> $crate::internal::io::Result<Option<Self::Item>> {
+                            #[allow(unused_variables)]
+                            fn frame_len(storage: &[u8]) -> $crate::internal::io::Result<Option<usize>> {
+                                if let Some(size) = SIZE {
+                                    return Ok(Some(size));
+                                }
+                                $(
+                                    if <$length_target as $crate::Field>::SIZE.is_none() {
+                                        return $crate::internal::length_field_frame_len::<$length_field, $length_target>(storage);
+                                    }
+                                )*
+                                $crate::internal::dynamic_frame_len(&LAYOUT, storage)
+                            }
+                            Ok($crate::internal::decode_frame(&LAYOUT, src, self.max_frame_length, frame_len)?.map(View::new))
+                        }
+                    }
+
+                    impl <S: AsRef<[u8]>> $crate::internal::tokio_util::codec::Encoder<View<S>> for Codec {
+                        type Error = $crate::internal::io::Error;
+
+                        fn encode(&mut self, item: View<S>, dst: &mut $crate::internal::tokio_util::bytes::BytesMut) -> $crate::internal::io::Result<()> {
+                            #[allow(unused_variables)]
+                            fn frame_len(storage: &[u8]) -> $crate::internal::io::Result<Option<usize>> {
+                                $(
+                                    if <$length_target as $crate::Field>::SIZE.is_none() {
+                                        return $crate::internal::length_field_frame_len::<$length_field, $length_target>(storage);
+                                    }
+                                )*
+                                Ok(None)
+                            }
+                            $crate::internal::encode_frame(&LAYOUT, item.storage.as_ref(), dst, frame_len)
+                        }
+                    }
+                }
+
+                $crate::internal::if_rkyv!{
+                    impl $crate::internal::rkyv::Archive for View<$crate::internal::Vec<u8>> {
+                        type Archived = $crate::internal::rkyv::vec::ArchivedVec<u8>;
+                        type Resolver = $crate::internal::rkyv::vec::VecResolver;
+
+                        #[inline]
+                        fn resolve(&self, resolver: Self::Resolver, out: $crate::internal::rkyv::Place<Self::Archived>) {
+                            self.storage.resolve(resolver, out)
+                        }
+                    }
+
+                    impl <S: $crate::internal::rkyv::rancor::Fallible + ?Sized> $crate::internal::rkyv::Serialize<S> for View<$crate::internal::Vec<u8>>
+                    where
+                        $crate::internal::Vec<u8>: $crate::internal::rkyv::Serialize<S>,
+                    {
+                        #[inline]
+                        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+                            self.storage.serialize(serializer)
+                        }
+                    }
+
+                    impl <D: $crate::internal::rkyv::rancor::Fallible + ?Sized> $crate::internal::rkyv::Deserialize<View<$crate::internal::Vec<u8>>, D> for $crate::internal::rkyv::vec::ArchivedVec<u8> {
+                        #[inline]
+                        fn deserialize(&self, _deserializer: &mut D) -> Result<View<$crate::internal::Vec<u8>>, D::Error> {
+                            Ok(View::new(self.as_slice().to_vec()))
+                        }
+                    }
+                }
+
+                $crate::internal::if_serde!{
+                    impl <S: AsRef<[u8]>> $crate::internal::serde::Serialize for View<S> {
+                        fn serialize<Ser: $crate::internal::serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+                            $crate::internal::serialize_view(&LAYOUT, self.storage.as_ref(), serializer)
+                        }
+                    }
+
+                    impl <'de> $crate::internal::serde::Deserialize<'de> for View<$crate::internal::Vec<u8>> {
+                        fn deserialize<D: $crate::internal::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                            Ok(View::new($crate::internal::deserialize_storage(&LAYOUT, deserializer)?))
+                        }
+                    }
+                }
+
+                $crate::internal::if_proptest!{
+                    /// A [proptest](https://docs.rs/proptest) strategy generating the storage for structurally valid views of this layout,
+                    /// see [proptest](crate::proptest).
+                    pub fn strategy() -> impl $crate::internal::proptest::strategy::Strategy<Value = $crate::internal::Vec<u8>> {
+                        <NestedView as $crate::internal::LayoutStrategy>::storage_strategy()
+                    }
+
+                    impl $crate::internal::LayoutStrategy for NestedView {
+                        fn storage_strategy() -> $crate::internal::proptest::strategy::BoxedStrategy<$crate::internal::Vec<u8>> {
+                            #[allow(unused_imports)]
+                            use $crate::internal::{BitsStrategyField, BytesStrategyField, NestedStrategyField, ValueStrategyField};
+                            $crate::internal::storage_strategy(&LAYOUT, $crate::internal::Vec::from([
+                                $((&&&$crate::internal::StrategyField::<$field_name>::default()).field_strategy(),)*
+                            ]))
+                        }
+                    }
+                }
 
                 /// Use this as a marker type for using this layout as a nested field within another layout.
                 ///
@@ -176,31 +1256,418 @@ macro_rules! binary_layout {
 
                 impl $crate::internal::NestedViewInfo for NestedView {
                     const SIZE: Option<usize> = SIZE;
+                    const LAYOUT: &'static $crate::LayoutInfo = &LAYOUT;
+                }
+
+                impl $crate::Layout for NestedView {
+                    const NAME: &'static str = stringify!($name);
+                    const SIZE: Option<usize> = SIZE;
+                    const FIELD_COUNT: usize = LAYOUT.fields.len();
+                    const LAYOUT: &'static $crate::LayoutInfo = &LAYOUT;
+                }
+
+                impl $crate::SizedLayout for NestedView {
+                    const STATIC_SIZE: usize = $crate::internal::unwrap_layout_size(SIZE);
+                }
+
+                impl <S: AsRef<[u8]>> $crate::LayoutView<S> for NestedView {
+                    type View = View<S>;
+
+                    #[inline]
+                    fn new_view(storage: S) -> View<S> {
+                        View::new(storage)
+                    }
+
+                    #[inline]
+                    fn into_storage(view: View<S>) -> S {
+                        view.into_storage()
+                    }
+                }
+            }
+        }
+    };
+
+    (@impl_value_struct {$packing: ident $pad_to: tt {} $builder: tt}, {$($field_name: ident),*}) => {};
+    (@impl_value_struct {$packing: ident $pad_to: tt {$struct_name: ident} $builder: tt}, {$($field_name: ident),*}) => {
+        /// The values of all fields of this layout, copied out of the storage with [View::read_all]
+        /// and written back with [View::write_all] or [write_to](Self::write_to).
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $struct_name {
+            $(
+                #[doc = concat!("Value of the `", stringify!($field_name), "` field")]
+                pub $field_name: <$field_name as $crate::FieldOwnedAccess>::Owned,
+            )*
+        }
+
+        impl $struct_name {
+            /// Write the values of all fields into the given storage, which starts at the beginning of the layout.
+            /// Padding fields are set to zero and magic fields to their value.
+            ///
+            /// # Panics
+            /// If the storage is too short for the fields.
+            pub fn write_to(&self, storage: &mut [u8]) {
+                $(
+                    <$field_name as $crate::FieldOwnedAccess>::write_owned(storage, &self.$field_name);
+                )*
+            }
+        }
+
+        impl <S: AsRef<[u8]>> View<S> {
+            $crate::internal::doc_comment!{
+                concat!("Copy the values of all fields out of the storage into a [", stringify!($struct_name), "]."),
+                pub fn read_all(&self) -> $struct_name {
+                    let storage = self.storage.as_ref();
+                    $struct_name {
+                        $(
+                            $field_name: <$field_name as $crate::FieldOwnedAccess>::read_owned(storage),
+                        )*
+                    }
                 }
             }
         }
+
+        impl <S: AsRef<[u8]> + AsMut<[u8]>> View<S> {
+            $crate::internal::doc_comment!{
+                concat!("Write the values of all fields from a [", stringify!($struct_name), "] into the storage, see [", stringify!($struct_name), "::write_to]."),
+                pub fn write_all(&mut self, value: &$struct_name) {
+                    value.write_to(self.storage.as_mut());
+                }
+            }
+        }
+
+        impl $crate::internal::NestedValueStruct for NestedView {
+            type Value = $struct_name;
+
+            #[inline]
+            fn read_value(storage: &[u8]) -> $struct_name {
+                View::new(storage).read_all()
+            }
+
+            #[inline]
+            fn write_value(storage: &mut [u8], value: &$struct_name) {
+                value.write_to(storage);
+            }
+        }
+    };
+
+    (@impl_builder {$packing: ident $pad_to: tt $value_struct: tt {}}, {$($field_name: ident),*}) => {};
+    (@impl_builder {$packing: ident $pad_to: tt $value_struct: tt {builder}}, {$($field_name: ident),*}) => {
+        /// Writes the fields of this layout one by one with `set_${field_name}()` and returns a [View] with `build()`.
+        /// The second type parameter tracks which fields were set as a tuple of [FieldSet](crate::FieldSet) and
+        /// [FieldUnset](crate::FieldUnset), so that `build()` only compiles once all fields were set.
+        /// [Padding](crate::Padding), [Magic](crate::Magic) and [Optional](crate::Optional) fields don't need to be set.
+        pub struct Builder<S, T> {
+            storage: S,
+            _state: ::core::marker::PhantomData<T>,
+        }
+
+        impl <S: AsRef<[u8]> + AsMut<[u8]>> Builder<S, ($(<$field_name as $crate::internal::BuilderField>::Initial,)*)> {
+            /// Start writing the fields of this layout into the given storage. Like [View::new], this doesn't check the storage,
+            /// so setting a field that the storage is too short for panics.
+            pub fn new(storage: S) -> Self {
+                Self {
+                    storage,
+                    _state: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl <S: AsRef<[u8]> + AsMut<[u8]>> Builder<S, ($($crate::binary_layout!(@field_set $field_name),)*)> {
+            /// Write the [Padding](crate::Padding) and [Magic](crate::Magic) fields and return a [View] of the storage.
+            /// This is only available once all other fields were set.
+            pub fn build(mut self) -> View<S> {
+                $(
+                    <$field_name as $crate::internal::BuilderField>::finish(self.storage.as_mut());
+                )*
+                View::new(self.storage)
+            }
+
+            $crate::internal::if_std!{
+                /// Build the [View] like [Builder::build] and write its bytes to the given writer, see [View::write_to].
+                pub fn build_to<W: $crate::internal::io::Write>(self, writer: W) -> $crate::internal::io::Result<View<S>> {
+                    let view = self.build();
+                    view.write_to(writer)?;
+                    Ok(view)
+                }
+            }
+        }
+
+        $crate::binary_layout!(@impl_builder_setters {} {$($field_name)*});
+    };
+
+    (@field_set $field_name: ident) => {
+        $crate::FieldSet
+    };
+
+    // Generates the setter of each field, which sets the type state of that field and keeps the others
+    (@impl_builder_setters {$($before: ident)*} {}) => {};
+    (@impl_builder_setters {$($before: ident)*} {$field_name: ident $($after: ident)*}) => {
+        $crate::internal::paste!{
+            #[allow(non_camel_case_types)]
+            impl <S: AsRef<[u8]> + AsMut<[u8]>, $([<__ $before>],)* [<__ $field_name>], $([<__ $after>],)*> Builder<S, ($([<__ $before>],)* [<__ $field_name>], $([<__ $after>],)*)> {
+                $crate::internal::doc_comment!{
+                    concat!("Write the value of the `", stringify!($field_name), "` field, see [FieldOwnedAccess](crate::FieldOwnedAccess)."),
+                    pub fn [<set_ $field_name>](mut self, value: <$field_name as $crate::FieldOwnedAccess>::Owned) -> Builder<S, ($([<__ $before>],)* $crate::FieldSet, $([<__ $after>],)*)> {
+                        <$field_name as $crate::FieldOwnedAccess>::write_owned(self.storage.as_mut(), &value);
+                        Builder {
+                            storage: self.storage,
+                            _state: ::core::marker::PhantomData,
+                        }
+                    }
+                }
+            }
+        }
+        $crate::binary_layout!(@impl_builder_setters {$($before)* $field_name} {$($after)*});
+    };
+
+    (@impl_finalize {} {}) => {};
+    (@impl_finalize {$($length_field: ident : $length_type: ident = $length_target: ident;)*} {$($checksum_field: ident = $checksum_algorithm: path $(, $checksum_range: ty)?;)*}) => {
+        $(
+            $crate::binary_layout!(@impl_checksum $checksum_field = $checksum_algorithm $(, $checksum_range)?);
+        )*
+
+        impl <S: AsRef<[u8]> + AsMut<[u8]>> View<S> {
+            /// Fill the length and checksum fields of this layout, after all other fields were written. Length fields are filled first,
+            /// then checksum fields in the order they're declared, so a checksum covers the lengths and the checksums declared before it.
+            pub fn finalize(&mut self) -> Result<(), $crate::FinalizeError> {
+                let storage = self.storage.as_mut();
+                $(
+                    let length = $crate::internal::field_data_len::<$length_target>(storage);
+                    let length = <$length_type as ::core::convert::TryFrom<usize>>::try_from(length).map_err(|_| $crate::FinalizeError::LengthOutOfRange {
+                        field: stringify!($length_field),
+                        length,
+                    })?;
+                    <$length_field as $crate::FieldWriteExt>::write(storage, length);
+                )*
+                $(
+                    let checksum = $crate::binary_layout!(@compute_checksum storage, $checksum_field = $checksum_algorithm $(, $checksum_range)?);
+                    <$checksum_field as $crate::FieldWriteExt>::write(storage, checksum);
+                )*
+                Ok(())
+            }
+        }
+    };
+
+    (@compute_checksum $storage: ident, $checksum_field: ident = $checksum_algorithm: path) => {
+        $crate::internal::compute_checksum::<$checksum_algorithm>(&LAYOUT, &Fields::$checksum_field, $storage)
+    };
+    (@compute_checksum $storage: ident, $checksum_field: ident = $checksum_algorithm: path, $checksum_range: ty) => {
+        $crate::internal::compute_checksum_over::<$checksum_algorithm, $checksum_range>($storage)
+    };
+
+    (@impl_checksum $checksum_field: ident = $checksum_algorithm: path) => {};
+    (@impl_checksum $checksum_field: ident = $checksum_algorithm: path, $checksum_range: ty) => {
+        const _: () = $crate::internal::check_checksum_range(
+            <$checksum_field as $crate::Field>::OFFSET,
+            <$checksum_field as $crate::Field>::SIZE,
+            <$checksum_range as $crate::internal::ChecksumRange>::START,
+            <$checksum_range as $crate::internal::ChecksumRange>::END,
+            SIZE,
+        );
+
+        $crate::internal::paste!{
+            impl <S: AsRef<[u8]>> View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Compute the checksum of the bytes covered by the `", stringify!($checksum_field), "` field"),
+                    pub fn [<compute_ $checksum_field>](&self) -> <$checksum_algorithm as $crate::Checksum>::Output {
+                        $crate::internal::compute_checksum_over::<$checksum_algorithm, $checksum_range>(self.storage.as_ref())
+                    }
+                }
+
+                $crate::internal::doc_comment!{
+                    concat!("Check whether the `", stringify!($checksum_field), "` field stores the checksum of the bytes it covers"),
+                    pub fn [<verify_ $checksum_field>](&self) -> bool {
+                        <$checksum_field as $crate::FieldReadExt>::read(self.storage.as_ref()) == self.[<compute_ $checksum_field>]()
+                    }
+                }
+            }
+
+            impl <S: AsRef<[u8]> + AsMut<[u8]>> View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Compute the checksum of the bytes covered by the `", stringify!($checksum_field), "` field and write it into the field"),
+                    pub fn [<update_ $checksum_field>](&mut self) {
+                        let checksum = self.[<compute_ $checksum_field>]();
+                        <$checksum_field as $crate::FieldWriteExt>::write(self.storage.as_mut(), checksum);
+                    }
+                }
+            }
+        }
+    };
+
+    (@impl_unit $unit: ident, $underlying_type: ident, $symbol: expr) => {
+        $crate::internal::doc_comment!{
+            concat!("A `", stringify!($underlying_type), "` value in ", $symbol, ". Converts from and into `", stringify!($underlying_type), "` and displays with its unit."),
+            #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+            #[repr(transparent)]
+            pub struct $unit(pub $underlying_type);
+        }
+
+        impl ::core::convert::From<$underlying_type> for $unit {
+            #[inline(always)]
+            fn from(value: $underlying_type) -> Self {
+                Self(value)
+            }
+        }
+
+        impl ::core::convert::From<$unit> for $underlying_type {
+            #[inline(always)]
+            fn from(value: $unit) -> Self {
+                value.0
+            }
+        }
+
+        impl ::core::fmt::Display for $unit {
+            fn fmt(&self, fmt: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(fmt, "{} {}", self.0, $symbol)
+            }
+        }
+
+        impl $crate::LayoutAs<$underlying_type> for $unit {
+            type ReadError = ::core::convert::Infallible;
+            type WriteError = ::core::convert::Infallible;
+
+            #[inline(always)]
+            fn try_read(v: $underlying_type) -> Result<Self, Self::ReadError> {
+                Ok(Self(v))
+            }
+
+            #[inline(always)]
+            fn try_write(v: Self) -> Result<$underlying_type, Self::WriteError> {
+                Ok(v.0)
+            }
+        }
+    };
+
+    // The offset accumulator is the offset after the fields so far, and the relative accumulator whether they include LengthPrefixed or
+    // varint fields, whose number of bytes depends on the data. The fields after those are wrapped into a RelativeField.
+    (@impl_fields $endianness: ty, {$packing: ident {$($pad_to: literal)?} $value_struct: tt $builder: tt}, $offset_accumulator: expr, $relative_accumulator: expr, {}) => {
+        /// Total size of the layout in number of bytes, including the padding of aligned layouts and of layouts with a `pad_to` size.
+        /// This is None if the layout ends with an open ended field like a byte slice or has [LengthPrefixed](crate::LengthPrefixed) or varint fields.
+        pub const SIZE: Option<usize> = $crate::binary_layout!(@layout_size $packing, ($crate::internal::static_layout_size($offset_accumulator, $relative_accumulator)) $(, $pad_to)?);
+    };
+    (@impl_fields $endianness: ty, $options: tt, $offset_accumulator: expr, $relative_accumulator: expr, {@ $offset: tt $name: ident $($tail: tt)*}) => {
+        $crate::binary_layout!(@impl_fields $endianness, $options, (Some($crate::internal::pin_field_offset($offset_accumulator, $offset))), $relative_accumulator, {$name $($tail)*});
+        const _: () = assert!(!<$name as $crate::internal::FieldKindInfo>::RELATIVE, "Error: Fields after LengthPrefixed or varint fields can't have an explicit offset");
+        const _: () = assert!(<$name as $crate::Field>::OFFSET == $offset, "Error: The explicit offset of a field in an aligned layout isn't aligned for the field");
+    };
+    (@impl_fields $endianness: ty, $options: tt, $offset_accumulator: expr, $relative_accumulator: expr, {$name: ident : $type: ty ; bits $bits: literal $(, $($tail:tt)*)?}) => {
+        $crate::binary_layout!(@impl_bit_fields $endianness, $options, ($crate::internal::unwrap_field_size($offset_accumulator)), $relative_accumulator, 0, {$name: $type; bits $bits $(, $($tail)*)?});
+    };
+    (@impl_fields $endianness: ty, $options: tt, $offset_accumulator: expr, $relative_accumulator: expr, {$name: ident : $type: ty as $underlying_type: ty => $quantity: ident in $quantity_unit: ident $(, $($tail:tt)*)?}) => {
+        $crate::internal::paste!{
+            $crate::internal::doc_comment!{
+                concat!("Conversion of the `", stringify!($name), "` field between `uom::si::f64::", stringify!($quantity), "` and its value in ", stringify!($quantity_unit)),
+                #[allow(non_camel_case_types)]
+                pub struct [<$name _quantity>];
+            }
+
+            impl $crate::internal::QuantityConversion for [<$name _quantity>] {
+                type Quantity = $crate::internal::uom::si::f64::$quantity;
+
+                #[inline(always)]
+                fn from_stored(value: f64) -> Self::Quantity {
+                    Self::Quantity::new::<$crate::internal::uom::si::[<$quantity:snake>]::$quantity_unit>(value)
+                }
+
+                #[inline(always)]
+                fn to_stored(quantity: Self::Quantity) -> f64 {
+                    quantity.get::<$crate::internal::uom::si::[<$quantity:snake>]::$quantity_unit>()
+                }
+            }
+
+            $crate::internal::doc_comment!{
+                concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
+                #[allow(non_camel_case_types)]
+                pub type $name = <$crate::uom::QuantityField::<[<$name _quantity>], $crate::PrimitiveField::<$underlying_type, $endianness, {$crate::binary_layout!(@field_offset $options, $offset_accumulator, $underlying_type, $endianness)}>> as $crate::internal::SelectField<NestedView, {$relative_accumulator}>>::Field;
+            }
+        }
+        $crate::binary_layout!(@impl_fields $endianness, $options, ($crate::binary_layout!(@next_field_offset $name)), ($crate::binary_layout!(@next_field_relative $name)), {$($($tail)*)?});
+    };
+    (@impl_fields $endianness: ty, $options: tt, $offset_accumulator: expr, $relative_accumulator: expr, {$name: ident : $type: ty as $underlying_type: ty where $context: ty $(, $($tail:tt)*)?}) => {
+        $crate::internal::doc_comment!{
+            concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
+            #[allow(non_camel_case_types)]
+            pub type $name = <$crate::ContextualField::<$underlying_type, $type, $context, $crate::PrimitiveField::<$underlying_type, $endianness, {$crate::binary_layout!(@field_offset $options, $offset_accumulator, $underlying_type, $endianness)}>> as $crate::internal::SelectField<NestedView, {$relative_accumulator}>>::Field;
+        }
+        $crate::binary_layout!(@impl_fields $endianness, $options, ($crate::binary_layout!(@next_field_offset $name)), ($crate::binary_layout!(@next_field_relative $name)), {$($($tail)*)?});
     };
-
-    (@impl_fields $endianness: ty, $offset_accumulator: expr, {}) => {
-        /// Total size of the layout in number of bytes.
-        /// This can be None if the layout ends with an open ended field like a byte slice.
-        pub const SIZE: Option<usize> = $offset_accumulator;
+    (@impl_fields $endianness: ty, $options: tt, $offset_accumulator: expr, $relative_accumulator: expr, {$name: ident : $type: ty as $underlying_type: ty $(, $($tail:tt)*)?}) => {
+        $crate::internal::doc_comment!{
+            concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
+            #[allow(non_camel_case_types)]
+            pub type $name = <$crate::WrappedField::<$underlying_type, $type, $crate::PrimitiveField::<$underlying_type, $endianness, {$crate::binary_layout!(@field_offset $options, $offset_accumulator, $underlying_type, $endianness)}>> as $crate::internal::SelectField<NestedView, {$relative_accumulator}>>::Field;
+        }
+        $crate::binary_layout!(@impl_fields $endianness, $options, ($crate::binary_layout!(@next_field_offset $name)), ($crate::binary_layout!(@next_field_relative $name)), {$($($tail)*)?});
     };
-    (@impl_fields $endianness: ty, $offset_accumulator: expr, {$name: ident : $type: ty as $underlying_type: ty $(, $($tail:tt)*)?}) => {
+    (@impl_fields $endianness: ty, $options: tt, $offset_accumulator: expr, $relative_accumulator: expr, {$name: ident : $type: ty $(, $($tail:tt)*)?}) => {
         $crate::internal::doc_comment!{
             concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
             #[allow(non_camel_case_types)]
-            pub type $name = $crate::WrappedField::<$underlying_type, $type, $crate::PrimitiveField::<$underlying_type, $endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>>;
+            pub type $name = <$crate::PrimitiveField::<$type, $endianness, {$crate::binary_layout!(@field_offset $options, $offset_accumulator, $type, $endianness)}> as $crate::internal::SelectField<NestedView, {$relative_accumulator}>>::Field;
         }
-        $crate::binary_layout!(@impl_fields $endianness, ($crate::internal::option_usize_add(<$name as $crate::Field>::OFFSET, <$name as $crate::Field>::SIZE)), {$($($tail)*)?});
+        $crate::binary_layout!(@impl_fields $endianness, $options, ($crate::binary_layout!(@next_field_offset $name)), ($crate::binary_layout!(@next_field_relative $name)), {$($($tail)*)?});
+    };
+
+    // Offset after a field, see next_field_offset()
+    (@next_field_offset $name: ident) => {
+        $crate::internal::next_field_offset(
+            <$name as $crate::Field>::OFFSET,
+            <$name as $crate::Field>::SIZE,
+            <$name as $crate::internal::FieldKindInfo>::KIND,
+        )
+    };
+
+    // Whether the fields after a field are relative, i.e. whether it is a LengthPrefixed or varint field or relative itself
+    (@next_field_relative $name: ident) => {
+        <$name as $crate::internal::FieldKindInfo>::RELATIVE || $crate::internal::has_dynamic_len_kind(<$name as $crate::internal::FieldKindInfo>::KIND)
+    };
+
+    // Size of the layout after its last field, which aligned layouts round up to their alignment
+    (@layout_size packed, $offset_accumulator: expr) => {
+        $offset_accumulator
+    };
+    (@layout_size aligned, $offset_accumulator: expr) => {
+        $crate::internal::option_align_offset($offset_accumulator, ALIGNMENT)
+    };
+    (@layout_size $packing: ident, $offset_accumulator: expr, $pad_to: literal) => {
+        Some($crate::internal::pad_to_size($crate::binary_layout!(@layout_size $packing, $offset_accumulator), $pad_to))
+    };
+
+    // Offset of the next field. Aligned layouts round it up to the natural alignment of the field type, bit fields aren't aligned.
+    (@field_offset {packed $pad_to: tt $value_struct: tt $builder: tt}, $offset_accumulator: expr, $type: ty, $endianness: ty) => {
+        $crate::internal::unwrap_field_size($offset_accumulator)
+    };
+    (@field_offset {aligned $pad_to: tt $value_struct: tt $builder: tt}, $offset_accumulator: expr, $type: ty, $endianness: ty) => {
+        $crate::internal::align_offset(
+            $crate::internal::unwrap_field_size($offset_accumulator),
+            $crate::internal::field_alignment(
+                <$crate::PrimitiveField<$type, $endianness, 0> as $crate::internal::FieldKindInfo>::KIND,
+                <$crate::PrimitiveField<$type, $endianness, 0> as $crate::Field>::SIZE,
+            ),
+        )
     };
-    (@impl_fields $endianness: ty, $offset_accumulator: expr, {$name: ident : $type: ty $(, $($tail:tt)*)?}) => {
+
+    // Consecutive bit fields form a group, the offset of the group is in bytes and the accumulator counts the bits of the group so far
+    (@impl_bit_fields $endianness: ty, $options: tt, $group_offset: expr, $relative: expr, $bit_accumulator: expr, {$name: ident : $type: ty ; bits $bits: literal $(, $($tail:tt)*)?}) => {
         $crate::internal::doc_comment!{
             concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
             #[allow(non_camel_case_types)]
-            pub type $name = $crate::PrimitiveField::<$type, $endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>;
+            pub type $name = <$crate::BitField::<$type, $endianness, {$group_offset + $bit_accumulator / 8}, {$bit_accumulator % 8}, $bits> as $crate::internal::SelectField<NestedView, {$relative}>>::Field;
         }
-        $crate::binary_layout!(@impl_fields $endianness, ($crate::internal::option_usize_add(<$name as $crate::Field>::OFFSET, <$name as $crate::Field>::SIZE)), {$($($tail)*)?});
+        $crate::binary_layout!(@impl_bit_fields $endianness, $options, $group_offset, $relative, ($bit_accumulator + $bits), {$($($tail)*)?});
+    };
+    (@impl_bit_fields $endianness: ty, $options: tt, $group_offset: expr, $relative: expr, $bit_accumulator: expr, {$($tail: tt)*}) => {
+        const _: () = assert!($bit_accumulator % 8 == 0, "Consecutive bit fields must add up to whole bytes");
+        $crate::binary_layout!(@impl_fields $endianness, $options, (Some($group_offset + $bit_accumulator / 8)), $relative, {$($tail)*});
+    };
+
+    (@impl_alignment {packed $pad_to: tt $value_struct: tt $builder: tt}, {$($field_name: ident),*}) => {};
+    (@impl_alignment {aligned $pad_to: tt $value_struct: tt $builder: tt}, {$($field_name: ident),*}) => {
+        const _: () = assert!(!$crate::internal::has_fields_after_dynamic_len(&LAYOUT), "Error: Aligned layouts can't have fields after LengthPrefixed or varint fields");
+        /// Alignment of this layout in bytes, i.e. the largest natural alignment of its fields. [SIZE] is a multiple of it.
+        pub const ALIGNMENT: usize = $crate::internal::fields_alignment(&[$(Fields::$field_name),*]);
+        /// Number of padding bytes inserted to align the fields of this layout, including those after the last field
+        pub const PADDING: usize = $crate::internal::padding_size(&LAYOUT);
     };
 
     (@impl_view_asref {}) => {};
@@ -244,6 +1711,208 @@ macro_rules! binary_layout {
     };
 }
 
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `std` feature of this crate is enabled, so [binary_layout!](crate::binary_layout!)
+/// can generate code that needs `std` without depending on the features of the crate using it.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_std {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `std` feature of this crate is enabled, so [binary_layout!](crate::binary_layout!)
+/// can generate code that needs `std` without depending on the features of the crate using it.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_std {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `arbitrary` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_arbitrary {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `arbitrary` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "arbitrary"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_arbitrary {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `binrw` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "binrw")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_binrw {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `binrw` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "binrw"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_binrw {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `memmap2` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "memmap2")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_memmap2 {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `memmap2` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "memmap2"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_memmap2 {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `nom` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "nom")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_nom {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `nom` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "nom"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_nom {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `proptest` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_proptest {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `proptest` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "proptest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_proptest {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `rkyv` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "rkyv")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_rkyv {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `rkyv` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "rkyv"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_rkyv {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `serde` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_serde {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `serde` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_serde {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `tokio` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_tokio {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `tokio` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "tokio"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_tokio {
+    ($($tokens: tt)*) => {};
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `tokio-util` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(feature = "tokio-util")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_tokio_util {
+    ($($tokens: tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Internal macro. Don't use this in user code.
+/// Expands to its input if the `tokio-util` feature of this crate is enabled, see [if_std!](crate::if_std!).
+#[cfg(not(feature = "tokio-util"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! if_tokio_util {
+    ($($tokens: tt)*) => {};
+}
+
 /// Deprecated name for [crate::binary_layout!]. Please switch to [crate::binary_layout!].
 #[deprecated = "The `define_layout!` macro was renamed to `binary_layout!` and the old name will be removed in future versions."]
 #[macro_export]
@@ -261,22 +1930,99 @@ pub const fn unwrap_field_size(opt: Option<usize>) -> usize {
     match opt {
         Some(x) => x,
         None => {
-            panic!("Error: Fields without a static size (e.g. open-ended byte arrays) can only be used at the end of a layout");
+            panic!("Error: Fields without a static size (e.g. open-ended byte arrays) can only be used at the end of a layout, except for LengthPrefixed and varint fields");
+        }
+    }
+}
+
+/// Internal function, don't use!
+/// Unwraps the size of a layout for [SizedLayout](crate::SizedLayout)
+#[inline(always)]
+pub const fn unwrap_layout_size(opt: Option<usize>) -> usize {
+    match opt {
+        Some(x) => x,
+        None => {
+            panic!("Error: SizedLayout::STATIC_SIZE can only be used for layouts with a static size, not for layouts ending with an open ended field or with LengthPrefixed or varint fields");
+        }
+    }
+}
+
+/// Internal function, don't use!
+/// Returns the explicit offset of a field after checking that it doesn't overlap the fields before it, which end at `end`
+#[inline(always)]
+pub const fn pin_field_offset(end: Option<usize>, offset: usize) -> usize {
+    assert!(
+        unwrap_field_size(end) <= offset,
+        "Error: A field with an explicit offset overlaps the fields before it"
+    );
+    offset
+}
+
+/// Internal function, don't use!
+/// Returns the size of a layout with a `pad_to` size after checking that its fields fit into it
+#[inline(always)]
+pub const fn pad_to_size(size: Option<usize>, pad_to: usize) -> usize {
+    match size {
+        Some(size) => {
+            assert!(
+                size <= pad_to,
+                "Error: The fields of the layout are larger than its pad_to size"
+            );
+            pad_to
+        }
+        None => {
+            panic!("Error: Layouts with an open ended field at the end or with LengthPrefixed or varint fields can't have a pad_to size")
         }
     }
 }
 
 /// Internal function, don't use!
+/// Rounds the offset up to a multiple of the alignment
+#[inline(always)]
+pub const fn align_offset(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) / alignment * alignment
+}
+
+/// Internal function, don't use!
+#[inline(always)]
+pub const fn option_align_offset(offset: Option<usize>, alignment: usize) -> Option<usize> {
+    match offset {
+        Some(offset) => Some(align_offset(offset, alignment)),
+        None => None,
+    }
+}
+
+/// Internal function, don't use!
+/// Returns the offset after a field, or `None` for open ended fields. For [LengthPrefixed](crate::LengthPrefixed) and varint fields,
+/// it's the offset after their minimal number of bytes, which the fields after them are relative to, see [RelativeField](crate::RelativeField).
 #[inline(always)]
-pub const fn option_usize_add(lhs: usize, rhs: Option<usize>) -> Option<usize> {
-    match (lhs, rhs) {
-        (lhs, Some(rhs)) => Some(lhs + rhs),
-        (_, None) => None,
+pub const fn next_field_offset(
+    offset: usize,
+    size: Option<usize>,
+    kind: crate::FieldKind,
+) -> Option<usize> {
+    match (size, kind) {
+        (Some(size), _) => Some(offset + size),
+        (None, crate::FieldKind::LengthPrefixed { prefix_size }) => Some(offset + prefix_size),
+        (None, crate::FieldKind::Varint(_)) => Some(offset + 1),
+        (None, _) => None,
+    }
+}
+
+/// Internal function, don't use!
+/// Returns the size of a layout ending at `end`, which is `None` if it has fields whose length depends on the data
+#[inline(always)]
+pub const fn static_layout_size(end: Option<usize>, relative: bool) -> Option<usize> {
+    if relative {
+        None
+    } else {
+        end
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #[allow(unused_imports)]
     use crate::prelude::*;
 
     use rand::{rngs::StdRng, RngCore, SeedableRng};
@@ -374,6 +2120,429 @@ mod tests {
             field2: i64,
         });
         assert_eq!(Some(10), my_layout::SIZE);
+        assert_eq!(10, my_layout::MIN_SIZE);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn new_zeroed() {
+        binary_layout!(my_layout, BigEndian, pad_to = 8, {
+            field1: u16,
+            field2: [u8; 3],
+        });
+        let mut view = my_layout::View::new_zeroed();
+        assert!(view.field2().iter().all(|byte| *byte == 0));
+        view.field1_mut().write(0x0102);
+        assert_eq!(vec![1, 2, 0, 0, 0, 0, 0, 0], view.into_storage());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_from() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: [u8; 2],
+        });
+        let mut reader: &[u8] = &[1, 2, 3, 4, 5, 6];
+        let view = my_layout::View::read_from(&mut reader).unwrap();
+        assert_eq!(0x0102, view.field1().read());
+        assert_eq!(&[3, 4], view.field2());
+        assert_eq!(&[5, 6], reader);
+
+        let error = my_layout::View::read_from(&mut reader).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error.kind());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_from_with_len() {
+        binary_layout!(my_layout, BigEndian, {
+            length: u16,
+            payload: [u8],
+        });
+        let mut reader: &[u8] = &[0, 3, 7, 8, 9, 10];
+        let view = my_layout::View::read_from_with_len(&mut reader, 5).unwrap();
+        assert_eq!(3, view.length().read());
+        assert_eq!(&[7, 8, 9], view.payload());
+        assert_eq!(&[10], reader);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_to() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: [u8; 2],
+        });
+        let view = my_layout::View::new([1, 2, 3, 4, 5, 6]);
+        let mut written = vec![0xff];
+        view.write_to(&mut written).unwrap();
+        assert_eq!(vec![0xff, 1, 2, 3, 4], written);
+
+        let view = my_layout::View::new([1, 2, 3]);
+        let error = view.write_to(&mut written).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, error.kind());
+        assert_eq!(5, written.len());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_to_open_ended_layout() {
+        binary_layout!(my_layout, BigEndian, {
+            length: u16,
+            payload: [u8],
+        });
+        let mut written = Vec::new();
+        my_layout::View::new([0, 3, 7, 8, 9])
+            .write_to(&mut written)
+            .unwrap();
+        assert_eq!(vec![0, 3, 7, 8, 9], written);
+        assert_eq!(
+            &[7, 8, 9],
+            my_layout::View::read_from_with_len(&written[..], 5)
+                .unwrap()
+                .payload()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "static size")]
+    fn read_from_open_ended_layout() {
+        binary_layout!(my_layout, BigEndian, {
+            length: u16,
+            payload: [u8],
+        });
+        let _ = my_layout::View::read_from(&[0u8, 0][..]);
+    }
+
+    #[test]
+    fn subview() {
+        binary_layout!(container, BigEndian, {
+            kind: u8,
+            reserved: u8,
+            payload: [u8],
+        });
+        binary_layout!(inner, LittleEndian, {
+            value: u16,
+        });
+        let mut storage = [1, 0, 0xaa, 0x34, 0x12, 0xbb];
+        let mut view = container::View::new(&mut storage[..]);
+        assert_eq!(&[0x34, 0x12], view.subview(3..5));
+        assert_eq!(&[0xaa, 0x34, 0x12, 0xbb], view.subview(2..));
+        assert_eq!(0x1234, inner::View::new(view.subview(3..=4)).value().read());
+
+        inner::View::new(view.subview_mut(3..5))
+            .value_mut()
+            .write(0x5678);
+        assert_eq!(&[0xaa, 0x78, 0x56, 0xbb], view.payload());
+        assert_eq!([1, 0, 0xaa, 0x78, 0x56, 0xbb], storage);
+    }
+
+    #[test]
+    fn into_subview() {
+        binary_layout!(container, BigEndian, {
+            kind: u8,
+            payload: [u8],
+        });
+        binary_layout!(inner, BigEndian, {
+            value: u16,
+        });
+        let view = container::View::new([1, 0x12, 0x34, 0xff]);
+        let data = view.into_subview(1..3);
+        assert_eq!(1..3, data.region());
+        let mut inner = inner::View::new(data);
+        assert_eq!(0x1234, inner.value().read());
+        inner.value_mut().write(0x5678);
+        assert_eq!([1, 0x56, 0x78, 0xff], inner.into_storage().into_inner());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn subview_out_of_bounds() {
+        binary_layout!(my_layout, BigEndian, {
+            field: u16,
+        });
+        let view = my_layout::View::new([0; 2]);
+        let _ = view.subview(1..3);
+    }
+
+    #[test]
+    fn layout_info() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: bool as u8,
+            field3: [u8; 5],
+            tail: [u8],
+        });
+        assert_eq!(
+            crate::LayoutInfo {
+                name: "my_layout",
+                endianness: crate::EndianKind::Big,
+                size: None,
+                fields: &[
+                    crate::FieldInfo {
+                        name: "field1",
+                        type_name: "u16",
+                        offset: 0,
+                        size: Some(2),
+                        kind: crate::FieldKind::UnsignedInteger,
+                        optional: false,
+                    },
+                    crate::FieldInfo {
+                        name: "field2",
+                        type_name: "bool as u8",
+                        offset: 2,
+                        size: Some(1),
+                        kind: crate::FieldKind::UnsignedInteger,
+                        optional: false,
+                    },
+                    crate::FieldInfo {
+                        name: "field3",
+                        type_name: "[u8; 5]",
+                        offset: 3,
+                        size: Some(5),
+                        kind: crate::FieldKind::Bytes,
+                        optional: false,
+                    },
+                    crate::FieldInfo {
+                        name: "tail",
+                        type_name: "[u8]",
+                        offset: 8,
+                        size: None,
+                        kind: crate::FieldKind::Bytes,
+                        optional: false,
+                    },
+                ],
+            },
+            my_layout::LAYOUT
+        );
+    }
+
+    #[test]
+    fn layout_info_of_empty_layout() {
+        binary_layout!(my_layout, NativeEndian, {});
+        assert_eq!("my_layout", my_layout::LAYOUT.name);
+        assert_eq!(crate::EndianKind::Native, my_layout::LAYOUT.endianness);
+        assert_eq!(Some(0), my_layout::LAYOUT.size);
+        assert!(my_layout::LAYOUT.fields.is_empty());
+        assert_eq!(0, my_layout::FieldId::iter().count());
+    }
+
+    #[test]
+    fn field_id() {
+        binary_layout!(my_layout, LittleEndian, {
+            first: u8,
+            second: u32,
+            tail: [u8],
+        });
+        use my_layout::FieldId;
+
+        assert_eq!(
+            &[FieldId::first, FieldId::second, FieldId::tail],
+            FieldId::ALL
+        );
+        assert!(FieldId::iter().eq(FieldId::ALL.iter().copied()));
+        assert_eq!("second", FieldId::second.name());
+        assert_eq!(1, FieldId::second.offset());
+        assert_eq!(Some(4), FieldId::second.size());
+        assert_eq!(None, FieldId::tail.size());
+        assert_eq!(&my_layout::Fields::tail, FieldId::tail.info());
+        assert_eq!(Some(FieldId::tail), FieldId::from_name("tail"));
+        assert_eq!(None, FieldId::from_name("third"));
+
+        const SECOND_OFFSET: usize = FieldId::second.offset();
+        assert_eq!(1, SECOND_OFFSET);
+
+        let mut storage = [0; 6];
+        let mut view = my_layout::View::new(&mut storage);
+        for (field, value) in [
+            (FieldId::second, 7),
+            (FieldId::first, 2),
+            (FieldId::tail, 9),
+        ] {
+            match field {
+                FieldId::first => view.first_mut().write(value as u8),
+                FieldId::second => view.second_mut().write(value),
+                FieldId::tail => view.tail_mut()[0] = value as u8,
+            }
+        }
+        assert_eq!([2, 7, 0, 0, 0, 9], storage);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn layout_info_field_kinds() {
+        binary_layout!(my_layout, LittleEndian, {
+            signed: i32,
+            nonzero: core::num::NonZeroI16,
+            float: f64,
+            unit: (),
+        });
+        let kinds: Vec<_> = my_layout::LAYOUT.fields.iter().map(|f| f.kind).collect();
+        assert_eq!(
+            vec![
+                crate::FieldKind::SignedInteger,
+                crate::FieldKind::SignedInteger,
+                crate::FieldKind::Float,
+                crate::FieldKind::Unit,
+            ],
+            kinds
+        );
+    }
+
+    #[test]
+    fn explicit_offsets() {
+        use crate::prelude::*;
+
+        binary_layout!(my_layout, BigEndian, {
+            first @ 2: u8,
+            second: u16,
+            flag: bool bits(1),
+            rest: u8 bits(7),
+            third @ 0x10: u32 in 1..=2,
+            fourth @ 0x14: [u8; 2],
+            tail @ 24: [u8],
+        });
+        assert_eq!(2, my_layout::first::OFFSET);
+        assert_eq!(3, my_layout::second::OFFSET);
+        assert_eq!(5, my_layout::rest::OFFSET);
+        assert_eq!(16, my_layout::third::OFFSET);
+        assert_eq!(20, my_layout::fourth::OFFSET);
+        assert_eq!(24, my_layout::tail::OFFSET);
+        assert_eq!(16, my_layout::Fields::third.offset);
+
+        let mut storage = [0xff; 26];
+        my_layout::third::write(&mut storage, 1);
+        let mut view = my_layout::View::try_new(&mut storage).unwrap();
+        view.second_mut().write(0x0102);
+        view.tail_mut().copy_from_slice(&[7, 8]);
+        assert_eq!(
+            [
+                0xff, 0xff, 0xff, 1, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff, 7, 8
+            ],
+            storage
+        );
+    }
+
+    binary_layout!(aligned_inner, BigEndian, aligned, {
+        value: u16,
+        tag: u8,
+    });
+
+    binary_layout!(aligned_layout, BigEndian, aligned, round_trip_tests, {
+        first: u8,
+        second: i64,
+        flag: bool bits(1),
+        rest: u8 bits(7),
+        nested: aligned_inner::NestedView,
+        array: [u32; 2],
+        third: u8,
+        pinned @ 0x22: u16,
+        enabled: bool as u8,
+        last: f32,
+    });
+
+    #[test]
+    fn aligned_layouts() {
+        use crate::prelude::*;
+
+        assert_eq!(Some(4), aligned_inner::SIZE);
+        assert_eq!(2, aligned_inner::ALIGNMENT);
+        assert_eq!(1, aligned_inner::PADDING);
+
+        assert_eq!(8, aligned_layout::second::OFFSET);
+        assert_eq!(16, aligned_layout::flag::OFFSET);
+        assert_eq!(18, aligned_layout::nested::OFFSET);
+        assert_eq!(24, aligned_layout::array::OFFSET);
+        assert_eq!(32, aligned_layout::third::OFFSET);
+        assert_eq!(0x22, aligned_layout::pinned::OFFSET);
+        assert_eq!(36, aligned_layout::enabled::OFFSET);
+        assert_eq!(40, aligned_layout::last::OFFSET);
+        assert_eq!(Some(48), aligned_layout::SIZE);
+        assert_eq!(8, aligned_layout::ALIGNMENT);
+        assert_eq!(7 + 1 + 2 + 1 + 3 + 4, aligned_layout::PADDING);
+    }
+
+    binary_layout!(padded_layout, BigEndian, round_trip_tests, pad_to = 16, aligned, {
+        first: u8,
+        second: u32,
+    });
+
+    #[test]
+    fn padded_layouts() {
+        use crate::prelude::*;
+
+        binary_layout!(packed_layout, LittleEndian, pad_to = 0x10, {
+            first: u8,
+            second: u32,
+        });
+        assert_eq!(1, packed_layout::second::OFFSET);
+        assert_eq!(Some(16), packed_layout::SIZE);
+        assert_eq!(Some(16), packed_layout::LAYOUT.size);
+
+        assert_eq!(4, padded_layout::second::OFFSET);
+        assert_eq!(Some(16), padded_layout::SIZE);
+        assert_eq!(3 + 8, padded_layout::PADDING);
+        assert!(padded_layout::View::try_new([0; 15]).is_err());
+        assert!(padded_layout::View::try_new([0; 16]).is_ok());
+    }
+
+    #[test]
+    fn layouts_without_gaps() {
+        use crate::prelude::*;
+
+        binary_layout!(registers, LittleEndian, no_gaps, pad_to = 12, aligned, {
+            id: u16,
+            reserved: crate::Padding<2>,
+            control @ 4: u32,
+            flags: u16,
+            tail: crate::Padding<2>,
+        });
+        assert_eq!(4, registers::control::OFFSET);
+        assert_eq!(Some(12), registers::SIZE);
+        assert_eq!(0, registers::PADDING);
+
+        binary_layout!(message, BigEndian, no_gaps, {
+            kind: u8,
+            payload: [u8],
+        });
+        assert_eq!(None, message::SIZE);
+    }
+
+    #[test]
+    fn aligned_open_ended_layout() {
+        use crate::prelude::*;
+
+        binary_layout!(my_layout, LittleEndian, aligned, {
+            first: u8,
+            second: u16,
+            tail: [u32],
+        });
+        assert_eq!(2, my_layout::second::OFFSET);
+        assert_eq!(4, my_layout::tail::OFFSET);
+        assert_eq!(None, my_layout::SIZE);
+        assert_eq!(4, my_layout::ALIGNMENT);
+        assert_eq!(1, my_layout::PADDING);
+    }
+
+    #[test]
+    fn sized_layout() {
+        use crate::SizedLayout;
+
+        binary_layout!(my_layout, LittleEndian, pad_to = 8, {
+            field1: u16,
+            field2: [u8; 3],
+        });
+        fn static_size<L: SizedLayout>() -> usize {
+            L::STATIC_SIZE
+        }
+        assert_eq!(8, static_size::<my_layout::NestedView>());
+        assert_eq!(
+            Some(<my_layout::NestedView as SizedLayout>::STATIC_SIZE),
+            my_layout::SIZE
+        );
     }
 
     #[test]
@@ -383,5 +2552,8 @@ mod tests {
             tail: [u8],
         });
         assert_eq!(None, my_layout::SIZE);
+        assert_eq!(2, my_layout::MIN_SIZE);
+        assert!(my_layout::View::try_new([0; 1]).is_err());
+        assert!(my_layout::View::try_new([0; my_layout::MIN_SIZE]).is_ok());
     }
 }
@@ -0,0 +1,167 @@
+/// Implemented by views for the structs that [map_struct!](crate::map_struct!) maps their layout onto,
+/// writing the fields of the struct into the view.
+pub trait WriteFrom<T> {
+    /// Write the fields of the value into the view. Fields of the layout that aren't mapped to the struct keep their values.
+    fn write_from(&mut self, value: &T);
+}
+
+/// Map the fields of a layout onto an existing struct, so that domain types can stay decoupled from the wire format.
+/// It implements `From<View>` and `From<&View>` for the struct to read it from a view, and [WriteFrom] for the views
+/// of the layout to write it into a view with `view.write_from(&value)`.
+///
+/// The struct fields are listed by name. They map to the layout field of the same name, unless another layout field is
+/// given with `struct_field = layout_field`. All struct fields have to be listed, but layout fields can be left out,
+/// e.g. reserved fields or checksums. The mapped layout fields need to be readable and writable with `read()`
+/// and `write()`, and the struct fields need to implement [Clone].
+///
+/// # Example
+/// ```
+/// use binary_layout::{map_struct, prelude::*};
+///
+/// binary_layout!(header, BigEndian, {
+///   version: u8,
+///   reserved: u8,
+///   length: u16,
+///   seq_no: u32,
+/// });
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Header {
+///     version: u8,
+///     len: u16,
+///     seq_no: u32,
+/// }
+///
+/// map_struct!(Header, header, {
+///     version,
+///     len = length,
+///     seq_no,
+/// });
+///
+/// # fn main() {
+/// let mut view = header::View::new([0; 8]);
+/// view.write_from(&Header { version: 1, len: 4, seq_no: 5 });
+/// assert_eq!(Header { version: 1, len: 4, seq_no: 5 }, Header::from(&view));
+/// assert_eq!([1, 0, 0, 4, 0, 0, 0, 5], view.into_storage());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! map_struct {
+    ($struct: ty, $($layout: ident)::+, {$($struct_field: ident $(= $layout_field: ident)?),* $(,)?} $(,)?) => {
+        impl <S: AsRef<[u8]>> ::core::convert::From<&$($layout)::+::View<S>> for $struct {
+            fn from(view: &$($layout)::+::View<S>) -> Self {
+                Self {
+                    $(
+                        $struct_field: $crate::map_struct!(@read view, $struct_field $(= $layout_field)?),
+                    )*
+                }
+            }
+        }
+
+        impl <S: AsRef<[u8]>> ::core::convert::From<$($layout)::+::View<S>> for $struct {
+            fn from(view: $($layout)::+::View<S>) -> Self {
+                Self::from(&view)
+            }
+        }
+
+        impl <S: AsRef<[u8]> + AsMut<[u8]>> $crate::WriteFrom<$struct> for $($layout)::+::View<S> {
+            fn write_from(&mut self, value: &$struct) {
+                $(
+                    $crate::map_struct!(@write self, value, $struct_field $(= $layout_field)?);
+                )*
+            }
+        }
+    };
+
+    (@read $view: ident, $struct_field: ident) => {
+        $view.$struct_field().read()
+    };
+    (@read $view: ident, $struct_field: ident = $layout_field: ident) => {
+        $view.$layout_field().read()
+    };
+    (@write $view: ident, $value: ident, $struct_field: ident) => {
+        $crate::internal::paste!{
+            $view.[<$struct_field _mut>]().write(::core::clone::Clone::clone(&$value.$struct_field))
+        }
+    };
+    (@write $view: ident, $value: ident, $struct_field: ident = $layout_field: ident) => {
+        $crate::internal::paste!{
+            $view.[<$layout_field _mut>]().write(::core::clone::Clone::clone(&$value.$struct_field))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(record, LittleEndian, {
+        id: u32,
+        reserved: [u8; 2],
+        temperature: i16,
+        ratio: f32,
+        tail: [u8],
+    });
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Record {
+        id: u32,
+        celsius: i16,
+        ratio: f32,
+    }
+
+    crate::map_struct!(Record, record, {
+        id,
+        celsius = temperature,
+        ratio,
+    });
+
+    mod nested {
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct Id {
+            pub value: u32,
+        }
+    }
+
+    crate::map_struct!(nested::Id, self::record, { value = id });
+
+    const RECORD: Record = Record {
+        id: 0x01020304,
+        celsius: -2,
+        ratio: 1.5,
+    };
+    const STORAGE: [u8; 13] = [4, 3, 2, 1, 0, 0, 0xfe, 0xff, 0, 0, 0xc0, 0x3f, 7];
+
+    #[test]
+    fn from_view() {
+        assert_eq!(RECORD, Record::from(record::View::new(STORAGE)));
+        assert_eq!(RECORD, Record::from(&record::View::new(&STORAGE[..])));
+        assert_eq!(
+            nested::Id { value: 0x01020304 },
+            nested::Id::from(record::View::new(STORAGE))
+        );
+    }
+
+    #[test]
+    fn write_from() {
+        let mut storage = [0xaa; 13];
+        let mut view = record::View::new(&mut storage[..]);
+        view.write_from(&RECORD);
+        assert_eq!(
+            [4, 3, 2, 1, 0xaa, 0xaa, 0xfe, 0xff, 0, 0, 0xc0, 0x3f, 0xaa],
+            storage
+        );
+
+        let mut view = record::View::new(STORAGE);
+        view.write_from(&nested::Id { value: 5 });
+        assert_eq!(5, view.id().read());
+        assert_eq!(-2, view.temperature().read());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut view = record::View::new([0; 12]);
+        view.write_from(&RECORD);
+        assert_eq!(RECORD, Record::from(&view));
+    }
+}
@@ -0,0 +1,206 @@
+//! Support for the [memmap2](https://docs.rs/memmap2) crate, enabled by the `memmap2` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) generates `View::map_file(path)`, which returns a view over a read-only memory map of a file,
+//! and `View::map_file_mut(path)` and `View::map_file_mut_with_len(path, len)`, which return a view over a writable memory map.
+//! The writable variants create the file if it doesn't exist and grow it to the size of the layout, or to `len` bytes for layouts
+//! ending with an open ended field, e.g. to make room for the tail of a database page. Files are never shrunk.
+//! Mapping a file that is too short for the layout fails with [InvalidData](std::io::ErrorKind::InvalidData).
+//!
+//! [MmapMut] implements [FlushableStorage](crate::FlushableStorage), so views over writable memory maps offer `View::flush()` and
+//! `View::flush_fields(fields)` to write the changed bytes back to the file.
+//!
+//! These functions are `unsafe` for the same reason as [Mmap::map]: the behavior is undefined if the file is modified,
+//! e.g. by another process, while it's mapped. Since they're generated into the module of each layout, crates declaring layouts
+//! can't `forbid(unsafe_code)` when this feature is enabled, but `deny(unsafe_code)` works.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//!
+//! binary_layout!(page, LittleEndian, {
+//!   id: u32,
+//!   used: u16,
+//!   data: [u8],
+//! });
+//!
+//! fn main() -> std::io::Result<()> {
+//!     let path = std::env::temp_dir().join("binary-layout-memmap2-example");
+//!     # let _ = std::fs::remove_file(&path);
+//!     // SAFETY: No one else accesses the file while it's mapped
+//!     let mut view = unsafe { page::View::map_file_mut_with_len(&path, 4096)? };
+//!     view.id_mut().write(7);
+//!     view.data_mut()[..3].copy_from_slice(&[1, 2, 3]);
+//!     view.flush()?;
+//!     drop(view);
+//!
+//!     let view = unsafe { page::View::map_file(&path)? };
+//!     assert_eq!(7, view.id().read());
+//!     assert_eq!(4090, view.data().len());
+//!     # std::fs::remove_file(&path)?;
+//!     Ok(())
+//! }
+//! ```
+
+#![allow(unsafe_code)]
+
+use core::ops::Range;
+use std::fs::{File, OpenOptions};
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use memmap2::{Mmap, MmapMut};
+
+use crate::{FlushableStorage, LayoutInfo};
+
+impl FlushableStorage for MmapMut {
+    type FlushError = Error;
+
+    #[inline]
+    fn flush_range(&mut self, range: Range<usize>) -> Result<(), Error> {
+        MmapMut::flush_range(self, range.start, range.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Error> {
+        MmapMut::flush(self)
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Maps the file read-only, failing if it's too short for the layout.
+///
+/// # Safety
+/// The file must not be modified while it's mapped, see [Mmap::map].
+pub unsafe fn map_file(layout: &LayoutInfo, path: &Path) -> std::io::Result<Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: The caller guarantees that the file isn't modified while it's mapped
+    let storage = unsafe { Mmap::map(&file)? };
+    check_size(layout, &storage)?;
+    Ok(storage)
+}
+
+/// Internal function. Don't use this in user code.
+/// Maps the file writable, creating it if it doesn't exist and growing it to at least `len` bytes
+/// and at least the size the layout needs.
+///
+/// # Safety
+/// The file must not be modified by others while it's mapped, see [MmapMut::map_mut].
+pub unsafe fn map_file_mut(
+    layout: &LayoutInfo,
+    path: &Path,
+    len: usize,
+) -> std::io::Result<MmapMut> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    let len = len.max(layout.size.unwrap_or_else(|| layout.min_size()));
+    let len = u64::try_from(len).map_err(|error| Error::new(ErrorKind::InvalidInput, error))?;
+    if file.metadata()?.len() < len {
+        file.set_len(len)?;
+    }
+    // SAFETY: The caller guarantees that the file isn't modified by others while it's mapped
+    let storage = unsafe { MmapMut::map_mut(&file)? };
+    check_size(layout, &storage)?;
+    Ok(storage)
+}
+
+fn check_size(layout: &LayoutInfo, storage: &[u8]) -> std::io::Result<()> {
+    crate::internal::check_storage_size(layout, storage)
+        .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+
+    use crate::prelude::*;
+
+    binary_layout!(header, LittleEndian, {
+        magic: u32,
+        version: u16,
+    });
+    binary_layout!(page, BigEndian, {
+        id: u32,
+        data: [u8],
+    });
+
+    /// A path in the temp directory that is removed again when dropped
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "binary-layout-memmap2-{}-{}",
+                std::process::id(),
+                name
+            ));
+            let _ = std::fs::remove_file(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn map_file() {
+        let path = TempPath::new("map_file");
+        std::fs::write(&path.0, [1, 0, 0, 0, 2, 0, 0xff]).unwrap();
+        let view = unsafe { header::View::map_file(&path.0) }.unwrap();
+        assert_eq!(1, view.magic().read());
+        assert_eq!(2, view.version().read());
+    }
+
+    #[test]
+    fn map_file_too_short() {
+        let path = TempPath::new("map_file_too_short");
+        std::fs::write(&path.0, [1, 0, 0, 0, 2]).unwrap();
+        let error = unsafe { header::View::map_file(&path.0) }.unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn map_file_mut_creates_file() {
+        let path = TempPath::new("map_file_mut_creates_file");
+        let mut view = unsafe { header::View::map_file_mut(&path.0) }.unwrap();
+        view.magic_mut().write(0x01020304);
+        view.version_mut().write(5);
+        view.flush().unwrap();
+        drop(view);
+        assert_eq!(vec![4, 3, 2, 1, 5, 0], std::fs::read(&path.0).unwrap());
+    }
+
+    #[test]
+    fn map_file_mut_keeps_longer_file() {
+        let path = TempPath::new("map_file_mut_keeps_longer_file");
+        std::fs::write(&path.0, [0, 0, 0, 1, 0xa, 0xb, 0xc]).unwrap();
+        let mut view = unsafe { page::View::map_file_mut(&path.0) }.unwrap();
+        assert_eq!(1, view.id().read());
+        view.data_mut()[1] = 0xff;
+        view.flush_fields(&[page::FieldId::data]).unwrap();
+        drop(view);
+        assert_eq!(
+            vec![0, 0, 0, 1, 0xa, 0xff, 0xc],
+            std::fs::read(&path.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn map_file_mut_with_len_grows_file() {
+        let path = TempPath::new("map_file_mut_with_len_grows_file");
+        std::fs::write(&path.0, [0, 0, 0, 9, 1]).unwrap();
+        let view = unsafe { page::View::map_file_mut_with_len(&path.0, 16) }.unwrap();
+        assert_eq!(9, view.id().read());
+        assert_eq!(12, view.data().len());
+        assert_eq!(1, view.data()[0]);
+        drop(view);
+        assert_eq!(16, std::fs::metadata(&path.0).unwrap().len());
+    }
+}
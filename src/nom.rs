@@ -0,0 +1,132 @@
+//! Support for the [nom](https://docs.rs/nom) crate, enabled by the `nom` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) generates a `parse` function for each layout, which is a nom parser
+//! returning a view of the layout. Layouts with a fixed size consume exactly that many bytes, and so do layouts whose size is given
+//! by their [LengthPrefixed](crate::LengthPrefixed) or varint fields. Layouts ending in another open ended field consume
+//! all of the input. Like the parsers in `nom::bytes::complete`, it fails with [ErrorKind::Eof] instead of returning
+//! [nom::Err::Incomplete] if the input is too short.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use nom::{bytes::complete::tag, sequence::{pair, preceded}, IResult};
+//!
+//! binary_layout!(record, BigEndian, {
+//!   kind: u8,
+//!   value: u16,
+//! });
+//!
+//! type Records<'a> = (record::View<&'a [u8]>, record::View<&'a [u8]>);
+//!
+//! fn records(input: &[u8]) -> IResult<&[u8], Records> {
+//!     preceded(tag(b"REC"), pair(record::parse, record::parse))(input)
+//! }
+//!
+//! let (rest, (first, second)) = records(b"REC\x01\x00\x02\x03\x00\x04\xff").unwrap();
+//! assert_eq!(2, first.value().read());
+//! assert_eq!(3, second.kind().read());
+//! assert_eq!(b"\xff", rest);
+//! ```
+
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+use crate::LayoutInfo;
+
+/// Internal function. Don't use this in user code.
+/// Splits the storage for a view of the layout off the input.
+pub fn parse_storage<'a>(layout: &LayoutInfo, input: &'a [u8]) -> IResult<&'a [u8], &'a [u8]> {
+    let len = match layout.size {
+        Some(size) if input.len() >= size => size,
+        Some(_) => return Err(Err::Error(Error::new(input, ErrorKind::Eof))),
+        // The data of LengthPrefixed and varint fields decides where the layout ends
+        None => layout
+            .size_in(input)
+            .map_err(|_| Err::Error(Error::new(input, ErrorKind::Eof)))?,
+    };
+    let (storage, rest) = input.split_at(len);
+    Ok((rest, storage))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::{Error, ErrorKind};
+    use nom::Err;
+
+    use crate::prelude::*;
+
+    binary_layout!(sized, LittleEndian, {
+        first: u16,
+        second: u8,
+    });
+    binary_layout!(open_ended, BigEndian, {
+        header: sized::NestedView,
+        payload: [u8],
+    });
+
+    #[test]
+    fn sized_layouts_consume_their_size() {
+        let (rest, view) = sized::parse(&[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(0x0201, view.first().read());
+        assert_eq!(3, view.second().read());
+        assert_eq!(&[4, 5], rest);
+    }
+
+    #[test]
+    fn open_ended_layouts_consume_all_input() {
+        let (rest, view) = open_ended::parse(&[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(3, view.header().second().read());
+        assert_eq!(&[4, 5], view.payload());
+        assert!(rest.is_empty());
+    }
+
+    binary_layout!(named, BigEndian, {
+        kind: u8,
+        name: crate::LengthPrefixed<u8>,
+        crc: u16,
+    });
+    binary_layout!(tagged, LittleEndian, {
+        kind: u8,
+        value: crate::Leb128<u32>,
+    });
+
+    #[test]
+    fn dynamic_layouts_consume_their_data() {
+        let (rest, view) = named::parse(&[1, 2, b'a', b'b', 0x12, 0x34, 0xff, 0xee]).unwrap();
+        assert_eq!(Ok(&b"ab"[..]), view.name().try_data());
+        assert_eq!(0x1234, view.crc().read());
+        assert_eq!(&[0xff, 0xee], rest);
+
+        let (rest, view) = tagged::parse(&[1, 0xac, 0x02, 7]).unwrap();
+        assert_eq!(Ok(300), view.value().try_read());
+        assert_eq!(&[7], rest);
+    }
+
+    #[test]
+    fn corrupt_length_prefix() {
+        // The length prefix points past the end of the input
+        let input: &[u8] = &[1, 200, b'a', b'b', 0x12, 0x34];
+        assert_eq!(
+            Err(Err::Error(Error::new(input, ErrorKind::Eof))),
+            named::parse(input).map(|(rest, view)| (rest, view.into_storage()))
+        );
+        let input: &[u8] = &[1, 0xac, 0x82];
+        assert_eq!(
+            Err(Err::Error(Error::new(input, ErrorKind::Eof))),
+            tagged::parse(input).map(|(rest, view)| (rest, view.into_storage()))
+        );
+    }
+
+    #[test]
+    fn too_short() {
+        let input: &[u8] = &[1, 2];
+        assert_eq!(
+            Err(Err::Error(Error::new(input, ErrorKind::Eof))),
+            sized::parse(input).map(|(rest, view)| (rest, view.into_storage()))
+        );
+        assert_eq!(
+            Err(Err::Error(Error::new(input, ErrorKind::Eof))),
+            open_ended::parse(input).map(|(rest, view)| (rest, view.into_storage()))
+        );
+    }
+}
@@ -0,0 +1,101 @@
+/// Expands to the offset of a field in its layout, e.g. `offset_of!(my_layout::field)`, see [Field::OFFSET](crate::Field::OFFSET).
+/// For fields after [LengthPrefixed](crate::LengthPrefixed) or varint fields, this is their offset when those fields have their minimal size.
+///
+/// It can be evaluated in const contexts, so constants, array lengths and FFI shims can refer to the geometry of a layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{offset_of, prelude::*, size_of};
+///
+/// binary_layout!(header, LittleEndian, {
+///   magic: [u8; 4],
+///   version: u16,
+///   flags: u32,
+/// });
+///
+/// const FLAGS_OFFSET: usize = offset_of!(header::flags);
+/// const FLAGS_END: usize = FLAGS_OFFSET + size_of!(header::flags);
+///
+/// let mut magic = [0; size_of!(header::magic)];
+/// magic.copy_from_slice(b"BLAY");
+/// assert_eq!(6, FLAGS_OFFSET);
+/// assert_eq!(10, FLAGS_END);
+/// ```
+#[macro_export]
+macro_rules! offset_of {
+    ($field: ty) => {
+        <$field as $crate::Field>::OFFSET
+    };
+}
+
+/// Expands to the size of a field in its layout, e.g. `size_of!(my_layout::field)`, see [Field::SIZE](crate::Field::SIZE).
+///
+/// Unlike [Field::SIZE](crate::Field::SIZE), this is a `usize`, which fails to compile in const contexts for open ended fields
+/// like `[u8]` since they don't have a static size, and panics when evaluated at runtime for them. See [offset_of!](crate::offset_of!) for an example.
+#[macro_export]
+macro_rules! size_of {
+    ($field: ty) => {
+        $crate::internal::static_field_size(<$field as $crate::Field>::SIZE)
+    };
+}
+
+/// Internal function. Don't use this in user code.
+/// Unwraps the size of a field in const contexts.
+pub const fn static_field_size(size: Option<usize>) -> usize {
+    match size {
+        Some(size) => size,
+        None => panic!(
+            "size_of! can't be used for open ended fields since they don't have a static size"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(header, BigEndian, {
+        kind: u8,
+        length: u16,
+    });
+    binary_layout!(packet, LittleEndian, {
+        id: u32,
+        head: header::NestedView,
+        checksum: [u8; 4],
+        payload: [u8],
+    });
+
+    const CHECKSUM_OFFSET: usize = crate::offset_of!(packet::checksum);
+    const HEAD_SIZE: usize = crate::size_of!(packet::head);
+
+    #[test]
+    fn offset_of() {
+        assert_eq!(0, crate::offset_of!(packet::id));
+        assert_eq!(4, crate::offset_of!(packet::head));
+        assert_eq!(7, CHECKSUM_OFFSET);
+        assert_eq!(11, crate::offset_of!(packet::payload));
+        assert_eq!(1, crate::offset_of!(header::length));
+    }
+
+    #[test]
+    fn size_of() {
+        let checksum = [0u8; crate::size_of!(packet::checksum)];
+        assert_eq!(4, checksum.len());
+        assert_eq!(3, HEAD_SIZE);
+        assert_eq!(2, crate::size_of!(header::length));
+    }
+
+    #[test]
+    fn in_generic_code() {
+        fn end_of<F: Field>() -> usize {
+            crate::offset_of!(F) + crate::size_of!(F)
+        }
+        assert_eq!(11, end_of::<packet::checksum>());
+    }
+
+    #[test]
+    #[should_panic(expected = "open ended")]
+    fn size_of_open_ended_field() {
+        let _ = crate::size_of!(packet::payload);
+    }
+}
@@ -0,0 +1,335 @@
+//! Support for the [proptest](https://docs.rs/proptest) crate, enabled by the `proptest` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) generates a `strategy()` function for each layout, returning a
+//! [Strategy] for the storage of a view. Fields are generated from a strategy for their type,
+//! so the generated storage is structurally valid, e.g. `bool as u8` fields are always `0` or `1` and `NonZeroU32`
+//! fields are never zero. Fields using a custom type via [LayoutAs](crate::LayoutAs) are generated from
+//! `proptest::arbitrary::any` if the custom type implements [Arbitrary], so the custom type can restrict the generated
+//! values to the valid ones. Otherwise, they are generated from arbitrary bytes. Open ended fields get an arbitrary length,
+//! and [LengthPrefixed](crate::LengthPrefixed) fields arbitrary data whose length is stored in their prefix.
+//!
+//! Because each field has its own strategy, failing test cases are shrunk field by field.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use proptest::prelude::*;
+//!
+//! binary_layout!(my_layout, BigEndian, {
+//!   flag: bool as u8,
+//!   length: u16,
+//!   payload: [u8],
+//! });
+//!
+//! proptest! {
+//!     # #![proptest_config(ProptestConfig::with_cases(10))]
+//!     fn flag_is_valid(data in my_layout::strategy()) {
+//!         let view = my_layout::View::new(data);
+//!         prop_assert!(view.flag().try_read().is_ok());
+//!     }
+//! }
+//! # fn main() { flag_is_valid(); }
+//! ```
+
+use core::marker::PhantomData;
+
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::vec;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::fields::primitive::NestedViewInfo;
+use crate::layout_info::FieldKindInfo;
+use crate::{
+    BitField, Endianness, Field, FieldCopyAccess, FieldKind, LayoutInfo, PrimitiveField,
+    RelativeField,
+};
+
+/// Maximal length of the data generated for open ended fields
+const MAX_OPEN_ENDED_LEN: usize = 64;
+
+/// Internal type. Don't use this in user code.
+/// [binary_layout!](crate::binary_layout!) calls `(&&&StrategyField::<F>::default()).field_strategy()` for each field,
+/// which uses the first of [NestedStrategyField] or [BitsStrategyField], [ValueStrategyField] and [BytesStrategyField] that is implemented for the field.
+pub struct StrategyField<F>(PhantomData<F>);
+
+impl<F> Default for StrategyField<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Generates nested layouts field by field.
+pub trait NestedStrategyField {
+    /// Strategy for the bytes of the field
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>>;
+}
+
+impl<N: NestedViewInfo + LayoutStrategy, E: Endianness, const OFFSET_: usize> NestedStrategyField
+    for &&StrategyField<PrimitiveField<N, E, OFFSET_>>
+{
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>> {
+        N::storage_strategy()
+    }
+}
+
+impl<L, N: NestedViewInfo + LayoutStrategy, E: Endianness, const OFFSET_: usize> NestedStrategyField
+    for &&StrategyField<RelativeField<L, PrimitiveField<N, E, OFFSET_>>>
+{
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>> {
+        N::storage_strategy()
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Generates bit fields from arbitrary bytes. Bit fields share their bytes with other bit fields, so they can't be generated
+/// from a strategy for their value like other fields, which would overwrite the bits of the other fields with zeroes.
+pub trait BitsStrategyField {
+    /// Strategy for the bytes of the field
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>>;
+}
+
+impl<T, E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+    BitsStrategyField for &&StrategyField<BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>
+where
+    BitField<T, E, OFFSET_, BIT_OFFSET, BITS>: Field + FieldKindInfo,
+{
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>> {
+        BytesStrategyField::field_strategy(&StrategyField::<
+            BitField<T, E, OFFSET_, BIT_OFFSET, BITS>,
+        >::default())
+    }
+}
+
+impl<L, T, E: Endianness, const OFFSET_: usize, const BIT_OFFSET: usize, const BITS: usize>
+    BitsStrategyField
+    for &&StrategyField<RelativeField<L, BitField<T, E, OFFSET_, BIT_OFFSET, BITS>>>
+where
+    BitField<T, E, OFFSET_, BIT_OFFSET, BITS>: Field + FieldKindInfo,
+{
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>> {
+        BytesStrategyField::field_strategy(&StrategyField::<
+            BitField<T, E, OFFSET_, BIT_OFFSET, BITS>,
+        >::default())
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Generates fields whose type implements [Arbitrary] from a strategy for that type.
+pub trait ValueStrategyField {
+    /// Strategy for the bytes of the field
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>>;
+}
+
+impl<F: FieldCopyAccess + FieldKindInfo + 'static> ValueStrategyField for &StrategyField<F>
+where
+    F::HighLevelType: Arbitrary,
+{
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>> {
+        any::<F::HighLevelType>()
+            .prop_filter_map("Value can't be written to the field", |value| {
+                // Values of fields without a static size like varints get as much space as the data of open ended fields
+                let mut data = vec![0; F::OFFSET + F::SIZE.unwrap_or(MAX_OPEN_ENDED_LEN)];
+                F::try_write(&mut data, value).ok()?;
+                let mut data = data.split_off(F::OFFSET);
+                if let (None, FieldKind::Varint(_)) = (F::SIZE, F::KIND) {
+                    // The fields after a varint start after its last byte, which is the first one without the continuation bit
+                    let len = data
+                        .iter()
+                        .position(|byte| byte & 0x80 == 0)
+                        .map_or(data.len(), |index| index + 1);
+                    data.truncate(len);
+                }
+                Some(data)
+            })
+            .boxed()
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Generates all other fields from arbitrary bytes.
+pub trait BytesStrategyField {
+    /// Strategy for the bytes of the field
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>>;
+}
+
+impl<F: Field + FieldKindInfo> BytesStrategyField for StrategyField<F> {
+    fn field_strategy(&self) -> BoxedStrategy<Vec<u8>> {
+        match (F::SIZE, F::KIND) {
+            (Some(size), _) => vec(any::<u8>(), size).boxed(),
+            // The prefix is left zero, storage_strategy() stores the length of the data in it
+            (None, FieldKind::LengthPrefixed { prefix_size }) => {
+                vec(any::<u8>(), 0..=MAX_OPEN_ENDED_LEN)
+                    .prop_map(move |data| {
+                        let mut bytes = vec![0; prefix_size];
+                        bytes.extend(data);
+                        bytes
+                    })
+                    .boxed()
+            }
+            (None, _) => vec(any::<u8>(), 0..=MAX_OPEN_ENDED_LEN).boxed(),
+        }
+    }
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the `NestedView` types generated by [binary_layout!](crate::binary_layout!).
+pub trait LayoutStrategy {
+    /// Strategy for the storage of a view of the layout
+    fn storage_strategy() -> BoxedStrategy<Vec<u8>>;
+}
+
+/// Internal function. Don't use this in user code.
+/// Combines the strategies for the bytes of each field of the layout into a strategy for the storage of a view.
+pub fn storage_strategy(
+    layout: &'static LayoutInfo,
+    fields: Vec<BoxedStrategy<Vec<u8>>>,
+) -> BoxedStrategy<Vec<u8>> {
+    fields
+        .prop_map(move |fields| {
+            let mut storage = vec![0; layout.size.unwrap_or(0)];
+            for (field, mut bytes) in layout.fields.iter().zip(fields) {
+                if let FieldKind::LengthPrefixed { prefix_size } = field.kind {
+                    let len = ((bytes.len() - prefix_size) as u64).to_le_bytes();
+                    bytes[..prefix_size].copy_from_slice(&len[..prefix_size]);
+                    if layout.endianness.is_big_endian() {
+                        bytes[..prefix_size].reverse();
+                    }
+                }
+                // The fields before are already stored, so the LengthPrefixed and varint fields among them give the offset of the field
+                let offset = field.offset
+                    + layout
+                        .shift_in(field.offset, &storage)
+                        .expect("The fields before are stored");
+                let end = offset + bytes.len();
+                if storage.len() < end {
+                    storage.resize(end, 0);
+                }
+                storage[offset..end].copy_from_slice(&bytes);
+            }
+            storage
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::prelude::*;
+    use crate::LayoutAs;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Odd(u16);
+    impl LayoutAs<u16> for Odd {
+        type ReadError = ();
+        type WriteError = ();
+
+        fn try_read(v: u16) -> Result<Self, ()> {
+            if v % 2 == 1 {
+                Ok(Odd(v))
+            } else {
+                Err(())
+            }
+        }
+
+        fn try_write(v: Self) -> Result<u16, ()> {
+            Ok(v.0)
+        }
+    }
+    impl Arbitrary for Odd {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            any::<u16>().prop_map(|v| Odd(v | 1)).boxed()
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Unconstrained(u16);
+    impl LayoutAs<u16> for Unconstrained {
+        type ReadError = ();
+        type WriteError = ();
+
+        fn try_read(v: u16) -> Result<Self, ()> {
+            Ok(Unconstrained(v))
+        }
+
+        fn try_write(v: Self) -> Result<u16, ()> {
+            Ok(v.0)
+        }
+    }
+
+    binary_layout!(inner, LittleEndian, {
+        flag: bool as u8,
+        tail: [u8],
+    });
+    binary_layout!(packet, BigEndian, {
+        flag: bool as u8,
+        character: char as u32,
+        non_zero: core::num::NonZeroU32,
+        odd: Odd as u16,
+        unconstrained: Unconstrained as u16,
+        float: f64,
+        unit: (),
+        mac: [u8; 6],
+        samples: [u16; 3],
+        version: u8 bits(4),
+        more: bool bits(1),
+        length: u16 bits(11),
+        nested: inner::NestedView,
+    });
+    binary_layout!(varint, LittleEndian, {
+        kind: u8,
+        value: crate::Leb128<u32>,
+    });
+    binary_layout!(sized, BigEndian, {
+        value: u64,
+    });
+    binary_layout!(message, BigEndian, {
+        name: crate::LengthPrefixed<u16>,
+        flags: u8 bits(4),
+        level: u8 bits(4),
+        delta: crate::ZigZag<i64>,
+        crc: u32,
+    });
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn fields_are_valid(data in packet::strategy()) {
+            let view = packet::View::new(data);
+            prop_assert!(view.flag().try_read().is_ok());
+            prop_assert!(view.character().try_read().is_ok());
+            prop_assert!(view.non_zero().try_read().is_ok());
+            prop_assert!(view.odd().try_read().is_ok());
+            prop_assert!(view.nested().flag().try_read().is_ok());
+        }
+
+        #[test]
+        fn open_ended_layouts_have_at_least_their_fixed_size(data in packet::strategy()) {
+            prop_assert!(data.len() >= packet::nested::OFFSET + inner::tail::OFFSET);
+        }
+
+        #[test]
+        fn varints_are_valid(data in varint::strategy()) {
+            prop_assert!(varint::View::new(data).value().try_read().is_ok());
+        }
+
+        #[test]
+        fn fields_after_length_prefixed_and_varint_fields(data in message::strategy()) {
+            // The storage ends right after the last field
+            prop_assert_eq!(Ok(data.len()), message::LAYOUT.size_in(&data));
+            let view = message::View::new(data);
+            prop_assert!(view.name().try_data().is_ok());
+            prop_assert!(view.delta().try_read().is_ok());
+        }
+
+        #[test]
+        fn sized_layouts_have_their_size(data in sized::strategy()) {
+            prop_assert_eq!(sized::SIZE, Some(data.len()));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Support for the [rkyv](https://docs.rs/rkyv) crate, enabled by the `rkyv` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) implements [Archive](rkyv::Archive) and [Serialize](rkyv::Serialize)
+//! for `View<Vec<u8>>` of each layout, and [Deserialize](rkyv::Deserialize) into `View<Vec<u8>>` for the archived form.
+//! A view is archived as its storage bytes, i.e. as an [`ArchivedVec<u8>`](rkyv::vec::ArchivedVec),
+//! so archived views can be accessed without copying by creating a view over the archived bytes.
+//! This allows using layouts for wire formats within structs that are persisted with rkyv.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use rkyv::rancor::Error;
+//!
+//! binary_layout!(header, BigEndian, {
+//!   version: u8,
+//!   length: u16,
+//! });
+//!
+//! #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+//! struct Record {
+//!     id: u32,
+//!     header: header::View<Vec<u8>>,
+//! }
+//!
+//! let record = Record { id: 1, header: header::View::new(vec![1, 0, 5]) };
+//! let bytes = rkyv::to_bytes::<Error>(&record).unwrap();
+//!
+//! let archived = rkyv::access::<ArchivedRecord, Error>(&bytes).unwrap();
+//! let archived_header = header::View::new(archived.header.as_slice());
+//! assert_eq!(5, archived_header.length().read());
+//!
+//! let deserialized = rkyv::deserialize::<Record, Error>(archived).unwrap();
+//! assert_eq!(1, deserialized.id);
+//! assert_eq!(vec![1, 0, 5], deserialized.header.into_storage());
+//! ```
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+    use rkyv::vec::ArchivedVec;
+
+    use crate::prelude::*;
+
+    binary_layout!(packet, LittleEndian, {
+        value: u32,
+        payload: [u8],
+    });
+
+    #[test]
+    fn round_trip() {
+        let view = packet::View::new(vec![1, 2, 3, 4, 5, 6]);
+        let bytes = rkyv::to_bytes::<Error>(&view).unwrap();
+
+        let archived = rkyv::access::<ArchivedVec<u8>, Error>(&bytes).unwrap();
+        assert_eq!(&[1, 2, 3, 4, 5, 6], archived.as_slice());
+
+        let deserialized = rkyv::deserialize::<packet::View<Vec<u8>>, Error>(archived).unwrap();
+        assert_eq!(0x04030201, deserialized.value().read());
+        assert_eq!(&[5, 6], deserialized.payload());
+    }
+}
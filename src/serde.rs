@@ -0,0 +1,642 @@
+//! Support for the [serde](https://docs.rs/serde) crate, enabled by the `serde` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) implements [Serialize](serde::Serialize) for all views of each layout
+//! and [Deserialize](serde::Deserialize) for `View<Vec<u8>>`. Views are serialized as structs mapping the name of each field
+//! to its decoded value like [LayoutInfo::read_field](crate::LayoutInfo::read_field) reads it, i.e. numbers as numbers,
+//! byte arrays as bytes, arrays as sequences and nested layouts as nested structs. Fields using a custom type via
+//! [LayoutAs](crate::LayoutAs) are serialized as their underlying type. [Optional](crate::Optional) fields that
+//! the storage ends before are skipped.
+//!
+//! Deserializing encodes the field values into a new storage with the endianness of the layout. This needs a self-describing
+//! format like JSON, since fields are looked up by name. [Padding](crate::Padding) fields can be left out and are zeroed,
+//! [Magic](crate::Magic) fields can be left out and are set to their value, and leaving out an [Optional](crate::Optional)
+//! field ends the storage before it. All other fields are required.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//!
+//! binary_layout!(header, BigEndian, {
+//!   version: u8,
+//!   length: u16,
+//!   tag: [u8; 2],
+//! });
+//!
+//! let view = header::View::new(vec![1, 0, 5, b'o', b'k']);
+//! let json = serde_json::to_string(&view).unwrap();
+//! assert_eq!(r#"{"version":1,"length":5,"tag":[111,107]}"#, json);
+//!
+//! let view: header::View<Vec<u8>> = serde_json::from_str(r#"{"version":2,"length":258,"tag":[0,1]}"#).unwrap();
+//! assert_eq!(vec![2, 1, 2, 0, 1], view.into_storage());
+//! ```
+
+use core::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::endianness::EndianKind;
+use crate::fields::bit_field::write_bits;
+use crate::fields::primitive::{encode_bcd, encode_varint, zigzag_encode, MAX_VARINT_LEN};
+use crate::{FieldInfo, FieldKind, FieldValue, LayoutInfo, VarintEncoding};
+
+/// Internal function. Don't use this in user code.
+/// Serializes the fields of a view of the layout. Fails if the data is too short for the layout.
+pub fn serialize_view<S: Serializer>(
+    layout: &'static LayoutInfo,
+    data: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let size = layout.size_in(data).map_err(ser::Error::custom)?;
+    SerializeView {
+        layout,
+        data: &data[..size],
+    }
+    .serialize(serializer)
+}
+
+struct SerializeView<'a> {
+    layout: &'static LayoutInfo,
+    data: &'a [u8],
+}
+
+impl Serialize for SerializeView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let stored = |field: &&FieldInfo| self.layout.stored_range(field, self.data).is_some();
+        let len = self.layout.fields.iter().filter(stored).count();
+        let mut state = serializer.serialize_struct(self.layout.name, len)?;
+        for field in self.layout.fields {
+            if stored(&field) {
+                let value = self.layout.read_field(field, self.data);
+                state.serialize_field(field.name, &SerializeValue(value))?;
+            } else {
+                state.skip_field(field.name)?;
+            }
+        }
+        state.end()
+    }
+}
+
+struct SerializeValue<'a>(FieldValue<'a>);
+
+impl Serialize for SerializeValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            // Most formats support 64 bit integers only, so larger types are only used if needed
+            FieldValue::UnsignedInteger(value) => match u64::try_from(value) {
+                Ok(value) => serializer.serialize_u64(value),
+                Err(_) => serializer.serialize_u128(value),
+            },
+            FieldValue::SignedInteger(value) => match i64::try_from(value) {
+                Ok(value) => serializer.serialize_i64(value),
+                Err(_) => serializer.serialize_i128(value),
+            },
+            FieldValue::Float(value) => serializer.serialize_f64(value),
+            FieldValue::Unit => serializer.serialize_unit(),
+            FieldValue::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            FieldValue::Array(array) => serializer.collect_seq(array.iter().map(SerializeValue)),
+            FieldValue::Nested(layout, data) => {
+                SerializeView { layout, data }.serialize(serializer)
+            }
+        }
+    }
+}
+
+/// Internal function. Don't use this in user code.
+/// Deserializes the field values of the layout and returns a storage encoding them.
+pub fn deserialize_storage<'de, D: Deserializer<'de>>(
+    layout: &'static LayoutInfo,
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    deserializer.deserialize_map(StorageVisitor { layout })
+}
+
+struct StorageVisitor {
+    layout: &'static LayoutInfo,
+}
+
+impl<'de> Visitor<'de> for StorageVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the fields of a `{}` layout", self.layout.name)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Vec<u8>, A::Error> {
+        let fields = self.layout.fields;
+        let mut values: Vec<Option<Encoded>> = fields.iter().map(|_| None).collect();
+        while let Some(name) = map.next_key::<String>()? {
+            let index = fields
+                .iter()
+                .position(|field| field.name == name)
+                .ok_or_else(|| {
+                    de::Error::custom(format!(
+                        "unknown field `{}` of `{}`",
+                        name, self.layout.name
+                    ))
+                })?;
+            if values[index].is_some() {
+                return Err(de::Error::duplicate_field(fields[index].name));
+            }
+            values[index] = Some(map.next_value_seed(FieldSeed {
+                endianness: self.layout.endianness,
+                field: &fields[index],
+            })?);
+        }
+        build_storage(self.layout, values)
+    }
+}
+
+/// The encoded value of a field
+enum Encoded {
+    /// The bytes of the field, starting at its offset
+    Bytes(Vec<u8>),
+    /// The value of a [FieldKind::Bits] field
+    Bits(u128),
+}
+
+fn build_storage<E: de::Error>(
+    layout: &LayoutInfo,
+    values: Vec<Option<Encoded>>,
+) -> Result<Vec<u8>, E> {
+    let mut storage = vec![0; layout.min_size()];
+    let mut missing_optional: Option<&FieldInfo> = None;
+    for (field, value) in layout.fields.iter().zip(values) {
+        // Fields after a missing optional field can't be stored
+        if let (Some(missing), Some(_)) = (missing_optional, &value) {
+            return Err(de::Error::missing_field(missing.name));
+        }
+        // The fields before are already stored, so the LengthPrefixed and varint fields among them give the offset of the field
+        let offset = field.offset
+            + layout
+                .shift_in(field.offset, &storage)
+                .map_err(de::Error::custom)?;
+        match (value, field.kind) {
+            (Some(Encoded::Bytes(bytes)), _) => {
+                let end = offset + bytes.len();
+                if storage.len() < end {
+                    storage.resize(end, 0);
+                }
+                storage[offset..end].copy_from_slice(&bytes);
+            }
+            (Some(Encoded::Bits(value)), FieldKind::Bits { bit_offset, bits }) => {
+                let end = offset + field.size.unwrap_or(0);
+                if storage.len() < end {
+                    storage.resize(end, 0);
+                }
+                let big_endian = layout.endianness.is_big_endian();
+                write_bits(
+                    &mut storage[offset..end],
+                    big_endian,
+                    bit_offset,
+                    bits,
+                    value,
+                );
+            }
+            (Some(Encoded::Bits(_)), _) => unreachable!("Only bit fields are encoded as bits"),
+            (None, _) if field.optional => missing_optional = Some(field),
+            (None, FieldKind::Padding | FieldKind::Unit) => {}
+            (None, FieldKind::Magic { value }) => {
+                let size = field.size.unwrap_or(0);
+                let bytes = encode_uint(layout.endianness, value, size);
+                if storage.len() < offset + size {
+                    storage.resize(offset + size, 0);
+                }
+                storage[offset..offset + size].copy_from_slice(&bytes);
+            }
+            (None, _) => return Err(de::Error::missing_field(field.name)),
+        }
+    }
+    if let (None, Some(size)) = (missing_optional, layout.size) {
+        // Padding after the last field, e.g. of layouts with `pad_to`
+        storage.resize(size.max(storage.len()), 0);
+    }
+    Ok(storage)
+}
+
+struct FieldSeed {
+    endianness: EndianKind,
+    field: &'static FieldInfo,
+}
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+    type Value = Encoded;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Encoded, D::Error> {
+        match self.field.kind {
+            FieldKind::Bits { bits, .. } => {
+                let value = u128::deserialize(deserializer)?;
+                check_unsigned(value, bits)?;
+                Ok(Encoded::Bits(value))
+            }
+            kind => ValueSeed {
+                endianness: self.endianness,
+                kind,
+                size: self.field.size,
+            }
+            .deserialize(deserializer)
+            .map(Encoded::Bytes),
+        }
+    }
+}
+
+/// Deserializes a value of the given kind and encodes it into the bytes it's stored in
+struct ValueSeed {
+    endianness: EndianKind,
+    kind: FieldKind,
+    size: Option<usize>,
+}
+
+impl<'de> DeserializeSeed<'de> for ValueSeed {
+    type Value = Vec<u8>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let size = self.size.unwrap_or(0);
+        match self.kind {
+            FieldKind::UnsignedInteger => {
+                let value = u128::deserialize(deserializer)?;
+                check_unsigned(value, 8 * size)?;
+                Ok(encode_uint(self.endianness, value, size))
+            }
+            FieldKind::SignedInteger => {
+                let value = i128::deserialize(deserializer)?;
+                let bits = 8 * size as u32;
+                if bits < 128 && (value >> (bits - 1)) != 0 && (value >> (bits - 1)) != -1 {
+                    return Err(de::Error::custom(format!(
+                        "{} doesn't fit into {} bytes",
+                        value, size
+                    )));
+                }
+                Ok(encode_uint(self.endianness, value as u128, size))
+            }
+            FieldKind::Float => {
+                let value = f64::deserialize(deserializer)?;
+                let bits = match size {
+                    8 => u128::from(value.to_bits()),
+                    4 => u128::from((value as f32).to_bits()),
+                    #[cfg(feature = "half")]
+                    2 => u128::from(half::f16::from_f64(value).to_bits()),
+                    _ => return Err(de::Error::custom("unsupported float size")),
+                };
+                Ok(encode_uint(self.endianness, bits, size))
+            }
+            FieldKind::BFloat16 => {
+                let value = f64::deserialize(deserializer)?;
+                #[cfg(feature = "half")]
+                let bits = u128::from(half::bf16::from_f64(value).to_bits());
+                // BFloat16 fields need the `half` feature, so this can't happen
+                #[cfg(not(feature = "half"))]
+                let bits = u128::from((value as f32).to_bits() >> 16);
+                Ok(encode_uint(self.endianness, bits, size))
+            }
+            FieldKind::Unit => {
+                <()>::deserialize(deserializer)?;
+                Ok(Vec::new())
+            }
+            FieldKind::Bytes | FieldKind::Padding | FieldKind::BitArray { .. } => {
+                let bytes = deserializer.deserialize_bytes(BytesVisitor)?;
+                match self.size {
+                    Some(size) if bytes.len() != size => Err(de::Error::invalid_length(
+                        bytes.len(),
+                        &format!("{} bytes", size).as_str(),
+                    )),
+                    _ => Ok(bytes),
+                }
+            }
+            FieldKind::Magic { value: expected } => {
+                let value = u128::deserialize(deserializer)?;
+                if value != expected {
+                    return Err(de::Error::custom(format!(
+                        "expected magic value {:#x} but got {:#x}",
+                        expected, value
+                    )));
+                }
+                Ok(encode_uint(self.endianness, value, size))
+            }
+            FieldKind::Array {
+                element,
+                element_size,
+            } => {
+                let bytes = deserializer.deserialize_seq(ArrayVisitor {
+                    endianness: self.endianness,
+                    element,
+                    element_size,
+                })?;
+                match self.size {
+                    Some(size) if bytes.len() != size => Err(de::Error::invalid_length(
+                        bytes.len() / element_size.max(1),
+                        &format!("{} elements", size / element_size.max(1)).as_str(),
+                    )),
+                    _ => Ok(bytes),
+                }
+            }
+            FieldKind::LengthPrefixed { prefix_size } => {
+                let bytes = deserializer.deserialize_bytes(BytesVisitor)?;
+                let len = bytes.len() as u128;
+                check_unsigned(len, 8 * prefix_size)?;
+                let mut encoded = encode_uint(self.endianness, len, prefix_size);
+                encoded.extend_from_slice(&bytes);
+                Ok(encoded)
+            }
+            FieldKind::Bcd { digits } => {
+                let value = u128::deserialize(deserializer)?;
+                if u32::try_from(digits).map_or(false, |digits| value >= 10u128.pow(digits)) {
+                    return Err(de::Error::custom(format!(
+                        "{} has more than {} digits",
+                        value, digits
+                    )));
+                }
+                let mut encoded = vec![0; size];
+                encode_bcd(&mut encoded, self.endianness.is_big_endian(), value);
+                Ok(encoded)
+            }
+            FieldKind::Varint(encoding) => {
+                let value = match encoding {
+                    VarintEncoding::ZigZag => zigzag_encode(i128::deserialize(deserializer)?),
+                    _ => u128::deserialize(deserializer)?,
+                };
+                let mut encoded = vec![0; MAX_VARINT_LEN];
+                let len =
+                    encode_varint(encoding, &mut encoded, value).map_err(de::Error::custom)?;
+                encoded.truncate(len);
+                Ok(encoded)
+            }
+            FieldKind::Nested(layout) => {
+                let mut storage = deserialize_storage(layout, deserializer)?;
+                // Nested layouts with optional fields may end before the end of the field
+                if let Some(size) = self.size {
+                    storage.resize(size, 0);
+                }
+                Ok(storage)
+            }
+            FieldKind::Bits { .. } => unreachable!("Bit fields are deserialized by FieldSeed"),
+        }
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+struct ArrayVisitor {
+    endianness: EndianKind,
+    element: &'static FieldKind,
+    element_size: usize,
+}
+
+impl<'de> Visitor<'de> for ArrayVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(element) = seq.next_element_seed(ValueSeed {
+            endianness: self.endianness,
+            kind: *self.element,
+            size: Some(self.element_size),
+        })? {
+            bytes.extend_from_slice(&element);
+        }
+        Ok(bytes)
+    }
+}
+
+fn check_unsigned<E: de::Error>(value: u128, bits: usize) -> Result<(), E> {
+    if bits < 128 && value >> bits != 0 {
+        return Err(E::custom(format!(
+            "{} doesn't fit into {} bits",
+            value, bits
+        )));
+    }
+    Ok(())
+}
+
+/// Encodes the lower `size` bytes of the value in the given endianness
+fn encode_uint(endianness: EndianKind, value: u128, size: usize) -> Vec<u8> {
+    let mut bytes = value.to_le_bytes()[..size.min(16)].to_vec();
+    bytes.resize(size, 0);
+    if endianness.is_big_endian() {
+        bytes.reverse();
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{Bcd, Leb128, LengthPrefixed, Magic, Optional, Padding, ZigZag};
+
+    binary_layout!(inner, LittleEndian, {
+        value: u16,
+        flag: bool as u8,
+    });
+
+    binary_layout!(record, BigEndian, {
+        magic: Magic<u16, 0xcafe>,
+        id: u32,
+        delta: i16,
+        ratio: f32,
+        reserved: Padding<2>,
+        name: [u8; 3],
+        values: [i16; 2],
+        point: inner::NestedView,
+        serial: Bcd<u32, 4>,
+        extension: Optional<u16>,
+    });
+
+    binary_layout!(packet, LittleEndian, {
+        kind: u8,
+        nested: [inner::NestedView],
+    });
+
+    binary_layout!(varint, BigEndian, {
+        kind: u8,
+        offset: ZigZag<i64>,
+    });
+
+    binary_layout!(leb128, BigEndian, {
+        length: Leb128<u32>,
+    });
+
+    binary_layout!(message, BigEndian, {
+        kind: u8,
+        name: LengthPrefixed<u8>,
+        delta: ZigZag<i32>,
+        crc: u32,
+    });
+
+    fn record_storage() -> Vec<u8> {
+        vec![
+            0xca, 0xfe, 0, 0, 0x01, 0x02, 0xff, 0xfe, 0x3f, 0xc0, 0, 0, 0, 0, b'a', b'b', b'c', 0,
+            1, 0xff, 0xff, 0x05, 0, 1, 0x12, 0x34, 0, 7,
+        ]
+    }
+
+    #[test]
+    fn serialize() {
+        let view = record::View::new(record_storage());
+        assert_eq!(
+            serde_json::json!({
+                "magic": 0xcafe,
+                "id": 0x0102,
+                "delta": -2,
+                "ratio": 1.5,
+                "reserved": [0, 0],
+                "name": [97, 98, 99],
+                "values": [1, -1],
+                "point": {"value": 5, "flag": 1},
+                "serial": 1234,
+                "extension": 7,
+            }),
+            serde_json::to_value(&view).unwrap()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let view = record::View::new(record_storage());
+        let json = serde_json::to_string(&view).unwrap();
+        let deserialized: record::View<Vec<u8>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(view, deserialized);
+        assert_eq!(record_storage(), deserialized.into_storage());
+    }
+
+    #[test]
+    fn optional_fields_that_are_not_stored_are_skipped() {
+        let mut storage = record_storage();
+        storage.truncate(26);
+        let value = serde_json::to_value(record::View::new(&storage)).unwrap();
+        assert!(value.get("serial").is_some());
+        assert!(value.get("extension").is_none());
+
+        let deserialized: record::View<Vec<u8>> = serde_json::from_value(value).unwrap();
+        assert_eq!(storage, deserialized.into_storage());
+    }
+
+    #[test]
+    fn padding_and_magic_fields_can_be_left_out() {
+        let json = r#"{
+            "id": 1, "delta": 0, "ratio": 0, "name": [0, 0, 0], "values": [0, 0],
+            "point": {"value": 0, "flag": 0}, "serial": 0
+        }"#;
+        let view: record::View<Vec<u8>> = serde_json::from_str(json).unwrap();
+        assert_eq!(1, view.id().read());
+        let storage = view.into_storage();
+        assert_eq!(26, storage.len());
+        assert_eq!([0xca, 0xfe], storage[..2]);
+    }
+
+    #[test]
+    fn open_ended_layouts() {
+        let view = packet::View::new(vec![1, 2, 0, 0, 3, 0, 1]);
+        let json = serde_json::to_string(&view).unwrap();
+        assert_eq!(
+            r#"{"kind":1,"nested":[{"value":2,"flag":0},{"value":3,"flag":1}]}"#,
+            json
+        );
+        let deserialized: packet::View<Vec<u8>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(vec![1, 2, 0, 0, 3, 0, 1], deserialized.into_storage());
+    }
+
+    #[test]
+    fn varints() {
+        let view: varint::View<Vec<u8>> =
+            serde_json::from_str(r#"{"kind":1,"offset":-65}"#).unwrap();
+        assert_eq!(Ok(-65), view.offset().try_read());
+        assert_eq!(vec![1, 0x81, 0x01], view.into_storage());
+
+        let view: leb128::View<Vec<u8>> = serde_json::from_str(r#"{"length":300}"#).unwrap();
+        assert_eq!(r#"{"length":300}"#, serde_json::to_string(&view).unwrap());
+    }
+
+    #[test]
+    fn fields_after_length_prefixed_and_varint_fields() {
+        let storage = vec![1, 2, b'a', b'b', 0x81, 0x01, 0, 0, 0, 7];
+        let view = message::View::new(storage.clone());
+        let json = serde_json::to_string(&view).unwrap();
+        assert_eq!(r#"{"kind":1,"name":[97,98],"delta":-65,"crc":7}"#, json);
+        let deserialized: message::View<Vec<u8>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(storage, deserialized.into_storage());
+    }
+
+    #[test]
+    fn storage_too_short() {
+        let view = record::View::new(vec![0xca, 0xfe, 0]);
+        assert!(serde_json::to_string(&view).is_err());
+    }
+
+    #[test]
+    fn invalid_values() {
+        let errors = [
+            (r#"{"length":1,"length":2}"#, "duplicate field `length`"),
+            (r#"{"size":1}"#, "unknown field `size` of `leb128`"),
+            (r#"{}"#, "missing field `length`"),
+        ];
+        for (json, message) in errors {
+            let error = serde_json::from_str::<leb128::View<Vec<u8>>>(json).unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+
+        let json = record_json_with("\"name\": [1, 2]");
+        let error = serde_json::from_str::<record::View<Vec<u8>>>(&json).unwrap_err();
+        assert!(error.to_string().contains("expected 3 bytes"), "{}", error);
+        let json = record_json_with("\"id\": 4294967296");
+        let error = serde_json::from_str::<record::View<Vec<u8>>>(&json).unwrap_err();
+        assert!(
+            error.to_string().contains("doesn't fit into 32 bits"),
+            "{}",
+            error
+        );
+        let json = record_json_with("\"magic\": 1");
+        let error = serde_json::from_str::<record::View<Vec<u8>>>(&json).unwrap_err();
+        assert!(
+            error.to_string().contains("magic value 0xcafe"),
+            "{}",
+            error
+        );
+        let json = record_json_with("\"serial\": 12345");
+        let error = serde_json::from_str::<record::View<Vec<u8>>>(&json).unwrap_err();
+        assert!(
+            error.to_string().contains("more than 4 digits"),
+            "{}",
+            error
+        );
+    }
+
+    /// JSON of a valid record, with one field replaced
+    fn record_json_with(field: &str) -> String {
+        let mut value = serde_json::to_value(record::View::new(record_storage())).unwrap();
+        let replacement: serde_json::Value =
+            serde_json::from_str(&format!("{{{}}}", field)).unwrap();
+        for (name, field) in replacement.as_object().unwrap() {
+            value[name] = field.clone();
+        }
+        value.to_string()
+    }
+}
@@ -0,0 +1,115 @@
+//! Support for the [tokio](https://docs.rs/tokio) crate, enabled by the `tokio` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) generates `View::read_from_async(reader)` and `View::read_from_with_len_async(reader, len)`,
+//! which create a view owning the bytes read from a [tokio::io::AsyncRead], and `View::write_to_async(writer)`, which writes the bytes of
+//! the layout to a [tokio::io::AsyncWrite]. They work like their blocking counterparts `View::read_from()`, `View::read_from_with_len()`
+//! and `View::write_to()`, so headers can be parsed from sockets without buffering them manually.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//!
+//! binary_layout!(header, BigEndian, {
+//!   kind: u8,
+//!   length: u16,
+//! });
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! async fn main() -> std::io::Result<()> {
+//!     let mut socket: &[u8] = &[1, 0, 2, 0xab, 0xcd];
+//!     let head = header::View::read_from_async(&mut socket).await?;
+//!     let mut payload = vec![0; head.length().read() as usize];
+//!     tokio::io::AsyncReadExt::read_exact(&mut socket, &mut payload).await?;
+//!     assert_eq!(vec![0xab, 0xcd], payload);
+//!
+//!     let mut written = Vec::new();
+//!     head.write_to_async(&mut written).await?;
+//!     assert_eq!(vec![1, 0, 2], written);
+//!     Ok(())
+//! }
+//! ```
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::LayoutInfo;
+
+/// Internal function. Don't use this in user code.
+/// Reads `len` bytes from the reader into a new storage, failing like [AsyncReadExt::read_exact] if the reader ends before.
+pub async fn read_storage_async<R: AsyncRead + Unpin>(
+    mut reader: R,
+    len: usize,
+) -> std::io::Result<Vec<u8>> {
+    let mut storage = vec![0; len];
+    reader.read_exact(&mut storage).await?;
+    Ok(storage)
+}
+
+/// Internal function. Don't use this in user code.
+/// Writes the bytes of a layout in the data to the writer, like [write_layout](crate::internal::write_layout).
+pub async fn write_layout_async<W: AsyncWrite + Unpin>(
+    layout: &LayoutInfo,
+    data: &[u8],
+    mut writer: W,
+) -> std::io::Result<()> {
+    let size = layout
+        .size_in(data)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+    writer.write_all(&data[..size]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(sized, LittleEndian, {
+        first: u16,
+        second: u8,
+    });
+    binary_layout!(open_ended, BigEndian, {
+        header: sized::NestedView,
+        payload: [u8],
+    });
+
+    #[tokio::test]
+    async fn read_from_async() {
+        let mut reader: &[u8] = &[1, 2, 3, 4, 5];
+        let view = sized::View::read_from_async(&mut reader).await.unwrap();
+        assert_eq!(0x0201, view.first().read());
+        assert_eq!(3, view.second().read());
+        assert_eq!(&[4, 5], reader);
+
+        let error = sized::View::read_from_async(&mut reader).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error.kind());
+    }
+
+    #[tokio::test]
+    async fn read_from_with_len_async() {
+        let mut reader: &[u8] = &[1, 2, 3, 4, 5, 6];
+        let view = open_ended::View::read_from_with_len_async(&mut reader, 5)
+            .await
+            .unwrap();
+        assert_eq!(3, view.header().second().read());
+        assert_eq!(&[4, 5], view.payload());
+        assert_eq!(&[6], reader);
+    }
+
+    #[tokio::test]
+    async fn write_to_async() {
+        let mut written = Vec::new();
+        sized::View::new([1, 2, 3, 4])
+            .write_to_async(&mut written)
+            .await
+            .unwrap();
+        open_ended::View::new([5, 6, 7, 8])
+            .write_to_async(&mut written)
+            .await
+            .unwrap();
+        assert_eq!(vec![1, 2, 3, 5, 6, 7, 8], written);
+
+        let error = sized::View::new([1, 2])
+            .write_to_async(&mut written)
+            .await
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, error.kind());
+    }
+}
@@ -0,0 +1,327 @@
+//! Support for the [tokio-util](https://docs.rs/tokio-util) crate, enabled by the `tokio-util` feature.
+//!
+//! [binary_layout!](crate::binary_layout!) generates a `Codec` for each layout, which implements the [Decoder](tokio_util::codec::Decoder)
+//! and [Encoder](tokio_util::codec::Encoder) traits, so a `Framed` stream over a socket yields views of the layout. The decoder splits frames of
+//! [SIZE](crate::Layout) bytes off the input for layouts with a static size. For layouts ending with an open ended field, the frame
+//! ends after the number of bytes stored in the field declared with `length_of(...)` for it, see the length fields of
+//! [binary_layout!](crate::binary_layout!). Decoding open ended layouts without such a length field
+//! fails with [InvalidInput](std::io::ErrorKind::InvalidInput), since their frames could end anywhere. The frames of layouts with
+//! [LengthPrefixed](crate::LengthPrefixed) or varint fields end after the last field, whose offset depends on these fields.
+//!
+//! Like [LengthDelimitedCodec](tokio_util::codec::LengthDelimitedCodec), the decoder rejects frames longer than the maximum frame length of the
+//! codec with [InvalidData](std::io::ErrorKind::InvalidData) as soon as it knows their length, so a peer can't make it buffer arbitrary amounts of data.
+//! It defaults to [DEFAULT_MAX_FRAME_LENGTH] and can be changed with `Codec::with_max_frame_length()` or `Codec::set_max_frame_length()`.
+//!
+//! The decoded views own a [BytesMut] split off the input, so the frame isn't copied. The encoder writes the bytes of the layout in a view,
+//! including the data of an open ended field. If the layout has a length field for it, that's the number of bytes stored in the length field.
+//! Encoding fails with [InvalidInput](std::io::ErrorKind::InvalidInput) if the storage is too short for the frame.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use tokio_util::bytes::BytesMut;
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! binary_layout!(message, BigEndian, {
+//!   kind: u8,
+//!   length: u16 length_of(payload),
+//!   payload: [u8],
+//! });
+//!
+//! let mut input = BytesMut::from(&[1, 0, 2, 0xab, 0xcd, 2, 0][..]);
+//! let mut codec = message::Codec::new();
+//! let frame = codec.decode(&mut input).unwrap().unwrap();
+//! assert_eq!(&[0xab, 0xcd], frame.payload());
+//! // The second message isn't complete yet
+//! assert!(codec.decode(&mut input).unwrap().is_none());
+//!
+//! let mut output = BytesMut::new();
+//! codec.encode(frame, &mut output).unwrap();
+//! assert_eq!(&[1, 0, 2, 0xab, 0xcd], &output[..]);
+//!
+//! // Messages longer than the maximum frame length are rejected
+//! let mut input = BytesMut::from(&[1, 0xff, 0xff][..]);
+//! let error = message::Codec::with_max_frame_length(1024).decode(&mut input).unwrap_err();
+//! assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+//! ```
+
+use core::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+
+use tokio_util::bytes::BytesMut;
+
+use crate::layout_info::FieldKindInfo;
+use crate::{Field, FieldKind, FieldReadExt, LayoutInfo, ViewError};
+
+/// The maximum frame length of a `Codec` created with `Codec::new()`, 8 MiB like for
+/// [LengthDelimitedCodec](tokio_util::codec::LengthDelimitedCodec).
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+/// Internal function. Don't use this in user code.
+/// Splits the next frame off the input once it is complete. `frame_len` returns the length of the frame from the
+/// beginning of the input, or `None` if the input is too short to know it yet. Fails if the frame is longer than `max_frame_length`.
+pub fn decode_frame(
+    layout: &LayoutInfo,
+    src: &mut BytesMut,
+    max_frame_length: usize,
+    frame_len: impl FnOnce(&[u8]) -> std::io::Result<Option<usize>>,
+) -> std::io::Result<Option<BytesMut>> {
+    let len = match frame_len(src)? {
+        Some(len) => len.max(layout.min_size()),
+        None => return Ok(None),
+    };
+    if len > max_frame_length {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Frame of layout {} has {} bytes, which exceeds the maximum frame length of {} bytes",
+                layout.name, len, max_frame_length
+            ),
+        ));
+    }
+    if src.len() < len {
+        return Ok(None);
+    }
+    Ok(Some(src.split_to(len)))
+}
+
+/// Internal function. Don't use this in user code.
+/// Returns the length of a frame ending with the open ended field `T`, whose length is stored in the field `L`.
+pub fn length_field_frame_len<L, T>(storage: &[u8]) -> std::io::Result<Option<usize>>
+where
+    L: FieldReadExt + FieldKindInfo,
+    usize: TryFrom<L::HighLevelType>,
+    T: Field + FieldKindInfo,
+{
+    // The offsets of fields after LengthPrefixed or varint fields are only known once the input contains these
+    let (length_offset, target_offset) = match (L::offset_in(storage), T::offset_in(storage)) {
+        (Some(length_offset), Some(target_offset)) => (length_offset, target_offset),
+        _ => return Ok(None),
+    };
+    let length_end = length_offset + L::SIZE.expect("Length fields must have a fixed size");
+    if storage.len() < length_end {
+        return Ok(None);
+    }
+    usize::try_from(L::read(storage))
+        .ok()
+        .and_then(|length| target_offset.checked_add(length))
+        .map(Some)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "The length field stores an invalid length",
+            )
+        })
+}
+
+/// Internal function. Don't use this in user code.
+/// Returns the length of a frame of a layout without a static size and without a length field, which is known from the input
+/// if the layout has [LengthPrefixed](crate::LengthPrefixed) or varint fields and doesn't end with an open ended field.
+pub fn dynamic_frame_len(layout: &LayoutInfo, storage: &[u8]) -> std::io::Result<Option<usize>> {
+    let known_end = match layout.fields.last() {
+        Some(last) => {
+            last.size.is_some()
+                || matches!(
+                    last.kind,
+                    FieldKind::LengthPrefixed { .. } | FieldKind::Varint(_)
+                )
+        }
+        None => false,
+    };
+    if !known_end {
+        return Err(unknown_frame_len(layout));
+    }
+    match layout.size_in(storage) {
+        Ok(size) => Ok(Some(size)),
+        Err(ViewError::StorageTooShort { .. }) => Ok(None),
+        Err(error) => Err(Error::new(ErrorKind::InvalidData, error)),
+    }
+}
+
+/// The error of decoding a layout whose frame length isn't known.
+fn unknown_frame_len(layout: &LayoutInfo) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "Layout {} ends with an open ended field without a length field, so its frames can't be decoded",
+            layout.name
+        ),
+    )
+}
+
+/// Internal function. Don't use this in user code.
+/// Appends the bytes of a layout in the data to the output, like [write_layout](crate::internal::write_layout).
+/// `frame_len` returns the length of the frame stored in the length field of the layout, or `None` if it doesn't have one.
+pub fn encode_frame(
+    layout: &LayoutInfo,
+    data: &[u8],
+    dst: &mut BytesMut,
+    frame_len: impl FnOnce(&[u8]) -> std::io::Result<Option<usize>>,
+) -> std::io::Result<()> {
+    let size = layout
+        .size_in(data)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, error))?;
+    // The data is long enough for the length field, since size_in() checked the minimum size of the layout
+    let size = match frame_len(data)? {
+        Some(len) if len > data.len() => {
+            let error = ViewError::StorageTooShort {
+                expected: len,
+                actual: data.len(),
+            };
+            return Err(Error::new(ErrorKind::InvalidInput, error));
+        }
+        Some(len) => len,
+        None => size,
+    };
+    dst.extend_from_slice(&data[..size]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use tokio_util::bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    use crate::prelude::*;
+
+    binary_layout!(sized, LittleEndian, {
+        first: u16,
+        second: u8,
+    });
+    binary_layout!(message, BigEndian, {
+        kind: u8,
+        length: u16 length_of(payload),
+        payload: [u8],
+    });
+    binary_layout!(open_ended, BigEndian, {
+        kind: u8,
+        payload: [u8],
+    });
+    binary_layout!(named, BigEndian, {
+        kind: u8,
+        name: crate::LengthPrefixed<u8>,
+        crc: u32,
+    });
+
+    #[test]
+    fn decode_sized_layout() {
+        let mut input = BytesMut::from(&[1, 2, 3, 4, 5][..]);
+        let frame = sized::Codec::new().decode(&mut input).unwrap().unwrap();
+        assert_eq!(0x0201, frame.first().read());
+        assert_eq!(3, frame.second().read());
+        assert_eq!(&[4, 5], &input[..]);
+        assert!(sized::Codec::new().decode(&mut input).unwrap().is_none());
+        input.extend_from_slice(&[6]);
+        let frame = sized::Codec::new().decode(&mut input).unwrap().unwrap();
+        assert_eq!(6, frame.second().read());
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn decode_length_prefixed_layout() {
+        let mut input = BytesMut::from(&[7, 0][..]);
+        assert!(message::Codec::new().decode(&mut input).unwrap().is_none());
+        input.extend_from_slice(&[3, 0xa, 0xb]);
+        assert!(message::Codec::new().decode(&mut input).unwrap().is_none());
+        input.extend_from_slice(&[0xc, 8, 0, 0]);
+        let frame = message::Codec::new().decode(&mut input).unwrap().unwrap();
+        assert_eq!(7, frame.kind().read());
+        assert_eq!(&[0xa, 0xb, 0xc], frame.payload());
+        let frame = message::Codec::new().decode(&mut input).unwrap().unwrap();
+        assert_eq!(8, frame.kind().read());
+        assert!(frame.payload().is_empty());
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn decode_open_ended_layout_without_length_field() {
+        let mut input = BytesMut::from(&[1, 2, 3][..]);
+        let error = open_ended::Codec::new().decode(&mut input).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, error.kind());
+        assert_eq!(3, input.len());
+    }
+
+    #[test]
+    fn decode_layout_with_length_prefixed_field() {
+        let mut input = BytesMut::from(&[1, 2, b'a', b'b', 0, 0, 0, 7, 2, 5][..]);
+        let frame = named::Codec::new().decode(&mut input).unwrap().unwrap();
+        assert_eq!(1, frame.kind().read());
+        assert_eq!(Ok(&b"ab"[..]), frame.name().try_data());
+        assert_eq!(7, frame.crc().read());
+        // The second message isn't complete yet
+        assert_eq!(&[2, 5], &input[..]);
+        assert!(named::Codec::new().decode(&mut input).unwrap().is_none());
+        assert_eq!(2, input.len());
+    }
+
+    #[test]
+    fn encode() {
+        let mut output = BytesMut::new();
+        sized::Codec::new()
+            .encode(sized::View::new([1, 2, 3, 4]), &mut output)
+            .unwrap();
+        message::Codec::new()
+            .encode(message::View::new(vec![5, 0, 1, 6]), &mut output)
+            .unwrap();
+        assert_eq!(&[1, 2, 3, 5, 0, 1, 6], &output[..]);
+
+        let error = sized::Codec::new()
+            .encode(sized::View::new([1, 2]), &mut output)
+            .unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, error.kind());
+    }
+
+    #[test]
+    fn decode_frame_longer_than_max_frame_length() {
+        let mut codec = message::Codec::with_max_frame_length(5);
+        assert_eq!(5, codec.max_frame_length());
+        let mut input = BytesMut::from(&[1, 0, 2, 0xa, 0xb, 2, 0, 3][..]);
+        let frame = codec.decode(&mut input).unwrap().unwrap();
+        assert_eq!(&[0xa, 0xb], frame.payload());
+        // The frame is rejected before its data arrives and without reserving space for it
+        let error = codec.decode(&mut input).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+        assert!(input.capacity() < 0x100);
+
+        codec.set_max_frame_length(6);
+        let frame = codec.decode(&mut input).unwrap();
+        assert!(frame.is_none());
+        input.extend_from_slice(&[0xc, 0xd, 0xe]);
+        let frame = codec.decode(&mut input).unwrap().unwrap();
+        assert_eq!(&[0xc, 0xd, 0xe], frame.payload());
+
+        let mut input = BytesMut::from(&[1, 0xff, 0xff][..]);
+        assert!(message::Codec::new().decode(&mut input).unwrap().is_none());
+        assert_eq!(
+            crate::tokio_util::DEFAULT_MAX_FRAME_LENGTH,
+            message::Codec::default().max_frame_length()
+        );
+        assert!(sized::Codec::with_max_frame_length(2)
+            .decode(&mut BytesMut::from(&[1, 2, 3][..]))
+            .is_err());
+    }
+
+    #[test]
+    fn encode_uses_length_field() {
+        let mut output = BytesMut::new();
+        // Only the bytes stored in the length field are written
+        message::Codec::new()
+            .encode(message::View::new(vec![5, 0, 1, 6, 7, 8]), &mut output)
+            .unwrap();
+        assert_eq!(&[5, 0, 1, 6], &output[..]);
+
+        // The storage is shorter than the length field says
+        let error = message::Codec::new()
+            .encode(message::View::new(vec![5, 0, 3, 6, 7]), &mut output)
+            .unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, error.kind());
+        assert_eq!(4, output.len());
+
+        // Open ended layouts without a length field write all of their data
+        open_ended::Codec::new()
+            .encode(open_ended::View::new(vec![1, 2, 3]), &mut output)
+            .unwrap();
+        assert_eq!(&[5, 0, 1, 6, 1, 2, 3], &output[..]);
+    }
+}
@@ -0,0 +1,313 @@
+//! Support for the [uom](https://docs.rs/uom) crate, enabled by the `uom` feature.
+//!
+//! Fields of primitive number types can be declared as a quantity stored in a given unit,
+//! e.g. `voltage: u16 quantity(ElectricPotential in millivolt)`. Such fields read and write
+//! `uom::si::f64::ElectricPotential` values instead of plain numbers, so physical units are checked by
+//! the type system where values enter or leave the layout. The quantity and unit names are looked up in
+//! `uom::si::f64` and `uom::si::<<quantity>>` respectively.
+//!
+//! Reading can't fail. Writing converts the quantity into the stored unit, rounding to the nearest integer
+//! for integer fields, and fails with [QuantityOutOfRangeError] if the value doesn't fit into the field,
+//! so it is only available through `try_write`.
+//!
+//! # Example
+//! ```
+//! use binary_layout::prelude::*;
+//! use uom::si::{electric_potential::{millivolt, volt}, f64::ElectricPotential};
+//!
+//! binary_layout!(sensor, LittleEndian, {
+//!   id: u8,
+//!   voltage: u16 quantity(ElectricPotential in millivolt),
+//! });
+//!
+//! let mut view = sensor::View::new([0; 3]);
+//! view.voltage_mut().try_write(ElectricPotential::new::<volt>(3.3)).unwrap();
+//! assert_eq!([0, 0xe4, 0x0c], view.into_storage());
+//!
+//! let view = sensor::View::new([0, 0xe4, 0x0c]);
+//! assert_eq!(3300.0, view.voltage().read().get::<millivolt>().round());
+//! assert!(view.voltage().read() > ElectricPotential::new::<volt>(3.0));
+//! ```
+
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use thiserror::Error;
+
+use crate::fields::{
+    primitive::{FieldCopyAccess, FieldView},
+    Field, StorageIntoFieldView, StorageToFieldView,
+};
+use crate::layout_info::{FieldKind, FieldKindInfo};
+use crate::WrappedFieldError;
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the marker type that [binary_layout!](crate::binary_layout!) generates for each quantity field,
+/// converting between the quantity and its value in the stored unit.
+pub trait QuantityConversion {
+    /// The `uom` quantity the field reads and writes
+    type Quantity;
+
+    /// Create the quantity from its value in the stored unit
+    fn from_stored(value: f64) -> Self::Quantity;
+
+    /// Get the value of the quantity in the stored unit
+    fn to_stored(quantity: Self::Quantity) -> f64;
+}
+
+/// Internal trait. Don't use this in user code.
+/// Implemented by the primitive number types that can store quantities.
+pub trait QuantityStorage: Sized {
+    /// Convert the stored number into a float
+    fn to_f64(self) -> f64;
+
+    /// Convert a float into the stored number, or return [None] if it is out of range
+    fn from_f64(value: f64) -> Option<Self>;
+}
+
+macro_rules! impl_quantity_storage_int {
+    ($($type: ty),*) => {
+        $(
+            impl QuantityStorage for $type {
+                #[inline(always)]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline(always)]
+                fn from_f64(value: f64) -> Option<Self> {
+                    let value = value.round();
+                    if value >= <$type>::MIN as f64 && value <= <$type>::MAX as f64 {
+                        Some(value as $type)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_quantity_storage_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl QuantityStorage for f32 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Option<Self> {
+        let result = value as f32;
+        if result.is_finite() || !value.is_finite() {
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+impl QuantityStorage for f64 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Option<Self> {
+        Some(value)
+    }
+}
+
+/// This error is thrown when writing a quantity into a field whose stored type can't represent it in the stored unit.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+#[error("The quantity is {value} in the stored unit, which is out of range for the field")]
+pub struct QuantityOutOfRangeError {
+    /// Value of the quantity in the stored unit
+    pub value: f64,
+}
+
+/// A [QuantityField] is a [Field] storing a `uom` quantity as a primitive number in a fixed unit,
+/// see [the module documentation](crate::uom). [binary_layout!](crate::binary_layout!) uses it for fields
+/// declared like `voltage: u16 quantity(ElectricPotential in millivolt)`.
+pub struct QuantityField<C: QuantityConversion, F: Field> {
+    _p1: PhantomData<C>,
+    _p2: PhantomData<F>,
+}
+
+impl<C: QuantityConversion, F: Field> Field for QuantityField<C, F> {
+    /// See [Field::Endian]
+    type Endian = F::Endian;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = F::OFFSET;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = F::SIZE;
+}
+
+impl<C: QuantityConversion, F: Field + FieldKindInfo> FieldKindInfo for QuantityField<C, F> {
+    const KIND: FieldKind = F::KIND;
+}
+
+impl<'a, C: QuantityConversion, F: FieldCopyAccess + StorageToFieldView<&'a [u8]>>
+    StorageToFieldView<&'a [u8]> for QuantityField<C, F>
+where
+    F::HighLevelType: QuantityStorage,
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, C: QuantityConversion, F: FieldCopyAccess + StorageToFieldView<&'a mut [u8]>>
+    StorageToFieldView<&'a mut [u8]> for QuantityField<C, F>
+where
+    F::HighLevelType: QuantityStorage,
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, C: QuantityConversion, F: FieldCopyAccess + StorageIntoFieldView<S>>
+    StorageIntoFieldView<S> for QuantityField<C, F>
+where
+    F::HighLevelType: QuantityStorage,
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<C: QuantityConversion, F: FieldCopyAccess> FieldCopyAccess for QuantityField<C, F>
+where
+    F::HighLevelType: QuantityStorage,
+{
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = WrappedFieldError<F::ReadError, Infallible>;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = WrappedFieldError<F::WriteError, QuantityOutOfRangeError>;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = C::Quantity;
+
+    /// Read the field from a given data region, assuming the defined layout, using the [Field] API.
+    #[inline(always)]
+    fn try_read(storage: &[u8]) -> Result<Self::HighLevelType, Self::ReadError> {
+        let value = F::try_read(storage).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        Ok(C::from_stored(value.to_f64()))
+    }
+
+    /// Write the field to a given data region, assuming the defined layout, using the [Field] API.
+    #[inline(always)]
+    fn try_write(storage: &mut [u8], v: Self::HighLevelType) -> Result<(), Self::WriteError> {
+        let value = C::to_stored(v);
+        let value = F::HighLevelType::from_f64(value).ok_or(WrappedFieldError::LayoutAsError(
+            QuantityOutOfRangeError { value },
+        ))?;
+        F::try_write(storage, value).map_err(WrappedFieldError::PrimitiveAccessError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)]
+    use uom::si::electric_potential::{millivolt, volt};
+    use uom::si::f64::{ElectricPotential, Length, ThermodynamicTemperature};
+    use uom::si::length::{kilometer, meter};
+    use uom::si::thermodynamic_temperature::{degree_celsius, kelvin};
+
+    use super::QuantityOutOfRangeError;
+    use crate::prelude::*;
+    use crate::{FieldKind, WrappedFieldError};
+
+    binary_layout!(sensor, BigEndian, round_trip_tests, {
+        voltage: u16 quantity(ElectricPotential in millivolt),
+        offset: i8 quantity(ElectricPotential in volt),
+        distance: f32 quantity(Length in kilometer),
+        temperature: i16 quantity(ThermodynamicTemperature in degree_celsius),
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(0, sensor::voltage::OFFSET);
+        assert_eq!(Some(2), sensor::voltage::SIZE);
+        assert_eq!(2, sensor::offset::OFFSET);
+        assert_eq!(3, sensor::distance::OFFSET);
+        assert_eq!(7, sensor::temperature::OFFSET);
+        assert_eq!(Some(9), sensor::SIZE);
+        assert_eq!(FieldKind::Float, sensor::Fields::distance.kind);
+        assert_eq!(
+            "ElectricPotential in millivolt as u16",
+            sensor::Fields::voltage.type_name
+        );
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn read() {
+        let view = sensor::View::new([0x0c, 0xe4, 0xfe, 0x3f, 0xc0, 0, 0, 0xff, 0xf6]);
+        assert_close(3300.0, view.voltage().read().get::<millivolt>());
+        assert_close(3.3, view.voltage().read().get::<volt>());
+        assert_close(-2.0, view.offset().read().get::<volt>());
+        assert_close(1.5, view.distance().read().get::<kilometer>());
+        assert_close(1500.0, view.distance().read().get::<meter>());
+        assert_close(-10.0, view.temperature().read().get::<degree_celsius>());
+        assert_close(263.15, view.temperature().read().get::<kelvin>());
+    }
+
+    #[test]
+    fn write() {
+        let mut view = sensor::View::new([0; 9]);
+        view.voltage_mut()
+            .try_write(ElectricPotential::new::<volt>(3.3))
+            .unwrap();
+        view.offset_mut()
+            .try_write(ElectricPotential::new::<millivolt>(-2000.0))
+            .unwrap();
+        view.distance_mut()
+            .try_write(Length::new::<meter>(1500.0))
+            .unwrap();
+        view.temperature_mut()
+            .try_write(ThermodynamicTemperature::new::<kelvin>(263.15))
+            .unwrap();
+        assert_eq!(
+            [0x0c, 0xe4, 0xfe, 0x3f, 0xc0, 0, 0, 0xff, 0xf6],
+            view.into_storage()
+        );
+    }
+
+    #[test]
+    fn write_out_of_range() {
+        let mut view = sensor::View::new([0; 9]);
+        assert!(matches!(
+            view.voltage_mut()
+                .try_write(ElectricPotential::new::<volt>(70.0)),
+            Err(WrappedFieldError::LayoutAsError(QuantityOutOfRangeError { value })) if value == 70000.0
+        ));
+        assert!(matches!(
+            view.offset_mut()
+                .try_write(ElectricPotential::new::<volt>(-129.0)),
+            Err(WrappedFieldError::LayoutAsError(_))
+        ));
+        assert!(matches!(
+            view.distance_mut().try_write(Length::new::<meter>(1e300)),
+            Err(WrappedFieldError::LayoutAsError(_))
+        ));
+        assert_eq!([0; 9], view.into_storage());
+    }
+}
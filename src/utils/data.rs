@@ -77,6 +77,30 @@ impl<S> Data<S> {
             },
         }
     }
+
+    /// Return the range of the enclosing storage that this [Data] instance (semantically) owns.
+    #[inline(always)]
+    pub fn region(&self) -> Range<usize> {
+        self.region.clone()
+    }
+
+    /// Return the enclosing storage, including any bytes outside of the subregion.
+    #[inline(always)]
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    /// Return the enclosing storage with write access, including any bytes outside of the subregion.
+    #[inline(always)]
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Destroy the [Data] instance and return the enclosing storage, including any bytes outside of the subregion.
+    #[inline(always)]
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
 }
 
 impl<S> From<S> for Data<S>
@@ -371,6 +395,19 @@ mod tests {
         data.into_subregion(..101);
     }
 
+    #[test]
+    fn given_subrangedata_when_accessinginner_then_returnsenclosingstorage() {
+        let data: Data<_> = data_region(1024, 0).into();
+        let mut data = data.into_subregion(10..100).into_subregion(5..);
+        assert_eq!(15..100, data.region());
+        assert_eq!(&data_region(1024, 0), data.inner());
+        data.inner_mut()[0] = 1;
+        data.as_mut()[0] = 2;
+        let inner = data.into_inner();
+        assert_eq!(1, inner[0]);
+        assert_eq!(2, inner[15]);
+    }
+
     #[test]
     fn given_fullrangedata_when_tryingtogrowstartbeyondend_then_returnszerolengthrange() {
         let data: Data<_> = data_region(1024, 0).into();
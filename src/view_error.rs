@@ -0,0 +1,231 @@
+use core::fmt;
+use core::ops::Range;
+
+use crate::{Data, FieldInfo, LayoutInfo};
+
+/// This error is thrown by the `View::try_new` constructor that [binary_layout!](crate::binary_layout!) generates
+/// when the storage isn't valid for the layout, and by the accessors that [layout_variants!](crate::layout_variants!) generates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ViewError {
+    /// The storage is too short for the fields of the layout.
+    StorageTooShort {
+        /// Number of bytes the layout needs at least
+        expected: usize,
+        /// Number of bytes in the storage
+        actual: usize,
+    },
+    /// The version field of the layout has a value outside of the supported versions.
+    UnsupportedVersion {
+        /// Name of the version field
+        field: &'static str,
+        /// Value of the version field
+        version: i128,
+    },
+    /// A [Magic](crate::Magic) field of the layout or of a nested layout doesn't store its value.
+    InvalidMagic {
+        /// Name of the magic field
+        field: &'static str,
+        /// The value the field has to store
+        expected: u128,
+        /// The value the field stores
+        actual: u128,
+    },
+    /// The tag field of a [layout_variants!](crate::layout_variants!) enum has a value that doesn't select any of its layouts.
+    UnknownVariant {
+        /// Name of the tag field
+        field: &'static str,
+        /// Value of the tag field
+        value: i128,
+    },
+}
+
+impl fmt::Display for ViewError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViewError::StorageTooShort { expected, actual } => write!(
+                fmt,
+                "The layout needs at least {} bytes but the storage only has {} bytes",
+                expected, actual
+            ),
+            ViewError::UnsupportedVersion { field, version } => {
+                write!(fmt, "Unsupported version {} in field `{}`", version, field)
+            }
+            ViewError::InvalidMagic {
+                field,
+                expected,
+                actual,
+            } => write!(
+                fmt,
+                "Invalid magic number {:#x} in field `{}`, expected {:#x}",
+                actual, field, expected
+            ),
+            ViewError::UnknownVariant { field, value } => {
+                write!(fmt, "Unknown variant {} in field `{}`", value, field)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ViewError {}
+
+/// Internal function. Don't use this in user code.
+/// Checks that the storage is long enough for the layout, including the data of a length prefixed field at its end.
+pub fn check_storage_size(layout: &LayoutInfo, storage: &[u8]) -> Result<(), ViewError> {
+    layout.size_in(storage).map(drop)
+}
+
+/// Internal function. Don't use this in user code.
+/// Checks that the storage is long enough for the layout and that all magic fields store their value.
+pub fn validate(layout: &LayoutInfo, storage: &[u8]) -> Result<(), ViewError> {
+    check_storage_size(layout, storage)?;
+    layout.check_magics(storage)
+}
+
+/// Internal function. Don't use this in user code.
+/// Returns the subregion of the storage starting at the offset, after checking that it is long enough for the layout.
+pub fn storage_at<S: AsRef<[u8]>>(
+    layout: &LayoutInfo,
+    storage: S,
+    offset: usize,
+) -> Result<Data<S>, ViewError> {
+    let expected = offset.saturating_add(layout.min_size());
+    let actual = storage.as_ref().len();
+    if actual < expected {
+        return Err(ViewError::StorageTooShort { expected, actual });
+    }
+    Ok(Data::from(storage).into_subregion(offset..))
+}
+
+/// Internal function. Don't use this in user code.
+/// Checks that the storage is long enough for the layout and returns the byte range of the field in it.
+pub fn field_region(
+    layout: &LayoutInfo,
+    field: &FieldInfo,
+    storage: &[u8],
+) -> Result<Range<usize>, ViewError> {
+    check_storage_size(layout, storage)?;
+    let start = field.offset + layout.shift_in(field.offset, storage)?;
+    let end = field.size.map_or(storage.len(), |size| start + size);
+    Ok(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ViewError;
+    use crate::prelude::*;
+
+    binary_layout!(packet, BigEndian, {
+        version: u8 in 1..=3,
+        length: u16,
+        payload: [u8],
+    });
+    binary_layout!(unversioned, LittleEndian, {
+        value: u32,
+    });
+    binary_layout!(signed, LittleEndian, round_trip_tests, {
+        header: unversioned::NestedView,
+        version: i16 in -1..=1,
+    });
+
+    #[test]
+    fn supported_versions() {
+        assert_eq!(1..=3, packet::SUPPORTED_VERSIONS);
+        assert_eq!(1, packet::MIN_VERSION);
+        assert_eq!(3, packet::MAX_VERSION);
+        assert_eq!(-1..=1, signed::SUPPORTED_VERSIONS);
+    }
+
+    #[test]
+    fn try_new_accepts_supported_versions() {
+        for version in 1..=3 {
+            let view = packet::View::try_new([version, 0, 5, 6]).unwrap();
+            assert_eq!(version, view.version().read());
+            assert_eq!(&[6], view.payload());
+        }
+        let view = signed::View::try_new([0, 0, 0, 0, 0xff, 0xff]).unwrap();
+        assert_eq!(-1, view.version().read());
+    }
+
+    #[test]
+    fn try_new_rejects_unsupported_versions() {
+        for version in [0, 4, 255] {
+            assert_eq!(
+                Err(ViewError::UnsupportedVersion {
+                    field: "version",
+                    version: version.into(),
+                }),
+                packet::View::try_new([version, 0, 5]).map(|view| view.into_storage())
+            );
+        }
+        assert_eq!(
+            Err(ViewError::UnsupportedVersion {
+                field: "version",
+                version: -2,
+            }),
+            signed::View::try_new([0, 0, 0, 0, 0xfe, 0xff]).map(|view| view.into_storage())
+        );
+    }
+
+    #[test]
+    fn new_at() {
+        let mut storage = [9, 9, 2, 0, 1, 7, 8];
+        let mut view = packet::View::new_at(&mut storage[..], 2).unwrap();
+        assert_eq!(2, view.version().read());
+        assert_eq!(&[7, 8], view.payload());
+        view.length_mut().write(3);
+        let data = view.into_storage();
+        assert_eq!(2..7, data.region());
+        assert_eq!(&[9, 9], &data.inner()[..2]);
+        assert_eq!([9, 9, 2, 0, 3, 7, 8], storage);
+
+        let view = unversioned::View::new_at([0, 1, 0, 0, 0], 1).unwrap();
+        assert_eq!(1, view.value().read());
+        assert_eq!([0, 1, 0, 0, 0], view.into_storage().into_inner());
+    }
+
+    #[test]
+    fn new_at_rejects_invalid_storage() {
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 6,
+                actual: 5,
+            }),
+            unversioned::View::new_at([0; 5], 2).map(|view| view.into_storage().into_inner())
+        );
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 10,
+                actual: 5,
+            }),
+            packet::View::new_at([0; 5], 7).map(|view| view.into_storage().into_inner())
+        );
+        assert_eq!(
+            Err(ViewError::UnsupportedVersion {
+                field: "version",
+                version: 5,
+            }),
+            packet::View::new_at([0, 5, 0, 0], 1).map(|view| view.into_storage().into_inner())
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_short_storage() {
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 3,
+                actual: 2,
+            }),
+            packet::View::try_new([1, 0]).map(|view| view.into_storage())
+        );
+        assert_eq!(
+            Err(ViewError::StorageTooShort {
+                expected: 4,
+                actual: 3,
+            }),
+            unversioned::View::try_new([1, 2, 3]).map(|view| view.into_storage())
+        );
+        assert!(unversioned::View::try_new([1, 2, 3, 4, 5]).is_ok());
+    }
+}
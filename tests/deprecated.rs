@@ -13,3 +13,20 @@ define_layout!(withslice, LittleEndian, {
     fifth: NonZeroI32,
     sixth: [u8],
 });
+
+define_layout!(floats, LittleEndian, {
+    first: f32,
+    second: f64,
+});
+
+#[test]
+fn float_fields() {
+    let mut view = floats::View::new([0; 12]);
+    view.first_mut().write(-1.5);
+    view.second_mut().write(1e300);
+    assert_eq!(-1.5, view.first().read());
+    assert_eq!(1e300, view.second().read());
+    let storage = view.into_storage();
+    assert_eq!((-1.5f32).to_le_bytes(), storage[0..4]);
+    assert_eq!(1e300f64.to_le_bytes(), storage[4..12]);
+}
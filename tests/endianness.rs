@@ -72,3 +72,62 @@ fn test_native_endian() {
         i64::from_ne_bytes((&storage[2..10]).try_into().unwrap())
     );
 }
+
+#[test]
+fn test_float_endianness() {
+    binary_layout!(little, LittleEndian, {
+        field1: f32,
+        field2: f64,
+    });
+    binary_layout!(big, BigEndian, {
+        field1: f32,
+        field2: f64,
+    });
+
+    let mut storage = data_region(1024, 0);
+    let mut view = little::View::new(&mut storage);
+    view.field1_mut().write(3.25);
+    view.field2_mut().write(-1e-300);
+    assert_eq!(3.25f32.to_bits().to_le_bytes(), storage[0..4]);
+    assert_eq!((-1e-300f64).to_bits().to_le_bytes(), storage[4..12]);
+
+    let mut view = big::View::new(&mut storage);
+    view.field1_mut().write(3.25);
+    view.field2_mut().write(-1e-300);
+    assert_eq!(3.25f32.to_bits().to_be_bytes(), storage[0..4]);
+    assert_eq!((-1e-300f64).to_bits().to_be_bytes(), storage[4..12]);
+    assert_eq!(3.25, big::View::new(&storage).field1().read());
+    assert_eq!(-1e-300, big::View::new(&storage).field2().read());
+}
+
+#[test]
+fn test_128bit_endianness() {
+    binary_layout!(little, LittleEndian, {
+        counter: u128,
+        nonce: i128,
+    });
+    binary_layout!(big, BigEndian, {
+        counter: u128,
+        nonce: i128,
+    });
+
+    let counter = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128;
+    let nonce = -(10i128.pow(30));
+
+    let mut storage = data_region(1024, 0);
+    let mut view = little::View::new(&mut storage);
+    view.counter_mut().write(counter);
+    view.nonce_mut().write(nonce);
+    assert_eq!(counter.to_le_bytes(), storage[0..16]);
+    assert_eq!(nonce.to_le_bytes(), storage[16..32]);
+    assert_eq!(counter, little::View::new(&storage).counter().read());
+    assert_eq!(nonce, little::View::new(&storage).nonce().read());
+
+    let mut view = big::View::new(&mut storage);
+    view.counter_mut().write(counter);
+    view.nonce_mut().write(nonce);
+    assert_eq!(counter.to_be_bytes(), storage[0..16]);
+    assert_eq!(nonce.to_be_bytes(), storage[16..32]);
+    assert_eq!(counter, big::View::new(&storage).counter().read());
+    assert_eq!(nonce, big::View::new(&storage).nonce().read());
+}
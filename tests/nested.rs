@@ -42,6 +42,22 @@ fn metadata() {
     assert_eq!(None, whole::foot::SIZE);
 }
 
+#[test]
+fn layout_info() {
+    assert_eq!(
+        binary_layout::FieldKind::Nested(&header::LAYOUT),
+        whole::LAYOUT.fields[0].kind
+    );
+    assert_eq!(
+        binary_layout::FieldKind::Nested(&footer::LAYOUT),
+        whole::LAYOUT.field("foot").unwrap().kind
+    );
+    assert_eq!(
+        binary_layout::FieldKind::Nested(&deep_nesting::LAYOUT),
+        footer::LAYOUT.field("deep").unwrap().kind
+    );
+}
+
 // TODO Test field API once it supports nesting (see "fields" test function in other integration tests here)
 
 #[test]
@@ -336,3 +352,64 @@ fn view_vec_readwrite() {
     let extracted_storage_2 = view.into_foot().into_storage();
     assert_eq!(&&extracted_storage[30..], &extracted_storage_2.as_ref());
 }
+
+binary_layout!(record, BigEndian, {
+    id: u16,
+    flags: u8,
+});
+binary_layout!(table, LittleEndian, {
+    count: u8,
+    records: [record::NestedView],
+});
+
+#[test]
+fn open_ended_slice_metadata() {
+    assert_eq!(1, table::records::OFFSET);
+    assert_eq!(None, table::records::SIZE);
+    assert_eq!(None, table::SIZE);
+    assert_eq!(
+        binary_layout::FieldKind::Array {
+            element: &binary_layout::FieldKind::Nested(&record::LAYOUT),
+            element_size: 3,
+        },
+        table::Fields::records.kind
+    );
+}
+
+#[test]
+fn open_ended_slice_readonly() {
+    let storage = [2, 0, 1, 10, 0, 2, 20, 0xff];
+    let view = table::View::new(&storage[..]);
+    assert_eq!(2, view.records().len());
+    assert_eq!(&[0xff], view.records().remainder());
+    assert_eq!(2, view.records().get(1).unwrap().id().read());
+    assert!(view.records().get(2).is_none());
+    assert_eq!(
+        vec![(1, 10), (2, 20)],
+        view.records()
+            .iter()
+            .map(|record| (record.id().read(), record.flags().read()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn open_ended_slice_readwrite() {
+    let mut storage = [0; 7];
+    let mut view = table::View::new(&mut storage[..]);
+    view.count_mut().write(2);
+    for (index, mut record) in view.records_mut().iter_mut().enumerate() {
+        record.id_mut().write(index as u16 + 1);
+    }
+    view.records_mut().get_mut(1).unwrap().flags_mut().write(20);
+    assert_eq!([2, 0, 1, 0, 0, 2, 20], storage);
+}
+
+#[test]
+fn open_ended_slice_vec() {
+    let view = table::View::new(vec![1, 0, 5, 6]);
+    let records = view.into_records();
+    assert_eq!(1, records.len());
+    assert_eq!(5, records.get(0).unwrap().id().read());
+    assert_eq!(6, records.get(0).unwrap().flags().read());
+}
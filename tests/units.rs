@@ -0,0 +1,70 @@
+use binary_layout::prelude::*;
+
+mod common;
+use common::data_region;
+
+binary_layout!(units, BigEndian, {
+    first: u8,
+    timeout_ms: u16 unit(Milliseconds),
+    retry_delay_ms: Milliseconds as u16,
+    voltage: i32 unit(Millivolts, "mV"),
+    ratio: f32 unit(Percent, "%"),
+    tail: [u8],
+});
+
+#[test]
+fn metadata() {
+    assert_eq!(1, units::timeout_ms::OFFSET);
+    assert_eq!(Some(2), units::timeout_ms::SIZE);
+    assert_eq!(3, units::retry_delay_ms::OFFSET);
+    assert_eq!(5, units::voltage::OFFSET);
+    assert_eq!(Some(4), units::voltage::SIZE);
+    assert_eq!(9, units::ratio::OFFSET);
+    assert_eq!(13, units::tail::OFFSET);
+    assert_eq!("Milliseconds as u16", units::Fields::timeout_ms.type_name);
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(units::Milliseconds(500), 500.into());
+    assert_eq!(500, u16::from(units::Milliseconds(500)));
+    assert_eq!(units::Millivolts(-5), units::Millivolts::from(-5));
+    assert!(units::Millivolts(-5) < units::Millivolts(3));
+    assert_eq!(units::Percent(0.0), Default::default());
+}
+
+#[test]
+fn display() {
+    assert_eq!("500 Milliseconds", units::Milliseconds(500).to_string());
+    assert_eq!("-5 mV", units::Millivolts(-5).to_string());
+    assert_eq!("12.5 %", units::Percent(12.5).to_string());
+}
+
+#[test]
+fn read_write() {
+    let mut storage = data_region(1024, 0);
+    let mut view = units::View::new(&mut storage);
+    view.timeout_ms_mut().write(units::Milliseconds(500));
+    view.retry_delay_ms_mut().write(units::Milliseconds(20));
+    view.voltage_mut().write(units::Millivolts(-3300));
+    view.ratio_mut().write(units::Percent(12.5));
+
+    assert_eq!(units::Milliseconds(500), view.timeout_ms().read());
+    assert_eq!(units::Milliseconds(20), view.retry_delay_ms().read());
+    assert_eq!(units::Millivolts(-3300), view.voltage().read());
+    assert_eq!(units::Percent(12.5), view.ratio().read());
+    assert_eq!(&500u16.to_be_bytes(), &storage[1..3]);
+    assert_eq!(&(-3300i32).to_be_bytes(), &storage[5..9]);
+}
+
+#[test]
+fn combined_with_version_field() {
+    binary_layout!(versioned, LittleEndian, {
+        version: u8 in 1..=2,
+        timeout_ms: u16 unit(Milliseconds),
+    });
+
+    let view = versioned::View::try_new([2, 0xf4, 0x01]).unwrap();
+    assert_eq!(versioned::Milliseconds(500), view.timeout_ms().read());
+    assert!(versioned::View::try_new([3, 0xf4, 0x01]).is_err());
+}